@@ -0,0 +1,17 @@
+CREATE TABLE user_profiles (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    display_name VARCHAR(100),
+    date_of_birth DATE,
+    sex VARCHAR(20),
+    height_cm DOUBLE PRECISION,
+    preferred_units VARCHAR(20) NOT NULL DEFAULT 'metric',
+    timezone VARCHAR(64) NOT NULL DEFAULT 'UTC',
+    locale VARCHAR(16) NOT NULL DEFAULT 'en',
+    avatar_url VARCHAR(2048),
+    is_private BOOLEAN NOT NULL DEFAULT FALSE,
+    default_workout_visibility VARCHAR(20) NOT NULL DEFAULT 'followers',
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT INTO user_profiles (user_id) SELECT id FROM users;
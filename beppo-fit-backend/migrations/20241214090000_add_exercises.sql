@@ -0,0 +1,441 @@
+CREATE TABLE exercises (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    slug VARCHAR(120) NOT NULL UNIQUE,
+    name VARCHAR(120) NOT NULL,
+    description TEXT,
+    primary_muscles TEXT[] NOT NULL DEFAULT '{}',
+    secondary_muscles TEXT[] NOT NULL DEFAULT '{}',
+    equipment VARCHAR(40) NOT NULL,
+    category VARCHAR(40) NOT NULL,
+    mechanics VARCHAR(20),
+    instructions TEXT[] NOT NULL DEFAULT '{}',
+    media_urls TEXT[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_exercises_name ON exercises(LOWER(name));
+CREATE INDEX idx_exercises_equipment ON exercises(equipment);
+CREATE INDEX idx_exercises_category ON exercises(category);
+CREATE INDEX idx_exercises_primary_muscles ON exercises USING GIN(primary_muscles);
+CREATE INDEX idx_exercises_secondary_muscles ON exercises USING GIN(secondary_muscles);
+
+INSERT INTO exercises (slug, name, description, primary_muscles, secondary_muscles, equipment, category, mechanics, instructions) VALUES
+    ('barbell-bench-press', 'Barbell Bench Press', 'Horizontal press lying on a flat bench; the primary upper-body strength movement.', ARRAY['chest']::TEXT[], ARRAY['triceps', 'shoulders']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Lie on the bench with your eyes under the bar and feet planted.', 'Lower the weight under control to the mid chest.', 'Press back up until the arms are straight.']::TEXT[]),
+    ('dumbbell-bench-press', 'Dumbbell Bench Press', 'Horizontal press lying on a flat bench; the primary upper-body strength movement.', ARRAY['chest']::TEXT[], ARRAY['triceps', 'shoulders']::TEXT[], 'dumbbell', 'strength', 'compound', ARRAY['Lie on the bench with your eyes under the bar and feet planted.', 'Lower the weight under control to the mid chest.', 'Press back up until the arms are straight.']::TEXT[]),
+    ('smith-machine-bench-press', 'Smith Machine Bench Press', 'Horizontal press lying on a flat bench; the primary upper-body strength movement.', ARRAY['chest']::TEXT[], ARRAY['triceps', 'shoulders']::TEXT[], 'smith_machine', 'strength', 'compound', ARRAY['Lie on the bench with your eyes under the bar and feet planted.', 'Lower the weight under control to the mid chest.', 'Press back up until the arms are straight.']::TEXT[]),
+    ('barbell-incline-bench-press', 'Barbell Incline Bench Press', 'Bench press on a 30-45 degree incline, emphasising the upper chest.', ARRAY['chest']::TEXT[], ARRAY['shoulders', 'triceps']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Set the bench to a 30-45 degree incline.', 'Lower the weight to the upper chest.', 'Press up and slightly back over the shoulders.']::TEXT[]),
+    ('dumbbell-incline-bench-press', 'Dumbbell Incline Bench Press', 'Bench press on a 30-45 degree incline, emphasising the upper chest.', ARRAY['chest']::TEXT[], ARRAY['shoulders', 'triceps']::TEXT[], 'dumbbell', 'strength', 'compound', ARRAY['Set the bench to a 30-45 degree incline.', 'Lower the weight to the upper chest.', 'Press up and slightly back over the shoulders.']::TEXT[]),
+    ('smith-machine-incline-bench-press', 'Smith Machine Incline Bench Press', 'Bench press on a 30-45 degree incline, emphasising the upper chest.', ARRAY['chest']::TEXT[], ARRAY['shoulders', 'triceps']::TEXT[], 'smith_machine', 'strength', 'compound', ARRAY['Set the bench to a 30-45 degree incline.', 'Lower the weight to the upper chest.', 'Press up and slightly back over the shoulders.']::TEXT[]),
+    ('machine-incline-bench-press', 'Machine Incline Bench Press', 'Bench press on a 30-45 degree incline, emphasising the upper chest.', ARRAY['chest']::TEXT[], ARRAY['shoulders', 'triceps']::TEXT[], 'machine', 'strength', 'compound', ARRAY['Set the bench to a 30-45 degree incline.', 'Lower the weight to the upper chest.', 'Press up and slightly back over the shoulders.']::TEXT[]),
+    ('barbell-decline-bench-press', 'Barbell Decline Bench Press', 'Bench press on a decline bench, emphasising the lower chest.', ARRAY['chest']::TEXT[], ARRAY['triceps', 'shoulders']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Secure your legs and lie back on the decline bench.', 'Lower the weight to the lower chest.', 'Press back to lockout.']::TEXT[]),
+    ('dumbbell-decline-bench-press', 'Dumbbell Decline Bench Press', 'Bench press on a decline bench, emphasising the lower chest.', ARRAY['chest']::TEXT[], ARRAY['triceps', 'shoulders']::TEXT[], 'dumbbell', 'strength', 'compound', ARRAY['Secure your legs and lie back on the decline bench.', 'Lower the weight to the lower chest.', 'Press back to lockout.']::TEXT[]),
+    ('smith-machine-decline-bench-press', 'Smith Machine Decline Bench Press', 'Bench press on a decline bench, emphasising the lower chest.', ARRAY['chest']::TEXT[], ARRAY['triceps', 'shoulders']::TEXT[], 'smith_machine', 'strength', 'compound', ARRAY['Secure your legs and lie back on the decline bench.', 'Lower the weight to the lower chest.', 'Press back to lockout.']::TEXT[]),
+    ('barbell-close-grip-bench-press', 'Barbell Close-Grip Bench Press', 'Bench press with a shoulder-width grip to shift work to the triceps.', ARRAY['triceps']::TEXT[], ARRAY['chest', 'shoulders']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Grip the bar about shoulder width apart.', 'Keep the elbows tucked while lowering to the lower chest.', 'Press to lockout.']::TEXT[]),
+    ('smith-machine-close-grip-bench-press', 'Smith Machine Close-Grip Bench Press', 'Bench press with a shoulder-width grip to shift work to the triceps.', ARRAY['triceps']::TEXT[], ARRAY['chest', 'shoulders']::TEXT[], 'smith_machine', 'strength', 'compound', ARRAY['Grip the bar about shoulder width apart.', 'Keep the elbows tucked while lowering to the lower chest.', 'Press to lockout.']::TEXT[]),
+    ('ez-bar-close-grip-bench-press', 'EZ-Bar Close-Grip Bench Press', 'Bench press with a shoulder-width grip to shift work to the triceps.', ARRAY['triceps']::TEXT[], ARRAY['chest', 'shoulders']::TEXT[], 'ez_bar', 'strength', 'compound', ARRAY['Grip the bar about shoulder width apart.', 'Keep the elbows tucked while lowering to the lower chest.', 'Press to lockout.']::TEXT[]),
+    ('barbell-floor-press', 'Barbell Floor Press', 'Press performed lying on the floor, limiting the range of motion at the bottom.', ARRAY['chest', 'triceps']::TEXT[], ARRAY['shoulders']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Lie on the floor with knees bent.', 'Lower until the upper arms touch the floor.', 'Pause briefly and press up.']::TEXT[]),
+    ('dumbbell-floor-press', 'Dumbbell Floor Press', 'Press performed lying on the floor, limiting the range of motion at the bottom.', ARRAY['chest', 'triceps']::TEXT[], ARRAY['shoulders']::TEXT[], 'dumbbell', 'strength', 'compound', ARRAY['Lie on the floor with knees bent.', 'Lower until the upper arms touch the floor.', 'Pause briefly and press up.']::TEXT[]),
+    ('kettlebell-floor-press', 'Kettlebell Floor Press', 'Press performed lying on the floor, limiting the range of motion at the bottom.', ARRAY['chest', 'triceps']::TEXT[], ARRAY['shoulders']::TEXT[], 'kettlebell', 'strength', 'compound', ARRAY['Lie on the floor with knees bent.', 'Lower until the upper arms touch the floor.', 'Pause briefly and press up.']::TEXT[]),
+    ('dumbbell-chest-fly', 'Dumbbell Chest Fly', 'Horizontal adduction with nearly straight arms to isolate the chest.', ARRAY['chest']::TEXT[], ARRAY['shoulders']::TEXT[], 'dumbbell', 'strength', 'isolation', ARRAY['Start with the arms extended in front of the chest and a slight bend in the elbows.', 'Open the arms in a wide arc until you feel a stretch.', 'Bring the hands back together by squeezing the chest.']::TEXT[]),
+    ('cable-chest-fly', 'Cable Chest Fly', 'Horizontal adduction with nearly straight arms to isolate the chest.', ARRAY['chest']::TEXT[], ARRAY['shoulders']::TEXT[], 'cable', 'strength', 'isolation', ARRAY['Start with the arms extended in front of the chest and a slight bend in the elbows.', 'Open the arms in a wide arc until you feel a stretch.', 'Bring the hands back together by squeezing the chest.']::TEXT[]),
+    ('machine-chest-fly', 'Machine Chest Fly', 'Horizontal adduction with nearly straight arms to isolate the chest.', ARRAY['chest']::TEXT[], ARRAY['shoulders']::TEXT[], 'machine', 'strength', 'isolation', ARRAY['Start with the arms extended in front of the chest and a slight bend in the elbows.', 'Open the arms in a wide arc until you feel a stretch.', 'Bring the hands back together by squeezing the chest.']::TEXT[]),
+    ('band-chest-fly', 'Band Chest Fly', 'Horizontal adduction with nearly straight arms to isolate the chest.', ARRAY['chest']::TEXT[], ARRAY['shoulders']::TEXT[], 'band', 'strength', 'isolation', ARRAY['Start with the arms extended in front of the chest and a slight bend in the elbows.', 'Open the arms in a wide arc until you feel a stretch.', 'Bring the hands back together by squeezing the chest.']::TEXT[]),
+    ('dumbbell-incline-chest-fly', 'Dumbbell Incline Chest Fly', 'Fly on an incline bench targeting the upper chest.', ARRAY['chest']::TEXT[], ARRAY['shoulders']::TEXT[], 'dumbbell', 'strength', 'isolation', ARRAY['Set the bench to a 30 degree incline.', 'Lower the weights in a wide arc.', 'Squeeze them back together over the upper chest.']::TEXT[]),
+    ('cable-incline-chest-fly', 'Cable Incline Chest Fly', 'Fly on an incline bench targeting the upper chest.', ARRAY['chest']::TEXT[], ARRAY['shoulders']::TEXT[], 'cable', 'strength', 'isolation', ARRAY['Set the bench to a 30 degree incline.', 'Lower the weights in a wide arc.', 'Squeeze them back together over the upper chest.']::TEXT[]),
+    ('cable-crossover', 'Cable Crossover', 'Standing fly between two high pulleys.', ARRAY['chest']::TEXT[], ARRAY['shoulders']::TEXT[], 'cable', 'strength', 'isolation', ARRAY['Stand between the pulleys with handles set high.', 'Pull the handles down and together in front of the hips.', 'Return slowly to the stretched position.']::TEXT[]),
+    ('low-to-high-cable-fly', 'Low-to-High Cable Fly', 'Cable fly from low pulleys sweeping upward for the upper chest.', ARRAY['chest']::TEXT[], ARRAY['shoulders']::TEXT[], 'cable', 'strength', 'isolation', ARRAY['Set the pulleys low.', 'Sweep the handles up and together to chin height.', 'Lower under control.']::TEXT[]),
+    ('pec-deck', 'Pec Deck', 'Machine fly with padded arms.', ARRAY['chest']::TEXT[], ARRAY['shoulders']::TEXT[], 'machine', 'strength', 'isolation', ARRAY['Adjust the seat so the handles are at chest height.', 'Bring the arms together in front of you.', 'Return slowly until you feel a stretch.']::TEXT[]),
+    ('machine-chest-press', 'Machine Chest Press', 'Seated chest press on a plate-loaded or selectorized machine.', ARRAY['chest']::TEXT[], ARRAY['triceps', 'shoulders']::TEXT[], 'machine', 'strength', 'compound', ARRAY['Adjust the seat so the handles line up with mid chest.', 'Press the handles forward to full extension.', 'Return under control.']::TEXT[]),
+    ('push-up', 'Push-Up', 'Bodyweight horizontal press from a plank position.', ARRAY['chest']::TEXT[], ARRAY['triceps', 'shoulders', 'abdominals']::TEXT[], 'bodyweight', 'strength', 'compound', ARRAY['Place the hands slightly wider than shoulder width.', 'Keep the body in a straight line and lower the chest to the floor.', 'Push back up to full arm extension.']::TEXT[]),
+    ('incline-push-up', 'Incline Push-Up', 'Push-up with the hands elevated, reducing the load.', ARRAY['chest']::TEXT[], ARRAY['shoulders', 'triceps', 'abdominals']::TEXT[], 'bodyweight', 'strength', 'compound', ARRAY['Set up in a plank with a straight body line.', 'Lower the chest under control.', 'Push back up to the start.']::TEXT[]),
+    ('decline-push-up', 'Decline Push-Up', 'Push-up with the feet elevated, increasing the load on the upper chest.', ARRAY['chest']::TEXT[], ARRAY['shoulders', 'triceps', 'abdominals']::TEXT[], 'bodyweight', 'strength', 'compound', ARRAY['Set up in a plank with a straight body line.', 'Lower the chest under control.', 'Push back up to the start.']::TEXT[]),
+    ('diamond-push-up', 'Diamond Push-Up', 'Push-up with the hands close together to emphasise the triceps.', ARRAY['triceps']::TEXT[], ARRAY['shoulders', 'triceps', 'abdominals']::TEXT[], 'bodyweight', 'strength', 'compound', ARRAY['Set up in a plank with a straight body line.', 'Lower the chest under control.', 'Push back up to the start.']::TEXT[]),
+    ('wide-push-up', 'Wide Push-Up', 'Push-up with a wide hand position to emphasise the chest.', ARRAY['chest']::TEXT[], ARRAY['shoulders', 'triceps', 'abdominals']::TEXT[], 'bodyweight', 'strength', 'compound', ARRAY['Set up in a plank with a straight body line.', 'Lower the chest under control.', 'Push back up to the start.']::TEXT[]),
+    ('archer-push-up', 'Archer Push-Up', 'Unilateral push-up variation shifting the weight to one arm.', ARRAY['chest']::TEXT[], ARRAY['shoulders', 'triceps', 'abdominals']::TEXT[], 'bodyweight', 'strength', 'compound', ARRAY['Set up in a plank with a straight body line.', 'Lower the chest under control.', 'Push back up to the start.']::TEXT[]),
+    ('clap-push-up', 'Clap Push-Up', 'Explosive push-up with a clap at the top.', ARRAY['chest']::TEXT[], ARRAY['shoulders', 'triceps', 'abdominals']::TEXT[], 'bodyweight', 'plyometrics', 'compound', ARRAY['Set up in a plank with a straight body line.', 'Lower the chest under control.', 'Push back up to the start.']::TEXT[]),
+    ('weighted-push-up', 'Weighted Push-Up', 'Push-up performed with a weight plate or vest on the back.', ARRAY['chest']::TEXT[], ARRAY['triceps', 'shoulders']::TEXT[], 'other', 'strength', 'compound', ARRAY['Place a plate across the upper back or wear a vest.', 'Lower the chest to the floor.', 'Push back up.']::TEXT[]),
+    ('chest-dip', 'Chest Dip', 'Dip with a forward torso lean to emphasise the chest.', ARRAY['chest']::TEXT[], ARRAY['triceps', 'shoulders']::TEXT[], 'bodyweight', 'strength', 'compound', ARRAY['Support yourself on the bars with straight arms.', 'Lean forward and lower until the shoulders are below the elbows.', 'Press back up.']::TEXT[]),
+    ('chest-dip-machine', 'Chest Dip (Machine)', 'Dip with a forward torso lean to emphasise the chest.', ARRAY['chest']::TEXT[], ARRAY['triceps', 'shoulders']::TEXT[], 'machine', 'strength', 'compound', ARRAY['Support yourself on the bars with straight arms.', 'Lean forward and lower until the shoulders are below the elbows.', 'Press back up.']::TEXT[]),
+    ('weighted-dip', 'Weighted Dip', 'Dip performed with added weight from a belt or vest.', ARRAY['chest', 'triceps']::TEXT[], ARRAY['shoulders']::TEXT[], 'other', 'strength', 'compound', ARRAY['Attach the weight with a dip belt.', 'Lower until the upper arms are parallel to the floor.', 'Press back to lockout.']::TEXT[]),
+    ('dumbbell-pullover', 'Dumbbell Pullover', 'Arc from behind the head to above the chest, working chest and lats.', ARRAY['chest', 'lats']::TEXT[], ARRAY['triceps']::TEXT[], 'dumbbell', 'strength', 'isolation', ARRAY['Lie across or along a bench holding the weight above the chest.', 'Lower it behind the head with slightly bent arms.', 'Pull it back over the chest.']::TEXT[]),
+    ('barbell-pullover', 'Barbell Pullover', 'Arc from behind the head to above the chest, working chest and lats.', ARRAY['chest', 'lats']::TEXT[], ARRAY['triceps']::TEXT[], 'barbell', 'strength', 'isolation', ARRAY['Lie across or along a bench holding the weight above the chest.', 'Lower it behind the head with slightly bent arms.', 'Pull it back over the chest.']::TEXT[]),
+    ('cable-pullover', 'Cable Pullover', 'Arc from behind the head to above the chest, working chest and lats.', ARRAY['chest', 'lats']::TEXT[], ARRAY['triceps']::TEXT[], 'cable', 'strength', 'isolation', ARRAY['Lie across or along a bench holding the weight above the chest.', 'Lower it behind the head with slightly bent arms.', 'Pull it back over the chest.']::TEXT[]),
+    ('svend-press', 'Svend Press', 'Pressing two plates together in front of the chest.', ARRAY['chest']::TEXT[], ARRAY['shoulders']::TEXT[], 'other', 'strength', 'isolation', ARRAY['Squeeze two plates together at chest height.', 'Press them straight out while keeping the squeeze.', 'Bring them back to the chest.']::TEXT[]),
+    ('landmine-press', 'Landmine Press', 'Angled press with one end of a barbell anchored in a landmine.', ARRAY['shoulders', 'chest']::TEXT[], ARRAY['triceps']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Hold the free end of the bar at shoulder height.', 'Press up and forward to full extension.', 'Lower back to the shoulder.']::TEXT[]),
+    ('barbell-overhead-press', 'Barbell Overhead Press', 'Standing or seated vertical press overhead.', ARRAY['shoulders']::TEXT[], ARRAY['triceps', 'upper_back']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Start with the weight at shoulder height.', 'Brace the core and press straight overhead.', 'Lower back to the shoulders under control.']::TEXT[]),
+    ('dumbbell-overhead-press', 'Dumbbell Overhead Press', 'Standing or seated vertical press overhead.', ARRAY['shoulders']::TEXT[], ARRAY['triceps', 'upper_back']::TEXT[], 'dumbbell', 'strength', 'compound', ARRAY['Start with the weight at shoulder height.', 'Brace the core and press straight overhead.', 'Lower back to the shoulders under control.']::TEXT[]),
+    ('kettlebell-overhead-press', 'Kettlebell Overhead Press', 'Standing or seated vertical press overhead.', ARRAY['shoulders']::TEXT[], ARRAY['triceps', 'upper_back']::TEXT[], 'kettlebell', 'strength', 'compound', ARRAY['Start with the weight at shoulder height.', 'Brace the core and press straight overhead.', 'Lower back to the shoulders under control.']::TEXT[]),
+    ('smith-machine-overhead-press', 'Smith Machine Overhead Press', 'Standing or seated vertical press overhead.', ARRAY['shoulders']::TEXT[], ARRAY['triceps', 'upper_back']::TEXT[], 'smith_machine', 'strength', 'compound', ARRAY['Start with the weight at shoulder height.', 'Brace the core and press straight overhead.', 'Lower back to the shoulders under control.']::TEXT[]),
+    ('barbell-seated-overhead-press', 'Barbell Seated Overhead Press', 'Overhead press performed seated with back support.', ARRAY['shoulders']::TEXT[], ARRAY['triceps']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Sit upright with the back against the pad.', 'Press the weight overhead.', 'Lower to shoulder height.']::TEXT[]),
+    ('dumbbell-seated-overhead-press', 'Dumbbell Seated Overhead Press', 'Overhead press performed seated with back support.', ARRAY['shoulders']::TEXT[], ARRAY['triceps']::TEXT[], 'dumbbell', 'strength', 'compound', ARRAY['Sit upright with the back against the pad.', 'Press the weight overhead.', 'Lower to shoulder height.']::TEXT[]),
+    ('push-press', 'Push Press', 'Overhead press driven by a short dip and drive of the legs.', ARRAY['shoulders']::TEXT[], ARRAY['triceps', 'quadriceps', 'glutes']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Start with the bar in the front rack.', 'Dip at the knees and drive up explosively.', 'Use the momentum to press the bar overhead.']::TEXT[]),
+    ('dumbbell-push-press', 'Dumbbell Push Press', 'Push press with a pair of dumbbells.', ARRAY['shoulders']::TEXT[], ARRAY['triceps', 'quadriceps']::TEXT[], 'dumbbell', 'strength', 'compound', ARRAY['Hold the dumbbells at the shoulders.', 'Dip and drive with the legs.', 'Press the dumbbells overhead.']::TEXT[]),
+    ('arnold-press', 'Arnold Press', 'Dumbbell press with a rotation from palms facing you to palms forward.', ARRAY['shoulders']::TEXT[], ARRAY['triceps']::TEXT[], 'dumbbell', 'strength', 'compound', ARRAY['Start with the dumbbells in front of the shoulders, palms facing you.', 'Rotate the palms outward while pressing overhead.', 'Reverse the motion on the way down.']::TEXT[]),
+    ('behind-the-neck-press', 'Behind-the-Neck Press', 'Overhead press starting from behind the neck.', ARRAY['shoulders']::TEXT[], ARRAY['triceps', 'traps']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Rest the bar on the upper traps.', 'Press straight up overhead.', 'Lower behind the head with control.']::TEXT[]),
+    ('z-press', 'Z Press', 'Seated overhead press on the floor without back support.', ARRAY['shoulders']::TEXT[], ARRAY['triceps', 'abdominals']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Sit on the floor with the legs straight.', 'Press the bar overhead while staying upright.', 'Lower to the front rack.']::TEXT[]),
+    ('dumbbell-lateral-raise', 'Dumbbell Lateral Raise', 'Raising the arms out to the sides to target the side delts.', ARRAY['shoulders']::TEXT[], ARRAY['traps']::TEXT[], 'dumbbell', 'strength', 'isolation', ARRAY['Hold the weight at your sides.', 'Raise the arms out to the side to shoulder height.', 'Lower slowly.']::TEXT[]),
+    ('cable-lateral-raise', 'Cable Lateral Raise', 'Raising the arms out to the sides to target the side delts.', ARRAY['shoulders']::TEXT[], ARRAY['traps']::TEXT[], 'cable', 'strength', 'isolation', ARRAY['Hold the weight at your sides.', 'Raise the arms out to the side to shoulder height.', 'Lower slowly.']::TEXT[]),
+    ('machine-lateral-raise', 'Machine Lateral Raise', 'Raising the arms out to the sides to target the side delts.', ARRAY['shoulders']::TEXT[], ARRAY['traps']::TEXT[], 'machine', 'strength', 'isolation', ARRAY['Hold the weight at your sides.', 'Raise the arms out to the side to shoulder height.', 'Lower slowly.']::TEXT[]),
+    ('band-lateral-raise', 'Band Lateral Raise', 'Raising the arms out to the sides to target the side delts.', ARRAY['shoulders']::TEXT[], ARRAY['traps']::TEXT[], 'band', 'strength', 'isolation', ARRAY['Hold the weight at your sides.', 'Raise the arms out to the side to shoulder height.', 'Lower slowly.']::TEXT[]),
+    ('seated-lateral-raise', 'Seated Lateral Raise', 'Lateral raise performed seated to reduce momentum.', ARRAY['shoulders']::TEXT[], '{}'::TEXT[], 'dumbbell', 'strength', 'isolation', ARRAY['Sit on the end of a bench.', 'Raise the dumbbells to shoulder height.', 'Lower slowly.']::TEXT[]),
+    ('lean-away-lateral-raise', 'Lean-Away Lateral Raise', 'Single-arm cable lateral raise while leaning away from the stack.', ARRAY['shoulders']::TEXT[], '{}'::TEXT[], 'cable', 'strength', 'isolation', ARRAY['Hold a fixed support and lean away from the cable.', 'Raise the handle out to the side.', 'Lower under control.']::TEXT[]),
+    ('dumbbell-front-raise', 'Dumbbell Front Raise', 'Raising the weight in front of the body to target the front delts.', ARRAY['shoulders']::TEXT[], ARRAY['chest']::TEXT[], 'dumbbell', 'strength', 'isolation', ARRAY['Hold the weight in front of the thighs.', 'Raise it to shoulder height with straight arms.', 'Lower under control.']::TEXT[]),
+    ('barbell-front-raise', 'Barbell Front Raise', 'Raising the weight in front of the body to target the front delts.', ARRAY['shoulders']::TEXT[], ARRAY['chest']::TEXT[], 'barbell', 'strength', 'isolation', ARRAY['Hold the weight in front of the thighs.', 'Raise it to shoulder height with straight arms.', 'Lower under control.']::TEXT[]),
+    ('cable-front-raise', 'Cable Front Raise', 'Raising the weight in front of the body to target the front delts.', ARRAY['shoulders']::TEXT[], ARRAY['chest']::TEXT[], 'cable', 'strength', 'isolation', ARRAY['Hold the weight in front of the thighs.', 'Raise it to shoulder height with straight arms.', 'Lower under control.']::TEXT[]),
+    ('band-front-raise', 'Band Front Raise', 'Raising the weight in front of the body to target the front delts.', ARRAY['shoulders']::TEXT[], ARRAY['chest']::TEXT[], 'band', 'strength', 'isolation', ARRAY['Hold the weight in front of the thighs.', 'Raise it to shoulder height with straight arms.', 'Lower under control.']::TEXT[]),
+    ('plate-front-raise', 'Plate Front Raise', 'Front raise holding a weight plate.', ARRAY['shoulders']::TEXT[], ARRAY['chest']::TEXT[], 'other', 'strength', 'isolation', ARRAY['Hold a plate at the sides.', 'Raise it to eye level.', 'Lower slowly.']::TEXT[]),
+    ('dumbbell-rear-delt-fly', 'Dumbbell Rear Delt Fly', 'Reverse fly targeting the rear delts.', ARRAY['shoulders']::TEXT[], ARRAY['upper_back']::TEXT[], 'dumbbell', 'strength', 'isolation', ARRAY['Hinge forward or face the machine.', 'Open the arms out to the side.', 'Return slowly.']::TEXT[]),
+    ('cable-rear-delt-fly', 'Cable Rear Delt Fly', 'Reverse fly targeting the rear delts.', ARRAY['shoulders']::TEXT[], ARRAY['upper_back']::TEXT[], 'cable', 'strength', 'isolation', ARRAY['Hinge forward or face the machine.', 'Open the arms out to the side.', 'Return slowly.']::TEXT[]),
+    ('machine-rear-delt-fly', 'Machine Rear Delt Fly', 'Reverse fly targeting the rear delts.', ARRAY['shoulders']::TEXT[], ARRAY['upper_back']::TEXT[], 'machine', 'strength', 'isolation', ARRAY['Hinge forward or face the machine.', 'Open the arms out to the side.', 'Return slowly.']::TEXT[]),
+    ('band-rear-delt-fly', 'Band Rear Delt Fly', 'Reverse fly targeting the rear delts.', ARRAY['shoulders']::TEXT[], ARRAY['upper_back']::TEXT[], 'band', 'strength', 'isolation', ARRAY['Hinge forward or face the machine.', 'Open the arms out to the side.', 'Return slowly.']::TEXT[]),
+    ('cable-face-pull', 'Cable Face Pull', 'Pulling a rope towards the face with external rotation.', ARRAY['shoulders']::TEXT[], ARRAY['upper_back', 'traps']::TEXT[], 'cable', 'strength', 'isolation', ARRAY['Set the rope at upper chest height.', 'Pull the rope towards the face, separating the hands.', 'Finish with the elbows high and hands beside the ears.']::TEXT[]),
+    ('band-face-pull', 'Band Face Pull', 'Pulling a rope towards the face with external rotation.', ARRAY['shoulders']::TEXT[], ARRAY['upper_back', 'traps']::TEXT[], 'band', 'strength', 'isolation', ARRAY['Set the rope at upper chest height.', 'Pull the rope towards the face, separating the hands.', 'Finish with the elbows high and hands beside the ears.']::TEXT[]),
+    ('barbell-upright-row', 'Barbell Upright Row', 'Pulling the weight vertically along the body to chest height.', ARRAY['shoulders']::TEXT[], ARRAY['traps', 'biceps']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Hold the weight in front of the thighs.', 'Pull it up along the body leading with the elbows.', 'Lower under control.']::TEXT[]),
+    ('dumbbell-upright-row', 'Dumbbell Upright Row', 'Pulling the weight vertically along the body to chest height.', ARRAY['shoulders']::TEXT[], ARRAY['traps', 'biceps']::TEXT[], 'dumbbell', 'strength', 'compound', ARRAY['Hold the weight in front of the thighs.', 'Pull it up along the body leading with the elbows.', 'Lower under control.']::TEXT[]),
+    ('cable-upright-row', 'Cable Upright Row', 'Pulling the weight vertically along the body to chest height.', ARRAY['shoulders']::TEXT[], ARRAY['traps', 'biceps']::TEXT[], 'cable', 'strength', 'compound', ARRAY['Hold the weight in front of the thighs.', 'Pull it up along the body leading with the elbows.', 'Lower under control.']::TEXT[]),
+    ('ez-bar-upright-row', 'EZ-Bar Upright Row', 'Pulling the weight vertically along the body to chest height.', ARRAY['shoulders']::TEXT[], ARRAY['traps', 'biceps']::TEXT[], 'ez_bar', 'strength', 'compound', ARRAY['Hold the weight in front of the thighs.', 'Pull it up along the body leading with the elbows.', 'Lower under control.']::TEXT[]),
+    ('machine-shoulder-press', 'Machine Shoulder Press', 'Seated shoulder press machine.', ARRAY['shoulders']::TEXT[], ARRAY['triceps']::TEXT[], 'machine', 'strength', 'compound', ARRAY['Adjust the seat so the handles are at shoulder height.', 'Press overhead.', 'Lower under control.']::TEXT[]),
+    ('handstand-push-up', 'Handstand Push-Up', 'Vertical push-up in a handstand, usually against a wall.', ARRAY['shoulders']::TEXT[], ARRAY['triceps', 'traps']::TEXT[], 'bodyweight', 'strength', 'compound', ARRAY['Kick up into a handstand against the wall.', 'Lower the head to the floor.', 'Press back to straight arms.']::TEXT[]),
+    ('pike-push-up', 'Pike Push-Up', 'Push-up with the hips piked high to shift work to the shoulders.', ARRAY['shoulders']::TEXT[], ARRAY['triceps']::TEXT[], 'bodyweight', 'strength', 'compound', ARRAY['Start in a pike position with hips high.', 'Lower the head towards the floor.', 'Press back up.']::TEXT[]),
+    ('cable-external-rotation', 'Cable External Rotation', 'Rotator cuff external rotation.', ARRAY['shoulders']::TEXT[], '{}'::TEXT[], 'cable', 'strength', 'isolation', ARRAY['Keep the elbow bent 90 degrees and close to the body.', 'Rotate the forearm outward.', 'Return slowly.']::TEXT[]),
+    ('dumbbell-external-rotation', 'Dumbbell External Rotation', 'Rotator cuff external rotation.', ARRAY['shoulders']::TEXT[], '{}'::TEXT[], 'dumbbell', 'strength', 'isolation', ARRAY['Keep the elbow bent 90 degrees and close to the body.', 'Rotate the forearm outward.', 'Return slowly.']::TEXT[]),
+    ('band-external-rotation', 'Band External Rotation', 'Rotator cuff external rotation.', ARRAY['shoulders']::TEXT[], '{}'::TEXT[], 'band', 'strength', 'isolation', ARRAY['Keep the elbow bent 90 degrees and close to the body.', 'Rotate the forearm outward.', 'Return slowly.']::TEXT[]),
+    ('cuban-press', 'Cuban Press', 'Upright row into external rotation and press for shoulder health.', ARRAY['shoulders']::TEXT[], ARRAY['upper_back']::TEXT[], 'dumbbell', 'strength', 'compound', ARRAY['Perform an upright row to shoulder height.', 'Rotate the forearms up.', 'Press overhead and reverse the sequence.']::TEXT[]),
+    ('barbell-deadlift', 'Barbell Deadlift', 'Lifting the weight from the floor to standing with a hip hinge.', ARRAY['hamstrings', 'glutes', 'lower_back']::TEXT[], ARRAY['quadriceps', 'traps', 'forearms', 'lats']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Stand with the mid foot under the bar.', 'Grip the bar, brace and pull the slack out.', 'Push through the floor and stand up tall.', 'Lower the weight back to the floor under control.']::TEXT[]),
+    ('trap-bar-deadlift', 'Trap Bar Deadlift', 'Lifting the weight from the floor to standing with a hip hinge.', ARRAY['hamstrings', 'glutes', 'lower_back']::TEXT[], ARRAY['quadriceps', 'traps', 'forearms', 'lats']::TEXT[], 'trap_bar', 'strength', 'compound', ARRAY['Stand with the mid foot under the bar.', 'Grip the bar, brace and pull the slack out.', 'Push through the floor and stand up tall.', 'Lower the weight back to the floor under control.']::TEXT[]),
+    ('dumbbell-deadlift', 'Dumbbell Deadlift', 'Lifting the weight from the floor to standing with a hip hinge.', ARRAY['hamstrings', 'glutes', 'lower_back']::TEXT[], ARRAY['quadriceps', 'traps', 'forearms', 'lats']::TEXT[], 'dumbbell', 'strength', 'compound', ARRAY['Stand with the mid foot under the bar.', 'Grip the bar, brace and pull the slack out.', 'Push through the floor and stand up tall.', 'Lower the weight back to the floor under control.']::TEXT[]),
+    ('kettlebell-deadlift', 'Kettlebell Deadlift', 'Lifting the weight from the floor to standing with a hip hinge.', ARRAY['hamstrings', 'glutes', 'lower_back']::TEXT[], ARRAY['quadriceps', 'traps', 'forearms', 'lats']::TEXT[], 'kettlebell', 'strength', 'compound', ARRAY['Stand with the mid foot under the bar.', 'Grip the bar, brace and pull the slack out.', 'Push through the floor and stand up tall.', 'Lower the weight back to the floor under control.']::TEXT[]),
+    ('sumo-deadlift', 'Sumo Deadlift', 'Deadlift with a wide stance and hands inside the knees.', ARRAY['glutes', 'quadriceps', 'adductors']::TEXT[], ARRAY['hamstrings', 'lower_back', 'traps']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Take a wide stance with toes pointed out.', 'Grip the bar inside the knees.', 'Drive the knees out and stand up.']::TEXT[]),
+    ('deficit-deadlift', 'Deficit Deadlift', 'Deadlift standing on a platform to increase range of motion.', ARRAY['hamstrings', 'glutes', 'lower_back']::TEXT[], ARRAY['quadriceps', 'traps']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Stand on a 2-5 cm platform.', 'Set up as for a conventional deadlift.', 'Pull to lockout.']::TEXT[]),
+    ('block-pull', 'Block Pull', 'Partial deadlift from blocks, overloading the lockout.', ARRAY['lower_back', 'glutes']::TEXT[], ARRAY['hamstrings', 'traps']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Set the bar on blocks at knee height.', 'Brace and pull to lockout.', 'Lower back to the blocks.']::TEXT[]),
+    ('rack-pull', 'Rack Pull', 'Partial deadlift from the safety pins of a rack.', ARRAY['lower_back', 'traps']::TEXT[], ARRAY['glutes', 'hamstrings']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Set the pins at or below the knee.', 'Pull the bar to lockout.', 'Lower to the pins.']::TEXT[]),
+    ('paused-deadlift', 'Paused Deadlift', 'Deadlift with a pause just after the bar leaves the floor.', ARRAY['hamstrings', 'glutes', 'lower_back']::TEXT[], ARRAY['quadriceps', 'traps']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Pull the bar just off the floor.', 'Hold for 1-2 seconds.', 'Finish the pull.']::TEXT[]),
+    ('snatch-grip-deadlift', 'Snatch-Grip Deadlift', 'Deadlift with a wide snatch grip.', ARRAY['hamstrings', 'glutes', 'upper_back']::TEXT[], ARRAY['lower_back', 'traps']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Take a wide grip on the bar.', 'Set the hips lower than normal.', 'Pull to lockout.']::TEXT[]),
+    ('barbell-romanian-deadlift', 'Barbell Romanian Deadlift', 'Hip hinge from the top with soft knees, emphasising the hamstrings.', ARRAY['hamstrings', 'glutes']::TEXT[], ARRAY['lower_back']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Start standing with the weight at the hips.', 'Push the hips back while keeping the back flat.', 'Lower until you feel a strong hamstring stretch and return.']::TEXT[]),
+    ('dumbbell-romanian-deadlift', 'Dumbbell Romanian Deadlift', 'Hip hinge from the top with soft knees, emphasising the hamstrings.', ARRAY['hamstrings', 'glutes']::TEXT[], ARRAY['lower_back']::TEXT[], 'dumbbell', 'strength', 'compound', ARRAY['Start standing with the weight at the hips.', 'Push the hips back while keeping the back flat.', 'Lower until you feel a strong hamstring stretch and return.']::TEXT[]),
+    ('kettlebell-romanian-deadlift', 'Kettlebell Romanian Deadlift', 'Hip hinge from the top with soft knees, emphasising the hamstrings.', ARRAY['hamstrings', 'glutes']::TEXT[], ARRAY['lower_back']::TEXT[], 'kettlebell', 'strength', 'compound', ARRAY['Start standing with the weight at the hips.', 'Push the hips back while keeping the back flat.', 'Lower until you feel a strong hamstring stretch and return.']::TEXT[]),
+    ('smith-machine-romanian-deadlift', 'Smith Machine Romanian Deadlift', 'Hip hinge from the top with soft knees, emphasising the hamstrings.', ARRAY['hamstrings', 'glutes']::TEXT[], ARRAY['lower_back']::TEXT[], 'smith_machine', 'strength', 'compound', ARRAY['Start standing with the weight at the hips.', 'Push the hips back while keeping the back flat.', 'Lower until you feel a strong hamstring stretch and return.']::TEXT[]),
+    ('barbell-stiff-leg-deadlift', 'Barbell Stiff-Leg Deadlift', 'Deadlift with minimal knee bend.', ARRAY['hamstrings']::TEXT[], ARRAY['glutes', 'lower_back']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Keep the knees nearly straight.', 'Hinge at the hips to lower the weight.', 'Return to standing.']::TEXT[]),
+    ('dumbbell-stiff-leg-deadlift', 'Dumbbell Stiff-Leg Deadlift', 'Deadlift with minimal knee bend.', ARRAY['hamstrings']::TEXT[], ARRAY['glutes', 'lower_back']::TEXT[], 'dumbbell', 'strength', 'compound', ARRAY['Keep the knees nearly straight.', 'Hinge at the hips to lower the weight.', 'Return to standing.']::TEXT[]),
+    ('dumbbell-single-leg-romanian-deadlift', 'Dumbbell Single-Leg Romanian Deadlift', 'Unilateral Romanian deadlift for balance and hamstring strength.', ARRAY['hamstrings', 'glutes']::TEXT[], ARRAY['lower_back']::TEXT[], 'dumbbell', 'strength', 'compound', ARRAY['Stand on one leg holding the weight.', 'Hinge forward while the free leg extends back.', 'Return to standing.']::TEXT[]),
+    ('kettlebell-single-leg-romanian-deadlift', 'Kettlebell Single-Leg Romanian Deadlift', 'Unilateral Romanian deadlift for balance and hamstring strength.', ARRAY['hamstrings', 'glutes']::TEXT[], ARRAY['lower_back']::TEXT[], 'kettlebell', 'strength', 'compound', ARRAY['Stand on one leg holding the weight.', 'Hinge forward while the free leg extends back.', 'Return to standing.']::TEXT[]),
+    ('good-morning', 'Good Morning', 'Hip hinge with the bar on the back.', ARRAY['hamstrings', 'lower_back']::TEXT[], ARRAY['glutes']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Place the bar on the upper back.', 'Push the hips back and lower the torso.', 'Return to upright.']::TEXT[]),
+    ('barbell-bent-over-row', 'Barbell Bent-Over Row', 'Row from a hip-hinged position.', ARRAY['upper_back', 'lats']::TEXT[], ARRAY['biceps', 'shoulders', 'lower_back']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Hinge to roughly 45 degrees with a flat back.', 'Row the weight to the lower chest.', 'Lower under control.']::TEXT[]),
+    ('dumbbell-bent-over-row', 'Dumbbell Bent-Over Row', 'Row from a hip-hinged position.', ARRAY['upper_back', 'lats']::TEXT[], ARRAY['biceps', 'shoulders', 'lower_back']::TEXT[], 'dumbbell', 'strength', 'compound', ARRAY['Hinge to roughly 45 degrees with a flat back.', 'Row the weight to the lower chest.', 'Lower under control.']::TEXT[]),
+    ('pendlay-row', 'Pendlay Row', 'Strict row from the floor with a horizontal torso.', ARRAY['upper_back', 'lats']::TEXT[], ARRAY['biceps', 'lower_back']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Set up with the torso parallel to the floor.', 'Row the bar explosively to the chest.', 'Return it to the floor each rep.']::TEXT[]),
+    ('yates-row', 'Yates Row', 'Underhand bent-over row with a more upright torso.', ARRAY['lats', 'upper_back']::TEXT[], ARRAY['biceps']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Grip the bar underhand.', 'Hinge to about 30 degrees.', 'Row to the belly button.']::TEXT[]),
+    ('dumbbell-one-arm-row', 'Dumbbell One-Arm Row', 'Single-arm row supported on a bench.', ARRAY['lats', 'upper_back']::TEXT[], ARRAY['biceps', 'shoulders']::TEXT[], 'dumbbell', 'strength', 'compound', ARRAY['Support yourself with one hand and knee on a bench.', 'Row the weight to the hip.', 'Lower fully.']::TEXT[]),
+    ('kettlebell-one-arm-row', 'Kettlebell One-Arm Row', 'Single-arm row supported on a bench.', ARRAY['lats', 'upper_back']::TEXT[], ARRAY['biceps', 'shoulders']::TEXT[], 'kettlebell', 'strength', 'compound', ARRAY['Support yourself with one hand and knee on a bench.', 'Row the weight to the hip.', 'Lower fully.']::TEXT[]),
+    ('cable-one-arm-row', 'Cable One-Arm Row', 'Single-arm row supported on a bench.', ARRAY['lats', 'upper_back']::TEXT[], ARRAY['biceps', 'shoulders']::TEXT[], 'cable', 'strength', 'compound', ARRAY['Support yourself with one hand and knee on a bench.', 'Row the weight to the hip.', 'Lower fully.']::TEXT[]),
+    ('meadows-row', 'Meadows Row', 'Single-arm landmine row with an overhand grip.', ARRAY['lats', 'upper_back']::TEXT[], ARRAY['biceps']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Stand perpendicular to the landmine.', 'Grip the end of the bar and row to the hip.', 'Lower under control.']::TEXT[]),
+    ('seal-row', 'Seal Row', 'Row lying face down on a raised bench.', ARRAY['upper_back', 'lats']::TEXT[], ARRAY['biceps']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Lie prone on a high bench.', 'Row the bar to the bench.', 'Lower to full extension.']::TEXT[]),
+    ('dumbbell-chest-supported-row', 'Dumbbell Chest-Supported Row', 'Row lying face down on an incline bench.', ARRAY['upper_back', 'lats']::TEXT[], ARRAY['biceps']::TEXT[], 'dumbbell', 'strength', 'compound', ARRAY['Lie on an incline bench.', 'Row the weights to the sides.', 'Lower fully.']::TEXT[]),
+    ('machine-chest-supported-row', 'Machine Chest-Supported Row', 'Row lying face down on an incline bench.', ARRAY['upper_back', 'lats']::TEXT[], ARRAY['biceps']::TEXT[], 'machine', 'strength', 'compound', ARRAY['Lie on an incline bench.', 'Row the weights to the sides.', 'Lower fully.']::TEXT[]),
+    ('t-bar-row', 'T-Bar Row', 'Row with a landmine and V handle.', ARRAY['upper_back', 'lats']::TEXT[], ARRAY['biceps', 'lower_back']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Straddle the bar and hinge forward.', 'Row the handle to the chest.', 'Lower under control.']::TEXT[]),
+    ('seated-cable-row', 'Seated Cable Row', 'Horizontal row on a cable station.', ARRAY['upper_back', 'lats']::TEXT[], ARRAY['biceps']::TEXT[], 'cable', 'strength', 'compound', ARRAY['Sit upright with a slight knee bend.', 'Pull the handle to the abdomen.', 'Extend the arms forward with control.']::TEXT[]),
+    ('wide-grip-seated-cable-row', 'Wide-Grip Seated Cable Row', 'Seated cable row with a wide bar, emphasising the upper back.', ARRAY['upper_back']::TEXT[], ARRAY['lats', 'shoulders', 'biceps']::TEXT[], 'cable', 'strength', 'compound', ARRAY['Grip a wide bar overhand.', 'Pull to the lower chest with elbows out.', 'Return slowly.']::TEXT[]),
+    ('machine-row', 'Machine Row', 'Seated row machine.', ARRAY['upper_back', 'lats']::TEXT[], ARRAY['biceps']::TEXT[], 'machine', 'strength', 'compound', ARRAY['Adjust the chest pad.', 'Pull the handles back.', 'Return under control.']::TEXT[]),
+    ('inverted-row', 'Inverted Row', 'Bodyweight row under a bar or rings.', ARRAY['upper_back', 'lats']::TEXT[], ARRAY['biceps']::TEXT[], 'bodyweight', 'strength', 'compound', ARRAY['Hang under the bar with a straight body.', 'Pull the chest to the bar.', 'Lower to full extension.']::TEXT[]),
+    ('band-row', 'Band Row', 'Row with a resistance band.', ARRAY['upper_back', 'lats']::TEXT[], ARRAY['biceps']::TEXT[], 'band', 'strength', 'compound', ARRAY['Anchor the band in front of you.', 'Row the handles to the torso.', 'Return slowly.']::TEXT[]),
+    ('pull-up', 'Pull-Up', 'Vertical pull from a dead hang with an overhand grip.', ARRAY['lats']::TEXT[], ARRAY['biceps', 'upper_back', 'forearms']::TEXT[], 'bodyweight', 'strength', 'compound', ARRAY['Hang from the bar with an overhand grip.', 'Pull until the chin is over the bar.', 'Lower to a full hang.']::TEXT[]),
+    ('chin-up', 'Chin-Up', 'Vertical pull with an underhand grip.', ARRAY['lats', 'biceps']::TEXT[], ARRAY['upper_back', 'forearms']::TEXT[], 'bodyweight', 'strength', 'compound', ARRAY['Hang with an underhand shoulder-width grip.', 'Pull the chin over the bar.', 'Lower to a full hang.']::TEXT[]),
+    ('neutral-grip-pull-up', 'Neutral-Grip Pull-Up', 'Pull-up with palms facing each other.', ARRAY['lats']::TEXT[], ARRAY['biceps', 'upper_back']::TEXT[], 'bodyweight', 'strength', 'compound', ARRAY['Grip parallel handles.', 'Pull the chest towards the handles.', 'Lower fully.']::TEXT[]),
+    ('wide-grip-pull-up', 'Wide-Grip Pull-Up', 'Pull-up with a grip wider than shoulders.', ARRAY['lats']::TEXT[], ARRAY['upper_back', 'biceps']::TEXT[], 'bodyweight', 'strength', 'compound', ARRAY['Take a wide overhand grip.', 'Pull the chest to the bar.', 'Lower fully.']::TEXT[]),
+    ('weighted-pull-up', 'Weighted Pull-Up', 'Pull-up with added weight.', ARRAY['lats']::TEXT[], ARRAY['biceps', 'upper_back']::TEXT[], 'other', 'strength', 'compound', ARRAY['Attach weight to a belt.', 'Perform a strict pull-up.', 'Lower to a dead hang.']::TEXT[]),
+    ('weighted-chin-up', 'Weighted Chin-Up', 'Chin-up with added weight.', ARRAY['lats', 'biceps']::TEXT[], ARRAY['upper_back']::TEXT[], 'other', 'strength', 'compound', ARRAY['Attach weight to a belt.', 'Perform a strict chin-up.', 'Lower to a dead hang.']::TEXT[]),
+    ('assisted-pull-up', 'Assisted Pull-Up', 'Pull-up on an assistance machine.', ARRAY['lats']::TEXT[], ARRAY['biceps', 'upper_back']::TEXT[], 'machine', 'strength', 'compound', ARRAY['Kneel on the platform and grip the handles.', 'Pull up until the chin clears the handles.', 'Lower slowly.']::TEXT[]),
+    ('band-assisted-pull-up', 'Band-Assisted Pull-Up', 'Pull-up with a band supporting part of the bodyweight.', ARRAY['lats']::TEXT[], ARRAY['biceps', 'upper_back']::TEXT[], 'band', 'strength', 'compound', ARRAY['Loop a band over the bar and place a knee or foot in it.', 'Perform a pull-up.', 'Lower slowly.']::TEXT[]),
+    ('muscle-up', 'Muscle-Up', 'Pull-up transitioning into a dip above the bar or rings.', ARRAY['lats', 'chest', 'triceps']::TEXT[], ARRAY['biceps', 'shoulders']::TEXT[], 'bodyweight', 'strength', 'compound', ARRAY['Pull explosively from a hang.', 'Transition the chest over the bar.', 'Press to lockout above the bar.']::TEXT[]),
+    ('lat-pulldown', 'Lat Pulldown', 'Pulling a wide bar down to the upper chest.', ARRAY['lats']::TEXT[], ARRAY['biceps', 'upper_back']::TEXT[], 'cable', 'strength', 'compound', ARRAY['Grip the bar wider than the shoulders.', 'Pull it to the upper chest while leaning back slightly.', 'Return with control.']::TEXT[]),
+    ('close-grip-lat-pulldown', 'Close-Grip Lat Pulldown', 'Pulldown with a close neutral handle.', ARRAY['lats']::TEXT[], ARRAY['biceps', 'upper_back']::TEXT[], 'cable', 'strength', 'compound', ARRAY['Grip the V handle.', 'Pull it to the upper chest.', 'Return slowly.']::TEXT[]),
+    ('reverse-grip-lat-pulldown', 'Reverse-Grip Lat Pulldown', 'Pulldown with an underhand grip.', ARRAY['lats']::TEXT[], ARRAY['biceps']::TEXT[], 'cable', 'strength', 'compound', ARRAY['Grip the bar underhand.', 'Pull to the upper chest.', 'Return slowly.']::TEXT[]),
+    ('single-arm-lat-pulldown', 'Single-Arm Lat Pulldown', 'Unilateral pulldown with a D handle.', ARRAY['lats']::TEXT[], ARRAY['biceps']::TEXT[], 'cable', 'strength', 'compound', ARRAY['Grip the handle with one hand.', 'Pull the elbow down to the side.', 'Return slowly.']::TEXT[]),
+    ('machine-lat-pulldown', 'Machine Lat Pulldown', 'Plate-loaded or selectorized pulldown machine.', ARRAY['lats']::TEXT[], ARRAY['biceps', 'upper_back']::TEXT[], 'machine', 'strength', 'compound', ARRAY['Secure the thighs under the pads.', 'Pull the handles down.', 'Return under control.']::TEXT[]),
+    ('straight-arm-pulldown', 'Straight-Arm Pulldown', 'Pulling a bar down with straight arms to isolate the lats.', ARRAY['lats']::TEXT[], ARRAY['triceps']::TEXT[], 'cable', 'strength', 'isolation', ARRAY['Stand facing a high pulley.', 'Keep the arms straight and pull the bar to the thighs.', 'Return slowly.']::TEXT[]),
+    ('barbell-shrug', 'Barbell Shrug', 'Elevating the shoulders to train the upper traps.', ARRAY['traps']::TEXT[], ARRAY['forearms']::TEXT[], 'barbell', 'strength', 'isolation', ARRAY['Hold the weight at arm length.', 'Shrug the shoulders up towards the ears.', 'Lower slowly.']::TEXT[]),
+    ('dumbbell-shrug', 'Dumbbell Shrug', 'Elevating the shoulders to train the upper traps.', ARRAY['traps']::TEXT[], ARRAY['forearms']::TEXT[], 'dumbbell', 'strength', 'isolation', ARRAY['Hold the weight at arm length.', 'Shrug the shoulders up towards the ears.', 'Lower slowly.']::TEXT[]),
+    ('trap-bar-shrug', 'Trap Bar Shrug', 'Elevating the shoulders to train the upper traps.', ARRAY['traps']::TEXT[], ARRAY['forearms']::TEXT[], 'trap_bar', 'strength', 'isolation', ARRAY['Hold the weight at arm length.', 'Shrug the shoulders up towards the ears.', 'Lower slowly.']::TEXT[]),
+    ('smith-machine-shrug', 'Smith Machine Shrug', 'Elevating the shoulders to train the upper traps.', ARRAY['traps']::TEXT[], ARRAY['forearms']::TEXT[], 'smith_machine', 'strength', 'isolation', ARRAY['Hold the weight at arm length.', 'Shrug the shoulders up towards the ears.', 'Lower slowly.']::TEXT[]),
+    ('machine-shrug', 'Machine Shrug', 'Elevating the shoulders to train the upper traps.', ARRAY['traps']::TEXT[], ARRAY['forearms']::TEXT[], 'machine', 'strength', 'isolation', ARRAY['Hold the weight at arm length.', 'Shrug the shoulders up towards the ears.', 'Lower slowly.']::TEXT[]),
+    ('back-extension', 'Back Extension', 'Hip extension on a 45 degree or horizontal bench.', ARRAY['lower_back']::TEXT[], ARRAY['glutes', 'hamstrings']::TEXT[], 'bodyweight', 'strength', 'isolation', ARRAY['Set the pad just below the hips.', 'Lower the torso.', 'Raise back to a straight line.']::TEXT[]),
+    ('weighted-back-extension', 'Weighted Back Extension', 'Back extension holding a plate.', ARRAY['lower_back']::TEXT[], ARRAY['glutes', 'hamstrings']::TEXT[], 'other', 'strength', 'isolation', ARRAY['Hold a plate against the chest.', 'Lower the torso.', 'Raise to a straight line.']::TEXT[]),
+    ('reverse-hyperextension', 'Reverse Hyperextension', 'Raising the legs behind you while lying prone.', ARRAY['glutes', 'lower_back']::TEXT[], ARRAY['hamstrings']::TEXT[], 'machine', 'strength', 'isolation', ARRAY['Lie on the bench with the legs hanging.', 'Raise the legs to hip height.', 'Lower under control.']::TEXT[]),
+    ('superman', 'Superman', 'Prone hold raising arms and legs off the floor.', ARRAY['lower_back']::TEXT[], ARRAY['glutes']::TEXT[], 'bodyweight', 'strength', 'isolation', ARRAY['Lie face down.', 'Raise the arms and legs together.', 'Hold briefly and lower.']::TEXT[]),
+    ('kettlebell-swing', 'Kettlebell Swing', 'Explosive hip hinge swinging a kettlebell to chest height.', ARRAY['glutes', 'hamstrings']::TEXT[], ARRAY['lower_back', 'shoulders', 'abdominals']::TEXT[], 'kettlebell', 'strength', 'compound', ARRAY['Hike the bell back between the legs.', 'Snap the hips forward to swing it up.', 'Let it fall back into the next rep.']::TEXT[]),
+    ('single-arm-kettlebell-swing', 'Single-Arm Kettlebell Swing', 'Kettlebell swing with one hand.', ARRAY['glutes', 'hamstrings']::TEXT[], ARRAY['lower_back', 'shoulders', 'obliques']::TEXT[], 'kettlebell', 'strength', 'compound', ARRAY['Hold the bell in one hand.', 'Hike and snap the hips.', 'Control the backswing.']::TEXT[]),
+    ('renegade-row', 'Renegade Row', 'Alternating row from a plank on dumbbells.', ARRAY['upper_back', 'abdominals']::TEXT[], ARRAY['lats', 'biceps', 'chest']::TEXT[], 'dumbbell', 'strength', 'compound', ARRAY['Hold a plank on two dumbbells.', 'Row one dumbbell while bracing.', 'Alternate sides.']::TEXT[]),
+    ('barbell-biceps-curl', 'Barbell Biceps Curl', 'Elbow flexion to train the biceps.', ARRAY['biceps']::TEXT[], ARRAY['forearms']::TEXT[], 'barbell', 'strength', 'isolation', ARRAY['Hold the weight with the arms extended.', 'Curl it up without moving the elbows.', 'Lower under control.']::TEXT[]),
+    ('dumbbell-biceps-curl', 'Dumbbell Biceps Curl', 'Elbow flexion to train the biceps.', ARRAY['biceps']::TEXT[], ARRAY['forearms']::TEXT[], 'dumbbell', 'strength', 'isolation', ARRAY['Hold the weight with the arms extended.', 'Curl it up without moving the elbows.', 'Lower under control.']::TEXT[]),
+    ('cable-biceps-curl', 'Cable Biceps Curl', 'Elbow flexion to train the biceps.', ARRAY['biceps']::TEXT[], ARRAY['forearms']::TEXT[], 'cable', 'strength', 'isolation', ARRAY['Hold the weight with the arms extended.', 'Curl it up without moving the elbows.', 'Lower under control.']::TEXT[]),
+    ('ez-bar-biceps-curl', 'EZ-Bar Biceps Curl', 'Elbow flexion to train the biceps.', ARRAY['biceps']::TEXT[], ARRAY['forearms']::TEXT[], 'ez_bar', 'strength', 'isolation', ARRAY['Hold the weight with the arms extended.', 'Curl it up without moving the elbows.', 'Lower under control.']::TEXT[]),
+    ('machine-biceps-curl', 'Machine Biceps Curl', 'Elbow flexion to train the biceps.', ARRAY['biceps']::TEXT[], ARRAY['forearms']::TEXT[], 'machine', 'strength', 'isolation', ARRAY['Hold the weight with the arms extended.', 'Curl it up without moving the elbows.', 'Lower under control.']::TEXT[]),
+    ('band-biceps-curl', 'Band Biceps Curl', 'Elbow flexion to train the biceps.', ARRAY['biceps']::TEXT[], ARRAY['forearms']::TEXT[], 'band', 'strength', 'isolation', ARRAY['Hold the weight with the arms extended.', 'Curl it up without moving the elbows.', 'Lower under control.']::TEXT[]),
+    ('kettlebell-biceps-curl', 'Kettlebell Biceps Curl', 'Elbow flexion to train the biceps.', ARRAY['biceps']::TEXT[], ARRAY['forearms']::TEXT[], 'kettlebell', 'strength', 'isolation', ARRAY['Hold the weight with the arms extended.', 'Curl it up without moving the elbows.', 'Lower under control.']::TEXT[]),
+    ('dumbbell-hammer-curl', 'Dumbbell Hammer Curl', 'Curl with a neutral grip, working the brachialis and forearms.', ARRAY['biceps', 'forearms']::TEXT[], '{}'::TEXT[], 'dumbbell', 'strength', 'isolation', ARRAY['Hold the weight with palms facing each other.', 'Curl up keeping the neutral grip.', 'Lower slowly.']::TEXT[]),
+    ('cable-hammer-curl', 'Cable Hammer Curl', 'Curl with a neutral grip, working the brachialis and forearms.', ARRAY['biceps', 'forearms']::TEXT[], '{}'::TEXT[], 'cable', 'strength', 'isolation', ARRAY['Hold the weight with palms facing each other.', 'Curl up keeping the neutral grip.', 'Lower slowly.']::TEXT[]),
+    ('ez-bar-preacher-curl', 'EZ-Bar Preacher Curl', 'Curl over a preacher bench to prevent cheating.', ARRAY['biceps']::TEXT[], ARRAY['forearms']::TEXT[], 'ez_bar', 'strength', 'isolation', ARRAY['Rest the upper arms on the pad.', 'Curl the weight up.', 'Lower to almost full extension.']::TEXT[]),
+    ('dumbbell-preacher-curl', 'Dumbbell Preacher Curl', 'Curl over a preacher bench to prevent cheating.', ARRAY['biceps']::TEXT[], ARRAY['forearms']::TEXT[], 'dumbbell', 'strength', 'isolation', ARRAY['Rest the upper arms on the pad.', 'Curl the weight up.', 'Lower to almost full extension.']::TEXT[]),
+    ('machine-preacher-curl', 'Machine Preacher Curl', 'Curl over a preacher bench to prevent cheating.', ARRAY['biceps']::TEXT[], ARRAY['forearms']::TEXT[], 'machine', 'strength', 'isolation', ARRAY['Rest the upper arms on the pad.', 'Curl the weight up.', 'Lower to almost full extension.']::TEXT[]),
+    ('cable-preacher-curl', 'Cable Preacher Curl', 'Curl over a preacher bench to prevent cheating.', ARRAY['biceps']::TEXT[], ARRAY['forearms']::TEXT[], 'cable', 'strength', 'isolation', ARRAY['Rest the upper arms on the pad.', 'Curl the weight up.', 'Lower to almost full extension.']::TEXT[]),
+    ('incline-dumbbell-curl', 'Incline Dumbbell Curl', 'Curl lying back on an incline bench, stretching the long head.', ARRAY['biceps']::TEXT[], ARRAY['forearms']::TEXT[], 'dumbbell', 'strength', 'isolation', ARRAY['Lie back on a 45 degree bench.', 'Curl without moving the upper arms.', 'Lower fully.']::TEXT[]),
+    ('concentration-curl', 'Concentration Curl', 'Seated single-arm curl with the elbow braced on the thigh.', ARRAY['biceps']::TEXT[], '{}'::TEXT[], 'dumbbell', 'strength', 'isolation', ARRAY['Brace the elbow on the inner thigh.', 'Curl the dumbbell up.', 'Lower slowly.']::TEXT[]),
+    ('spider-curl', 'Spider Curl', 'Curl lying face down on an incline bench.', ARRAY['biceps']::TEXT[], '{}'::TEXT[], 'dumbbell', 'strength', 'isolation', ARRAY['Lie chest down on an incline bench.', 'Curl the weights up.', 'Lower fully.']::TEXT[]),
+    ('drag-curl', 'Drag Curl', 'Curl dragging the bar up the torso with elbows moving back.', ARRAY['biceps']::TEXT[], ARRAY['forearms']::TEXT[], 'barbell', 'strength', 'isolation', ARRAY['Hold the bar at arm length.', 'Drag it up the body while pulling the elbows back.', 'Lower the same way.']::TEXT[]),
+    ('reverse-curl', 'Reverse Curl', 'Curl with an overhand grip.', ARRAY['forearms', 'biceps']::TEXT[], '{}'::TEXT[], 'ez_bar', 'strength', 'isolation', ARRAY['Grip the bar overhand.', 'Curl it up.', 'Lower slowly.']::TEXT[]),
+    ('zottman-curl', 'Zottman Curl', 'Curl up supinated, lower pronated.', ARRAY['biceps', 'forearms']::TEXT[], '{}'::TEXT[], 'dumbbell', 'strength', 'isolation', ARRAY['Curl up with palms up.', 'Rotate to palms down at the top.', 'Lower slowly and rotate back.']::TEXT[]),
+    ('bayesian-curl', 'Bayesian Curl', 'Single-arm cable curl facing away from the stack.', ARRAY['biceps']::TEXT[], '{}'::TEXT[], 'cable', 'strength', 'isolation', ARRAY['Face away from a low pulley.', 'Curl with the arm behind the body.', 'Lower slowly.']::TEXT[]),
+    ('chin-up-hold', 'Chin-Up Hold', 'Isometric hold at the top of a chin-up.', ARRAY['biceps', 'lats']::TEXT[], ARRAY['forearms']::TEXT[], 'bodyweight', 'strength', 'isolation', ARRAY['Pull up to the top of a chin-up.', 'Hold as long as possible.', 'Lower slowly.']::TEXT[]),
+    ('ez-bar-skull-crusher', 'EZ-Bar Skull Crusher', 'Lying triceps extension lowering the weight towards the forehead.', ARRAY['triceps']::TEXT[], '{}'::TEXT[], 'ez_bar', 'strength', 'isolation', ARRAY['Lie on a bench with the weight above the chest.', 'Bend the elbows to lower it towards the head.', 'Extend back up.']::TEXT[]),
+    ('barbell-skull-crusher', 'Barbell Skull Crusher', 'Lying triceps extension lowering the weight towards the forehead.', ARRAY['triceps']::TEXT[], '{}'::TEXT[], 'barbell', 'strength', 'isolation', ARRAY['Lie on a bench with the weight above the chest.', 'Bend the elbows to lower it towards the head.', 'Extend back up.']::TEXT[]),
+    ('dumbbell-skull-crusher', 'Dumbbell Skull Crusher', 'Lying triceps extension lowering the weight towards the forehead.', ARRAY['triceps']::TEXT[], '{}'::TEXT[], 'dumbbell', 'strength', 'isolation', ARRAY['Lie on a bench with the weight above the chest.', 'Bend the elbows to lower it towards the head.', 'Extend back up.']::TEXT[]),
+    ('dumbbell-overhead-triceps-extension', 'Dumbbell Overhead Triceps Extension', 'Elbow extension with the arms overhead.', ARRAY['triceps']::TEXT[], '{}'::TEXT[], 'dumbbell', 'strength', 'isolation', ARRAY['Hold the weight overhead.', 'Lower it behind the head.', 'Extend the arms.']::TEXT[]),
+    ('cable-overhead-triceps-extension', 'Cable Overhead Triceps Extension', 'Elbow extension with the arms overhead.', ARRAY['triceps']::TEXT[], '{}'::TEXT[], 'cable', 'strength', 'isolation', ARRAY['Hold the weight overhead.', 'Lower it behind the head.', 'Extend the arms.']::TEXT[]),
+    ('ez-bar-overhead-triceps-extension', 'EZ-Bar Overhead Triceps Extension', 'Elbow extension with the arms overhead.', ARRAY['triceps']::TEXT[], '{}'::TEXT[], 'ez_bar', 'strength', 'isolation', ARRAY['Hold the weight overhead.', 'Lower it behind the head.', 'Extend the arms.']::TEXT[]),
+    ('band-overhead-triceps-extension', 'Band Overhead Triceps Extension', 'Elbow extension with the arms overhead.', ARRAY['triceps']::TEXT[], '{}'::TEXT[], 'band', 'strength', 'isolation', ARRAY['Hold the weight overhead.', 'Lower it behind the head.', 'Extend the arms.']::TEXT[]),
+    ('triceps-pushdown', 'Triceps Pushdown', 'Cable elbow extension with a straight bar.', ARRAY['triceps']::TEXT[], '{}'::TEXT[], 'cable', 'strength', 'isolation', ARRAY['Grip the bar at chest height.', 'Push it down to full extension.', 'Return with control.']::TEXT[]),
+    ('rope-triceps-pushdown', 'Rope Triceps Pushdown', 'Cable pushdown with a rope, spreading the ends at the bottom.', ARRAY['triceps']::TEXT[], '{}'::TEXT[], 'cable', 'strength', 'isolation', ARRAY['Grip the rope at chest height.', 'Push down and spread the rope.', 'Return slowly.']::TEXT[]),
+    ('reverse-grip-triceps-pushdown', 'Reverse-Grip Triceps Pushdown', 'Pushdown with an underhand grip.', ARRAY['triceps']::TEXT[], '{}'::TEXT[], 'cable', 'strength', 'isolation', ARRAY['Grip the bar underhand.', 'Extend the elbows.', 'Return slowly.']::TEXT[]),
+    ('single-arm-triceps-pushdown', 'Single-Arm Triceps Pushdown', 'Pushdown with one D handle.', ARRAY['triceps']::TEXT[], '{}'::TEXT[], 'cable', 'strength', 'isolation', ARRAY['Grip the handle.', 'Push down to full extension.', 'Return slowly.']::TEXT[]),
+    ('band-triceps-pushdown', 'Band Triceps Pushdown', 'Pushdown with a band anchored overhead.', ARRAY['triceps']::TEXT[], '{}'::TEXT[], 'band', 'strength', 'isolation', ARRAY['Anchor the band high.', 'Push down to extension.', 'Return slowly.']::TEXT[]),
+    ('dumbbell-triceps-kickback', 'Dumbbell Triceps Kickback', 'Extending the elbow behind the body while hinged forward.', ARRAY['triceps']::TEXT[], '{}'::TEXT[], 'dumbbell', 'strength', 'isolation', ARRAY['Hinge forward with the upper arm parallel to the floor.', 'Extend the elbow.', 'Return slowly.']::TEXT[]),
+    ('cable-triceps-kickback', 'Cable Triceps Kickback', 'Extending the elbow behind the body while hinged forward.', ARRAY['triceps']::TEXT[], '{}'::TEXT[], 'cable', 'strength', 'isolation', ARRAY['Hinge forward with the upper arm parallel to the floor.', 'Extend the elbow.', 'Return slowly.']::TEXT[]),
+    ('jm-press', 'JM Press', 'Hybrid between a close-grip bench and skull crusher.', ARRAY['triceps']::TEXT[], ARRAY['chest']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Lower the bar towards the chin with elbows forward.', 'Stop above the chest.', 'Press back up.']::TEXT[]),
+    ('bench-dip', 'Bench Dip', 'Dip with the hands on a bench behind you.', ARRAY['triceps']::TEXT[], ARRAY['chest', 'shoulders']::TEXT[], 'bodyweight', 'strength', 'compound', ARRAY['Place the hands on a bench behind you.', 'Lower the hips by bending the elbows.', 'Press back up.']::TEXT[]),
+    ('triceps-dip', 'Triceps Dip', 'Upright dip on parallel bars to emphasise the triceps.', ARRAY['triceps']::TEXT[], ARRAY['chest', 'shoulders']::TEXT[], 'bodyweight', 'strength', 'compound', ARRAY['Support yourself on the bars with an upright torso.', 'Lower until the elbows reach 90 degrees.', 'Press up.']::TEXT[]),
+    ('machine-triceps-extension', 'Machine Triceps Extension', 'Seated triceps extension machine.', ARRAY['triceps']::TEXT[], '{}'::TEXT[], 'machine', 'strength', 'isolation', ARRAY['Set the arms on the pad.', 'Extend the elbows.', 'Return slowly.']::TEXT[]),
+    ('tate-press', 'Tate Press', 'Lying extension bringing the dumbbells to the chest with elbows flared.', ARRAY['triceps']::TEXT[], '{}'::TEXT[], 'dumbbell', 'strength', 'isolation', ARRAY['Hold dumbbells above the chest.', 'Bend the elbows out to lower the bells to the chest.', 'Extend.']::TEXT[]),
+    ('barbell-wrist-curl', 'Barbell Wrist Curl', 'Wrist flexion with the forearms supported.', ARRAY['forearms']::TEXT[], '{}'::TEXT[], 'barbell', 'strength', 'isolation', ARRAY['Rest the forearms on the thighs or bench.', 'Curl the wrists up.', 'Lower slowly.']::TEXT[]),
+    ('dumbbell-wrist-curl', 'Dumbbell Wrist Curl', 'Wrist flexion with the forearms supported.', ARRAY['forearms']::TEXT[], '{}'::TEXT[], 'dumbbell', 'strength', 'isolation', ARRAY['Rest the forearms on the thighs or bench.', 'Curl the wrists up.', 'Lower slowly.']::TEXT[]),
+    ('cable-wrist-curl', 'Cable Wrist Curl', 'Wrist flexion with the forearms supported.', ARRAY['forearms']::TEXT[], '{}'::TEXT[], 'cable', 'strength', 'isolation', ARRAY['Rest the forearms on the thighs or bench.', 'Curl the wrists up.', 'Lower slowly.']::TEXT[]),
+    ('barbell-reverse-wrist-curl', 'Barbell Reverse Wrist Curl', 'Wrist extension with an overhand grip.', ARRAY['forearms']::TEXT[], '{}'::TEXT[], 'barbell', 'strength', 'isolation', ARRAY['Rest the forearms with palms down.', 'Extend the wrists.', 'Lower slowly.']::TEXT[]),
+    ('dumbbell-reverse-wrist-curl', 'Dumbbell Reverse Wrist Curl', 'Wrist extension with an overhand grip.', ARRAY['forearms']::TEXT[], '{}'::TEXT[], 'dumbbell', 'strength', 'isolation', ARRAY['Rest the forearms with palms down.', 'Extend the wrists.', 'Lower slowly.']::TEXT[]),
+    ('farmer-s-walk', 'Farmer''s Walk', 'Walking with heavy weights in each hand.', ARRAY['forearms', 'traps']::TEXT[], ARRAY['abdominals', 'quadriceps', 'glutes']::TEXT[], 'dumbbell', 'strength', 'compound', ARRAY['Pick up heavy weights at the sides.', 'Walk with short quick steps.', 'Keep the torso upright.']::TEXT[]),
+    ('trap-bar-farmer-s-walk', 'Trap Bar Farmer''s Walk', 'Loaded carry inside a trap bar.', ARRAY['forearms', 'traps']::TEXT[], ARRAY['quadriceps', 'glutes']::TEXT[], 'trap_bar', 'strongman', 'compound', ARRAY['Deadlift the trap bar.', 'Walk for distance or time.', 'Set it down under control.']::TEXT[]),
+    ('dead-hang', 'Dead Hang', 'Hanging from a bar for time.', ARRAY['forearms']::TEXT[], ARRAY['lats']::TEXT[], 'bodyweight', 'strength', 'isolation', ARRAY['Grip the bar.', 'Hang with relaxed shoulders.', 'Hold for time.']::TEXT[]),
+    ('plate-pinch', 'Plate Pinch', 'Pinching plates together for time.', ARRAY['forearms']::TEXT[], '{}'::TEXT[], 'other', 'strength', 'isolation', ARRAY['Pinch two plates smooth side out.', 'Hold for time.', 'Set them down.']::TEXT[]),
+    ('wrist-roller', 'Wrist Roller', 'Rolling up a weight on a rope.', ARRAY['forearms']::TEXT[], '{}'::TEXT[], 'other', 'strength', 'isolation', ARRAY['Hold the roller at shoulder height.', 'Roll the weight up.', 'Unroll slowly.']::TEXT[]),
+    ('barbell-back-squat', 'Barbell Back Squat', 'Squat with the bar on the upper back.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['hamstrings', 'lower_back', 'adductors']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Place the bar on the upper back.', 'Sit down between the hips until the thighs are at least parallel.', 'Stand back up.']::TEXT[]),
+    ('smith-machine-back-squat', 'Smith Machine Back Squat', 'Squat with the bar on the upper back.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['hamstrings', 'lower_back', 'adductors']::TEXT[], 'smith_machine', 'strength', 'compound', ARRAY['Place the bar on the upper back.', 'Sit down between the hips until the thighs are at least parallel.', 'Stand back up.']::TEXT[]),
+    ('low-bar-back-squat', 'Low-Bar Back Squat', 'Back squat with the bar lower on the rear delts.', ARRAY['glutes', 'quadriceps']::TEXT[], ARRAY['hamstrings', 'lower_back']::TEXT[], 'barbell', 'powerlifting', 'compound', ARRAY['Place the bar across the rear delts.', 'Push the hips back and squat to depth.', 'Drive up.']::TEXT[]),
+    ('pause-squat', 'Pause Squat', 'Back squat with a pause in the hole.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['hamstrings', 'lower_back']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Squat to depth.', 'Pause for 2 seconds.', 'Drive up.']::TEXT[]),
+    ('box-squat', 'Box Squat', 'Squatting to a box.', ARRAY['glutes', 'quadriceps']::TEXT[], ARRAY['hamstrings', 'lower_back']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Sit back onto a box.', 'Pause briefly.', 'Drive up.']::TEXT[]),
+    ('barbell-front-squat', 'Barbell Front Squat', 'Squat with the weight held in front.', ARRAY['quadriceps']::TEXT[], ARRAY['glutes', 'abdominals', 'upper_back']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Hold the weight in the front rack.', 'Squat keeping the torso upright.', 'Stand up.']::TEXT[]),
+    ('dumbbell-front-squat', 'Dumbbell Front Squat', 'Squat with the weight held in front.', ARRAY['quadriceps']::TEXT[], ARRAY['glutes', 'abdominals', 'upper_back']::TEXT[], 'dumbbell', 'strength', 'compound', ARRAY['Hold the weight in the front rack.', 'Squat keeping the torso upright.', 'Stand up.']::TEXT[]),
+    ('kettlebell-front-squat', 'Kettlebell Front Squat', 'Squat with the weight held in front.', ARRAY['quadriceps']::TEXT[], ARRAY['glutes', 'abdominals', 'upper_back']::TEXT[], 'kettlebell', 'strength', 'compound', ARRAY['Hold the weight in the front rack.', 'Squat keeping the torso upright.', 'Stand up.']::TEXT[]),
+    ('smith-machine-front-squat', 'Smith Machine Front Squat', 'Squat with the weight held in front.', ARRAY['quadriceps']::TEXT[], ARRAY['glutes', 'abdominals', 'upper_back']::TEXT[], 'smith_machine', 'strength', 'compound', ARRAY['Hold the weight in the front rack.', 'Squat keeping the torso upright.', 'Stand up.']::TEXT[]),
+    ('zercher-squat', 'Zercher Squat', 'Squat holding the bar in the crook of the elbows.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['upper_back', 'abdominals', 'biceps']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Cradle the bar in the elbows.', 'Squat to depth.', 'Stand up.']::TEXT[]),
+    ('overhead-squat', 'Overhead Squat', 'Squat with the bar locked out overhead.', ARRAY['quadriceps', 'shoulders']::TEXT[], ARRAY['glutes', 'upper_back', 'abdominals']::TEXT[], 'barbell', 'olympic_weightlifting', 'compound', ARRAY['Lock the bar overhead with a wide grip.', 'Squat keeping the bar over the mid foot.', 'Stand up.']::TEXT[]),
+    ('safety-bar-squat', 'Safety Bar Squat', 'Squat with a safety squat bar.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['upper_back', 'hamstrings']::TEXT[], 'other', 'strength', 'compound', ARRAY['Set the yoke on the shoulders.', 'Squat to depth.', 'Stand up.']::TEXT[]),
+    ('dumbbell-goblet-squat', 'Dumbbell Goblet Squat', 'Squat holding a weight at the chest.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['abdominals']::TEXT[], 'dumbbell', 'strength', 'compound', ARRAY['Hold the weight against the chest.', 'Squat between the legs.', 'Stand up.']::TEXT[]),
+    ('kettlebell-goblet-squat', 'Kettlebell Goblet Squat', 'Squat holding a weight at the chest.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['abdominals']::TEXT[], 'kettlebell', 'strength', 'compound', ARRAY['Hold the weight against the chest.', 'Squat between the legs.', 'Stand up.']::TEXT[]),
+    ('bodyweight-squat', 'Bodyweight Squat', 'Air squat without external load.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['hamstrings']::TEXT[], 'bodyweight', 'strength', 'compound', ARRAY['Stand with feet shoulder width.', 'Squat down.', 'Stand up.']::TEXT[]),
+    ('jump-squat', 'Jump Squat', 'Explosive squat into a jump.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['calves']::TEXT[], 'bodyweight', 'plyometrics', 'compound', ARRAY['Squat down.', 'Jump explosively.', 'Land softly into the next rep.']::TEXT[]),
+    ('pistol-squat', 'Pistol Squat', 'Single-leg squat with the free leg extended.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['abdominals']::TEXT[], 'bodyweight', 'strength', 'compound', ARRAY['Stand on one leg.', 'Squat down with the other leg forward.', 'Stand up.']::TEXT[]),
+    ('hack-squat', 'Hack Squat', 'Machine squat on an angled sled.', ARRAY['quadriceps']::TEXT[], ARRAY['glutes']::TEXT[], 'machine', 'strength', 'compound', ARRAY['Place the shoulders under the pads.', 'Lower until the knees are deeply bent.', 'Press up.']::TEXT[]),
+    ('barbell-hack-squat', 'Barbell Hack Squat', 'Deadlift with the bar behind the legs.', ARRAY['quadriceps']::TEXT[], ARRAY['glutes', 'hamstrings']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Hold the bar behind the legs.', 'Stand up.', 'Lower the bar.']::TEXT[]),
+    ('pendulum-squat', 'Pendulum Squat', 'Squat on a pendulum machine.', ARRAY['quadriceps']::TEXT[], ARRAY['glutes']::TEXT[], 'machine', 'strength', 'compound', ARRAY['Set the shoulders under the pads.', 'Squat deep.', 'Drive up.']::TEXT[]),
+    ('belt-squat', 'Belt Squat', 'Squat with load hanging from a hip belt.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['adductors']::TEXT[], 'machine', 'strength', 'compound', ARRAY['Attach the belt.', 'Squat to depth.', 'Stand up.']::TEXT[]),
+    ('leg-press', 'Leg Press', 'Pressing a sled away with the legs.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['hamstrings', 'adductors']::TEXT[], 'machine', 'strength', 'compound', ARRAY['Place the feet shoulder width on the platform.', 'Lower until the knees are at 90 degrees or deeper.', 'Press back up.']::TEXT[]),
+    ('single-leg-leg-press', 'Single-Leg Leg Press', 'Leg press one leg at a time.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['hamstrings']::TEXT[], 'machine', 'strength', 'compound', ARRAY['Place one foot on the platform.', 'Lower under control.', 'Press up.']::TEXT[]),
+    ('leg-extension', 'Leg Extension', 'Knee extension on a machine.', ARRAY['quadriceps']::TEXT[], '{}'::TEXT[], 'machine', 'strength', 'isolation', ARRAY['Sit with the pad on the lower shins.', 'Extend the knees.', 'Lower slowly.']::TEXT[]),
+    ('lying-leg-curl', 'Lying Leg Curl', 'Knee flexion lying face down.', ARRAY['hamstrings']::TEXT[], ARRAY['calves']::TEXT[], 'machine', 'strength', 'isolation', ARRAY['Lie face down with the pad above the heels.', 'Curl the heels to the glutes.', 'Lower slowly.']::TEXT[]),
+    ('seated-leg-curl', 'Seated Leg Curl', 'Knee flexion seated.', ARRAY['hamstrings']::TEXT[], ARRAY['calves']::TEXT[], 'machine', 'strength', 'isolation', ARRAY['Sit with the pad behind the lower legs.', 'Curl the legs down.', 'Return slowly.']::TEXT[]),
+    ('standing-leg-curl', 'Standing Leg Curl', 'Single-leg standing curl.', ARRAY['hamstrings']::TEXT[], '{}'::TEXT[], 'machine', 'strength', 'isolation', ARRAY['Place the leg under the pad.', 'Curl the heel up.', 'Lower slowly.']::TEXT[]),
+    ('nordic-hamstring-curl', 'Nordic Hamstring Curl', 'Eccentric-focused bodyweight hamstring curl.', ARRAY['hamstrings']::TEXT[], ARRAY['glutes']::TEXT[], 'bodyweight', 'strength', 'isolation', ARRAY['Kneel with the ankles anchored.', 'Lower the torso forward as slowly as possible.', 'Push back up with the hands.']::TEXT[]),
+    ('glute-ham-raise', 'Glute-Ham Raise', 'Hamstring curl and hip extension on a GHD.', ARRAY['hamstrings', 'glutes']::TEXT[], ARRAY['lower_back']::TEXT[], 'machine', 'strength', 'compound', ARRAY['Set the feet in the GHD.', 'Lower the torso.', 'Curl back up with the hamstrings.']::TEXT[]),
+    ('stability-ball-leg-curl', 'Stability Ball Leg Curl', 'Hamstring curl with the feet on a ball.', ARRAY['hamstrings']::TEXT[], ARRAY['glutes']::TEXT[], 'other', 'strength', 'isolation', ARRAY['Lie with heels on the ball and hips raised.', 'Curl the ball in.', 'Extend slowly.']::TEXT[]),
+    ('barbell-lunge', 'Barbell Lunge', 'Stepping forward into a split stance.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['hamstrings', 'adductors']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Step forward.', 'Lower the back knee towards the floor.', 'Push back to the start.']::TEXT[]),
+    ('dumbbell-lunge', 'Dumbbell Lunge', 'Stepping forward into a split stance.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['hamstrings', 'adductors']::TEXT[], 'dumbbell', 'strength', 'compound', ARRAY['Step forward.', 'Lower the back knee towards the floor.', 'Push back to the start.']::TEXT[]),
+    ('kettlebell-lunge', 'Kettlebell Lunge', 'Stepping forward into a split stance.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['hamstrings', 'adductors']::TEXT[], 'kettlebell', 'strength', 'compound', ARRAY['Step forward.', 'Lower the back knee towards the floor.', 'Push back to the start.']::TEXT[]),
+    ('lunge', 'Lunge', 'Stepping forward into a split stance.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['hamstrings', 'adductors']::TEXT[], 'bodyweight', 'strength', 'compound', ARRAY['Step forward.', 'Lower the back knee towards the floor.', 'Push back to the start.']::TEXT[]),
+    ('smith-machine-lunge', 'Smith Machine Lunge', 'Stepping forward into a split stance.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['hamstrings', 'adductors']::TEXT[], 'smith_machine', 'strength', 'compound', ARRAY['Step forward.', 'Lower the back knee towards the floor.', 'Push back to the start.']::TEXT[]),
+    ('barbell-reverse-lunge', 'Barbell Reverse Lunge', 'Lunge stepping backward.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['hamstrings']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Step back.', 'Lower the back knee.', 'Return to standing.']::TEXT[]),
+    ('dumbbell-reverse-lunge', 'Dumbbell Reverse Lunge', 'Lunge stepping backward.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['hamstrings']::TEXT[], 'dumbbell', 'strength', 'compound', ARRAY['Step back.', 'Lower the back knee.', 'Return to standing.']::TEXT[]),
+    ('reverse-lunge', 'Reverse Lunge', 'Lunge stepping backward.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['hamstrings']::TEXT[], 'bodyweight', 'strength', 'compound', ARRAY['Step back.', 'Lower the back knee.', 'Return to standing.']::TEXT[]),
+    ('dumbbell-walking-lunge', 'Dumbbell Walking Lunge', 'Continuous forward lunges.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['hamstrings', 'adductors']::TEXT[], 'dumbbell', 'strength', 'compound', ARRAY['Lunge forward.', 'Bring the back foot through into the next lunge.', 'Continue for distance.']::TEXT[]),
+    ('barbell-walking-lunge', 'Barbell Walking Lunge', 'Continuous forward lunges.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['hamstrings', 'adductors']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Lunge forward.', 'Bring the back foot through into the next lunge.', 'Continue for distance.']::TEXT[]),
+    ('walking-lunge', 'Walking Lunge', 'Continuous forward lunges.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['hamstrings', 'adductors']::TEXT[], 'bodyweight', 'strength', 'compound', ARRAY['Lunge forward.', 'Bring the back foot through into the next lunge.', 'Continue for distance.']::TEXT[]),
+    ('dumbbell-bulgarian-split-squat', 'Dumbbell Bulgarian Split Squat', 'Split squat with the rear foot elevated.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['hamstrings', 'adductors']::TEXT[], 'dumbbell', 'strength', 'compound', ARRAY['Place the rear foot on a bench.', 'Lower until the front thigh is parallel.', 'Drive up.']::TEXT[]),
+    ('barbell-bulgarian-split-squat', 'Barbell Bulgarian Split Squat', 'Split squat with the rear foot elevated.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['hamstrings', 'adductors']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Place the rear foot on a bench.', 'Lower until the front thigh is parallel.', 'Drive up.']::TEXT[]),
+    ('bulgarian-split-squat', 'Bulgarian Split Squat', 'Split squat with the rear foot elevated.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['hamstrings', 'adductors']::TEXT[], 'bodyweight', 'strength', 'compound', ARRAY['Place the rear foot on a bench.', 'Lower until the front thigh is parallel.', 'Drive up.']::TEXT[]),
+    ('smith-machine-bulgarian-split-squat', 'Smith Machine Bulgarian Split Squat', 'Split squat with the rear foot elevated.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['hamstrings', 'adductors']::TEXT[], 'smith_machine', 'strength', 'compound', ARRAY['Place the rear foot on a bench.', 'Lower until the front thigh is parallel.', 'Drive up.']::TEXT[]),
+    ('dumbbell-split-squat', 'Dumbbell Split Squat', 'Static split-stance squat.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['hamstrings']::TEXT[], 'dumbbell', 'strength', 'compound', ARRAY['Take a long split stance.', 'Lower the back knee.', 'Stand back up.']::TEXT[]),
+    ('barbell-split-squat', 'Barbell Split Squat', 'Static split-stance squat.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['hamstrings']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Take a long split stance.', 'Lower the back knee.', 'Stand back up.']::TEXT[]),
+    ('split-squat', 'Split Squat', 'Static split-stance squat.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['hamstrings']::TEXT[], 'bodyweight', 'strength', 'compound', ARRAY['Take a long split stance.', 'Lower the back knee.', 'Stand back up.']::TEXT[]),
+    ('dumbbell-step-up', 'Dumbbell Step-Up', 'Stepping up onto a box.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['hamstrings']::TEXT[], 'dumbbell', 'strength', 'compound', ARRAY['Place one foot on the box.', 'Drive up to stand on the box.', 'Step down under control.']::TEXT[]),
+    ('barbell-step-up', 'Barbell Step-Up', 'Stepping up onto a box.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['hamstrings']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Place one foot on the box.', 'Drive up to stand on the box.', 'Step down under control.']::TEXT[]),
+    ('step-up', 'Step-Up', 'Stepping up onto a box.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['hamstrings']::TEXT[], 'bodyweight', 'strength', 'compound', ARRAY['Place one foot on the box.', 'Drive up to stand on the box.', 'Step down under control.']::TEXT[]),
+    ('lateral-lunge', 'Lateral Lunge', 'Lunge to the side.', ARRAY['adductors', 'quadriceps', 'glutes']::TEXT[], ARRAY['hamstrings']::TEXT[], 'dumbbell', 'strength', 'compound', ARRAY['Step wide to the side.', 'Sit back into the hip.', 'Push back to the start.']::TEXT[]),
+    ('curtsy-lunge', 'Curtsy Lunge', 'Lunge crossing behind the front leg.', ARRAY['glutes', 'quadriceps']::TEXT[], ARRAY['adductors']::TEXT[], 'dumbbell', 'strength', 'compound', ARRAY['Step back diagonally behind the front leg.', 'Lower.', 'Return.']::TEXT[]),
+    ('barbell-hip-thrust', 'Barbell Hip Thrust', 'Hip extension with the upper back on a bench.', ARRAY['glutes']::TEXT[], ARRAY['hamstrings', 'quadriceps']::TEXT[], 'barbell', 'strength', 'compound', ARRAY['Rest the upper back on a bench with the weight on the hips.', 'Drive the hips up to full extension.', 'Lower under control.']::TEXT[]),
+    ('dumbbell-hip-thrust', 'Dumbbell Hip Thrust', 'Hip extension with the upper back on a bench.', ARRAY['glutes']::TEXT[], ARRAY['hamstrings', 'quadriceps']::TEXT[], 'dumbbell', 'strength', 'compound', ARRAY['Rest the upper back on a bench with the weight on the hips.', 'Drive the hips up to full extension.', 'Lower under control.']::TEXT[]),
+    ('machine-hip-thrust', 'Machine Hip Thrust', 'Hip extension with the upper back on a bench.', ARRAY['glutes']::TEXT[], ARRAY['hamstrings', 'quadriceps']::TEXT[], 'machine', 'strength', 'compound', ARRAY['Rest the upper back on a bench with the weight on the hips.', 'Drive the hips up to full extension.', 'Lower under control.']::TEXT[]),
+    ('band-hip-thrust', 'Band Hip Thrust', 'Hip extension with the upper back on a bench.', ARRAY['glutes']::TEXT[], ARRAY['hamstrings', 'quadriceps']::TEXT[], 'band', 'strength', 'compound', ARRAY['Rest the upper back on a bench with the weight on the hips.', 'Drive the hips up to full extension.', 'Lower under control.']::TEXT[]),
+    ('smith-machine-hip-thrust', 'Smith Machine Hip Thrust', 'Hip extension with the upper back on a bench.', ARRAY['glutes']::TEXT[], ARRAY['hamstrings', 'quadriceps']::TEXT[], 'smith_machine', 'strength', 'compound', ARRAY['Rest the upper back on a bench with the weight on the hips.', 'Drive the hips up to full extension.', 'Lower under control.']::TEXT[]),
+    ('single-leg-hip-thrust', 'Single-Leg Hip Thrust', 'Hip thrust on one leg.', ARRAY['glutes']::TEXT[], ARRAY['hamstrings']::TEXT[], 'bodyweight', 'strength', 'compound', ARRAY['Rest the upper back on a bench.', 'Extend one leg.', 'Drive the hips up with the other.']::TEXT[]),
+    ('barbell-glute-bridge', 'Barbell Glute Bridge', 'Hip extension lying on the floor.', ARRAY['glutes']::TEXT[], ARRAY['hamstrings']::TEXT[], 'barbell', 'strength', 'isolation', ARRAY['Lie on the back with knees bent.', 'Drive the hips up.', 'Lower.']::TEXT[]),
+    ('glute-bridge', 'Glute Bridge', 'Hip extension lying on the floor.', ARRAY['glutes']::TEXT[], ARRAY['hamstrings']::TEXT[], 'bodyweight', 'strength', 'isolation', ARRAY['Lie on the back with knees bent.', 'Drive the hips up.', 'Lower.']::TEXT[]),
+    ('dumbbell-glute-bridge', 'Dumbbell Glute Bridge', 'Hip extension lying on the floor.', ARRAY['glutes']::TEXT[], ARRAY['hamstrings']::TEXT[], 'dumbbell', 'strength', 'isolation', ARRAY['Lie on the back with knees bent.', 'Drive the hips up.', 'Lower.']::TEXT[]),
+    ('cable-pull-through', 'Cable Pull-Through', 'Hip hinge pulling a rope between the legs.', ARRAY['glutes', 'hamstrings']::TEXT[], ARRAY['lower_back']::TEXT[], 'cable', 'strength', 'compound', ARRAY['Face away from a low pulley holding the rope between the legs.', 'Hinge back.', 'Drive the hips forward.']::TEXT[]),
+    ('cable-glute-kickback', 'Cable Glute Kickback', 'Kicking one leg back against a cable.', ARRAY['glutes']::TEXT[], ARRAY['hamstrings']::TEXT[], 'cable', 'strength', 'isolation', ARRAY['Attach the cuff to the ankle.', 'Kick the leg back.', 'Return slowly.']::TEXT[]),
+    ('hip-abduction-machine', 'Hip Abduction Machine', 'Seated hip abduction.', ARRAY['abductors', 'glutes']::TEXT[], '{}'::TEXT[], 'machine', 'strength', 'isolation', ARRAY['Sit with the pads outside the knees.', 'Push the legs apart.', 'Return slowly.']::TEXT[]),
+    ('hip-adduction-machine', 'Hip Adduction Machine', 'Seated hip adduction.', ARRAY['adductors']::TEXT[], '{}'::TEXT[], 'machine', 'strength', 'isolation', ARRAY['Sit with the pads inside the knees.', 'Squeeze the legs together.', 'Return slowly.']::TEXT[]),
+    ('banded-lateral-walk', 'Banded Lateral Walk', 'Side steps against a mini band.', ARRAY['abductors', 'glutes']::TEXT[], '{}'::TEXT[], 'band', 'strength', 'isolation', ARRAY['Place a band around the knees or ankles.', 'Step sideways keeping tension.', 'Repeat in both directions.']::TEXT[]),
+    ('copenhagen-plank', 'Copenhagen Plank', 'Side plank with the top leg on a bench.', ARRAY['adductors']::TEXT[], ARRAY['obliques']::TEXT[], 'bodyweight', 'strength', 'isolation', ARRAY['Place the top leg on a bench.', 'Lift the hips into a side plank.', 'Hold for time.']::TEXT[]),
+    ('standing-calf-raise-machine', 'Standing Calf Raise (Machine)', 'Plantar flexion standing.', ARRAY['calves']::TEXT[], '{}'::TEXT[], 'machine', 'strength', 'isolation', ARRAY['Stand with the balls of the feet on a step.', 'Raise the heels as high as possible.', 'Lower into a stretch.']::TEXT[]),
+    ('standing-calf-raise-barbell', 'Standing Calf Raise (Barbell)', 'Plantar flexion standing.', ARRAY['calves']::TEXT[], '{}'::TEXT[], 'barbell', 'strength', 'isolation', ARRAY['Stand with the balls of the feet on a step.', 'Raise the heels as high as possible.', 'Lower into a stretch.']::TEXT[]),
+    ('standing-calf-raise-dumbbell', 'Standing Calf Raise (Dumbbell)', 'Plantar flexion standing.', ARRAY['calves']::TEXT[], '{}'::TEXT[], 'dumbbell', 'strength', 'isolation', ARRAY['Stand with the balls of the feet on a step.', 'Raise the heels as high as possible.', 'Lower into a stretch.']::TEXT[]),
+    ('standing-calf-raise-smith-machine', 'Standing Calf Raise (Smith Machine)', 'Plantar flexion standing.', ARRAY['calves']::TEXT[], '{}'::TEXT[], 'smith_machine', 'strength', 'isolation', ARRAY['Stand with the balls of the feet on a step.', 'Raise the heels as high as possible.', 'Lower into a stretch.']::TEXT[]),
+    ('standing-calf-raise', 'Standing Calf Raise', 'Plantar flexion standing.', ARRAY['calves']::TEXT[], '{}'::TEXT[], 'bodyweight', 'strength', 'isolation', ARRAY['Stand with the balls of the feet on a step.', 'Raise the heels as high as possible.', 'Lower into a stretch.']::TEXT[]),
+    ('seated-calf-raise', 'Seated Calf Raise', 'Plantar flexion seated with bent knees, emphasising the soleus.', ARRAY['calves']::TEXT[], '{}'::TEXT[], 'machine', 'strength', 'isolation', ARRAY['Sit with the pad on the thighs.', 'Raise the heels.', 'Lower into a stretch.']::TEXT[]),
+    ('leg-press-calf-raise', 'Leg Press Calf Raise', 'Calf raise on the leg press.', ARRAY['calves']::TEXT[], '{}'::TEXT[], 'machine', 'strength', 'isolation', ARRAY['Place the balls of the feet on the platform edge.', 'Extend the ankles.', 'Lower into a stretch.']::TEXT[]),
+    ('single-leg-calf-raise', 'Single-Leg Calf Raise', 'Calf raise on one leg.', ARRAY['calves']::TEXT[], '{}'::TEXT[], 'bodyweight', 'strength', 'isolation', ARRAY['Stand on one leg on a step.', 'Raise the heel.', 'Lower slowly.']::TEXT[]),
+    ('tibialis-raise', 'Tibialis Raise', 'Raising the toes with the back against a wall.', ARRAY['calves']::TEXT[], '{}'::TEXT[], 'bodyweight', 'strength', 'isolation', ARRAY['Lean against a wall with heels forward.', 'Raise the toes.', 'Lower slowly.']::TEXT[]),
+    ('sissy-squat', 'Sissy Squat', 'Squat leaning back with the heels raised.', ARRAY['quadriceps']::TEXT[], '{}'::TEXT[], 'bodyweight', 'strength', 'isolation', ARRAY['Rise onto the toes.', 'Lean back while bending the knees.', 'Return upright.']::TEXT[]),
+    ('wall-sit', 'Wall Sit', 'Isometric squat against a wall.', ARRAY['quadriceps']::TEXT[], ARRAY['glutes']::TEXT[], 'bodyweight', 'strength', 'isolation', ARRAY['Slide down a wall to thighs parallel.', 'Hold for time.', 'Stand up.']::TEXT[]),
+    ('sled-push', 'Sled Push', 'Pushing a weighted sled.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['calves', 'shoulders']::TEXT[], 'other', 'strongman', 'compound', ARRAY['Grip the sled handles.', 'Drive with short powerful steps.', 'Push for distance.']::TEXT[]),
+    ('sled-drag', 'Sled Drag', 'Dragging a weighted sled.', ARRAY['quadriceps', 'hamstrings', 'glutes']::TEXT[], ARRAY['calves']::TEXT[], 'other', 'strongman', 'compound', ARRAY['Attach the strap.', 'Walk forward or backward.', 'Drag for distance.']::TEXT[]),
+    ('plank', 'Plank', 'Isometric hold in a forearm plank.', ARRAY['abdominals']::TEXT[], ARRAY['obliques', 'shoulders']::TEXT[], 'bodyweight', 'strength', 'isolation', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('side-plank', 'Side Plank', 'Isometric hold on one forearm.', ARRAY['obliques']::TEXT[], ARRAY['abdominals']::TEXT[], 'bodyweight', 'strength', 'isolation', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('crunch', 'Crunch', 'Spinal flexion lying on the back.', ARRAY['abdominals']::TEXT[], '{}'::TEXT[], 'bodyweight', 'strength', 'isolation', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('sit-up', 'Sit-Up', 'Full sit-up from lying to seated.', ARRAY['abdominals']::TEXT[], ARRAY['obliques']::TEXT[], 'bodyweight', 'strength', 'isolation', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('decline-sit-up', 'Decline Sit-Up', 'Sit-up on a decline bench.', ARRAY['abdominals']::TEXT[], ARRAY['obliques']::TEXT[], 'bodyweight', 'strength', 'isolation', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('cable-crunch', 'Cable Crunch', 'Kneeling crunch against a rope.', ARRAY['abdominals']::TEXT[], '{}'::TEXT[], 'cable', 'strength', 'isolation', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('machine-crunch', 'Machine Crunch', 'Seated crunch machine.', ARRAY['abdominals']::TEXT[], '{}'::TEXT[], 'machine', 'strength', 'isolation', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('hanging-leg-raise', 'Hanging Leg Raise', 'Raising straight legs from a dead hang.', ARRAY['abdominals']::TEXT[], ARRAY['obliques']::TEXT[], 'bodyweight', 'strength', 'isolation', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('hanging-knee-raise', 'Hanging Knee Raise', 'Raising the knees from a dead hang.', ARRAY['abdominals']::TEXT[], '{}'::TEXT[], 'bodyweight', 'strength', 'isolation', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('toes-to-bar', 'Toes-to-Bar', 'Raising the toes to the bar from a hang.', ARRAY['abdominals']::TEXT[], ARRAY['lats', 'obliques']::TEXT[], 'bodyweight', 'strength', 'isolation', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('lying-leg-raise', 'Lying Leg Raise', 'Raising straight legs lying on the back.', ARRAY['abdominals']::TEXT[], '{}'::TEXT[], 'bodyweight', 'strength', 'isolation', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('captain-s-chair-leg-raise', 'Captain''s Chair Leg Raise', 'Leg raise on a vertical knee raise station.', ARRAY['abdominals']::TEXT[], '{}'::TEXT[], 'machine', 'strength', 'isolation', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('ab-wheel-rollout', 'Ab Wheel Rollout', 'Rolling an ab wheel out and back.', ARRAY['abdominals']::TEXT[], ARRAY['lats', 'shoulders']::TEXT[], 'other', 'strength', 'isolation', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('barbell-rollout', 'Barbell Rollout', 'Rollout using a loaded barbell.', ARRAY['abdominals']::TEXT[], ARRAY['lats']::TEXT[], 'barbell', 'strength', 'isolation', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('russian-twist', 'Russian Twist', 'Seated rotation side to side.', ARRAY['obliques']::TEXT[], ARRAY['abdominals']::TEXT[], 'medicine_ball', 'strength', 'isolation', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('bicycle-crunch', 'Bicycle Crunch', 'Alternating elbow to knee crunch.', ARRAY['abdominals', 'obliques']::TEXT[], '{}'::TEXT[], 'bodyweight', 'strength', 'isolation', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('dead-bug', 'Dead Bug', 'Alternating arm and leg extension lying on the back.', ARRAY['abdominals']::TEXT[], '{}'::TEXT[], 'bodyweight', 'strength', 'isolation', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('bird-dog', 'Bird Dog', 'Opposite arm and leg extension on all fours.', ARRAY['lower_back']::TEXT[], ARRAY['abdominals', 'glutes']::TEXT[], 'bodyweight', 'strength', 'isolation', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('hollow-body-hold', 'Hollow Body Hold', 'Isometric hold in a hollow position.', ARRAY['abdominals']::TEXT[], '{}'::TEXT[], 'bodyweight', 'strength', 'isolation', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('l-sit', 'L-Sit', 'Supported hold with the legs straight in front.', ARRAY['abdominals']::TEXT[], ARRAY['triceps', 'quadriceps']::TEXT[], 'bodyweight', 'strength', 'isolation', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('dragon-flag', 'Dragon Flag', 'Lowering a rigid body from a bench.', ARRAY['abdominals']::TEXT[], ARRAY['lower_back']::TEXT[], 'bodyweight', 'strength', 'isolation', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('v-up', 'V-Up', 'Simultaneous leg and torso raise.', ARRAY['abdominals']::TEXT[], '{}'::TEXT[], 'bodyweight', 'strength', 'isolation', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('mountain-climber', 'Mountain Climber', 'Alternating knee drives from a plank.', ARRAY['abdominals']::TEXT[], ARRAY['shoulders', 'quadriceps']::TEXT[], 'bodyweight', 'strength', 'compound', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('pallof-press', 'Pallof Press', 'Anti-rotation press against a cable.', ARRAY['obliques', 'abdominals']::TEXT[], '{}'::TEXT[], 'cable', 'strength', 'isolation', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('cable-woodchopper', 'Cable Woodchopper', 'Diagonal rotation against a cable.', ARRAY['obliques']::TEXT[], ARRAY['abdominals', 'shoulders']::TEXT[], 'cable', 'strength', 'isolation', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('landmine-rotation', 'Landmine Rotation', 'Rotating a landmine bar side to side.', ARRAY['obliques']::TEXT[], ARRAY['shoulders', 'abdominals']::TEXT[], 'barbell', 'strength', 'isolation', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('dumbbell-side-bend', 'Dumbbell Side Bend', 'Lateral flexion holding a dumbbell.', ARRAY['obliques']::TEXT[], '{}'::TEXT[], 'dumbbell', 'strength', 'isolation', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('suitcase-carry', 'Suitcase Carry', 'Carrying a weight in one hand.', ARRAY['obliques']::TEXT[], ARRAY['forearms', 'traps']::TEXT[], 'dumbbell', 'strength', 'compound', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('weighted-plank', 'Weighted Plank', 'Plank with a plate on the back.', ARRAY['abdominals']::TEXT[], ARRAY['obliques']::TEXT[], 'other', 'strength', 'isolation', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('stir-the-pot', 'Stir the Pot', 'Forearm plank on a ball making circles.', ARRAY['abdominals']::TEXT[], ARRAY['obliques']::TEXT[], 'other', 'strength', 'isolation', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('reverse-crunch', 'Reverse Crunch', 'Curling the hips towards the chest lying on the back.', ARRAY['abdominals']::TEXT[], '{}'::TEXT[], 'bodyweight', 'strength', 'isolation', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('flutter-kicks', 'Flutter Kicks', 'Alternating small leg kicks lying on the back.', ARRAY['abdominals']::TEXT[], '{}'::TEXT[], 'bodyweight', 'strength', 'isolation', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('medicine-ball-slam', 'Medicine Ball Slam', 'Slamming a medicine ball into the floor.', ARRAY['abdominals']::TEXT[], ARRAY['lats', 'shoulders']::TEXT[], 'medicine_ball', 'strength', 'compound', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('turkish-get-up', 'Turkish Get-Up', 'Rising from lying to standing with a weight overhead.', ARRAY['abdominals', 'shoulders']::TEXT[], ARRAY['glutes', 'obliques']::TEXT[], 'kettlebell', 'strength', 'compound', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('neck-flexion', 'Neck Flexion', 'Neck curl lying on a bench with a plate.', ARRAY['neck']::TEXT[], '{}'::TEXT[], 'other', 'strength', 'isolation', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('neck-extension', 'Neck Extension', 'Neck extension lying prone with a plate or harness.', ARRAY['neck']::TEXT[], '{}'::TEXT[], 'other', 'strength', 'isolation', ARRAY['Brace the core.', 'Perform the movement slowly and with control.', 'Keep breathing throughout the set.']::TEXT[]),
+    ('power-clean', 'Power Clean', 'Explosive pull from the floor caught in a partial front squat.', ARRAY['quadriceps', 'glutes', 'traps']::TEXT[], ARRAY['hamstrings', 'shoulders', 'lower_back']::TEXT[], 'barbell', 'olympic_weightlifting', 'compound', ARRAY['Set up with a flat back over the bar.', 'Accelerate the bar with an explosive hip extension.', 'Receive the bar in a stable position and stand up.']::TEXT[]),
+    ('squat-clean', 'Squat Clean', 'Clean caught in a full front squat.', ARRAY['quadriceps', 'glutes', 'traps']::TEXT[], ARRAY['hamstrings', 'shoulders']::TEXT[], 'barbell', 'olympic_weightlifting', 'compound', ARRAY['Set up with a flat back over the bar.', 'Accelerate the bar with an explosive hip extension.', 'Receive the bar in a stable position and stand up.']::TEXT[]),
+    ('hang-clean', 'Hang Clean', 'Clean starting from above the knees.', ARRAY['traps', 'glutes']::TEXT[], ARRAY['quadriceps', 'hamstrings']::TEXT[], 'barbell', 'olympic_weightlifting', 'compound', ARRAY['Set up with a flat back over the bar.', 'Accelerate the bar with an explosive hip extension.', 'Receive the bar in a stable position and stand up.']::TEXT[]),
+    ('hang-power-clean', 'Hang Power Clean', 'Power clean starting from above the knees.', ARRAY['traps', 'glutes']::TEXT[], ARRAY['quadriceps', 'hamstrings']::TEXT[], 'barbell', 'olympic_weightlifting', 'compound', ARRAY['Set up with a flat back over the bar.', 'Accelerate the bar with an explosive hip extension.', 'Receive the bar in a stable position and stand up.']::TEXT[]),
+    ('clean-pull', 'Clean Pull', 'Pulling phase of the clean without the catch.', ARRAY['traps', 'hamstrings', 'glutes']::TEXT[], ARRAY['lower_back', 'quadriceps']::TEXT[], 'barbell', 'olympic_weightlifting', 'compound', ARRAY['Set up with a flat back over the bar.', 'Accelerate the bar with an explosive hip extension.', 'Receive the bar in a stable position and stand up.']::TEXT[]),
+    ('clean-and-jerk', 'Clean and Jerk', 'The full two-part Olympic lift.', ARRAY['quadriceps', 'glutes', 'shoulders']::TEXT[], ARRAY['traps', 'triceps', 'hamstrings']::TEXT[], 'barbell', 'olympic_weightlifting', 'compound', ARRAY['Set up with a flat back over the bar.', 'Accelerate the bar with an explosive hip extension.', 'Receive the bar in a stable position and stand up.']::TEXT[]),
+    ('power-snatch', 'Power Snatch', 'Snatch caught above parallel.', ARRAY['glutes', 'traps', 'shoulders']::TEXT[], ARRAY['hamstrings', 'quadriceps']::TEXT[], 'barbell', 'olympic_weightlifting', 'compound', ARRAY['Set up with a flat back over the bar.', 'Accelerate the bar with an explosive hip extension.', 'Receive the bar in a stable position and stand up.']::TEXT[]),
+    ('squat-snatch', 'Squat Snatch', 'Snatch caught in a full overhead squat.', ARRAY['glutes', 'quadriceps', 'shoulders']::TEXT[], ARRAY['traps', 'hamstrings']::TEXT[], 'barbell', 'olympic_weightlifting', 'compound', ARRAY['Set up with a flat back over the bar.', 'Accelerate the bar with an explosive hip extension.', 'Receive the bar in a stable position and stand up.']::TEXT[]),
+    ('hang-snatch', 'Hang Snatch', 'Snatch starting from above the knees.', ARRAY['traps', 'shoulders']::TEXT[], ARRAY['glutes', 'hamstrings']::TEXT[], 'barbell', 'olympic_weightlifting', 'compound', ARRAY['Set up with a flat back over the bar.', 'Accelerate the bar with an explosive hip extension.', 'Receive the bar in a stable position and stand up.']::TEXT[]),
+    ('snatch-pull', 'Snatch Pull', 'Pulling phase of the snatch without the catch.', ARRAY['traps', 'hamstrings', 'glutes']::TEXT[], ARRAY['lower_back']::TEXT[], 'barbell', 'olympic_weightlifting', 'compound', ARRAY['Set up with a flat back over the bar.', 'Accelerate the bar with an explosive hip extension.', 'Receive the bar in a stable position and stand up.']::TEXT[]),
+    ('snatch-balance', 'Snatch Balance', 'Dropping under the bar into an overhead squat.', ARRAY['shoulders', 'quadriceps']::TEXT[], ARRAY['triceps', 'abdominals']::TEXT[], 'barbell', 'olympic_weightlifting', 'compound', ARRAY['Set up with a flat back over the bar.', 'Accelerate the bar with an explosive hip extension.', 'Receive the bar in a stable position and stand up.']::TEXT[]),
+    ('split-jerk', 'Split Jerk', 'Jerk caught in a split stance.', ARRAY['shoulders', 'triceps']::TEXT[], ARRAY['quadriceps', 'glutes']::TEXT[], 'barbell', 'olympic_weightlifting', 'compound', ARRAY['Set up with a flat back over the bar.', 'Accelerate the bar with an explosive hip extension.', 'Receive the bar in a stable position and stand up.']::TEXT[]),
+    ('push-jerk', 'Push Jerk', 'Jerk caught in a partial squat.', ARRAY['shoulders', 'triceps']::TEXT[], ARRAY['quadriceps']::TEXT[], 'barbell', 'olympic_weightlifting', 'compound', ARRAY['Set up with a flat back over the bar.', 'Accelerate the bar with an explosive hip extension.', 'Receive the bar in a stable position and stand up.']::TEXT[]),
+    ('muscle-snatch', 'Muscle Snatch', 'Snatch pulled straight overhead without rebending the knees.', ARRAY['shoulders', 'traps']::TEXT[], ARRAY['upper_back']::TEXT[], 'barbell', 'olympic_weightlifting', 'compound', ARRAY['Set up with a flat back over the bar.', 'Accelerate the bar with an explosive hip extension.', 'Receive the bar in a stable position and stand up.']::TEXT[]),
+    ('thruster', 'Thruster', 'Front squat into a push press in one movement.', ARRAY['quadriceps', 'shoulders']::TEXT[], ARRAY['glutes', 'triceps']::TEXT[], 'barbell', 'olympic_weightlifting', 'compound', ARRAY['Set up with a flat back over the bar.', 'Accelerate the bar with an explosive hip extension.', 'Receive the bar in a stable position and stand up.']::TEXT[]),
+    ('dumbbell-snatch', 'Dumbbell Snatch', 'Single-arm snatch with a dumbbell.', ARRAY['shoulders', 'glutes']::TEXT[], ARRAY['traps', 'hamstrings']::TEXT[], 'dumbbell', 'olympic_weightlifting', 'compound', ARRAY['Hinge with the dumbbell between the feet.', 'Extend explosively.', 'Punch the dumbbell overhead.']::TEXT[]),
+    ('kettlebell-snatch', 'Kettlebell Snatch', 'Swinging a kettlebell directly overhead.', ARRAY['shoulders', 'glutes']::TEXT[], ARRAY['hamstrings', 'traps']::TEXT[], 'kettlebell', 'olympic_weightlifting', 'compound', ARRAY['Hike the bell back.', 'Snap the hips and pull it high.', 'Punch through to lockout overhead.']::TEXT[]),
+    ('kettlebell-clean', 'Kettlebell Clean', 'Swinging a kettlebell into the rack.', ARRAY['glutes', 'hamstrings']::TEXT[], ARRAY['shoulders', 'forearms']::TEXT[], 'kettlebell', 'strength', 'compound', ARRAY['Hike the bell back.', 'Snap the hips.', 'Guide the bell into the rack.']::TEXT[]),
+    ('dumbbell-thruster', 'Dumbbell Thruster', 'Thruster with dumbbells.', ARRAY['quadriceps', 'shoulders']::TEXT[], ARRAY['glutes', 'triceps']::TEXT[], 'dumbbell', 'strength', 'compound', ARRAY['Hold the dumbbells at the shoulders.', 'Squat.', 'Drive up and press overhead.']::TEXT[]),
+    ('wall-ball', 'Wall Ball', 'Squat and throw a medicine ball to a target.', ARRAY['quadriceps', 'shoulders']::TEXT[], ARRAY['glutes']::TEXT[], 'medicine_ball', 'strength', 'compound', ARRAY['Hold the ball at the chest.', 'Squat and drive up.', 'Throw the ball to the target and catch it.']::TEXT[]),
+    ('box-jump', 'Box Jump', 'Jumping onto a box.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['calves']::TEXT[], 'bodyweight', 'plyometrics', 'compound', ARRAY['Stand in front of the box.', 'Swing the arms and jump.', 'Land softly and step down.']::TEXT[]),
+    ('broad-jump', 'Broad Jump', 'Horizontal jump for distance.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['hamstrings', 'calves']::TEXT[], 'bodyweight', 'plyometrics', 'compound', ARRAY['Swing the arms.', 'Jump forward as far as possible.', 'Land softly.']::TEXT[]),
+    ('depth-jump', 'Depth Jump', 'Drop from a box into an immediate jump.', ARRAY['quadriceps', 'calves']::TEXT[], ARRAY['glutes']::TEXT[], 'bodyweight', 'plyometrics', 'compound', ARRAY['Step off a box.', 'Land and immediately jump.', 'Land softly.']::TEXT[]),
+    ('burpee', 'Burpee', 'Squat thrust into a jump.', ARRAY['quadriceps', 'chest']::TEXT[], ARRAY['shoulders', 'abdominals']::TEXT[], 'bodyweight', 'plyometrics', 'compound', ARRAY['Drop into a push-up position.', 'Jump the feet in.', 'Jump up with the arms overhead.']::TEXT[]),
+    ('jumping-lunge', 'Jumping Lunge', 'Switching legs in the air between lunges.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['calves']::TEXT[], 'bodyweight', 'plyometrics', 'compound', ARRAY['Start in a lunge.', 'Jump and switch legs.', 'Land in a lunge.']::TEXT[]),
+    ('medicine-ball-chest-pass', 'Medicine Ball Chest Pass', 'Explosive two-handed throw from the chest.', ARRAY['chest']::TEXT[], ARRAY['triceps', 'shoulders']::TEXT[], 'medicine_ball', 'plyometrics', 'compound', ARRAY['Hold the ball at the chest.', 'Throw it explosively forward.', 'Catch or retrieve.']::TEXT[]),
+    ('skater-jump', 'Skater Jump', 'Lateral bounding from leg to leg.', ARRAY['glutes', 'quadriceps']::TEXT[], ARRAY['adductors', 'abductors']::TEXT[], 'bodyweight', 'plyometrics', 'compound', ARRAY['Jump sideways onto one leg.', 'Land and stick briefly.', 'Bound to the other side.']::TEXT[]),
+    ('atlas-stone-lift', 'Atlas Stone Lift', 'Lifting a stone from the floor onto a platform.', ARRAY['glutes', 'hamstrings', 'upper_back']::TEXT[], ARRAY['biceps', 'lower_back']::TEXT[], 'other', 'strongman', 'compound', ARRAY['Set up with a braced torso.', 'Lift or move the implement explosively.', 'Control it back down.']::TEXT[]),
+    ('log-press', 'Log Press', 'Overhead press with a log.', ARRAY['shoulders']::TEXT[], ARRAY['triceps', 'upper_back']::TEXT[], 'other', 'strongman', 'compound', ARRAY['Set up with a braced torso.', 'Lift or move the implement explosively.', 'Control it back down.']::TEXT[]),
+    ('yoke-carry', 'Yoke Carry', 'Walking with a loaded yoke across the shoulders.', ARRAY['quadriceps', 'upper_back']::TEXT[], ARRAY['abdominals', 'glutes']::TEXT[], 'other', 'strongman', 'compound', ARRAY['Set up with a braced torso.', 'Lift or move the implement explosively.', 'Control it back down.']::TEXT[]),
+    ('tire-flip', 'Tire Flip', 'Flipping a heavy tire end over end.', ARRAY['glutes', 'hamstrings', 'quadriceps']::TEXT[], ARRAY['chest', 'shoulders']::TEXT[], 'other', 'strongman', 'compound', ARRAY['Set up with a braced torso.', 'Lift or move the implement explosively.', 'Control it back down.']::TEXT[]),
+    ('axle-deadlift', 'Axle Deadlift', 'Deadlift with a thick axle bar.', ARRAY['hamstrings', 'glutes']::TEXT[], ARRAY['forearms', 'lower_back']::TEXT[], 'other', 'strongman', 'compound', ARRAY['Set up with a braced torso.', 'Lift or move the implement explosively.', 'Control it back down.']::TEXT[]),
+    ('sandbag-carry', 'Sandbag Carry', 'Carrying a sandbag in a bear hug.', ARRAY['upper_back', 'quadriceps']::TEXT[], ARRAY['biceps', 'abdominals']::TEXT[], 'other', 'strongman', 'compound', ARRAY['Set up with a braced torso.', 'Lift or move the implement explosively.', 'Control it back down.']::TEXT[]),
+    ('keg-carry', 'Keg Carry', 'Carrying a keg for distance.', ARRAY['upper_back', 'glutes']::TEXT[], ARRAY['biceps']::TEXT[], 'other', 'strongman', 'compound', ARRAY['Set up with a braced torso.', 'Lift or move the implement explosively.', 'Control it back down.']::TEXT[]),
+    ('husafell-stone-carry', 'Husafell Stone Carry', 'Carrying a flat stone against the chest.', ARRAY['upper_back', 'quadriceps']::TEXT[], ARRAY['abdominals']::TEXT[], 'other', 'strongman', 'compound', ARRAY['Set up with a braced torso.', 'Lift or move the implement explosively.', 'Control it back down.']::TEXT[]),
+    ('running', 'Running', 'Outdoor or track running.', ARRAY['quadriceps', 'calves']::TEXT[], ARRAY['hamstrings', 'glutes']::TEXT[], 'bodyweight', 'cardio', NULL, ARRAY['Warm up at an easy pace.', 'Maintain your target intensity.', 'Cool down gradually.']::TEXT[]),
+    ('treadmill-running', 'Treadmill Running', 'Running on a treadmill.', ARRAY['quadriceps', 'calves']::TEXT[], ARRAY['hamstrings', 'glutes']::TEXT[], 'machine', 'cardio', NULL, ARRAY['Warm up at an easy pace.', 'Maintain your target intensity.', 'Cool down gradually.']::TEXT[]),
+    ('walking', 'Walking', 'Walking outdoors or on a treadmill.', ARRAY['quadriceps', 'calves']::TEXT[], ARRAY['hamstrings', 'glutes']::TEXT[], 'bodyweight', 'cardio', NULL, ARRAY['Warm up at an easy pace.', 'Maintain your target intensity.', 'Cool down gradually.']::TEXT[]),
+    ('incline-treadmill-walk', 'Incline Treadmill Walk', 'Walking on an inclined treadmill.', ARRAY['quadriceps', 'calves']::TEXT[], ARRAY['hamstrings', 'glutes']::TEXT[], 'machine', 'cardio', NULL, ARRAY['Warm up at an easy pace.', 'Maintain your target intensity.', 'Cool down gradually.']::TEXT[]),
+    ('hiking', 'Hiking', 'Walking on trails and hills.', ARRAY['quadriceps', 'calves']::TEXT[], ARRAY['hamstrings', 'glutes']::TEXT[], 'bodyweight', 'cardio', NULL, ARRAY['Warm up at an easy pace.', 'Maintain your target intensity.', 'Cool down gradually.']::TEXT[]),
+    ('cycling', 'Cycling', 'Road or mountain biking.', ARRAY['quadriceps', 'calves']::TEXT[], ARRAY['hamstrings', 'glutes']::TEXT[], 'other', 'cardio', NULL, ARRAY['Warm up at an easy pace.', 'Maintain your target intensity.', 'Cool down gradually.']::TEXT[]),
+    ('stationary-bike', 'Stationary Bike', 'Cycling on a stationary bike.', ARRAY['quadriceps', 'calves']::TEXT[], ARRAY['hamstrings', 'glutes']::TEXT[], 'machine', 'cardio', NULL, ARRAY['Warm up at an easy pace.', 'Maintain your target intensity.', 'Cool down gradually.']::TEXT[]),
+    ('assault-bike', 'Assault Bike', 'Fan bike with moving arm handles.', ARRAY['quadriceps', 'calves']::TEXT[], ARRAY['hamstrings', 'glutes']::TEXT[], 'machine', 'cardio', NULL, ARRAY['Warm up at an easy pace.', 'Maintain your target intensity.', 'Cool down gradually.']::TEXT[]),
+    ('spin-class', 'Spin Class', 'Indoor cycling class.', ARRAY['quadriceps', 'calves']::TEXT[], ARRAY['hamstrings', 'glutes']::TEXT[], 'machine', 'cardio', NULL, ARRAY['Warm up at an easy pace.', 'Maintain your target intensity.', 'Cool down gradually.']::TEXT[]),
+    ('rowing-machine', 'Rowing Machine', 'Indoor rowing on an ergometer.', ARRAY['lats', 'shoulders']::TEXT[], ARRAY['biceps', 'abdominals']::TEXT[], 'machine', 'cardio', NULL, ARRAY['Warm up at an easy pace.', 'Maintain your target intensity.', 'Cool down gradually.']::TEXT[]),
+    ('skierg', 'SkiErg', 'Double-pole ski ergometer.', ARRAY['lats', 'shoulders']::TEXT[], ARRAY['biceps', 'abdominals']::TEXT[], 'machine', 'cardio', NULL, ARRAY['Warm up at an easy pace.', 'Maintain your target intensity.', 'Cool down gradually.']::TEXT[]),
+    ('elliptical-trainer', 'Elliptical Trainer', 'Low-impact elliptical machine.', ARRAY['quadriceps', 'calves']::TEXT[], ARRAY['hamstrings', 'glutes']::TEXT[], 'machine', 'cardio', NULL, ARRAY['Warm up at an easy pace.', 'Maintain your target intensity.', 'Cool down gradually.']::TEXT[]),
+    ('stair-climber', 'Stair Climber', 'Stair stepping machine.', ARRAY['quadriceps', 'calves']::TEXT[], ARRAY['hamstrings', 'glutes']::TEXT[], 'machine', 'cardio', NULL, ARRAY['Warm up at an easy pace.', 'Maintain your target intensity.', 'Cool down gradually.']::TEXT[]),
+    ('swimming', 'Swimming', 'Pool or open water swimming.', ARRAY['lats', 'shoulders']::TEXT[], ARRAY['biceps', 'abdominals']::TEXT[], 'other', 'cardio', NULL, ARRAY['Warm up at an easy pace.', 'Maintain your target intensity.', 'Cool down gradually.']::TEXT[]),
+    ('jump-rope', 'Jump Rope', 'Skipping rope.', ARRAY['quadriceps', 'calves']::TEXT[], ARRAY['hamstrings', 'glutes']::TEXT[], 'other', 'cardio', NULL, ARRAY['Warm up at an easy pace.', 'Maintain your target intensity.', 'Cool down gradually.']::TEXT[]),
+    ('sprints', 'Sprints', 'Short maximal effort runs.', ARRAY['quadriceps', 'calves']::TEXT[], ARRAY['hamstrings', 'glutes']::TEXT[], 'bodyweight', 'cardio', NULL, ARRAY['Warm up at an easy pace.', 'Maintain your target intensity.', 'Cool down gradually.']::TEXT[]),
+    ('hill-sprints', 'Hill Sprints', 'Sprints up an incline.', ARRAY['quadriceps', 'calves']::TEXT[], ARRAY['hamstrings', 'glutes']::TEXT[], 'bodyweight', 'cardio', NULL, ARRAY['Warm up at an easy pace.', 'Maintain your target intensity.', 'Cool down gradually.']::TEXT[]),
+    ('battle-ropes', 'Battle Ropes', 'Waving heavy ropes.', ARRAY['lats', 'shoulders']::TEXT[], ARRAY['biceps', 'abdominals']::TEXT[], 'other', 'cardio', NULL, ARRAY['Warm up at an easy pace.', 'Maintain your target intensity.', 'Cool down gradually.']::TEXT[]),
+    ('jumping-jacks', 'Jumping Jacks', 'Jumping while spreading arms and legs.', ARRAY['quadriceps', 'calves']::TEXT[], ARRAY['hamstrings', 'glutes']::TEXT[], 'bodyweight', 'cardio', NULL, ARRAY['Warm up at an easy pace.', 'Maintain your target intensity.', 'Cool down gradually.']::TEXT[]),
+    ('high-knees', 'High Knees', 'Running in place driving the knees high.', ARRAY['quadriceps', 'calves']::TEXT[], ARRAY['hamstrings', 'glutes']::TEXT[], 'bodyweight', 'cardio', NULL, ARRAY['Warm up at an easy pace.', 'Maintain your target intensity.', 'Cool down gradually.']::TEXT[]),
+    ('shadow-boxing', 'Shadow Boxing', 'Boxing combinations without a partner.', ARRAY['lats', 'shoulders']::TEXT[], ARRAY['biceps', 'abdominals']::TEXT[], 'bodyweight', 'cardio', NULL, ARRAY['Warm up at an easy pace.', 'Maintain your target intensity.', 'Cool down gradually.']::TEXT[]),
+    ('heavy-bag', 'Heavy Bag', 'Striking a heavy bag.', ARRAY['lats', 'shoulders']::TEXT[], ARRAY['biceps', 'abdominals']::TEXT[], 'other', 'cardio', NULL, ARRAY['Warm up at an easy pace.', 'Maintain your target intensity.', 'Cool down gradually.']::TEXT[]),
+    ('rucking', 'Rucking', 'Walking with a weighted backpack.', ARRAY['quadriceps', 'calves']::TEXT[], ARRAY['hamstrings', 'glutes']::TEXT[], 'other', 'cardio', NULL, ARRAY['Warm up at an easy pace.', 'Maintain your target intensity.', 'Cool down gradually.']::TEXT[]),
+    ('inline-skating', 'Inline Skating', 'Skating on inline skates.', ARRAY['quadriceps', 'calves']::TEXT[], ARRAY['hamstrings', 'glutes']::TEXT[], 'other', 'cardio', NULL, ARRAY['Warm up at an easy pace.', 'Maintain your target intensity.', 'Cool down gradually.']::TEXT[]),
+    ('cross-country-skiing', 'Cross-Country Skiing', 'Nordic skiing.', ARRAY['quadriceps', 'calves']::TEXT[], ARRAY['hamstrings', 'glutes']::TEXT[], 'other', 'cardio', NULL, ARRAY['Warm up at an easy pace.', 'Maintain your target intensity.', 'Cool down gradually.']::TEXT[]),
+    ('kayaking', 'Kayaking', 'Paddling a kayak.', ARRAY['lats', 'shoulders']::TEXT[], ARRAY['biceps', 'abdominals']::TEXT[], 'other', 'cardio', NULL, ARRAY['Warm up at an easy pace.', 'Maintain your target intensity.', 'Cool down gradually.']::TEXT[]),
+    ('stand-up-paddling', 'Stand-Up Paddling', 'Paddle boarding.', ARRAY['lats', 'shoulders']::TEXT[], ARRAY['biceps', 'abdominals']::TEXT[], 'other', 'cardio', NULL, ARRAY['Warm up at an easy pace.', 'Maintain your target intensity.', 'Cool down gradually.']::TEXT[]),
+    ('dancing', 'Dancing', 'Dance workout.', ARRAY['quadriceps', 'calves']::TEXT[], ARRAY['hamstrings', 'glutes']::TEXT[], 'bodyweight', 'cardio', NULL, ARRAY['Warm up at an easy pace.', 'Maintain your target intensity.', 'Cool down gradually.']::TEXT[]),
+    ('bear-crawl', 'Bear Crawl', 'Crawling on hands and feet.', ARRAY['quadriceps', 'calves']::TEXT[], ARRAY['hamstrings', 'glutes']::TEXT[], 'bodyweight', 'cardio', NULL, ARRAY['Warm up at an easy pace.', 'Maintain your target intensity.', 'Cool down gradually.']::TEXT[]),
+    ('shuttle-run', 'Shuttle Run', 'Back and forth sprints between two markers.', ARRAY['quadriceps', 'calves']::TEXT[], ARRAY['hamstrings', 'glutes']::TEXT[], 'bodyweight', 'cardio', NULL, ARRAY['Warm up at an easy pace.', 'Maintain your target intensity.', 'Cool down gradually.']::TEXT[]),
+    ('standing-hamstring-stretch', 'Standing Hamstring Stretch', 'Static stretch to improve flexibility.', ARRAY['hamstrings']::TEXT[], '{}'::TEXT[], 'bodyweight', 'stretching', NULL, ARRAY['Move into the position slowly.', 'Breathe deeply and relax into the stretch.', 'Hold for 30-60 seconds per side.']::TEXT[]),
+    ('seated-forward-fold', 'Seated Forward Fold', 'Static stretch to improve flexibility.', ARRAY['hamstrings', 'lower_back']::TEXT[], '{}'::TEXT[], 'bodyweight', 'stretching', NULL, ARRAY['Move into the position slowly.', 'Breathe deeply and relax into the stretch.', 'Hold for 30-60 seconds per side.']::TEXT[]),
+    ('couch-stretch', 'Couch Stretch', 'Static stretch to improve flexibility.', ARRAY['quadriceps']::TEXT[], '{}'::TEXT[], 'bodyweight', 'stretching', NULL, ARRAY['Move into the position slowly.', 'Breathe deeply and relax into the stretch.', 'Hold for 30-60 seconds per side.']::TEXT[]),
+    ('standing-quad-stretch', 'Standing Quad Stretch', 'Static stretch to improve flexibility.', ARRAY['quadriceps']::TEXT[], '{}'::TEXT[], 'bodyweight', 'stretching', NULL, ARRAY['Move into the position slowly.', 'Breathe deeply and relax into the stretch.', 'Hold for 30-60 seconds per side.']::TEXT[]),
+    ('pigeon-pose', 'Pigeon Pose', 'Static stretch to improve flexibility.', ARRAY['glutes']::TEXT[], '{}'::TEXT[], 'bodyweight', 'stretching', NULL, ARRAY['Move into the position slowly.', 'Breathe deeply and relax into the stretch.', 'Hold for 30-60 seconds per side.']::TEXT[]),
+    ('figure-four-stretch', 'Figure-Four Stretch', 'Static stretch to improve flexibility.', ARRAY['glutes']::TEXT[], '{}'::TEXT[], 'bodyweight', 'stretching', NULL, ARRAY['Move into the position slowly.', 'Breathe deeply and relax into the stretch.', 'Hold for 30-60 seconds per side.']::TEXT[]),
+    ('butterfly-stretch', 'Butterfly Stretch', 'Static stretch to improve flexibility.', ARRAY['adductors']::TEXT[], '{}'::TEXT[], 'bodyweight', 'stretching', NULL, ARRAY['Move into the position slowly.', 'Breathe deeply and relax into the stretch.', 'Hold for 30-60 seconds per side.']::TEXT[]),
+    ('frog-stretch', 'Frog Stretch', 'Static stretch to improve flexibility.', ARRAY['adductors']::TEXT[], '{}'::TEXT[], 'bodyweight', 'stretching', NULL, ARRAY['Move into the position slowly.', 'Breathe deeply and relax into the stretch.', 'Hold for 30-60 seconds per side.']::TEXT[]),
+    ('calf-stretch', 'Calf Stretch', 'Static stretch to improve flexibility.', ARRAY['calves']::TEXT[], '{}'::TEXT[], 'bodyweight', 'stretching', NULL, ARRAY['Move into the position slowly.', 'Breathe deeply and relax into the stretch.', 'Hold for 30-60 seconds per side.']::TEXT[]),
+    ('doorway-chest-stretch', 'Doorway Chest Stretch', 'Static stretch to improve flexibility.', ARRAY['chest']::TEXT[], '{}'::TEXT[], 'bodyweight', 'stretching', NULL, ARRAY['Move into the position slowly.', 'Breathe deeply and relax into the stretch.', 'Hold for 30-60 seconds per side.']::TEXT[]),
+    ('cross-body-shoulder-stretch', 'Cross-Body Shoulder Stretch', 'Static stretch to improve flexibility.', ARRAY['shoulders']::TEXT[], '{}'::TEXT[], 'bodyweight', 'stretching', NULL, ARRAY['Move into the position slowly.', 'Breathe deeply and relax into the stretch.', 'Hold for 30-60 seconds per side.']::TEXT[]),
+    ('overhead-triceps-stretch', 'Overhead Triceps Stretch', 'Static stretch to improve flexibility.', ARRAY['triceps']::TEXT[], '{}'::TEXT[], 'bodyweight', 'stretching', NULL, ARRAY['Move into the position slowly.', 'Breathe deeply and relax into the stretch.', 'Hold for 30-60 seconds per side.']::TEXT[]),
+    ('lat-stretch', 'Lat Stretch', 'Static stretch to improve flexibility.', ARRAY['lats']::TEXT[], '{}'::TEXT[], 'bodyweight', 'stretching', NULL, ARRAY['Move into the position slowly.', 'Breathe deeply and relax into the stretch.', 'Hold for 30-60 seconds per side.']::TEXT[]),
+    ('child-s-pose', 'Child''s Pose', 'Static stretch to improve flexibility.', ARRAY['lower_back', 'lats']::TEXT[], '{}'::TEXT[], 'bodyweight', 'stretching', NULL, ARRAY['Move into the position slowly.', 'Breathe deeply and relax into the stretch.', 'Hold for 30-60 seconds per side.']::TEXT[]),
+    ('cat-cow', 'Cat-Cow', 'Mobility and flexibility drill.', ARRAY['lower_back']::TEXT[], '{}'::TEXT[], 'bodyweight', 'mobility', NULL, ARRAY['Move into the position slowly.', 'Breathe deeply and relax into the stretch.', 'Hold for 30-60 seconds per side.']::TEXT[]),
+    ('thoracic-rotation', 'Thoracic Rotation', 'Mobility and flexibility drill.', ARRAY['upper_back']::TEXT[], '{}'::TEXT[], 'bodyweight', 'mobility', NULL, ARRAY['Move into the position slowly.', 'Breathe deeply and relax into the stretch.', 'Hold for 30-60 seconds per side.']::TEXT[]),
+    ('world-s-greatest-stretch', 'World''s Greatest Stretch', 'Mobility and flexibility drill.', ARRAY['hamstrings', 'glutes']::TEXT[], '{}'::TEXT[], 'bodyweight', 'mobility', NULL, ARRAY['Move into the position slowly.', 'Breathe deeply and relax into the stretch.', 'Hold for 30-60 seconds per side.']::TEXT[]),
+    ('90-90-hip-switch', '90/90 Hip Switch', 'Mobility and flexibility drill.', ARRAY['glutes']::TEXT[], '{}'::TEXT[], 'bodyweight', 'mobility', NULL, ARRAY['Move into the position slowly.', 'Breathe deeply and relax into the stretch.', 'Hold for 30-60 seconds per side.']::TEXT[]),
+    ('hip-flexor-stretch', 'Hip Flexor Stretch', 'Static stretch to improve flexibility.', ARRAY['quadriceps']::TEXT[], '{}'::TEXT[], 'bodyweight', 'stretching', NULL, ARRAY['Move into the position slowly.', 'Breathe deeply and relax into the stretch.', 'Hold for 30-60 seconds per side.']::TEXT[]),
+    ('cossack-squat', 'Cossack Squat', 'Mobility and flexibility drill.', ARRAY['adductors', 'quadriceps']::TEXT[], '{}'::TEXT[], 'bodyweight', 'mobility', NULL, ARRAY['Move into the position slowly.', 'Breathe deeply and relax into the stretch.', 'Hold for 30-60 seconds per side.']::TEXT[]),
+    ('deep-squat-hold', 'Deep Squat Hold', 'Mobility and flexibility drill.', ARRAY['quadriceps', 'glutes']::TEXT[], '{}'::TEXT[], 'bodyweight', 'mobility', NULL, ARRAY['Move into the position slowly.', 'Breathe deeply and relax into the stretch.', 'Hold for 30-60 seconds per side.']::TEXT[]),
+    ('shoulder-dislocate', 'Shoulder Dislocate', 'Mobility and flexibility drill.', ARRAY['shoulders']::TEXT[], '{}'::TEXT[], 'bodyweight', 'mobility', NULL, ARRAY['Move into the position slowly.', 'Breathe deeply and relax into the stretch.', 'Hold for 30-60 seconds per side.']::TEXT[]),
+    ('wall-slide', 'Wall Slide', 'Mobility and flexibility drill.', ARRAY['shoulders', 'upper_back']::TEXT[], '{}'::TEXT[], 'bodyweight', 'mobility', NULL, ARRAY['Move into the position slowly.', 'Breathe deeply and relax into the stretch.', 'Hold for 30-60 seconds per side.']::TEXT[]),
+    ('ankle-mobility-drill', 'Ankle Mobility Drill', 'Mobility and flexibility drill.', ARRAY['calves']::TEXT[], '{}'::TEXT[], 'bodyweight', 'mobility', NULL, ARRAY['Move into the position slowly.', 'Breathe deeply and relax into the stretch.', 'Hold for 30-60 seconds per side.']::TEXT[]),
+    ('foam-roll-quads', 'Foam Roll Quads', 'Mobility and flexibility drill.', ARRAY['quadriceps']::TEXT[], '{}'::TEXT[], 'other', 'mobility', NULL, ARRAY['Move into the position slowly.', 'Breathe deeply and relax into the stretch.', 'Hold for 30-60 seconds per side.']::TEXT[]),
+    ('foam-roll-upper-back', 'Foam Roll Upper Back', 'Mobility and flexibility drill.', ARRAY['upper_back']::TEXT[], '{}'::TEXT[], 'other', 'mobility', NULL, ARRAY['Move into the position slowly.', 'Breathe deeply and relax into the stretch.', 'Hold for 30-60 seconds per side.']::TEXT[]),
+    ('wrist-mobility-drill', 'Wrist Mobility Drill', 'Mobility and flexibility drill.', ARRAY['forearms']::TEXT[], '{}'::TEXT[], 'bodyweight', 'mobility', NULL, ARRAY['Move into the position slowly.', 'Breathe deeply and relax into the stretch.', 'Hold for 30-60 seconds per side.']::TEXT[]),
+    ('neck-rolls', 'Neck Rolls', 'Mobility and flexibility drill.', ARRAY['neck']::TEXT[], '{}'::TEXT[], 'bodyweight', 'mobility', NULL, ARRAY['Move into the position slowly.', 'Breathe deeply and relax into the stretch.', 'Hold for 30-60 seconds per side.']::TEXT[]),
+    ('scorpion-stretch', 'Scorpion Stretch', 'Mobility and flexibility drill.', ARRAY['lower_back', 'obliques']::TEXT[], '{}'::TEXT[], 'bodyweight', 'mobility', NULL, ARRAY['Move into the position slowly.', 'Breathe deeply and relax into the stretch.', 'Hold for 30-60 seconds per side.']::TEXT[]),
+    ('downward-dog', 'Downward Dog', 'Static stretch to improve flexibility.', ARRAY['hamstrings', 'calves', 'shoulders']::TEXT[], '{}'::TEXT[], 'bodyweight', 'stretching', NULL, ARRAY['Move into the position slowly.', 'Breathe deeply and relax into the stretch.', 'Hold for 30-60 seconds per side.']::TEXT[]),
+    ('spoto-press', 'Spoto Press', 'Bench press pausing just above the chest.', ARRAY['chest']::TEXT[], ARRAY['triceps', 'shoulders']::TEXT[], 'barbell', 'powerlifting', 'compound', ARRAY['Set up as for the competition lift.', 'Perform the variation with strict form.', 'Return to the start position.']::TEXT[]),
+    ('pin-press', 'Pin Press', 'Bench press from safety pins.', ARRAY['triceps', 'chest']::TEXT[], ARRAY['shoulders']::TEXT[], 'barbell', 'powerlifting', 'compound', ARRAY['Set up as for the competition lift.', 'Perform the variation with strict form.', 'Return to the start position.']::TEXT[]),
+    ('board-press', 'Board Press', 'Bench press to a board on the chest.', ARRAY['triceps']::TEXT[], ARRAY['chest']::TEXT[], 'barbell', 'powerlifting', 'compound', ARRAY['Set up as for the competition lift.', 'Perform the variation with strict form.', 'Return to the start position.']::TEXT[]),
+    ('larsen-press', 'Larsen Press', 'Bench press with the legs off the floor.', ARRAY['chest']::TEXT[], ARRAY['triceps']::TEXT[], 'barbell', 'powerlifting', 'compound', ARRAY['Set up as for the competition lift.', 'Perform the variation with strict form.', 'Return to the start position.']::TEXT[]),
+    ('paused-bench-press', 'Paused Bench Press', 'Bench press with a pause on the chest.', ARRAY['chest']::TEXT[], ARRAY['triceps', 'shoulders']::TEXT[], 'barbell', 'powerlifting', 'compound', ARRAY['Set up as for the competition lift.', 'Perform the variation with strict form.', 'Return to the start position.']::TEXT[]),
+    ('tempo-squat', 'Tempo Squat', 'Squat with a slow eccentric.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['hamstrings']::TEXT[], 'barbell', 'powerlifting', 'compound', ARRAY['Set up as for the competition lift.', 'Perform the variation with strict form.', 'Return to the start position.']::TEXT[]),
+    ('pin-squat', 'Pin Squat', 'Squat from safety pins.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['lower_back']::TEXT[], 'barbell', 'powerlifting', 'compound', ARRAY['Set up as for the competition lift.', 'Perform the variation with strict form.', 'Return to the start position.']::TEXT[]),
+    ('anderson-squat', 'Anderson Squat', 'Squat starting from the bottom position on pins.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['lower_back']::TEXT[], 'barbell', 'powerlifting', 'compound', ARRAY['Set up as for the competition lift.', 'Perform the variation with strict form.', 'Return to the start position.']::TEXT[]),
+    ('ssb-good-morning', 'SSB Good Morning', 'Good morning with a safety squat bar.', ARRAY['hamstrings', 'lower_back']::TEXT[], ARRAY['glutes']::TEXT[], 'other', 'powerlifting', 'compound', ARRAY['Set up as for the competition lift.', 'Perform the variation with strict form.', 'Return to the start position.']::TEXT[]),
+    ('banded-deadlift', 'Banded Deadlift', 'Deadlift with bands for accommodating resistance.', ARRAY['hamstrings', 'glutes']::TEXT[], ARRAY['lower_back', 'traps']::TEXT[], 'barbell', 'powerlifting', 'compound', ARRAY['Set up as for the competition lift.', 'Perform the variation with strict form.', 'Return to the start position.']::TEXT[]),
+    ('reverse-band-bench-press', 'Reverse Band Bench Press', 'Bench press with bands deloading the bottom.', ARRAY['chest', 'triceps']::TEXT[], ARRAY['shoulders']::TEXT[], 'barbell', 'powerlifting', 'compound', ARRAY['Set up as for the competition lift.', 'Perform the variation with strict form.', 'Return to the start position.']::TEXT[]),
+    ('chain-squat', 'Chain Squat', 'Squat with chains for accommodating resistance.', ARRAY['quadriceps', 'glutes']::TEXT[], ARRAY['lower_back']::TEXT[], 'barbell', 'powerlifting', 'compound', ARRAY['Set up as for the competition lift.', 'Perform the variation with strict form.', 'Return to the start position.']::TEXT[]);
@@ -0,0 +1,10 @@
+ALTER TABLE users ADD COLUMN is_admin BOOLEAN NOT NULL DEFAULT FALSE;
+
+ALTER TABLE exercises ADD COLUMN owner_id UUID REFERENCES users(id) ON DELETE CASCADE;
+ALTER TABLE exercises ADD COLUMN archived_at TIMESTAMPTZ;
+
+-- Slugs stay unique within the global catalog and within each user's custom exercises.
+ALTER TABLE exercises DROP CONSTRAINT exercises_slug_key;
+CREATE UNIQUE INDEX idx_exercises_global_slug ON exercises(slug) WHERE owner_id IS NULL;
+CREATE UNIQUE INDEX idx_exercises_owner_slug ON exercises(owner_id, slug) WHERE owner_id IS NOT NULL;
+CREATE INDEX idx_exercises_owner_id ON exercises(owner_id);
@@ -0,0 +1,40 @@
+CREATE TABLE workouts (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(120),
+    notes TEXT,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    finished_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    CHECK (finished_at IS NULL OR finished_at >= started_at)
+);
+
+CREATE INDEX idx_workouts_user_started ON workouts(user_id, started_at DESC);
+
+CREATE TABLE workout_exercises (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    workout_id UUID NOT NULL REFERENCES workouts(id) ON DELETE CASCADE,
+    exercise_id UUID NOT NULL REFERENCES exercises(id),
+    position INTEGER NOT NULL,
+    notes TEXT,
+    UNIQUE (workout_id, position)
+);
+
+CREATE INDEX idx_workout_exercises_exercise_id ON workout_exercises(exercise_id);
+
+CREATE TABLE sets (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    workout_exercise_id UUID NOT NULL REFERENCES workout_exercises(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    set_type VARCHAR(20) NOT NULL DEFAULT 'working',
+    reps INTEGER,
+    weight_kg DOUBLE PRECISION,
+    duration_seconds INTEGER,
+    distance_m DOUBLE PRECISION,
+    rpe DOUBLE PRECISION,
+    rir INTEGER,
+    notes TEXT,
+    completed_at TIMESTAMPTZ,
+    UNIQUE (workout_exercise_id, position)
+);
@@ -0,0 +1,31 @@
+CREATE TABLE workout_templates (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(120) NOT NULL,
+    description TEXT,
+    share_code VARCHAR(32) UNIQUE,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_workout_templates_user_id ON workout_templates(user_id);
+
+CREATE TABLE template_exercises (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    template_id UUID NOT NULL REFERENCES workout_templates(id) ON DELETE CASCADE,
+    exercise_id UUID NOT NULL REFERENCES exercises(id),
+    position INTEGER NOT NULL,
+    group_label VARCHAR(20),
+    group_type VARCHAR(20),
+    target_sets INTEGER NOT NULL DEFAULT 3,
+    target_reps_min INTEGER,
+    target_reps_max INTEGER,
+    target_weight_kg DOUBLE PRECISION,
+    target_rpe DOUBLE PRECISION,
+    rest_seconds INTEGER,
+    notes TEXT,
+    UNIQUE (template_id, position)
+);
+
+ALTER TABLE workouts ADD COLUMN template_id UUID REFERENCES workout_templates(id) ON DELETE SET NULL;
+ALTER TABLE workout_exercises ADD COLUMN group_label VARCHAR(20);
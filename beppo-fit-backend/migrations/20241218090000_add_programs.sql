@@ -0,0 +1,66 @@
+CREATE TABLE programs (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(120) NOT NULL,
+    description TEXT,
+    rounding_kg DOUBLE PRECISION NOT NULL DEFAULT 2.5,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_programs_user_id ON programs(user_id);
+
+CREATE TABLE program_weeks (
+    program_id UUID NOT NULL REFERENCES programs(id) ON DELETE CASCADE,
+    week_number INTEGER NOT NULL,
+    is_deload BOOLEAN NOT NULL DEFAULT FALSE,
+    PRIMARY KEY (program_id, week_number)
+);
+
+CREATE TABLE program_days (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    program_id UUID NOT NULL,
+    week_number INTEGER NOT NULL,
+    day_number INTEGER NOT NULL,
+    template_id UUID NOT NULL REFERENCES workout_templates(id) ON DELETE RESTRICT,
+    name VARCHAR(120),
+    FOREIGN KEY (program_id, week_number) REFERENCES program_weeks(program_id, week_number) ON DELETE CASCADE,
+    UNIQUE (program_id, week_number, day_number)
+);
+
+-- Percentage-of-training-max sets; exercises of the day's template without a
+-- prescription fall back to the template targets.
+CREATE TABLE program_prescriptions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    program_day_id UUID NOT NULL REFERENCES program_days(id) ON DELETE CASCADE,
+    exercise_id UUID NOT NULL REFERENCES exercises(id),
+    position INTEGER NOT NULL,
+    percent_of_tm DOUBLE PRECISION NOT NULL,
+    reps INTEGER NOT NULL,
+    is_amrap BOOLEAN NOT NULL DEFAULT FALSE,
+    UNIQUE (program_day_id, position)
+);
+
+CREATE TABLE training_maxes (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    exercise_id UUID NOT NULL REFERENCES exercises(id),
+    weight_kg DOUBLE PRECISION NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (user_id, exercise_id)
+);
+
+CREATE TABLE program_enrollments (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    program_id UUID NOT NULL REFERENCES programs(id) ON DELETE CASCADE,
+    current_week INTEGER NOT NULL DEFAULT 1,
+    current_day INTEGER NOT NULL DEFAULT 1,
+    -- Workout started for the current position; finishing it advances the enrollment.
+    pending_workout_id UUID REFERENCES workouts(id) ON DELETE SET NULL,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    completed_at TIMESTAMPTZ,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX idx_program_enrollments_active ON program_enrollments(user_id) WHERE completed_at IS NULL;
+CREATE INDEX idx_program_enrollments_pending_workout ON program_enrollments(pending_workout_id);
@@ -0,0 +1,20 @@
+ALTER TABLE template_exercises ADD COLUMN progression_rule VARCHAR(30);
+ALTER TABLE template_exercises ADD COLUMN progression_increment_kg DOUBLE PRECISION;
+
+CREATE TABLE progression_recommendations (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    template_exercise_id UUID NOT NULL REFERENCES template_exercises(id) ON DELETE CASCADE,
+    exercise_id UUID NOT NULL REFERENCES exercises(id),
+    workout_id UUID NOT NULL REFERENCES workouts(id) ON DELETE CASCADE,
+    rule VARCHAR(30) NOT NULL,
+    target_weight_kg DOUBLE PRECISION,
+    target_reps INTEGER,
+    -- Consecutive sessions that missed their targets, including the evaluated one.
+    failure_streak INTEGER NOT NULL DEFAULT 0,
+    reason TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_progression_recommendations_template_exercise ON progression_recommendations(template_exercise_id, created_at DESC);
+CREATE INDEX idx_progression_recommendations_workout ON progression_recommendations(workout_id);
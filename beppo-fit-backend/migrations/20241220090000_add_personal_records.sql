@@ -0,0 +1,19 @@
+CREATE TABLE personal_records (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    exercise_id UUID NOT NULL REFERENCES exercises(id),
+    workout_id UUID NOT NULL REFERENCES workouts(id) ON DELETE CASCADE,
+    -- The set that achieved the record; NULL for whole-session records like volume.
+    set_id UUID REFERENCES sets(id) ON DELETE CASCADE,
+    record_type VARCHAR(30) NOT NULL,
+    value DOUBLE PRECISION NOT NULL,
+    -- Weight the reps were done at, for rep records.
+    weight_kg DOUBLE PRECISION,
+    -- Distance covered, for time records.
+    distance_m DOUBLE PRECISION,
+    achieved_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_personal_records_user_exercise ON personal_records(user_id, exercise_id, record_type);
+CREATE INDEX idx_personal_records_workout ON personal_records(workout_id);
@@ -0,0 +1,23 @@
+-- Values are stored in metric units and converted to the profile's preferred units on the way out.
+CREATE TABLE body_measurements (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    measured_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    weight_kg DOUBLE PRECISION,
+    body_fat_percent DOUBLE PRECISION,
+    neck_cm DOUBLE PRECISION,
+    chest_cm DOUBLE PRECISION,
+    waist_cm DOUBLE PRECISION,
+    hips_cm DOUBLE PRECISION,
+    left_arm_cm DOUBLE PRECISION,
+    right_arm_cm DOUBLE PRECISION,
+    left_thigh_cm DOUBLE PRECISION,
+    right_thigh_cm DOUBLE PRECISION,
+    left_calf_cm DOUBLE PRECISION,
+    right_calf_cm DOUBLE PRECISION,
+    notes TEXT,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_body_measurements_user_measured ON body_measurements(user_id, measured_at);
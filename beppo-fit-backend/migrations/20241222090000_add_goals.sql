@@ -0,0 +1,33 @@
+CREATE TABLE goals (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    goal_type VARCHAR(20) NOT NULL,
+    title VARCHAR(120) NOT NULL,
+    -- Strength goals: the exercise and which record kind is tracked.
+    exercise_id UUID REFERENCES exercises(id),
+    metric VARCHAR(30),
+    -- Free-form unit label for custom goals.
+    unit VARCHAR(20),
+    start_value DOUBLE PRECISION,
+    target_value DOUBLE PRECISION NOT NULL,
+    current_value DOUBLE PRECISION,
+    deadline DATE,
+    completed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_goals_user_id ON goals(user_id);
+
+CREATE TABLE notifications (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    kind VARCHAR(40) NOT NULL,
+    title VARCHAR(200) NOT NULL,
+    body TEXT,
+    goal_id UUID REFERENCES goals(id) ON DELETE CASCADE,
+    read_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_notifications_user_created ON notifications(user_id, created_at DESC);
@@ -0,0 +1,8 @@
+CREATE TABLE user_achievements (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- Code of a definition in models::achievement::ACHIEVEMENTS.
+    code VARCHAR(40) NOT NULL,
+    unlocked_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (user_id, code)
+);
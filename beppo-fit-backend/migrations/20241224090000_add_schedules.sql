@@ -0,0 +1,27 @@
+CREATE TABLE workout_schedules (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    title VARCHAR(120) NOT NULL,
+    -- What to train; the plan stays on the calendar if either is deleted.
+    template_id UUID REFERENCES workout_templates(id) ON DELETE SET NULL,
+    program_day_id UUID REFERENCES program_days(id) ON DELETE SET NULL,
+    start_date DATE NOT NULL,
+    start_time TIME,
+    -- Canonical RRULE subset; NULL for a one-off session.
+    rrule TEXT,
+    notes TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_workout_schedules_user_id ON workout_schedules(user_id);
+
+-- Outcome of a single occurrence; occurrences without a row are still planned.
+CREATE TABLE scheduled_sessions (
+    schedule_id UUID NOT NULL REFERENCES workout_schedules(id) ON DELETE CASCADE,
+    scheduled_date DATE NOT NULL,
+    status VARCHAR(20) NOT NULL,
+    workout_id UUID REFERENCES workouts(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (schedule_id, scheduled_date)
+);
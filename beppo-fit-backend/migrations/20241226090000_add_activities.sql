@@ -0,0 +1,37 @@
+CREATE TABLE activities (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    activity_type VARCHAR(20) NOT NULL,
+    name VARCHAR(120),
+    started_at TIMESTAMPTZ NOT NULL,
+    duration_seconds INTEGER NOT NULL,
+    moving_seconds INTEGER,
+    distance_m DOUBLE PRECISION,
+    elevation_gain_m DOUBLE PRECISION,
+    elevation_loss_m DOUBLE PRECISION,
+    avg_heart_rate INTEGER,
+    max_heart_rate INTEGER,
+    avg_cadence INTEGER,
+    source_format VARCHAR(10),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    -- The same recording imported twice
+    UNIQUE (user_id, started_at)
+);
+
+CREATE TABLE activity_laps (
+    activity_id UUID NOT NULL REFERENCES activities(id) ON DELETE CASCADE,
+    lap_index INTEGER NOT NULL,
+    started_at TIMESTAMPTZ NOT NULL,
+    duration_seconds DOUBLE PRECISION NOT NULL,
+    distance_m DOUBLE PRECISION,
+    avg_heart_rate INTEGER,
+    PRIMARY KEY (activity_id, lap_index)
+);
+
+-- Recorded samples in the compact encoding of services::track.
+CREATE TABLE activity_tracks (
+    activity_id UUID PRIMARY KEY REFERENCES activities(id) ON DELETE CASCADE,
+    point_count INTEGER NOT NULL,
+    data BYTEA NOT NULL
+);
@@ -0,0 +1,20 @@
+-- Uploaded history files from other apps, kept until the user commits or
+-- discards them so the exercise mapping can be reviewed in between.
+CREATE TABLE strength_imports (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    source VARCHAR(20) NOT NULL,
+    units VARCHAR(10) NOT NULL,
+    data BYTEA NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_strength_imports_user ON strength_imports(user_id);
+
+-- Exercise names the user mapped by hand; a NULL exercise skips the name.
+CREATE TABLE strength_import_mappings (
+    import_id UUID NOT NULL REFERENCES strength_imports(id) ON DELETE CASCADE,
+    source_name VARCHAR(200) NOT NULL,
+    exercise_id UUID REFERENCES exercises(id) ON DELETE CASCADE,
+    PRIMARY KEY (import_id, source_name)
+);
@@ -0,0 +1,13 @@
+ALTER TABLE user_profiles
+    ADD COLUMN max_heart_rate INTEGER,
+    ADD COLUMN resting_heart_rate INTEGER,
+    ADD COLUMN heart_rate_zone_method VARCHAR(20) NOT NULL DEFAULT 'percent_max';
+
+ALTER TABLE activities ADD COLUMN notes TEXT;
+
+-- Seconds per kilometre over the moving time, or the elapsed time when stops were not recorded.
+ALTER TABLE activities ADD COLUMN pace_seconds_per_km DOUBLE PRECISION GENERATED ALWAYS AS (
+    CASE WHEN distance_m > 0
+        THEN ROUND((COALESCE(NULLIF(moving_seconds, 0), duration_seconds) * 1000.0 / distance_m)::NUMERIC, 1)::DOUBLE PRECISION
+    END
+) STORED;
@@ -0,0 +1,62 @@
+CREATE TABLE interval_workouts (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(120) NOT NULL,
+    description TEXT,
+    format VARCHAR(20) NOT NULL,
+    -- Planned length including rests, derived from the blocks on save.
+    total_seconds INTEGER NOT NULL,
+    share_code VARCHAR(32) UNIQUE,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_interval_workouts_user_id ON interval_workouts(user_id);
+
+-- A sequence of steps repeated for a number of rounds, or as often as
+-- possible within the time cap.
+CREATE TABLE interval_blocks (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    interval_workout_id UUID NOT NULL REFERENCES interval_workouts(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    name VARCHAR(120),
+    rounds INTEGER NOT NULL DEFAULT 1,
+    time_cap_seconds INTEGER,
+    rest_between_rounds_seconds INTEGER NOT NULL DEFAULT 0,
+    rest_after_seconds INTEGER NOT NULL DEFAULT 0,
+    UNIQUE (interval_workout_id, position)
+);
+
+CREATE TABLE interval_steps (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    block_id UUID NOT NULL REFERENCES interval_blocks(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    kind VARCHAR(10) NOT NULL,
+    exercise_id UUID REFERENCES exercises(id),
+    label VARCHAR(120),
+    duration_seconds INTEGER,
+    target_reps INTEGER,
+    UNIQUE (block_id, position)
+);
+
+CREATE TABLE interval_sessions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    interval_workout_id UUID REFERENCES interval_workouts(id) ON DELETE SET NULL,
+    name VARCHAR(120) NOT NULL,
+    format VARCHAR(20) NOT NULL,
+    started_at TIMESTAMPTZ NOT NULL,
+    duration_seconds INTEGER NOT NULL,
+    notes TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_interval_sessions_user_id ON interval_sessions(user_id, started_at);
+
+CREATE TABLE interval_session_blocks (
+    session_id UUID NOT NULL REFERENCES interval_sessions(id) ON DELETE CASCADE,
+    block_position INTEGER NOT NULL,
+    rounds_completed INTEGER NOT NULL,
+    reps INTEGER,
+    PRIMARY KEY (session_id, block_position)
+);
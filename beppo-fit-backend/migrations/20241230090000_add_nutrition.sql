@@ -0,0 +1,54 @@
+-- Nutrient values are per 100 g of the food.
+CREATE TABLE foods (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    -- NULL for the shared catalog, set for a user's own foods.
+    owner_id UUID REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(200) NOT NULL,
+    brand VARCHAR(200),
+    barcode VARCHAR(32),
+    -- Where catalog entries were imported from, and their id there.
+    source VARCHAR(10) NOT NULL DEFAULT 'user',
+    source_id VARCHAR(40),
+    serving_size_g DOUBLE PRECISION,
+    serving_name VARCHAR(60),
+    energy_kcal DOUBLE PRECISION NOT NULL,
+    protein_g DOUBLE PRECISION,
+    carbohydrate_g DOUBLE PRECISION,
+    fat_g DOUBLE PRECISION,
+    fiber_g DOUBLE PRECISION,
+    sugar_g DOUBLE PRECISION,
+    saturated_fat_g DOUBLE PRECISION,
+    sodium_mg DOUBLE PRECISION,
+    potassium_mg DOUBLE PRECISION,
+    calcium_mg DOUBLE PRECISION,
+    iron_mg DOUBLE PRECISION,
+    vitamin_c_mg DOUBLE PRECISION,
+    archived_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX idx_foods_source_id ON foods(source, source_id) WHERE source_id IS NOT NULL;
+CREATE INDEX idx_foods_owner_id ON foods(owner_id);
+CREATE INDEX idx_foods_barcode ON foods(barcode);
+
+CREATE TABLE meals (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    meal_type VARCHAR(20) NOT NULL,
+    eaten_at TIMESTAMPTZ NOT NULL,
+    notes TEXT,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_meals_user_id ON meals(user_id, eaten_at);
+
+CREATE TABLE meal_items (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    meal_id UUID NOT NULL REFERENCES meals(id) ON DELETE CASCADE,
+    food_id UUID NOT NULL REFERENCES foods(id),
+    position INTEGER NOT NULL,
+    grams DOUBLE PRECISION NOT NULL,
+    UNIQUE (meal_id, position)
+);
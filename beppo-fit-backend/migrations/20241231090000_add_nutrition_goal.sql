@@ -0,0 +1,2 @@
+-- Drives the calorie target: cut, maintain or bulk.
+ALTER TABLE user_profiles ADD COLUMN nutrition_goal VARCHAR(20) NOT NULL DEFAULT 'maintain';
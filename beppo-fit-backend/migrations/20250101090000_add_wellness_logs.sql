@@ -0,0 +1,18 @@
+-- One entry per user and local day. Scales run from 1 to 5; for soreness
+-- and stress 5 is the worst, for sleep quality it is the best.
+CREATE TABLE wellness_logs (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    log_date DATE NOT NULL,
+    water_ml INTEGER,
+    sleep_minutes INTEGER,
+    sleep_quality INTEGER,
+    soreness INTEGER,
+    stress INTEGER,
+    resting_heart_rate INTEGER,
+    hrv_ms DOUBLE PRECISION,
+    notes TEXT,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (user_id, log_date)
+);
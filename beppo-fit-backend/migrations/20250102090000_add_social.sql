@@ -0,0 +1,33 @@
+ALTER TABLE workouts ADD COLUMN visibility VARCHAR(20) NOT NULL DEFAULT 'private';
+
+-- Existing workouts get their owner's default visibility.
+UPDATE workouts w SET visibility = p.default_workout_visibility
+FROM user_profiles p
+WHERE p.user_id = w.user_id;
+
+CREATE INDEX idx_workouts_user_finished ON workouts(user_id, finished_at DESC, id DESC)
+    WHERE finished_at IS NOT NULL;
+CREATE INDEX idx_personal_records_user_achieved ON personal_records(user_id, achieved_at DESC, id DESC);
+
+CREATE TABLE follows (
+    follower_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    followee_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- 'pending' until a private profile approves the request.
+    status VARCHAR(20) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    accepted_at TIMESTAMPTZ,
+    PRIMARY KEY (follower_id, followee_id),
+    CHECK (follower_id <> followee_id)
+);
+
+CREATE INDEX idx_follows_followee ON follows(followee_id, status);
+
+CREATE TABLE blocks (
+    blocker_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    blocked_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (blocker_id, blocked_id),
+    CHECK (blocker_id <> blocked_id)
+);
+
+CREATE INDEX idx_blocks_blocked ON blocks(blocked_id);
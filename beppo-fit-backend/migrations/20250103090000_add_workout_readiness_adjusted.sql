@@ -0,0 +1,5 @@
+-- Sessions lightened for low readiness are not judged against the plan.
+ALTER TABLE workouts ADD COLUMN readiness_adjusted BOOLEAN NOT NULL DEFAULT FALSE;
+
+UPDATE workouts SET readiness_adjusted = TRUE
+WHERE template_id IS NOT NULL AND notes LIKE 'Readiness %/100: lighter session suggested%';
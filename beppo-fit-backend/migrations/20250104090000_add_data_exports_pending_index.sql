@@ -0,0 +1,11 @@
+-- At most one export is prepared per user at a time. Older duplicates from
+-- before this index are given up on.
+UPDATE data_exports SET status = 'failed', completed_at = NOW()
+WHERE status = 'pending'
+  AND id NOT IN (
+      SELECT DISTINCT ON (user_id) id FROM data_exports
+      WHERE status = 'pending'
+      ORDER BY user_id, created_at DESC
+  );
+
+CREATE UNIQUE INDEX idx_data_exports_one_pending ON data_exports(user_id) WHERE status = 'pending';
@@ -0,0 +1,33 @@
+-- Meal items keep the nutrients per 100 g the food had when it was logged,
+-- so later edits or catalog re-imports do not change past meals.
+ALTER TABLE meal_items
+    ADD COLUMN energy_kcal DOUBLE PRECISION,
+    ADD COLUMN protein_g DOUBLE PRECISION,
+    ADD COLUMN carbohydrate_g DOUBLE PRECISION,
+    ADD COLUMN fat_g DOUBLE PRECISION,
+    ADD COLUMN fiber_g DOUBLE PRECISION,
+    ADD COLUMN sugar_g DOUBLE PRECISION,
+    ADD COLUMN saturated_fat_g DOUBLE PRECISION,
+    ADD COLUMN sodium_mg DOUBLE PRECISION,
+    ADD COLUMN potassium_mg DOUBLE PRECISION,
+    ADD COLUMN calcium_mg DOUBLE PRECISION,
+    ADD COLUMN iron_mg DOUBLE PRECISION,
+    ADD COLUMN vitamin_c_mg DOUBLE PRECISION;
+
+UPDATE meal_items mi SET
+    energy_kcal = f.energy_kcal,
+    protein_g = f.protein_g,
+    carbohydrate_g = f.carbohydrate_g,
+    fat_g = f.fat_g,
+    fiber_g = f.fiber_g,
+    sugar_g = f.sugar_g,
+    saturated_fat_g = f.saturated_fat_g,
+    sodium_mg = f.sodium_mg,
+    potassium_mg = f.potassium_mg,
+    calcium_mg = f.calcium_mg,
+    iron_mg = f.iron_mg,
+    vitamin_c_mg = f.vitamin_c_mg
+FROM foods f
+WHERE f.id = mi.food_id;
+
+ALTER TABLE meal_items ALTER COLUMN energy_kcal SET NOT NULL;
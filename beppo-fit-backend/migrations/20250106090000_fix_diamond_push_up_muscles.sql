@@ -0,0 +1,4 @@
+-- The triceps are the primary muscle of the diamond push-up, not also a secondary one.
+UPDATE exercises
+SET secondary_muscles = array_remove(secondary_muscles, 'triceps'), updated_at = NOW()
+WHERE slug = 'diamond-push-up' AND owner_id IS NULL;
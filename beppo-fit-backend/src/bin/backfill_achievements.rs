@@ -0,0 +1,26 @@
+//! Unlocks achievements earned by history logged before they existed.
+//! Run once after deploying new achievements; repeated runs change nothing.
+//!
+//! Usage:
+//!   backfill_achievements
+
+use beppo_fit_backend::services::achievement::backfill_achievements;
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::INFO)
+        .init();
+
+    dotenvy::dotenv().ok();
+
+    let pool = beppo_fit_backend::db::init_pool().await;
+
+    match backfill_achievements(&pool).await {
+        Ok(unlocked) => tracing::info!("Backfilled {} achievements", unlocked),
+        Err(e) => {
+            tracing::error!("Achievement backfill failed: {:?}", e);
+            std::process::exit(1);
+        }
+    }
+}
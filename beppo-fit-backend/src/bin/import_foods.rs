@@ -0,0 +1,50 @@
+//! Seeds the shared food catalog from dump files on local disk.
+//!
+//! Usage:
+//!   import_foods off <en.openfoodfacts.org.products.csv>
+//!   import_foods usda <FoodData Central CSV directory>
+
+use beppo_fit_backend::services::food_import::{import_open_food_facts, import_usda};
+use std::path::PathBuf;
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::INFO)
+        .init();
+
+    dotenvy::dotenv().ok();
+
+    let args: Vec<String> = std::env::args().collect();
+    let (source, path) = match args.as_slice() {
+        [_, source, path] => (source.as_str(), PathBuf::from(path)),
+        _ => {
+            eprintln!("Usage: import_foods <off|usda> <path>");
+            std::process::exit(2);
+        }
+    };
+
+    // Runs the migrations, so a fresh database can be seeded directly
+    let pool = beppo_fit_backend::db::init_pool().await;
+
+    let result = match source {
+        "off" => import_open_food_facts(&pool, &path).await,
+        "usda" => import_usda(&pool, &path).await,
+        _ => {
+            eprintln!("Unknown source '{}'. Allowed: off, usda", source);
+            std::process::exit(2);
+        }
+    };
+
+    match result {
+        Ok(summary) => tracing::info!(
+            "Imported {} foods, skipped {} rows without a name or energy value",
+            summary.imported,
+            summary.skipped
+        ),
+        Err(e) => {
+            tracing::error!("Food import failed: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
@@ -10,7 +10,11 @@ pub enum AppError {
     InternalServerError,
     BadRequest(String),
     Unauthorized(String),
+    Forbidden(String),
     Conflict(String),
+    NotFound(String),
+    PayloadTooLarge(String),
+    StorageError(String),
     SqlxError(sqlx::Error),
     PasswordHashError(argon2::password_hash::Error),
     JwtError(jsonwebtoken::errors::Error),
@@ -24,7 +28,14 @@ impl IntoResponse for AppError {
             }
             AppError::BadRequest(ref msg) => (StatusCode::BAD_REQUEST, msg.as_str()),
             AppError::Unauthorized(ref msg) => (StatusCode::UNAUTHORIZED, msg.as_str()),
+            AppError::Forbidden(ref msg) => (StatusCode::FORBIDDEN, msg.as_str()),
             AppError::Conflict(ref msg) => (StatusCode::CONFLICT, msg.as_str()),
+            AppError::NotFound(ref msg) => (StatusCode::NOT_FOUND, msg.as_str()),
+            AppError::PayloadTooLarge(ref msg) => (StatusCode::PAYLOAD_TOO_LARGE, msg.as_str()),
+            AppError::StorageError(ref e) => {
+                tracing::error!("Storage error: {}", e);
+                (StatusCode::INTERNAL_SERVER_ERROR, "Storage Error")
+            }
             AppError::SqlxError(ref e) => {
                 tracing::error!("Database error: {:?}", e);
                 (StatusCode::INTERNAL_SERVER_ERROR, "Database Error")
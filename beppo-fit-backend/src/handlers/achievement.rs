@@ -0,0 +1,17 @@
+use axum::{extract::State, Json};
+
+use crate::{
+    error::AppError, handlers::auth::Claims, models::achievement::AchievementsResponse,
+    services::achievement::achievement_overview, state::AppState,
+};
+
+/// Every achievement with the user's progress towards it, and their streaks.
+pub async fn list_achievements(
+    State(state): State<AppState>,
+    claims: Claims,
+) -> Result<Json<AchievementsResponse>, AppError> {
+    let user_id = claims.user_id()?;
+    let mut conn = state.pool.acquire().await?;
+
+    Ok(Json(achievement_overview(&mut conn, user_id).await?))
+}
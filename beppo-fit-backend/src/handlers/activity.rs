@@ -0,0 +1,607 @@
+use axum::{
+    extract::{multipart::Field, Multipart, Path, Query, State},
+    http::{
+        header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+        StatusCode,
+    },
+    response::IntoResponse,
+    Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    error::AppError,
+    handlers::auth::Claims,
+    models::activity::{
+        Activity, ActivityDetail, ActivityExportQuery, ActivityLap, ActivityPeriodTotals,
+        ActivityQuery, ActivitySummary, ActivitySummaryQuery, ActivityTotals,
+        CreateActivityRequest, TrackPoint, UpdateActivityRequest, ACTIVITY_OTHER, ACTIVITY_TYPES,
+    },
+    services::{
+        activity::{load_activity_detail, load_track},
+        activity_export::{render_gpx, render_tcx, GPX_CONTENT_TYPE, TCX_CONTENT_TYPE},
+        activity_import::{parse_activity_file, MAX_ACTIVITY_FILE_BYTES},
+        profile::ensure_profile,
+        stats::resolve_range,
+        track::{encode_track, summarize_track},
+    },
+    state::AppState,
+};
+
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 200;
+/// Recordings starting this close to an existing activity are duplicates.
+const DUPLICATE_WINDOW_SECONDS: i64 = 60;
+
+pub(crate) fn validate_activity_type(activity_type: &str) -> Result<(), AppError> {
+    if ACTIVITY_TYPES.contains(&activity_type) {
+        Ok(())
+    } else {
+        Err(AppError::BadRequest(format!(
+            "Activity type must be one of: {}",
+            ACTIVITY_TYPES.join(", ")
+        )))
+    }
+}
+
+/// Turns a second activity at the same start into a readable conflict.
+fn start_conflict(e: sqlx::Error) -> AppError {
+    match e {
+        sqlx::Error::Database(ref db) if db.is_unique_violation() => {
+            AppError::Conflict("Another activity starts at the same time".into())
+        }
+        e => AppError::SqlxError(e),
+    }
+}
+
+fn validate_moving_time(
+    duration_seconds: i32,
+    moving_seconds: Option<i32>,
+) -> Result<(), AppError> {
+    if moving_seconds.is_some_and(|m| m > duration_seconds) {
+        return Err(AppError::BadRequest(
+            "Moving time cannot exceed the duration".into(),
+        ));
+    }
+    Ok(())
+}
+
+async fn find_own_activity(
+    conn: &mut sqlx::PgConnection,
+    user_id: Uuid,
+    activity_id: Uuid,
+) -> Result<Activity, AppError> {
+    sqlx::query_as!(
+        Activity,
+        "SELECT * FROM activities WHERE id = $1 AND user_id = $2",
+        activity_id,
+        user_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Activity not found".into()))
+}
+
+/// The parts of an activity upload we care about.
+#[derive(Default)]
+struct ActivityUpload {
+    file: Option<Vec<u8>>,
+    activity_type: Option<String>,
+    name: Option<String>,
+}
+
+async fn read_text(field: Field<'_>, name: &str) -> Result<String, AppError> {
+    field
+        .text()
+        .await
+        .map(|text| text.trim().to_string())
+        .map_err(|_| AppError::BadRequest(format!("Invalid {}", name)))
+}
+
+async fn read_activity_upload(mut multipart: Multipart) -> Result<ActivityUpload, AppError> {
+    let mut upload = ActivityUpload::default();
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::BadRequest(e.body_text()))?
+    {
+        match field.name() {
+            Some("file") => {
+                let bytes = field.bytes().await.map_err(|e| {
+                    if e.status() == StatusCode::PAYLOAD_TOO_LARGE {
+                        AppError::PayloadTooLarge("File is too large".into())
+                    } else {
+                        AppError::BadRequest(e.body_text())
+                    }
+                })?;
+                if bytes.len() > MAX_ACTIVITY_FILE_BYTES {
+                    return Err(AppError::PayloadTooLarge(format!(
+                        "File must be at most {} MB",
+                        MAX_ACTIVITY_FILE_BYTES / 1024 / 1024
+                    )));
+                }
+                upload.file = Some(bytes.to_vec());
+            }
+            Some("activity_type") => {
+                let activity_type = read_text(field, "activity_type").await?;
+                validate_activity_type(&activity_type)?;
+                upload.activity_type = Some(activity_type);
+            }
+            Some("name") => {
+                let name = read_text(field, "name").await?;
+                if name.is_empty() || name.chars().count() > 120 {
+                    return Err(AppError::BadRequest("Name must be 1-120 characters".into()));
+                }
+                upload.name = Some(name);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(upload)
+}
+
+/// Imports a GPX, TCX or FIT recording as a new activity.
+pub async fn import_activity(
+    State(state): State<AppState>,
+    claims: Claims,
+    multipart: Multipart,
+) -> Result<(StatusCode, Json<ActivityDetail>), AppError> {
+    let user_id = claims.user_id()?;
+    let upload = read_activity_upload(multipart).await?;
+    let file = upload
+        .file
+        .ok_or(AppError::BadRequest("Missing file".into()))?;
+
+    let (parsed, summary, track) = tokio::task::spawn_blocking(move || {
+        let parsed = parse_activity_file(&file)?;
+        let summary = summarize_track(&parsed.points).ok_or(AppError::InternalServerError)?;
+        let track = encode_track(&parsed.points);
+        Ok::<_, AppError>((parsed, summary, track))
+    })
+    .await
+    .map_err(|_| AppError::InternalServerError)??;
+
+    let mut tx = state.pool.begin().await?;
+
+    let window = chrono::Duration::seconds(DUPLICATE_WINDOW_SECONDS);
+    let duplicate = sqlx::query_scalar!(
+        r#"
+        SELECT id FROM activities
+        WHERE user_id = $1 AND started_at BETWEEN $2 AND $3
+        LIMIT 1
+        "#,
+        user_id,
+        summary.started_at - window,
+        summary.started_at + window
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+    if let Some(id) = duplicate {
+        return Err(AppError::Conflict(format!(
+            "This recording was already imported as activity {}",
+            id
+        )));
+    }
+
+    let name = upload.name.or(parsed.name);
+    let activity_type = upload
+        .activity_type
+        .as_deref()
+        .or(parsed.sport)
+        .unwrap_or(ACTIVITY_OTHER);
+    let activity = sqlx::query_as!(
+        Activity,
+        r#"
+        INSERT INTO activities (
+            user_id, activity_type, name, started_at, duration_seconds, moving_seconds, distance_m,
+            elevation_gain_m, elevation_loss_m, avg_heart_rate, max_heart_rate, avg_cadence, source_format
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+        RETURNING *
+        "#,
+        user_id,
+        activity_type,
+        name.map(|n| n.chars().take(120).collect::<String>()),
+        summary.started_at,
+        summary.duration_seconds,
+        summary.moving_seconds,
+        summary.distance_m,
+        summary.elevation_gain_m,
+        summary.elevation_loss_m,
+        summary.avg_heart_rate,
+        summary.max_heart_rate,
+        summary.avg_cadence,
+        parsed.format
+    )
+    .fetch_one(&mut *tx)
+    .await
+    // The window check above cannot stop two uploads of the same file racing.
+    .map_err(start_conflict)?;
+
+    for (index, lap) in parsed.laps.iter().enumerate() {
+        sqlx::query!(
+            r#"
+            INSERT INTO activity_laps (activity_id, lap_index, started_at, duration_seconds, distance_m, avg_heart_rate)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+            activity.id,
+            index as i32,
+            lap.started_at,
+            lap.duration_seconds,
+            lap.distance_m,
+            lap.avg_heart_rate
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+    sqlx::query!(
+        "INSERT INTO activity_tracks (activity_id, point_count, data) VALUES ($1, $2, $3)",
+        activity.id,
+        parsed.points.len() as i32,
+        track
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let profile = ensure_profile(&mut tx, user_id).await?;
+    let detail = load_activity_detail(&mut tx, activity, &profile).await?;
+    tx.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(detail)))
+}
+
+/// Logs an activity by hand, e.g. a treadmill run or a session without a recording.
+pub async fn create_activity(
+    State(state): State<AppState>,
+    claims: Claims,
+    Json(payload): Json<CreateActivityRequest>,
+) -> Result<(StatusCode, Json<ActivityDetail>), AppError> {
+    let user_id = claims.user_id()?;
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+    validate_activity_type(&payload.activity_type)?;
+    validate_moving_time(payload.duration_seconds, payload.moving_seconds)?;
+
+    let mut conn = state.pool.acquire().await?;
+    let activity = sqlx::query_as!(
+        Activity,
+        r#"
+        INSERT INTO activities (
+            user_id, activity_type, name, started_at, duration_seconds, moving_seconds, distance_m,
+            elevation_gain_m, elevation_loss_m, avg_heart_rate, max_heart_rate, avg_cadence, notes
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+        RETURNING *
+        "#,
+        user_id,
+        payload.activity_type,
+        payload.name,
+        payload.started_at,
+        payload.duration_seconds,
+        payload.moving_seconds,
+        payload.distance_m,
+        payload.elevation_gain_m,
+        payload.elevation_loss_m,
+        payload.avg_heart_rate,
+        payload.max_heart_rate,
+        payload.avg_cadence,
+        payload.notes
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(start_conflict)?;
+
+    let profile = ensure_profile(&mut conn, user_id).await?;
+    let detail = load_activity_detail(&mut conn, activity, &profile).await?;
+
+    Ok((StatusCode::CREATED, Json(detail)))
+}
+
+pub async fn update_activity(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(activity_id): Path<Uuid>,
+    Json(payload): Json<UpdateActivityRequest>,
+) -> Result<Json<ActivityDetail>, AppError> {
+    let user_id = claims.user_id()?;
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+    if let Some(activity_type) = &payload.activity_type {
+        validate_activity_type(activity_type)?;
+    }
+
+    let mut conn = state.pool.acquire().await?;
+    let current = find_own_activity(&mut conn, user_id, activity_id).await?;
+    if current.source_format.is_some() && payload.changes_metrics() {
+        return Err(AppError::BadRequest(
+            "The metrics of a recorded activity come from its track; only the type, name and notes can change".into(),
+        ));
+    }
+    validate_moving_time(
+        payload.duration_seconds.unwrap_or(current.duration_seconds),
+        payload.moving_seconds.or(current.moving_seconds),
+    )?;
+
+    let activity = sqlx::query_as!(
+        Activity,
+        r#"
+        UPDATE activities SET
+            activity_type = COALESCE($3, activity_type),
+            name = COALESCE($4, name),
+            started_at = COALESCE($5, started_at),
+            duration_seconds = COALESCE($6, duration_seconds),
+            moving_seconds = COALESCE($7, moving_seconds),
+            distance_m = COALESCE($8, distance_m),
+            elevation_gain_m = COALESCE($9, elevation_gain_m),
+            elevation_loss_m = COALESCE($10, elevation_loss_m),
+            avg_heart_rate = COALESCE($11, avg_heart_rate),
+            max_heart_rate = COALESCE($12, max_heart_rate),
+            avg_cadence = COALESCE($13, avg_cadence),
+            notes = COALESCE($14, notes),
+            updated_at = NOW()
+        WHERE id = $1 AND user_id = $2
+        RETURNING *
+        "#,
+        activity_id,
+        user_id,
+        payload.activity_type,
+        payload.name,
+        payload.started_at,
+        payload.duration_seconds,
+        payload.moving_seconds,
+        payload.distance_m,
+        payload.elevation_gain_m,
+        payload.elevation_loss_m,
+        payload.avg_heart_rate,
+        payload.max_heart_rate,
+        payload.avg_cadence,
+        payload.notes
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(start_conflict)?;
+
+    let profile = ensure_profile(&mut conn, user_id).await?;
+
+    Ok(Json(
+        load_activity_detail(&mut conn, activity, &profile).await?,
+    ))
+}
+
+/// Distance, time and climbing per activity type, in total and per period.
+pub async fn activity_summary(
+    State(state): State<AppState>,
+    claims: Claims,
+    Query(query): Query<ActivitySummaryQuery>,
+) -> Result<Json<ActivitySummary>, AppError> {
+    let user_id = claims.user_id()?;
+    if let Some(activity_type) = &query.activity_type {
+        validate_activity_type(activity_type)?;
+    }
+    let range = resolve_range(
+        &state.pool,
+        user_id,
+        query.from,
+        query.to,
+        query.period.as_deref(),
+    )
+    .await?;
+
+    // Pace only over activities with a distance, on the same time basis as
+    // activities.pace_seconds_per_km.
+    let totals = sqlx::query_as!(
+        ActivityTotals,
+        r#"
+        SELECT
+            activity_type AS "activity_type!",
+            COUNT(*) AS "activities!",
+            COALESCE(SUM(distance_m), 0) AS "distance_m!",
+            COALESCE(SUM(duration_seconds), 0)::BIGINT AS "duration_seconds!",
+            COALESCE(SUM(COALESCE(moving_seconds, duration_seconds)), 0)::BIGINT AS "moving_seconds!",
+            COALESCE(SUM(elevation_gain_m), 0) AS "elevation_gain_m!",
+            ROUND((SUM(COALESCE(NULLIF(moving_seconds, 0), duration_seconds)) FILTER (WHERE distance_m > 0)
+                * 1000.0 / NULLIF(SUM(distance_m) FILTER (WHERE distance_m > 0), 0))::NUMERIC, 1)::DOUBLE PRECISION
+                AS pace_seconds_per_km
+        FROM activities
+        WHERE user_id = $1 AND started_at >= $2 AND started_at < $3
+          AND ($4::VARCHAR IS NULL OR activity_type = $4)
+        GROUP BY activity_type
+        ORDER BY activity_type
+        "#,
+        user_id,
+        range.start,
+        range.end,
+        query.activity_type
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    let periods = sqlx::query_as!(
+        ActivityPeriodTotals,
+        r#"
+        SELECT
+            date_trunc($5, started_at AT TIME ZONE $4)::DATE AS "period_start!",
+            activity_type AS "activity_type!",
+            COUNT(*) AS "activities!",
+            COALESCE(SUM(distance_m), 0) AS "distance_m!",
+            COALESCE(SUM(duration_seconds), 0)::BIGINT AS "duration_seconds!",
+            COALESCE(SUM(COALESCE(moving_seconds, duration_seconds)), 0)::BIGINT AS "moving_seconds!",
+            COALESCE(SUM(elevation_gain_m), 0) AS "elevation_gain_m!",
+            ROUND((SUM(COALESCE(NULLIF(moving_seconds, 0), duration_seconds)) FILTER (WHERE distance_m > 0)
+                * 1000.0 / NULLIF(SUM(distance_m) FILTER (WHERE distance_m > 0), 0))::NUMERIC, 1)::DOUBLE PRECISION
+                AS pace_seconds_per_km
+        FROM activities
+        WHERE user_id = $1 AND started_at >= $2 AND started_at < $3
+          AND ($6::VARCHAR IS NULL OR activity_type = $6)
+        GROUP BY 1, 2
+        ORDER BY 1, 2
+        "#,
+        user_id,
+        range.start,
+        range.end,
+        range.timezone,
+        range.period,
+        query.activity_type
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(ActivitySummary { totals, periods }))
+}
+
+pub async fn list_activities(
+    State(state): State<AppState>,
+    claims: Claims,
+    Query(query): Query<ActivityQuery>,
+) -> Result<Json<Vec<Activity>>, AppError> {
+    let user_id = claims.user_id()?;
+    if let Some(activity_type) = &query.activity_type {
+        validate_activity_type(activity_type)?;
+    }
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    let activities = sqlx::query_as!(
+        Activity,
+        r#"
+        SELECT * FROM activities
+        WHERE user_id = $1
+          AND ($2::TIMESTAMPTZ IS NULL OR started_at >= $2)
+          AND ($3::TIMESTAMPTZ IS NULL OR started_at < $3)
+          AND ($4::VARCHAR IS NULL OR activity_type = $4)
+        ORDER BY started_at DESC
+        LIMIT $5 OFFSET $6
+        "#,
+        user_id,
+        query.from,
+        query.to,
+        query.activity_type,
+        limit,
+        offset
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(activities))
+}
+
+pub async fn get_activity(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(activity_id): Path<Uuid>,
+) -> Result<Json<ActivityDetail>, AppError> {
+    let user_id = claims.user_id()?;
+    let mut conn = state.pool.acquire().await?;
+
+    let activity = find_own_activity(&mut conn, user_id, activity_id).await?;
+    let profile = ensure_profile(&mut conn, user_id).await?;
+
+    Ok(Json(
+        load_activity_detail(&mut conn, activity, &profile).await?,
+    ))
+}
+
+/// Every recorded sample of an activity, oldest first.
+pub async fn get_activity_track(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(activity_id): Path<Uuid>,
+) -> Result<Json<Vec<TrackPoint>>, AppError> {
+    let user_id = claims.user_id()?;
+    let mut conn = state.pool.acquire().await?;
+
+    find_own_activity(&mut conn, user_id, activity_id).await?;
+    let track = load_track(&mut conn, activity_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Activity has no recorded track".into()))?;
+
+    Ok(Json(track))
+}
+
+/// Downloads an activity as a GPX or TCX file.
+pub async fn export_activity(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(activity_id): Path<Uuid>,
+    Query(query): Query<ActivityExportQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id = claims.user_id()?;
+    let mut conn = state.pool.acquire().await?;
+
+    let activity = find_own_activity(&mut conn, user_id, activity_id).await?;
+    let points = load_track(&mut conn, activity_id)
+        .await?
+        .unwrap_or_default();
+
+    let (content_type, body) = match query.format.as_str() {
+        "gpx" => {
+            if !points.iter().any(|p| p.lat.is_some() && p.lon.is_some()) {
+                return Err(AppError::BadRequest(
+                    "Activity has no GPS positions; export it as TCX instead".into(),
+                ));
+            }
+            (GPX_CONTENT_TYPE, render_gpx(&activity, &points))
+        }
+        "tcx" => {
+            let laps = sqlx::query_as!(
+                ActivityLap,
+                "SELECT * FROM activity_laps WHERE activity_id = $1 ORDER BY lap_index",
+                activity_id
+            )
+            .fetch_all(&mut *conn)
+            .await?;
+            (TCX_CONTENT_TYPE, render_tcx(&activity, &laps, &points))
+        }
+        _ => {
+            return Err(AppError::BadRequest(
+                "Format must be one of: gpx, tcx".into(),
+            ))
+        }
+    };
+
+    let filename = format!(
+        "activity-{}.{}",
+        activity.started_at.format("%Y%m%d-%H%M"),
+        query.format
+    );
+    Ok((
+        [
+            (CONTENT_TYPE, content_type.to_string()),
+            (
+                CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        body,
+    ))
+}
+
+pub async fn delete_activity(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(activity_id): Path<Uuid>,
+) -> Result<Json<&'static str>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let result = sqlx::query!(
+        "DELETE FROM activities WHERE id = $1 AND user_id = $2",
+        activity_id,
+        user_id
+    )
+    .execute(&state.pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Activity not found".into()));
+    }
+
+    Ok(Json("Activity deleted successfully"))
+}
@@ -12,13 +12,19 @@ use axum::{
 use jsonwebtoken::{decode, DecodingKey, Validation};
 use jsonwebtoken::{encode, EncodingKey, Header};
 use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
 use std::env;
 use uuid::Uuid;
 use validator::Validate;
 
 use crate::{
     error::AppError,
-    models::user::{AuthResponse, LoginRequest, RegisterRequest, User},
+    handlers::media::delete_media_files,
+    models::{
+        media::Media,
+        user::{AuthResponse, LoginRequest, RegisterRequest, User},
+    },
+    services::profile::ensure_profile,
     state::AppState,
 };
 
@@ -28,6 +34,27 @@ pub struct Claims {
     pub exp: usize,
 }
 
+impl Claims {
+    pub fn user_id(&self) -> Result<Uuid, AppError> {
+        Uuid::parse_str(&self.sub)
+            .map_err(|_| AppError::Unauthorized("Invalid User ID in token".into()))
+    }
+
+    /// Returns the user id if the user is an administrator.
+    pub async fn require_admin(&self, pool: &PgPool) -> Result<Uuid, AppError> {
+        let user_id = self.user_id()?;
+        let is_admin = sqlx::query_scalar!("SELECT is_admin FROM users WHERE id = $1", user_id)
+            .fetch_optional(pool)
+            .await?
+            .unwrap_or(false);
+
+        if !is_admin {
+            return Err(AppError::Forbidden("Administrator access required".into()));
+        }
+        Ok(user_id)
+    }
+}
+
 #[derive(Deserialize)]
 pub struct VerifyTokenQuery {
     token: String,
@@ -118,8 +145,13 @@ pub async fn register(
                 tracing::error!("Failed to resend verification email: {:?}", e);
             }
 
+            let profile = ensure_profile(&mut *state.pool.acquire().await?, user.id).await?;
             let token = generate_token(&user.id.to_string())?;
-            return Ok(Json(AuthResponse { token, user }));
+            return Ok(Json(AuthResponse {
+                token,
+                user,
+                profile,
+            }));
         }
     }
 
@@ -154,9 +186,14 @@ pub async fn register(
         tracing::error!("Failed to send verification email: {:?}", e);
     }
 
+    let profile = ensure_profile(&mut *state.pool.acquire().await?, user.id).await?;
     let token = generate_token(&user.id.to_string())?;
 
-    Ok(Json(AuthResponse { token, user }))
+    Ok(Json(AuthResponse {
+        token,
+        user,
+        profile,
+    }))
 }
 
 pub async fn login(
@@ -184,9 +221,14 @@ pub async fn login(
     // Removed check for is_verified to allow account management access
     // if !user.is_verified { ... }
 
+    let profile = ensure_profile(&mut *state.pool.acquire().await?, user.id).await?;
     let token = generate_token(&user.id.to_string())?;
 
-    Ok(Json(AuthResponse { token, user }))
+    Ok(Json(AuthResponse {
+        token,
+        user,
+        profile,
+    }))
 }
 
 pub async fn verify_email(
@@ -293,12 +335,19 @@ pub async fn delete_account(
     State(state): State<AppState>,
     claims: Claims,
 ) -> Result<Json<&'static str>, AppError> {
-    let user_id = Uuid::parse_str(&claims.sub)
-        .map_err(|_| AppError::Unauthorized("Invalid User ID in token".into()))?;
+    let user_id = claims.user_id()?;
+
+    let media = sqlx::query_as!(Media, "SELECT * FROM media WHERE user_id = $1", user_id)
+        .fetch_all(&state.pool)
+        .await?;
 
     sqlx::query!("DELETE FROM users WHERE id = $1", user_id)
         .execute(&state.pool)
         .await?;
 
+    for item in &media {
+        delete_media_files(&state, item).await;
+    }
+
     Ok(Json("Account deleted successfully"))
 }
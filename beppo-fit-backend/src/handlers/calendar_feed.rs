@@ -0,0 +1,272 @@
+use axum::{
+    extract::{Path, State},
+    http::{
+        header::{CACHE_CONTROL, CONTENT_DISPOSITION, CONTENT_TYPE},
+        StatusCode,
+    },
+    response::IntoResponse,
+    Json,
+};
+use chrono::{Duration, NaiveDate};
+use sqlx::PgConnection;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    handlers::auth::Claims,
+    models::{
+        calendar_feed::{CalendarFeed, CalendarFeedResponse},
+        profile::UserProfile,
+        schedule::{CalendarEntry, SESSION_SKIPPED},
+    },
+    services::{
+        ical::{render_calendar, EventTime, IcsEvent, ICS_CONTENT_TYPE},
+        profile::ensure_profile,
+        schedule::calendar_entries,
+    },
+    state::AppState,
+};
+
+/// How much of the calendar the feed covers around today.
+const FEED_DAYS_BEHIND: i64 = 30;
+const FEED_DAYS_AHEAD: i64 = 180;
+/// Length of the calendar event for sessions with a planned time.
+const SESSION_MINUTES: i64 = 60;
+
+/// What each scheduled template or program day contains, keyed by its id.
+async fn event_descriptions(
+    conn: &mut PgConnection,
+    entries: &[CalendarEntry],
+) -> Result<HashMap<Uuid, String>, AppError> {
+    let template_ids: Vec<Uuid> = entries.iter().filter_map(|e| e.template_id).collect();
+    let program_day_ids: Vec<Uuid> = entries.iter().filter_map(|e| e.program_day_id).collect();
+
+    let program_days = sqlx::query!(
+        r#"
+        SELECT d.id, d.template_id, d.week_number, d.day_number, p.name AS program_name
+        FROM program_days d
+        JOIN programs p ON p.id = d.program_id
+        WHERE d.id = ANY($1)
+        "#,
+        &program_day_ids
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let all_template_ids: Vec<Uuid> = template_ids
+        .iter()
+        .copied()
+        .chain(program_days.iter().map(|d| d.template_id))
+        .collect();
+    let exercises: HashMap<Uuid, String> = sqlx::query!(
+        r#"
+        SELECT te.template_id, string_agg(e.name, ', ' ORDER BY te.position) AS "exercises!"
+        FROM template_exercises te
+        JOIN exercises e ON e.id = te.exercise_id
+        WHERE te.template_id = ANY($1)
+        GROUP BY te.template_id
+        "#,
+        &all_template_ids
+    )
+    .fetch_all(&mut *conn)
+    .await?
+    .into_iter()
+    .map(|row| (row.template_id, row.exercises))
+    .collect();
+
+    let mut descriptions: HashMap<Uuid, String> = template_ids
+        .into_iter()
+        .filter_map(|id| exercises.get(&id).map(|list| (id, list.clone())))
+        .collect();
+    for day in program_days {
+        let mut description = format!(
+            "{}, week {}, day {}",
+            day.program_name, day.week_number, day.day_number
+        );
+        if let Some(list) = exercises.get(&day.template_id) {
+            description.push('\n');
+            description.push_str(list);
+        }
+        descriptions.insert(day.id, description);
+    }
+
+    Ok(descriptions)
+}
+
+fn session_event(
+    entry: &CalendarEntry,
+    profile: &UserProfile,
+    descriptions: &HashMap<Uuid, String>,
+) -> IcsEvent {
+    let time = match entry.time {
+        Some(time) => EventTime::Timed {
+            start: profile.local_instant(entry.date.and_time(time)),
+            duration: Duration::minutes(SESSION_MINUTES),
+        },
+        None => EventTime::AllDay(entry.date),
+    };
+
+    IcsEvent {
+        uid: format!(
+            "{}-{}@beppofit",
+            entry.schedule_id,
+            entry.date.format("%Y%m%d")
+        ),
+        summary: entry.title.clone(),
+        description: entry
+            .template_id
+            .or(entry.program_day_id)
+            .and_then(|id| descriptions.get(&id).cloned()),
+        time,
+        status: (entry.status == SESSION_SKIPPED).then_some("CANCELLED"),
+    }
+}
+
+async fn render_entries(
+    conn: &mut PgConnection,
+    profile: &UserProfile,
+    entries: &[CalendarEntry],
+) -> Result<String, AppError> {
+    let descriptions = event_descriptions(conn, entries).await?;
+    let events: Vec<IcsEvent> = entries
+        .iter()
+        .map(|entry| session_event(entry, profile, &descriptions))
+        .collect();
+
+    Ok(render_calendar(
+        "BeppoFit workouts",
+        &profile.timezone,
+        &events,
+    ))
+}
+
+pub async fn get_calendar_feed(
+    State(state): State<AppState>,
+    claims: Claims,
+) -> Result<Json<CalendarFeedResponse>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let feed = sqlx::query_as!(
+        CalendarFeed,
+        "SELECT * FROM calendar_feeds WHERE user_id = $1",
+        user_id
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Calendar feed not enabled".into()))?;
+
+    Ok(Json(feed.into()))
+}
+
+/// Enables the feed, or replaces its URL so the previous one stops working.
+pub async fn create_calendar_feed(
+    State(state): State<AppState>,
+    claims: Claims,
+) -> Result<(StatusCode, Json<CalendarFeedResponse>), AppError> {
+    let user_id = claims.user_id()?;
+    let token = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+
+    let feed = sqlx::query_as!(
+        CalendarFeed,
+        r#"
+        INSERT INTO calendar_feeds (user_id, token)
+        VALUES ($1, $2)
+        ON CONFLICT (user_id) DO UPDATE SET token = EXCLUDED.token, created_at = NOW()
+        RETURNING *
+        "#,
+        user_id,
+        token
+    )
+    .fetch_one(&state.pool)
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(feed.into())))
+}
+
+pub async fn revoke_calendar_feed(
+    State(state): State<AppState>,
+    claims: Claims,
+) -> Result<Json<&'static str>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let result = sqlx::query!("DELETE FROM calendar_feeds WHERE user_id = $1", user_id)
+        .execute(&state.pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Calendar feed not enabled".into()));
+    }
+
+    Ok(Json("Calendar feed revoked"))
+}
+
+/// The subscribed calendar. Authorized by the secret token in the URL, since
+/// calendar apps cannot send an `Authorization` header.
+pub async fn serve_calendar_feed(
+    State(state): State<AppState>,
+    Path(token): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let token = token.strip_suffix(".ics").unwrap_or(&token);
+    let mut conn = state.pool.acquire().await?;
+
+    let user_id = sqlx::query_scalar!("SELECT user_id FROM calendar_feeds WHERE token = $1", token)
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Calendar feed not found".into()))?;
+    let profile = ensure_profile(&mut conn, user_id).await?;
+    let today = profile.today();
+
+    let entries = calendar_entries(
+        &mut conn,
+        user_id,
+        today - Duration::days(FEED_DAYS_BEHIND),
+        today + Duration::days(FEED_DAYS_AHEAD),
+        today,
+    )
+    .await?;
+    let calendar = render_entries(&mut conn, &profile, &entries).await?;
+
+    Ok((
+        [
+            (CONTENT_TYPE, ICS_CONTENT_TYPE),
+            (CACHE_CONTROL, "private, max-age=900"),
+        ],
+        calendar,
+    ))
+}
+
+/// A single planned session as an `.ics` file to import.
+pub async fn download_session_ics(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path((schedule_id, date)): Path<(Uuid, NaiveDate)>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id = claims.user_id()?;
+    let mut conn = state.pool.acquire().await?;
+
+    let profile = ensure_profile(&mut conn, user_id).await?;
+    let entries: Vec<CalendarEntry> =
+        calendar_entries(&mut conn, user_id, date, date, profile.today())
+            .await?
+            .into_iter()
+            .filter(|e| e.schedule_id == schedule_id)
+            .collect();
+    if entries.is_empty() {
+        return Err(AppError::NotFound(format!(
+            "Schedule has no session on {}",
+            date
+        )));
+    }
+    let calendar = render_entries(&mut conn, &profile, &entries).await?;
+
+    Ok((
+        [
+            (CONTENT_TYPE, ICS_CONTENT_TYPE.to_string()),
+            (
+                CONTENT_DISPOSITION,
+                format!("attachment; filename=\"workout-{}.ics\"", date),
+            ),
+        ],
+        calendar,
+    ))
+}
@@ -0,0 +1,257 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    error::AppError,
+    handlers::auth::Claims,
+    models::exercise::{
+        slugify, CreateExerciseRequest, Exercise, ExerciseQuery, UpdateExerciseRequest, CATEGORIES,
+        EQUIPMENT, MUSCLE_GROUPS,
+    },
+    state::AppState,
+};
+
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 200;
+
+fn check_filter(name: &str, value: &Option<String>, allowed: &[&str]) -> Result<(), AppError> {
+    match value {
+        Some(v) if !allowed.contains(&v.as_str()) => Err(AppError::BadRequest(format!(
+            "Unknown {} '{}'. Allowed: {}",
+            name,
+            v,
+            allowed.join(", ")
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Turns a slug collision into a readable conflict instead of a database error.
+fn slug_conflict(e: sqlx::Error) -> AppError {
+    match e {
+        sqlx::Error::Database(ref db) if db.is_unique_violation() => {
+            AppError::Conflict("An exercise with this name already exists".into())
+        }
+        e => AppError::SqlxError(e),
+    }
+}
+
+pub async fn list_exercises(
+    State(state): State<AppState>,
+    claims: Claims,
+    Query(query): Query<ExerciseQuery>,
+) -> Result<Json<Vec<Exercise>>, AppError> {
+    let user_id = claims.user_id()?;
+
+    check_filter("muscle", &query.muscle, MUSCLE_GROUPS)?;
+    check_filter("equipment", &query.equipment, EQUIPMENT)?;
+    check_filter("category", &query.category, CATEGORIES)?;
+
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+    let offset = query.offset.unwrap_or(0).max(0);
+    // Matched literally, so '%' and '_' in the search are not wildcards.
+    let search = query.q.as_deref().map(str::trim);
+
+    // Custom exercises are only ever visible to their owner.
+    let exercises = sqlx::query_as!(
+        Exercise,
+        r#"
+        SELECT * FROM exercises
+        WHERE (owner_id IS NULL OR owner_id = $1)
+          AND ($2::BOOLEAN IS NULL OR (owner_id IS NOT NULL) = $2)
+          AND (archived_at IS NULL OR $3)
+          AND ($4::TEXT IS NULL OR $4 = ANY(primary_muscles) OR $4 = ANY(secondary_muscles))
+          AND ($5::TEXT IS NULL OR equipment = $5)
+          AND ($6::TEXT IS NULL OR category = $6)
+          AND ($7::TEXT IS NULL OR strpos(lower(name), lower($7)) > 0)
+        ORDER BY name
+        LIMIT $8 OFFSET $9
+        "#,
+        user_id,
+        query.custom,
+        query.include_archived.unwrap_or(false),
+        query.muscle,
+        query.equipment,
+        query.category,
+        search,
+        limit,
+        offset
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(exercises))
+}
+
+pub async fn get_exercise(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(exercise_id): Path<Uuid>,
+) -> Result<Json<Exercise>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let exercise = sqlx::query_as!(
+        Exercise,
+        "SELECT * FROM exercises WHERE id = $1 AND (owner_id IS NULL OR owner_id = $2)",
+        exercise_id,
+        user_id
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(AppError::NotFound("Exercise not found".into()))?;
+
+    Ok(Json(exercise))
+}
+
+pub async fn create_exercise(
+    State(state): State<AppState>,
+    claims: Claims,
+    Json(payload): Json<CreateExerciseRequest>,
+) -> Result<(StatusCode, Json<Exercise>), AppError> {
+    let user_id = claims.user_id()?;
+
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+
+    let exercise = sqlx::query_as!(
+        Exercise,
+        r#"
+        INSERT INTO exercises (slug, name, description, primary_muscles, secondary_muscles, equipment, category, mechanics, instructions, owner_id)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        RETURNING *
+        "#,
+        slugify(&payload.name),
+        payload.name.trim(),
+        payload.description,
+        &payload.primary_muscles,
+        &payload.secondary_muscles,
+        payload.equipment,
+        payload.category,
+        payload.mechanics,
+        &payload.instructions,
+        user_id
+    )
+    .fetch_one(&state.pool)
+    .await
+    .map_err(slug_conflict)?;
+
+    Ok((StatusCode::CREATED, Json(exercise)))
+}
+
+pub async fn update_exercise(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(exercise_id): Path<Uuid>,
+    Json(payload): Json<UpdateExerciseRequest>,
+) -> Result<Json<Exercise>, AppError> {
+    let user_id = claims.user_id()?;
+
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+
+    let name = payload.name.as_deref().map(str::trim);
+    let exercise = sqlx::query_as!(
+        Exercise,
+        r#"
+        UPDATE exercises SET
+            slug = COALESCE($3, slug),
+            name = COALESCE($4, name),
+            description = CASE WHEN $13 THEN $5 ELSE description END,
+            primary_muscles = COALESCE($6, primary_muscles),
+            secondary_muscles = COALESCE($7, secondary_muscles),
+            equipment = COALESCE($8, equipment),
+            category = COALESCE($9, category),
+            mechanics = CASE WHEN $14 THEN $10 ELSE mechanics END,
+            instructions = COALESCE($11, instructions),
+            archived_at = CASE
+                WHEN $12::BOOLEAN IS NULL THEN archived_at
+                WHEN $12 THEN COALESCE(archived_at, NOW())
+                ELSE NULL
+            END,
+            updated_at = NOW()
+        WHERE id = $1 AND owner_id = $2
+        RETURNING *
+        "#,
+        exercise_id,
+        user_id,
+        name.map(slugify),
+        name,
+        payload.description.clone().flatten(),
+        payload.primary_muscles.as_deref(),
+        payload.secondary_muscles.as_deref(),
+        payload.equipment,
+        payload.category,
+        payload.mechanics.clone().flatten(),
+        payload.instructions.as_deref(),
+        payload.archived,
+        payload.description.is_some(),
+        payload.mechanics.is_some()
+    )
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(slug_conflict)?
+    .ok_or(AppError::NotFound("Custom exercise not found".into()))?;
+
+    Ok(Json(exercise))
+}
+
+/// Archives a custom exercise. It disappears from searches but stays
+/// resolvable by id, so workouts that reference it keep working.
+pub async fn archive_exercise(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(exercise_id): Path<Uuid>,
+) -> Result<Json<Exercise>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let exercise = sqlx::query_as!(
+        Exercise,
+        r#"
+        UPDATE exercises SET archived_at = COALESCE(archived_at, NOW()), updated_at = NOW()
+        WHERE id = $1 AND owner_id = $2
+        RETURNING *
+        "#,
+        exercise_id,
+        user_id
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(AppError::NotFound("Custom exercise not found".into()))?;
+
+    Ok(Json(exercise))
+}
+
+/// Moves a user's custom exercise into the global catalog (admin only).
+pub async fn promote_exercise(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(exercise_id): Path<Uuid>,
+) -> Result<Json<Exercise>, AppError> {
+    claims.require_admin(&state.pool).await?;
+
+    let exercise = sqlx::query_as!(
+        Exercise,
+        r#"
+        UPDATE exercises SET owner_id = NULL, archived_at = NULL, updated_at = NOW()
+        WHERE id = $1 AND owner_id IS NOT NULL
+        RETURNING *
+        "#,
+        exercise_id
+    )
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(slug_conflict)?
+    .ok_or(AppError::NotFound("Custom exercise not found".into()))?;
+
+    Ok(Json(exercise))
+}
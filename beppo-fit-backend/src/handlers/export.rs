@@ -0,0 +1,109 @@
+use axum::{
+    extract::{Query, State},
+    http::{
+        header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+        StatusCode,
+    },
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+
+use crate::{
+    error::AppError,
+    handlers::auth::Claims,
+    models::export::{
+        DataExport, EXPORT_STATUS_FAILED, EXPORT_STATUS_PENDING, EXPORT_STATUS_READY,
+    },
+    services::export::{process_export, PENDING_EXPORT_TIMEOUT_MINUTES},
+    state::AppState,
+};
+
+#[derive(Deserialize)]
+pub struct DownloadExportQuery {
+    token: String,
+}
+
+pub async fn request_export(
+    State(state): State<AppState>,
+    claims: Claims,
+) -> Result<(StatusCode, Json<DataExport>), AppError> {
+    let user_id = claims.user_id()?;
+
+    let user = sqlx::query!("SELECT email FROM users WHERE id = $1", user_id)
+        .fetch_optional(&state.pool)
+        .await?
+        .ok_or(AppError::Unauthorized("User not found".into()))?;
+
+    sqlx::query!(
+        r#"
+        UPDATE data_exports SET status = $3, completed_at = NOW()
+        WHERE user_id = $1 AND status = $2 AND created_at < NOW() - make_interval(mins => $4)
+        "#,
+        user_id,
+        EXPORT_STATUS_PENDING,
+        EXPORT_STATUS_FAILED,
+        PENDING_EXPORT_TIMEOUT_MINUTES as i32
+    )
+    .execute(&state.pool)
+    .await?;
+
+    // Drop archives whose download links have expired; they are no longer reachable.
+    sqlx::query!(
+        "DELETE FROM data_exports WHERE user_id = $1 AND expires_at < NOW()",
+        user_id
+    )
+    .execute(&state.pool)
+    .await?;
+
+    let export = sqlx::query_as!(
+        DataExport,
+        "INSERT INTO data_exports (user_id) VALUES ($1) RETURNING id, status, expires_at, created_at, completed_at",
+        user_id
+    )
+    .fetch_one(&state.pool)
+    .await
+    .map_err(|e| match e {
+        // Only one export may be pending per user.
+        sqlx::Error::Database(ref db) if db.is_unique_violation() => {
+            AppError::Conflict("A data export is already being prepared".into())
+        }
+        e => AppError::SqlxError(e),
+    })?;
+
+    tokio::spawn(process_export(
+        state.clone(),
+        export.id,
+        user_id,
+        user.email,
+    ));
+
+    Ok((StatusCode::ACCEPTED, Json(export)))
+}
+
+pub async fn download_export(
+    State(state): State<AppState>,
+    Query(query): Query<DownloadExportQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let export = sqlx::query!(
+        "SELECT archive FROM data_exports WHERE download_token = $1 AND status = $2 AND expires_at > NOW()",
+        query.token,
+        EXPORT_STATUS_READY
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(AppError::BadRequest("Invalid or expired token".into()))?;
+
+    let archive = export.archive.ok_or(AppError::InternalServerError)?;
+
+    Ok((
+        [
+            (CONTENT_TYPE, "application/zip"),
+            (
+                CONTENT_DISPOSITION,
+                "attachment; filename=\"beppofit-export.zip\"",
+            ),
+        ],
+        archive,
+    ))
+}
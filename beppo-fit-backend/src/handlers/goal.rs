@@ -0,0 +1,217 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    error::AppError,
+    handlers::auth::Claims,
+    models::goal::{
+        CreateGoalRequest, Goal, GoalResponse, UpdateGoalRequest, GOAL_BODYWEIGHT, GOAL_CUSTOM,
+        GOAL_STRENGTH,
+    },
+    services::{
+        goal::{measure_goal, refresh_goals},
+        profile::ensure_profile,
+        workout::check_exercise_access,
+    },
+    state::AppState,
+};
+
+pub async fn list_goals(
+    State(state): State<AppState>,
+    claims: Claims,
+) -> Result<Json<Vec<GoalResponse>>, AppError> {
+    let user_id = claims.user_id()?;
+    let mut tx = state.pool.begin().await?;
+
+    let goals = refresh_goals(&mut tx, user_id).await?;
+    let today = ensure_profile(&mut tx, user_id).await?.today();
+
+    tx.commit().await?;
+
+    Ok(Json(
+        goals
+            .into_iter()
+            .map(|goal| GoalResponse::new(goal, today))
+            .collect(),
+    ))
+}
+
+pub async fn create_goal(
+    State(state): State<AppState>,
+    claims: Claims,
+    Json(payload): Json<CreateGoalRequest>,
+) -> Result<(StatusCode, Json<GoalResponse>), AppError> {
+    let user_id = claims.user_id()?;
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+
+    let mut tx = state.pool.begin().await?;
+    if let Some(exercise_id) = payload.exercise_id {
+        check_exercise_access(&mut *tx, user_id, &[exercise_id]).await?;
+    }
+    let profile = ensure_profile(&mut tx, user_id).await?;
+
+    let mut goal = sqlx::query_as!(
+        Goal,
+        r#"
+        INSERT INTO goals (user_id, goal_type, title, exercise_id, metric, unit, start_value, target_value, current_value, deadline)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $7, $9)
+        RETURNING *
+        "#,
+        user_id,
+        payload.goal_type,
+        payload.title,
+        payload.exercise_id,
+        (payload.goal_type == GOAL_STRENGTH)
+            .then(|| payload.metric.clone().unwrap_or_else(|| "max_weight".to_string())),
+        payload.unit,
+        payload.start_value,
+        payload.target_value,
+        payload.deadline
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    // Tracked goals start from where the logged data stands today.
+    if goal.goal_type != GOAL_CUSTOM {
+        let start = measure_goal(&mut tx, &profile, &goal).await?;
+        // Without a starting weight there is no telling a cut from a bulk.
+        if goal.goal_type == GOAL_BODYWEIGHT && start.is_none() {
+            return Err(AppError::BadRequest(
+                "Log your weight before setting a bodyweight goal".into(),
+            ));
+        }
+        goal = sqlx::query_as!(
+            Goal,
+            "UPDATE goals SET start_value = $2 WHERE id = $1 RETURNING *",
+            goal.id,
+            start
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+    }
+
+    let goal = refresh_goals(&mut tx, user_id)
+        .await?
+        .into_iter()
+        .find(|g| g.id == goal.id)
+        .unwrap_or(goal);
+
+    tx.commit().await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(GoalResponse::new(goal, profile.today())),
+    ))
+}
+
+pub async fn get_goal(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(goal_id): Path<Uuid>,
+) -> Result<Json<GoalResponse>, AppError> {
+    let user_id = claims.user_id()?;
+    let mut tx = state.pool.begin().await?;
+
+    let goal = refresh_goals(&mut tx, user_id)
+        .await?
+        .into_iter()
+        .find(|g| g.id == goal_id)
+        .ok_or_else(|| AppError::NotFound("Goal not found".into()))?;
+    let today = ensure_profile(&mut tx, user_id).await?.today();
+
+    tx.commit().await?;
+
+    Ok(Json(GoalResponse::new(goal, today)))
+}
+
+/// Partial update. Changing the target of a completed goal reopens it.
+pub async fn update_goal(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(goal_id): Path<Uuid>,
+    Json(payload): Json<UpdateGoalRequest>,
+) -> Result<Json<GoalResponse>, AppError> {
+    let user_id = claims.user_id()?;
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+
+    let mut tx = state.pool.begin().await?;
+
+    let goal_type = sqlx::query_scalar!(
+        "SELECT goal_type FROM goals WHERE id = $1 AND user_id = $2",
+        goal_id,
+        user_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Goal not found".into()))?;
+    if payload.current_value.is_some() && goal_type != GOAL_CUSTOM {
+        return Err(AppError::BadRequest(
+            "Only custom goals can be updated by hand".into(),
+        ));
+    }
+
+    sqlx::query!(
+        r#"
+        UPDATE goals SET
+            title = COALESCE($3, title),
+            unit = COALESCE($4, unit),
+            target_value = COALESCE($5, target_value),
+            current_value = COALESCE($6, current_value),
+            deadline = COALESCE($7, deadline),
+            completed_at = CASE WHEN $5::DOUBLE PRECISION IS NULL THEN completed_at END,
+            updated_at = NOW()
+        WHERE id = $1 AND user_id = $2
+        "#,
+        goal_id,
+        user_id,
+        payload.title,
+        payload.unit,
+        payload.target_value,
+        payload.current_value,
+        payload.deadline
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let goal = refresh_goals(&mut tx, user_id)
+        .await?
+        .into_iter()
+        .find(|g| g.id == goal_id)
+        .ok_or_else(|| AppError::NotFound("Goal not found".into()))?;
+    let today = ensure_profile(&mut tx, user_id).await?.today();
+
+    tx.commit().await?;
+
+    Ok(Json(GoalResponse::new(goal, today)))
+}
+
+pub async fn delete_goal(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(goal_id): Path<Uuid>,
+) -> Result<Json<&'static str>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let result = sqlx::query!(
+        "DELETE FROM goals WHERE id = $1 AND user_id = $2",
+        goal_id,
+        user_id
+    )
+    .execute(&state.pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Goal not found".into()));
+    }
+
+    Ok(Json("Goal deleted successfully"))
+}
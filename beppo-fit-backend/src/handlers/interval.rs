@@ -0,0 +1,457 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use std::collections::HashSet;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    error::AppError,
+    handlers::auth::Claims,
+    models::interval::{
+        IntervalSession, IntervalSessionDetail, IntervalSessionQuery, IntervalWorkout,
+        IntervalWorkoutDetail, LogIntervalSessionRequest, SaveIntervalWorkoutRequest,
+    },
+    services::{
+        interval::{
+            copy_interval_workout, insert_interval_blocks, load_interval_detail,
+            load_session_detail, planned_seconds, step_exercise_ids,
+        },
+        workout::{check_exercise_access, check_shared_exercises},
+    },
+    state::AppState,
+};
+
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 200;
+
+fn validate_interval_workout(payload: &SaveIntervalWorkoutRequest) -> Result<i32, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+    planned_seconds(payload)
+}
+
+async fn find_own_interval_workout<'e>(
+    executor: impl sqlx::PgExecutor<'e>,
+    user_id: Uuid,
+    interval_workout_id: Uuid,
+) -> Result<IntervalWorkout, AppError> {
+    sqlx::query_as!(
+        IntervalWorkout,
+        "SELECT * FROM interval_workouts WHERE id = $1 AND user_id = $2",
+        interval_workout_id,
+        user_id
+    )
+    .fetch_optional(executor)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Interval workout not found".into()))
+}
+
+async fn find_shared_interval_workout<'e>(
+    executor: impl sqlx::PgExecutor<'e>,
+    share_code: &str,
+) -> Result<IntervalWorkout, AppError> {
+    sqlx::query_as!(
+        IntervalWorkout,
+        "SELECT * FROM interval_workouts WHERE share_code = $1",
+        share_code
+    )
+    .fetch_optional(executor)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Interval workout not found".into()))
+}
+
+async fn find_own_session<'e>(
+    executor: impl sqlx::PgExecutor<'e>,
+    user_id: Uuid,
+    session_id: Uuid,
+) -> Result<IntervalSession, AppError> {
+    sqlx::query_as!(
+        IntervalSession,
+        "SELECT * FROM interval_sessions WHERE id = $1 AND user_id = $2",
+        session_id,
+        user_id
+    )
+    .fetch_optional(executor)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Interval session not found".into()))
+}
+
+pub async fn list_interval_workouts(
+    State(state): State<AppState>,
+    claims: Claims,
+) -> Result<Json<Vec<IntervalWorkout>>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let workouts = sqlx::query_as!(
+        IntervalWorkout,
+        "SELECT * FROM interval_workouts WHERE user_id = $1 ORDER BY name",
+        user_id
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(workouts))
+}
+
+pub async fn create_interval_workout(
+    State(state): State<AppState>,
+    claims: Claims,
+    Json(payload): Json<SaveIntervalWorkoutRequest>,
+) -> Result<(StatusCode, Json<IntervalWorkoutDetail>), AppError> {
+    let user_id = claims.user_id()?;
+    let total_seconds = validate_interval_workout(&payload)?;
+
+    let mut tx = state.pool.begin().await?;
+
+    check_exercise_access(&mut *tx, user_id, &step_exercise_ids(&payload.blocks)).await?;
+
+    let workout = sqlx::query_as!(
+        IntervalWorkout,
+        r#"
+        INSERT INTO interval_workouts (user_id, name, description, format, total_seconds)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING *
+        "#,
+        user_id,
+        payload.name,
+        payload.description,
+        payload.format,
+        total_seconds
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    insert_interval_blocks(&mut tx, workout.id, &payload.blocks).await?;
+    let detail = load_interval_detail(&mut tx, workout).await?;
+
+    tx.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(detail)))
+}
+
+pub async fn get_interval_workout(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(interval_workout_id): Path<Uuid>,
+) -> Result<Json<IntervalWorkoutDetail>, AppError> {
+    let user_id = claims.user_id()?;
+    let mut conn = state.pool.acquire().await?;
+
+    let workout = find_own_interval_workout(&mut *conn, user_id, interval_workout_id).await?;
+
+    Ok(Json(load_interval_detail(&mut conn, workout).await?))
+}
+
+/// Replaces a definition with the given payload, including all of its blocks.
+/// Logged sessions keep the results they were recorded with.
+pub async fn update_interval_workout(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(interval_workout_id): Path<Uuid>,
+    Json(payload): Json<SaveIntervalWorkoutRequest>,
+) -> Result<Json<IntervalWorkoutDetail>, AppError> {
+    let user_id = claims.user_id()?;
+    let total_seconds = validate_interval_workout(&payload)?;
+
+    let mut tx = state.pool.begin().await?;
+
+    check_exercise_access(&mut *tx, user_id, &step_exercise_ids(&payload.blocks)).await?;
+
+    let workout = sqlx::query_as!(
+        IntervalWorkout,
+        r#"
+        UPDATE interval_workouts
+        SET name = $3, description = $4, format = $5, total_seconds = $6, updated_at = NOW()
+        WHERE id = $1 AND user_id = $2
+        RETURNING *
+        "#,
+        interval_workout_id,
+        user_id,
+        payload.name,
+        payload.description,
+        payload.format,
+        total_seconds
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Interval workout not found".into()))?;
+
+    sqlx::query!(
+        "DELETE FROM interval_blocks WHERE interval_workout_id = $1",
+        interval_workout_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    insert_interval_blocks(&mut tx, interval_workout_id, &payload.blocks).await?;
+    let detail = load_interval_detail(&mut tx, workout).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(detail))
+}
+
+pub async fn delete_interval_workout(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(interval_workout_id): Path<Uuid>,
+) -> Result<Json<&'static str>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let result = sqlx::query!(
+        "DELETE FROM interval_workouts WHERE id = $1 AND user_id = $2",
+        interval_workout_id,
+        user_id
+    )
+    .execute(&state.pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Interval workout not found".into()));
+    }
+
+    Ok(Json("Interval workout deleted successfully"))
+}
+
+/// Generates a share code for the definition, keeping the existing one if already shared.
+pub async fn share_interval_workout(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(interval_workout_id): Path<Uuid>,
+) -> Result<Json<IntervalWorkout>, AppError> {
+    let user_id = claims.user_id()?;
+    let share_code = Uuid::new_v4().simple().to_string()[..12].to_string();
+
+    let workout = sqlx::query_as!(
+        IntervalWorkout,
+        r#"
+        UPDATE interval_workouts SET share_code = COALESCE(share_code, $3), updated_at = NOW()
+        WHERE id = $1 AND user_id = $2
+        RETURNING *
+        "#,
+        interval_workout_id,
+        user_id,
+        share_code
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Interval workout not found".into()))?;
+
+    Ok(Json(workout))
+}
+
+pub async fn unshare_interval_workout(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(interval_workout_id): Path<Uuid>,
+) -> Result<Json<IntervalWorkout>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let workout = sqlx::query_as!(
+        IntervalWorkout,
+        r#"
+        UPDATE interval_workouts SET share_code = NULL, updated_at = NOW()
+        WHERE id = $1 AND user_id = $2
+        RETURNING *
+        "#,
+        interval_workout_id,
+        user_id
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Interval workout not found".into()))?;
+
+    Ok(Json(workout))
+}
+
+pub async fn get_shared_interval_workout(
+    State(state): State<AppState>,
+    _claims: Claims,
+    Path(share_code): Path<String>,
+) -> Result<Json<IntervalWorkoutDetail>, AppError> {
+    let mut conn = state.pool.acquire().await?;
+
+    let workout = find_shared_interval_workout(&mut *conn, &share_code).await?;
+    let mut detail = load_interval_detail(&mut conn, workout).await?;
+    detail.workout.share_code = None;
+
+    Ok(Json(detail))
+}
+
+/// Copies a shared definition into the caller's own interval workouts.
+pub async fn copy_shared_interval_workout(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(share_code): Path<String>,
+) -> Result<(StatusCode, Json<IntervalWorkoutDetail>), AppError> {
+    let user_id = claims.user_id()?;
+    let mut tx = state.pool.begin().await?;
+
+    let source = find_shared_interval_workout(&mut *tx, &share_code).await?;
+    let source = load_interval_detail(&mut tx, source).await?;
+
+    let exercise_ids: Vec<Uuid> = source
+        .blocks
+        .iter()
+        .flat_map(|b| &b.steps)
+        .filter_map(|s| s.exercise_id)
+        .collect();
+    check_shared_exercises(&mut *tx, user_id, &exercise_ids).await?;
+
+    let copy = copy_interval_workout(&mut tx, user_id, &source).await?;
+    let detail = load_interval_detail(&mut tx, copy).await?;
+
+    tx.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(detail)))
+}
+
+/// Records a completed run of an interval workout with the rounds and reps achieved per block.
+pub async fn log_interval_session(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(interval_workout_id): Path<Uuid>,
+    Json(payload): Json<LogIntervalSessionRequest>,
+) -> Result<(StatusCode, Json<IntervalSessionDetail>), AppError> {
+    let user_id = claims.user_id()?;
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+
+    let mut tx = state.pool.begin().await?;
+
+    let workout = find_own_interval_workout(&mut *tx, user_id, interval_workout_id).await?;
+    let workout = load_interval_detail(&mut tx, workout).await?;
+
+    let mut seen = HashSet::new();
+    for result in &payload.blocks {
+        let block = usize::try_from(result.block_position)
+            .ok()
+            .and_then(|position| workout.blocks.get(position))
+            .ok_or_else(|| {
+                AppError::BadRequest(format!("There is no block {}", result.block_position))
+            })?;
+        if !seen.insert(result.block_position) {
+            return Err(AppError::BadRequest(format!(
+                "Block {} is logged twice",
+                result.block_position
+            )));
+        }
+        // Capped blocks go as many rounds as the athlete manages.
+        if block.block.time_cap_seconds.is_none() && result.rounds_completed > block.block.rounds {
+            return Err(AppError::BadRequest(format!(
+                "Block {} has only {} rounds",
+                result.block_position, block.block.rounds
+            )));
+        }
+    }
+
+    let session = sqlx::query_as!(
+        IntervalSession,
+        r#"
+        INSERT INTO interval_sessions (user_id, interval_workout_id, name, format, started_at, duration_seconds, notes)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING *
+        "#,
+        user_id,
+        interval_workout_id,
+        workout.workout.name,
+        workout.workout.format,
+        payload.started_at,
+        payload.duration_seconds,
+        payload.notes
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    for result in &payload.blocks {
+        sqlx::query!(
+            r#"
+            INSERT INTO interval_session_blocks (session_id, block_position, rounds_completed, reps)
+            VALUES ($1, $2, $3, $4)
+            "#,
+            session.id,
+            result.block_position,
+            result.rounds_completed,
+            result.reps
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+    let detail = load_session_detail(&mut tx, session).await?;
+
+    tx.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(detail)))
+}
+
+/// Logged interval sessions, newest first.
+pub async fn list_interval_sessions(
+    State(state): State<AppState>,
+    claims: Claims,
+    Query(query): Query<IntervalSessionQuery>,
+) -> Result<Json<Vec<IntervalSession>>, AppError> {
+    let user_id = claims.user_id()?;
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    let sessions = sqlx::query_as!(
+        IntervalSession,
+        r#"
+        SELECT * FROM interval_sessions
+        WHERE user_id = $1 AND ($2::UUID IS NULL OR interval_workout_id = $2)
+        ORDER BY started_at DESC
+        LIMIT $3 OFFSET $4
+        "#,
+        user_id,
+        query.interval_workout_id,
+        limit,
+        offset
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(sessions))
+}
+
+pub async fn get_interval_session(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(session_id): Path<Uuid>,
+) -> Result<Json<IntervalSessionDetail>, AppError> {
+    let user_id = claims.user_id()?;
+    let mut conn = state.pool.acquire().await?;
+
+    let session = find_own_session(&mut *conn, user_id, session_id).await?;
+
+    Ok(Json(load_session_detail(&mut conn, session).await?))
+}
+
+pub async fn delete_interval_session(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(session_id): Path<Uuid>,
+) -> Result<Json<&'static str>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let result = sqlx::query!(
+        "DELETE FROM interval_sessions WHERE id = $1 AND user_id = $2",
+        session_id,
+        user_id
+    )
+    .execute(&state.pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Interval session not found".into()));
+    }
+
+    Ok(Json("Interval session deleted successfully"))
+}
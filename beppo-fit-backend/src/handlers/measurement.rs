@@ -0,0 +1,378 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    error::AppError,
+    handlers::auth::Claims,
+    models::{
+        measurement::{
+            length_from_cm, length_to_cm, mass_from_kg, mass_to_kg, BodyMeasurement,
+            MeasurementQuery, MeasurementResponse, SaveMeasurementRequest, SeriesPoint,
+            SeriesQuery, UpdateMeasurementRequest, MEASUREMENT_METRICS,
+        },
+        profile::{UserProfile, UNIT_SYSTEMS},
+        validation::one_of,
+    },
+    services::{goal::refresh_goals, profile::ensure_profile},
+    state::AppState,
+};
+
+const DEFAULT_PAGE_SIZE: i64 = 100;
+const MAX_PAGE_SIZE: i64 = 1000;
+const DEFAULT_WINDOW_DAYS: i32 = 7;
+
+/// The unit system to answer in: an explicit choice or the profile preference.
+fn response_units(requested: &Option<String>, profile: &UserProfile) -> Result<String, AppError> {
+    match requested {
+        Some(units) => {
+            one_of(units, UNIT_SYSTEMS, "units")
+                .map_err(|e| AppError::BadRequest(e.to_string()))?;
+            Ok(units.clone())
+        }
+        None => Ok(profile.preferred_units.clone()),
+    }
+}
+
+fn validate_measurement(payload: &SaveMeasurementRequest) -> Result<(), AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+    if !payload.has_values() {
+        return Err(AppError::BadRequest(
+            "At least one measurement value is required".into(),
+        ));
+    }
+    Ok(())
+}
+
+pub async fn list_measurements(
+    State(state): State<AppState>,
+    claims: Claims,
+    Query(query): Query<MeasurementQuery>,
+) -> Result<Json<Vec<MeasurementResponse>>, AppError> {
+    let user_id = claims.user_id()?;
+    let profile = ensure_profile(&mut *state.pool.acquire().await?, user_id).await?;
+    let units = response_units(&query.units, &profile)?;
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    let measurements = sqlx::query_as!(
+        BodyMeasurement,
+        r#"
+        SELECT * FROM body_measurements
+        WHERE user_id = $1
+          AND ($2::TIMESTAMPTZ IS NULL OR measured_at >= $2)
+          AND ($3::TIMESTAMPTZ IS NULL OR measured_at < $3)
+        ORDER BY measured_at DESC
+        LIMIT $4 OFFSET $5
+        "#,
+        user_id,
+        query.from,
+        query.to,
+        limit,
+        offset
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(
+        measurements
+            .into_iter()
+            .map(|m| MeasurementResponse::new(m, &units, profile.height_cm))
+            .collect(),
+    ))
+}
+
+pub async fn create_measurement(
+    State(state): State<AppState>,
+    claims: Claims,
+    Json(payload): Json<SaveMeasurementRequest>,
+) -> Result<(StatusCode, Json<MeasurementResponse>), AppError> {
+    let user_id = claims.user_id()?;
+    validate_measurement(&payload)?;
+    let mut tx = state.pool.begin().await?;
+    let profile = ensure_profile(&mut tx, user_id).await?;
+    let units = response_units(&payload.units, &profile)?;
+    let cm = payload.circumferences.map(|v| length_to_cm(v, &units));
+
+    let measurement = sqlx::query_as!(
+        BodyMeasurement,
+        r#"
+        INSERT INTO body_measurements (
+            user_id, measured_at, weight_kg, body_fat_percent, neck_cm, chest_cm, waist_cm, hips_cm,
+            left_arm_cm, right_arm_cm, left_thigh_cm, right_thigh_cm, left_calf_cm, right_calf_cm, notes
+        )
+        VALUES ($1, COALESCE($2, NOW()), $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+        RETURNING *
+        "#,
+        user_id,
+        payload.measured_at,
+        payload.weight.map(|v| mass_to_kg(v, &units)),
+        payload.body_fat_percent,
+        cm.neck,
+        cm.chest,
+        cm.waist,
+        cm.hips,
+        cm.left_arm,
+        cm.right_arm,
+        cm.left_thigh,
+        cm.right_thigh,
+        cm.left_calf,
+        cm.right_calf,
+        payload.notes
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    refresh_goals(&mut tx, user_id).await?;
+
+    tx.commit().await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(MeasurementResponse::new(
+            measurement,
+            &units,
+            profile.height_cm,
+        )),
+    ))
+}
+
+pub async fn get_measurement(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(measurement_id): Path<Uuid>,
+    Query(query): Query<MeasurementQuery>,
+) -> Result<Json<MeasurementResponse>, AppError> {
+    let user_id = claims.user_id()?;
+    let profile = ensure_profile(&mut *state.pool.acquire().await?, user_id).await?;
+    let units = response_units(&query.units, &profile)?;
+
+    let measurement = sqlx::query_as!(
+        BodyMeasurement,
+        "SELECT * FROM body_measurements WHERE id = $1 AND user_id = $2",
+        measurement_id,
+        user_id
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Measurement not found".into()))?;
+
+    Ok(Json(MeasurementResponse::new(
+        measurement,
+        &units,
+        profile.height_cm,
+    )))
+}
+
+/// Partial update; omitted values keep their current value and `null` clears them.
+pub async fn update_measurement(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(measurement_id): Path<Uuid>,
+    Json(payload): Json<UpdateMeasurementRequest>,
+) -> Result<Json<MeasurementResponse>, AppError> {
+    let user_id = claims.user_id()?;
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+    let mut tx = state.pool.begin().await?;
+    let profile = ensure_profile(&mut tx, user_id).await?;
+    let units = response_units(&payload.units, &profile)?;
+    let cm = payload.circumferences.map(|v| length_to_cm(v, &units));
+
+    let measurement = sqlx::query_as!(
+        BodyMeasurement,
+        r#"
+        UPDATE body_measurements SET
+            measured_at = COALESCE($3, measured_at),
+            weight_kg = CASE WHEN $5 THEN $4 ELSE weight_kg END,
+            body_fat_percent = CASE WHEN $7 THEN $6 ELSE body_fat_percent END,
+            neck_cm = CASE WHEN $9 THEN $8 ELSE neck_cm END,
+            chest_cm = CASE WHEN $11 THEN $10 ELSE chest_cm END,
+            waist_cm = CASE WHEN $13 THEN $12 ELSE waist_cm END,
+            hips_cm = CASE WHEN $15 THEN $14 ELSE hips_cm END,
+            left_arm_cm = CASE WHEN $17 THEN $16 ELSE left_arm_cm END,
+            right_arm_cm = CASE WHEN $19 THEN $18 ELSE right_arm_cm END,
+            left_thigh_cm = CASE WHEN $21 THEN $20 ELSE left_thigh_cm END,
+            right_thigh_cm = CASE WHEN $23 THEN $22 ELSE right_thigh_cm END,
+            left_calf_cm = CASE WHEN $25 THEN $24 ELSE left_calf_cm END,
+            right_calf_cm = CASE WHEN $27 THEN $26 ELSE right_calf_cm END,
+            notes = CASE WHEN $29 THEN $28 ELSE notes END,
+            updated_at = NOW()
+        WHERE id = $1 AND user_id = $2
+        RETURNING *
+        "#,
+        measurement_id,
+        user_id,
+        payload.measured_at,
+        payload.weight.flatten().map(|v| mass_to_kg(v, &units)),
+        payload.weight.is_some(),
+        payload.body_fat_percent.flatten(),
+        payload.body_fat_percent.is_some(),
+        cm.neck.flatten(),
+        cm.neck.is_some(),
+        cm.chest.flatten(),
+        cm.chest.is_some(),
+        cm.waist.flatten(),
+        cm.waist.is_some(),
+        cm.hips.flatten(),
+        cm.hips.is_some(),
+        cm.left_arm.flatten(),
+        cm.left_arm.is_some(),
+        cm.right_arm.flatten(),
+        cm.right_arm.is_some(),
+        cm.left_thigh.flatten(),
+        cm.left_thigh.is_some(),
+        cm.right_thigh.flatten(),
+        cm.right_thigh.is_some(),
+        cm.left_calf.flatten(),
+        cm.left_calf.is_some(),
+        cm.right_calf.flatten(),
+        cm.right_calf.is_some(),
+        payload.notes.clone().flatten(),
+        payload.notes.is_some()
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Measurement not found".into()))?;
+    if !measurement.has_values() {
+        return Err(AppError::BadRequest(
+            "At least one measurement value is required".into(),
+        ));
+    }
+    refresh_goals(&mut tx, user_id).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(MeasurementResponse::new(
+        measurement,
+        &units,
+        profile.height_cm,
+    )))
+}
+
+pub async fn delete_measurement(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(measurement_id): Path<Uuid>,
+) -> Result<Json<&'static str>, AppError> {
+    let user_id = claims.user_id()?;
+    let mut tx = state.pool.begin().await?;
+
+    let result = sqlx::query!(
+        "DELETE FROM body_measurements WHERE id = $1 AND user_id = $2",
+        measurement_id,
+        user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Measurement not found".into()));
+    }
+    // Bodyweight goals may have been tracking the deleted weight.
+    refresh_goals(&mut tx, user_id).await?;
+
+    tx.commit().await?;
+
+    Ok(Json("Measurement deleted successfully"))
+}
+
+/// One metric over time with a trailing moving average. The average also
+/// takes in measurements just before `from`, so the first points are not skewed.
+pub async fn measurement_series(
+    State(state): State<AppState>,
+    claims: Claims,
+    Query(query): Query<SeriesQuery>,
+) -> Result<Json<Vec<SeriesPoint>>, AppError> {
+    let user_id = claims.user_id()?;
+    if !MEASUREMENT_METRICS.contains(&query.metric.as_str()) {
+        return Err(AppError::BadRequest(format!(
+            "Unknown metric '{}'. Allowed: {}",
+            query.metric,
+            MEASUREMENT_METRICS.join(", ")
+        )));
+    }
+    let window_days = query.window_days.unwrap_or(DEFAULT_WINDOW_DAYS);
+    if !(1..=365).contains(&window_days) {
+        return Err(AppError::BadRequest(
+            "window_days must be between 1 and 365".into(),
+        ));
+    }
+    let profile = ensure_profile(&mut *state.pool.acquire().await?, user_id).await?;
+    let units = response_units(&query.units, &profile)?;
+    if matches!(query.metric.as_str(), "bmi" | "ffmi") && profile.height_cm.is_none() {
+        return Err(AppError::BadRequest(
+            "Set your height in the profile to derive BMI and FFMI".into(),
+        ));
+    }
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT measured_at AS "measured_at!", value AS "value!", moving_average AS "moving_average!"
+        FROM (
+            SELECT measured_at, value,
+                   AVG(value) OVER (
+                       ORDER BY measured_at
+                       RANGE BETWEEN make_interval(days => $6) PRECEDING AND CURRENT ROW
+                   ) AS moving_average
+            FROM (
+                SELECT measured_at,
+                       CASE $2
+                           WHEN 'weight' THEN weight_kg
+                           WHEN 'body_fat_percent' THEN body_fat_percent
+                           WHEN 'neck' THEN neck_cm
+                           WHEN 'chest' THEN chest_cm
+                           WHEN 'waist' THEN waist_cm
+                           WHEN 'hips' THEN hips_cm
+                           WHEN 'left_arm' THEN left_arm_cm
+                           WHEN 'right_arm' THEN right_arm_cm
+                           WHEN 'left_thigh' THEN left_thigh_cm
+                           WHEN 'right_thigh' THEN right_thigh_cm
+                           WHEN 'left_calf' THEN left_calf_cm
+                           WHEN 'right_calf' THEN right_calf_cm
+                           WHEN 'bmi' THEN weight_kg / POWER($5::DOUBLE PRECISION / 100, 2)
+                           WHEN 'ffmi' THEN weight_kg * (1 - body_fat_percent / 100.0) / POWER($5::DOUBLE PRECISION / 100, 2)
+                       END AS value
+                FROM body_measurements
+                WHERE user_id = $1 AND ($4::TIMESTAMPTZ IS NULL OR measured_at < $4)
+            ) metric
+            WHERE value IS NOT NULL
+        ) series
+        WHERE $3::TIMESTAMPTZ IS NULL OR measured_at >= $3
+        ORDER BY measured_at
+        "#,
+        user_id,
+        query.metric,
+        query.from,
+        query.to,
+        profile.height_cm,
+        window_days
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    let convert = |value: f64| match query.metric.as_str() {
+        "weight" => mass_from_kg(value, &units),
+        "body_fat_percent" | "bmi" | "ffmi" => (value * 10.0).round() / 10.0,
+        _ => length_from_cm(value, &units),
+    };
+
+    Ok(Json(
+        rows.into_iter()
+            .map(|row| SeriesPoint {
+                measured_at: row.measured_at,
+                value: convert(row.value),
+                moving_average: convert(row.moving_average),
+            })
+            .collect(),
+    ))
+}
@@ -0,0 +1,323 @@
+use axum::{
+    extract::{multipart::Field, Multipart, Path, Query, State},
+    http::{
+        header::{CACHE_CONTROL, CONTENT_TYPE},
+        StatusCode,
+    },
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    handlers::auth::Claims,
+    models::media::{Media, MediaResponse, MEDIA_KIND_AVATAR, MEDIA_KIND_PROGRESS_PHOTO},
+    services::{
+        media::{process_image, verify_signature, MAX_UPLOAD_BYTES},
+        profile::ensure_profile,
+    },
+    state::AppState,
+};
+
+#[derive(Deserialize)]
+pub struct SignedUrlQuery {
+    expires: i64,
+    signature: String,
+}
+
+/// The parts of a multipart upload we care about.
+#[derive(Default)]
+struct Upload {
+    file: Option<(String, Vec<u8>)>,
+    taken_at: Option<chrono::DateTime<chrono::Utc>>,
+    notes: Option<String>,
+}
+
+async fn read_field(field: Field<'_>) -> Result<Vec<u8>, AppError> {
+    let bytes = field.bytes().await.map_err(|e| {
+        if e.status() == StatusCode::PAYLOAD_TOO_LARGE {
+            AppError::PayloadTooLarge("File is too large".into())
+        } else {
+            AppError::BadRequest(e.body_text())
+        }
+    })?;
+    if bytes.len() > MAX_UPLOAD_BYTES {
+        return Err(AppError::PayloadTooLarge(format!(
+            "File must be at most {} MB",
+            MAX_UPLOAD_BYTES / 1024 / 1024
+        )));
+    }
+    Ok(bytes.to_vec())
+}
+
+async fn read_upload(mut multipart: Multipart) -> Result<Upload, AppError> {
+    let mut upload = Upload::default();
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::BadRequest(e.body_text()))?
+    {
+        match field.name() {
+            Some("file") => {
+                let content_type = field
+                    .content_type()
+                    .unwrap_or("application/octet-stream")
+                    .to_string();
+                upload.file = Some((content_type, read_field(field).await?));
+            }
+            Some("taken_at") => {
+                let value = String::from_utf8(read_field(field).await?)
+                    .map_err(|_| AppError::BadRequest("Invalid taken_at".into()))?;
+                let taken_at =
+                    chrono::DateTime::parse_from_rfc3339(value.trim()).map_err(|_| {
+                        AppError::BadRequest("taken_at must be an RFC 3339 timestamp".into())
+                    })?;
+                upload.taken_at = Some(taken_at.with_timezone(&chrono::Utc));
+            }
+            Some("notes") => {
+                let notes = String::from_utf8(read_field(field).await?)
+                    .map_err(|_| AppError::BadRequest("Invalid notes".into()))?;
+                if notes.chars().count() > 1000 {
+                    return Err(AppError::BadRequest(
+                        "Notes must be at most 1000 characters".into(),
+                    ));
+                }
+                upload.notes = Some(notes);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(upload)
+}
+
+/// Processes an uploaded image, writes both variants to storage and records the media row.
+async fn store_media(
+    state: &AppState,
+    user_id: Uuid,
+    kind: &str,
+    upload: Upload,
+) -> Result<Media, AppError> {
+    let (content_type, data) = upload
+        .file
+        .ok_or(AppError::BadRequest("Missing file".into()))?;
+
+    let processed = tokio::task::spawn_blocking(move || process_image(&data, &content_type))
+        .await
+        .map_err(|_| AppError::InternalServerError)??;
+
+    let media_id = Uuid::new_v4();
+    let storage_key = format!("{}/{}/original.{}", user_id, media_id, processed.extension);
+    let thumbnail_key = format!("{}/{}/thumbnail.{}", user_id, media_id, processed.extension);
+    let size_bytes = processed.original.len() as i64;
+
+    state
+        .storage
+        .put(&storage_key, processed.original, processed.content_type)
+        .await?;
+    state
+        .storage
+        .put(&thumbnail_key, processed.thumbnail, processed.content_type)
+        .await?;
+
+    let media = sqlx::query_as!(
+        Media,
+        r#"
+        INSERT INTO media (id, user_id, kind, content_type, storage_key, thumbnail_key, width, height, size_bytes, taken_at, notes)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+        RETURNING *
+        "#,
+        media_id,
+        user_id,
+        kind,
+        processed.content_type,
+        storage_key,
+        thumbnail_key,
+        processed.width as i32,
+        processed.height as i32,
+        size_bytes,
+        upload.taken_at,
+        upload.notes
+    )
+    .fetch_one(&state.pool)
+    .await?;
+
+    Ok(media)
+}
+
+/// Removes the stored files of a media item. Failures are logged because the
+/// database row is already gone and the blobs are unreachable either way.
+pub async fn delete_media_files(state: &AppState, media: &Media) {
+    for key in [&media.storage_key, &media.thumbnail_key] {
+        if let Err(e) = state.storage.delete(key).await {
+            tracing::error!("Failed to delete media file {}: {:?}", key, e);
+        }
+    }
+}
+
+pub async fn upload_avatar(
+    State(state): State<AppState>,
+    claims: Claims,
+    multipart: Multipart,
+) -> Result<Json<MediaResponse>, AppError> {
+    let user_id = claims.user_id()?;
+    let upload = read_upload(multipart).await?;
+    let media = store_media(&state, user_id, MEDIA_KIND_AVATAR, upload).await?;
+
+    let mut tx = state.pool.begin().await?;
+    ensure_profile(&mut tx, user_id).await?;
+    sqlx::query!(
+        "UPDATE user_profiles SET avatar_media_id = $1, updated_at = NOW() WHERE user_id = $2",
+        media.id,
+        user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+    let previous = sqlx::query_as!(
+        Media,
+        "DELETE FROM media WHERE user_id = $1 AND kind = $2 AND id <> $3 RETURNING *",
+        user_id,
+        MEDIA_KIND_AVATAR,
+        media.id
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    // Only once the new avatar is in place, so a failure never leaves the profile without one.
+    for old in previous {
+        delete_media_files(&state, &old).await;
+    }
+
+    Ok(Json(media.into()))
+}
+
+pub async fn get_avatar(
+    State(state): State<AppState>,
+    claims: Claims,
+) -> Result<Json<MediaResponse>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let media = sqlx::query_as!(
+        Media,
+        "SELECT * FROM media WHERE user_id = $1 AND kind = $2",
+        user_id,
+        MEDIA_KIND_AVATAR
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(AppError::NotFound("No avatar uploaded".into()))?;
+
+    Ok(Json(media.into()))
+}
+
+pub async fn delete_avatar(
+    State(state): State<AppState>,
+    claims: Claims,
+) -> Result<Json<&'static str>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let deleted = sqlx::query_as!(
+        Media,
+        "DELETE FROM media WHERE user_id = $1 AND kind = $2 RETURNING *",
+        user_id,
+        MEDIA_KIND_AVATAR
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    for media in &deleted {
+        delete_media_files(&state, media).await;
+    }
+
+    Ok(Json("Avatar deleted successfully"))
+}
+
+pub async fn upload_progress_photo(
+    State(state): State<AppState>,
+    claims: Claims,
+    multipart: Multipart,
+) -> Result<(StatusCode, Json<MediaResponse>), AppError> {
+    let user_id = claims.user_id()?;
+    let upload = read_upload(multipart).await?;
+    let media = store_media(&state, user_id, MEDIA_KIND_PROGRESS_PHOTO, upload).await?;
+
+    Ok((StatusCode::CREATED, Json(media.into())))
+}
+
+pub async fn list_progress_photos(
+    State(state): State<AppState>,
+    claims: Claims,
+) -> Result<Json<Vec<MediaResponse>>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let photos = sqlx::query_as!(
+        Media,
+        "SELECT * FROM media WHERE user_id = $1 AND kind = $2 ORDER BY COALESCE(taken_at, created_at) DESC",
+        user_id,
+        MEDIA_KIND_PROGRESS_PHOTO
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(photos.into_iter().map(Into::into).collect()))
+}
+
+pub async fn delete_media(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(media_id): Path<Uuid>,
+) -> Result<Json<&'static str>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let media = sqlx::query_as!(
+        Media,
+        "DELETE FROM media WHERE id = $1 AND user_id = $2 RETURNING *",
+        media_id,
+        user_id
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(AppError::NotFound("Media not found".into()))?;
+
+    delete_media_files(&state, &media).await;
+
+    Ok(Json("Media deleted successfully"))
+}
+
+/// Serves a media variant. Authorization comes from the signed URL, so this
+/// works in plain `<img>` tags without an `Authorization` header.
+pub async fn download_media(
+    State(state): State<AppState>,
+    Path((media_id, variant)): Path<(Uuid, String)>,
+    Query(query): Query<SignedUrlQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    if !verify_signature(media_id, &variant, query.expires, &query.signature) {
+        return Err(AppError::Unauthorized("Invalid or expired link".into()));
+    }
+
+    let media = sqlx::query_as!(Media, "SELECT * FROM media WHERE id = $1", media_id)
+        .fetch_optional(&state.pool)
+        .await?
+        .ok_or(AppError::NotFound("Media not found".into()))?;
+
+    let key = media
+        .key_for(&variant)
+        .ok_or(AppError::NotFound("Media not found".into()))?;
+    let data = state
+        .storage
+        .get(key)
+        .await?
+        .ok_or(AppError::NotFound("Media not found".into()))?;
+
+    Ok((
+        [
+            (CONTENT_TYPE, media.content_type),
+            (CACHE_CONTROL, "private, max-age=3600".to_string()),
+        ],
+        data,
+    ))
+}
@@ -0,0 +1,84 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    handlers::auth::Claims,
+    models::notification::{Notification, NotificationQuery},
+    state::AppState,
+};
+
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 200;
+
+pub async fn list_notifications(
+    State(state): State<AppState>,
+    claims: Claims,
+    Query(query): Query<NotificationQuery>,
+) -> Result<Json<Vec<Notification>>, AppError> {
+    let user_id = claims.user_id()?;
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+
+    let notifications = sqlx::query_as!(
+        Notification,
+        r#"
+        SELECT * FROM notifications
+        WHERE user_id = $1 AND (NOT $2 OR read_at IS NULL)
+        ORDER BY created_at DESC
+        LIMIT $3
+        "#,
+        user_id,
+        query.unread,
+        limit
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(notifications))
+}
+
+pub async fn mark_notification_read(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(notification_id): Path<Uuid>,
+) -> Result<Json<Notification>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let notification = sqlx::query_as!(
+        Notification,
+        r#"
+        UPDATE notifications SET read_at = COALESCE(read_at, NOW())
+        WHERE id = $1 AND user_id = $2
+        RETURNING *
+        "#,
+        notification_id,
+        user_id
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Notification not found".into()))?;
+
+    Ok(Json(notification))
+}
+
+pub async fn mark_all_notifications_read(
+    State(state): State<AppState>,
+    claims: Claims,
+) -> Result<Json<&'static str>, AppError> {
+    let user_id = claims.user_id()?;
+
+    sqlx::query!(
+        "UPDATE notifications SET read_at = NOW() WHERE user_id = $1 AND read_at IS NULL",
+        user_id
+    )
+    .execute(&state.pool)
+    .await?;
+
+    Ok(Json("Notifications marked as read"))
+}
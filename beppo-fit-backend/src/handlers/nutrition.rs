@@ -0,0 +1,544 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::Duration;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    error::AppError,
+    handlers::auth::Claims,
+    models::nutrition::{
+        check_macros, CreateFoodRequest, DailyNutrition, DailyNutritionQuery, Food, FoodQuery,
+        Meal, MealDetail, MealQuery, Nutrients, NutritionTargets, NutritionTargetsQuery,
+        SaveMealRequest, UpdateFoodRequest, MEAL_TYPES, NUTRITION_GOALS, SOURCE_USER,
+    },
+    services::{
+        nutrition::{insert_meal_items, load_meal_detail},
+        nutrition_targets::{compute_targets, load_target_inputs},
+        profile::ensure_profile,
+        stats::resolve_range,
+    },
+    state::AppState,
+};
+
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 200;
+/// Longest span of days the meal list returns at once.
+const MAX_MEAL_RANGE_DAYS: i64 = 31;
+
+async fn find_own_food<'e>(
+    executor: impl sqlx::PgExecutor<'e>,
+    user_id: Uuid,
+    food_id: Uuid,
+) -> Result<Food, AppError> {
+    sqlx::query_as!(
+        Food,
+        "SELECT * FROM foods WHERE id = $1 AND owner_id = $2",
+        food_id,
+        user_id
+    )
+    .fetch_optional(executor)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Custom food not found".into()))
+}
+
+async fn find_own_meal<'e>(
+    executor: impl sqlx::PgExecutor<'e>,
+    user_id: Uuid,
+    meal_id: Uuid,
+) -> Result<Meal, AppError> {
+    sqlx::query_as!(
+        Meal,
+        "SELECT * FROM meals WHERE id = $1 AND user_id = $2",
+        meal_id,
+        user_id
+    )
+    .fetch_optional(executor)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Meal not found".into()))
+}
+
+/// Searches the shared catalog and the caller's own foods.
+pub async fn list_foods(
+    State(state): State<AppState>,
+    claims: Claims,
+    Query(query): Query<FoodQuery>,
+) -> Result<Json<Vec<Food>>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+    let offset = query.offset.unwrap_or(0).max(0);
+    let search = query.q.as_deref().map(str::trim);
+    let barcode = query.barcode.as_deref().map(str::trim);
+
+    // The caller's own foods come first so they win over catalog entries
+    // with the same barcode.
+    let foods = sqlx::query_as!(
+        Food,
+        r#"
+        SELECT * FROM foods
+        WHERE (owner_id IS NULL OR owner_id = $1)
+          AND ($2::BOOLEAN IS NULL OR (owner_id IS NOT NULL) = $2)
+          AND (archived_at IS NULL OR $3)
+          AND ($4::TEXT IS NULL OR strpos(lower(name), lower($4)) > 0
+               OR strpos(lower(brand), lower($4)) > 0)
+          AND ($5::TEXT IS NULL OR barcode = $5)
+        ORDER BY owner_id IS NULL, name
+        LIMIT $6 OFFSET $7
+        "#,
+        user_id,
+        query.custom,
+        query.include_archived.unwrap_or(false),
+        search,
+        barcode,
+        limit,
+        offset
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(foods))
+}
+
+pub async fn get_food(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(food_id): Path<Uuid>,
+) -> Result<Json<Food>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let food = sqlx::query_as!(
+        Food,
+        "SELECT * FROM foods WHERE id = $1 AND (owner_id IS NULL OR owner_id = $2)",
+        food_id,
+        user_id
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(AppError::NotFound("Food not found".into()))?;
+
+    Ok(Json(food))
+}
+
+pub async fn create_food(
+    State(state): State<AppState>,
+    claims: Claims,
+    Json(payload): Json<CreateFoodRequest>,
+) -> Result<(StatusCode, Json<Food>), AppError> {
+    let user_id = claims.user_id()?;
+
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+
+    let food = sqlx::query_as!(
+        Food,
+        r#"
+        INSERT INTO foods (
+            owner_id, source, name, brand, barcode, serving_size_g, serving_name, energy_kcal,
+            protein_g, carbohydrate_g, fat_g, fiber_g, sugar_g, saturated_fat_g,
+            sodium_mg, potassium_mg, calcium_mg, iron_mg, vitamin_c_mg
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19)
+        RETURNING *
+        "#,
+        user_id,
+        SOURCE_USER,
+        payload.name.trim(),
+        payload.brand,
+        payload.barcode,
+        payload.serving_size_g,
+        payload.serving_name,
+        payload.energy_kcal,
+        payload.protein_g,
+        payload.carbohydrate_g,
+        payload.fat_g,
+        payload.fiber_g,
+        payload.sugar_g,
+        payload.saturated_fat_g,
+        payload.sodium_mg,
+        payload.potassium_mg,
+        payload.calcium_mg,
+        payload.iron_mg,
+        payload.vitamin_c_mg
+    )
+    .fetch_one(&state.pool)
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(food)))
+}
+
+pub async fn update_food(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(food_id): Path<Uuid>,
+    Json(payload): Json<UpdateFoodRequest>,
+) -> Result<Json<Food>, AppError> {
+    let user_id = claims.user_id()?;
+
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+
+    let mut tx = state.pool.begin().await?;
+
+    let current = find_own_food(&mut *tx, user_id, food_id).await?;
+    check_macros(
+        payload.protein_g.or(current.protein_g),
+        payload.carbohydrate_g.or(current.carbohydrate_g),
+        payload.fat_g.or(current.fat_g),
+    )
+    .map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+    let food = sqlx::query_as!(
+        Food,
+        r#"
+        UPDATE foods SET
+            name = COALESCE($3, name),
+            brand = COALESCE($4, brand),
+            barcode = COALESCE($5, barcode),
+            serving_size_g = COALESCE($6, serving_size_g),
+            serving_name = COALESCE($7, serving_name),
+            energy_kcal = COALESCE($8, energy_kcal),
+            protein_g = COALESCE($9, protein_g),
+            carbohydrate_g = COALESCE($10, carbohydrate_g),
+            fat_g = COALESCE($11, fat_g),
+            fiber_g = COALESCE($12, fiber_g),
+            sugar_g = COALESCE($13, sugar_g),
+            saturated_fat_g = COALESCE($14, saturated_fat_g),
+            sodium_mg = COALESCE($15, sodium_mg),
+            potassium_mg = COALESCE($16, potassium_mg),
+            calcium_mg = COALESCE($17, calcium_mg),
+            iron_mg = COALESCE($18, iron_mg),
+            vitamin_c_mg = COALESCE($19, vitamin_c_mg),
+            archived_at = CASE
+                WHEN $20::BOOLEAN IS NULL THEN archived_at
+                WHEN $20 THEN COALESCE(archived_at, NOW())
+                ELSE NULL
+            END,
+            updated_at = NOW()
+        WHERE id = $1 AND owner_id = $2
+        RETURNING *
+        "#,
+        food_id,
+        user_id,
+        payload.name.as_deref().map(str::trim),
+        payload.brand,
+        payload.barcode,
+        payload.serving_size_g,
+        payload.serving_name,
+        payload.energy_kcal,
+        payload.protein_g,
+        payload.carbohydrate_g,
+        payload.fat_g,
+        payload.fiber_g,
+        payload.sugar_g,
+        payload.saturated_fat_g,
+        payload.sodium_mg,
+        payload.potassium_mg,
+        payload.calcium_mg,
+        payload.iron_mg,
+        payload.vitamin_c_mg,
+        payload.archived
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(food))
+}
+
+/// Archives a custom food. It disappears from searches but meals that
+/// contain it keep their nutrients.
+pub async fn archive_food(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(food_id): Path<Uuid>,
+) -> Result<Json<Food>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let food = sqlx::query_as!(
+        Food,
+        r#"
+        UPDATE foods SET archived_at = COALESCE(archived_at, NOW()), updated_at = NOW()
+        WHERE id = $1 AND owner_id = $2
+        RETURNING *
+        "#,
+        food_id,
+        user_id
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(AppError::NotFound("Custom food not found".into()))?;
+
+    Ok(Json(food))
+}
+
+fn validate_meal(payload: &SaveMealRequest) -> Result<(), AppError> {
+    payload
+        .validate()
+        .map_err(|e| AppError::BadRequest(e.to_string()))
+}
+
+pub async fn list_meals(
+    State(state): State<AppState>,
+    claims: Claims,
+    Query(query): Query<MealQuery>,
+) -> Result<Json<Vec<MealDetail>>, AppError> {
+    let user_id = claims.user_id()?;
+
+    if let Some(meal_type) = &query.meal_type {
+        if !MEAL_TYPES.contains(&meal_type.as_str()) {
+            return Err(AppError::BadRequest(format!(
+                "Unknown meal_type '{}'. Allowed: {}",
+                meal_type,
+                MEAL_TYPES.join(", ")
+            )));
+        }
+    }
+
+    let mut conn = state.pool.acquire().await?;
+    let profile = ensure_profile(&mut conn, user_id).await?;
+    let to = query.to.unwrap_or_else(|| profile.today());
+    let from = query.from.unwrap_or(to);
+    if from > to {
+        return Err(AppError::BadRequest("from must not be after to".into()));
+    }
+    if (to - from).num_days() >= MAX_MEAL_RANGE_DAYS {
+        return Err(AppError::BadRequest(format!(
+            "Date range must be at most {} days",
+            MAX_MEAL_RANGE_DAYS
+        )));
+    }
+
+    let meals = sqlx::query_as!(
+        Meal,
+        r#"
+        SELECT * FROM meals
+        WHERE user_id = $1 AND eaten_at >= $2 AND eaten_at < $3
+          AND ($4::TEXT IS NULL OR meal_type = $4)
+        ORDER BY eaten_at
+        "#,
+        user_id,
+        profile.start_of_day(from),
+        profile.start_of_day(to + Duration::days(1)),
+        query.meal_type
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut details = Vec::with_capacity(meals.len());
+    for meal in meals {
+        details.push(load_meal_detail(&mut conn, meal).await?);
+    }
+
+    Ok(Json(details))
+}
+
+pub async fn create_meal(
+    State(state): State<AppState>,
+    claims: Claims,
+    Json(payload): Json<SaveMealRequest>,
+) -> Result<(StatusCode, Json<MealDetail>), AppError> {
+    let user_id = claims.user_id()?;
+    validate_meal(&payload)?;
+
+    let mut tx = state.pool.begin().await?;
+
+    let meal = sqlx::query_as!(
+        Meal,
+        r#"
+        INSERT INTO meals (user_id, meal_type, eaten_at, notes)
+        VALUES ($1, $2, $3, $4)
+        RETURNING *
+        "#,
+        user_id,
+        payload.meal_type,
+        payload.eaten_at,
+        payload.notes
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    insert_meal_items(&mut tx, user_id, meal.id, &payload.items).await?;
+    let detail = load_meal_detail(&mut tx, meal).await?;
+
+    tx.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(detail)))
+}
+
+pub async fn get_meal(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(meal_id): Path<Uuid>,
+) -> Result<Json<MealDetail>, AppError> {
+    let user_id = claims.user_id()?;
+    let mut conn = state.pool.acquire().await?;
+
+    let meal = find_own_meal(&mut *conn, user_id, meal_id).await?;
+
+    Ok(Json(load_meal_detail(&mut conn, meal).await?))
+}
+
+pub async fn update_meal(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(meal_id): Path<Uuid>,
+    Json(payload): Json<SaveMealRequest>,
+) -> Result<Json<MealDetail>, AppError> {
+    let user_id = claims.user_id()?;
+    validate_meal(&payload)?;
+
+    let mut tx = state.pool.begin().await?;
+
+    let meal = sqlx::query_as!(
+        Meal,
+        r#"
+        UPDATE meals SET meal_type = $3, eaten_at = $4, notes = $5, updated_at = NOW()
+        WHERE id = $1 AND user_id = $2
+        RETURNING *
+        "#,
+        meal_id,
+        user_id,
+        payload.meal_type,
+        payload.eaten_at,
+        payload.notes
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Meal not found".into()))?;
+
+    insert_meal_items(&mut tx, user_id, meal.id, &payload.items).await?;
+    let detail = load_meal_detail(&mut tx, meal).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(detail))
+}
+
+pub async fn delete_meal(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(meal_id): Path<Uuid>,
+) -> Result<Json<&'static str>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let result = sqlx::query!(
+        "DELETE FROM meals WHERE id = $1 AND user_id = $2",
+        meal_id,
+        user_id
+    )
+    .execute(&state.pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Meal not found".into()));
+    }
+
+    Ok(Json("Meal deleted successfully"))
+}
+
+/// Nutrient totals per local day.
+pub async fn daily_nutrition(
+    State(state): State<AppState>,
+    claims: Claims,
+    Query(query): Query<DailyNutritionQuery>,
+) -> Result<Json<Vec<DailyNutrition>>, AppError> {
+    let user_id = claims.user_id()?;
+    let range = resolve_range(&state.pool, user_id, query.from, query.to, Some("day")).await?;
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            (m.eaten_at AT TIME ZONE $4)::DATE AS "date!",
+            COUNT(DISTINCT m.id) AS "meals!",
+            SUM(mi.energy_kcal * mi.grams / 100) AS "energy_kcal!",
+            SUM(mi.protein_g * mi.grams / 100) AS protein_g,
+            SUM(mi.carbohydrate_g * mi.grams / 100) AS carbohydrate_g,
+            SUM(mi.fat_g * mi.grams / 100) AS fat_g,
+            SUM(mi.fiber_g * mi.grams / 100) AS fiber_g,
+            SUM(mi.sugar_g * mi.grams / 100) AS sugar_g,
+            SUM(mi.saturated_fat_g * mi.grams / 100) AS saturated_fat_g,
+            SUM(mi.sodium_mg * mi.grams / 100) AS sodium_mg,
+            SUM(mi.potassium_mg * mi.grams / 100) AS potassium_mg,
+            SUM(mi.calcium_mg * mi.grams / 100) AS calcium_mg,
+            SUM(mi.iron_mg * mi.grams / 100) AS iron_mg,
+            SUM(mi.vitamin_c_mg * mi.grams / 100) AS vitamin_c_mg
+        FROM meals m
+        JOIN meal_items mi ON mi.meal_id = m.id
+        WHERE m.user_id = $1 AND m.eaten_at >= $2 AND m.eaten_at < $3
+        GROUP BY 1
+        ORDER BY 1
+        "#,
+        user_id,
+        range.start,
+        range.end,
+        range.timezone
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    let days = rows
+        .into_iter()
+        .map(|row| DailyNutrition {
+            date: row.date,
+            meals: row.meals,
+            totals: Nutrients {
+                energy_kcal: row.energy_kcal,
+                protein_g: row.protein_g,
+                carbohydrate_g: row.carbohydrate_g,
+                fat_g: row.fat_g,
+                fiber_g: row.fiber_g,
+                sugar_g: row.sugar_g,
+                saturated_fat_g: row.saturated_fat_g,
+                sodium_mg: row.sodium_mg,
+                potassium_mg: row.potassium_mg,
+                calcium_mg: row.calcium_mg,
+                iron_mg: row.iron_mg,
+                vitamin_c_mg: row.vitamin_c_mg,
+            }
+            .scaled(1.0),
+        })
+        .collect();
+
+    Ok(Json(days))
+}
+
+/// Calorie and macro targets for the goal in the profile, or the one in the query.
+pub async fn nutrition_targets(
+    State(state): State<AppState>,
+    claims: Claims,
+    Query(query): Query<NutritionTargetsQuery>,
+) -> Result<Json<NutritionTargets>, AppError> {
+    let user_id = claims.user_id()?;
+
+    if let Some(goal) = &query.goal {
+        if !NUTRITION_GOALS.contains(&goal.as_str()) {
+            return Err(AppError::BadRequest(format!(
+                "Unknown goal '{}'. Allowed: {}",
+                goal,
+                NUTRITION_GOALS.join(", ")
+            )));
+        }
+    }
+
+    let mut conn = state.pool.acquire().await?;
+    let profile = ensure_profile(&mut conn, user_id).await?;
+    let inputs = load_target_inputs(&mut conn, &profile).await?;
+    let goal = query.goal.as_deref().unwrap_or(&profile.nutrition_goal);
+
+    Ok(Json(compute_targets(
+        &profile,
+        goal,
+        profile.today(),
+        &inputs,
+    )?))
+}
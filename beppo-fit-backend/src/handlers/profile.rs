@@ -0,0 +1,113 @@
+use axum::{extract::State, Json};
+use validator::Validate;
+
+use crate::{
+    error::AppError,
+    handlers::auth::Claims,
+    models::{
+        profile::{UpdateProfileRequest, UserProfile},
+        social::{FOLLOW_ACCEPTED, FOLLOW_PENDING},
+    },
+    services::profile::ensure_profile,
+    state::AppState,
+};
+
+pub async fn get_profile(
+    State(state): State<AppState>,
+    claims: Claims,
+) -> Result<Json<UserProfile>, AppError> {
+    let user_id = claims.user_id()?;
+    let profile = ensure_profile(&mut *state.pool.acquire().await?, user_id).await?;
+
+    Ok(Json(profile))
+}
+
+pub async fn update_profile(
+    State(state): State<AppState>,
+    claims: Claims,
+    Json(payload): Json<UpdateProfileRequest>,
+) -> Result<Json<UserProfile>, AppError> {
+    let user_id = claims.user_id()?;
+
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+
+    let current = ensure_profile(&mut *state.pool.acquire().await?, user_id).await?;
+    let max_heart_rate = payload.max_heart_rate.unwrap_or(current.max_heart_rate);
+    let resting_heart_rate = payload
+        .resting_heart_rate
+        .unwrap_or(current.resting_heart_rate);
+    if let Some((max, resting)) = max_heart_rate.zip(resting_heart_rate) {
+        if resting >= max {
+            return Err(AppError::BadRequest(
+                "Resting heart rate must be below max heart rate".into(),
+            ));
+        }
+    }
+
+    let profile = sqlx::query_as!(
+        UserProfile,
+        r#"
+        UPDATE user_profiles SET
+            display_name = CASE WHEN $16 THEN $2 ELSE display_name END,
+            date_of_birth = CASE WHEN $17 THEN $3 ELSE date_of_birth END,
+            sex = CASE WHEN $18 THEN $4 ELSE sex END,
+            height_cm = CASE WHEN $19 THEN $5 ELSE height_cm END,
+            preferred_units = COALESCE($6, preferred_units),
+            timezone = COALESCE($7, timezone),
+            locale = COALESCE($8, locale),
+            avatar_url = CASE WHEN $20 THEN $9 ELSE avatar_url END,
+            is_private = COALESCE($10, is_private),
+            default_workout_visibility = COALESCE($11, default_workout_visibility),
+            max_heart_rate = CASE WHEN $21 THEN $12 ELSE max_heart_rate END,
+            resting_heart_rate = CASE WHEN $22 THEN $13 ELSE resting_heart_rate END,
+            heart_rate_zone_method = COALESCE($14, heart_rate_zone_method),
+            nutrition_goal = COALESCE($15, nutrition_goal),
+            updated_at = NOW()
+        WHERE user_id = $1
+        RETURNING *
+        "#,
+        user_id,
+        payload.display_name.clone().flatten(),
+        payload.date_of_birth.flatten(),
+        payload.sex.clone().flatten(),
+        payload.height_cm.flatten(),
+        payload.preferred_units,
+        payload.timezone,
+        payload.locale,
+        payload.avatar_url.clone().flatten(),
+        payload.is_private,
+        payload.default_workout_visibility,
+        payload.max_heart_rate.flatten(),
+        payload.resting_heart_rate.flatten(),
+        payload.heart_rate_zone_method,
+        payload.nutrition_goal,
+        payload.display_name.is_some(),
+        payload.date_of_birth.is_some(),
+        payload.sex.is_some(),
+        payload.height_cm.is_some(),
+        payload.avatar_url.is_some(),
+        payload.max_heart_rate.is_some(),
+        payload.resting_heart_rate.is_some()
+    )
+    .fetch_one(&state.pool)
+    .await?;
+
+    // Going public lets everyone waiting for approval in.
+    if payload.is_private == Some(false) {
+        sqlx::query!(
+            r#"
+            UPDATE follows SET status = $2, accepted_at = NOW()
+            WHERE followee_id = $1 AND status = $3
+            "#,
+            user_id,
+            FOLLOW_ACCEPTED,
+            FOLLOW_PENDING
+        )
+        .execute(&state.pool)
+        .await?;
+    }
+
+    Ok(Json(profile))
+}
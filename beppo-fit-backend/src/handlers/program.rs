@@ -0,0 +1,392 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    error::AppError,
+    handlers::auth::Claims,
+    models::{
+        program::{
+            Enrollment, NextWorkout, Program, ProgramDetail, SaveProgramRequest,
+            SetTrainingMaxRequest, TrainingMax,
+        },
+        workout::WorkoutDetail,
+    },
+    services::{
+        program::{
+            check_program_weeks, insert_program_weeks, load_program_detail, plan_next_workout,
+            start_planned_workout,
+        },
+        workout::{check_exercise_access, load_workout_detail},
+    },
+    state::AppState,
+};
+
+fn validate_program(payload: &SaveProgramRequest) -> Result<(), AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+    Ok(())
+}
+
+async fn find_own_program<'e>(
+    executor: impl sqlx::PgExecutor<'e>,
+    user_id: Uuid,
+    program_id: Uuid,
+) -> Result<Program, AppError> {
+    sqlx::query_as!(
+        Program,
+        "SELECT * FROM programs WHERE id = $1 AND user_id = $2",
+        program_id,
+        user_id
+    )
+    .fetch_optional(executor)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Program not found".into()))
+}
+
+async fn find_active_enrollment<'e>(
+    executor: impl sqlx::PgExecutor<'e>,
+    user_id: Uuid,
+) -> Result<Enrollment, AppError> {
+    sqlx::query_as!(
+        Enrollment,
+        "SELECT * FROM program_enrollments WHERE user_id = $1 AND completed_at IS NULL FOR UPDATE",
+        user_id
+    )
+    .fetch_optional(executor)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Not enrolled in a program".into()))
+}
+
+pub async fn list_programs(
+    State(state): State<AppState>,
+    claims: Claims,
+) -> Result<Json<Vec<Program>>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let programs = sqlx::query_as!(
+        Program,
+        "SELECT * FROM programs WHERE user_id = $1 ORDER BY name",
+        user_id
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(programs))
+}
+
+pub async fn create_program(
+    State(state): State<AppState>,
+    claims: Claims,
+    Json(payload): Json<SaveProgramRequest>,
+) -> Result<(StatusCode, Json<ProgramDetail>), AppError> {
+    let user_id = claims.user_id()?;
+    validate_program(&payload)?;
+
+    let mut tx = state.pool.begin().await?;
+    check_program_weeks(&mut tx, user_id, &payload.weeks).await?;
+
+    let program = sqlx::query_as!(
+        Program,
+        r#"
+        INSERT INTO programs (user_id, name, description, rounding_kg)
+        VALUES ($1, $2, $3, $4)
+        RETURNING *
+        "#,
+        user_id,
+        payload.name,
+        payload.description,
+        payload.rounding_kg
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    insert_program_weeks(&mut tx, program.id, &payload.weeks).await?;
+    let detail = load_program_detail(&mut tx, program).await?;
+
+    tx.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(detail)))
+}
+
+pub async fn get_program(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(program_id): Path<Uuid>,
+) -> Result<Json<ProgramDetail>, AppError> {
+    let user_id = claims.user_id()?;
+    let mut conn = state.pool.acquire().await?;
+
+    let program = find_own_program(&mut *conn, user_id, program_id).await?;
+    let detail = load_program_detail(&mut conn, program).await?;
+
+    Ok(Json(detail))
+}
+
+/// Replaces a program's structure. Enrollments keep their position, which
+/// skips ahead to the next existing day if that one was removed.
+pub async fn update_program(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(program_id): Path<Uuid>,
+    Json(payload): Json<SaveProgramRequest>,
+) -> Result<Json<ProgramDetail>, AppError> {
+    let user_id = claims.user_id()?;
+    validate_program(&payload)?;
+
+    let mut tx = state.pool.begin().await?;
+    check_program_weeks(&mut tx, user_id, &payload.weeks).await?;
+
+    let program = sqlx::query_as!(
+        Program,
+        r#"
+        UPDATE programs SET name = $3, description = $4, rounding_kg = $5, updated_at = NOW()
+        WHERE id = $1 AND user_id = $2
+        RETURNING *
+        "#,
+        program_id,
+        user_id,
+        payload.name,
+        payload.description,
+        payload.rounding_kg
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Program not found".into()))?;
+
+    sqlx::query!(
+        "DELETE FROM program_weeks WHERE program_id = $1",
+        program_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    insert_program_weeks(&mut tx, program_id, &payload.weeks).await?;
+    let detail = load_program_detail(&mut tx, program).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(detail))
+}
+
+pub async fn delete_program(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(program_id): Path<Uuid>,
+) -> Result<Json<&'static str>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let result = sqlx::query!(
+        "DELETE FROM programs WHERE id = $1 AND user_id = $2",
+        program_id,
+        user_id
+    )
+    .execute(&state.pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Program not found".into()));
+    }
+
+    Ok(Json("Program deleted successfully"))
+}
+
+pub async fn enroll(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(program_id): Path<Uuid>,
+) -> Result<(StatusCode, Json<Enrollment>), AppError> {
+    let user_id = claims.user_id()?;
+    find_own_program(&state.pool, user_id, program_id).await?;
+
+    let enrollment = sqlx::query_as!(
+        Enrollment,
+        "INSERT INTO program_enrollments (user_id, program_id) VALUES ($1, $2) RETURNING *",
+        user_id,
+        program_id
+    )
+    .fetch_one(&state.pool)
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::Database(ref db) if db.is_unique_violation() => {
+            AppError::Conflict("Already enrolled in a program".into())
+        }
+        e => AppError::SqlxError(e),
+    })?;
+
+    Ok((StatusCode::CREATED, Json(enrollment)))
+}
+
+pub async fn get_enrollment(
+    State(state): State<AppState>,
+    claims: Claims,
+) -> Result<Json<Enrollment>, AppError> {
+    let user_id = claims.user_id()?;
+    let enrollment = sqlx::query_as!(
+        Enrollment,
+        "SELECT * FROM program_enrollments WHERE user_id = $1 AND completed_at IS NULL",
+        user_id
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Not enrolled in a program".into()))?;
+
+    Ok(Json(enrollment))
+}
+
+pub async fn leave_enrollment(
+    State(state): State<AppState>,
+    claims: Claims,
+) -> Result<Json<&'static str>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let result = sqlx::query!(
+        "DELETE FROM program_enrollments WHERE user_id = $1 AND completed_at IS NULL",
+        user_id
+    )
+    .execute(&state.pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Not enrolled in a program".into()));
+    }
+
+    Ok(Json("Left the program"))
+}
+
+pub async fn next_workout(
+    State(state): State<AppState>,
+    claims: Claims,
+) -> Result<Json<NextWorkout>, AppError> {
+    let user_id = claims.user_id()?;
+    let mut tx = state.pool.begin().await?;
+
+    let enrollment = find_active_enrollment(&mut *tx, user_id).await?;
+    let plan = plan_next_workout(&mut tx, enrollment)
+        .await?
+        .ok_or_else(|| AppError::NotFound("The program has no workouts left".into()))?;
+
+    tx.commit().await?;
+
+    Ok(Json(plan))
+}
+
+/// Starts the next program workout. Finishing it advances the enrollment.
+pub async fn start_next_workout(
+    State(state): State<AppState>,
+    claims: Claims,
+) -> Result<(StatusCode, Json<WorkoutDetail>), AppError> {
+    let user_id = claims.user_id()?;
+    let mut tx = state.pool.begin().await?;
+
+    let enrollment = find_active_enrollment(&mut *tx, user_id).await?;
+    if let Some(pending_workout_id) = enrollment.pending_workout_id {
+        let unfinished = sqlx::query_scalar!(
+            r#"SELECT finished_at IS NULL AS "unfinished!" FROM workouts WHERE id = $1"#,
+            pending_workout_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        if unfinished {
+            return Err(AppError::Conflict(
+                "The current program workout is still in progress".into(),
+            ));
+        }
+    }
+
+    let plan = plan_next_workout(&mut tx, enrollment)
+        .await?
+        .ok_or_else(|| AppError::NotFound("The program has no workouts left".into()))?;
+    let workout_id = start_planned_workout(&mut tx, &plan).await?;
+    let detail = load_workout_detail(&mut tx, user_id, workout_id).await?;
+
+    tx.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(detail)))
+}
+
+pub async fn list_training_maxes(
+    State(state): State<AppState>,
+    claims: Claims,
+) -> Result<Json<Vec<TrainingMax>>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let training_maxes = sqlx::query_as!(
+        TrainingMax,
+        r#"
+        SELECT tm.exercise_id, e.name AS exercise_name, tm.weight_kg, tm.updated_at
+        FROM training_maxes tm
+        JOIN exercises e ON e.id = tm.exercise_id
+        WHERE tm.user_id = $1
+        ORDER BY e.name
+        "#,
+        user_id
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(training_maxes))
+}
+
+pub async fn set_training_max(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(exercise_id): Path<Uuid>,
+    Json(payload): Json<SetTrainingMaxRequest>,
+) -> Result<Json<TrainingMax>, AppError> {
+    let user_id = claims.user_id()?;
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+    check_exercise_access(&state.pool, user_id, &[exercise_id]).await?;
+
+    let training_max = sqlx::query_as!(
+        TrainingMax,
+        r#"
+        WITH saved AS (
+            INSERT INTO training_maxes (user_id, exercise_id, weight_kg)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_id, exercise_id)
+            DO UPDATE SET weight_kg = EXCLUDED.weight_kg, updated_at = NOW()
+            RETURNING exercise_id, weight_kg, updated_at
+        )
+        SELECT saved.exercise_id, e.name AS exercise_name, saved.weight_kg, saved.updated_at
+        FROM saved
+        JOIN exercises e ON e.id = saved.exercise_id
+        "#,
+        user_id,
+        exercise_id,
+        payload.weight_kg
+    )
+    .fetch_one(&state.pool)
+    .await?;
+
+    Ok(Json(training_max))
+}
+
+pub async fn delete_training_max(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(exercise_id): Path<Uuid>,
+) -> Result<Json<&'static str>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let result = sqlx::query!(
+        "DELETE FROM training_maxes WHERE user_id = $1 AND exercise_id = $2",
+        user_id,
+        exercise_id
+    )
+    .execute(&state.pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Training max not found".into()));
+    }
+
+    Ok(Json("Training max deleted successfully"))
+}
@@ -0,0 +1,344 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{Duration, NaiveDate};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    error::AppError,
+    handlers::auth::Claims,
+    models::schedule::{
+        AdherenceReport, CalendarEntry, CalendarQuery, CompleteSessionRequest, SaveScheduleRequest,
+        Schedule, ScheduledSession, SESSION_DONE, SESSION_SKIPPED,
+    },
+    services::{
+        profile::ensure_profile,
+        recurrence::RecurrenceRule,
+        schedule::{adherence_report, calendar_entries, schedule_dates, schedule_title},
+    },
+    state::AppState,
+};
+
+const DEFAULT_RANGE_DAYS: i64 = 28;
+const MAX_RANGE_DAYS: i64 = 366;
+
+fn validate_schedule(payload: &SaveScheduleRequest) -> Result<Option<String>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+    // Store rules in canonical form
+    payload
+        .rrule
+        .as_deref()
+        .map(|rule| {
+            rule.parse::<RecurrenceRule>()
+                .map(|rule| rule.to_string())
+                .map_err(AppError::BadRequest)
+        })
+        .transpose()
+}
+
+fn check_range(from: NaiveDate, to: NaiveDate) -> Result<(), AppError> {
+    if from > to {
+        return Err(AppError::BadRequest("from must not be after to".into()));
+    }
+    if (to - from).num_days() >= MAX_RANGE_DAYS {
+        return Err(AppError::BadRequest(format!(
+            "Date range must be at most {} days",
+            MAX_RANGE_DAYS
+        )));
+    }
+    Ok(())
+}
+
+async fn find_own_schedule<'e>(
+    executor: impl sqlx::PgExecutor<'e>,
+    user_id: Uuid,
+    schedule_id: Uuid,
+) -> Result<Schedule, AppError> {
+    sqlx::query_as!(
+        Schedule,
+        "SELECT * FROM workout_schedules WHERE id = $1 AND user_id = $2",
+        schedule_id,
+        user_id
+    )
+    .fetch_optional(executor)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Schedule not found".into()))
+}
+
+pub async fn list_schedules(
+    State(state): State<AppState>,
+    claims: Claims,
+) -> Result<Json<Vec<Schedule>>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let schedules = sqlx::query_as!(
+        Schedule,
+        "SELECT * FROM workout_schedules WHERE user_id = $1 ORDER BY start_date, start_time",
+        user_id
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(schedules))
+}
+
+pub async fn create_schedule(
+    State(state): State<AppState>,
+    claims: Claims,
+    Json(payload): Json<SaveScheduleRequest>,
+) -> Result<(StatusCode, Json<Schedule>), AppError> {
+    let user_id = claims.user_id()?;
+    let rrule = validate_schedule(&payload)?;
+
+    let mut conn = state.pool.acquire().await?;
+    let title = schedule_title(&mut conn, user_id, &payload).await?;
+
+    let schedule = sqlx::query_as!(
+        Schedule,
+        r#"
+        INSERT INTO workout_schedules (user_id, title, template_id, program_day_id, start_date, start_time, rrule, notes)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING *
+        "#,
+        user_id,
+        title,
+        payload.template_id,
+        payload.program_day_id,
+        payload.start_date,
+        payload.start_time,
+        rrule,
+        payload.notes
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(schedule)))
+}
+
+pub async fn get_schedule(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(schedule_id): Path<Uuid>,
+) -> Result<Json<Schedule>, AppError> {
+    let user_id = claims.user_id()?;
+
+    Ok(Json(
+        find_own_schedule(&state.pool, user_id, schedule_id).await?,
+    ))
+}
+
+/// Replaces a schedule. Outcomes recorded for past occurrences are kept.
+pub async fn update_schedule(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(schedule_id): Path<Uuid>,
+    Json(payload): Json<SaveScheduleRequest>,
+) -> Result<Json<Schedule>, AppError> {
+    let user_id = claims.user_id()?;
+    let rrule = validate_schedule(&payload)?;
+
+    let mut conn = state.pool.acquire().await?;
+    let title = schedule_title(&mut conn, user_id, &payload).await?;
+
+    let schedule = sqlx::query_as!(
+        Schedule,
+        r#"
+        UPDATE workout_schedules SET
+            title = $3, template_id = $4, program_day_id = $5, start_date = $6,
+            start_time = $7, rrule = $8, notes = $9, updated_at = NOW()
+        WHERE id = $1 AND user_id = $2
+        RETURNING *
+        "#,
+        schedule_id,
+        user_id,
+        title,
+        payload.template_id,
+        payload.program_day_id,
+        payload.start_date,
+        payload.start_time,
+        rrule,
+        payload.notes
+    )
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Schedule not found".into()))?;
+
+    Ok(Json(schedule))
+}
+
+pub async fn delete_schedule(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(schedule_id): Path<Uuid>,
+) -> Result<Json<&'static str>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let result = sqlx::query!(
+        "DELETE FROM workout_schedules WHERE id = $1 AND user_id = $2",
+        schedule_id,
+        user_id
+    )
+    .execute(&state.pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Schedule not found".into()));
+    }
+
+    Ok(Json("Schedule deleted successfully"))
+}
+
+/// Planned sessions in a date range, by default the next four weeks.
+pub async fn get_calendar(
+    State(state): State<AppState>,
+    claims: Claims,
+    Query(query): Query<CalendarQuery>,
+) -> Result<Json<Vec<CalendarEntry>>, AppError> {
+    let user_id = claims.user_id()?;
+    let mut conn = state.pool.acquire().await?;
+
+    let today = ensure_profile(&mut conn, user_id).await?.today();
+    let from = query.from.unwrap_or(today);
+    let to = query
+        .to
+        .unwrap_or(from + Duration::days(DEFAULT_RANGE_DAYS - 1));
+    check_range(from, to)?;
+
+    Ok(Json(
+        calendar_entries(&mut conn, user_id, from, to, today).await?,
+    ))
+}
+
+/// How many planned sessions were done, by default over the last four weeks.
+pub async fn get_adherence(
+    State(state): State<AppState>,
+    claims: Claims,
+    Query(query): Query<CalendarQuery>,
+) -> Result<Json<AdherenceReport>, AppError> {
+    let user_id = claims.user_id()?;
+    let mut conn = state.pool.acquire().await?;
+
+    let today = ensure_profile(&mut conn, user_id).await?.today();
+    let to = query.to.unwrap_or(today);
+    let from = query
+        .from
+        .unwrap_or(to - Duration::days(DEFAULT_RANGE_DAYS - 1));
+    check_range(from, to)?;
+
+    let entries = calendar_entries(&mut conn, user_id, from, to, today).await?;
+
+    Ok(Json(adherence_report(&entries, from, to)))
+}
+
+/// Loads a schedule and checks `date` is one of its occurrences.
+async fn find_occurrence(
+    conn: &mut sqlx::PgConnection,
+    user_id: Uuid,
+    schedule_id: Uuid,
+    date: NaiveDate,
+) -> Result<Schedule, AppError> {
+    let schedule = find_own_schedule(&mut *conn, user_id, schedule_id).await?;
+    if schedule_dates(&schedule, date, date)?.is_empty() {
+        return Err(AppError::NotFound(format!(
+            "Schedule has no session on {}",
+            date
+        )));
+    }
+    Ok(schedule)
+}
+
+/// Marks a planned session as done by linking the workout that was logged for it.
+pub async fn complete_session(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path((schedule_id, date)): Path<(Uuid, NaiveDate)>,
+    Json(payload): Json<CompleteSessionRequest>,
+) -> Result<Json<ScheduledSession>, AppError> {
+    let user_id = claims.user_id()?;
+    let mut conn = state.pool.acquire().await?;
+
+    find_occurrence(&mut conn, user_id, schedule_id, date).await?;
+    let owns_workout = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM workouts WHERE id = $1 AND user_id = $2) AS "exists!""#,
+        payload.workout_id,
+        user_id
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+    if !owns_workout {
+        return Err(AppError::NotFound("Workout not found".into()));
+    }
+
+    let session = sqlx::query_as!(
+        ScheduledSession,
+        r#"
+        INSERT INTO scheduled_sessions (schedule_id, scheduled_date, status, workout_id)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (schedule_id, scheduled_date)
+        DO UPDATE SET status = EXCLUDED.status, workout_id = EXCLUDED.workout_id
+        RETURNING *
+        "#,
+        schedule_id,
+        date,
+        SESSION_DONE,
+        payload.workout_id
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(Json(session))
+}
+
+pub async fn skip_session(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path((schedule_id, date)): Path<(Uuid, NaiveDate)>,
+) -> Result<Json<ScheduledSession>, AppError> {
+    let user_id = claims.user_id()?;
+    let mut conn = state.pool.acquire().await?;
+
+    find_occurrence(&mut conn, user_id, schedule_id, date).await?;
+    let session = sqlx::query_as!(
+        ScheduledSession,
+        r#"
+        INSERT INTO scheduled_sessions (schedule_id, scheduled_date, status)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (schedule_id, scheduled_date)
+        DO UPDATE SET status = EXCLUDED.status, workout_id = NULL
+        RETURNING *
+        "#,
+        schedule_id,
+        date,
+        SESSION_SKIPPED
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(Json(session))
+}
+
+/// Clears a recorded outcome so the session counts as planned again.
+pub async fn reset_session(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path((schedule_id, date)): Path<(Uuid, NaiveDate)>,
+) -> Result<Json<&'static str>, AppError> {
+    let user_id = claims.user_id()?;
+    let mut conn = state.pool.acquire().await?;
+
+    find_occurrence(&mut conn, user_id, schedule_id, date).await?;
+    sqlx::query!(
+        "DELETE FROM scheduled_sessions WHERE schedule_id = $1 AND scheduled_date = $2",
+        schedule_id,
+        date
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(Json("Session reset successfully"))
+}
@@ -0,0 +1,469 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    handlers::auth::Claims,
+    models::{
+        notification::{
+            NOTIFICATION_FOLLOW_ACCEPTED, NOTIFICATION_FOLLOW_REQUEST, NOTIFICATION_NEW_FOLLOWER,
+        },
+        social::{
+            CursorQuery, FeedItem, Follow, Page, SocialUser, FOLLOW_ACCEPTED, FOLLOW_PENDING,
+        },
+        workout::{WorkoutDetail, WorkoutSummary},
+    },
+    services::{
+        notification::notify,
+        profile::ensure_profile,
+        social::{
+            can_view_workout, decode_cursor, into_page, is_blocked, load_feed, load_user_workouts,
+        },
+        workout::load_workout_detail,
+    },
+    state::AppState,
+};
+
+const DEFAULT_PAGE_SIZE: i64 = 20;
+const MAX_PAGE_SIZE: i64 = 100;
+
+fn page_size(query: &CursorQuery) -> i64 {
+    query
+        .limit
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE)
+}
+
+/// Fails unless `other_id` is an existing user. Users blocked in either
+/// direction are reported as missing.
+async fn check_other_user(
+    conn: &mut sqlx::PgConnection,
+    user_id: Uuid,
+    other_id: Uuid,
+) -> Result<(), AppError> {
+    let exists = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM users WHERE id = $1) AS "exists!""#,
+        other_id
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+    if !exists || is_blocked(&mut *conn, user_id, other_id).await? {
+        return Err(AppError::NotFound("User not found".into()));
+    }
+    Ok(())
+}
+
+async fn display_name(conn: &mut sqlx::PgConnection, user_id: Uuid) -> Result<String, AppError> {
+    Ok(ensure_profile(&mut *conn, user_id)
+        .await?
+        .display_name
+        .unwrap_or_else(|| "Someone".into()))
+}
+
+/// Follows a user. Private profiles get a pending request to approve instead.
+pub async fn follow_user(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(followee_id): Path<Uuid>,
+) -> Result<(StatusCode, Json<Follow>), AppError> {
+    let user_id = claims.user_id()?;
+    if followee_id == user_id {
+        return Err(AppError::BadRequest("You cannot follow yourself".into()));
+    }
+    let mut tx = state.pool.begin().await?;
+
+    check_other_user(&mut tx, user_id, followee_id).await?;
+
+    let followee = ensure_profile(&mut tx, followee_id).await?;
+    let status = if followee.is_private {
+        FOLLOW_PENDING
+    } else {
+        FOLLOW_ACCEPTED
+    };
+    let inserted = sqlx::query_as!(
+        Follow,
+        r#"
+        INSERT INTO follows (follower_id, followee_id, status, accepted_at)
+        VALUES ($1, $2, $3, CASE WHEN $4 THEN NOW() END)
+        ON CONFLICT (follower_id, followee_id) DO NOTHING
+        RETURNING *
+        "#,
+        user_id,
+        followee_id,
+        status,
+        !followee.is_private
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    // Already following or requested: hand back the existing follow.
+    let Some(follow) = inserted else {
+        let existing = sqlx::query_as!(
+            Follow,
+            "SELECT * FROM follows WHERE follower_id = $1 AND followee_id = $2",
+            user_id,
+            followee_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        return Ok((StatusCode::OK, Json(existing)));
+    };
+
+    let name = display_name(&mut tx, user_id).await?;
+    let (kind, title) = if followee.is_private {
+        (
+            NOTIFICATION_FOLLOW_REQUEST,
+            format!("{} wants to follow you", name),
+        )
+    } else {
+        (
+            NOTIFICATION_NEW_FOLLOWER,
+            format!("{} started following you", name),
+        )
+    };
+    notify(&mut *tx, followee_id, kind, &title, None, None).await?;
+
+    tx.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(follow)))
+}
+
+/// Stops following a user or withdraws a pending request.
+pub async fn unfollow_user(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(followee_id): Path<Uuid>,
+) -> Result<Json<&'static str>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let result = sqlx::query!(
+        "DELETE FROM follows WHERE follower_id = $1 AND followee_id = $2",
+        user_id,
+        followee_id
+    )
+    .execute(&state.pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Not following this user".into()));
+    }
+
+    Ok(Json("Unfollowed"))
+}
+
+pub async fn list_followers(
+    State(state): State<AppState>,
+    claims: Claims,
+) -> Result<Json<Vec<SocialUser>>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let followers = sqlx::query_as!(
+        SocialUser,
+        r#"
+        SELECT f.follower_id AS user_id, p.display_name AS "display_name?", p.avatar_url AS "avatar_url?",
+               COALESCE(f.accepted_at, f.created_at) AS "since!"
+        FROM follows f
+        LEFT JOIN user_profiles p ON p.user_id = f.follower_id
+        WHERE f.followee_id = $1 AND f.status = $2
+        ORDER BY 4 DESC
+        "#,
+        user_id,
+        FOLLOW_ACCEPTED
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(followers))
+}
+
+pub async fn list_following(
+    State(state): State<AppState>,
+    claims: Claims,
+) -> Result<Json<Vec<SocialUser>>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let following = sqlx::query_as!(
+        SocialUser,
+        r#"
+        SELECT f.followee_id AS user_id, p.display_name AS "display_name?", p.avatar_url AS "avatar_url?",
+               COALESCE(f.accepted_at, f.created_at) AS "since!"
+        FROM follows f
+        LEFT JOIN user_profiles p ON p.user_id = f.followee_id
+        WHERE f.follower_id = $1 AND f.status = $2
+        ORDER BY 4 DESC
+        "#,
+        user_id,
+        FOLLOW_ACCEPTED
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(following))
+}
+
+/// Removes someone from the user's followers.
+pub async fn remove_follower(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(follower_id): Path<Uuid>,
+) -> Result<Json<&'static str>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let result = sqlx::query!(
+        "DELETE FROM follows WHERE follower_id = $1 AND followee_id = $2 AND status = $3",
+        follower_id,
+        user_id,
+        FOLLOW_ACCEPTED
+    )
+    .execute(&state.pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Follower not found".into()));
+    }
+
+    Ok(Json("Follower removed"))
+}
+
+/// Pending requests to follow the user, oldest first.
+pub async fn list_follow_requests(
+    State(state): State<AppState>,
+    claims: Claims,
+) -> Result<Json<Vec<SocialUser>>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let requests = sqlx::query_as!(
+        SocialUser,
+        r#"
+        SELECT f.follower_id AS user_id, p.display_name AS "display_name?", p.avatar_url AS "avatar_url?",
+               f.created_at AS since
+        FROM follows f
+        LEFT JOIN user_profiles p ON p.user_id = f.follower_id
+        WHERE f.followee_id = $1 AND f.status = $2
+        ORDER BY f.created_at
+        "#,
+        user_id,
+        FOLLOW_PENDING
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(requests))
+}
+
+pub async fn accept_follow_request(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(follower_id): Path<Uuid>,
+) -> Result<Json<Follow>, AppError> {
+    let user_id = claims.user_id()?;
+    let mut tx = state.pool.begin().await?;
+
+    let follow = sqlx::query_as!(
+        Follow,
+        r#"
+        UPDATE follows SET status = $3, accepted_at = NOW()
+        WHERE follower_id = $1 AND followee_id = $2 AND status = $4
+        RETURNING *
+        "#,
+        follower_id,
+        user_id,
+        FOLLOW_ACCEPTED,
+        FOLLOW_PENDING
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Follow request not found".into()))?;
+
+    let name = display_name(&mut tx, user_id).await?;
+    notify(
+        &mut *tx,
+        follower_id,
+        NOTIFICATION_FOLLOW_ACCEPTED,
+        &format!("{} accepted your follow request", name),
+        None,
+        None,
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(follow))
+}
+
+pub async fn decline_follow_request(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(follower_id): Path<Uuid>,
+) -> Result<Json<&'static str>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let result = sqlx::query!(
+        "DELETE FROM follows WHERE follower_id = $1 AND followee_id = $2 AND status = $3",
+        follower_id,
+        user_id,
+        FOLLOW_PENDING
+    )
+    .execute(&state.pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Follow request not found".into()));
+    }
+
+    Ok(Json("Follow request declined"))
+}
+
+/// Blocks a user and ends any follow between the two in either direction.
+pub async fn block_user(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(blocked_id): Path<Uuid>,
+) -> Result<Json<&'static str>, AppError> {
+    let user_id = claims.user_id()?;
+    if blocked_id == user_id {
+        return Err(AppError::BadRequest("You cannot block yourself".into()));
+    }
+    let mut tx = state.pool.begin().await?;
+
+    let exists = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM users WHERE id = $1) AS "exists!""#,
+        blocked_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    if !exists {
+        return Err(AppError::NotFound("User not found".into()));
+    }
+
+    sqlx::query!(
+        "INSERT INTO blocks (blocker_id, blocked_id) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+        user_id,
+        blocked_id
+    )
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query!(
+        r#"
+        DELETE FROM follows
+        WHERE (follower_id = $1 AND followee_id = $2) OR (follower_id = $2 AND followee_id = $1)
+        "#,
+        user_id,
+        blocked_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json("User blocked"))
+}
+
+pub async fn unblock_user(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(blocked_id): Path<Uuid>,
+) -> Result<Json<&'static str>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let result = sqlx::query!(
+        "DELETE FROM blocks WHERE blocker_id = $1 AND blocked_id = $2",
+        user_id,
+        blocked_id
+    )
+    .execute(&state.pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("User is not blocked".into()));
+    }
+
+    Ok(Json("User unblocked"))
+}
+
+pub async fn list_blocks(
+    State(state): State<AppState>,
+    claims: Claims,
+) -> Result<Json<Vec<SocialUser>>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let blocks = sqlx::query_as!(
+        SocialUser,
+        r#"
+        SELECT b.blocked_id AS user_id, p.display_name AS "display_name?", p.avatar_url AS "avatar_url?",
+               b.created_at AS since
+        FROM blocks b
+        LEFT JOIN user_profiles p ON p.user_id = b.blocked_id
+        WHERE b.blocker_id = $1
+        ORDER BY b.created_at DESC
+        "#,
+        user_id
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(blocks))
+}
+
+pub async fn feed(
+    State(state): State<AppState>,
+    claims: Claims,
+    Query(query): Query<CursorQuery>,
+) -> Result<Json<Page<FeedItem>>, AppError> {
+    let user_id = claims.user_id()?;
+    let cursor = query.cursor.as_deref().map(decode_cursor).transpose()?;
+
+    let mut conn = state.pool.acquire().await?;
+    let page = load_feed(&mut conn, user_id, cursor, page_size(&query)).await?;
+
+    Ok(Json(page))
+}
+
+/// Another user's finished workouts that the caller may see, newest first.
+pub async fn list_user_workouts(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(owner_id): Path<Uuid>,
+    Query(query): Query<CursorQuery>,
+) -> Result<Json<Page<WorkoutSummary>>, AppError> {
+    let user_id = claims.user_id()?;
+    let cursor = query.cursor.as_deref().map(decode_cursor).transpose()?;
+    let limit = page_size(&query);
+
+    let mut conn = state.pool.acquire().await?;
+    if owner_id != user_id {
+        check_other_user(&mut conn, user_id, owner_id).await?;
+    }
+
+    let workouts = load_user_workouts(&mut conn, user_id, owner_id, cursor, limit + 1).await?;
+
+    Ok(Json(into_page(workouts, limit, |w| {
+        (w.finished_at.unwrap_or(w.started_at), w.id)
+    })))
+}
+
+/// A workout of another user, with the same visibility rules as the list.
+pub async fn get_user_workout(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path((owner_id, workout_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<WorkoutDetail>, AppError> {
+    let user_id = claims.user_id()?;
+    let mut conn = state.pool.acquire().await?;
+
+    if owner_id != user_id {
+        check_other_user(&mut conn, user_id, owner_id).await?;
+        let visible = can_view_workout(&mut conn, user_id, owner_id, workout_id).await?;
+        if !visible {
+            return Err(AppError::NotFound("Workout not found".into()));
+        }
+    }
+
+    let detail = load_workout_detail(&mut conn, owner_id, workout_id).await?;
+
+    Ok(Json(detail))
+}
@@ -0,0 +1,386 @@
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use std::collections::HashMap;
+
+use crate::{
+    error::AppError,
+    handlers::auth::Claims,
+    models::{
+        record::{ExerciseRecords, PersonalRecord, RecordQuery, RECORD_TYPES},
+        stats::{
+            DurationBucket, DurationDistribution, EstimatedMaxPoint, MuscleVolume, StatsQuery,
+            TonnagePoint, TrainingFrequency,
+        },
+    },
+    services::{
+        record::{beats, MAX_REPS_FOR_1RM},
+        stats::resolve_range,
+    },
+    state::AppState,
+};
+
+const DURATION_BUCKET_MINUTES: i32 = 15;
+
+/// Working-set volume per primary muscle group and period.
+pub async fn muscle_volume(
+    State(state): State<AppState>,
+    claims: Claims,
+    Query(query): Query<StatsQuery>,
+) -> Result<Json<Vec<MuscleVolume>>, AppError> {
+    let user_id = claims.user_id()?;
+    let range = resolve_range(
+        &state.pool,
+        user_id,
+        query.from,
+        query.to,
+        query.period.as_deref(),
+    )
+    .await?;
+
+    let rows = sqlx::query_as!(
+        MuscleVolume,
+        r#"
+        SELECT
+            date_trunc($5, w.started_at AT TIME ZONE $4)::DATE AS "period_start!",
+            m.muscle AS "muscle_group!",
+            COUNT(*) AS "sets!",
+            COALESCE(SUM(s.reps * s.weight_kg), 0) AS "volume_kg!"
+        FROM workouts w
+        JOIN workout_exercises we ON we.workout_id = w.id
+        JOIN exercises e ON e.id = we.exercise_id
+        JOIN sets s ON s.workout_exercise_id = we.id
+        CROSS JOIN LATERAL UNNEST(e.primary_muscles) AS m(muscle)
+        WHERE w.user_id = $1 AND w.finished_at IS NOT NULL
+          AND w.started_at >= $2 AND w.started_at < $3
+          AND s.set_type <> 'warmup'
+        GROUP BY 1, 2
+        ORDER BY 1, 2
+        "#,
+        user_id,
+        range.start,
+        range.end,
+        range.timezone,
+        range.period
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(rows))
+}
+
+/// Workouts and distinct training days per period.
+pub async fn training_frequency(
+    State(state): State<AppState>,
+    claims: Claims,
+    Query(query): Query<StatsQuery>,
+) -> Result<Json<Vec<TrainingFrequency>>, AppError> {
+    let user_id = claims.user_id()?;
+    let range = resolve_range(
+        &state.pool,
+        user_id,
+        query.from,
+        query.to,
+        query.period.as_deref(),
+    )
+    .await?;
+
+    let rows = sqlx::query_as!(
+        TrainingFrequency,
+        r#"
+        SELECT
+            date_trunc($5, w.started_at AT TIME ZONE $4)::DATE AS "period_start!",
+            COUNT(*) AS "workouts!",
+            COUNT(DISTINCT (w.started_at AT TIME ZONE $4)::DATE) AS "training_days!"
+        FROM workouts w
+        WHERE w.user_id = $1 AND w.finished_at IS NOT NULL
+          AND w.started_at >= $2 AND w.started_at < $3
+        GROUP BY 1
+        ORDER BY 1
+        "#,
+        user_id,
+        range.start,
+        range.end,
+        range.timezone,
+        range.period
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(rows))
+}
+
+/// Best estimated one-rep max of an exercise per training day.
+pub async fn estimated_max_trend(
+    State(state): State<AppState>,
+    claims: Claims,
+    Query(query): Query<StatsQuery>,
+) -> Result<Json<Vec<EstimatedMaxPoint>>, AppError> {
+    let user_id = claims.user_id()?;
+    let exercise_id = query
+        .exercise_id
+        .ok_or_else(|| AppError::BadRequest("exercise_id is required".into()))?;
+    let range = resolve_range(
+        &state.pool,
+        user_id,
+        query.from,
+        query.to,
+        query.period.as_deref(),
+    )
+    .await?;
+
+    // Same formulas as services::record::estimate_1rm.
+    let rows = sqlx::query_as!(
+        EstimatedMaxPoint,
+        r#"
+        SELECT
+            (w.started_at AT TIME ZONE $4)::DATE AS "date!",
+            ROUND(MAX(
+                CASE
+                    WHEN s.reps = 1 THEN s.weight_kg
+                    WHEN s.reps <= 10 THEN s.weight_kg * 36.0 / (37 - s.reps)
+                    ELSE s.weight_kg * (1 + s.reps / 30.0)
+                END
+            )::NUMERIC, 1)::DOUBLE PRECISION AS "estimated_1rm!"
+        FROM workouts w
+        JOIN workout_exercises we ON we.workout_id = w.id
+        JOIN sets s ON s.workout_exercise_id = we.id
+        WHERE w.user_id = $1 AND w.finished_at IS NOT NULL
+          AND w.started_at >= $2 AND w.started_at < $3
+          AND we.exercise_id = $5
+          AND s.set_type <> 'warmup' AND s.reps BETWEEN 1 AND $6 AND s.weight_kg > 0
+        GROUP BY 1
+        ORDER BY 1
+        "#,
+        user_id,
+        range.start,
+        range.end,
+        range.timezone,
+        exercise_id,
+        MAX_REPS_FOR_1RM
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(rows))
+}
+
+/// How long finished workouts took, in fixed-width buckets.
+pub async fn duration_distribution(
+    State(state): State<AppState>,
+    claims: Claims,
+    Query(query): Query<StatsQuery>,
+) -> Result<Json<DurationDistribution>, AppError> {
+    let user_id = claims.user_id()?;
+    let range = resolve_range(
+        &state.pool,
+        user_id,
+        query.from,
+        query.to,
+        query.period.as_deref(),
+    )
+    .await?;
+
+    let summary = sqlx::query!(
+        r#"
+        SELECT
+            COUNT(*) AS "workouts!",
+            AVG(EXTRACT(EPOCH FROM finished_at - started_at) / 60)::DOUBLE PRECISION AS average_minutes,
+            PERCENTILE_CONT(0.5) WITHIN GROUP (ORDER BY EXTRACT(EPOCH FROM finished_at - started_at) / 60) AS median_minutes
+        FROM workouts
+        WHERE user_id = $1 AND finished_at IS NOT NULL
+          AND started_at >= $2 AND started_at < $3
+        "#,
+        user_id,
+        range.start,
+        range.end
+    )
+    .fetch_one(&state.pool)
+    .await?;
+
+    let buckets = sqlx::query!(
+        r#"
+        SELECT
+            (FLOOR(EXTRACT(EPOCH FROM finished_at - started_at) / 60 / $4) * $4)::INTEGER AS "minutes_from!",
+            COUNT(*) AS "workouts!"
+        FROM workouts
+        WHERE user_id = $1 AND finished_at IS NOT NULL
+          AND started_at >= $2 AND started_at < $3
+        GROUP BY 1
+        ORDER BY 1
+        "#,
+        user_id,
+        range.start,
+        range.end,
+        DURATION_BUCKET_MINUTES as f64
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(DurationDistribution {
+        workouts: summary.workouts,
+        average_minutes: summary.average_minutes,
+        median_minutes: summary.median_minutes,
+        buckets: buckets
+            .into_iter()
+            .map(|b| DurationBucket {
+                minutes_from: b.minutes_from,
+                minutes_to: b.minutes_from + DURATION_BUCKET_MINUTES,
+                workouts: b.workouts,
+            })
+            .collect(),
+    }))
+}
+
+/// Total weight moved (reps x weight of working sets) per period.
+pub async fn tonnage(
+    State(state): State<AppState>,
+    claims: Claims,
+    Query(query): Query<StatsQuery>,
+) -> Result<Json<Vec<TonnagePoint>>, AppError> {
+    let user_id = claims.user_id()?;
+    let range = resolve_range(
+        &state.pool,
+        user_id,
+        query.from,
+        query.to,
+        query.period.as_deref(),
+    )
+    .await?;
+
+    let rows = sqlx::query_as!(
+        TonnagePoint,
+        r#"
+        SELECT
+            date_trunc($5, w.started_at AT TIME ZONE $4)::DATE AS "period_start!",
+            COUNT(DISTINCT w.id) AS "workouts!",
+            COUNT(s.id) AS "sets!",
+            COALESCE(SUM(s.reps * s.weight_kg), 0) AS "tonnage_kg!"
+        FROM workouts w
+        LEFT JOIN workout_exercises we ON we.workout_id = w.id
+        LEFT JOIN sets s ON s.workout_exercise_id = we.id AND s.set_type <> 'warmup'
+        WHERE w.user_id = $1 AND w.finished_at IS NOT NULL
+          AND w.started_at >= $2 AND w.started_at < $3
+        GROUP BY 1
+        ORDER BY 1
+        "#,
+        user_id,
+        range.start,
+        range.end,
+        range.timezone,
+        range.period
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(rows))
+}
+
+/// Personal records grouped by exercise, optionally narrowed to one exercise,
+/// record type or the workout that set them.
+pub async fn list_personal_records(
+    State(state): State<AppState>,
+    claims: Claims,
+    Query(query): Query<RecordQuery>,
+) -> Result<Json<Vec<ExerciseRecords>>, AppError> {
+    let user_id = claims.user_id()?;
+    if let Some(record_type) = &query.record_type {
+        if !RECORD_TYPES.contains(&record_type.as_str()) {
+            return Err(AppError::BadRequest(format!(
+                "Unknown record type '{}'. Allowed: {}",
+                record_type,
+                RECORD_TYPES.join(", ")
+            )));
+        }
+    }
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT r.id, r.exercise_id, e.name AS exercise_name, r.workout_id, r.set_id, r.record_type,
+               r.value, r.weight_kg, r.distance_m, r.achieved_at, r.created_at
+        FROM personal_records r
+        JOIN exercises e ON e.id = r.exercise_id
+        WHERE r.user_id = $1
+          AND ($2::UUID IS NULL OR r.exercise_id = $2)
+          AND ($3::VARCHAR IS NULL OR r.record_type = $3)
+          AND ($4::UUID IS NULL OR r.workout_id = $4)
+        ORDER BY e.name, r.exercise_id, r.achieved_at, r.created_at
+        "#,
+        user_id,
+        query.exercise_id,
+        query.record_type,
+        query.workout_id
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    let mut exercises: Vec<ExerciseRecords> = Vec::new();
+    for row in rows {
+        let record = PersonalRecord {
+            id: row.id,
+            user_id,
+            exercise_id: row.exercise_id,
+            workout_id: row.workout_id,
+            set_id: row.set_id,
+            record_type: row.record_type,
+            value: row.value,
+            weight_kg: row.weight_kg,
+            distance_m: row.distance_m,
+            achieved_at: row.achieved_at,
+            created_at: row.created_at,
+        };
+        match exercises.last_mut() {
+            Some(last) if last.exercise_id == record.exercise_id => last.history.push(record),
+            _ => exercises.push(ExerciseRecords {
+                exercise_id: record.exercise_id,
+                exercise_name: row.exercise_name,
+                current: Vec::new(),
+                history: vec![record],
+            }),
+        }
+    }
+
+    for exercise in &mut exercises {
+        exercise.current = current_records(&exercise.history);
+    }
+
+    Ok(Json(exercises))
+}
+
+/// The standing best of each record kind; rep and time records are kept per weight and distance.
+fn current_records(history: &[PersonalRecord]) -> Vec<PersonalRecord> {
+    let mut bests: HashMap<(&str, Option<u64>, Option<u64>), &PersonalRecord> = HashMap::new();
+    for record in history {
+        let key = (
+            record.record_type.as_str(),
+            record.weight_kg.map(f64::to_bits),
+            record.distance_m.map(f64::to_bits),
+        );
+        match bests.get(&key) {
+            Some(best) if !beats(&record.record_type, record.value, best.value) => {}
+            _ => {
+                bests.insert(key, record);
+            }
+        }
+    }
+
+    let mut current: Vec<PersonalRecord> = bests.into_values().cloned().collect();
+    current.sort_by(|a, b| {
+        RECORD_TYPES
+            .iter()
+            .position(|t| *t == a.record_type)
+            .cmp(&RECORD_TYPES.iter().position(|t| *t == b.record_type))
+            .then(
+                a.weight_kg
+                    .partial_cmp(&b.weight_kg)
+                    .unwrap_or(std::cmp::Ordering::Equal),
+            )
+            .then(
+                a.distance_m
+                    .partial_cmp(&b.distance_m)
+                    .unwrap_or(std::cmp::Ordering::Equal),
+            )
+    });
+    current
+}
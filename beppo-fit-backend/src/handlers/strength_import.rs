@@ -0,0 +1,268 @@
+use axum::{
+    extract::{Multipart, Path, State},
+    http::StatusCode,
+    Json,
+};
+use std::collections::HashSet;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    error::AppError,
+    handlers::auth::Claims,
+    models::{
+        profile::{UserProfile, UNIT_SYSTEMS},
+        strength_import::{ImportPreview, ImportResult, SaveMappingsRequest, StrengthImport},
+        validation::one_of,
+    },
+    services::{
+        profile::ensure_profile,
+        strength_import::{
+            build_preview, commit_history, parse_history, ParsedHistory, IMPORT_RETENTION_HOURS,
+            MAX_IMPORT_FILE_BYTES,
+        },
+        workout::check_exercise_access,
+    },
+    state::AppState,
+};
+
+async fn find_own_import(
+    conn: &mut sqlx::PgConnection,
+    user_id: Uuid,
+    import_id: Uuid,
+) -> Result<StrengthImport, AppError> {
+    sqlx::query_as!(
+        StrengthImport,
+        "SELECT * FROM strength_imports WHERE id = $1 AND user_id = $2",
+        import_id,
+        user_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Import not found".into()))
+}
+
+/// Parses the stored upload again, away from the async runtime.
+async fn parse_import(
+    import: &StrengthImport,
+    profile: UserProfile,
+) -> Result<ParsedHistory, AppError> {
+    let data = import.data.clone();
+    let units = import.units.clone();
+    tokio::task::spawn_blocking(move || parse_history(&data, &units, &profile))
+        .await
+        .map_err(|_| AppError::InternalServerError)?
+}
+
+/// Uploads a Strong, Hevy or FitNotes export and returns the dry-run preview.
+/// Nothing is imported until the preview is committed.
+pub async fn create_import(
+    State(state): State<AppState>,
+    claims: Claims,
+    mut multipart: Multipart,
+) -> Result<(StatusCode, Json<ImportPreview>), AppError> {
+    let user_id = claims.user_id()?;
+    let mut file = None;
+    let mut units = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::BadRequest(e.body_text()))?
+    {
+        match field.name() {
+            Some("file") => {
+                let bytes = field.bytes().await.map_err(|e| {
+                    if e.status() == StatusCode::PAYLOAD_TOO_LARGE {
+                        AppError::PayloadTooLarge("File is too large".into())
+                    } else {
+                        AppError::BadRequest(e.body_text())
+                    }
+                })?;
+                if bytes.len() > MAX_IMPORT_FILE_BYTES {
+                    return Err(AppError::PayloadTooLarge(format!(
+                        "File must be at most {} MB",
+                        MAX_IMPORT_FILE_BYTES / 1024 / 1024
+                    )));
+                }
+                file = Some(bytes.to_vec());
+            }
+            Some("units") => {
+                let value = field
+                    .text()
+                    .await
+                    .map_err(|_| AppError::BadRequest("Invalid units".into()))?;
+                one_of(value.trim(), UNIT_SYSTEMS, "units")
+                    .map_err(|e| AppError::BadRequest(e.to_string()))?;
+                units = Some(value.trim().to_string());
+            }
+            _ => {}
+        }
+    }
+    let file = file.ok_or(AppError::BadRequest("Missing file".into()))?;
+
+    let mut tx = state.pool.begin().await?;
+    let profile = ensure_profile(&mut tx, user_id).await?;
+    let units = units.unwrap_or_else(|| profile.preferred_units.clone());
+
+    let (history, file) = {
+        let units = units.clone();
+        tokio::task::spawn_blocking(move || {
+            parse_history(&file, &units, &profile).map(|history| (history, file))
+        })
+        .await
+        .map_err(|_| AppError::InternalServerError)??
+    };
+
+    sqlx::query!(
+        "DELETE FROM strength_imports WHERE user_id = $1 AND created_at < NOW() - make_interval(hours => $2)",
+        user_id,
+        IMPORT_RETENTION_HOURS as i32
+    )
+    .execute(&mut *tx)
+    .await?;
+    let import = sqlx::query_as!(
+        StrengthImport,
+        r#"
+        INSERT INTO strength_imports (user_id, source, units, data)
+        VALUES ($1, $2, $3, $4)
+        RETURNING *
+        "#,
+        user_id,
+        history.source,
+        units,
+        file
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    let preview = build_preview(&mut tx, &import, &history).await?;
+
+    tx.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(preview)))
+}
+
+pub async fn get_import(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(import_id): Path<Uuid>,
+) -> Result<Json<ImportPreview>, AppError> {
+    let user_id = claims.user_id()?;
+    let mut conn = state.pool.acquire().await?;
+
+    let import = find_own_import(&mut conn, user_id, import_id).await?;
+    let profile = ensure_profile(&mut conn, user_id).await?;
+    let history = parse_import(&import, profile).await?;
+
+    Ok(Json(build_preview(&mut conn, &import, &history).await?))
+}
+
+/// Confirms or corrects how exercise names from the file map to our catalog.
+/// A `null` exercise leaves that name's sets out of the import.
+pub async fn save_mappings(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(import_id): Path<Uuid>,
+    Json(payload): Json<SaveMappingsRequest>,
+) -> Result<Json<ImportPreview>, AppError> {
+    let user_id = claims.user_id()?;
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+
+    let mut tx = state.pool.begin().await?;
+    let import = find_own_import(&mut tx, user_id, import_id).await?;
+    let profile = ensure_profile(&mut tx, user_id).await?;
+    let history = parse_import(&import, profile).await?;
+
+    let names: HashSet<String> = history
+        .exercise_names()
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+    if let Some(unknown) = payload.mappings.iter().find(|m| !names.contains(&m.name)) {
+        return Err(AppError::BadRequest(format!(
+            "'{}' does not appear in the file",
+            unknown.name
+        )));
+    }
+    let exercise_ids: Vec<Uuid> = payload
+        .mappings
+        .iter()
+        .filter_map(|m| m.exercise_id)
+        .collect();
+    check_exercise_access(&mut *tx, user_id, &exercise_ids).await?;
+
+    for mapping in &payload.mappings {
+        sqlx::query!(
+            r#"
+            INSERT INTO strength_import_mappings (import_id, source_name, exercise_id)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (import_id, source_name) DO UPDATE SET exercise_id = EXCLUDED.exercise_id
+            "#,
+            import_id,
+            mapping.name,
+            mapping.exercise_id
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+    let preview = build_preview(&mut tx, &import, &history).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(preview))
+}
+
+/// Imports the previewed workouts in a single transaction and discards the upload.
+pub async fn commit_import(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(import_id): Path<Uuid>,
+) -> Result<Json<ImportResult>, AppError> {
+    let user_id = claims.user_id()?;
+    let mut tx = state.pool.begin().await?;
+
+    // The row lock makes a second commit of the same upload wait and then find nothing.
+    let import = sqlx::query_as!(
+        StrengthImport,
+        "SELECT * FROM strength_imports WHERE id = $1 AND user_id = $2 FOR UPDATE",
+        import_id,
+        user_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Import not found".into()))?;
+    let profile = ensure_profile(&mut tx, user_id).await?;
+    let history = parse_import(&import, profile).await?;
+
+    let result = commit_history(&mut tx, &import, &history).await?;
+    sqlx::query!("DELETE FROM strength_imports WHERE id = $1", import_id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(result))
+}
+
+pub async fn delete_import(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(import_id): Path<Uuid>,
+) -> Result<Json<&'static str>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let result = sqlx::query!(
+        "DELETE FROM strength_imports WHERE id = $1 AND user_id = $2",
+        import_id,
+        user_id
+    )
+    .execute(&state.pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Import not found".into()));
+    }
+
+    Ok(Json("Import discarded"))
+}
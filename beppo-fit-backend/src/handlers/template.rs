@@ -0,0 +1,354 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    error::AppError,
+    handlers::auth::Claims,
+    models::{
+        progression::ProgressionRecommendation,
+        template::{SaveTemplateRequest, TemplateDetail, WorkoutTemplate},
+        workout::WorkoutDetail,
+    },
+    services::{
+        progression::latest_recommendations,
+        template::{
+            copy_template, insert_template_exercises, load_template_detail,
+            start_workout_from_template,
+        },
+        workout::{check_exercise_access, check_shared_exercises, load_workout_detail},
+    },
+    state::AppState,
+};
+
+fn validate_template(payload: &SaveTemplateRequest) -> Result<(), AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+    Ok(())
+}
+
+async fn find_own_template<'e>(
+    executor: impl sqlx::PgExecutor<'e>,
+    user_id: Uuid,
+    template_id: Uuid,
+) -> Result<WorkoutTemplate, AppError> {
+    sqlx::query_as!(
+        WorkoutTemplate,
+        "SELECT * FROM workout_templates WHERE id = $1 AND user_id = $2",
+        template_id,
+        user_id
+    )
+    .fetch_optional(executor)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Template not found".into()))
+}
+
+async fn find_shared_template<'e>(
+    executor: impl sqlx::PgExecutor<'e>,
+    share_code: &str,
+) -> Result<WorkoutTemplate, AppError> {
+    sqlx::query_as!(
+        WorkoutTemplate,
+        "SELECT * FROM workout_templates WHERE share_code = $1",
+        share_code
+    )
+    .fetch_optional(executor)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Template not found".into()))
+}
+
+pub async fn list_templates(
+    State(state): State<AppState>,
+    claims: Claims,
+) -> Result<Json<Vec<WorkoutTemplate>>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let templates = sqlx::query_as!(
+        WorkoutTemplate,
+        "SELECT * FROM workout_templates WHERE user_id = $1 ORDER BY name",
+        user_id
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(templates))
+}
+
+pub async fn create_template(
+    State(state): State<AppState>,
+    claims: Claims,
+    Json(payload): Json<SaveTemplateRequest>,
+) -> Result<(StatusCode, Json<TemplateDetail>), AppError> {
+    let user_id = claims.user_id()?;
+    validate_template(&payload)?;
+
+    let mut tx = state.pool.begin().await?;
+
+    let exercise_ids: Vec<Uuid> = payload.exercises.iter().map(|e| e.exercise_id).collect();
+    check_exercise_access(&mut *tx, user_id, &exercise_ids).await?;
+
+    let template = sqlx::query_as!(
+        WorkoutTemplate,
+        "INSERT INTO workout_templates (user_id, name, description) VALUES ($1, $2, $3) RETURNING *",
+        user_id,
+        payload.name,
+        payload.description
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    insert_template_exercises(&mut tx, template.id, &payload.exercises).await?;
+    let detail = load_template_detail(&mut tx, template).await?;
+
+    tx.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(detail)))
+}
+
+pub async fn get_template(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(template_id): Path<Uuid>,
+) -> Result<Json<TemplateDetail>, AppError> {
+    let user_id = claims.user_id()?;
+    let mut conn = state.pool.acquire().await?;
+
+    let template = find_own_template(&mut *conn, user_id, template_id).await?;
+    let detail = load_template_detail(&mut conn, template).await?;
+
+    Ok(Json(detail))
+}
+
+/// Replaces a template with the given payload, including all of its exercises.
+pub async fn update_template(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(template_id): Path<Uuid>,
+    Json(payload): Json<SaveTemplateRequest>,
+) -> Result<Json<TemplateDetail>, AppError> {
+    let user_id = claims.user_id()?;
+    validate_template(&payload)?;
+
+    let mut tx = state.pool.begin().await?;
+
+    let exercise_ids: Vec<Uuid> = payload.exercises.iter().map(|e| e.exercise_id).collect();
+    check_exercise_access(&mut *tx, user_id, &exercise_ids).await?;
+
+    let template = sqlx::query_as!(
+        WorkoutTemplate,
+        r#"
+        UPDATE workout_templates SET name = $3, description = $4, updated_at = NOW()
+        WHERE id = $1 AND user_id = $2
+        RETURNING *
+        "#,
+        template_id,
+        user_id,
+        payload.name,
+        payload.description
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Template not found".into()))?;
+
+    sqlx::query!(
+        "DELETE FROM template_exercises WHERE template_id = $1",
+        template_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    insert_template_exercises(&mut tx, template_id, &payload.exercises).await?;
+    let detail = load_template_detail(&mut tx, template).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(detail))
+}
+
+pub async fn delete_template(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(template_id): Path<Uuid>,
+) -> Result<Json<&'static str>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let result = sqlx::query!(
+        "DELETE FROM workout_templates WHERE id = $1 AND user_id = $2",
+        template_id,
+        user_id
+    )
+    .execute(&state.pool)
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::Database(ref db) if db.is_foreign_key_violation() => {
+            AppError::Conflict("Template is used by a program".into())
+        }
+        e => AppError::SqlxError(e),
+    })?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Template not found".into()));
+    }
+
+    Ok(Json("Template deleted successfully"))
+}
+
+pub async fn duplicate_template(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(template_id): Path<Uuid>,
+) -> Result<(StatusCode, Json<TemplateDetail>), AppError> {
+    let user_id = claims.user_id()?;
+    let mut tx = state.pool.begin().await?;
+
+    let source = find_own_template(&mut *tx, user_id, template_id).await?;
+    let source = load_template_detail(&mut tx, source).await?;
+
+    let name = format!("{} (copy)", source.template.name);
+    let copy = copy_template(&mut tx, user_id, &source, &name).await?;
+    let detail = load_template_detail(&mut tx, copy).await?;
+
+    tx.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(detail)))
+}
+
+/// Generates a share code for the template, keeping the existing one if already shared.
+pub async fn share_template(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(template_id): Path<Uuid>,
+) -> Result<Json<WorkoutTemplate>, AppError> {
+    let user_id = claims.user_id()?;
+    let share_code = Uuid::new_v4().simple().to_string()[..12].to_string();
+
+    let template = sqlx::query_as!(
+        WorkoutTemplate,
+        r#"
+        UPDATE workout_templates SET share_code = COALESCE(share_code, $3), updated_at = NOW()
+        WHERE id = $1 AND user_id = $2
+        RETURNING *
+        "#,
+        template_id,
+        user_id,
+        share_code
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Template not found".into()))?;
+
+    Ok(Json(template))
+}
+
+pub async fn unshare_template(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(template_id): Path<Uuid>,
+) -> Result<Json<WorkoutTemplate>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let template = sqlx::query_as!(
+        WorkoutTemplate,
+        r#"
+        UPDATE workout_templates SET share_code = NULL, updated_at = NOW()
+        WHERE id = $1 AND user_id = $2
+        RETURNING *
+        "#,
+        template_id,
+        user_id
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Template not found".into()))?;
+
+    Ok(Json(template))
+}
+
+pub async fn get_shared_template(
+    State(state): State<AppState>,
+    _claims: Claims,
+    Path(share_code): Path<String>,
+) -> Result<Json<TemplateDetail>, AppError> {
+    let mut conn = state.pool.acquire().await?;
+
+    let template = find_shared_template(&mut *conn, &share_code).await?;
+    let mut detail = load_template_detail(&mut conn, template).await?;
+    detail.template.share_code = None;
+
+    Ok(Json(detail))
+}
+
+/// Copies a shared template into the caller's own templates.
+pub async fn copy_shared_template(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(share_code): Path<String>,
+) -> Result<(StatusCode, Json<TemplateDetail>), AppError> {
+    let user_id = claims.user_id()?;
+    let mut tx = state.pool.begin().await?;
+
+    let source = find_shared_template(&mut *tx, &share_code).await?;
+    let source = load_template_detail(&mut tx, source).await?;
+
+    let exercise_ids: Vec<Uuid> = source.exercises.iter().map(|e| e.exercise_id).collect();
+    check_shared_exercises(&mut *tx, user_id, &exercise_ids).await?;
+
+    let name = source.template.name.clone();
+    let copy = copy_template(&mut tx, user_id, &source, &name).await?;
+    let detail = load_template_detail(&mut tx, copy).await?;
+
+    tx.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(detail)))
+}
+
+/// Starts a new workout from a template, pre-filled with the last performance of each exercise.
+pub async fn start_template(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(template_id): Path<Uuid>,
+) -> Result<(StatusCode, Json<WorkoutDetail>), AppError> {
+    let user_id = claims.user_id()?;
+    let mut tx = state.pool.begin().await?;
+
+    let template = find_own_template(&mut *tx, user_id, template_id).await?;
+    let template = load_template_detail(&mut tx, template).await?;
+
+    let exercise_ids: Vec<Uuid> = template.exercises.iter().map(|e| e.exercise_id).collect();
+    check_exercise_access(&mut *tx, user_id, &exercise_ids).await?;
+
+    let workout_id = start_workout_from_template(&mut tx, user_id, &template).await?;
+    let detail = load_workout_detail(&mut tx, user_id, workout_id).await?;
+
+    tx.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(detail)))
+}
+
+/// The latest progression recommendation for each exercise of the template.
+pub async fn get_template_recommendations(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(template_id): Path<Uuid>,
+) -> Result<Json<Vec<ProgressionRecommendation>>, AppError> {
+    let user_id = claims.user_id()?;
+    let mut conn = state.pool.acquire().await?;
+
+    let template = find_own_template(&mut *conn, user_id, template_id).await?;
+    let template = load_template_detail(&mut conn, template).await?;
+    let template_exercise_ids: Vec<Uuid> = template.exercises.iter().map(|e| e.id).collect();
+    let mut latest = latest_recommendations(&mut conn, user_id, &template_exercise_ids).await?;
+
+    Ok(Json(
+        template
+            .exercises
+            .iter()
+            .filter_map(|e| latest.remove(&e.id))
+            .collect(),
+    ))
+}
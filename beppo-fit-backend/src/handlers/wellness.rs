@@ -0,0 +1,177 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use chrono::{Duration, NaiveDate};
+use validator::Validate;
+
+use crate::{
+    error::AppError,
+    handlers::auth::Claims,
+    models::wellness::{
+        Readiness, ReadinessQuery, SaveWellnessLogRequest, WellnessLog, WellnessQuery,
+    },
+    services::{profile::ensure_profile, wellness::load_readiness},
+    state::AppState,
+};
+
+/// Days listed when no range is given.
+const DEFAULT_RANGE_DAYS: i64 = 30;
+const MAX_RANGE_DAYS: i64 = 366;
+
+pub async fn list_wellness_logs(
+    State(state): State<AppState>,
+    claims: Claims,
+    Query(query): Query<WellnessQuery>,
+) -> Result<Json<Vec<WellnessLog>>, AppError> {
+    let user_id = claims.user_id()?;
+    let mut conn = state.pool.acquire().await?;
+
+    let profile = ensure_profile(&mut conn, user_id).await?;
+    let to = query.to.unwrap_or_else(|| profile.today());
+    let from = query
+        .from
+        .unwrap_or(to - Duration::days(DEFAULT_RANGE_DAYS - 1));
+    if from > to {
+        return Err(AppError::BadRequest("from must not be after to".into()));
+    }
+    if (to - from).num_days() >= MAX_RANGE_DAYS {
+        return Err(AppError::BadRequest(format!(
+            "Date range must be at most {} days",
+            MAX_RANGE_DAYS
+        )));
+    }
+
+    let logs = sqlx::query_as!(
+        WellnessLog,
+        r#"
+        SELECT * FROM wellness_logs
+        WHERE user_id = $1 AND log_date BETWEEN $2 AND $3
+        ORDER BY log_date
+        "#,
+        user_id,
+        from,
+        to
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(Json(logs))
+}
+
+pub async fn get_wellness_log(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(date): Path<NaiveDate>,
+) -> Result<Json<WellnessLog>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let log = sqlx::query_as!(
+        WellnessLog,
+        "SELECT * FROM wellness_logs WHERE user_id = $1 AND log_date = $2",
+        user_id,
+        date
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound("No wellness log for this day".into()))?;
+
+    Ok(Json(log))
+}
+
+/// Creates or replaces the log for a day.
+pub async fn save_wellness_log(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(date): Path<NaiveDate>,
+    Json(payload): Json<SaveWellnessLogRequest>,
+) -> Result<Json<WellnessLog>, AppError> {
+    payload
+        .validate()
+        .map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+    let user_id = claims.user_id()?;
+    let mut conn = state.pool.acquire().await?;
+
+    let profile = ensure_profile(&mut conn, user_id).await?;
+    if date > profile.today() {
+        return Err(AppError::BadRequest(
+            "Wellness cannot be logged for future days".into(),
+        ));
+    }
+
+    let log = sqlx::query_as!(
+        WellnessLog,
+        r#"
+        INSERT INTO wellness_logs (user_id, log_date, water_ml, sleep_minutes, sleep_quality, soreness, stress, resting_heart_rate, hrv_ms, notes)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        ON CONFLICT (user_id, log_date) DO UPDATE SET
+            water_ml = EXCLUDED.water_ml,
+            sleep_minutes = EXCLUDED.sleep_minutes,
+            sleep_quality = EXCLUDED.sleep_quality,
+            soreness = EXCLUDED.soreness,
+            stress = EXCLUDED.stress,
+            resting_heart_rate = EXCLUDED.resting_heart_rate,
+            hrv_ms = EXCLUDED.hrv_ms,
+            notes = EXCLUDED.notes,
+            updated_at = NOW()
+        RETURNING *
+        "#,
+        user_id,
+        date,
+        payload.water_ml,
+        payload.sleep_minutes,
+        payload.sleep_quality,
+        payload.soreness,
+        payload.stress,
+        payload.resting_heart_rate,
+        payload.hrv_ms,
+        payload.notes
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(Json(log))
+}
+
+pub async fn delete_wellness_log(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(date): Path<NaiveDate>,
+) -> Result<Json<&'static str>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let result = sqlx::query!(
+        "DELETE FROM wellness_logs WHERE user_id = $1 AND log_date = $2",
+        user_id,
+        date
+    )
+    .execute(&state.pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("No wellness log for this day".into()));
+    }
+
+    Ok(Json("Wellness log deleted successfully"))
+}
+
+/// Readiness for a day, from its log compared to the preceding weeks.
+pub async fn readiness(
+    State(state): State<AppState>,
+    claims: Claims,
+    Query(query): Query<ReadinessQuery>,
+) -> Result<Json<Readiness>, AppError> {
+    let user_id = claims.user_id()?;
+    let mut conn = state.pool.acquire().await?;
+
+    let date = match query.date {
+        Some(date) => date,
+        None => ensure_profile(&mut conn, user_id).await?.today(),
+    };
+    let readiness = load_readiness(&mut conn, user_id, date)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Nothing logged to score readiness on".into()))?;
+
+    Ok(Json(readiness))
+}
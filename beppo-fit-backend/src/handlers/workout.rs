@@ -0,0 +1,376 @@
+use axum::{
+    body::Body,
+    extract::{Path, Query, State},
+    http::{
+        header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+        StatusCode,
+    },
+    response::IntoResponse,
+    Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    error::AppError,
+    handlers::auth::Claims,
+    models::{
+        progression::ProgressionRecommendation,
+        workout::{
+            SaveWorkoutRequest, Workout, WorkoutDetail, WorkoutExportQuery, WorkoutListQuery,
+            WorkoutSummary, EXPORT_FORMAT_CSV, EXPORT_FORMAT_JSON,
+        },
+    },
+    services::{
+        achievement::check_achievements,
+        goal::refresh_goals,
+        program::{advance_enrollment, claim_program_day},
+        progression::evaluate_workout,
+        record::{detect_personal_records, recompute_personal_records, record_scope},
+        workout::{
+            check_exercise_access, check_template_access, insert_workout_exercises,
+            load_workout_detail,
+        },
+        workout_export::workout_history_stream,
+    },
+    state::AppState,
+};
+
+const DEFAULT_PAGE_SIZE: i64 = 20;
+const MAX_PAGE_SIZE: i64 = 100;
+
+fn validate_workout(payload: &SaveWorkoutRequest) -> Result<(), AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+    if let (Some(started_at), Some(finished_at)) = (payload.started_at, payload.finished_at) {
+        if finished_at < started_at {
+            return Err(AppError::BadRequest(
+                "finished_at must not be before started_at".into(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+pub async fn list_workouts(
+    State(state): State<AppState>,
+    claims: Claims,
+    Query(query): Query<WorkoutListQuery>,
+) -> Result<Json<Vec<WorkoutSummary>>, AppError> {
+    let user_id = claims.user_id()?;
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    let workouts = sqlx::query_as!(
+        WorkoutSummary,
+        r#"
+        SELECT
+            w.id,
+            w.name,
+            w.started_at,
+            w.finished_at,
+            COUNT(DISTINCT we.id) AS "exercise_count!",
+            COUNT(s.id) AS "set_count!",
+            COALESCE(SUM(s.reps * s.weight_kg), 0) AS "volume_kg!"
+        FROM workouts w
+        LEFT JOIN workout_exercises we ON we.workout_id = w.id
+        LEFT JOIN sets s ON s.workout_exercise_id = we.id
+        WHERE w.user_id = $1
+          AND ($2::TIMESTAMPTZ IS NULL OR w.started_at >= $2)
+          AND ($3::TIMESTAMPTZ IS NULL OR w.started_at < $3)
+        GROUP BY w.id
+        ORDER BY w.started_at DESC
+        LIMIT $4 OFFSET $5
+        "#,
+        user_id,
+        query.from,
+        query.to,
+        limit,
+        offset
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(workouts))
+}
+
+/// Streams the workout history as CSV (one row per set) or JSON, optionally
+/// limited to workouts started in `[from, to)`.
+pub async fn export_workouts(
+    State(state): State<AppState>,
+    claims: Claims,
+    Query(query): Query<WorkoutExportQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id = claims.user_id()?;
+    let format = query.format.as_deref().unwrap_or(EXPORT_FORMAT_CSV);
+    let content_type = match format {
+        EXPORT_FORMAT_CSV => "text/csv; charset=utf-8",
+        EXPORT_FORMAT_JSON => "application/json",
+        _ => {
+            return Err(AppError::BadRequest(format!(
+                "Format must be one of: {}, {}",
+                EXPORT_FORMAT_CSV, EXPORT_FORMAT_JSON
+            )))
+        }
+    };
+    if let (Some(from), Some(to)) = (query.from, query.to) {
+        if to <= from {
+            return Err(AppError::BadRequest("to must be after from".into()));
+        }
+    }
+
+    let stream = workout_history_stream(state.pool.clone(), user_id, format, query.from, query.to);
+
+    Ok((
+        [
+            (CONTENT_TYPE, content_type.to_string()),
+            (
+                CONTENT_DISPOSITION,
+                format!("attachment; filename=\"beppofit-workouts.{}\"", format),
+            ),
+        ],
+        Body::from_stream(stream),
+    ))
+}
+
+pub async fn create_workout(
+    State(state): State<AppState>,
+    claims: Claims,
+    Json(payload): Json<SaveWorkoutRequest>,
+) -> Result<(StatusCode, Json<WorkoutDetail>), AppError> {
+    let user_id = claims.user_id()?;
+    validate_workout(&payload)?;
+
+    let mut tx = state.pool.begin().await?;
+
+    let exercise_ids: Vec<Uuid> = payload.exercises.iter().map(|e| e.exercise_id).collect();
+    check_exercise_access(&mut *tx, user_id, &exercise_ids).await?;
+    let template_id = payload.template_id.flatten();
+    if let Some(template_id) = template_id {
+        check_template_access(&mut *tx, user_id, template_id).await?;
+    }
+
+    let workout = sqlx::query_as!(
+        Workout,
+        r#"
+        INSERT INTO workouts (user_id, name, notes, started_at, finished_at, visibility, template_id)
+        VALUES (
+            $1, $2, $3, COALESCE($4, NOW()), $5,
+            COALESCE($6, (SELECT default_workout_visibility FROM user_profiles WHERE user_id = $1), 'private'),
+            $7
+        )
+        RETURNING *
+        "#,
+        user_id,
+        payload.name,
+        payload.notes,
+        payload.started_at,
+        payload.finished_at,
+        payload.visibility,
+        template_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    insert_workout_exercises(&mut tx, workout.id, &payload.exercises).await?;
+    detect_personal_records(&mut tx, user_id, workout.id).await?;
+    if payload.finished_at.is_some() {
+        if let Some(template_id) = template_id {
+            claim_program_day(&mut tx, user_id, workout.id, template_id).await?;
+        }
+        advance_enrollment(&mut tx, workout.id).await?;
+        evaluate_workout(&mut tx, user_id, workout.id).await?;
+    }
+    refresh_goals(&mut tx, user_id).await?;
+    check_achievements(&mut tx, user_id).await?;
+    let detail = load_workout_detail(&mut tx, user_id, workout.id).await?;
+
+    tx.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(detail)))
+}
+
+pub async fn get_workout(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(workout_id): Path<Uuid>,
+) -> Result<Json<WorkoutDetail>, AppError> {
+    let user_id = claims.user_id()?;
+    let mut conn = state.pool.acquire().await?;
+    let detail = load_workout_detail(&mut conn, user_id, workout_id).await?;
+
+    Ok(Json(detail))
+}
+
+/// Replaces a workout with the given payload, including all of its exercises and sets.
+pub async fn update_workout(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(workout_id): Path<Uuid>,
+    Json(payload): Json<SaveWorkoutRequest>,
+) -> Result<Json<WorkoutDetail>, AppError> {
+    let user_id = claims.user_id()?;
+    validate_workout(&payload)?;
+
+    let mut tx = state.pool.begin().await?;
+
+    let exercise_ids: Vec<Uuid> = payload.exercises.iter().map(|e| e.exercise_id).collect();
+    check_exercise_access(&mut *tx, user_id, &exercise_ids).await?;
+    if let Some(Some(template_id)) = payload.template_id {
+        check_template_access(&mut *tx, user_id, template_id).await?;
+    }
+
+    let was_finished = sqlx::query_scalar!(
+        "SELECT finished_at IS NOT NULL AS \"finished!\" FROM workouts WHERE id = $1 AND user_id = $2 FOR UPDATE",
+        workout_id,
+        user_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(AppError::NotFound("Workout not found".into()))?;
+    let before = record_scope(&mut tx, user_id, workout_id).await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE workouts SET
+            name = $3,
+            notes = $4,
+            started_at = COALESCE($5, started_at),
+            finished_at = $6,
+            visibility = COALESCE($7, visibility),
+            template_id = CASE WHEN $9 THEN $8 ELSE template_id END,
+            updated_at = NOW()
+        WHERE id = $1 AND user_id = $2
+        "#,
+        workout_id,
+        user_id,
+        payload.name,
+        payload.notes,
+        payload.started_at,
+        payload.finished_at,
+        payload.visibility,
+        payload.template_id.flatten(),
+        payload.template_id.is_some()
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "DELETE FROM workout_exercises WHERE workout_id = $1",
+        workout_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    insert_workout_exercises(&mut tx, workout_id, &payload.exercises).await?;
+    let scope = before.merge(record_scope(&mut tx, user_id, workout_id).await?);
+    recompute_personal_records(&mut tx, user_id, &scope).await?;
+    // Only finishing counts; re-evaluating an old workout on every edit would
+    // replace the current targets with ones from stale data.
+    if !was_finished && payload.finished_at.is_some() {
+        advance_enrollment(&mut tx, workout_id).await?;
+        evaluate_workout(&mut tx, user_id, workout_id).await?;
+    }
+    refresh_goals(&mut tx, user_id).await?;
+    check_achievements(&mut tx, user_id).await?;
+    let detail = load_workout_detail(&mut tx, user_id, workout_id).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(detail))
+}
+
+pub async fn finish_workout(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(workout_id): Path<Uuid>,
+) -> Result<Json<WorkoutDetail>, AppError> {
+    let user_id = claims.user_id()?;
+    let mut tx = state.pool.begin().await?;
+
+    let finished = sqlx::query_scalar!(
+        r#"
+        UPDATE workouts SET finished_at = GREATEST(NOW(), started_at), updated_at = NOW()
+        WHERE id = $1 AND user_id = $2 AND finished_at IS NULL
+        RETURNING id
+        "#,
+        workout_id,
+        user_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let detail = load_workout_detail(&mut tx, user_id, workout_id).await?;
+    if finished.is_none() {
+        return Err(AppError::Conflict("Workout is already finished".into()));
+    }
+    detect_personal_records(&mut tx, user_id, workout_id).await?;
+    advance_enrollment(&mut tx, workout_id).await?;
+    evaluate_workout(&mut tx, user_id, workout_id).await?;
+    refresh_goals(&mut tx, user_id).await?;
+    check_achievements(&mut tx, user_id).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(detail))
+}
+
+pub async fn delete_workout(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(workout_id): Path<Uuid>,
+) -> Result<Json<&'static str>, AppError> {
+    let user_id = claims.user_id()?;
+    let mut tx = state.pool.begin().await?;
+
+    let exists = sqlx::query_scalar!(
+        "SELECT id FROM workouts WHERE id = $1 AND user_id = $2 FOR UPDATE",
+        workout_id,
+        user_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+    if exists.is_none() {
+        return Err(AppError::NotFound("Workout not found".into()));
+    }
+    // Later sessions may have been measured against this one.
+    let scope = record_scope(&mut tx, user_id, workout_id).await?;
+
+    sqlx::query!("DELETE FROM workouts WHERE id = $1", workout_id)
+        .execute(&mut *tx)
+        .await?;
+    recompute_personal_records(&mut tx, user_id, &scope).await?;
+
+    tx.commit().await?;
+
+    Ok(Json("Workout deleted successfully"))
+}
+
+/// Recommendations produced when the workout was finished.
+pub async fn get_workout_recommendations(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(workout_id): Path<Uuid>,
+) -> Result<Json<Vec<ProgressionRecommendation>>, AppError> {
+    let user_id = claims.user_id()?;
+
+    let recommendations = sqlx::query_as!(
+        ProgressionRecommendation,
+        r#"
+        SELECT r.* FROM progression_recommendations r
+        JOIN template_exercises te ON te.id = r.template_exercise_id
+        WHERE r.workout_id = $1 AND r.user_id = $2
+        ORDER BY te.position
+        "#,
+        workout_id,
+        user_id
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(recommendations))
+}
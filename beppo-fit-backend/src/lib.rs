@@ -1,5 +1,6 @@
 use axum::{
-    routing::{get, post},
+    extract::DefaultBodyLimit,
+    routing::{delete, get, post, put},
     Router,
 };
 use std::sync::Arc;
@@ -13,18 +14,31 @@ pub mod models;
 pub mod services;
 pub mod state;
 
-use crate::{services::email::EmailService, state::AppState};
+use crate::{
+    services::{
+        activity_import::MAX_ACTIVITY_FILE_BYTES, email::EmailService, media::MAX_UPLOAD_BYTES,
+        storage::storage_from_env, strength_import::MAX_IMPORT_FILE_BYTES,
+    },
+    state::AppState,
+};
 
 use sqlx::PgPool;
 
 pub async fn app(pool: PgPool) -> Router {
     let email_service = Arc::new(EmailService::new());
+    let storage = storage_from_env();
 
     let state = AppState {
         pool,
         email_service,
+        storage,
     };
 
+    // Leave room for the multipart framing and the other form fields.
+    let upload_limit = DefaultBodyLimit::max(MAX_UPLOAD_BYTES + 64 * 1024);
+    let activity_file_limit = DefaultBodyLimit::max(MAX_ACTIVITY_FILE_BYTES + 64 * 1024);
+    let import_file_limit = DefaultBodyLimit::max(MAX_IMPORT_FILE_BYTES + 64 * 1024);
+
     Router::new()
         .route("/", get(root))
         .route("/auth/register", post(handlers::auth::register))
@@ -40,9 +54,368 @@ pub async fn app(pool: PgPool) -> Router {
             "/auth/google/callback",
             get(handlers::oauth::google_callback),
         )
+        .route("/auth/me", delete(handlers::auth::delete_account))
+        .route(
+            "/me/profile",
+            get(handlers::profile::get_profile).patch(handlers::profile::update_profile),
+        )
+        .route(
+            "/me/avatar",
+            get(handlers::media::get_avatar)
+                .post(handlers::media::upload_avatar)
+                .delete(handlers::media::delete_avatar)
+                .layer(upload_limit),
+        )
+        .route(
+            "/media/progress-photos",
+            get(handlers::media::list_progress_photos)
+                .post(handlers::media::upload_progress_photo)
+                .layer(upload_limit),
+        )
+        .route("/media/:id", delete(handlers::media::delete_media))
+        .route("/media/:id/:variant", get(handlers::media::download_media))
+        .route(
+            "/exercises",
+            get(handlers::exercise::list_exercises).post(handlers::exercise::create_exercise),
+        )
+        .route(
+            "/exercises/:id",
+            get(handlers::exercise::get_exercise)
+                .patch(handlers::exercise::update_exercise)
+                .delete(handlers::exercise::archive_exercise),
+        )
+        .route(
+            "/exercises/:id/promote",
+            post(handlers::exercise::promote_exercise),
+        )
+        .route(
+            "/workouts",
+            get(handlers::workout::list_workouts).post(handlers::workout::create_workout),
+        )
+        .route("/workouts/export", get(handlers::workout::export_workouts))
+        .route(
+            "/workouts/:id",
+            get(handlers::workout::get_workout)
+                .put(handlers::workout::update_workout)
+                .delete(handlers::workout::delete_workout),
+        )
+        .route(
+            "/workouts/:id/finish",
+            post(handlers::workout::finish_workout),
+        )
+        .route(
+            "/workouts/:id/recommendations",
+            get(handlers::workout::get_workout_recommendations),
+        )
+        .route(
+            "/templates",
+            get(handlers::template::list_templates).post(handlers::template::create_template),
+        )
+        .route(
+            "/templates/:id",
+            get(handlers::template::get_template)
+                .put(handlers::template::update_template)
+                .delete(handlers::template::delete_template),
+        )
+        .route(
+            "/templates/:id/duplicate",
+            post(handlers::template::duplicate_template),
+        )
+        .route(
+            "/templates/:id/share",
+            post(handlers::template::share_template).delete(handlers::template::unshare_template),
+        )
+        .route(
+            "/templates/:id/recommendations",
+            get(handlers::template::get_template_recommendations),
+        )
+        .route(
+            "/templates/:id/start",
+            post(handlers::template::start_template),
+        )
+        .route(
+            "/templates/shared/:code",
+            get(handlers::template::get_shared_template),
+        )
+        .route(
+            "/templates/shared/:code/copy",
+            post(handlers::template::copy_shared_template),
+        )
+        .route(
+            "/intervals",
+            get(handlers::interval::list_interval_workouts)
+                .post(handlers::interval::create_interval_workout),
+        )
+        .route(
+            "/intervals/:id",
+            get(handlers::interval::get_interval_workout)
+                .put(handlers::interval::update_interval_workout)
+                .delete(handlers::interval::delete_interval_workout),
+        )
+        .route(
+            "/intervals/:id/share",
+            post(handlers::interval::share_interval_workout)
+                .delete(handlers::interval::unshare_interval_workout),
+        )
+        .route(
+            "/intervals/:id/sessions",
+            post(handlers::interval::log_interval_session),
+        )
+        .route(
+            "/intervals/shared/:code",
+            get(handlers::interval::get_shared_interval_workout),
+        )
+        .route(
+            "/intervals/shared/:code/copy",
+            post(handlers::interval::copy_shared_interval_workout),
+        )
+        .route(
+            "/intervals/sessions",
+            get(handlers::interval::list_interval_sessions),
+        )
+        .route(
+            "/intervals/sessions/:id",
+            get(handlers::interval::get_interval_session)
+                .delete(handlers::interval::delete_interval_session),
+        )
+        .route(
+            "/foods",
+            get(handlers::nutrition::list_foods).post(handlers::nutrition::create_food),
+        )
+        .route(
+            "/foods/:id",
+            get(handlers::nutrition::get_food)
+                .patch(handlers::nutrition::update_food)
+                .delete(handlers::nutrition::archive_food),
+        )
+        .route(
+            "/meals",
+            get(handlers::nutrition::list_meals).post(handlers::nutrition::create_meal),
+        )
+        .route(
+            "/meals/:id",
+            get(handlers::nutrition::get_meal)
+                .put(handlers::nutrition::update_meal)
+                .delete(handlers::nutrition::delete_meal),
+        )
+        .route(
+            "/nutrition/daily",
+            get(handlers::nutrition::daily_nutrition),
+        )
+        .route(
+            "/nutrition/targets",
+            get(handlers::nutrition::nutrition_targets),
+        )
+        .route("/feed", get(handlers::social::feed))
+        .route(
+            "/users/:id/follow",
+            post(handlers::social::follow_user).delete(handlers::social::unfollow_user),
+        )
+        .route(
+            "/users/:id/block",
+            post(handlers::social::block_user).delete(handlers::social::unblock_user),
+        )
+        .route(
+            "/users/:id/workouts",
+            get(handlers::social::list_user_workouts),
+        )
+        .route(
+            "/users/:id/workouts/:workout_id",
+            get(handlers::social::get_user_workout),
+        )
+        .route("/me/followers", get(handlers::social::list_followers))
+        .route(
+            "/me/followers/:user_id",
+            delete(handlers::social::remove_follower),
+        )
+        .route("/me/following", get(handlers::social::list_following))
+        .route(
+            "/me/follow-requests",
+            get(handlers::social::list_follow_requests),
+        )
+        .route(
+            "/me/follow-requests/:user_id",
+            delete(handlers::social::decline_follow_request),
+        )
+        .route(
+            "/me/follow-requests/:user_id/accept",
+            post(handlers::social::accept_follow_request),
+        )
+        .route("/me/blocks", get(handlers::social::list_blocks))
+        .route("/wellness", get(handlers::wellness::list_wellness_logs))
+        .route("/wellness/readiness", get(handlers::wellness::readiness))
+        .route(
+            "/wellness/:date",
+            get(handlers::wellness::get_wellness_log)
+                .put(handlers::wellness::save_wellness_log)
+                .delete(handlers::wellness::delete_wellness_log),
+        )
+        .route(
+            "/programs",
+            get(handlers::program::list_programs).post(handlers::program::create_program),
+        )
+        .route(
+            "/programs/:id",
+            get(handlers::program::get_program)
+                .put(handlers::program::update_program)
+                .delete(handlers::program::delete_program),
+        )
+        .route("/programs/:id/enroll", post(handlers::program::enroll))
+        .route(
+            "/me/enrollment",
+            get(handlers::program::get_enrollment).delete(handlers::program::leave_enrollment),
+        )
+        .route("/me/enrollment/next", get(handlers::program::next_workout))
+        .route(
+            "/me/enrollment/next/start",
+            post(handlers::program::start_next_workout),
+        )
+        .route(
+            "/me/training-maxes",
+            get(handlers::program::list_training_maxes),
+        )
+        .route(
+            "/me/training-maxes/:exercise_id",
+            put(handlers::program::set_training_max).delete(handlers::program::delete_training_max),
+        )
+        .route(
+            "/measurements",
+            get(handlers::measurement::list_measurements)
+                .post(handlers::measurement::create_measurement),
+        )
+        .route(
+            "/measurements/series",
+            get(handlers::measurement::measurement_series),
+        )
+        .route(
+            "/measurements/:id",
+            get(handlers::measurement::get_measurement)
+                .patch(handlers::measurement::update_measurement)
+                .delete(handlers::measurement::delete_measurement),
+        )
+        .route(
+            "/goals",
+            get(handlers::goal::list_goals).post(handlers::goal::create_goal),
+        )
+        .route(
+            "/goals/:id",
+            get(handlers::goal::get_goal)
+                .patch(handlers::goal::update_goal)
+                .delete(handlers::goal::delete_goal),
+        )
+        .route(
+            "/activities",
+            get(handlers::activity::list_activities).post(handlers::activity::create_activity),
+        )
+        .route(
+            "/activities/summary",
+            get(handlers::activity::activity_summary),
+        )
+        .route(
+            "/activities/import",
+            post(handlers::activity::import_activity).layer(activity_file_limit),
+        )
+        .route(
+            "/activities/:id",
+            get(handlers::activity::get_activity)
+                .put(handlers::activity::update_activity)
+                .delete(handlers::activity::delete_activity),
+        )
+        .route(
+            "/activities/:id/track",
+            get(handlers::activity::get_activity_track),
+        )
+        .route(
+            "/activities/:id/export",
+            get(handlers::activity::export_activity),
+        )
+        .route(
+            "/imports/strength",
+            post(handlers::strength_import::create_import).layer(import_file_limit),
+        )
+        .route(
+            "/imports/strength/:id",
+            get(handlers::strength_import::get_import)
+                .delete(handlers::strength_import::delete_import),
+        )
+        .route(
+            "/imports/strength/:id/mappings",
+            put(handlers::strength_import::save_mappings),
+        )
+        .route(
+            "/imports/strength/:id/commit",
+            post(handlers::strength_import::commit_import),
+        )
+        .route(
+            "/schedules",
+            get(handlers::schedule::list_schedules).post(handlers::schedule::create_schedule),
+        )
+        .route(
+            "/schedules/:id",
+            get(handlers::schedule::get_schedule)
+                .put(handlers::schedule::update_schedule)
+                .delete(handlers::schedule::delete_schedule),
+        )
+        .route(
+            "/schedules/:id/sessions/:date",
+            delete(handlers::schedule::reset_session),
+        )
+        .route(
+            "/schedules/:id/sessions/:date/complete",
+            post(handlers::schedule::complete_session),
+        )
+        .route(
+            "/schedules/:id/sessions/:date/skip",
+            post(handlers::schedule::skip_session),
+        )
+        .route(
+            "/schedules/:id/sessions/:date/ics",
+            get(handlers::calendar_feed::download_session_ics),
+        )
+        .route("/calendar", get(handlers::schedule::get_calendar))
+        .route(
+            "/calendar/adherence",
+            get(handlers::schedule::get_adherence),
+        )
+        .route(
+            "/calendar/feed/:token",
+            get(handlers::calendar_feed::serve_calendar_feed),
+        )
+        .route(
+            "/me/calendar-feed",
+            get(handlers::calendar_feed::get_calendar_feed)
+                .post(handlers::calendar_feed::create_calendar_feed)
+                .delete(handlers::calendar_feed::revoke_calendar_feed),
+        )
+        .route(
+            "/me/achievements",
+            get(handlers::achievement::list_achievements),
+        )
+        .route(
+            "/me/notifications",
+            get(handlers::notification::list_notifications),
+        )
+        .route(
+            "/me/notifications/read",
+            post(handlers::notification::mark_all_notifications_read),
+        )
+        .route(
+            "/me/notifications/:id/read",
+            post(handlers::notification::mark_notification_read),
+        )
+        .route("/stats/prs", get(handlers::stats::list_personal_records))
+        .route("/stats/volume", get(handlers::stats::muscle_volume))
+        .route("/stats/frequency", get(handlers::stats::training_frequency))
+        .route("/stats/e1rm", get(handlers::stats::estimated_max_trend))
+        .route(
+            "/stats/durations",
+            get(handlers::stats::duration_distribution),
+        )
+        .route("/stats/tonnage", get(handlers::stats::tonnage))
+        .route("/auth/me/export", post(handlers::export::request_export))
         .route(
-            "/auth/me",
-            axum::routing::delete(handlers::auth::delete_account),
+            "/auth/me/export/download",
+            get(handlers::export::download_export),
         )
         .layer(TraceLayer::new_for_http())
         .layer(CorsLayer::permissive())
@@ -0,0 +1,159 @@
+use serde::Serialize;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// What an achievement counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AchievementMetric {
+    /// Finished workouts.
+    Workouts,
+    /// Consecutive weeks (Monday to Sunday, profile timezone) with a finished workout.
+    WeeklyStreak,
+    /// Exercises that set a personal record in a workout; several record
+    /// types broken by the same lift count once.
+    PersonalRecords,
+    /// Lifetime working-set volume (reps × weight) in kg.
+    VolumeKg,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AchievementDefinition {
+    pub code: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub metric: AchievementMetric,
+    pub threshold: f64,
+}
+
+const fn achievement(
+    code: &'static str,
+    name: &'static str,
+    description: &'static str,
+    metric: AchievementMetric,
+    threshold: f64,
+) -> AchievementDefinition {
+    AchievementDefinition {
+        code,
+        name,
+        description,
+        metric,
+        threshold,
+    }
+}
+
+/// Every achievement there is. Codes are stored in `user_achievements`, so
+/// never rename one; add a new definition instead.
+pub const ACHIEVEMENTS: &[AchievementDefinition] = &[
+    achievement(
+        "first_workout",
+        "First Steps",
+        "Finish your first workout",
+        AchievementMetric::Workouts,
+        1.0,
+    ),
+    achievement(
+        "workouts_10",
+        "Getting Serious",
+        "Finish 10 workouts",
+        AchievementMetric::Workouts,
+        10.0,
+    ),
+    achievement(
+        "workouts_100",
+        "Centurion",
+        "Finish 100 workouts",
+        AchievementMetric::Workouts,
+        100.0,
+    ),
+    achievement(
+        "streak_4_weeks",
+        "Habit Formed",
+        "Train every week for 4 weeks in a row",
+        AchievementMetric::WeeklyStreak,
+        4.0,
+    ),
+    achievement(
+        "streak_12_weeks",
+        "Unstoppable",
+        "Train every week for 12 weeks in a row",
+        AchievementMetric::WeeklyStreak,
+        12.0,
+    ),
+    achievement(
+        "first_pr",
+        "Personal Best",
+        "Set your first personal record",
+        AchievementMetric::PersonalRecords,
+        1.0,
+    ),
+    achievement(
+        "prs_10",
+        "Record Breaker",
+        "Set 10 personal records",
+        AchievementMetric::PersonalRecords,
+        10.0,
+    ),
+    achievement(
+        "prs_50",
+        "Record Collector",
+        "Set 50 personal records",
+        AchievementMetric::PersonalRecords,
+        50.0,
+    ),
+    achievement(
+        "volume_10t",
+        "Ten Tonnes",
+        "Lift 10,000 kg in total",
+        AchievementMetric::VolumeKg,
+        10_000.0,
+    ),
+    achievement(
+        "volume_100t",
+        "Heavy Hauler",
+        "Lift 100,000 kg in total",
+        AchievementMetric::VolumeKg,
+        100_000.0,
+    ),
+    achievement(
+        "volume_1000t",
+        "Mountain Mover",
+        "Lift 1,000,000 kg in total",
+        AchievementMetric::VolumeKg,
+        1_000_000.0,
+    ),
+];
+
+pub fn find_achievement(code: &str) -> Option<&'static AchievementDefinition> {
+    ACHIEVEMENTS.iter().find(|a| a.code == code)
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct UserAchievement {
+    #[serde(skip)]
+    pub user_id: Uuid,
+    pub code: String,
+    pub unlocked_at: chrono::DateTime<chrono::Utc>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AchievementStatus {
+    #[serde(flatten)]
+    pub definition: &'static AchievementDefinition,
+    pub progress: f64,
+    pub unlocked_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StreakSummary {
+    /// Weeks in a row up to this week; a week without training yet does not break it.
+    pub current_weeks: i64,
+    pub longest_weeks: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AchievementsResponse {
+    pub streak: StreakSummary,
+    pub achievements: Vec<AchievementStatus>,
+}
@@ -0,0 +1,302 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+pub const ACTIVITY_RUN: &str = "run";
+pub const ACTIVITY_RIDE: &str = "ride";
+pub const ACTIVITY_SWIM: &str = "swim";
+pub const ACTIVITY_ROW: &str = "row";
+pub const ACTIVITY_WALK: &str = "walk";
+pub const ACTIVITY_OTHER: &str = "other";
+
+pub const ACTIVITY_TYPES: &[&str] = &[
+    ACTIVITY_RUN,
+    ACTIVITY_RIDE,
+    ACTIVITY_SWIM,
+    ACTIVITY_ROW,
+    ACTIVITY_WALK,
+    ACTIVITY_OTHER,
+];
+
+pub const SOURCE_GPX: &str = "gpx";
+pub const SOURCE_TCX: &str = "tcx";
+pub const SOURCE_FIT: &str = "fit";
+
+pub const SPLIT_UNIT_KM: &str = "km";
+pub const SPLIT_UNIT_MILE: &str = "mi";
+
+/// A cardio activity. `duration_seconds` is the elapsed time, `moving_seconds`
+/// leaves out stops. `pace_seconds_per_km` is computed by the database from the
+/// moving time where known.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct Activity {
+    pub id: Uuid,
+    #[serde(skip)]
+    pub user_id: Uuid,
+    pub activity_type: String,
+    pub name: Option<String>,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub duration_seconds: i32,
+    pub moving_seconds: Option<i32>,
+    pub distance_m: Option<f64>,
+    pub elevation_gain_m: Option<f64>,
+    pub elevation_loss_m: Option<f64>,
+    pub avg_heart_rate: Option<i32>,
+    pub max_heart_rate: Option<i32>,
+    pub avg_cadence: Option<i32>,
+    /// File format the activity was imported from; NULL when entered by hand.
+    pub source_format: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub notes: Option<String>,
+    pub pace_seconds_per_km: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct ActivityLap {
+    #[serde(skip)]
+    pub activity_id: Uuid,
+    pub lap_index: i32,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub duration_seconds: f64,
+    pub distance_m: Option<f64>,
+    pub avg_heart_rate: Option<i32>,
+}
+
+/// One recorded sample of a track.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TrackPoint {
+    pub time: chrono::DateTime<chrono::Utc>,
+    pub lat: Option<f64>,
+    pub lon: Option<f64>,
+    pub elevation_m: Option<f64>,
+    /// Cumulative distance from the start.
+    pub distance_m: Option<f64>,
+    pub heart_rate: Option<i32>,
+    pub cadence: Option<i32>,
+}
+
+/// A kilometre or mile of a recorded track; the last split may be shorter.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ActivitySplit {
+    /// 1-based position of the split.
+    pub split: i32,
+    pub distance_m: f64,
+    pub duration_seconds: f64,
+    pub pace_seconds_per_km: f64,
+    pub elevation_change_m: Option<f64>,
+    pub avg_heart_rate: Option<i32>,
+}
+
+/// Time spent in one heart rate zone. `max_bpm` is exclusive except for the top zone.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HeartRateZone {
+    pub zone: i32,
+    pub min_bpm: i32,
+    pub max_bpm: i32,
+    pub seconds: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HeartRateZones {
+    pub method: String,
+    pub max_heart_rate: i32,
+    pub resting_heart_rate: Option<i32>,
+    pub zones: Vec<HeartRateZone>,
+    /// Recorded time below the first zone.
+    pub below_zones_seconds: i32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ActivityDetail {
+    #[serde(flatten)]
+    pub activity: Activity,
+    pub laps: Vec<ActivityLap>,
+    /// Route as an encoded polyline (precision 5), when the track has positions.
+    pub polyline: Option<String>,
+    /// Kilometres or miles, following the profile's preferred units.
+    pub split_unit: &'static str,
+    pub splits: Vec<ActivitySplit>,
+    /// Time in zone, when the track has heart rate and the profile a max heart rate.
+    pub heart_rate_zones: Option<HeartRateZones>,
+}
+
+/// An activity entered by hand rather than imported from a recording.
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateActivityRequest {
+    pub activity_type: String,
+    #[validate(length(min = 1, max = 120, message = "Name must be 1-120 characters"))]
+    pub name: Option<String>,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    #[validate(range(
+        min = 1,
+        max = 604800,
+        message = "Duration must be between 1 second and 7 days"
+    ))]
+    pub duration_seconds: i32,
+    #[validate(range(
+        min = 0,
+        max = 604800,
+        message = "Moving time must be between 0 seconds and 7 days"
+    ))]
+    pub moving_seconds: Option<i32>,
+    #[validate(range(
+        min = 0.0,
+        max = 1000000.0,
+        message = "Distance must be between 0 and 1000 km"
+    ))]
+    pub distance_m: Option<f64>,
+    #[validate(range(
+        min = 0.0,
+        max = 20000.0,
+        message = "Elevation gain must be between 0 and 20000 m"
+    ))]
+    pub elevation_gain_m: Option<f64>,
+    #[validate(range(
+        min = 0.0,
+        max = 20000.0,
+        message = "Elevation loss must be between 0 and 20000 m"
+    ))]
+    pub elevation_loss_m: Option<f64>,
+    #[validate(range(
+        min = 25,
+        max = 250,
+        message = "Heart rate must be between 25 and 250 bpm"
+    ))]
+    pub avg_heart_rate: Option<i32>,
+    #[validate(range(
+        min = 25,
+        max = 250,
+        message = "Heart rate must be between 25 and 250 bpm"
+    ))]
+    pub max_heart_rate: Option<i32>,
+    #[validate(range(max = 300, message = "Cadence must be at most 300"))]
+    pub avg_cadence: Option<i32>,
+    #[validate(length(max = 2000, message = "Notes must be at most 2000 characters"))]
+    pub notes: Option<String>,
+}
+
+/// Partial activity update; omitted fields keep their current value. The
+/// metrics of a recorded activity come from its track and cannot be changed.
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateActivityRequest {
+    pub activity_type: Option<String>,
+    #[validate(length(min = 1, max = 120, message = "Name must be 1-120 characters"))]
+    pub name: Option<String>,
+    pub started_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[validate(range(
+        min = 1,
+        max = 604800,
+        message = "Duration must be between 1 second and 7 days"
+    ))]
+    pub duration_seconds: Option<i32>,
+    #[validate(range(
+        min = 0,
+        max = 604800,
+        message = "Moving time must be between 0 seconds and 7 days"
+    ))]
+    pub moving_seconds: Option<i32>,
+    #[validate(range(
+        min = 0.0,
+        max = 1000000.0,
+        message = "Distance must be between 0 and 1000 km"
+    ))]
+    pub distance_m: Option<f64>,
+    #[validate(range(
+        min = 0.0,
+        max = 20000.0,
+        message = "Elevation gain must be between 0 and 20000 m"
+    ))]
+    pub elevation_gain_m: Option<f64>,
+    #[validate(range(
+        min = 0.0,
+        max = 20000.0,
+        message = "Elevation loss must be between 0 and 20000 m"
+    ))]
+    pub elevation_loss_m: Option<f64>,
+    #[validate(range(
+        min = 25,
+        max = 250,
+        message = "Heart rate must be between 25 and 250 bpm"
+    ))]
+    pub avg_heart_rate: Option<i32>,
+    #[validate(range(
+        min = 25,
+        max = 250,
+        message = "Heart rate must be between 25 and 250 bpm"
+    ))]
+    pub max_heart_rate: Option<i32>,
+    #[validate(range(max = 300, message = "Cadence must be at most 300"))]
+    pub avg_cadence: Option<i32>,
+    #[validate(length(max = 2000, message = "Notes must be at most 2000 characters"))]
+    pub notes: Option<String>,
+}
+
+impl UpdateActivityRequest {
+    /// Whether the update touches anything a recorded track determines.
+    pub fn changes_metrics(&self) -> bool {
+        self.started_at.is_some()
+            || self.duration_seconds.is_some()
+            || self.moving_seconds.is_some()
+            || self.distance_m.is_some()
+            || self.elevation_gain_m.is_some()
+            || self.elevation_loss_m.is_some()
+            || self.avg_heart_rate.is_some()
+            || self.max_heart_rate.is_some()
+            || self.avg_cadence.is_some()
+    }
+}
+
+/// Calendar days in the profile timezone, both ends inclusive.
+#[derive(Debug, Deserialize)]
+pub struct ActivitySummaryQuery {
+    pub from: Option<chrono::NaiveDate>,
+    pub to: Option<chrono::NaiveDate>,
+    pub period: Option<String>,
+    pub activity_type: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ActivityTotals {
+    pub activity_type: String,
+    pub activities: i64,
+    pub distance_m: f64,
+    pub duration_seconds: i64,
+    pub moving_seconds: i64,
+    pub elevation_gain_m: f64,
+    /// Over the activities with a distance.
+    pub pace_seconds_per_km: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ActivityPeriodTotals {
+    pub period_start: chrono::NaiveDate,
+    pub activity_type: String,
+    pub activities: i64,
+    pub distance_m: f64,
+    pub duration_seconds: i64,
+    pub moving_seconds: i64,
+    pub elevation_gain_m: f64,
+    pub pace_seconds_per_km: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ActivitySummary {
+    pub totals: Vec<ActivityTotals>,
+    pub periods: Vec<ActivityPeriodTotals>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ActivityQuery {
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+    pub activity_type: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ActivityExportQuery {
+    pub format: String,
+}
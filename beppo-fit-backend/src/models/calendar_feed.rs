@@ -0,0 +1,26 @@
+use serde::Serialize;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, FromRow)]
+pub struct CalendarFeed {
+    pub user_id: Uuid,
+    pub token: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CalendarFeedResponse {
+    /// Secret subscription URL; anyone who has it can read the calendar.
+    pub url: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<CalendarFeed> for CalendarFeedResponse {
+    fn from(feed: CalendarFeed) -> Self {
+        Self {
+            url: format!("/calendar/feed/{}.ics", feed.token),
+            created_at: feed.created_at,
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::models::validation::{nullable, one_of};
+
+pub const MUSCLE_GROUPS: &[&str] = &[
+    "abdominals",
+    "abductors",
+    "adductors",
+    "biceps",
+    "calves",
+    "chest",
+    "forearms",
+    "glutes",
+    "hamstrings",
+    "lats",
+    "lower_back",
+    "neck",
+    "obliques",
+    "quadriceps",
+    "shoulders",
+    "traps",
+    "triceps",
+    "upper_back",
+];
+
+pub const EQUIPMENT: &[&str] = &[
+    "barbell",
+    "dumbbell",
+    "kettlebell",
+    "cable",
+    "machine",
+    "bodyweight",
+    "band",
+    "smith_machine",
+    "ez_bar",
+    "trap_bar",
+    "medicine_ball",
+    "other",
+];
+
+pub const CATEGORIES: &[&str] = &[
+    "strength",
+    "powerlifting",
+    "olympic_weightlifting",
+    "strongman",
+    "plyometrics",
+    "cardio",
+    "stretching",
+    "mobility",
+];
+
+pub const MECHANICS: &[&str] = &["compound", "isolation"];
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct Exercise {
+    pub id: Uuid,
+    pub slug: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub primary_muscles: Vec<String>,
+    pub secondary_muscles: Vec<String>,
+    pub equipment: String,
+    pub category: String,
+    pub mechanics: Option<String>,
+    pub instructions: Vec<String>,
+    pub media_urls: Vec<String>,
+    /// Set for a user's private custom exercise, `None` for the global catalog.
+    pub owner_id: Option<Uuid>,
+    pub archived_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExerciseQuery {
+    /// Matches exercises that train the muscle as a primary or secondary mover.
+    pub muscle: Option<String>,
+    pub equipment: Option<String>,
+    pub category: Option<String>,
+    /// Case-insensitive substring search on the name.
+    pub q: Option<String>,
+    /// Only custom exercises (`true`) or only the global catalog (`false`).
+    pub custom: Option<bool>,
+    /// Include the caller's archived custom exercises.
+    pub include_archived: Option<bool>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateExerciseRequest {
+    #[validate(length(min = 1, max = 120, message = "Name must be 1-120 characters"))]
+    pub name: String,
+    #[validate(length(max = 5000, message = "Description must be at most 5000 characters"))]
+    pub description: Option<String>,
+    #[validate(
+        length(min = 1, message = "At least one primary muscle is required"),
+        custom = "validate_muscles"
+    )]
+    pub primary_muscles: Vec<String>,
+    #[serde(default)]
+    #[validate(custom = "validate_muscles")]
+    pub secondary_muscles: Vec<String>,
+    #[validate(custom = "validate_equipment")]
+    pub equipment: String,
+    #[validate(custom = "validate_category")]
+    pub category: String,
+    #[validate(custom = "validate_mechanics")]
+    pub mechanics: Option<String>,
+    #[serde(default)]
+    pub instructions: Vec<String>,
+}
+
+/// Partial update of a custom exercise; omitted fields keep their value and
+/// `null` clears the optional ones. `archived: false` restores an archived exercise.
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateExerciseRequest {
+    #[validate(length(min = 1, max = 120, message = "Name must be 1-120 characters"))]
+    pub name: Option<String>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(length(max = 5000, message = "Description must be at most 5000 characters"))]
+    pub description: Option<Option<String>>,
+    #[validate(
+        length(min = 1, message = "At least one primary muscle is required"),
+        custom = "validate_muscles"
+    )]
+    pub primary_muscles: Option<Vec<String>>,
+    #[validate(custom = "validate_muscles")]
+    pub secondary_muscles: Option<Vec<String>>,
+    #[validate(custom = "validate_equipment")]
+    pub equipment: Option<String>,
+    #[validate(custom = "validate_category")]
+    pub category: Option<String>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(custom = "validate_mechanics")]
+    pub mechanics: Option<Option<String>>,
+    pub instructions: Option<Vec<String>>,
+    pub archived: Option<bool>,
+}
+
+fn validate_muscles(muscles: &[String]) -> Result<(), ValidationError> {
+    muscles
+        .iter()
+        .try_for_each(|m| one_of(m, MUSCLE_GROUPS, "muscle"))
+}
+
+fn validate_equipment(equipment: &str) -> Result<(), ValidationError> {
+    one_of(equipment, EQUIPMENT, "equipment")
+}
+
+fn validate_category(category: &str) -> Result<(), ValidationError> {
+    one_of(category, CATEGORIES, "category")
+}
+
+fn validate_mechanics(mechanics: &str) -> Result<(), ValidationError> {
+    one_of(mechanics, MECHANICS, "mechanics")
+}
+
+/// Lowercase, dash-separated identifier derived from an exercise name.
+pub fn slugify(name: &str) -> String {
+    let slug = name
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+
+    if slug.is_empty() {
+        "exercise".to_string()
+    } else {
+        slug
+    }
+}
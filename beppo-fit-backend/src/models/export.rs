@@ -0,0 +1,102 @@
+use serde::Serialize;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::models::media::Media;
+
+pub const EXPORT_STATUS_PENDING: &str = "pending";
+pub const EXPORT_STATUS_READY: &str = "ready";
+pub const EXPORT_STATUS_FAILED: &str = "failed";
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct DataExport {
+    pub id: Uuid,
+    pub status: String,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// A single login method linked to an account, as listed in `identities.csv`.
+#[derive(Debug, Serialize)]
+pub struct IdentityRecord {
+    pub provider: &'static str,
+    pub subject: String,
+}
+
+/// Metadata of an uploaded image, as listed in `media.csv`.
+#[derive(Debug, Serialize)]
+pub struct MediaRecord {
+    pub id: Uuid,
+    pub kind: String,
+    pub content_type: String,
+    pub width: i32,
+    pub height: i32,
+    pub taken_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub notes: Option<String>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<&Media> for MediaRecord {
+    fn from(media: &Media) -> Self {
+        Self {
+            id: media.id,
+            kind: media.kind.clone(),
+            content_type: media.content_type.clone(),
+            width: media.width,
+            height: media.height,
+            taken_at: media.taken_at,
+            notes: media.notes.clone(),
+            created_at: media.created_at,
+        }
+    }
+}
+
+/// One logged set per row, as listed in `workouts.csv`.
+#[derive(Debug, Serialize, FromRow)]
+pub struct WorkoutSetRecord {
+    pub workout_id: Uuid,
+    pub workout_name: Option<String>,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub finished_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub exercise: String,
+    pub exercise_position: i32,
+    pub set_position: i32,
+    pub set_type: String,
+    pub reps: Option<i32>,
+    pub weight_kg: Option<f64>,
+    pub duration_seconds: Option<i32>,
+    pub distance_m: Option<f64>,
+    pub rpe: Option<f64>,
+    pub rir: Option<i32>,
+    pub notes: Option<String>,
+}
+
+/// One lap of an activity per row, as listed in `activity_laps.csv`.
+#[derive(Debug, Serialize, FromRow)]
+pub struct ActivityLapRecord {
+    pub activity_id: Uuid,
+    pub lap_index: i32,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub duration_seconds: f64,
+    pub distance_m: Option<f64>,
+    pub avg_heart_rate: Option<i32>,
+}
+
+/// An uploaded history file awaiting commit, as listed in `strength_imports.csv`.
+/// The file itself is included under `strength_imports/`.
+#[derive(Debug, Serialize, FromRow)]
+pub struct StrengthImportRecord {
+    pub id: Uuid,
+    pub source: String,
+    pub units: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// An exercise name mapped by hand, as listed in `strength_import_mappings.csv`.
+#[derive(Debug, Serialize, FromRow)]
+pub struct StrengthImportMappingRecord {
+    pub import_id: Uuid,
+    pub source_name: String,
+    pub exercise_id: Option<Uuid>,
+}
@@ -0,0 +1,211 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::models::{
+    record::{RECORD_ESTIMATED_1RM, RECORD_MAX_WEIGHT},
+    validation::one_of,
+};
+
+pub const GOAL_STRENGTH: &str = "strength";
+pub const GOAL_BODYWEIGHT: &str = "bodyweight";
+pub const GOAL_FREQUENCY: &str = "frequency";
+pub const GOAL_CUSTOM: &str = "custom";
+
+pub const GOAL_TYPES: &[&str] = &[GOAL_STRENGTH, GOAL_BODYWEIGHT, GOAL_FREQUENCY, GOAL_CUSTOM];
+pub const STRENGTH_METRICS: &[&str] = &[RECORD_MAX_WEIGHT, RECORD_ESTIMATED_1RM];
+
+pub const GOAL_STATUS_ACTIVE: &str = "active";
+pub const GOAL_STATUS_COMPLETED: &str = "completed";
+pub const GOAL_STATUS_MISSED: &str = "missed";
+
+/// A goal. Strength and bodyweight values are in kg, frequency goals count
+/// finished workouts in the current week.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct Goal {
+    pub id: Uuid,
+    #[serde(skip)]
+    pub user_id: Uuid,
+    pub goal_type: String,
+    pub title: String,
+    pub exercise_id: Option<Uuid>,
+    pub metric: Option<String>,
+    pub unit: Option<String>,
+    pub start_value: Option<f64>,
+    pub target_value: f64,
+    pub current_value: Option<f64>,
+    pub deadline: Option<chrono::NaiveDate>,
+    pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl Goal {
+    /// Bodyweight and custom goals may aim below their starting point. Bodyweight
+    /// goals always have one; custom goals without one count up.
+    pub fn is_decreasing(&self) -> bool {
+        self.start_value
+            .is_some_and(|start| self.target_value < start)
+    }
+
+    pub fn is_reached(&self) -> bool {
+        match self.current_value {
+            Some(current) if self.is_decreasing() => current <= self.target_value,
+            Some(current) => current >= self.target_value,
+            None => false,
+        }
+    }
+
+    /// Share of the way from the start value to the target, 0-100.
+    pub fn progress_percent(&self) -> Option<f64> {
+        let current = self.current_value?;
+        let start = self.start_value.unwrap_or(0.0);
+        if self.is_reached() {
+            return Some(100.0);
+        }
+        if self.target_value == start {
+            return Some(0.0);
+        }
+        let progress = (current - start) / (self.target_value - start) * 100.0;
+        Some((progress.clamp(0.0, 100.0) * 10.0).round() / 10.0)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct GoalResponse {
+    #[serde(flatten)]
+    pub goal: Goal,
+    pub status: &'static str,
+    pub progress_percent: Option<f64>,
+    /// Days left until the deadline in the user's timezone; negative once passed.
+    pub days_remaining: Option<i64>,
+}
+
+impl GoalResponse {
+    pub fn new(goal: Goal, today: chrono::NaiveDate) -> Self {
+        let days_remaining = goal.deadline.map(|d| (d - today).num_days());
+        let status = if goal.completed_at.is_some() {
+            GOAL_STATUS_COMPLETED
+        } else if days_remaining.is_some_and(|d| d < 0) {
+            GOAL_STATUS_MISSED
+        } else {
+            GOAL_STATUS_ACTIVE
+        };
+        Self {
+            progress_percent: goal.progress_percent(),
+            status,
+            days_remaining,
+            goal,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[validate(schema(function = "validate_goal_fields"))]
+pub struct CreateGoalRequest {
+    #[validate(custom = "validate_goal_type")]
+    pub goal_type: String,
+    #[validate(length(min = 1, max = 120, message = "Title must be 1-120 characters"))]
+    pub title: String,
+    pub exercise_id: Option<Uuid>,
+    #[validate(custom = "validate_strength_metric")]
+    pub metric: Option<String>,
+    #[validate(length(min = 1, max = 20, message = "Unit must be 1-20 characters"))]
+    pub unit: Option<String>,
+    /// Only for custom goals; other kinds start from the logged data.
+    pub start_value: Option<f64>,
+    #[validate(range(
+        min = 0.0,
+        max = 100000.0,
+        message = "Target must be between 0 and 100000"
+    ))]
+    pub target_value: f64,
+    pub deadline: Option<chrono::NaiveDate>,
+}
+
+/// Partial goal update; `current_value` can only be set on custom goals.
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateGoalRequest {
+    #[validate(length(min = 1, max = 120, message = "Title must be 1-120 characters"))]
+    pub title: Option<String>,
+    #[validate(length(min = 1, max = 20, message = "Unit must be 1-20 characters"))]
+    pub unit: Option<String>,
+    #[validate(range(
+        min = 0.0,
+        max = 100000.0,
+        message = "Target must be between 0 and 100000"
+    ))]
+    pub target_value: Option<f64>,
+    pub current_value: Option<f64>,
+    pub deadline: Option<chrono::NaiveDate>,
+}
+
+fn validate_goal_type(goal_type: &str) -> Result<(), ValidationError> {
+    one_of(goal_type, GOAL_TYPES, "goal_type")
+}
+
+fn validate_strength_metric(metric: &str) -> Result<(), ValidationError> {
+    one_of(metric, STRENGTH_METRICS, "metric")
+}
+
+fn validate_goal_fields(goal: &CreateGoalRequest) -> Result<(), ValidationError> {
+    let is_strength = goal.goal_type == GOAL_STRENGTH;
+    let message = if is_strength && goal.exercise_id.is_none() {
+        Some("Strength goals require an exercise_id")
+    } else if !is_strength && (goal.exercise_id.is_some() || goal.metric.is_some()) {
+        Some("Only strength goals take an exercise and metric")
+    } else if goal.goal_type != GOAL_CUSTOM && goal.start_value.is_some() {
+        Some("Only custom goals take a start_value")
+    } else {
+        None
+    };
+
+    match message {
+        Some(message) => {
+            let mut error = ValidationError::new("goal");
+            error.message = Some(message.into());
+            Err(error)
+        }
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn goal(start: Option<f64>, target: f64, current: Option<f64>) -> Goal {
+        Goal {
+            id: Uuid::nil(),
+            user_id: Uuid::nil(),
+            goal_type: GOAL_BODYWEIGHT.into(),
+            title: "Goal".into(),
+            exercise_id: None,
+            metric: None,
+            unit: None,
+            start_value: start,
+            target_value: target,
+            current_value: current,
+            deadline: None,
+            completed_at: None,
+            created_at: chrono::Utc::now(),
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn computes_progress_in_both_directions() {
+        assert_eq!(
+            goal(Some(80.0), 75.0, Some(77.0)).progress_percent(),
+            Some(60.0)
+        );
+        assert!(goal(Some(80.0), 75.0, Some(74.5)).is_reached());
+        assert_eq!(goal(None, 100.0, Some(90.0)).progress_percent(), Some(90.0));
+        assert_eq!(
+            goal(Some(60.0), 65.0, Some(58.0)).progress_percent(),
+            Some(0.0)
+        );
+        assert_eq!(goal(None, 4.0, None).progress_percent(), None);
+    }
+}
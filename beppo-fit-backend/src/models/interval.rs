@@ -0,0 +1,239 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::models::validation::one_of;
+
+/// A new round starts every minute; round lengths are whole minutes.
+pub const FORMAT_EMOM: &str = "emom";
+/// As many rounds as possible; every block has a time cap.
+pub const FORMAT_AMRAP: &str = "amrap";
+/// Rounds of one work and one rest step.
+pub const FORMAT_TABATA: &str = "tabata";
+pub const FORMAT_CUSTOM: &str = "custom";
+
+pub const INTERVAL_FORMATS: &[&str] = &[FORMAT_EMOM, FORMAT_AMRAP, FORMAT_TABATA, FORMAT_CUSTOM];
+
+pub const STEP_WORK: &str = "work";
+pub const STEP_REST: &str = "rest";
+
+pub const STEP_KINDS: &[&str] = &[STEP_WORK, STEP_REST];
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct IntervalWorkout {
+    pub id: Uuid,
+    #[serde(skip)]
+    pub user_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub format: String,
+    /// Planned length including rests.
+    pub total_seconds: i32,
+    /// Present while the definition is shared; anyone with the code can view and copy it.
+    pub share_code: Option<String>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct IntervalBlock {
+    pub id: Uuid,
+    #[serde(skip)]
+    pub interval_workout_id: Uuid,
+    pub position: i32,
+    pub name: Option<String>,
+    pub rounds: i32,
+    pub time_cap_seconds: Option<i32>,
+    pub rest_between_rounds_seconds: i32,
+    pub rest_after_seconds: i32,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct IntervalStep {
+    #[serde(skip)]
+    pub block_id: Uuid,
+    pub position: i32,
+    pub kind: String,
+    pub exercise_id: Option<Uuid>,
+    pub exercise_name: Option<String>,
+    pub label: Option<String>,
+    pub duration_seconds: Option<i32>,
+    pub target_reps: Option<i32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IntervalBlockDetail {
+    #[serde(flatten)]
+    pub block: IntervalBlock,
+    pub steps: Vec<IntervalStep>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IntervalWorkoutDetail {
+    #[serde(flatten)]
+    pub workout: IntervalWorkout,
+    pub blocks: Vec<IntervalBlockDetail>,
+}
+
+/// Full interval definition; blocks and steps are ordered by their position.
+#[derive(Debug, Deserialize, Validate)]
+#[validate(schema(function = "validate_block_count"))]
+pub struct SaveIntervalWorkoutRequest {
+    #[validate(length(min = 1, max = 120, message = "Name must be 1-120 characters"))]
+    pub name: String,
+    #[validate(length(max = 5000, message = "Description must be at most 5000 characters"))]
+    pub description: Option<String>,
+    #[validate(custom = "validate_format")]
+    pub format: String,
+    #[validate]
+    pub blocks: Vec<SaveIntervalBlock>,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[validate(schema(function = "validate_step_count"))]
+pub struct SaveIntervalBlock {
+    #[validate(length(min = 1, max = 120, message = "Block name must be 1-120 characters"))]
+    pub name: Option<String>,
+    #[serde(default = "default_rounds")]
+    #[validate(range(min = 1, max = 100, message = "Rounds must be between 1 and 100"))]
+    pub rounds: i32,
+    #[validate(range(
+        min = 10,
+        max = 7200,
+        message = "Time cap must be between 10 seconds and 2 hours"
+    ))]
+    pub time_cap_seconds: Option<i32>,
+    #[serde(default)]
+    #[validate(range(min = 0, max = 3600, message = "Rest must be at most one hour"))]
+    pub rest_between_rounds_seconds: i32,
+    #[serde(default)]
+    #[validate(range(min = 0, max = 3600, message = "Rest must be at most one hour"))]
+    pub rest_after_seconds: i32,
+    #[validate]
+    pub steps: Vec<SaveIntervalStep>,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[validate(schema(function = "validate_step"))]
+pub struct SaveIntervalStep {
+    #[validate(custom = "validate_step_kind")]
+    pub kind: String,
+    pub exercise_id: Option<Uuid>,
+    #[validate(length(min = 1, max = 120, message = "Label must be 1-120 characters"))]
+    pub label: Option<String>,
+    #[validate(range(
+        min = 1,
+        max = 3600,
+        message = "Step duration must be between 1 second and one hour"
+    ))]
+    pub duration_seconds: Option<i32>,
+    #[validate(range(min = 1, max = 1000, message = "Reps must be between 1 and 1000"))]
+    pub target_reps: Option<i32>,
+}
+
+fn default_rounds() -> i32 {
+    1
+}
+
+fn validate_format(format: &str) -> Result<(), ValidationError> {
+    one_of(format, INTERVAL_FORMATS, "format")
+}
+
+fn validate_step_kind(kind: &str) -> Result<(), ValidationError> {
+    one_of(kind, STEP_KINDS, "kind")
+}
+
+fn validate_block_count(workout: &SaveIntervalWorkoutRequest) -> Result<(), ValidationError> {
+    if (1..=20).contains(&workout.blocks.len()) {
+        Ok(())
+    } else {
+        let mut error = ValidationError::new("blocks");
+        error.message = Some("An interval workout has 1-20 blocks".into());
+        Err(error)
+    }
+}
+
+fn validate_step_count(block: &SaveIntervalBlock) -> Result<(), ValidationError> {
+    if (1..=30).contains(&block.steps.len()) {
+        Ok(())
+    } else {
+        let mut error = ValidationError::new("steps");
+        error.message = Some("A block has 1-30 steps".into());
+        Err(error)
+    }
+}
+
+fn validate_step(step: &SaveIntervalStep) -> Result<(), ValidationError> {
+    if step.kind == STEP_REST && (step.exercise_id.is_some() || step.target_reps.is_some()) {
+        let mut error = ValidationError::new("kind");
+        error.message = Some("Rest steps have no exercise or reps".into());
+        return Err(error);
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct IntervalSession {
+    pub id: Uuid,
+    #[serde(skip)]
+    pub user_id: Uuid,
+    /// NULL once the definition has been deleted.
+    pub interval_workout_id: Option<Uuid>,
+    pub name: String,
+    pub format: String,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub duration_seconds: i32,
+    pub notes: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct IntervalBlockResult {
+    #[serde(skip)]
+    pub session_id: Uuid,
+    pub block_position: i32,
+    pub rounds_completed: i32,
+    /// Reps beyond the completed rounds, e.g. the partial round of an AMRAP.
+    pub reps: Option<i32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IntervalSessionDetail {
+    #[serde(flatten)]
+    pub session: IntervalSession,
+    pub blocks: Vec<IntervalBlockResult>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct LogIntervalSessionRequest {
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    #[validate(range(
+        min = 1,
+        max = 86400,
+        message = "Duration must be between 1 second and one day"
+    ))]
+    pub duration_seconds: i32,
+    #[validate(length(max = 2000, message = "Notes must be at most 2000 characters"))]
+    pub notes: Option<String>,
+    #[serde(default)]
+    #[validate]
+    pub blocks: Vec<SaveIntervalBlockResult>,
+}
+
+/// What was achieved in one block of the definition, by its position.
+#[derive(Debug, Deserialize, Validate)]
+pub struct SaveIntervalBlockResult {
+    pub block_position: i32,
+    #[validate(range(min = 0, max = 1000, message = "Rounds must be between 0 and 1000"))]
+    pub rounds_completed: i32,
+    #[validate(range(min = 0, max = 10000, message = "Reps must be between 0 and 10000"))]
+    pub reps: Option<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IntervalSessionQuery {
+    pub interval_workout_id: Option<Uuid>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
@@ -0,0 +1,438 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::models::{profile::validate_units, validation::nullable};
+
+pub const KG_PER_LB: f64 = 0.45359237;
+pub const CM_PER_INCH: f64 = 2.54;
+
+/// Metrics available as time series; `bmi` and `ffmi` are derived using the profile height.
+pub const MEASUREMENT_METRICS: &[&str] = &[
+    "weight",
+    "body_fat_percent",
+    "neck",
+    "chest",
+    "waist",
+    "hips",
+    "left_arm",
+    "right_arm",
+    "left_thigh",
+    "right_thigh",
+    "left_calf",
+    "right_calf",
+    "bmi",
+    "ffmi",
+];
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+pub fn mass_from_kg(kg: f64, units: &str) -> f64 {
+    match units {
+        "imperial" => round2(kg / KG_PER_LB),
+        _ => round2(kg),
+    }
+}
+
+pub fn mass_to_kg(value: f64, units: &str) -> f64 {
+    match units {
+        "imperial" => value * KG_PER_LB,
+        _ => value,
+    }
+}
+
+pub fn length_from_cm(cm: f64, units: &str) -> f64 {
+    match units {
+        "imperial" => round2(cm / CM_PER_INCH),
+        _ => round2(cm),
+    }
+}
+
+pub fn length_to_cm(value: f64, units: &str) -> f64 {
+    match units {
+        "imperial" => value * CM_PER_INCH,
+        _ => value,
+    }
+}
+
+pub fn bmi(weight_kg: f64, height_cm: f64) -> f64 {
+    let height_m = height_cm / 100.0;
+    (weight_kg / (height_m * height_m) * 10.0).round() / 10.0
+}
+
+/// Fat-free mass index: lean body mass over height squared.
+pub fn ffmi(weight_kg: f64, body_fat_percent: f64, height_cm: f64) -> f64 {
+    bmi(weight_kg * (1.0 - body_fat_percent / 100.0), height_cm)
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct BodyMeasurement {
+    pub id: Uuid,
+    #[serde(skip)]
+    pub user_id: Uuid,
+    pub measured_at: chrono::DateTime<chrono::Utc>,
+    pub weight_kg: Option<f64>,
+    pub body_fat_percent: Option<f64>,
+    pub neck_cm: Option<f64>,
+    pub chest_cm: Option<f64>,
+    pub waist_cm: Option<f64>,
+    pub hips_cm: Option<f64>,
+    pub left_arm_cm: Option<f64>,
+    pub right_arm_cm: Option<f64>,
+    pub left_thigh_cm: Option<f64>,
+    pub right_thigh_cm: Option<f64>,
+    pub left_calf_cm: Option<f64>,
+    pub right_calf_cm: Option<f64>,
+    pub notes: Option<String>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl BodyMeasurement {
+    pub fn has_values(&self) -> bool {
+        self.weight_kg.is_some()
+            || self.body_fat_percent.is_some()
+            || !Circumferences::from(self).is_empty()
+    }
+}
+
+/// Circumferences in centimetres or inches, depending on context.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate)]
+pub struct Circumferences {
+    #[validate(range(
+        min = 1.0,
+        max = 500.0,
+        message = "Circumferences must be between 1 and 500"
+    ))]
+    pub neck: Option<f64>,
+    #[validate(range(
+        min = 1.0,
+        max = 500.0,
+        message = "Circumferences must be between 1 and 500"
+    ))]
+    pub chest: Option<f64>,
+    #[validate(range(
+        min = 1.0,
+        max = 500.0,
+        message = "Circumferences must be between 1 and 500"
+    ))]
+    pub waist: Option<f64>,
+    #[validate(range(
+        min = 1.0,
+        max = 500.0,
+        message = "Circumferences must be between 1 and 500"
+    ))]
+    pub hips: Option<f64>,
+    #[validate(range(
+        min = 1.0,
+        max = 500.0,
+        message = "Circumferences must be between 1 and 500"
+    ))]
+    pub left_arm: Option<f64>,
+    #[validate(range(
+        min = 1.0,
+        max = 500.0,
+        message = "Circumferences must be between 1 and 500"
+    ))]
+    pub right_arm: Option<f64>,
+    #[validate(range(
+        min = 1.0,
+        max = 500.0,
+        message = "Circumferences must be between 1 and 500"
+    ))]
+    pub left_thigh: Option<f64>,
+    #[validate(range(
+        min = 1.0,
+        max = 500.0,
+        message = "Circumferences must be between 1 and 500"
+    ))]
+    pub right_thigh: Option<f64>,
+    #[validate(range(
+        min = 1.0,
+        max = 500.0,
+        message = "Circumferences must be between 1 and 500"
+    ))]
+    pub left_calf: Option<f64>,
+    #[validate(range(
+        min = 1.0,
+        max = 500.0,
+        message = "Circumferences must be between 1 and 500"
+    ))]
+    pub right_calf: Option<f64>,
+}
+
+impl Circumferences {
+    pub fn map(&self, f: impl Fn(f64) -> f64) -> Self {
+        Self {
+            neck: self.neck.map(&f),
+            chest: self.chest.map(&f),
+            waist: self.waist.map(&f),
+            hips: self.hips.map(&f),
+            left_arm: self.left_arm.map(&f),
+            right_arm: self.right_arm.map(&f),
+            left_thigh: self.left_thigh.map(&f),
+            right_thigh: self.right_thigh.map(&f),
+            left_calf: self.left_calf.map(&f),
+            right_calf: self.right_calf.map(&f),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        [
+            self.neck,
+            self.chest,
+            self.waist,
+            self.hips,
+            self.left_arm,
+            self.right_arm,
+            self.left_thigh,
+            self.right_thigh,
+            self.left_calf,
+            self.right_calf,
+        ]
+        .iter()
+        .all(Option::is_none)
+    }
+}
+
+/// Circumference changes of a partial update: omitted keeps, `null` clears.
+#[derive(Debug, Clone, Default, Deserialize, Validate)]
+pub struct CircumferenceChanges {
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(range(
+        min = 1.0,
+        max = 500.0,
+        message = "Circumferences must be between 1 and 500"
+    ))]
+    pub neck: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(range(
+        min = 1.0,
+        max = 500.0,
+        message = "Circumferences must be between 1 and 500"
+    ))]
+    pub chest: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(range(
+        min = 1.0,
+        max = 500.0,
+        message = "Circumferences must be between 1 and 500"
+    ))]
+    pub waist: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(range(
+        min = 1.0,
+        max = 500.0,
+        message = "Circumferences must be between 1 and 500"
+    ))]
+    pub hips: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(range(
+        min = 1.0,
+        max = 500.0,
+        message = "Circumferences must be between 1 and 500"
+    ))]
+    pub left_arm: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(range(
+        min = 1.0,
+        max = 500.0,
+        message = "Circumferences must be between 1 and 500"
+    ))]
+    pub right_arm: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(range(
+        min = 1.0,
+        max = 500.0,
+        message = "Circumferences must be between 1 and 500"
+    ))]
+    pub left_thigh: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(range(
+        min = 1.0,
+        max = 500.0,
+        message = "Circumferences must be between 1 and 500"
+    ))]
+    pub right_thigh: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(range(
+        min = 1.0,
+        max = 500.0,
+        message = "Circumferences must be between 1 and 500"
+    ))]
+    pub left_calf: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(range(
+        min = 1.0,
+        max = 500.0,
+        message = "Circumferences must be between 1 and 500"
+    ))]
+    pub right_calf: Option<Option<f64>>,
+}
+
+impl CircumferenceChanges {
+    pub fn map(&self, f: impl Fn(f64) -> f64) -> Self {
+        Self {
+            neck: self.neck.map(|v| v.map(&f)),
+            chest: self.chest.map(|v| v.map(&f)),
+            waist: self.waist.map(|v| v.map(&f)),
+            hips: self.hips.map(|v| v.map(&f)),
+            left_arm: self.left_arm.map(|v| v.map(&f)),
+            right_arm: self.right_arm.map(|v| v.map(&f)),
+            left_thigh: self.left_thigh.map(|v| v.map(&f)),
+            right_thigh: self.right_thigh.map(|v| v.map(&f)),
+            left_calf: self.left_calf.map(|v| v.map(&f)),
+            right_calf: self.right_calf.map(|v| v.map(&f)),
+        }
+    }
+}
+
+impl From<&BodyMeasurement> for Circumferences {
+    fn from(m: &BodyMeasurement) -> Self {
+        Self {
+            neck: m.neck_cm,
+            chest: m.chest_cm,
+            waist: m.waist_cm,
+            hips: m.hips_cm,
+            left_arm: m.left_arm_cm,
+            right_arm: m.right_arm_cm,
+            left_thigh: m.left_thigh_cm,
+            right_thigh: m.right_thigh_cm,
+            left_calf: m.left_calf_cm,
+            right_calf: m.right_calf_cm,
+        }
+    }
+}
+
+/// A measurement in the requested unit system, with derived indices.
+#[derive(Debug, Serialize)]
+pub struct MeasurementResponse {
+    pub id: Uuid,
+    pub measured_at: chrono::DateTime<chrono::Utc>,
+    pub units: String,
+    pub weight: Option<f64>,
+    pub body_fat_percent: Option<f64>,
+    #[serde(flatten)]
+    pub circumferences: Circumferences,
+    pub notes: Option<String>,
+    /// Requires the profile height.
+    pub bmi: Option<f64>,
+    /// Requires the profile height and a body fat percentage.
+    pub ffmi: Option<f64>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl MeasurementResponse {
+    pub fn new(m: BodyMeasurement, units: &str, height_cm: Option<f64>) -> Self {
+        Self {
+            id: m.id,
+            measured_at: m.measured_at,
+            units: units.to_string(),
+            weight: m.weight_kg.map(|kg| mass_from_kg(kg, units)),
+            body_fat_percent: m.body_fat_percent,
+            circumferences: Circumferences::from(&m).map(|cm| length_from_cm(cm, units)),
+            bmi: m.weight_kg.zip(height_cm).map(|(w, h)| bmi(w, h)),
+            ffmi: m
+                .weight_kg
+                .zip(m.body_fat_percent)
+                .zip(height_cm)
+                .map(|((w, bf), h)| ffmi(w, bf, h)),
+            notes: m.notes,
+            created_at: m.created_at,
+            updated_at: m.updated_at,
+        }
+    }
+}
+
+/// Measurement values in `units`, or the profile's preferred units if omitted.
+#[derive(Debug, Deserialize, Validate)]
+pub struct SaveMeasurementRequest {
+    pub measured_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[validate(custom = "validate_units")]
+    pub units: Option<String>,
+    #[validate(range(min = 1.0, max = 1500.0, message = "Weight must be between 1 and 1500"))]
+    pub weight: Option<f64>,
+    #[validate(range(min = 1.0, max = 75.0, message = "Body fat must be between 1 and 75%"))]
+    pub body_fat_percent: Option<f64>,
+    #[serde(flatten)]
+    #[validate]
+    pub circumferences: Circumferences,
+    #[validate(length(max = 2000, message = "Notes must be at most 2000 characters"))]
+    pub notes: Option<String>,
+}
+
+/// Partial update; omitted values keep their current value and `null` clears them.
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateMeasurementRequest {
+    pub measured_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[validate(custom = "validate_units")]
+    pub units: Option<String>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(range(min = 1.0, max = 1500.0, message = "Weight must be between 1 and 1500"))]
+    pub weight: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(range(min = 1.0, max = 75.0, message = "Body fat must be between 1 and 75%"))]
+    pub body_fat_percent: Option<Option<f64>>,
+    #[serde(flatten)]
+    #[validate]
+    pub circumferences: CircumferenceChanges,
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(length(max = 2000, message = "Notes must be at most 2000 characters"))]
+    pub notes: Option<Option<String>>,
+}
+
+impl SaveMeasurementRequest {
+    pub fn has_values(&self) -> bool {
+        self.weight.is_some() || self.body_fat_percent.is_some() || !self.circumferences.is_empty()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MeasurementQuery {
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+    pub units: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SeriesQuery {
+    pub metric: String,
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+    pub units: Option<String>,
+    /// Trailing window of the moving average, in days.
+    pub window_days: Option<i32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SeriesPoint {
+    pub measured_at: chrono::DateTime<chrono::Utc>,
+    pub value: f64,
+    pub moving_average: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_units() {
+        assert_eq!(mass_from_kg(80.0, "imperial"), 176.37);
+        assert_eq!(mass_from_kg(80.0, "metric"), 80.0);
+        assert!((mass_to_kg(176.37, "imperial") - 80.0).abs() < 0.01);
+        assert_eq!(length_from_cm(81.28, "imperial"), 32.0);
+        assert_eq!(length_to_cm(32.0, "imperial"), 81.28);
+    }
+
+    #[test]
+    fn derives_body_indices() {
+        assert_eq!(bmi(80.0, 180.0), 24.7);
+        assert_eq!(ffmi(80.0, 15.0, 180.0), 21.0);
+    }
+}
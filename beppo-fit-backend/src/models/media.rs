@@ -0,0 +1,58 @@
+use serde::Serialize;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::services::media::signed_url;
+
+pub const MEDIA_KIND_AVATAR: &str = "avatar";
+pub const MEDIA_KIND_PROGRESS_PHOTO: &str = "progress_photo";
+
+pub const VARIANT_ORIGINAL: &str = "original";
+pub const VARIANT_THUMBNAIL: &str = "thumbnail";
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct Media {
+    pub id: Uuid,
+    #[serde(skip)]
+    pub user_id: Uuid,
+    pub kind: String,
+    pub content_type: String,
+    #[serde(skip)]
+    pub storage_key: String,
+    #[serde(skip)]
+    pub thumbnail_key: String,
+    pub width: i32,
+    pub height: i32,
+    pub size_bytes: i64,
+    pub taken_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub notes: Option<String>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl Media {
+    pub fn key_for(&self, variant: &str) -> Option<&str> {
+        match variant {
+            VARIANT_ORIGINAL => Some(&self.storage_key),
+            VARIANT_THUMBNAIL => Some(&self.thumbnail_key),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct MediaResponse {
+    #[serde(flatten)]
+    pub media: Media,
+    pub url: String,
+    pub thumbnail_url: String,
+}
+
+impl From<Media> for MediaResponse {
+    fn from(media: Media) -> Self {
+        Self {
+            url: signed_url(media.id, VARIANT_ORIGINAL),
+            thumbnail_url: signed_url(media.id, VARIANT_THUMBNAIL),
+            media,
+        }
+    }
+}
@@ -1 +1,24 @@
+pub mod achievement;
+pub mod activity;
+pub mod calendar_feed;
+pub mod exercise;
+pub mod export;
+pub mod goal;
+pub mod interval;
+pub mod measurement;
+pub mod media;
+pub mod notification;
+pub mod nutrition;
+pub mod profile;
+pub mod program;
+pub mod progression;
+pub mod record;
+pub mod schedule;
+pub mod social;
+pub mod stats;
+pub mod strength_import;
+pub mod template;
 pub mod user;
+pub mod validation;
+pub mod wellness;
+pub mod workout;
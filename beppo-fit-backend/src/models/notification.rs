@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const NOTIFICATION_GOAL_COMPLETED: &str = "goal_completed";
+pub const NOTIFICATION_ACHIEVEMENT_UNLOCKED: &str = "achievement_unlocked";
+pub const NOTIFICATION_NEW_FOLLOWER: &str = "new_follower";
+pub const NOTIFICATION_FOLLOW_REQUEST: &str = "follow_request";
+pub const NOTIFICATION_FOLLOW_ACCEPTED: &str = "follow_accepted";
+
+/// An in-app notification; clients poll for unread ones.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct Notification {
+    pub id: Uuid,
+    #[serde(skip)]
+    pub user_id: Uuid,
+    pub kind: String,
+    pub title: String,
+    pub body: Option<String>,
+    pub goal_id: Option<Uuid>,
+    pub read_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NotificationQuery {
+    #[serde(default)]
+    pub unread: bool,
+    pub limit: Option<i64>,
+}
@@ -0,0 +1,443 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::models::validation::one_of;
+
+pub const SOURCE_USER: &str = "user";
+/// Imported from an Open Food Facts product dump.
+pub const SOURCE_OPEN_FOOD_FACTS: &str = "off";
+/// Imported from a USDA FoodData Central CSV download.
+pub const SOURCE_USDA: &str = "usda";
+
+pub const NUTRITION_GOAL_CUT: &str = "cut";
+pub const NUTRITION_GOAL_MAINTAIN: &str = "maintain";
+pub const NUTRITION_GOAL_BULK: &str = "bulk";
+
+pub const NUTRITION_GOALS: &[&str] = &[
+    NUTRITION_GOAL_CUT,
+    NUTRITION_GOAL_MAINTAIN,
+    NUTRITION_GOAL_BULK,
+];
+
+pub const MEAL_TYPES: &[&str] = &["breakfast", "lunch", "dinner", "snack"];
+
+/// Nutrient values are per 100 g.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct Food {
+    pub id: Uuid,
+    /// Set for a user's own food, `None` for the shared catalog.
+    pub owner_id: Option<Uuid>,
+    pub name: String,
+    pub brand: Option<String>,
+    pub barcode: Option<String>,
+    pub source: String,
+    pub source_id: Option<String>,
+    pub serving_size_g: Option<f64>,
+    pub serving_name: Option<String>,
+    pub energy_kcal: f64,
+    pub protein_g: Option<f64>,
+    pub carbohydrate_g: Option<f64>,
+    pub fat_g: Option<f64>,
+    pub fiber_g: Option<f64>,
+    pub sugar_g: Option<f64>,
+    pub saturated_fat_g: Option<f64>,
+    pub sodium_mg: Option<f64>,
+    pub potassium_mg: Option<f64>,
+    pub calcium_mg: Option<f64>,
+    pub iron_mg: Option<f64>,
+    pub vitamin_c_mg: Option<f64>,
+    pub archived_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Nutrients of an amount of food. Values the source does not list are `None`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Nutrients {
+    pub energy_kcal: f64,
+    pub protein_g: Option<f64>,
+    pub carbohydrate_g: Option<f64>,
+    pub fat_g: Option<f64>,
+    pub fiber_g: Option<f64>,
+    pub sugar_g: Option<f64>,
+    pub saturated_fat_g: Option<f64>,
+    pub sodium_mg: Option<f64>,
+    pub potassium_mg: Option<f64>,
+    pub calcium_mg: Option<f64>,
+    pub iron_mg: Option<f64>,
+    pub vitamin_c_mg: Option<f64>,
+}
+
+fn round1(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+fn add(total: Option<f64>, value: Option<f64>) -> Option<f64> {
+    match (total, value) {
+        (Some(a), Some(b)) => Some(a + b),
+        (a, b) => a.or(b),
+    }
+}
+
+impl Nutrients {
+    /// Every value multiplied by `factor`, rounded to one decimal.
+    pub fn scaled(&self, factor: f64) -> Nutrients {
+        let scale = |v: Option<f64>| v.map(|v| round1(v * factor));
+        Nutrients {
+            energy_kcal: round1(self.energy_kcal * factor),
+            protein_g: scale(self.protein_g),
+            carbohydrate_g: scale(self.carbohydrate_g),
+            fat_g: scale(self.fat_g),
+            fiber_g: scale(self.fiber_g),
+            sugar_g: scale(self.sugar_g),
+            saturated_fat_g: scale(self.saturated_fat_g),
+            sodium_mg: scale(self.sodium_mg),
+            potassium_mg: scale(self.potassium_mg),
+            calcium_mg: scale(self.calcium_mg),
+            iron_mg: scale(self.iron_mg),
+            vitamin_c_mg: scale(self.vitamin_c_mg),
+        }
+    }
+
+    /// Adds `other`; a value is only `None` if neither side lists it.
+    pub fn add(&mut self, other: &Nutrients) {
+        self.energy_kcal = round1(self.energy_kcal + other.energy_kcal);
+        self.protein_g = add(self.protein_g, other.protein_g).map(round1);
+        self.carbohydrate_g = add(self.carbohydrate_g, other.carbohydrate_g).map(round1);
+        self.fat_g = add(self.fat_g, other.fat_g).map(round1);
+        self.fiber_g = add(self.fiber_g, other.fiber_g).map(round1);
+        self.sugar_g = add(self.sugar_g, other.sugar_g).map(round1);
+        self.saturated_fat_g = add(self.saturated_fat_g, other.saturated_fat_g).map(round1);
+        self.sodium_mg = add(self.sodium_mg, other.sodium_mg).map(round1);
+        self.potassium_mg = add(self.potassium_mg, other.potassium_mg).map(round1);
+        self.calcium_mg = add(self.calcium_mg, other.calcium_mg).map(round1);
+        self.iron_mg = add(self.iron_mg, other.iron_mg).map(round1);
+        self.vitamin_c_mg = add(self.vitamin_c_mg, other.vitamin_c_mg).map(round1);
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FoodQuery {
+    /// Case-insensitive substring search on the name and brand.
+    pub q: Option<String>,
+    /// Exact barcode (EAN/UPC) lookup.
+    pub barcode: Option<String>,
+    /// Only the caller's own foods (`true`) or only the shared catalog (`false`).
+    pub custom: Option<bool>,
+    /// Include the caller's archived foods.
+    pub include_archived: Option<bool>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[validate(schema(function = "validate_new_food_macros"))]
+pub struct CreateFoodRequest {
+    #[validate(length(min = 1, max = 200, message = "Name must be 1-200 characters"))]
+    pub name: String,
+    #[validate(length(min = 1, max = 200, message = "Brand must be 1-200 characters"))]
+    pub brand: Option<String>,
+    #[validate(custom = "validate_barcode")]
+    pub barcode: Option<String>,
+    #[validate(range(
+        min = 0.1,
+        max = 5000.0,
+        message = "Serving size must be between 0.1 and 5000 g"
+    ))]
+    pub serving_size_g: Option<f64>,
+    #[validate(length(min = 1, max = 60, message = "Serving name must be 1-60 characters"))]
+    pub serving_name: Option<String>,
+    #[validate(range(
+        min = 0.0,
+        max = 900.0,
+        message = "Energy must be between 0 and 900 kcal per 100 g"
+    ))]
+    pub energy_kcal: f64,
+    #[validate(range(min = 0.0, max = 100.0, message = "Grams per 100 g must be 0-100"))]
+    pub protein_g: Option<f64>,
+    #[validate(range(min = 0.0, max = 100.0, message = "Grams per 100 g must be 0-100"))]
+    pub carbohydrate_g: Option<f64>,
+    #[validate(range(min = 0.0, max = 100.0, message = "Grams per 100 g must be 0-100"))]
+    pub fat_g: Option<f64>,
+    #[validate(range(min = 0.0, max = 100.0, message = "Grams per 100 g must be 0-100"))]
+    pub fiber_g: Option<f64>,
+    #[validate(range(min = 0.0, max = 100.0, message = "Grams per 100 g must be 0-100"))]
+    pub sugar_g: Option<f64>,
+    #[validate(range(min = 0.0, max = 100.0, message = "Grams per 100 g must be 0-100"))]
+    pub saturated_fat_g: Option<f64>,
+    #[validate(range(
+        min = 0.0,
+        max = 100000.0,
+        message = "Milligrams per 100 g must be 0-100000"
+    ))]
+    pub sodium_mg: Option<f64>,
+    #[validate(range(
+        min = 0.0,
+        max = 100000.0,
+        message = "Milligrams per 100 g must be 0-100000"
+    ))]
+    pub potassium_mg: Option<f64>,
+    #[validate(range(
+        min = 0.0,
+        max = 100000.0,
+        message = "Milligrams per 100 g must be 0-100000"
+    ))]
+    pub calcium_mg: Option<f64>,
+    #[validate(range(
+        min = 0.0,
+        max = 100000.0,
+        message = "Milligrams per 100 g must be 0-100000"
+    ))]
+    pub iron_mg: Option<f64>,
+    #[validate(range(
+        min = 0.0,
+        max = 100000.0,
+        message = "Milligrams per 100 g must be 0-100000"
+    ))]
+    pub vitamin_c_mg: Option<f64>,
+}
+
+/// Partial update of a user's own food; omitted fields keep their value.
+/// `archived: false` restores an archived food.
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateFoodRequest {
+    #[validate(length(min = 1, max = 200, message = "Name must be 1-200 characters"))]
+    pub name: Option<String>,
+    #[validate(length(min = 1, max = 200, message = "Brand must be 1-200 characters"))]
+    pub brand: Option<String>,
+    #[validate(custom = "validate_barcode")]
+    pub barcode: Option<String>,
+    #[validate(range(
+        min = 0.1,
+        max = 5000.0,
+        message = "Serving size must be between 0.1 and 5000 g"
+    ))]
+    pub serving_size_g: Option<f64>,
+    #[validate(length(min = 1, max = 60, message = "Serving name must be 1-60 characters"))]
+    pub serving_name: Option<String>,
+    #[validate(range(
+        min = 0.0,
+        max = 900.0,
+        message = "Energy must be between 0 and 900 kcal per 100 g"
+    ))]
+    pub energy_kcal: Option<f64>,
+    #[validate(range(min = 0.0, max = 100.0, message = "Grams per 100 g must be 0-100"))]
+    pub protein_g: Option<f64>,
+    #[validate(range(min = 0.0, max = 100.0, message = "Grams per 100 g must be 0-100"))]
+    pub carbohydrate_g: Option<f64>,
+    #[validate(range(min = 0.0, max = 100.0, message = "Grams per 100 g must be 0-100"))]
+    pub fat_g: Option<f64>,
+    #[validate(range(min = 0.0, max = 100.0, message = "Grams per 100 g must be 0-100"))]
+    pub fiber_g: Option<f64>,
+    #[validate(range(min = 0.0, max = 100.0, message = "Grams per 100 g must be 0-100"))]
+    pub sugar_g: Option<f64>,
+    #[validate(range(min = 0.0, max = 100.0, message = "Grams per 100 g must be 0-100"))]
+    pub saturated_fat_g: Option<f64>,
+    #[validate(range(
+        min = 0.0,
+        max = 100000.0,
+        message = "Milligrams per 100 g must be 0-100000"
+    ))]
+    pub sodium_mg: Option<f64>,
+    #[validate(range(
+        min = 0.0,
+        max = 100000.0,
+        message = "Milligrams per 100 g must be 0-100000"
+    ))]
+    pub potassium_mg: Option<f64>,
+    #[validate(range(
+        min = 0.0,
+        max = 100000.0,
+        message = "Milligrams per 100 g must be 0-100000"
+    ))]
+    pub calcium_mg: Option<f64>,
+    #[validate(range(
+        min = 0.0,
+        max = 100000.0,
+        message = "Milligrams per 100 g must be 0-100000"
+    ))]
+    pub iron_mg: Option<f64>,
+    #[validate(range(
+        min = 0.0,
+        max = 100000.0,
+        message = "Milligrams per 100 g must be 0-100000"
+    ))]
+    pub vitamin_c_mg: Option<f64>,
+    pub archived: Option<bool>,
+}
+
+fn validate_barcode(barcode: &str) -> Result<(), ValidationError> {
+    if (8..=14).contains(&barcode.len()) && barcode.bytes().all(|b| b.is_ascii_digit()) {
+        Ok(())
+    } else {
+        let mut error = ValidationError::new("barcode");
+        error.message = Some("Barcode must be 8-14 digits".into());
+        Err(error)
+    }
+}
+
+/// The macronutrients of 100 g cannot weigh more than 100 g.
+pub fn check_macros(
+    protein_g: Option<f64>,
+    carbohydrate_g: Option<f64>,
+    fat_g: Option<f64>,
+) -> Result<(), ValidationError> {
+    let total = protein_g.unwrap_or(0.0) + carbohydrate_g.unwrap_or(0.0) + fat_g.unwrap_or(0.0);
+    // Leave room for rounding on nutrition labels
+    if total > 101.0 {
+        let mut error = ValidationError::new("macros");
+        error.message = Some("Protein, carbohydrate and fat exceed 100 g per 100 g".into());
+        return Err(error);
+    }
+    Ok(())
+}
+
+fn validate_new_food_macros(food: &CreateFoodRequest) -> Result<(), ValidationError> {
+    check_macros(food.protein_g, food.carbohydrate_g, food.fat_g)
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct Meal {
+    pub id: Uuid,
+    #[serde(skip)]
+    pub user_id: Uuid,
+    pub meal_type: String,
+    pub eaten_at: chrono::DateTime<chrono::Utc>,
+    pub notes: Option<String>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MealItemDetail {
+    pub id: Uuid,
+    pub position: i32,
+    pub food_id: Uuid,
+    pub food_name: String,
+    pub brand: Option<String>,
+    pub grams: f64,
+    pub nutrients: Nutrients,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MealDetail {
+    #[serde(flatten)]
+    pub meal: Meal,
+    pub items: Vec<MealItemDetail>,
+    pub totals: Nutrients,
+}
+
+/// A full meal; items replace the existing ones on update.
+#[derive(Debug, Deserialize, Validate)]
+#[validate(schema(function = "validate_item_count"))]
+pub struct SaveMealRequest {
+    #[validate(custom = "validate_meal_type")]
+    pub meal_type: String,
+    pub eaten_at: chrono::DateTime<chrono::Utc>,
+    #[validate(length(max = 2000, message = "Notes must be at most 2000 characters"))]
+    pub notes: Option<String>,
+    #[validate]
+    pub items: Vec<SaveMealItem>,
+}
+
+/// A portion, either in grams or in servings of the food.
+#[derive(Debug, Deserialize, Validate)]
+#[validate(schema(function = "validate_portion"))]
+pub struct SaveMealItem {
+    pub food_id: Uuid,
+    #[validate(range(
+        min = 0.1,
+        max = 5000.0,
+        message = "Grams must be between 0.1 and 5000"
+    ))]
+    pub grams: Option<f64>,
+    #[validate(range(min = 0.1, max = 50.0, message = "Servings must be between 0.1 and 50"))]
+    pub servings: Option<f64>,
+}
+
+fn validate_meal_type(meal_type: &str) -> Result<(), ValidationError> {
+    one_of(meal_type, MEAL_TYPES, "meal_type")
+}
+
+fn validate_item_count(meal: &SaveMealRequest) -> Result<(), ValidationError> {
+    if (1..=100).contains(&meal.items.len()) {
+        Ok(())
+    } else {
+        let mut error = ValidationError::new("items");
+        error.message = Some("A meal has 1-100 items".into());
+        Err(error)
+    }
+}
+
+fn validate_portion(item: &SaveMealItem) -> Result<(), ValidationError> {
+    if item.grams.is_some() == item.servings.is_some() {
+        let mut error = ValidationError::new("portion");
+        error.message = Some("Give either grams or servings".into());
+        return Err(error);
+    }
+    Ok(())
+}
+
+/// Meals eaten on the given local days; both default to today.
+#[derive(Debug, Deserialize)]
+pub struct MealQuery {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub meal_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DailyNutritionQuery {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+/// What was eaten on one local day. Days without meals are omitted.
+#[derive(Debug, Serialize)]
+pub struct DailyNutrition {
+    pub date: NaiveDate,
+    pub meals: i64,
+    #[serde(flatten)]
+    pub totals: Nutrients,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NutritionTargetsQuery {
+    /// Overrides the goal stored in the profile.
+    pub goal: Option<String>,
+}
+
+/// Energy expenditure observed from logged intake and the bodyweight trend.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AdaptiveTdee {
+    pub days_logged: i64,
+    pub average_intake_kcal: f64,
+    pub weight_change_kg_per_week: f64,
+    pub observed_tdee_kcal: f64,
+    /// Applied to the formula estimate; capped so one noisy month cannot swing it far.
+    pub adjustment_kcal: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NutritionTargets {
+    pub goal: String,
+    /// `katch_mcardle` when a recent body fat reading exists, else `mifflin_st_jeor`.
+    pub bmr_method: &'static str,
+    pub weight_kg: f64,
+    pub body_fat_percent: Option<f64>,
+    pub bmr_kcal: f64,
+    pub training_days_per_week: f64,
+    pub activity_level: &'static str,
+    pub activity_factor: f64,
+    pub formula_tdee_kcal: f64,
+    /// Present once enough meals and weigh-ins are logged.
+    pub adaptive: Option<AdaptiveTdee>,
+    pub tdee_kcal: f64,
+    pub calories_kcal: f64,
+    pub protein_g: f64,
+    pub fat_g: f64,
+    pub carbohydrate_g: f64,
+}
@@ -0,0 +1,222 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::models::{
+    nutrition::NUTRITION_GOALS,
+    validation::{nullable, one_of},
+};
+
+pub const SEXES: &[&str] = &["male", "female", "other"];
+pub const UNIT_SYSTEMS: &[&str] = &["metric", "imperial"];
+pub const VISIBILITIES: &[&str] = &["public", "followers", "private"];
+
+/// Zone boundaries as a share of the heart rate reserve (max - resting).
+pub const ZONE_METHOD_KARVONEN: &str = "karvonen";
+/// Zone boundaries as a share of the maximum heart rate.
+pub const ZONE_METHOD_PERCENT_MAX: &str = "percent_max";
+pub const ZONE_METHODS: &[&str] = &[ZONE_METHOD_PERCENT_MAX, ZONE_METHOD_KARVONEN];
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct UserProfile {
+    #[serde(skip)]
+    pub user_id: Uuid,
+    pub display_name: Option<String>,
+    pub date_of_birth: Option<NaiveDate>,
+    pub sex: Option<String>,
+    pub height_cm: Option<f64>,
+    pub preferred_units: String,
+    pub timezone: String,
+    pub locale: String,
+    pub avatar_url: Option<String>,
+    pub is_private: bool,
+    pub default_workout_visibility: String,
+    pub avatar_media_id: Option<Uuid>,
+    pub max_heart_rate: Option<i32>,
+    pub resting_heart_rate: Option<i32>,
+    pub heart_rate_zone_method: String,
+    pub nutrition_goal: String,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl UserProfile {
+    /// The profile timezone, falling back to UTC if the stored name is unknown.
+    pub fn tz(&self) -> chrono_tz::Tz {
+        self.timezone.parse().unwrap_or(chrono_tz::UTC)
+    }
+
+    /// The user's current calendar date.
+    pub fn today(&self) -> NaiveDate {
+        chrono::Utc::now().with_timezone(&self.tz()).date_naive()
+    }
+
+    /// The instant a calendar day starts in the profile timezone.
+    pub fn start_of_day(&self, date: NaiveDate) -> chrono::DateTime<chrono::Utc> {
+        self.local_instant(date.and_hms_opt(0, 0, 0).unwrap())
+    }
+
+    /// The instant a wall-clock time in the profile timezone refers to.
+    /// Times skipped by a DST change are moved forward by an hour.
+    pub fn local_instant(&self, local: chrono::NaiveDateTime) -> chrono::DateTime<chrono::Utc> {
+        use chrono::TimeZone;
+
+        let tz = self.tz();
+        tz.from_local_datetime(&local)
+            .earliest()
+            .or_else(|| {
+                tz.from_local_datetime(&(local + chrono::Duration::hours(1)))
+                    .earliest()
+            })
+            .map(|t| t.with_timezone(&chrono::Utc))
+            .unwrap_or_else(|| chrono::Utc.from_utc_datetime(&local))
+    }
+
+    /// A freshly created profile, as the database defaults make it.
+    #[cfg(test)]
+    pub fn test_default(user_id: Uuid) -> Self {
+        Self {
+            user_id,
+            display_name: None,
+            date_of_birth: None,
+            sex: None,
+            height_cm: None,
+            preferred_units: "metric".into(),
+            timezone: "UTC".into(),
+            locale: "en".into(),
+            avatar_url: None,
+            is_private: false,
+            default_workout_visibility: "followers".into(),
+            avatar_media_id: None,
+            max_heart_rate: None,
+            resting_heart_rate: None,
+            heart_rate_zone_method: ZONE_METHOD_PERCENT_MAX.into(),
+            nutrition_goal: "maintain".into(),
+            created_at: None,
+            updated_at: None,
+        }
+    }
+}
+
+/// Partial profile update; fields that are omitted keep their current value.
+/// Optional fields are cleared by sending `null`.
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateProfileRequest {
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(length(min = 1, max = 100, message = "Display name must be 1-100 characters"))]
+    pub display_name: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(custom = "validate_date_of_birth")]
+    pub date_of_birth: Option<Option<NaiveDate>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(custom = "validate_sex")]
+    pub sex: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(range(
+        min = 50.0,
+        max = 300.0,
+        message = "Height must be between 50 and 300 cm"
+    ))]
+    pub height_cm: Option<Option<f64>>,
+    #[validate(custom = "validate_units")]
+    pub preferred_units: Option<String>,
+    #[validate(custom = "validate_timezone")]
+    pub timezone: Option<String>,
+    #[validate(custom = "validate_locale")]
+    pub locale: Option<String>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(url(message = "Avatar must be a valid URL"))]
+    pub avatar_url: Option<Option<String>>,
+    pub is_private: Option<bool>,
+    #[validate(custom = "validate_visibility")]
+    pub default_workout_visibility: Option<String>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(range(
+        min = 100,
+        max = 240,
+        message = "Max heart rate must be between 100 and 240 bpm"
+    ))]
+    pub max_heart_rate: Option<Option<i32>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(range(
+        min = 25,
+        max = 120,
+        message = "Resting heart rate must be between 25 and 120 bpm"
+    ))]
+    pub resting_heart_rate: Option<Option<i32>>,
+    #[validate(custom = "validate_zone_method")]
+    pub heart_rate_zone_method: Option<String>,
+    #[validate(custom = "validate_nutrition_goal")]
+    pub nutrition_goal: Option<String>,
+}
+
+fn validate_sex(sex: &str) -> Result<(), ValidationError> {
+    one_of(sex, SEXES, "sex")
+}
+
+pub fn validate_units(units: &str) -> Result<(), ValidationError> {
+    one_of(units, UNIT_SYSTEMS, "preferred_units")
+}
+
+pub fn validate_visibility(visibility: &str) -> Result<(), ValidationError> {
+    one_of(visibility, VISIBILITIES, "visibility")
+}
+
+fn validate_zone_method(method: &str) -> Result<(), ValidationError> {
+    one_of(method, ZONE_METHODS, "heart_rate_zone_method")
+}
+
+pub fn validate_nutrition_goal(goal: &str) -> Result<(), ValidationError> {
+    one_of(goal, NUTRITION_GOALS, "nutrition_goal")
+}
+
+fn validate_timezone(timezone: &str) -> Result<(), ValidationError> {
+    timezone
+        .parse::<chrono_tz::Tz>()
+        .map(|_| ())
+        .map_err(|_| ValidationError::new("timezone"))
+}
+
+fn validate_locale(locale: &str) -> Result<(), ValidationError> {
+    // Accepts BCP 47 language tags of the form "en" or "de-CH".
+    let mut parts = locale.split('-');
+    let language_ok = parts
+        .next()
+        .is_some_and(|l| (2..=3).contains(&l.len()) && l.chars().all(|c| c.is_ascii_lowercase()));
+    let region_ok =
+        parts.all(|p| (2..=8).contains(&p.len()) && p.chars().all(|c| c.is_ascii_alphanumeric()));
+    if language_ok && region_ok {
+        Ok(())
+    } else {
+        Err(ValidationError::new("locale"))
+    }
+}
+
+fn validate_date_of_birth(date: &NaiveDate) -> Result<(), ValidationError> {
+    let today = chrono::Utc::now().date_naive();
+    if *date > today || *date < NaiveDate::from_ymd_opt(1900, 1, 1).unwrap() {
+        return Err(ValidationError::new("date_of_birth"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locale_validation() {
+        assert!(validate_locale("en").is_ok());
+        assert!(validate_locale("de-CH").is_ok());
+        assert!(validate_locale("EN").is_err());
+        assert!(validate_locale("english").is_err());
+    }
+
+    #[test]
+    fn test_timezone_validation() {
+        assert!(validate_timezone("Europe/Zurich").is_ok());
+        assert!(validate_timezone("Mars/Olympus").is_err());
+    }
+}
@@ -0,0 +1,211 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::models::wellness::SessionAdjustment;
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct Program {
+    pub id: Uuid,
+    #[serde(skip)]
+    pub user_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    /// Computed target weights are rounded to a multiple of this.
+    pub rounding_kg: f64,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct ProgramDay {
+    pub id: Uuid,
+    #[serde(skip)]
+    pub program_id: Uuid,
+    pub week_number: i32,
+    pub day_number: i32,
+    pub template_id: Uuid,
+    pub template_name: String,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct Prescription {
+    #[serde(skip)]
+    pub program_day_id: Uuid,
+    pub exercise_id: Uuid,
+    pub position: i32,
+    pub percent_of_tm: f64,
+    pub reps: i32,
+    pub is_amrap: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProgramDayDetail {
+    #[serde(flatten)]
+    pub day: ProgramDay,
+    pub prescriptions: Vec<Prescription>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProgramWeekDetail {
+    pub week_number: i32,
+    pub is_deload: bool,
+    pub days: Vec<ProgramDayDetail>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProgramDetail {
+    #[serde(flatten)]
+    pub program: Program,
+    pub weeks: Vec<ProgramWeekDetail>,
+}
+
+/// Full program payload; weeks and days are numbered by their position.
+#[derive(Debug, Deserialize, Validate)]
+#[validate(schema(function = "validate_week_count"))]
+pub struct SaveProgramRequest {
+    #[validate(length(min = 1, max = 120, message = "Name must be 1-120 characters"))]
+    pub name: String,
+    #[validate(length(max = 5000, message = "Description must be at most 5000 characters"))]
+    pub description: Option<String>,
+    #[serde(default = "default_rounding_kg")]
+    #[validate(range(
+        min = 0.25,
+        max = 20.0,
+        message = "Rounding must be between 0.25 and 20 kg"
+    ))]
+    pub rounding_kg: f64,
+    #[validate]
+    pub weeks: Vec<SaveProgramWeek>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[validate(schema(function = "validate_day_count"))]
+pub struct SaveProgramWeek {
+    #[serde(default)]
+    pub is_deload: bool,
+    #[validate]
+    pub days: Vec<SaveProgramDay>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct SaveProgramDay {
+    pub template_id: Uuid,
+    #[validate(length(min = 1, max = 120, message = "Day name must be 1-120 characters"))]
+    pub name: Option<String>,
+    #[serde(default)]
+    #[validate]
+    pub prescriptions: Vec<SavePrescription>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct SavePrescription {
+    pub exercise_id: Uuid,
+    #[validate(range(
+        min = 1.0,
+        max = 150.0,
+        message = "Percentage must be between 1 and 150"
+    ))]
+    pub percent_of_tm: f64,
+    #[validate(range(min = 1, max = 100, message = "Reps must be between 1 and 100"))]
+    pub reps: i32,
+    #[serde(default)]
+    pub is_amrap: bool,
+}
+
+fn default_rounding_kg() -> f64 {
+    2.5
+}
+
+fn validate_week_count(program: &SaveProgramRequest) -> Result<(), ValidationError> {
+    if (1..=52).contains(&program.weeks.len()) {
+        Ok(())
+    } else {
+        let mut error = ValidationError::new("weeks");
+        error.message = Some("A program has 1-52 weeks".into());
+        Err(error)
+    }
+}
+
+fn validate_day_count(week: &SaveProgramWeek) -> Result<(), ValidationError> {
+    if (1..=14).contains(&week.days.len()) {
+        Ok(())
+    } else {
+        let mut error = ValidationError::new("days");
+        error.message = Some("A week has 1-14 days".into());
+        Err(error)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct TrainingMax {
+    pub exercise_id: Uuid,
+    pub exercise_name: String,
+    pub weight_kg: f64,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct SetTrainingMaxRequest {
+    #[validate(range(
+        min = 1.0,
+        max = 2000.0,
+        message = "Weight must be between 1 and 2000 kg"
+    ))]
+    pub weight_kg: f64,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct Enrollment {
+    pub id: Uuid,
+    #[serde(skip)]
+    pub user_id: Uuid,
+    pub program_id: Uuid,
+    pub current_week: i32,
+    pub current_day: i32,
+    pub pending_workout_id: Option<Uuid>,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PlannedSet {
+    pub reps: Option<i32>,
+    pub weight_kg: Option<f64>,
+    pub duration_seconds: Option<i32>,
+    pub distance_m: Option<f64>,
+    /// Set when the weight was derived from a training max.
+    pub percent_of_tm: Option<f64>,
+    pub is_amrap: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PlannedExercise {
+    pub exercise_id: Uuid,
+    pub exercise_name: String,
+    pub group_label: Option<String>,
+    pub rest_seconds: Option<i32>,
+    pub notes: Option<String>,
+    pub sets: Vec<PlannedSet>,
+}
+
+/// The enrollment's next workout with concrete target weights.
+#[derive(Debug, Serialize)]
+pub struct NextWorkout {
+    pub enrollment: Enrollment,
+    pub program_name: String,
+    pub week_number: i32,
+    pub day_number: i32,
+    pub day_name: Option<String>,
+    pub is_deload: bool,
+    pub template_id: Uuid,
+    pub workout_name: String,
+    pub exercises: Vec<PlannedExercise>,
+    /// Prescribed exercises whose weights could not be computed for lack of a training max.
+    pub missing_training_maxes: Vec<Uuid>,
+    /// Set when low readiness lightened the planned sets.
+    pub readiness: Option<SessionAdjustment>,
+}
@@ -0,0 +1,32 @@
+use serde::Serialize;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const PROGRESSION_LINEAR: &str = "linear";
+pub const PROGRESSION_DOUBLE: &str = "double_progression";
+pub const PROGRESSION_RPE: &str = "rpe";
+pub const PROGRESSION_FAILURE_DELOAD: &str = "failure_deload";
+
+pub const PROGRESSION_RULES: &[&str] = &[
+    PROGRESSION_LINEAR,
+    PROGRESSION_DOUBLE,
+    PROGRESSION_RPE,
+    PROGRESSION_FAILURE_DELOAD,
+];
+
+/// Targets suggested for the next session of a template exercise.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct ProgressionRecommendation {
+    pub id: Uuid,
+    #[serde(skip)]
+    pub user_id: Uuid,
+    pub template_exercise_id: Uuid,
+    pub exercise_id: Uuid,
+    pub workout_id: Uuid,
+    pub rule: String,
+    pub target_weight_kg: Option<f64>,
+    pub target_reps: Option<i32>,
+    pub failure_streak: i32,
+    pub reason: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const RECORD_MAX_WEIGHT: &str = "max_weight";
+pub const RECORD_ESTIMATED_1RM: &str = "estimated_1rm";
+pub const RECORD_MAX_REPS: &str = "max_reps";
+pub const RECORD_MAX_VOLUME: &str = "max_volume";
+pub const RECORD_FASTEST_TIME: &str = "fastest_time";
+
+pub const RECORD_TYPES: &[&str] = &[
+    RECORD_MAX_WEIGHT,
+    RECORD_ESTIMATED_1RM,
+    RECORD_MAX_REPS,
+    RECORD_MAX_VOLUME,
+    RECORD_FASTEST_TIME,
+];
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct PersonalRecord {
+    pub id: Uuid,
+    #[serde(skip)]
+    pub user_id: Uuid,
+    pub exercise_id: Uuid,
+    pub workout_id: Uuid,
+    pub set_id: Option<Uuid>,
+    pub record_type: String,
+    pub value: f64,
+    pub weight_kg: Option<f64>,
+    pub distance_m: Option<f64>,
+    pub achieved_at: chrono::DateTime<chrono::Utc>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Records of one exercise: the standing bests and every record ever set, oldest first.
+#[derive(Debug, Serialize)]
+pub struct ExerciseRecords {
+    pub exercise_id: Uuid,
+    pub exercise_name: String,
+    pub current: Vec<PersonalRecord>,
+    pub history: Vec<PersonalRecord>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecordQuery {
+    pub exercise_id: Option<Uuid>,
+    pub record_type: Option<String>,
+    pub workout_id: Option<Uuid>,
+}
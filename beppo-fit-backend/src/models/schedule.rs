@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::services::recurrence::RecurrenceRule;
+
+pub const SESSION_PLANNED: &str = "planned";
+pub const SESSION_DONE: &str = "done";
+pub const SESSION_SKIPPED: &str = "skipped";
+pub const SESSION_MISSED: &str = "missed";
+
+/// A planned workout, either once on `start_date` or repeating by `rrule`.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct Schedule {
+    pub id: Uuid,
+    #[serde(skip)]
+    pub user_id: Uuid,
+    pub title: String,
+    pub template_id: Option<Uuid>,
+    pub program_day_id: Option<Uuid>,
+    pub start_date: chrono::NaiveDate,
+    pub start_time: Option<chrono::NaiveTime>,
+    pub rrule: Option<String>,
+    pub notes: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct ScheduledSession {
+    pub schedule_id: Uuid,
+    pub scheduled_date: chrono::NaiveDate,
+    pub status: String,
+    pub workout_id: Option<Uuid>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Creates or replaces a schedule. Without a title, the template or program
+/// day name is used.
+#[derive(Debug, Deserialize, Validate)]
+#[validate(schema(function = "validate_schedule_target"))]
+pub struct SaveScheduleRequest {
+    #[validate(length(min = 1, max = 120, message = "Title must be 1-120 characters"))]
+    pub title: Option<String>,
+    pub template_id: Option<Uuid>,
+    pub program_day_id: Option<Uuid>,
+    pub start_date: chrono::NaiveDate,
+    pub start_time: Option<chrono::NaiveTime>,
+    #[validate(custom = "validate_rrule")]
+    pub rrule: Option<String>,
+    #[validate(length(max = 2000, message = "Notes must be at most 2000 characters"))]
+    pub notes: Option<String>,
+}
+
+fn validate_rrule(rrule: &str) -> Result<(), ValidationError> {
+    rrule
+        .parse::<RecurrenceRule>()
+        .map(|_| ())
+        .map_err(|message| {
+            let mut error = ValidationError::new("rrule");
+            error.message = Some(message.into());
+            error
+        })
+}
+
+fn validate_schedule_target(schedule: &SaveScheduleRequest) -> Result<(), ValidationError> {
+    let message = if schedule.template_id.is_some() && schedule.program_day_id.is_some() {
+        Some("Schedule either a template or a program day, not both")
+    } else if schedule.title.is_none()
+        && schedule.template_id.is_none()
+        && schedule.program_day_id.is_none()
+    {
+        Some("A title is required when no template or program day is scheduled")
+    } else {
+        None
+    };
+
+    match message {
+        Some(message) => {
+            let mut error = ValidationError::new("schedule");
+            error.message = Some(message.into());
+            Err(error)
+        }
+        None => Ok(()),
+    }
+}
+
+/// Calendar range; dates are days in the profile timezone, both inclusive.
+#[derive(Debug, Deserialize)]
+pub struct CalendarQuery {
+    pub from: Option<chrono::NaiveDate>,
+    pub to: Option<chrono::NaiveDate>,
+}
+
+/// One occurrence of a schedule.
+#[derive(Debug, Serialize)]
+pub struct CalendarEntry {
+    pub schedule_id: Uuid,
+    pub date: chrono::NaiveDate,
+    pub time: Option<chrono::NaiveTime>,
+    pub title: String,
+    pub template_id: Option<Uuid>,
+    pub program_day_id: Option<Uuid>,
+    /// planned, done, skipped, or missed once the day has passed unlogged.
+    pub status: &'static str,
+    pub workout_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CompleteSessionRequest {
+    pub workout_id: Uuid,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdherenceReport {
+    pub from: chrono::NaiveDate,
+    pub to: chrono::NaiveDate,
+    pub planned: i64,
+    pub done: i64,
+    pub skipped: i64,
+    pub missed: i64,
+    /// Sessions later than today, which do not count towards adherence yet.
+    pub upcoming: i64,
+    /// Done sessions as a share of those due so far, 0-100.
+    pub adherence_percent: Option<f64>,
+}
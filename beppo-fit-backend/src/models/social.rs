@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const FOLLOW_PENDING: &str = "pending";
+pub const FOLLOW_ACCEPTED: &str = "accepted";
+
+pub const FEED_WORKOUT: &str = "workout";
+pub const FEED_PERSONAL_RECORD: &str = "personal_record";
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct Follow {
+    pub follower_id: Uuid,
+    pub followee_id: Uuid,
+    /// `pending` until a private profile approves the request.
+    pub status: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub accepted_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct Block {
+    #[serde(skip)]
+    pub blocker_id: Uuid,
+    pub blocked_id: Uuid,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Another user in follower, following, request and block lists.
+#[derive(Debug, Serialize, FromRow)]
+pub struct SocialUser {
+    pub user_id: Uuid,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+    /// When the follow, request or block was made.
+    pub since: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CursorQuery {
+    /// `next_cursor` of the previous page.
+    pub cursor: Option<String>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FeedAuthor {
+    pub user_id: Uuid,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FeedWorkout {
+    pub name: Option<String>,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub exercise_count: i64,
+    pub set_count: i64,
+    pub volume_kg: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FeedRecord {
+    pub exercise_id: Uuid,
+    pub exercise_name: String,
+    pub record_type: String,
+    pub value: f64,
+    pub weight_kg: Option<f64>,
+}
+
+/// A finished workout or a personal record of a followed user. Exactly one of
+/// `workout` and `personal_record` is set, matching `kind`.
+#[derive(Debug, Serialize)]
+pub struct FeedItem {
+    pub kind: &'static str,
+    pub id: Uuid,
+    pub occurred_at: chrono::DateTime<chrono::Utc>,
+    pub author: FeedAuthor,
+    pub workout_id: Uuid,
+    pub workout: Option<FeedWorkout>,
+    pub personal_record: Option<FeedRecord>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Pass as `cursor` to get the next page; `None` on the last page.
+    pub next_cursor: Option<String>,
+}
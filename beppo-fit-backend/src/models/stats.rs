@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub const PERIODS: &[&str] = &["day", "week", "month"];
+
+/// Date range and bucketing for statistics. Dates are calendar days in the
+/// profile timezone and both ends are inclusive.
+#[derive(Debug, Deserialize)]
+pub struct StatsQuery {
+    pub from: Option<chrono::NaiveDate>,
+    pub to: Option<chrono::NaiveDate>,
+    pub period: Option<String>,
+    pub exercise_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MuscleVolume {
+    pub period_start: chrono::NaiveDate,
+    pub muscle_group: String,
+    pub sets: i64,
+    pub volume_kg: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TrainingFrequency {
+    pub period_start: chrono::NaiveDate,
+    pub workouts: i64,
+    pub training_days: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EstimatedMaxPoint {
+    pub date: chrono::NaiveDate,
+    pub estimated_1rm: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DurationBucket {
+    pub minutes_from: i32,
+    pub minutes_to: i32,
+    pub workouts: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DurationDistribution {
+    pub workouts: i64,
+    pub average_minutes: Option<f64>,
+    pub median_minutes: Option<f64>,
+    pub buckets: Vec<DurationBucket>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TonnagePoint {
+    pub period_start: chrono::NaiveDate,
+    pub workouts: i64,
+    pub sets: i64,
+    pub tonnage_kg: f64,
+}
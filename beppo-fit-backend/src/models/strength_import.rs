@@ -0,0 +1,102 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+pub const IMPORT_SOURCE_STRONG: &str = "strong";
+pub const IMPORT_SOURCE_HEVY: &str = "hevy";
+pub const IMPORT_SOURCE_FITNOTES: &str = "fitnotes";
+
+pub const IMPORT_SOURCES: &[&str] = &[
+    IMPORT_SOURCE_STRONG,
+    IMPORT_SOURCE_HEVY,
+    IMPORT_SOURCE_FITNOTES,
+];
+
+/// How an exercise name from the file resolves to our catalog.
+pub const MAPPING_CONFIRMED: &str = "confirmed";
+pub const MAPPING_MATCHED: &str = "matched";
+pub const MAPPING_SKIPPED: &str = "skipped";
+pub const MAPPING_UNMATCHED: &str = "unmatched";
+
+/// An uploaded history file waiting to be committed. `units` is the unit
+/// system weights and distances are read in where the file does not say.
+#[derive(Debug, Clone, FromRow)]
+pub struct StrengthImport {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub source: String,
+    pub units: String,
+    pub data: Vec<u8>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MatchCandidate {
+    pub exercise_id: Uuid,
+    pub name: String,
+    pub score: f64,
+}
+
+/// One distinct exercise name in the file and what it will be imported as.
+#[derive(Debug, Serialize)]
+pub struct ExerciseMapping {
+    pub name: String,
+    pub set_count: usize,
+    pub status: &'static str,
+    pub exercise_id: Option<Uuid>,
+    pub exercise_name: Option<String>,
+    /// Similarity of the automatic match, 0-1.
+    pub score: Option<f64>,
+    pub candidates: Vec<MatchCandidate>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportedWorkoutPreview {
+    pub name: Option<String>,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub finished_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub exercise_count: usize,
+    pub set_count: usize,
+    /// A workout starting at the same instant already exists and will be skipped.
+    pub duplicate: bool,
+}
+
+/// What committing the import would do; nothing is written until then.
+#[derive(Debug, Serialize)]
+pub struct ImportPreview {
+    pub id: Uuid,
+    pub source: String,
+    pub units: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Whether every exercise name is matched or skipped, so the import can be committed.
+    pub ready: bool,
+    pub workout_count: usize,
+    pub duplicate_count: usize,
+    pub set_count: usize,
+    pub exercises: Vec<ExerciseMapping>,
+    pub workouts: Vec<ImportedWorkoutPreview>,
+    /// Rows that could not be read and will be left out.
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct MappingEntry {
+    #[validate(length(min = 1, max = 200, message = "Name must be 1-200 characters"))]
+    pub name: String,
+    /// The exercise to import the name as; `null` leaves its sets out.
+    pub exercise_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct SaveMappingsRequest {
+    #[validate]
+    pub mappings: Vec<MappingEntry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportResult {
+    pub workouts_created: usize,
+    pub sets_created: usize,
+    pub duplicates_skipped: usize,
+}
@@ -0,0 +1,149 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::models::{
+    progression::{PROGRESSION_DOUBLE, PROGRESSION_RPE, PROGRESSION_RULES},
+    validation::one_of,
+};
+
+pub const GROUP_TYPES: &[&str] = &["superset", "circuit"];
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct WorkoutTemplate {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    /// Present while the template is shared; anyone with the code can view and copy it.
+    pub share_code: Option<String>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct TemplateExercise {
+    pub id: Uuid,
+    #[serde(skip)]
+    pub template_id: Uuid,
+    pub exercise_id: Uuid,
+    pub exercise_name: String,
+    pub position: i32,
+    pub group_label: Option<String>,
+    pub group_type: Option<String>,
+    pub target_sets: i32,
+    pub target_reps_min: Option<i32>,
+    pub target_reps_max: Option<i32>,
+    pub target_weight_kg: Option<f64>,
+    pub target_rpe: Option<f64>,
+    pub rest_seconds: Option<i32>,
+    pub notes: Option<String>,
+    pub progression_rule: Option<String>,
+    pub progression_increment_kg: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TemplateDetail {
+    #[serde(flatten)]
+    pub template: WorkoutTemplate,
+    pub exercises: Vec<TemplateExercise>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct SaveTemplateRequest {
+    #[validate(length(min = 1, max = 120, message = "Name must be 1-120 characters"))]
+    pub name: String,
+    #[validate(length(max = 5000, message = "Description must be at most 5000 characters"))]
+    pub description: Option<String>,
+    #[serde(default)]
+    #[validate]
+    pub exercises: Vec<SaveTemplateExercise>,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[validate(schema(function = "validate_template_exercise"))]
+pub struct SaveTemplateExercise {
+    pub exercise_id: Uuid,
+    #[validate(length(min = 1, max = 20, message = "Group label must be 1-20 characters"))]
+    pub group_label: Option<String>,
+    #[validate(custom = "validate_group_type")]
+    pub group_type: Option<String>,
+    #[serde(default = "default_target_sets")]
+    #[validate(range(min = 1, max = 50, message = "Target sets must be between 1 and 50"))]
+    pub target_sets: i32,
+    #[validate(range(
+        min = 0,
+        max = 1000,
+        message = "Target reps must be between 0 and 1000"
+    ))]
+    pub target_reps_min: Option<i32>,
+    #[validate(range(
+        min = 0,
+        max = 1000,
+        message = "Target reps must be between 0 and 1000"
+    ))]
+    pub target_reps_max: Option<i32>,
+    #[validate(range(
+        min = 0.0,
+        max = 2000.0,
+        message = "Weight must be between 0 and 2000 kg"
+    ))]
+    pub target_weight_kg: Option<f64>,
+    #[validate(range(min = 1.0, max = 10.0, message = "RPE must be between 1 and 10"))]
+    pub target_rpe: Option<f64>,
+    #[validate(range(min = 0, max = 3600, message = "Rest must be at most one hour"))]
+    pub rest_seconds: Option<i32>,
+    #[validate(length(max = 2000, message = "Notes must be at most 2000 characters"))]
+    pub notes: Option<String>,
+    #[validate(custom = "validate_progression_rule")]
+    pub progression_rule: Option<String>,
+    #[validate(range(
+        min = 0.25,
+        max = 50.0,
+        message = "Increment must be between 0.25 and 50 kg"
+    ))]
+    pub progression_increment_kg: Option<f64>,
+}
+
+fn default_target_sets() -> i32 {
+    3
+}
+
+fn validate_group_type(group_type: &str) -> Result<(), ValidationError> {
+    one_of(group_type, GROUP_TYPES, "group_type")
+}
+
+fn validate_progression_rule(rule: &str) -> Result<(), ValidationError> {
+    one_of(rule, PROGRESSION_RULES, "progression_rule")
+}
+
+fn validate_template_exercise(exercise: &SaveTemplateExercise) -> Result<(), ValidationError> {
+    if let (Some(min), Some(max)) = (exercise.target_reps_min, exercise.target_reps_max) {
+        if min > max {
+            let mut error = ValidationError::new("target_reps");
+            error.message = Some("target_reps_min must not exceed target_reps_max".into());
+            return Err(error);
+        }
+    }
+    if exercise.group_type.is_some() && exercise.group_label.is_none() {
+        let mut error = ValidationError::new("group_label");
+        error.message = Some("group_type requires a group_label".into());
+        return Err(error);
+    }
+    match exercise.progression_rule.as_deref() {
+        Some(PROGRESSION_DOUBLE)
+            if exercise.target_reps_min.is_none() || exercise.target_reps_max.is_none() =>
+        {
+            let mut error = ValidationError::new("progression_rule");
+            error.message = Some("Double progression requires a target rep range".into());
+            Err(error)
+        }
+        Some(PROGRESSION_RPE) if exercise.target_rpe.is_none() => {
+            let mut error = ValidationError::new("progression_rule");
+            error.message = Some("RPE progression requires a target RPE".into());
+            Err(error)
+        }
+        _ => Ok(()),
+    }
+}
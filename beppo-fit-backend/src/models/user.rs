@@ -3,6 +3,8 @@ use sqlx::FromRow;
 use uuid::Uuid;
 use validator::Validate;
 
+use crate::models::profile::UserProfile;
+
 #[derive(Debug, Serialize, FromRow)]
 pub struct User {
     pub id: Uuid,
@@ -11,6 +13,7 @@ pub struct User {
     pub password_hash: Option<String>,
     pub google_id: Option<String>,
     pub is_verified: bool,
+    pub is_admin: bool,
     #[serde(skip)]
     #[allow(dead_code)]
     pub verification_token: Option<String>,
@@ -45,4 +48,5 @@ pub struct LoginRequest {
 pub struct AuthResponse {
     pub token: String,
     pub user: User,
+    pub profile: UserProfile,
 }
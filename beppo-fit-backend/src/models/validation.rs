@@ -0,0 +1,23 @@
+use serde::{Deserialize, Deserializer};
+use validator::ValidationError;
+
+/// Accepts `value` only if it is one of `allowed`, naming the choices otherwise.
+pub fn one_of(value: &str, allowed: &[&str], code: &'static str) -> Result<(), ValidationError> {
+    if allowed.contains(&value) {
+        Ok(())
+    } else {
+        let mut error = ValidationError::new(code);
+        error.message = Some(format!("'{}' is not one of: {}", value, allowed.join(", ")).into());
+        Err(error)
+    }
+}
+
+/// Tells an explicit `null` (`Some(None)`) apart from an omitted field (`None`),
+/// for use with `#[serde(default, deserialize_with = "nullable")]`.
+pub fn nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
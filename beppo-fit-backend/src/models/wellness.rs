@@ -0,0 +1,116 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+pub const SUGGESTION_TRAIN: &str = "train";
+pub const SUGGESTION_LIGHTER: &str = "lighter";
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct WellnessLog {
+    pub id: Uuid,
+    #[serde(skip)]
+    pub user_id: Uuid,
+    pub log_date: NaiveDate,
+    pub water_ml: Option<i32>,
+    pub sleep_minutes: Option<i32>,
+    /// 1 (poor) to 5 (great).
+    pub sleep_quality: Option<i32>,
+    /// 1 (none) to 5 (severe).
+    pub soreness: Option<i32>,
+    /// 1 (relaxed) to 5 (very stressed).
+    pub stress: Option<i32>,
+    pub resting_heart_rate: Option<i32>,
+    pub hrv_ms: Option<f64>,
+    pub notes: Option<String>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// The full entry for a day; it replaces whatever was logged before.
+#[derive(Debug, Deserialize, Validate)]
+#[validate(schema(function = "validate_not_empty"))]
+pub struct SaveWellnessLogRequest {
+    #[validate(range(min = 0, max = 20000, message = "Water must be at most 20 litres"))]
+    pub water_ml: Option<i32>,
+    #[validate(range(min = 0, max = 1440, message = "Sleep must be at most 24 hours"))]
+    pub sleep_minutes: Option<i32>,
+    #[validate(range(min = 1, max = 5, message = "Sleep quality must be between 1 and 5"))]
+    pub sleep_quality: Option<i32>,
+    #[validate(range(min = 1, max = 5, message = "Soreness must be between 1 and 5"))]
+    pub soreness: Option<i32>,
+    #[validate(range(min = 1, max = 5, message = "Stress must be between 1 and 5"))]
+    pub stress: Option<i32>,
+    #[validate(range(
+        min = 25,
+        max = 120,
+        message = "Resting heart rate must be between 25 and 120 bpm"
+    ))]
+    pub resting_heart_rate: Option<i32>,
+    #[validate(range(min = 1.0, max = 300.0, message = "HRV must be between 1 and 300 ms"))]
+    pub hrv_ms: Option<f64>,
+    #[validate(length(max = 2000, message = "Notes must be at most 2000 characters"))]
+    pub notes: Option<String>,
+}
+
+fn validate_not_empty(log: &SaveWellnessLogRequest) -> Result<(), ValidationError> {
+    let empty = log.water_ml.is_none()
+        && log.sleep_minutes.is_none()
+        && log.sleep_quality.is_none()
+        && log.soreness.is_none()
+        && log.stress.is_none()
+        && log.resting_heart_rate.is_none()
+        && log.hrv_ms.is_none();
+    if empty {
+        let mut error = ValidationError::new("wellness");
+        error.message = Some("Log at least one value".into());
+        return Err(error);
+    }
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WellnessQuery {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReadinessQuery {
+    /// Defaults to today in the profile timezone.
+    pub date: Option<NaiveDate>,
+}
+
+/// Scores from 0 (worst) to 100 for the values that were logged.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ReadinessComponents {
+    pub sleep: Option<i32>,
+    pub hrv: Option<i32>,
+    pub resting_heart_rate: Option<i32>,
+    pub soreness: Option<i32>,
+    pub stress: Option<i32>,
+    pub hydration: Option<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Readiness {
+    pub date: NaiveDate,
+    /// Weighted mean of the available components.
+    pub score: i32,
+    pub components: ReadinessComponents,
+    /// Averages of the preceding weeks that heart rate and HRV are compared to.
+    pub baseline_resting_heart_rate: Option<f64>,
+    pub baseline_hrv_ms: Option<f64>,
+    /// `lighter` when templates and programs start with reduced targets.
+    pub suggestion: &'static str,
+}
+
+/// How a session is lightened on a low readiness day.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SessionAdjustment {
+    pub readiness_score: i32,
+    pub weight_factor: f64,
+    pub sets_removed: usize,
+    pub reason: String,
+}
@@ -0,0 +1,178 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::models::{
+    profile::validate_visibility,
+    validation::{nullable, one_of},
+};
+
+pub const SET_TYPES: &[&str] = &["warmup", "working", "drop", "failure"];
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct Workout {
+    pub id: Uuid,
+    #[serde(skip)]
+    pub user_id: Uuid,
+    pub name: Option<String>,
+    pub notes: Option<String>,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub finished_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub template_id: Option<Uuid>,
+    /// Who besides the owner may see it: public, followers or private.
+    pub visibility: String,
+    /// Started lighter than planned because of low readiness; it does not
+    /// move progression targets.
+    pub readiness_adjusted: bool,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct WorkoutExercise {
+    pub id: Uuid,
+    #[serde(skip)]
+    pub workout_id: Uuid,
+    pub exercise_id: Uuid,
+    pub exercise_name: String,
+    pub position: i32,
+    pub notes: Option<String>,
+    /// Exercises sharing a label are performed as a superset or circuit.
+    pub group_label: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct WorkoutSet {
+    pub id: Uuid,
+    #[serde(skip)]
+    pub workout_exercise_id: Uuid,
+    pub position: i32,
+    pub set_type: String,
+    pub reps: Option<i32>,
+    pub weight_kg: Option<f64>,
+    pub duration_seconds: Option<i32>,
+    pub distance_m: Option<f64>,
+    pub rpe: Option<f64>,
+    pub rir: Option<i32>,
+    pub notes: Option<String>,
+    pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WorkoutExerciseDetail {
+    #[serde(flatten)]
+    pub exercise: WorkoutExercise,
+    pub sets: Vec<WorkoutSet>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WorkoutDetail {
+    #[serde(flatten)]
+    pub workout: Workout,
+    pub exercises: Vec<WorkoutExerciseDetail>,
+}
+
+/// A workout in list views, with aggregate numbers instead of the full set log.
+#[derive(Debug, Serialize, FromRow)]
+pub struct WorkoutSummary {
+    pub id: Uuid,
+    pub name: Option<String>,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub finished_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub exercise_count: i64,
+    pub set_count: i64,
+    pub volume_kg: f64,
+}
+
+/// Full workout payload used to create or replace a workout in one transaction.
+#[derive(Debug, Deserialize, Validate)]
+pub struct SaveWorkoutRequest {
+    #[validate(length(max = 120, message = "Name must be at most 120 characters"))]
+    pub name: Option<String>,
+    #[validate(length(max = 5000, message = "Notes must be at most 5000 characters"))]
+    pub notes: Option<String>,
+    pub started_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub finished_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Template the workout followed; finished workouts feed its progression
+    /// rules. Logging a finished one also completes the next program day if
+    /// that day uses the template. On replace, omitting it keeps the current
+    /// template and `null` unlinks it.
+    #[serde(default, deserialize_with = "nullable")]
+    pub template_id: Option<Option<Uuid>>,
+    /// Defaults to the profile's default workout visibility on create.
+    #[validate(custom = "validate_visibility")]
+    pub visibility: Option<String>,
+    #[serde(default)]
+    #[validate]
+    pub exercises: Vec<SaveWorkoutExercise>,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct SaveWorkoutExercise {
+    pub exercise_id: Uuid,
+    #[validate(length(max = 2000, message = "Notes must be at most 2000 characters"))]
+    pub notes: Option<String>,
+    #[validate(length(min = 1, max = 20, message = "Group label must be 1-20 characters"))]
+    pub group_label: Option<String>,
+    #[serde(default)]
+    #[validate]
+    pub sets: Vec<SaveWorkoutSet>,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct SaveWorkoutSet {
+    #[serde(default = "default_set_type")]
+    #[validate(custom = "validate_set_type")]
+    pub set_type: String,
+    #[validate(range(min = 0, max = 10000, message = "Reps must be between 0 and 10000"))]
+    pub reps: Option<i32>,
+    #[validate(range(
+        min = 0.0,
+        max = 2000.0,
+        message = "Weight must be between 0 and 2000 kg"
+    ))]
+    pub weight_kg: Option<f64>,
+    #[validate(range(min = 0, max = 86400, message = "Duration must be at most 24 hours"))]
+    pub duration_seconds: Option<i32>,
+    #[validate(range(
+        min = 0.0,
+        max = 1000000.0,
+        message = "Distance must be at most 1000 km"
+    ))]
+    pub distance_m: Option<f64>,
+    #[validate(range(min = 1.0, max = 10.0, message = "RPE must be between 1 and 10"))]
+    pub rpe: Option<f64>,
+    #[validate(range(min = 0, max = 10, message = "RIR must be between 0 and 10"))]
+    pub rir: Option<i32>,
+    #[validate(length(max = 1000, message = "Notes must be at most 1000 characters"))]
+    pub notes: Option<String>,
+    pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+fn default_set_type() -> String {
+    "working".to_string()
+}
+
+fn validate_set_type(set_type: &str) -> Result<(), ValidationError> {
+    one_of(set_type, SET_TYPES, "set_type")
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WorkoutListQuery {
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+pub const EXPORT_FORMAT_CSV: &str = "csv";
+pub const EXPORT_FORMAT_JSON: &str = "json";
+
+/// Filters for `/workouts/export`; the range applies to `started_at`.
+#[derive(Debug, Deserialize)]
+pub struct WorkoutExportQuery {
+    pub format: Option<String>,
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+}
@@ -0,0 +1,350 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use sqlx::{PgConnection, PgPool};
+use std::collections::{BTreeMap, HashSet};
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    models::{
+        achievement::{
+            AchievementDefinition, AchievementMetric, AchievementStatus, AchievementsResponse,
+            StreakSummary, UserAchievement, ACHIEVEMENTS,
+        },
+        notification::NOTIFICATION_ACHIEVEMENT_UNLOCKED,
+    },
+    services::{notification::notify, profile::ensure_profile},
+};
+
+/// A finished workout as far as achievements are concerned.
+#[derive(Debug, Clone)]
+pub struct FinishedWorkout {
+    pub finished_at: DateTime<Utc>,
+    /// Monday of the week the workout started in, in the profile timezone.
+    pub week: NaiveDate,
+    pub volume_kg: f64,
+}
+
+/// Everything achievements are computed from, oldest first.
+#[derive(Debug, Default)]
+pub struct TrainingHistory {
+    pub workouts: Vec<FinishedWorkout>,
+    /// When an exercise first set a record in each workout.
+    pub records: Vec<DateTime<Utc>>,
+}
+
+impl TrainingHistory {
+    pub async fn load(conn: &mut PgConnection, user_id: Uuid) -> Result<Self, AppError> {
+        let profile = ensure_profile(&mut *conn, user_id).await?;
+        let workouts = sqlx::query!(
+            r#"
+            SELECT
+                w.finished_at AS "finished_at!",
+                date_trunc('week', w.started_at AT TIME ZONE $2)::DATE AS "week!",
+                COALESCE(SUM(s.reps * s.weight_kg), 0) AS "volume_kg!"
+            FROM workouts w
+            LEFT JOIN workout_exercises we ON we.workout_id = w.id
+            LEFT JOIN sets s ON s.workout_exercise_id = we.id AND s.set_type <> 'warmup'
+            WHERE w.user_id = $1 AND w.finished_at IS NOT NULL
+            GROUP BY w.id
+            ORDER BY w.finished_at
+            "#,
+            user_id,
+            profile.timezone
+        )
+        .fetch_all(&mut *conn)
+        .await?
+        .into_iter()
+        .map(|row| FinishedWorkout {
+            finished_at: row.finished_at,
+            week: row.week,
+            volume_kg: row.volume_kg,
+        })
+        .collect();
+        let records = sqlx::query_scalar!(
+            r#"
+            SELECT MIN(achieved_at) AS "achieved_at!" FROM personal_records
+            WHERE user_id = $1
+            GROUP BY exercise_id, workout_id
+            ORDER BY 1
+            "#,
+            user_id
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(Self { workouts, records })
+    }
+
+    /// Earliest workout of every trained week, in week order.
+    fn weeks(&self) -> BTreeMap<NaiveDate, DateTime<Utc>> {
+        let mut weeks = BTreeMap::new();
+        for workout in &self.workouts {
+            weeks
+                .entry(workout.week)
+                .and_modify(|at: &mut DateTime<Utc>| *at = (*at).min(workout.finished_at))
+                .or_insert(workout.finished_at);
+        }
+        weeks
+    }
+
+    /// Runs of consecutive trained weeks as (length, when each length was reached).
+    fn streak_runs(&self) -> Vec<Vec<DateTime<Utc>>> {
+        let mut runs: Vec<Vec<DateTime<Utc>>> = Vec::new();
+        let mut previous: Option<NaiveDate> = None;
+        for (week, reached_at) in self.weeks() {
+            match (previous, runs.last_mut()) {
+                (Some(prev), Some(run)) if week - prev == Duration::weeks(1) => {
+                    run.push(reached_at)
+                }
+                _ => runs.push(vec![reached_at]),
+            }
+            previous = Some(week);
+        }
+        runs
+    }
+
+    pub fn longest_streak(&self) -> i64 {
+        self.streak_runs()
+            .iter()
+            .map(|run| run.len() as i64)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The streak still alive today: it counts while this or last week was trained.
+    pub fn current_streak(&self, today: NaiveDate) -> i64 {
+        let this_week = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+        let weeks: Vec<NaiveDate> = self.weeks().into_keys().collect();
+        let Some(&latest) = weeks.last() else {
+            return 0;
+        };
+        if this_week - latest > Duration::weeks(1) {
+            return 0;
+        }
+        self.streak_runs().last().map_or(0, |run| run.len() as i64)
+    }
+
+    /// Where the user stands on a metric today.
+    pub fn progress(&self, metric: AchievementMetric) -> f64 {
+        match metric {
+            AchievementMetric::Workouts => self.workouts.len() as f64,
+            AchievementMetric::WeeklyStreak => self.longest_streak() as f64,
+            AchievementMetric::PersonalRecords => self.records.len() as f64,
+            AchievementMetric::VolumeKg => self.workouts.iter().map(|w| w.volume_kg).sum(),
+        }
+    }
+
+    /// When the definition was first met, if it has been.
+    pub fn reached_at(&self, definition: &AchievementDefinition) -> Option<DateTime<Utc>> {
+        let nth = |count: usize| definition.threshold.ceil().max(1.0) as usize <= count;
+        match definition.metric {
+            AchievementMetric::Workouts => self
+                .workouts
+                .iter()
+                .enumerate()
+                .find(|(i, _)| nth(i + 1))
+                .map(|(_, w)| w.finished_at),
+            AchievementMetric::PersonalRecords => self
+                .records
+                .iter()
+                .enumerate()
+                .find(|(i, _)| nth(i + 1))
+                .map(|(_, at)| *at),
+            AchievementMetric::WeeklyStreak => self
+                .streak_runs()
+                .iter()
+                .filter_map(|run| {
+                    run.iter()
+                        .enumerate()
+                        .find(|(i, _)| nth(i + 1))
+                        .map(|(_, at)| *at)
+                })
+                .min(),
+            AchievementMetric::VolumeKg => {
+                let mut total = 0.0;
+                self.workouts.iter().find_map(|w| {
+                    total += w.volume_kg;
+                    (total >= definition.threshold).then_some(w.finished_at)
+                })
+            }
+        }
+    }
+}
+
+async fn unlock_achievements(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    send_notifications: bool,
+) -> Result<Vec<&'static AchievementDefinition>, AppError> {
+    let unlocked: HashSet<String> = sqlx::query_scalar!(
+        "SELECT code FROM user_achievements WHERE user_id = $1",
+        user_id
+    )
+    .fetch_all(&mut *conn)
+    .await?
+    .into_iter()
+    .collect();
+    if ACHIEVEMENTS.iter().all(|a| unlocked.contains(a.code)) {
+        return Ok(Vec::new());
+    }
+
+    let history = TrainingHistory::load(conn, user_id).await?;
+    let mut newly_unlocked = Vec::new();
+    for definition in ACHIEVEMENTS {
+        if unlocked.contains(definition.code) {
+            continue;
+        }
+        let Some(reached_at) = history.reached_at(definition) else {
+            continue;
+        };
+
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO user_achievements (user_id, code, unlocked_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT DO NOTHING
+            "#,
+            user_id,
+            definition.code,
+            reached_at
+        )
+        .execute(&mut *conn)
+        .await?;
+        if result.rows_affected() == 0 {
+            continue;
+        }
+
+        if send_notifications {
+            notify(
+                &mut *conn,
+                user_id,
+                NOTIFICATION_ACHIEVEMENT_UNLOCKED,
+                &format!("Achievement unlocked: {}", definition.name),
+                Some(definition.description),
+                None,
+            )
+            .await?;
+        }
+        newly_unlocked.push(definition);
+    }
+
+    Ok(newly_unlocked)
+}
+
+/// Unlocks whatever the user's training now qualifies for and notifies them.
+/// Call after anything that changes workouts or personal records.
+pub async fn check_achievements(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+) -> Result<Vec<&'static AchievementDefinition>, AppError> {
+    unlock_achievements(conn, user_id, true).await
+}
+
+/// Unlocks achievements earned by existing history, dated when they were
+/// earned. Safe to run any number of times; sends no notifications.
+pub async fn backfill_achievements(pool: &PgPool) -> Result<usize, AppError> {
+    let user_ids =
+        sqlx::query_scalar!("SELECT DISTINCT user_id FROM workouts WHERE finished_at IS NOT NULL")
+            .fetch_all(pool)
+            .await?;
+
+    let mut unlocked = 0;
+    let mut conn = pool.acquire().await?;
+    for user_id in user_ids {
+        unlocked += unlock_achievements(&mut conn, user_id, false).await?.len();
+    }
+
+    Ok(unlocked)
+}
+
+pub async fn achievement_overview(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+) -> Result<AchievementsResponse, AppError> {
+    let today = ensure_profile(&mut *conn, user_id).await?.today();
+    let history = TrainingHistory::load(conn, user_id).await?;
+    let unlocked = sqlx::query_as!(
+        UserAchievement,
+        "SELECT * FROM user_achievements WHERE user_id = $1",
+        user_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let achievements = ACHIEVEMENTS
+        .iter()
+        .map(|definition| AchievementStatus {
+            definition,
+            progress: history
+                .progress(definition.metric)
+                .min(definition.threshold),
+            unlocked_at: unlocked
+                .iter()
+                .find(|u| u.code == definition.code)
+                .map(|u| u.unlocked_at),
+        })
+        .collect();
+
+    Ok(AchievementsResponse {
+        streak: StreakSummary {
+            current_weeks: history.current_streak(today),
+            longest_weeks: history.longest_streak(),
+        },
+        achievements,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::achievement::find_achievement;
+    use chrono::TimeZone;
+
+    fn history(days: &[u32]) -> TrainingHistory {
+        TrainingHistory {
+            workouts: days
+                .iter()
+                .map(|&day| {
+                    let date = NaiveDate::from_ymd_opt(2024, 12, day).unwrap();
+                    FinishedWorkout {
+                        finished_at: Utc.from_utc_datetime(&date.and_hms_opt(18, 0, 0).unwrap()),
+                        week: date - Duration::days(date.weekday().num_days_from_monday() as i64),
+                        volume_kg: 4000.0,
+                    }
+                })
+                .collect(),
+            records: Vec::new(),
+        }
+    }
+
+    fn day(day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 12, day, 18, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn dates_achievements_when_they_were_earned() {
+        // Mondays 2nd, 9th (twice), 16th and 23rd are four weeks in a row
+        let history = history(&[2, 9, 11, 16, 23]);
+        let reached = |code| history.reached_at(find_achievement(code).unwrap());
+
+        assert_eq!(reached("first_workout"), Some(day(2)));
+        assert_eq!(reached("workouts_10"), None);
+        assert_eq!(reached("volume_10t"), Some(day(11)));
+        assert_eq!(reached("streak_4_weeks"), Some(day(23)));
+        assert_eq!(reached("first_pr"), None);
+    }
+
+    #[test]
+    fn a_missed_week_restarts_the_streak() {
+        let history = history(&[2, 9, 23, 30]);
+        assert_eq!(history.longest_streak(), 2);
+        assert_eq!(
+            history.current_streak(NaiveDate::from_ymd_opt(2025, 1, 8).unwrap()),
+            2
+        );
+        assert_eq!(
+            history.current_streak(NaiveDate::from_ymd_opt(2025, 1, 15).unwrap()),
+            0
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    models::{
+        activity::{
+            Activity, ActivityDetail, ActivityLap, TrackPoint, SPLIT_UNIT_KM, SPLIT_UNIT_MILE,
+        },
+        profile::UserProfile,
+    },
+    services::{
+        activity_metrics::{compute_splits, heart_rate_zones, METRES_PER_KM, METRES_PER_MILE},
+        track::{decode_track, encode_polyline},
+    },
+};
+
+/// The stored track of an activity; `None` for activities without one.
+pub async fn load_track(
+    conn: &mut PgConnection,
+    activity_id: Uuid,
+) -> Result<Option<Vec<TrackPoint>>, AppError> {
+    let data = sqlx::query_scalar!(
+        "SELECT data FROM activity_tracks WHERE activity_id = $1",
+        activity_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    data.map(|data| {
+        decode_track(&data).ok_or_else(|| {
+            tracing::error!("Track of activity {} cannot be decoded", activity_id);
+            AppError::InternalServerError
+        })
+    })
+    .transpose()
+}
+
+/// The activity with its laps and the metrics derived from its track. Splits
+/// follow the profile's preferred units and heart rate zones its settings.
+pub async fn load_activity_detail(
+    conn: &mut PgConnection,
+    activity: Activity,
+    profile: &UserProfile,
+) -> Result<ActivityDetail, AppError> {
+    let laps = sqlx::query_as!(
+        ActivityLap,
+        "SELECT * FROM activity_laps WHERE activity_id = $1 ORDER BY lap_index",
+        activity.id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let points = load_track(conn, activity.id).await?.unwrap_or_default();
+
+    let (split_unit, split_m) = if profile.preferred_units == "imperial" {
+        (SPLIT_UNIT_MILE, METRES_PER_MILE)
+    } else {
+        (SPLIT_UNIT_KM, METRES_PER_KM)
+    };
+
+    Ok(ActivityDetail {
+        activity,
+        laps,
+        polyline: encode_polyline(&points),
+        split_unit,
+        splits: compute_splits(&points, split_m),
+        heart_rate_zones: heart_rate_zones(&points, profile),
+    })
+}
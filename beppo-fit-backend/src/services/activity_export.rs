@@ -0,0 +1,293 @@
+//! GPX 1.1 and TCX v2 writers for stored activities.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::fmt::Write;
+
+use crate::models::activity::{
+    Activity, ActivityLap, TrackPoint, ACTIVITY_RIDE, ACTIVITY_ROW, ACTIVITY_RUN, ACTIVITY_SWIM,
+    ACTIVITY_WALK,
+};
+
+pub const GPX_CONTENT_TYPE: &str = "application/gpx+xml";
+pub const TCX_CONTENT_TYPE: &str = "application/vnd.garmin.tcx+xml";
+
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn format_time(instant: DateTime<Utc>) -> String {
+    instant.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+/// The GPX `<type>` we write; the names are the ones the importer reads back.
+fn gpx_type(activity_type: &str) -> Option<&'static str> {
+    match activity_type {
+        ACTIVITY_RUN => Some("running"),
+        ACTIVITY_RIDE => Some("cycling"),
+        ACTIVITY_SWIM => Some("swimming"),
+        ACTIVITY_ROW => Some("rowing"),
+        ACTIVITY_WALK => Some("walking"),
+        _ => None,
+    }
+}
+
+/// The TCX schema only knows these three sports.
+fn tcx_sport(activity_type: &str) -> &'static str {
+    match activity_type {
+        ACTIVITY_RUN => "Running",
+        ACTIVITY_RIDE => "Biking",
+        _ => "Other",
+    }
+}
+
+/// Renders the positioned points of a track as GPX. Heart rate and cadence
+/// go into Garmin's track point extension, which most tools understand.
+pub fn render_gpx(activity: &Activity, points: &[TrackPoint]) -> String {
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str(
+        "<gpx version=\"1.1\" creator=\"BeppoFit\" xmlns=\"http://www.topografix.com/GPX/1/1\" \
+         xmlns:gpxtpx=\"http://www.garmin.com/xmlschemas/TrackPointExtension/v1\">\n",
+    );
+    out.push_str("  <metadata>\n");
+    if let Some(name) = &activity.name {
+        let _ = writeln!(out, "    <name>{}</name>", escape_xml(name));
+    }
+    let _ = writeln!(out, "    <time>{}</time>", format_time(activity.started_at));
+    out.push_str("  </metadata>\n  <trk>\n");
+    if let Some(name) = &activity.name {
+        let _ = writeln!(out, "    <name>{}</name>", escape_xml(name));
+    }
+    if let Some(kind) = gpx_type(&activity.activity_type) {
+        let _ = writeln!(out, "    <type>{}</type>", kind);
+    }
+    out.push_str("    <trkseg>\n");
+    for point in points {
+        let (Some(lat), Some(lon)) = (point.lat, point.lon) else {
+            continue;
+        };
+        let _ = writeln!(out, "      <trkpt lat=\"{}\" lon=\"{}\">", lat, lon);
+        if let Some(elevation) = point.elevation_m {
+            let _ = writeln!(out, "        <ele>{}</ele>", elevation);
+        }
+        let _ = writeln!(out, "        <time>{}</time>", format_time(point.time));
+        if point.heart_rate.is_some() || point.cadence.is_some() {
+            out.push_str("        <extensions><gpxtpx:TrackPointExtension>");
+            if let Some(heart_rate) = point.heart_rate {
+                let _ = write!(out, "<gpxtpx:hr>{}</gpxtpx:hr>", heart_rate);
+            }
+            if let Some(cadence) = point.cadence {
+                let _ = write!(out, "<gpxtpx:cad>{}</gpxtpx:cad>", cadence);
+            }
+            out.push_str("</gpxtpx:TrackPointExtension></extensions>\n");
+        }
+        out.push_str("      </trkpt>\n");
+    }
+    out.push_str("    </trkseg>\n  </trk>\n</gpx>\n");
+    out
+}
+
+/// Renders an activity as TCX with its laps. Activities without stored laps
+/// get a single lap spanning the whole recording.
+pub fn render_tcx(activity: &Activity, laps: &[ActivityLap], points: &[TrackPoint]) -> String {
+    let whole = ActivityLap {
+        activity_id: activity.id,
+        lap_index: 0,
+        started_at: activity.started_at,
+        duration_seconds: activity.duration_seconds as f64,
+        distance_m: activity.distance_m,
+        avg_heart_rate: activity.avg_heart_rate,
+    };
+    let laps = if laps.is_empty() {
+        std::slice::from_ref(&whole)
+    } else {
+        laps
+    };
+
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str(
+        "<TrainingCenterDatabase xmlns=\"http://www.garmin.com/xmlschemas/TrainingCenterDatabase/v2\">\n",
+    );
+    out.push_str("  <Activities>\n");
+    let _ = writeln!(
+        out,
+        "    <Activity Sport=\"{}\">",
+        tcx_sport(&activity.activity_type)
+    );
+    let _ = writeln!(out, "      <Id>{}</Id>", format_time(activity.started_at));
+    for (index, lap) in laps.iter().enumerate() {
+        // Points before the first lap belong to it, the rest to the lap they fall in.
+        let next_start = laps.get(index + 1).map(|next| next.started_at);
+        let in_lap = points.iter().filter(|p| {
+            (index == 0 || p.time >= lap.started_at) && next_start.is_none_or(|end| p.time < end)
+        });
+
+        let _ = writeln!(
+            out,
+            "      <Lap StartTime=\"{}\">",
+            format_time(lap.started_at)
+        );
+        let _ = writeln!(
+            out,
+            "        <TotalTimeSeconds>{}</TotalTimeSeconds>",
+            lap.duration_seconds
+        );
+        let _ = writeln!(
+            out,
+            "        <DistanceMeters>{}</DistanceMeters>",
+            lap.distance_m.unwrap_or(0.0)
+        );
+        out.push_str("        <Calories>0</Calories>\n");
+        if let Some(heart_rate) = lap.avg_heart_rate {
+            let _ = writeln!(
+                out,
+                "        <AverageHeartRateBpm><Value>{}</Value></AverageHeartRateBpm>",
+                heart_rate
+            );
+        }
+        out.push_str("        <Intensity>Active</Intensity>\n");
+        out.push_str("        <TriggerMethod>Manual</TriggerMethod>\n");
+        out.push_str("        <Track>\n");
+        for point in in_lap {
+            out.push_str("          <Trackpoint>");
+            let _ = write!(out, "<Time>{}</Time>", format_time(point.time));
+            if let (Some(lat), Some(lon)) = (point.lat, point.lon) {
+                let _ = write!(
+                    out,
+                    "<Position><LatitudeDegrees>{}</LatitudeDegrees><LongitudeDegrees>{}</LongitudeDegrees></Position>",
+                    lat, lon
+                );
+            }
+            if let Some(elevation) = point.elevation_m {
+                let _ = write!(out, "<AltitudeMeters>{}</AltitudeMeters>", elevation);
+            }
+            if let Some(distance) = point.distance_m {
+                let _ = write!(out, "<DistanceMeters>{}</DistanceMeters>", distance);
+            }
+            if let Some(heart_rate) = point.heart_rate {
+                let _ = write!(
+                    out,
+                    "<HeartRateBpm><Value>{}</Value></HeartRateBpm>",
+                    heart_rate
+                );
+            }
+            if let Some(cadence) = point.cadence {
+                let _ = write!(out, "<Cadence>{}</Cadence>", cadence);
+            }
+            out.push_str("</Trackpoint>\n");
+        }
+        out.push_str("        </Track>\n      </Lap>\n");
+    }
+    if let Some(name) = &activity.name {
+        let _ = writeln!(out, "      <Notes>{}</Notes>", escape_xml(name));
+    }
+    out.push_str("    </Activity>\n  </Activities>\n</TrainingCenterDatabase>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::activity_import::parse_activity_file;
+    use chrono::TimeZone;
+    use uuid::Uuid;
+
+    fn activity() -> Activity {
+        Activity {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            activity_type: ACTIVITY_RUN.into(),
+            name: Some("Hills & <intervals>".into()),
+            started_at: Utc.with_ymd_and_hms(2024, 12, 1, 8, 0, 0).unwrap(),
+            duration_seconds: 120,
+            moving_seconds: Some(120),
+            distance_m: Some(400.0),
+            elevation_gain_m: None,
+            elevation_loss_m: None,
+            avg_heart_rate: Some(150),
+            max_heart_rate: Some(160),
+            avg_cadence: None,
+            source_format: None,
+            created_at: Utc::now(),
+            updated_at: None,
+            notes: None,
+            pace_seconds_per_km: Some(300.0),
+        }
+    }
+
+    fn points() -> Vec<TrackPoint> {
+        (0..5)
+            .map(|i| TrackPoint {
+                time: Utc.with_ymd_and_hms(2024, 12, 1, 8, 0, 0).unwrap()
+                    + chrono::Duration::seconds(i * 30),
+                lat: Some(47.0 + i as f64 * 0.0009),
+                lon: Some(8.5),
+                elevation_m: Some(400.0 + i as f64),
+                distance_m: Some(i as f64 * 100.0),
+                heart_rate: Some(140 + i as i32 * 5),
+                cadence: Some(85),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn gpx_round_trips_through_the_importer() {
+        let gpx = render_gpx(&activity(), &points());
+        let parsed = parse_activity_file(gpx.as_bytes()).unwrap();
+
+        assert_eq!(parsed.format, "gpx");
+        assert_eq!(parsed.sport, Some(ACTIVITY_RUN));
+        assert_eq!(parsed.name.as_deref(), Some("Hills & <intervals>"));
+        assert_eq!(parsed.points.len(), 5);
+        assert_eq!(parsed.points[4].heart_rate, Some(160));
+        assert_eq!(parsed.points[4].elevation_m, Some(404.0));
+        assert_eq!(parsed.points[2].time, points()[2].time);
+    }
+
+    #[test]
+    fn tcx_splits_points_into_laps() {
+        let activity = activity();
+        let laps: Vec<ActivityLap> = (0..2)
+            .map(|i| ActivityLap {
+                activity_id: activity.id,
+                lap_index: i,
+                started_at: activity.started_at + chrono::Duration::seconds(i as i64 * 60),
+                duration_seconds: 60.0,
+                distance_m: Some(200.0),
+                avg_heart_rate: None,
+            })
+            .collect();
+        let tcx = render_tcx(&activity, &laps, &points());
+        let parsed = parse_activity_file(tcx.as_bytes()).unwrap();
+
+        assert_eq!(parsed.format, "tcx");
+        assert_eq!(parsed.sport, Some(ACTIVITY_RUN));
+        assert_eq!(parsed.laps.len(), 2);
+        assert_eq!(parsed.laps[1].distance_m, Some(200.0));
+        assert_eq!(parsed.points.len(), 5);
+        assert_eq!(parsed.points[3].distance_m, Some(300.0));
+        assert_eq!(tcx.matches("<Trackpoint>").count(), 5);
+    }
+
+    #[test]
+    fn tcx_without_laps_covers_the_whole_activity() {
+        let tcx = render_tcx(&activity(), &[], &points());
+        let parsed = parse_activity_file(tcx.as_bytes()).unwrap();
+
+        assert_eq!(parsed.laps.len(), 1);
+        assert_eq!(parsed.laps[0].duration_seconds, 120.0);
+        assert_eq!(parsed.laps[0].avg_heart_rate, Some(150));
+    }
+}
@@ -0,0 +1,281 @@
+use chrono::{DateTime, Duration, Utc};
+use roxmltree::{Document, Node};
+
+use crate::{
+    error::AppError,
+    models::activity::{
+        TrackPoint, ACTIVITY_RIDE, ACTIVITY_ROW, ACTIVITY_RUN, ACTIVITY_SWIM, ACTIVITY_WALK,
+        SOURCE_FIT, SOURCE_GPX, SOURCE_TCX,
+    },
+    services::{
+        fit::{is_fit, parse_fit},
+        track::normalize_track,
+    },
+};
+
+/// Largest activity file we accept; a multi-hour GPX track stays well below.
+pub const MAX_ACTIVITY_FILE_BYTES: usize = 25 * 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedLap {
+    pub started_at: DateTime<Utc>,
+    pub duration_seconds: f64,
+    pub distance_m: Option<f64>,
+    pub avg_heart_rate: Option<i32>,
+}
+
+/// An activity file's contents in a format-independent shape.
+#[derive(Debug, Default)]
+pub struct ParsedActivity {
+    pub format: &'static str,
+    /// Activity type the file declares, if we recognise it.
+    pub sport: Option<&'static str>,
+    pub name: Option<String>,
+    pub points: Vec<TrackPoint>,
+    pub laps: Vec<ParsedLap>,
+}
+
+/// Maps the sport names used by GPX `<type>` and TCX `Sport` to activity types.
+fn sport_type(sport: &str) -> Option<&'static str> {
+    match sport.trim().to_ascii_lowercase().as_str() {
+        "run" | "running" | "trail_running" | "treadmill_running" => Some(ACTIVITY_RUN),
+        "ride" | "biking" | "cycling" | "road_biking" | "mountain_biking" => Some(ACTIVITY_RIDE),
+        "swim" | "swimming" | "open_water_swimming" | "lap_swimming" => Some(ACTIVITY_SWIM),
+        "row" | "rowing" | "indoor_rowing" => Some(ACTIVITY_ROW),
+        "walk" | "walking" | "hike" | "hiking" => Some(ACTIVITY_WALK),
+        _ => None,
+    }
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|n| n.tag_name().name() == name)
+}
+
+fn child_text<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+    child(node, name).and_then(|n| n.text()).map(str::trim)
+}
+
+/// Text of the first descendant with one of the names, for extension
+/// elements whose namespace prefixes vary between devices.
+fn descendant_text<'a>(node: Node<'a, '_>, names: &[&str]) -> Option<&'a str> {
+    node.descendants()
+        .find(|n| names.contains(&n.tag_name().name()))
+        .and_then(|n| n.text())
+        .map(str::trim)
+}
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+fn parse_number<T: std::str::FromStr>(value: Option<&str>) -> Option<T> {
+    value.and_then(|v| v.parse().ok())
+}
+
+fn parse_gpx(root: Node) -> ParsedActivity {
+    let mut activity = ParsedActivity {
+        format: SOURCE_GPX,
+        ..Default::default()
+    };
+    activity.name = child(root, "metadata").and_then(|m| child_text(m, "name").map(String::from));
+
+    for track in root.children().filter(|n| n.has_tag_name("trk")) {
+        if activity.name.is_none() {
+            activity.name = child_text(track, "name").map(String::from);
+        }
+        if activity.sport.is_none() {
+            activity.sport = child_text(track, "type").and_then(sport_type);
+        }
+        for point in track
+            .descendants()
+            .filter(|n| n.tag_name().name() == "trkpt")
+        {
+            let Some(time) = child_text(point, "time").and_then(parse_time) else {
+                continue;
+            };
+            activity.points.push(TrackPoint {
+                time,
+                lat: parse_number(point.attribute("lat")),
+                lon: parse_number(point.attribute("lon")),
+                elevation_m: parse_number(child_text(point, "ele")),
+                distance_m: None,
+                heart_rate: parse_number(descendant_text(point, &["hr", "heartrate"])),
+                cadence: parse_number(descendant_text(point, &["cad", "cadence"])),
+            });
+        }
+    }
+
+    activity
+}
+
+fn parse_tcx(root: Node) -> ParsedActivity {
+    let mut activity = ParsedActivity {
+        format: SOURCE_TCX,
+        ..Default::default()
+    };
+    // Files can hold several activities; we import the first.
+    let Some(node) = root
+        .descendants()
+        .find(|n| n.tag_name().name() == "Activity")
+    else {
+        return activity;
+    };
+    activity.sport = node.attribute("Sport").and_then(sport_type);
+    activity.name = child_text(node, "Notes").map(String::from);
+
+    for lap in node.children().filter(|n| n.tag_name().name() == "Lap") {
+        if let Some(started_at) = lap.attribute("StartTime").and_then(parse_time) {
+            activity.laps.push(ParsedLap {
+                started_at,
+                duration_seconds: parse_number(child_text(lap, "TotalTimeSeconds")).unwrap_or(0.0),
+                distance_m: parse_number(child_text(lap, "DistanceMeters")),
+                avg_heart_rate: child(lap, "AverageHeartRateBpm")
+                    .and_then(|hr| parse_number::<f64>(child_text(hr, "Value")))
+                    .map(|hr| hr.round() as i32),
+            });
+        }
+        for point in lap
+            .descendants()
+            .filter(|n| n.tag_name().name() == "Trackpoint")
+        {
+            let Some(time) = child_text(point, "Time").and_then(parse_time) else {
+                continue;
+            };
+            let position = child(point, "Position");
+            activity.points.push(TrackPoint {
+                time,
+                lat: position.and_then(|p| parse_number(child_text(p, "LatitudeDegrees"))),
+                lon: position.and_then(|p| parse_number(child_text(p, "LongitudeDegrees"))),
+                elevation_m: parse_number(child_text(point, "AltitudeMeters")),
+                distance_m: parse_number(child_text(point, "DistanceMeters")),
+                heart_rate: child(point, "HeartRateBpm")
+                    .and_then(|hr| parse_number(child_text(hr, "Value"))),
+                // Bike cadence is a plain element, run cadence an extension
+                cadence: parse_number(child_text(point, "Cadence"))
+                    .or_else(|| parse_number(descendant_text(point, &["RunCadence"]))),
+            });
+        }
+    }
+
+    activity
+}
+
+/// Fills in lap figures the file left out from the track itself.
+fn complete_laps(activity: &mut ParsedActivity) {
+    let points = &activity.points;
+    for lap in &mut activity.laps {
+        let end = lap.started_at + Duration::milliseconds((lap.duration_seconds * 1000.0) as i64);
+        let in_lap: Vec<&TrackPoint> = points
+            .iter()
+            .filter(|p| p.time >= lap.started_at && p.time <= end)
+            .collect();
+        if lap.distance_m.is_none() {
+            if let (Some(first), Some(last)) = (in_lap.first(), in_lap.last()) {
+                lap.distance_m = last.distance_m.zip(first.distance_m).map(|(b, a)| b - a);
+            }
+        }
+        if lap.avg_heart_rate.is_none() {
+            let rates: Vec<i32> = in_lap.iter().filter_map(|p| p.heart_rate).collect();
+            if !rates.is_empty() {
+                lap.avg_heart_rate =
+                    Some((rates.iter().sum::<i32>() as f64 / rates.len() as f64).round() as i32);
+            }
+        }
+    }
+    activity.laps.sort_by_key(|lap| lap.started_at);
+}
+
+/// Parses a GPX, TCX or FIT file, telling them apart by content.
+pub fn parse_activity_file(bytes: &[u8]) -> Result<ParsedActivity, AppError> {
+    let mut activity = if is_fit(bytes) {
+        let mut activity = parse_fit(bytes)
+            .map_err(|e| AppError::BadRequest(format!("Invalid FIT file: {}", e)))?;
+        activity.format = SOURCE_FIT;
+        activity
+    } else {
+        let text = std::str::from_utf8(bytes)
+            .map_err(|_| AppError::BadRequest("Expected a GPX, TCX or FIT file".into()))?;
+        let document = Document::parse(text.trim_start_matches('\u{feff}'))
+            .map_err(|e| AppError::BadRequest(format!("Invalid XML: {}", e)))?;
+        let root = document.root_element();
+        match root.tag_name().name() {
+            "gpx" => parse_gpx(root),
+            "TrainingCenterDatabase" => parse_tcx(root),
+            _ => {
+                return Err(AppError::BadRequest(
+                    "Expected a GPX, TCX or FIT file".into(),
+                ))
+            }
+        }
+    };
+
+    normalize_track(&mut activity.points);
+    if activity.points.len() < 2 {
+        return Err(AppError::BadRequest(
+            "The file contains no timed track points".into(),
+        ));
+    }
+    complete_laps(&mut activity);
+
+    Ok(activity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_gpx_with_garmin_extensions() {
+        let gpx = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <gpx version="1.1" xmlns="http://www.topografix.com/GPX/1/1"
+                 xmlns:gpxtpx="http://www.garmin.com/xmlschemas/TrackPointExtension/v1">
+              <metadata><name>Morning Run</name></metadata>
+              <trk><type>running</type><trkseg>
+                <trkpt lat="52.5" lon="13.4"><ele>34.0</ele><time>2024-12-01T07:00:00Z</time>
+                  <extensions><gpxtpx:TrackPointExtension><gpxtpx:hr>120</gpxtpx:hr><gpxtpx:cad>85</gpxtpx:cad></gpxtpx:TrackPointExtension></extensions>
+                </trkpt>
+                <trkpt lat="52.501" lon="13.4"><ele>36.0</ele><time>2024-12-01T07:00:30Z</time></trkpt>
+              </trkseg></trk>
+            </gpx>"#;
+        let activity = parse_activity_file(gpx.as_bytes()).unwrap();
+
+        assert_eq!(activity.format, SOURCE_GPX);
+        assert_eq!(activity.sport, Some(ACTIVITY_RUN));
+        assert_eq!(activity.name.as_deref(), Some("Morning Run"));
+        assert_eq!(activity.points[0].heart_rate, Some(120));
+        assert_eq!(activity.points[0].cadence, Some(85));
+        // Distance is derived from the positions
+        assert_eq!(activity.points[1].distance_m.unwrap().round(), 111.0);
+    }
+
+    #[test]
+    fn parses_tcx_laps() {
+        let tcx = r#"<TrainingCenterDatabase xmlns="http://www.garmin.com/xmlschemas/TrainingCenterDatabase/v2">
+              <Activities><Activity Sport="Biking"><Id>2024-12-01T07:00:00Z</Id>
+                <Lap StartTime="2024-12-01T07:00:00Z"><TotalTimeSeconds>60</TotalTimeSeconds><DistanceMeters>500</DistanceMeters>
+                  <Track>
+                    <Trackpoint><Time>2024-12-01T07:00:00Z</Time><DistanceMeters>0</DistanceMeters><HeartRateBpm><Value>110</Value></HeartRateBpm><Cadence>80</Cadence></Trackpoint>
+                    <Trackpoint><Time>2024-12-01T07:01:00Z</Time><DistanceMeters>500</DistanceMeters><HeartRateBpm><Value>130</Value></HeartRateBpm></Trackpoint>
+                  </Track>
+                </Lap>
+              </Activity></Activities>
+            </TrainingCenterDatabase>"#;
+        let activity = parse_activity_file(tcx.as_bytes()).unwrap();
+
+        assert_eq!(activity.format, SOURCE_TCX);
+        assert_eq!(activity.sport, Some(ACTIVITY_RIDE));
+        assert_eq!(activity.points.len(), 2);
+        assert_eq!(activity.points[1].distance_m, Some(500.0));
+        assert_eq!(activity.laps.len(), 1);
+        assert_eq!(activity.laps[0].avg_heart_rate, Some(120));
+    }
+
+    #[test]
+    fn rejects_other_files() {
+        assert!(parse_activity_file(b"<kml></kml>").is_err());
+        assert!(parse_activity_file(b"\x00\x01binary").is_err());
+        assert!(parse_activity_file(b"<gpx></gpx>").is_err());
+    }
+}
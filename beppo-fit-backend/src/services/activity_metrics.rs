@@ -0,0 +1,271 @@
+//! Per-distance splits and time in heart rate zones, derived from a recorded track.
+
+use crate::models::{
+    activity::{ActivitySplit, HeartRateZone, HeartRateZones, TrackPoint},
+    profile::{UserProfile, ZONE_METHOD_KARVONEN, ZONE_METHOD_PERCENT_MAX},
+};
+
+pub const METRES_PER_KM: f64 = 1000.0;
+pub const METRES_PER_MILE: f64 = 1609.344;
+
+/// A trailing partial split shorter than this is left out.
+const MIN_PARTIAL_SPLIT_M: f64 = 50.0;
+
+/// Lower bounds of zones 1-5 as a share of max heart rate or heart rate reserve.
+const ZONE_FRACTIONS: [f64; 5] = [0.5, 0.6, 0.7, 0.8, 0.9];
+
+/// Gaps between samples longer than this are pauses and count towards no zone.
+const MAX_SAMPLE_GAP_SECONDS: f64 = 60.0;
+
+fn round_tenth(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+fn seconds_between(from: &TrackPoint, to: &TrackPoint) -> f64 {
+    (to.time - from.time).num_milliseconds() as f64 / 1000.0
+}
+
+/// A position along the track, possibly between two samples.
+#[derive(Clone, Copy)]
+struct Mark {
+    seconds: f64,
+    distance_m: f64,
+    elevation_m: Option<f64>,
+}
+
+#[derive(Default)]
+struct HeartRates {
+    sum: i64,
+    count: i64,
+}
+
+impl HeartRates {
+    fn add(&mut self, heart_rate: Option<i32>) {
+        if let Some(heart_rate) = heart_rate {
+            self.sum += heart_rate as i64;
+            self.count += 1;
+        }
+    }
+
+    fn average(&self) -> Option<i32> {
+        (self.count > 0).then(|| (self.sum as f64 / self.count as f64).round() as i32)
+    }
+}
+
+fn split(index: usize, start: Mark, end: Mark, heart_rates: &HeartRates) -> ActivitySplit {
+    let distance_m = end.distance_m - start.distance_m;
+    let duration_seconds = end.seconds - start.seconds;
+    ActivitySplit {
+        split: index as i32 + 1,
+        distance_m: round_tenth(distance_m),
+        duration_seconds: round_tenth(duration_seconds),
+        pace_seconds_per_km: round_tenth(duration_seconds * METRES_PER_KM / distance_m),
+        elevation_change_m: start
+            .elevation_m
+            .zip(end.elevation_m)
+            .map(|(a, b)| round_tenth(b - a)),
+        avg_heart_rate: heart_rates.average(),
+    }
+}
+
+/// Splits every `split_m` metres of a normalized track. Split boundaries fall
+/// between samples, so times and elevations there are interpolated.
+pub fn compute_splits(points: &[TrackPoint], split_m: f64) -> Vec<ActivitySplit> {
+    let samples: Vec<&TrackPoint> = points.iter().filter(|p| p.distance_m.is_some()).collect();
+    let Some(first) = samples.first() else {
+        return Vec::new();
+    };
+
+    let mark = |point: &TrackPoint, elevation_m: Option<f64>| Mark {
+        seconds: seconds_between(first, point),
+        distance_m: point.distance_m.unwrap_or_default(),
+        elevation_m: point.elevation_m.or(elevation_m),
+    };
+    let mut splits = Vec::new();
+    let mut start = mark(first, None);
+    let mut previous = start;
+    let mut heart_rates = HeartRates::default();
+    heart_rates.add(first.heart_rate);
+
+    for point in &samples[1..] {
+        let current = mark(point, previous.elevation_m);
+        while current.distance_m >= start.distance_m + split_m
+            && current.distance_m > previous.distance_m
+        {
+            let boundary = start.distance_m + split_m;
+            let fraction =
+                (boundary - previous.distance_m) / (current.distance_m - previous.distance_m);
+            let end = Mark {
+                seconds: previous.seconds + fraction * (current.seconds - previous.seconds),
+                distance_m: boundary,
+                elevation_m: match (previous.elevation_m, current.elevation_m) {
+                    (Some(a), Some(b)) => Some(a + fraction * (b - a)),
+                    (a, b) => a.or(b),
+                },
+            };
+            splits.push(split(splits.len(), start, end, &heart_rates));
+            start = end;
+            previous = end;
+            heart_rates = HeartRates::default();
+        }
+        heart_rates.add(point.heart_rate);
+        previous = current;
+    }
+    if previous.distance_m - start.distance_m >= MIN_PARTIAL_SPLIT_M
+        && previous.seconds > start.seconds
+    {
+        splits.push(split(splits.len(), start, previous, &heart_rates));
+    }
+    splits
+}
+
+/// Zone bounds in bpm from the profile, or `None` without a max heart rate.
+/// Karvonen needs a resting heart rate and falls back to percentage of max.
+fn zone_bounds(profile: &UserProfile) -> Option<(&'static str, Option<i32>, [i32; 5], i32)> {
+    let max = profile.max_heart_rate?;
+    let resting = profile.resting_heart_rate.filter(|r| *r < max);
+    let (method, base, range) = match resting {
+        Some(resting) if profile.heart_rate_zone_method == ZONE_METHOD_KARVONEN => {
+            (ZONE_METHOD_KARVONEN, resting as f64, (max - resting) as f64)
+        }
+        _ => (ZONE_METHOD_PERCENT_MAX, 0.0, max as f64),
+    };
+    let lower = ZONE_FRACTIONS.map(|f| (base + f * range).round() as i32);
+    Some((method, resting, lower, max))
+}
+
+/// Time spent in each of five heart rate zones. Each sample's heart rate
+/// holds until the next sample.
+pub fn heart_rate_zones(points: &[TrackPoint], profile: &UserProfile) -> Option<HeartRateZones> {
+    let (method, resting, lower, max) = zone_bounds(profile)?;
+    if !points.iter().any(|p| p.heart_rate.is_some()) {
+        return None;
+    }
+
+    let mut seconds = [0.0; 5];
+    let mut below = 0.0;
+    for pair in points.windows(2) {
+        let Some(heart_rate) = pair[0].heart_rate else {
+            continue;
+        };
+        let elapsed = seconds_between(&pair[0], &pair[1]);
+        if elapsed <= 0.0 || elapsed > MAX_SAMPLE_GAP_SECONDS {
+            continue;
+        }
+        match lower.iter().rposition(|bound| heart_rate >= *bound) {
+            Some(zone) => seconds[zone] += elapsed,
+            None => below += elapsed,
+        }
+    }
+
+    let zones = (0..5)
+        .map(|i| HeartRateZone {
+            zone: i as i32 + 1,
+            min_bpm: lower[i],
+            max_bpm: lower.get(i + 1).copied().unwrap_or(max),
+            seconds: seconds[i].round() as i32,
+        })
+        .collect();
+    Some(HeartRateZones {
+        method: method.to_string(),
+        max_heart_rate: max,
+        resting_heart_rate: resting,
+        zones,
+        below_zones_seconds: below.round() as i32,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Utc};
+    use uuid::Uuid;
+
+    fn point(second: i64, distance_m: f64, elevation: f64, heart_rate: i32) -> TrackPoint {
+        TrackPoint {
+            time: DateTime::<Utc>::from_timestamp(1_733_000_000 + second, 0).unwrap(),
+            lat: None,
+            lon: None,
+            elevation_m: Some(elevation),
+            distance_m: Some(distance_m),
+            heart_rate: Some(heart_rate),
+            cadence: None,
+        }
+    }
+
+    fn profile(max: Option<i32>, resting: Option<i32>, method: &str) -> UserProfile {
+        UserProfile {
+            max_heart_rate: max,
+            resting_heart_rate: resting,
+            heart_rate_zone_method: method.into(),
+            ..UserProfile::test_default(Uuid::nil())
+        }
+    }
+
+    #[test]
+    fn splits_interpolate_boundaries() {
+        // 4 m/s for 2.3 km, climbing 1 m every 100 m
+        let points: Vec<TrackPoint> = (0..=23)
+            .map(|i| point(i * 25, i as f64 * 100.0, i as f64, 140 + i as i32))
+            .collect();
+        let splits = compute_splits(&points, METRES_PER_KM);
+
+        assert_eq!(splits.len(), 3);
+        assert_eq!(splits[0].split, 1);
+        assert_eq!(splits[0].distance_m, 1000.0);
+        assert_eq!(splits[0].duration_seconds, 250.0);
+        assert_eq!(splits[0].pace_seconds_per_km, 250.0);
+        assert_eq!(splits[0].elevation_change_m, Some(10.0));
+        assert_eq!(splits[1].avg_heart_rate, Some(155));
+        assert_eq!(splits[2].distance_m, 300.0);
+        assert_eq!(splits[2].duration_seconds, 75.0);
+        assert_eq!(splits[2].pace_seconds_per_km, 250.0);
+
+        let miles = compute_splits(&points, METRES_PER_MILE);
+        assert_eq!(miles.len(), 2);
+        assert_eq!(miles[0].duration_seconds, 402.3);
+        assert_eq!(miles[1].distance_m, 690.7);
+    }
+
+    #[test]
+    fn short_tracks_have_no_full_split() {
+        let points = [point(0, 0.0, 0.0, 120), point(10, 30.0, 0.0, 120)];
+        assert!(compute_splits(&points, METRES_PER_KM).is_empty());
+        assert!(compute_splits(&[], METRES_PER_KM).is_empty());
+    }
+
+    #[test]
+    fn heart_rate_zone_methods() {
+        let points = [
+            point(0, 0.0, 0.0, 90),
+            point(30, 0.0, 0.0, 120),
+            point(60, 0.0, 0.0, 150),
+            point(90, 0.0, 0.0, 185),
+            // A pause in the recording
+            point(300, 0.0, 0.0, 100),
+            point(310, 0.0, 0.0, 100),
+        ];
+
+        let zones = heart_rate_zones(&points, &profile(Some(190), None, "percent_max")).unwrap();
+        assert_eq!(zones.method, "percent_max");
+        let bounds: Vec<i32> = zones.zones.iter().map(|z| z.min_bpm).collect();
+        assert_eq!(bounds, [95, 114, 133, 152, 171]);
+        let seconds: Vec<i32> = zones.zones.iter().map(|z| z.seconds).collect();
+        assert_eq!(seconds, [10, 30, 30, 0, 0]);
+        assert_eq!(zones.below_zones_seconds, 30);
+        assert_eq!(zones.zones[4].max_bpm, 190);
+
+        let zones = heart_rate_zones(&points, &profile(Some(190), Some(50), "karvonen")).unwrap();
+        assert_eq!(zones.method, "karvonen");
+        let bounds: Vec<i32> = zones.zones.iter().map(|z| z.min_bpm).collect();
+        assert_eq!(bounds, [120, 134, 148, 162, 176]);
+        let seconds: Vec<i32> = zones.zones.iter().map(|z| z.seconds).collect();
+        assert_eq!(seconds, [30, 0, 30, 0, 0]);
+        assert_eq!(zones.below_zones_seconds, 40);
+
+        // Karvonen without a resting heart rate uses percentage of max
+        let zones = heart_rate_zones(&points, &profile(Some(190), None, "karvonen")).unwrap();
+        assert_eq!(zones.method, "percent_max");
+        assert!(heart_rate_zones(&points, &profile(None, Some(50), "karvonen")).is_none());
+    }
+}
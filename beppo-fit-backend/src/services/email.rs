@@ -7,6 +7,7 @@ use crate::error::AppError;
 pub struct EmailService {
     mailer: SmtpTransport,
     frontend_url: String,
+    api_url: String,
 }
 
 impl EmailService {
@@ -21,18 +22,20 @@ impl EmailService {
         let mailer = SmtpTransport::builder_dangerous(&host).port(port).build();
         let frontend_url =
             env::var("FRONTEND_URL").unwrap_or_else(|_| "http://localhost:80".to_string());
+        let api_url = env::var("API_URL").unwrap_or_else(|_| "http://localhost:8080".to_string());
 
         Self {
             mailer,
             frontend_url,
+            api_url,
         }
     }
 
     pub fn send_verification_email(&self, to_email: &str, token: &str) -> Result<(), AppError> {
         let subject = "Verify your BeppoFit account";
         let body = format!(
-            "Welcome to BeppoFit! Click here to verify your account: http://localhost:8080/auth/verify?token={}",
-            token
+            "Welcome to BeppoFit! Click here to verify your account: {}/auth/verify?token={}",
+            self.api_url, token
         );
 
         self.send_email(to_email, subject, &body)
@@ -48,6 +51,16 @@ impl EmailService {
         self.send_email(to_email, subject, &body)
     }
 
+    pub fn send_data_export_email(&self, to_email: &str, token: &str) -> Result<(), AppError> {
+        let subject = "Your BeppoFit data export is ready";
+        let body = format!(
+            "Your data export is ready. Download it here within the next 48 hours: {}/auth/me/export/download?token={}",
+            self.api_url, token
+        );
+
+        self.send_email(to_email, subject, &body)
+    }
+
     fn send_email(&self, to: &str, subject: &str, body: &str) -> Result<(), AppError> {
         let email = Message::builder()
             .from("BeppoFit <noreply@beppofit.com>".parse().unwrap())
@@ -84,5 +97,6 @@ mod tests {
         // Just verify it doesn't panic and constructs successfully
         // We can't easily check private fields without accessors, but this ensures Default works.
         assert!(!service.frontend_url.is_empty());
+        assert!(!service.api_url.is_empty());
     }
 }
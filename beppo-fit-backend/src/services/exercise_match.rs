@@ -0,0 +1,210 @@
+//! Fuzzy matching of exercise names from other apps against our catalog.
+//!
+//! Names are compared as bags of words, so "Bench Press (Barbell)" finds
+//! "Barbell Bench Press". Words are normalised (plurals, common abbreviations,
+//! single typos) and qualifiers that only restate the default variant count
+//! for less, so a plain "Squat (Barbell)" prefers the back squat.
+
+use uuid::Uuid;
+
+/// Scores at or above this are applied without asking the user.
+pub const AUTO_MATCH_SCORE: f64 = 0.8;
+
+const STOP_WORDS: &[&str] = &["a", "and", "the", "with", "on", "of", "to"];
+
+/// Words that name the usual variant of a movement.
+const DEFAULT_QUALIFIERS: &[&str] = &["back", "flat", "standing", "conventional", "regular"];
+const QUALIFIER_WEIGHT: f64 = 0.3;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoredExercise {
+    pub exercise_id: Uuid,
+    pub name: String,
+    pub score: f64,
+}
+
+fn expand(word: &str) -> &[&str] {
+    match word {
+        "db" => &["dumbbell"],
+        "bb" => &["barbell"],
+        "kb" => &["kettlebell"],
+        "ohp" => &["overhead", "press"],
+        "rdl" => &["romanian", "deadlift"],
+        "sldl" => &["stiff", "leg", "deadlift"],
+        "pullup" => &["pull", "up"],
+        "chinup" => &["chin", "up"],
+        "pushup" => &["push", "up"],
+        "situp" => &["sit", "up"],
+        "stepup" => &["step", "up"],
+        _ => &[],
+    }
+}
+
+fn singular(word: &str) -> String {
+    if word.len() > 4 && ["ches", "shes", "xes"].iter().any(|s| word.ends_with(s)) {
+        word[..word.len() - 2].to_string()
+    } else if word.len() > 3 && word.ends_with('s') && !word.ends_with("ss") {
+        word[..word.len() - 1].to_string()
+    } else {
+        word.to_string()
+    }
+}
+
+/// The normalised words of an exercise name.
+pub fn name_tokens(name: &str) -> Vec<String> {
+    let lowered = name.to_lowercase();
+    let mut tokens = Vec::new();
+    for word in lowered
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty() && !STOP_WORDS.contains(w))
+    {
+        let word = singular(word);
+        match expand(&word) {
+            [] => tokens.push(word),
+            expanded => tokens.extend(expanded.iter().map(|w| w.to_string())),
+        }
+    }
+    tokens
+}
+
+fn weight(token: &str) -> f64 {
+    if DEFAULT_QUALIFIERS.contains(&token) {
+        QUALIFIER_WEIGHT
+    } else {
+        1.0
+    }
+}
+
+/// Whether two words differ by at most one edit; only for longer words,
+/// where that is a typo rather than a different word.
+fn nearly_equal(a: &str, b: &str) -> bool {
+    if a == b {
+        return true;
+    }
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    if a.len().min(b.len()) < 5 || a.len().abs_diff(b.len()) > 1 {
+        return false;
+    }
+    let prefix = a.iter().zip(&b).take_while(|(x, y)| x == y).count();
+    let suffix = a
+        .iter()
+        .rev()
+        .zip(b.iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    // One substitution, insertion or deletion leaves everything else in place.
+    prefix + suffix >= a.len().max(b.len()) - 1
+}
+
+/// Weighted Dice coefficient of the two word bags, between 0 and 1.
+pub fn name_similarity(a: &[String], b: &[String]) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    if a.concat() == b.concat() {
+        return 1.0;
+    }
+    let mut used = vec![false; b.len()];
+    let mut shared = 0.0;
+    for token in a {
+        let found = b
+            .iter()
+            .enumerate()
+            .find(|(i, other)| !used[*i] && *other == token)
+            .or_else(|| {
+                b.iter()
+                    .enumerate()
+                    .find(|(i, other)| !used[*i] && nearly_equal(token, other))
+            });
+        if let Some((i, other)) = found {
+            used[i] = true;
+            shared += weight(token).min(weight(other));
+        }
+    }
+    let total: f64 = a.iter().chain(b).map(|t| weight(t)).sum();
+    2.0 * shared / total
+}
+
+/// The closest catalog exercises to a name, best first.
+pub fn rank_exercises(name: &str, catalog: &[(Uuid, String)], limit: usize) -> Vec<ScoredExercise> {
+    let tokens = name_tokens(name);
+    let mut scored: Vec<ScoredExercise> = catalog
+        .iter()
+        .map(|(id, candidate)| ScoredExercise {
+            exercise_id: *id,
+            name: candidate.clone(),
+            score: (name_similarity(&tokens, &name_tokens(candidate)) * 100.0).round() / 100.0,
+        })
+        .filter(|c| c.score > 0.0)
+        .collect();
+    scored.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.name.len().cmp(&b.name.len()))
+    });
+    scored.truncate(limit);
+    scored
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn catalog() -> Vec<(Uuid, String)> {
+        [
+            "Barbell Bench Press",
+            "Dumbbell Bench Press",
+            "Barbell Back Squat",
+            "Barbell Front Squat",
+            "Barbell Deadlift",
+            "Barbell Romanian Deadlift",
+            "Barbell Biceps Curl",
+            "Lat Pulldown",
+            "Machine Lat Pulldown",
+            "Pull-Up",
+            "Cable Crunch",
+        ]
+        .iter()
+        .map(|name| (Uuid::new_v4(), name.to_string()))
+        .collect()
+    }
+
+    fn best(name: &str) -> ScoredExercise {
+        rank_exercises(name, &catalog(), 1).remove(0)
+    }
+
+    #[test]
+    fn normalises_words() {
+        assert_eq!(name_tokens("DB Curls"), ["dumbbell", "curl"]);
+        assert_eq!(name_tokens("Pullups"), ["pull", "up"]);
+        assert_eq!(name_tokens("Cable Crunches"), ["cable", "crunch"]);
+        assert_eq!(
+            name_tokens("Bench Press (Barbell)"),
+            ["bench", "press", "barbell"]
+        );
+    }
+
+    #[test]
+    fn matches_names_from_other_apps() {
+        assert_eq!(best("Bench Press (Barbell)").name, "Barbell Bench Press");
+        assert_eq!(best("Bench Press (Barbell)").score, 1.0);
+        assert_eq!(best("Bench Press (Dumbbell)").name, "Dumbbell Bench Press");
+        assert_eq!(best("Flat Barbell Bench Press").name, "Barbell Bench Press");
+        assert_eq!(best("Squat (Barbell)").name, "Barbell Back Squat");
+        assert_eq!(best("Deadlift (Barbell)").name, "Barbell Deadlift");
+        assert_eq!(best("Bicep Curl (Barbell)").name, "Barbell Biceps Curl");
+        assert_eq!(best("Lat Pulldown (Cable)").name, "Lat Pulldown");
+        assert_eq!(best("Pull Up").name, "Pull-Up");
+        assert_eq!(best("RDL").name, "Barbell Romanian Deadlift");
+        assert_eq!(best("Dumbell Bench Press").name, "Dumbbell Bench Press");
+        assert!(best("Squat (Barbell)").score >= AUTO_MATCH_SCORE);
+    }
+
+    #[test]
+    fn unrelated_names_score_low() {
+        let ranked = rank_exercises("Zercher Carry", &catalog(), 3);
+        assert!(ranked.iter().all(|c| c.score < AUTO_MATCH_SCORE));
+        assert!(!nearly_equal("curl", "cur"));
+        assert!(nearly_equal("pulldown", "puldown"));
+    }
+}
@@ -0,0 +1,519 @@
+use serde::Serialize;
+use std::io::{Cursor, Write};
+use uuid::Uuid;
+use zip::{write::SimpleFileOptions, ZipWriter};
+
+use crate::{
+    error::AppError,
+    models::{
+        achievement::UserAchievement,
+        activity::Activity,
+        calendar_feed::{CalendarFeed, CalendarFeedResponse},
+        exercise::Exercise,
+        export::{
+            ActivityLapRecord, IdentityRecord, MediaRecord, StrengthImportMappingRecord,
+            StrengthImportRecord, WorkoutSetRecord, EXPORT_STATUS_FAILED, EXPORT_STATUS_READY,
+        },
+        goal::Goal,
+        interval::{IntervalSession, IntervalWorkout},
+        measurement::BodyMeasurement,
+        media::Media,
+        notification::Notification,
+        nutrition::{Food, Meal},
+        program::{Enrollment, Program, TrainingMax},
+        progression::ProgressionRecommendation,
+        record::PersonalRecord,
+        schedule::{Schedule, ScheduledSession},
+        social::{Block, Follow},
+        template::WorkoutTemplate,
+        user::User,
+        wellness::WellnessLog,
+        workout::Workout,
+    },
+    services::{
+        activity::load_track,
+        activity_export::render_gpx,
+        interval::{load_interval_detail, load_session_detail},
+        nutrition::load_meal_detail,
+        profile::ensure_profile,
+        program::load_program_detail,
+        template::load_template_detail,
+    },
+    state::AppState,
+};
+
+/// How long the emailed download link stays valid.
+pub const DOWNLOAD_LINK_VALIDITY_HOURS: i64 = 48;
+/// Exports still pending after this long were lost, e.g. to a restart, and are
+/// given up on so a new one can be requested.
+pub const PENDING_EXPORT_TIMEOUT_MINUTES: i64 = 30;
+
+/// Included at the top of every archive.
+const ARCHIVE_README: &str = "\
+This archive holds everything BeppoFit stores about your account.
+
+Sign-ins and other authentication events are not logged, so there is no file
+for them; account.json holds when the account was created and whether it is
+verified. Recorded activity routes are included as GPX files under activities/,
+uploaded history files waiting to be imported under strength_imports/.
+";
+
+/// In-memory ZIP archive the individual export sections are written into.
+pub struct ExportArchive {
+    writer: ZipWriter<Cursor<Vec<u8>>>,
+}
+
+impl ExportArchive {
+    pub fn new() -> Self {
+        Self {
+            writer: ZipWriter::new(Cursor::new(Vec::new())),
+        }
+    }
+
+    pub fn add_json<T: Serialize + ?Sized>(
+        &mut self,
+        name: &str,
+        value: &T,
+    ) -> Result<(), AppError> {
+        let bytes = serde_json::to_vec_pretty(value).map_err(|e| {
+            tracing::error!("Failed to serialize {}: {:?}", name, e);
+            AppError::InternalServerError
+        })?;
+        self.add_file(name, &bytes)
+    }
+
+    pub fn add_csv<T: Serialize>(&mut self, name: &str, rows: &[T]) -> Result<(), AppError> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        for row in rows {
+            writer.serialize(row).map_err(|e| {
+                tracing::error!("Failed to write {}: {:?}", name, e);
+                AppError::InternalServerError
+            })?;
+        }
+        let bytes = writer.into_inner().map_err(|e| {
+            tracing::error!("Failed to write {}: {:?}", name, e);
+            AppError::InternalServerError
+        })?;
+        self.add_file(name, &bytes)
+    }
+
+    pub fn add_file(&mut self, name: &str, bytes: &[u8]) -> Result<(), AppError> {
+        self.writer
+            .start_file(name, SimpleFileOptions::default())
+            .map_err(|e| {
+                tracing::error!("Failed to add {} to export: {:?}", name, e);
+                AppError::InternalServerError
+            })?;
+        self.writer
+            .write_all(bytes)
+            .map_err(|_| AppError::InternalServerError)
+    }
+
+    pub fn finish(self) -> Result<Vec<u8>, AppError> {
+        let cursor = self.writer.finish().map_err(|e| {
+            tracing::error!("Failed to finish export archive: {:?}", e);
+            AppError::InternalServerError
+        })?;
+        Ok(cursor.into_inner())
+    }
+}
+
+impl Default for ExportArchive {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Assembles everything we store about a user into a ZIP archive. No
+/// authentication events are logged, which the archive README states.
+pub async fn build_archive(state: &AppState, user_id: Uuid) -> Result<Vec<u8>, AppError> {
+    let pool = &state.pool;
+    let user = sqlx::query_as!(User, "SELECT * FROM users WHERE id = $1", user_id)
+        .fetch_one(pool)
+        .await?;
+    let profile = ensure_profile(&mut *pool.acquire().await?, user_id).await?;
+    let custom_exercises = sqlx::query_as!(
+        Exercise,
+        "SELECT * FROM exercises WHERE owner_id = $1 ORDER BY created_at",
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+    let workouts = sqlx::query_as!(
+        Workout,
+        "SELECT * FROM workouts WHERE user_id = $1 ORDER BY started_at",
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+    let workout_sets = sqlx::query_as!(
+        WorkoutSetRecord,
+        r#"
+        SELECT w.id AS workout_id, w.name AS workout_name, w.started_at, w.finished_at,
+               e.name AS exercise, we.position AS exercise_position, s.position AS set_position,
+               s.set_type, s.reps, s.weight_kg, s.duration_seconds, s.distance_m, s.rpe, s.rir, s.notes
+        FROM workouts w
+        JOIN workout_exercises we ON we.workout_id = w.id
+        JOIN exercises e ON e.id = we.exercise_id
+        JOIN sets s ON s.workout_exercise_id = we.id
+        WHERE w.user_id = $1
+        ORDER BY w.started_at, we.position, s.position
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+    let mut conn = pool.acquire().await?;
+    let mut templates = Vec::new();
+    for template in sqlx::query_as!(
+        WorkoutTemplate,
+        "SELECT * FROM workout_templates WHERE user_id = $1 ORDER BY created_at",
+        user_id
+    )
+    .fetch_all(&mut *conn)
+    .await?
+    {
+        templates.push(load_template_detail(&mut conn, template).await?);
+    }
+    let mut programs = Vec::new();
+    for program in sqlx::query_as!(
+        Program,
+        "SELECT * FROM programs WHERE user_id = $1 ORDER BY created_at",
+        user_id
+    )
+    .fetch_all(&mut *conn)
+    .await?
+    {
+        programs.push(load_program_detail(&mut conn, program).await?);
+    }
+    let enrollments = sqlx::query_as!(
+        Enrollment,
+        "SELECT * FROM program_enrollments WHERE user_id = $1 ORDER BY started_at",
+        user_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let training_maxes = sqlx::query_as!(
+        TrainingMax,
+        r#"
+        SELECT tm.exercise_id, e.name AS exercise_name, tm.weight_kg, tm.updated_at
+        FROM training_maxes tm
+        JOIN exercises e ON e.id = tm.exercise_id
+        WHERE tm.user_id = $1
+        ORDER BY e.name
+        "#,
+        user_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let recommendations = sqlx::query_as!(
+        ProgressionRecommendation,
+        "SELECT * FROM progression_recommendations WHERE user_id = $1 ORDER BY created_at",
+        user_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let personal_records = sqlx::query_as!(
+        PersonalRecord,
+        "SELECT * FROM personal_records WHERE user_id = $1 ORDER BY achieved_at",
+        user_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let measurements = sqlx::query_as!(
+        BodyMeasurement,
+        "SELECT * FROM body_measurements WHERE user_id = $1 ORDER BY measured_at",
+        user_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let goals = sqlx::query_as!(
+        Goal,
+        "SELECT * FROM goals WHERE user_id = $1 ORDER BY created_at",
+        user_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let notifications = sqlx::query_as!(
+        Notification,
+        "SELECT * FROM notifications WHERE user_id = $1 ORDER BY created_at",
+        user_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let achievements = sqlx::query_as!(
+        UserAchievement,
+        "SELECT * FROM user_achievements WHERE user_id = $1 ORDER BY unlocked_at",
+        user_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let schedules = sqlx::query_as!(
+        Schedule,
+        "SELECT * FROM workout_schedules WHERE user_id = $1 ORDER BY created_at",
+        user_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let scheduled_sessions = sqlx::query_as!(
+        ScheduledSession,
+        r#"
+        SELECT ss.* FROM scheduled_sessions ss
+        JOIN workout_schedules s ON s.id = ss.schedule_id
+        WHERE s.user_id = $1
+        ORDER BY ss.scheduled_date
+        "#,
+        user_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let activities = sqlx::query_as!(
+        Activity,
+        "SELECT * FROM activities WHERE user_id = $1 ORDER BY started_at",
+        user_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let activity_laps = sqlx::query_as!(
+        ActivityLapRecord,
+        r#"
+        SELECT l.activity_id, l.lap_index, l.started_at, l.duration_seconds, l.distance_m, l.avg_heart_rate
+        FROM activity_laps l
+        JOIN activities a ON a.id = l.activity_id
+        WHERE a.user_id = $1
+        ORDER BY a.started_at, l.lap_index
+        "#,
+        user_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let mut activity_tracks = Vec::new();
+    for activity in &activities {
+        if let Some(points) = load_track(&mut conn, activity.id).await? {
+            activity_tracks.push((activity.id, render_gpx(activity, &points)));
+        }
+    }
+    let mut interval_workouts = Vec::new();
+    for workout in sqlx::query_as!(
+        IntervalWorkout,
+        "SELECT * FROM interval_workouts WHERE user_id = $1 ORDER BY created_at",
+        user_id
+    )
+    .fetch_all(&mut *conn)
+    .await?
+    {
+        interval_workouts.push(load_interval_detail(&mut conn, workout).await?);
+    }
+    let mut interval_sessions = Vec::new();
+    for session in sqlx::query_as!(
+        IntervalSession,
+        "SELECT * FROM interval_sessions WHERE user_id = $1 ORDER BY started_at",
+        user_id
+    )
+    .fetch_all(&mut *conn)
+    .await?
+    {
+        interval_sessions.push(load_session_detail(&mut conn, session).await?);
+    }
+    let custom_foods = sqlx::query_as!(
+        Food,
+        "SELECT * FROM foods WHERE owner_id = $1 ORDER BY created_at",
+        user_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let mut meals = Vec::new();
+    for meal in sqlx::query_as!(
+        Meal,
+        "SELECT * FROM meals WHERE user_id = $1 ORDER BY eaten_at",
+        user_id
+    )
+    .fetch_all(&mut *conn)
+    .await?
+    {
+        meals.push(load_meal_detail(&mut conn, meal).await?);
+    }
+    let follows = sqlx::query_as!(
+        Follow,
+        "SELECT * FROM follows WHERE follower_id = $1 OR followee_id = $1 ORDER BY created_at",
+        user_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let blocks = sqlx::query_as!(
+        Block,
+        "SELECT * FROM blocks WHERE blocker_id = $1 ORDER BY created_at",
+        user_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let wellness_logs = sqlx::query_as!(
+        WellnessLog,
+        "SELECT * FROM wellness_logs WHERE user_id = $1 ORDER BY log_date",
+        user_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let strength_imports = sqlx::query!(
+        "SELECT id, source, units, data, created_at FROM strength_imports WHERE user_id = $1 ORDER BY created_at",
+        user_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let strength_import_mappings = sqlx::query_as!(
+        StrengthImportMappingRecord,
+        r#"
+        SELECT m.import_id, m.source_name, m.exercise_id
+        FROM strength_import_mappings m
+        JOIN strength_imports i ON i.id = m.import_id
+        WHERE i.user_id = $1
+        ORDER BY i.created_at, m.source_name
+        "#,
+        user_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let calendar_feeds = sqlx::query_as!(
+        CalendarFeed,
+        "SELECT * FROM calendar_feeds WHERE user_id = $1",
+        user_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let media = sqlx::query_as!(
+        Media,
+        "SELECT * FROM media WHERE user_id = $1 ORDER BY created_at",
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut identities = Vec::new();
+    if user.password_hash.is_some() {
+        identities.push(IdentityRecord {
+            provider: "password",
+            subject: user.email.clone(),
+        });
+    }
+    if let Some(google_id) = &user.google_id {
+        identities.push(IdentityRecord {
+            provider: "google",
+            subject: google_id.clone(),
+        });
+    }
+
+    let mut archive = ExportArchive::new();
+    archive.add_file("README.txt", ARCHIVE_README.as_bytes())?;
+    archive.add_json("account.json", &user)?;
+    archive.add_csv("identities.csv", &identities)?;
+    archive.add_json("profile.json", &profile)?;
+    archive.add_json("custom_exercises.json", &custom_exercises)?;
+    archive.add_json("workouts.json", &workouts)?;
+    archive.add_csv("workouts.csv", &workout_sets)?;
+    archive.add_json("templates.json", &templates)?;
+    archive.add_json("programs.json", &programs)?;
+    archive.add_csv("program_enrollments.csv", &enrollments)?;
+    archive.add_csv("training_maxes.csv", &training_maxes)?;
+    archive.add_csv("progression_recommendations.csv", &recommendations)?;
+    archive.add_csv("personal_records.csv", &personal_records)?;
+    archive.add_csv("body_measurements.csv", &measurements)?;
+    archive.add_json("goals.json", &goals)?;
+    archive.add_json("notifications.json", &notifications)?;
+    archive.add_csv("achievements.csv", &achievements)?;
+    archive.add_json("schedules.json", &schedules)?;
+    archive.add_csv("scheduled_sessions.csv", &scheduled_sessions)?;
+    archive.add_csv("activities.csv", &activities)?;
+    archive.add_csv("activity_laps.csv", &activity_laps)?;
+    for (activity_id, gpx) in &activity_tracks {
+        archive.add_file(&format!("activities/{}.gpx", activity_id), gpx.as_bytes())?;
+    }
+    archive.add_json("interval_workouts.json", &interval_workouts)?;
+    archive.add_json("interval_sessions.json", &interval_sessions)?;
+    archive.add_json("custom_foods.json", &custom_foods)?;
+    archive.add_json("meals.json", &meals)?;
+    archive.add_csv("wellness_logs.csv", &wellness_logs)?;
+    archive.add_csv("follows.csv", &follows)?;
+    archive.add_csv("blocks.csv", &blocks)?;
+    archive.add_csv(
+        "strength_imports.csv",
+        &strength_imports
+            .iter()
+            .map(|i| StrengthImportRecord {
+                id: i.id,
+                source: i.source.clone(),
+                units: i.units.clone(),
+                created_at: i.created_at,
+            })
+            .collect::<Vec<_>>(),
+    )?;
+    for import in &strength_imports {
+        archive.add_file(&format!("strength_imports/{}.csv", import.id), &import.data)?;
+    }
+    archive.add_csv("strength_import_mappings.csv", &strength_import_mappings)?;
+    archive.add_csv(
+        "calendar_feeds.csv",
+        &calendar_feeds
+            .into_iter()
+            .map(CalendarFeedResponse::from)
+            .collect::<Vec<_>>(),
+    )?;
+    archive.add_csv(
+        "media.csv",
+        &media.iter().map(MediaRecord::from).collect::<Vec<_>>(),
+    )?;
+    for item in &media {
+        if let Some(data) = state.storage.get(&item.storage_key).await? {
+            let extension = item.storage_key.rsplit('.').next().unwrap_or("bin");
+            archive.add_file(&format!("media/{}.{}", item.id, extension), &data)?;
+        }
+    }
+    archive.finish()
+}
+
+/// Generates the archive for a pending export and emails the download link.
+/// Runs in the background, so failures are recorded on the export instead of returned.
+pub async fn process_export(state: AppState, export_id: Uuid, user_id: Uuid, email: String) {
+    let archive = match build_archive(&state, user_id).await {
+        Ok(archive) => archive,
+        Err(e) => {
+            tracing::error!("Failed to build data export {}: {:?}", export_id, e);
+            if let Err(e) = sqlx::query!(
+                "UPDATE data_exports SET status = $1, completed_at = NOW() WHERE id = $2",
+                EXPORT_STATUS_FAILED,
+                export_id
+            )
+            .execute(&state.pool)
+            .await
+            {
+                tracing::error!(
+                    "Failed to mark data export {} as failed: {:?}",
+                    export_id,
+                    e
+                );
+            }
+            return;
+        }
+    };
+
+    let token = Uuid::new_v4().to_string();
+    let expires_at = chrono::Utc::now() + chrono::Duration::hours(DOWNLOAD_LINK_VALIDITY_HOURS);
+
+    if let Err(e) = sqlx::query!(
+        "UPDATE data_exports SET status = $1, archive = $2, download_token = $3, expires_at = $4, completed_at = NOW() WHERE id = $5",
+        EXPORT_STATUS_READY,
+        archive,
+        token,
+        expires_at,
+        export_id
+    )
+    .execute(&state.pool)
+    .await
+    {
+        tracing::error!("Failed to store data export {}: {:?}", export_id, e);
+        return;
+    }
+
+    if let Err(e) = state.email_service.send_data_export_email(&email, &token) {
+        tracing::error!("Failed to send data export email: {:?}", e);
+    }
+}
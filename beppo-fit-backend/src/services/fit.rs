@@ -0,0 +1,312 @@
+//! Decoder for the parts of Garmin FIT activity files we import: sessions
+//! (sport), laps and records. Other messages and developer fields are skipped.
+
+use chrono::{DateTime, Utc};
+
+use crate::models::activity::{
+    TrackPoint, ACTIVITY_RIDE, ACTIVITY_ROW, ACTIVITY_RUN, ACTIVITY_SWIM, ACTIVITY_WALK,
+};
+use crate::services::activity_import::{ParsedActivity, ParsedLap};
+
+/// Seconds between the Unix epoch and the FIT epoch (1989-12-31T00:00:00Z).
+const FIT_EPOCH_OFFSET: i64 = 631_065_600;
+const SEMICIRCLES_TO_DEGREES: f64 = 180.0 / 2_147_483_648.0;
+
+const MESG_SESSION: u16 = 18;
+const MESG_LAP: u16 = 19;
+const MESG_RECORD: u16 = 20;
+const FIELD_TIMESTAMP: u8 = 253;
+
+#[derive(Debug, Clone)]
+struct FieldDefinition {
+    number: u8,
+    size: usize,
+    base_type: u8,
+}
+
+#[derive(Debug, Clone)]
+struct Definition {
+    big_endian: bool,
+    global: u16,
+    fields: Vec<FieldDefinition>,
+    /// Total size of developer fields, which are skipped.
+    developer_size: usize,
+}
+
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let slice = self
+            .bytes
+            .get(self.pos..self.pos + len)
+            .ok_or("FIT file is truncated")?;
+        self.pos += len;
+        Ok(slice)
+    }
+
+    fn byte(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+}
+
+/// Integer value of a field, `None` for the type's invalid marker or types
+/// we do not read.
+fn field_value(bytes: &[u8], base_type: u8, big_endian: bool) -> Option<i64> {
+    let unsigned = |bytes: &[u8]| {
+        let mut value = 0u64;
+        for i in 0..bytes.len() {
+            let byte = if big_endian {
+                bytes[i]
+            } else {
+                bytes[bytes.len() - 1 - i]
+            };
+            value = (value << 8) | byte as u64;
+        }
+        value
+    };
+    let raw = unsigned(bytes);
+    match (base_type & 0x1F, bytes.len()) {
+        // enum, uint8
+        (0x00 | 0x02, 1) => (raw != 0xFF).then_some(raw as i64),
+        (0x01, 1) => (raw != 0x7F).then_some(raw as u8 as i8 as i64),
+        (0x03, 2) => (raw != 0x7FFF).then_some(raw as u16 as i16 as i64),
+        (0x04, 2) => (raw != 0xFFFF).then_some(raw as i64),
+        (0x05, 4) => (raw != 0x7FFF_FFFF).then_some(raw as u32 as i32 as i64),
+        (0x06, 4) => (raw != 0xFFFF_FFFF).then_some(raw as i64),
+        // uint8z, uint16z, uint32z
+        (0x0A, 1) | (0x0B, 2) => (raw != 0).then_some(raw as i64),
+        (0x0C, 4) => (raw != 0).then_some(raw as i64),
+        _ => None,
+    }
+}
+
+fn fit_time(timestamp: i64) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp(timestamp + FIT_EPOCH_OFFSET, 0)
+}
+
+fn sport_type(sport: i64) -> Option<&'static str> {
+    match sport {
+        1 => Some(ACTIVITY_RUN),
+        2 => Some(ACTIVITY_RIDE),
+        5 => Some(ACTIVITY_SWIM),
+        11 | 17 => Some(ACTIVITY_WALK),
+        15 => Some(ACTIVITY_ROW),
+        _ => None,
+    }
+}
+
+pub fn is_fit(bytes: &[u8]) -> bool {
+    bytes.get(8..12) == Some(b".FIT")
+}
+
+pub fn parse_fit(bytes: &[u8]) -> Result<ParsedActivity, String> {
+    if !is_fit(bytes) {
+        return Err("Not a FIT file".into());
+    }
+    let header_size = bytes[0] as usize;
+    let data_size = u32::from_le_bytes(bytes[4..8].try_into().unwrap()) as usize;
+    let data = bytes
+        .get(header_size..header_size + data_size)
+        .ok_or("FIT file is truncated")?;
+
+    let mut cursor = Cursor {
+        bytes: data,
+        pos: 0,
+    };
+    let mut definitions: [Option<Definition>; 16] = Default::default();
+    let mut last_timestamp: Option<i64> = None;
+    let mut activity = ParsedActivity::default();
+
+    while cursor.pos < data.len() {
+        let header = cursor.byte()?;
+        let (local, compressed_time) = if header & 0x80 != 0 {
+            // Compressed timestamp header: a 5-bit offset from the last timestamp
+            let offset = (header & 0x1F) as i64;
+            let last = last_timestamp.ok_or("FIT compressed timestamp without a reference")?;
+            let mut timestamp = (last & !0x1F) + offset;
+            if offset < (last & 0x1F) {
+                timestamp += 0x20;
+            }
+            (((header >> 5) & 0x03) as usize, Some(timestamp))
+        } else if header & 0x40 != 0 {
+            let local = (header & 0x0F) as usize;
+            cursor.byte()?;
+            let big_endian = cursor.byte()? == 1;
+            let global = cursor.take(2)?;
+            let global = if big_endian {
+                u16::from_be_bytes([global[0], global[1]])
+            } else {
+                u16::from_le_bytes([global[0], global[1]])
+            };
+            let field_count = cursor.byte()?;
+            let mut fields = Vec::with_capacity(field_count as usize);
+            for _ in 0..field_count {
+                let field = cursor.take(3)?;
+                fields.push(FieldDefinition {
+                    number: field[0],
+                    size: field[1] as usize,
+                    base_type: field[2],
+                });
+            }
+            let mut developer_size = 0;
+            if header & 0x20 != 0 {
+                for _ in 0..cursor.byte()? {
+                    developer_size += cursor.take(3)?[1] as usize;
+                }
+            }
+            definitions[local] = Some(Definition {
+                big_endian,
+                global,
+                fields,
+                developer_size,
+            });
+            continue;
+        } else {
+            ((header & 0x0F) as usize, None)
+        };
+
+        let definition = definitions[local]
+            .as_ref()
+            .ok_or("FIT data message without a definition")?;
+        let mut values = Vec::with_capacity(definition.fields.len());
+        for field in &definition.fields {
+            let raw = cursor.take(field.size)?;
+            values.push((
+                field.number,
+                field_value(raw, field.base_type, definition.big_endian),
+            ));
+        }
+        cursor.take(definition.developer_size)?;
+
+        let value = |number: u8| {
+            values
+                .iter()
+                .find(|(n, _)| *n == number)
+                .and_then(|(_, v)| *v)
+        };
+        if let Some(timestamp) = value(FIELD_TIMESTAMP).or(compressed_time) {
+            last_timestamp = Some(timestamp);
+        }
+        let timestamp = value(FIELD_TIMESTAMP).or(compressed_time);
+
+        match definition.global {
+            MESG_SESSION => {
+                if let Some(sport) = value(5).and_then(sport_type) {
+                    activity.sport = Some(sport);
+                }
+            }
+            MESG_LAP => {
+                let Some(started_at) = value(2).or(timestamp).and_then(fit_time) else {
+                    continue;
+                };
+                activity.laps.push(ParsedLap {
+                    started_at,
+                    duration_seconds: value(7).map_or(0.0, |ms| ms as f64 / 1000.0),
+                    distance_m: value(9).map(|cm| cm as f64 / 100.0),
+                    avg_heart_rate: value(15).map(|hr| hr as i32),
+                });
+            }
+            MESG_RECORD => {
+                let Some(time) = timestamp.and_then(fit_time) else {
+                    continue;
+                };
+                let position = value(0).zip(value(1)).map(|(lat, lon)| {
+                    (
+                        lat as f64 * SEMICIRCLES_TO_DEGREES,
+                        lon as f64 * SEMICIRCLES_TO_DEGREES,
+                    )
+                });
+                activity.points.push(TrackPoint {
+                    time,
+                    lat: position.map(|p| p.0),
+                    lon: position.map(|p| p.1),
+                    // Enhanced altitude (78) supersedes the 16-bit field (2)
+                    elevation_m: value(78).or(value(2)).map(|alt| alt as f64 / 5.0 - 500.0),
+                    distance_m: value(5).map(|cm| cm as f64 / 100.0),
+                    heart_rate: value(3).map(|hr| hr as i32),
+                    cadence: value(4).map(|c| c as i32),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    Ok(activity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes a minimal FIT file: a running session and one record per
+    /// (seconds since FIT epoch, lat, lon, heart rate) sample.
+    fn fit_file(records: &[(u32, f64, f64, u8)]) -> Vec<u8> {
+        let degrees = |d: f64| ((d / SEMICIRCLES_TO_DEGREES).round() as i32).to_le_bytes();
+        let mut data = vec![
+            // Definition, local 0: session with sport (enum)
+            0x40, 0, 0, 18, 0, 1, 5, 1, 0x00, // Data: running
+            0x00, 1,
+            // Definition, local 1, with one developer field: record with
+            // timestamp, lat, lon, heart rate
+            0x61, 0, 0, 20, 0, 4, 253, 4, 0x86, 0, 4, 0x85, 1, 4, 0x85, 3, 1, 0x02, 1, 0, 2, 0,
+        ];
+        for (i, (timestamp, lat, lon, heart_rate)) in records.iter().enumerate() {
+            if i > 0 && timestamp - records[i - 1].0 < 16 {
+                // Exercise the compressed timestamp header for later samples,
+                // which carry the timestamp field as invalid instead
+                data.push(0x80 | 0x20 | (timestamp & 0x1F) as u8);
+                data.extend_from_slice(&0xFFFF_FFFFu32.to_le_bytes());
+            } else {
+                data.push(0x01);
+                data.extend_from_slice(&timestamp.to_le_bytes());
+            }
+            data.extend_from_slice(&degrees(*lat));
+            data.extend_from_slice(&degrees(*lon));
+            data.push(*heart_rate);
+            data.extend_from_slice(&[0xAB, 0xCD]);
+        }
+
+        let mut file = vec![12, 0x10, 0, 0];
+        file.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        file.extend_from_slice(b".FIT");
+        file.extend_from_slice(&data);
+        // CRC, which we do not check
+        file.extend_from_slice(&[0, 0]);
+        file
+    }
+
+    #[test]
+    fn decodes_records_with_compressed_timestamps() {
+        let start = 1_100_000_000;
+        let file = fit_file(&[
+            (start, 52.5, 13.4, 120),
+            (start + 5, 52.5001, 13.4, 130),
+            (start + 40, 52.5002, 13.4, 0xFF),
+        ]);
+        let activity = parse_fit(&file).unwrap();
+
+        assert_eq!(activity.sport, Some(ACTIVITY_RUN));
+        assert_eq!(activity.points.len(), 3);
+        assert_eq!(
+            activity.points[1].time.timestamp(),
+            start as i64 + 5 + FIT_EPOCH_OFFSET
+        );
+        assert!((activity.points[1].lat.unwrap() - 52.5001).abs() < 1e-6);
+        assert_eq!(activity.points[1].heart_rate, Some(130));
+        // 0xFF is the invalid marker for uint8
+        assert_eq!(activity.points[2].heart_rate, None);
+    }
+
+    #[test]
+    fn rejects_truncated_files() {
+        let file = fit_file(&[(1_100_000_000, 52.5, 13.4, 120)]);
+        let mut truncated = file[..file.len() - 6].to_vec();
+        let data_size = (truncated.len() - 12) as u32;
+        truncated[4..8].copy_from_slice(&data_size.to_le_bytes());
+        assert!(parse_fit(&truncated).is_err());
+    }
+}
@@ -0,0 +1,568 @@
+//! Seeds the shared food catalog from dump files on local disk, so no network
+//! access is needed at import time.
+//!
+//! * Open Food Facts: the tab-separated product export
+//!   (`en.openfoodfacts.org.products.csv`, decompressed).
+//! * USDA FoodData Central: a directory holding the CSV download, i.e.
+//!   `food.csv`, `food_nutrient.csv` and optionally `branded_food.csv`.
+//!
+//! Rows are upserted by their id in the source, so re-running an import with a
+//! newer dump updates the existing catalog entries.
+
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::{collections::HashMap, fs::File, io::Read, path::Path};
+
+use crate::models::nutrition::{Nutrients, SOURCE_OPEN_FOOD_FACTS, SOURCE_USDA};
+
+/// Rows written per upsert statement.
+const BATCH_SIZE: usize = 1000;
+const KJ_PER_KCAL: f64 = 4.184;
+
+#[derive(Debug)]
+pub enum FoodImportError {
+    Io(std::io::Error),
+    Csv(csv::Error),
+    Database(sqlx::Error),
+}
+
+impl std::fmt::Display for FoodImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FoodImportError::Io(e) => write!(f, "failed to read dump: {}", e),
+            FoodImportError::Csv(e) => write!(f, "malformed dump: {}", e),
+            FoodImportError::Database(e) => write!(f, "failed to store foods: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for FoodImportError {}
+
+impl From<std::io::Error> for FoodImportError {
+    fn from(e: std::io::Error) -> Self {
+        FoodImportError::Io(e)
+    }
+}
+
+impl From<csv::Error> for FoodImportError {
+    fn from(e: csv::Error) -> Self {
+        FoodImportError::Csv(e)
+    }
+}
+
+impl From<sqlx::Error> for FoodImportError {
+    fn from(e: sqlx::Error) -> Self {
+        FoodImportError::Database(e)
+    }
+}
+
+/// A catalog entry read from a dump; nutrients are per 100 g.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedFood {
+    pub source_id: String,
+    pub name: String,
+    pub brand: Option<String>,
+    pub barcode: Option<String>,
+    pub serving_size_g: Option<f64>,
+    pub nutrients: Nutrients,
+}
+
+#[derive(Debug, Default)]
+pub struct FoodImportSummary {
+    pub imported: usize,
+    /// Rows without a name or an energy value.
+    pub skipped: usize,
+}
+
+fn text(value: Option<&str>, max_chars: usize) -> Option<String> {
+    let value = value?.trim();
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.chars().take(max_chars).collect())
+    }
+}
+
+fn number(value: Option<&str>) -> Option<f64> {
+    value
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .filter(|v| v.is_finite() && *v >= 0.0)
+}
+
+fn barcode(value: Option<&str>) -> Option<String> {
+    let value = value?.trim();
+    ((8..=14).contains(&value.len()) && value.bytes().all(|b| b.is_ascii_digit()))
+        .then(|| value.to_string())
+}
+
+/// Parses the Open Food Facts product export. The export is not valid quoted
+/// CSV, so fields are split on tabs only.
+pub fn read_open_food_facts<R: Read>(
+    reader: R,
+) -> Result<impl Iterator<Item = Result<Option<ImportedFood>, csv::Error>>, csv::Error> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(b'\t')
+        .quoting(false)
+        .flexible(true)
+        .from_reader(reader);
+
+    let headers = reader.headers()?.clone();
+    let column = |name: &str| headers.iter().position(|h| h == name);
+    let columns = OffColumns {
+        code: column("code"),
+        name: column("product_name"),
+        brands: column("brands"),
+        serving_quantity: column("serving_quantity"),
+        energy_kcal: column("energy-kcal_100g"),
+        energy_kj: column("energy_100g"),
+        protein: column("proteins_100g"),
+        carbohydrate: column("carbohydrates_100g"),
+        fat: column("fat_100g"),
+        fiber: column("fiber_100g"),
+        sugar: column("sugars_100g"),
+        saturated_fat: column("saturated-fat_100g"),
+        sodium: column("sodium_100g"),
+        potassium: column("potassium_100g"),
+        calcium: column("calcium_100g"),
+        iron: column("iron_100g"),
+        vitamin_c: column("vitamin-c_100g"),
+    };
+
+    Ok(reader
+        .into_records()
+        .map(move |record| record.map(|r| columns.food(&r))))
+}
+
+struct OffColumns {
+    code: Option<usize>,
+    name: Option<usize>,
+    brands: Option<usize>,
+    serving_quantity: Option<usize>,
+    energy_kcal: Option<usize>,
+    energy_kj: Option<usize>,
+    protein: Option<usize>,
+    carbohydrate: Option<usize>,
+    fat: Option<usize>,
+    fiber: Option<usize>,
+    sugar: Option<usize>,
+    saturated_fat: Option<usize>,
+    sodium: Option<usize>,
+    potassium: Option<usize>,
+    calcium: Option<usize>,
+    iron: Option<usize>,
+    vitamin_c: Option<usize>,
+}
+
+impl OffColumns {
+    fn food(&self, record: &csv::StringRecord) -> Option<ImportedFood> {
+        let field = |column: Option<usize>| column.and_then(|c| record.get(c));
+        let grams = |column| number(field(column));
+        // Minerals and vitamins are exported in g per 100 g
+        let milligrams = |column| grams(column).map(|g| g * 1000.0);
+
+        let code = text(field(self.code), 40)?;
+        let name = text(field(self.name), 200)?;
+        let energy_kcal = grams(self.energy_kcal)
+            .or_else(|| grams(self.energy_kj).map(|kj| kj / KJ_PER_KCAL))
+            .filter(|kcal| *kcal <= 900.0)?;
+
+        Some(ImportedFood {
+            barcode: barcode(Some(&code)),
+            source_id: code,
+            name,
+            // Brands are a comma-separated list; the first is the owner
+            brand: text(field(self.brands).and_then(|b| b.split(',').next()), 200),
+            serving_size_g: grams(self.serving_quantity).filter(|g| *g > 0.0 && *g <= 5000.0),
+            nutrients: Nutrients {
+                energy_kcal: (energy_kcal * 10.0).round() / 10.0,
+                protein_g: grams(self.protein),
+                carbohydrate_g: grams(self.carbohydrate),
+                fat_g: grams(self.fat),
+                fiber_g: grams(self.fiber),
+                sugar_g: grams(self.sugar),
+                saturated_fat_g: grams(self.saturated_fat),
+                sodium_mg: milligrams(self.sodium),
+                potassium_mg: milligrams(self.potassium),
+                calcium_mg: milligrams(self.calcium),
+                iron_mg: milligrams(self.iron),
+                vitamin_c_mg: milligrams(self.vitamin_c),
+            },
+        })
+    }
+}
+
+// FoodData Central nutrient ids.
+const USDA_ENERGY_KCAL: i64 = 1008;
+/// Atwater general and specific factors, listed instead of 1008 for newer foods.
+const USDA_ENERGY_ATWATER: &[i64] = &[2047, 2048];
+const USDA_PROTEIN: i64 = 1003;
+const USDA_FAT: i64 = 1004;
+const USDA_CARBOHYDRATE: i64 = 1005;
+const USDA_FIBER: i64 = 1079;
+const USDA_SUGAR: i64 = 2000;
+const USDA_SATURATED_FAT: i64 = 1258;
+const USDA_CALCIUM: i64 = 1087;
+const USDA_IRON: i64 = 1089;
+const USDA_POTASSIUM: i64 = 1092;
+const USDA_SODIUM: i64 = 1093;
+const USDA_VITAMIN_C: i64 = 1162;
+
+#[derive(Deserialize)]
+struct UsdaFoodRow {
+    fdc_id: i64,
+    description: String,
+}
+
+#[derive(Deserialize)]
+struct UsdaNutrientRow {
+    fdc_id: i64,
+    nutrient_id: i64,
+    amount: Option<f64>,
+}
+
+#[derive(Deserialize)]
+struct UsdaBrandedRow {
+    fdc_id: i64,
+    brand_owner: Option<String>,
+    brand_name: Option<String>,
+    gtin_upc: Option<String>,
+    serving_size: Option<f64>,
+    serving_size_unit: Option<String>,
+}
+
+#[derive(Default)]
+struct UsdaNutrients {
+    nutrients: Nutrients,
+    energy_kcal: Option<f64>,
+    atwater_kcal: Option<f64>,
+}
+
+struct UsdaBrand {
+    brand: Option<String>,
+    barcode: Option<String>,
+    serving_size_g: Option<f64>,
+}
+
+/// Collects the tracked nutrients per food. USDA amounts are already per 100 g
+/// and minerals are in mg, matching the catalog.
+fn read_usda_nutrients<R: Read>(reader: R) -> Result<HashMap<i64, UsdaNutrients>, csv::Error> {
+    let mut foods: HashMap<i64, UsdaNutrients> = HashMap::new();
+    for row in csv::Reader::from_reader(reader).deserialize() {
+        let row: UsdaNutrientRow = row?;
+        let value = row.amount.filter(|a| a.is_finite() && *a >= 0.0);
+        if value.is_none() {
+            continue;
+        }
+
+        let food = foods.entry(row.fdc_id).or_default();
+        let nutrients = &mut food.nutrients;
+        match row.nutrient_id {
+            USDA_ENERGY_KCAL => food.energy_kcal = value,
+            id if USDA_ENERGY_ATWATER.contains(&id) => {
+                food.atwater_kcal = food.atwater_kcal.or(value)
+            }
+            USDA_PROTEIN => nutrients.protein_g = value,
+            USDA_FAT => nutrients.fat_g = value,
+            USDA_CARBOHYDRATE => nutrients.carbohydrate_g = value,
+            USDA_FIBER => nutrients.fiber_g = value,
+            USDA_SUGAR => nutrients.sugar_g = value,
+            USDA_SATURATED_FAT => nutrients.saturated_fat_g = value,
+            USDA_CALCIUM => nutrients.calcium_mg = value,
+            USDA_IRON => nutrients.iron_mg = value,
+            USDA_POTASSIUM => nutrients.potassium_mg = value,
+            USDA_SODIUM => nutrients.sodium_mg = value,
+            USDA_VITAMIN_C => nutrients.vitamin_c_mg = value,
+            _ => {}
+        }
+    }
+    Ok(foods)
+}
+
+fn read_usda_brands<R: Read>(reader: R) -> Result<HashMap<i64, UsdaBrand>, csv::Error> {
+    let mut brands = HashMap::new();
+    for row in csv::Reader::from_reader(reader).deserialize() {
+        let row: UsdaBrandedRow = row?;
+        // Serving sizes in ml cannot be converted without the density
+        let in_grams = matches!(
+            row.serving_size_unit
+                .as_deref()
+                .map(str::to_ascii_lowercase)
+                .as_deref(),
+            Some("g" | "grm")
+        );
+        brands.insert(
+            row.fdc_id,
+            UsdaBrand {
+                brand: text(row.brand_name.as_deref(), 200)
+                    .or_else(|| text(row.brand_owner.as_deref(), 200)),
+                barcode: barcode(row.gtin_upc.as_deref()),
+                serving_size_g: row
+                    .serving_size
+                    .filter(|g| in_grams && *g > 0.0 && *g <= 5000.0),
+            },
+        );
+    }
+    Ok(brands)
+}
+
+/// Joins the FoodData Central CSV files. The nutrient and branded files are
+/// read up front; `food.csv` is streamed.
+pub fn read_usda<F: Read, N: Read, B: Read>(
+    food: F,
+    food_nutrient: N,
+    branded_food: Option<B>,
+) -> Result<impl Iterator<Item = Result<Option<ImportedFood>, csv::Error>>, csv::Error> {
+    let mut nutrients = read_usda_nutrients(food_nutrient)?;
+    let mut brands = match branded_food {
+        Some(reader) => read_usda_brands(reader)?,
+        None => HashMap::new(),
+    };
+
+    Ok(csv::Reader::from_reader(food)
+        .into_deserialize()
+        .map(move |row| {
+            let row: UsdaFoodRow = row?;
+            let Some(mut food) = nutrients.remove(&row.fdc_id) else {
+                return Ok(None);
+            };
+            let (Some(name), Some(energy_kcal)) = (
+                text(Some(&row.description), 200),
+                food.energy_kcal
+                    .or(food.atwater_kcal)
+                    .filter(|kcal| *kcal <= 900.0),
+            ) else {
+                return Ok(None);
+            };
+            food.nutrients.energy_kcal = energy_kcal;
+            let brand = brands.remove(&row.fdc_id);
+
+            Ok(Some(ImportedFood {
+                source_id: row.fdc_id.to_string(),
+                name,
+                brand: brand.as_ref().and_then(|b| b.brand.clone()),
+                barcode: brand.as_ref().and_then(|b| b.barcode.clone()),
+                serving_size_g: brand.and_then(|b| b.serving_size_g),
+                nutrients: food.nutrients,
+            }))
+        }))
+}
+
+/// Writes one batch; later rows win when a dump lists an id twice.
+async fn upsert_foods(
+    pool: &PgPool,
+    source: &str,
+    batch: &[ImportedFood],
+) -> Result<(), sqlx::Error> {
+    let mut by_id: HashMap<&str, &ImportedFood> = HashMap::new();
+    for food in batch {
+        by_id.insert(&food.source_id, food);
+    }
+    let foods: Vec<&ImportedFood> = by_id.into_values().collect();
+    let column = |f: fn(&Nutrients) -> Option<f64>| -> Vec<Option<f64>> {
+        foods.iter().map(|food| f(&food.nutrients)).collect()
+    };
+
+    sqlx::query!(
+        r#"
+        INSERT INTO foods (
+            source, source_id, name, brand, barcode, serving_size_g, energy_kcal,
+            protein_g, carbohydrate_g, fat_g, fiber_g, sugar_g, saturated_fat_g,
+            sodium_mg, potassium_mg, calcium_mg, iron_mg, vitamin_c_mg
+        )
+        SELECT $1, * FROM UNNEST(
+            $2::TEXT[], $3::TEXT[], $4::TEXT[], $5::TEXT[], $6::FLOAT8[], $7::FLOAT8[],
+            $8::FLOAT8[], $9::FLOAT8[], $10::FLOAT8[], $11::FLOAT8[], $12::FLOAT8[],
+            $13::FLOAT8[], $14::FLOAT8[], $15::FLOAT8[], $16::FLOAT8[], $17::FLOAT8[],
+            $18::FLOAT8[]
+        )
+        ON CONFLICT (source, source_id) WHERE source_id IS NOT NULL DO UPDATE SET
+            name = EXCLUDED.name,
+            brand = EXCLUDED.brand,
+            barcode = EXCLUDED.barcode,
+            serving_size_g = EXCLUDED.serving_size_g,
+            energy_kcal = EXCLUDED.energy_kcal,
+            protein_g = EXCLUDED.protein_g,
+            carbohydrate_g = EXCLUDED.carbohydrate_g,
+            fat_g = EXCLUDED.fat_g,
+            fiber_g = EXCLUDED.fiber_g,
+            sugar_g = EXCLUDED.sugar_g,
+            saturated_fat_g = EXCLUDED.saturated_fat_g,
+            sodium_mg = EXCLUDED.sodium_mg,
+            potassium_mg = EXCLUDED.potassium_mg,
+            calcium_mg = EXCLUDED.calcium_mg,
+            iron_mg = EXCLUDED.iron_mg,
+            vitamin_c_mg = EXCLUDED.vitamin_c_mg,
+            updated_at = NOW()
+        "#,
+        source,
+        &foods
+            .iter()
+            .map(|f| f.source_id.clone())
+            .collect::<Vec<_>>(),
+        &foods.iter().map(|f| f.name.clone()).collect::<Vec<_>>(),
+        &foods.iter().map(|f| f.brand.clone()).collect::<Vec<_>>() as _,
+        &foods.iter().map(|f| f.barcode.clone()).collect::<Vec<_>>() as _,
+        &foods.iter().map(|f| f.serving_size_g).collect::<Vec<_>>() as _,
+        &foods
+            .iter()
+            .map(|f| f.nutrients.energy_kcal)
+            .collect::<Vec<_>>(),
+        &column(|n| n.protein_g) as _,
+        &column(|n| n.carbohydrate_g) as _,
+        &column(|n| n.fat_g) as _,
+        &column(|n| n.fiber_g) as _,
+        &column(|n| n.sugar_g) as _,
+        &column(|n| n.saturated_fat_g) as _,
+        &column(|n| n.sodium_mg) as _,
+        &column(|n| n.potassium_mg) as _,
+        &column(|n| n.calcium_mg) as _,
+        &column(|n| n.iron_mg) as _,
+        &column(|n| n.vitamin_c_mg) as _
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Upserts parsed rows into the shared catalog in batches. Reading happens on
+/// the calling task, so this is meant for the `import_foods` binary rather
+/// than request handlers.
+pub async fn import_foods(
+    pool: &PgPool,
+    source: &str,
+    rows: impl Iterator<Item = Result<Option<ImportedFood>, csv::Error>>,
+) -> Result<FoodImportSummary, FoodImportError> {
+    let mut summary = FoodImportSummary::default();
+    let mut batch = Vec::with_capacity(BATCH_SIZE);
+    for row in rows {
+        match row? {
+            Some(food) => batch.push(food),
+            None => summary.skipped += 1,
+        }
+        if batch.len() == BATCH_SIZE {
+            upsert_foods(pool, source, &batch).await?;
+            summary.imported += batch.len();
+            batch.clear();
+            tracing::info!("Imported {} foods", summary.imported);
+        }
+    }
+    if !batch.is_empty() {
+        upsert_foods(pool, source, &batch).await?;
+        summary.imported += batch.len();
+    }
+    Ok(summary)
+}
+
+pub async fn import_open_food_facts(
+    pool: &PgPool,
+    path: &Path,
+) -> Result<FoodImportSummary, FoodImportError> {
+    let rows = read_open_food_facts(File::open(path)?)?;
+    import_foods(pool, SOURCE_OPEN_FOOD_FACTS, rows).await
+}
+
+pub async fn import_usda(pool: &PgPool, dir: &Path) -> Result<FoodImportSummary, FoodImportError> {
+    let branded_path = dir.join("branded_food.csv");
+    let branded = if branded_path.exists() {
+        Some(File::open(branded_path)?)
+    } else {
+        None
+    };
+    let rows = read_usda(
+        File::open(dir.join("food.csv"))?,
+        File::open(dir.join("food_nutrient.csv"))?,
+        branded,
+    )?;
+    import_foods(pool, SOURCE_USDA, rows).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(
+        rows: impl Iterator<Item = Result<Option<ImportedFood>, csv::Error>>,
+    ) -> Vec<Option<ImportedFood>> {
+        rows.map(Result::unwrap).collect()
+    }
+
+    #[test]
+    fn reads_open_food_facts_export() {
+        let dump = "code\tproduct_name\tbrands\tserving_quantity\tenergy-kcal_100g\tenergy_100g\tproteins_100g\tcarbohydrates_100g\tfat_100g\tsodium_100g\tvitamin-c_100g\n\
+            3017620422003\tNutella\tFerrero,Nutella\t15\t539\t2252\t6.3\t57.5\t30.9\t0.0428\t\n\
+            0000000000123\tOat \"drink\"\t\t\t\t1966\t1\t6.5\t1.5\t\t0.0012\n\
+            42\t\t\t\t100\t\t\t\t\t\t\n\
+            43\tNo energy\t\t\t\t\t1\t\t\t\t\n";
+        let foods = collect(read_open_food_facts(dump.as_bytes()).unwrap());
+        assert_eq!(foods.len(), 4);
+
+        let nutella = foods[0].as_ref().unwrap();
+        assert_eq!(nutella.source_id, "3017620422003");
+        assert_eq!(nutella.barcode.as_deref(), Some("3017620422003"));
+        assert_eq!(nutella.brand.as_deref(), Some("Ferrero"));
+        assert_eq!(nutella.serving_size_g, Some(15.0));
+        assert_eq!(nutella.nutrients.energy_kcal, 539.0);
+        assert_eq!(nutella.nutrients.fat_g, Some(30.9));
+        assert!((nutella.nutrients.sodium_mg.unwrap() - 42.8).abs() < 1e-9);
+        assert_eq!(nutella.nutrients.vitamin_c_mg, None);
+
+        // Unquoted exports keep stray quotes; energy falls back to kJ
+        let oat = foods[1].as_ref().unwrap();
+        assert_eq!(oat.name, "Oat \"drink\"");
+        assert_eq!(oat.nutrients.energy_kcal, 469.9);
+        assert!((oat.nutrients.vitamin_c_mg.unwrap() - 1.2).abs() < 1e-9);
+        assert_eq!(oat.brand, None);
+
+        // Nameless rows and rows without energy are skipped
+        assert!(foods[2].is_none());
+        assert!(foods[3].is_none());
+    }
+
+    #[test]
+    fn joins_usda_csv_files() {
+        let food =
+            "\"fdc_id\",\"data_type\",\"description\",\"food_category_id\",\"publication_date\"\n\
+            \"171705\",\"sr_legacy_food\",\"Oats, rolled\",\"8\",\"2019-04-01\"\n\
+            \"2345678\",\"branded_food\",\"PEANUT BUTTER, CREAMY\",\"\",\"2022-01-01\"\n\
+            \"999\",\"foundation_food\",\"Water\",\"\",\"2022-01-01\"\n";
+        let nutrients = "\"id\",\"fdc_id\",\"nutrient_id\",\"amount\"\n\
+            \"1\",\"171705\",\"1008\",\"379\"\n\
+            \"2\",\"171705\",\"2047\",\"375\"\n\
+            \"3\",\"171705\",\"1003\",\"13.2\"\n\
+            \"4\",\"171705\",\"1093\",\"6\"\n\
+            \"5\",\"2345678\",\"2048\",\"588\"\n\
+            \"6\",\"2345678\",\"1004\",\"50\"\n\
+            \"7\",\"2345678\",\"1234\",\"1\"\n";
+        let branded = "\"fdc_id\",\"brand_owner\",\"brand_name\",\"gtin_upc\",\"serving_size\",\"serving_size_unit\"\n\
+            \"2345678\",\"Nut Co\",\"\",\"0051500255162\",\"32\",\"g\"\n";
+
+        let foods = collect(
+            read_usda(
+                food.as_bytes(),
+                nutrients.as_bytes(),
+                Some(branded.as_bytes()),
+            )
+            .unwrap(),
+        );
+        assert_eq!(foods.len(), 3);
+
+        let oats = foods[0].as_ref().unwrap();
+        assert_eq!(oats.source_id, "171705");
+        assert_eq!(oats.name, "Oats, rolled");
+        // 1008 wins over the Atwater values
+        assert_eq!(oats.nutrients.energy_kcal, 379.0);
+        assert_eq!(oats.nutrients.protein_g, Some(13.2));
+        assert_eq!(oats.nutrients.sodium_mg, Some(6.0));
+        assert_eq!(oats.barcode, None);
+
+        let peanut_butter = foods[1].as_ref().unwrap();
+        assert_eq!(peanut_butter.nutrients.energy_kcal, 588.0);
+        assert_eq!(peanut_butter.brand.as_deref(), Some("Nut Co"));
+        assert_eq!(peanut_butter.barcode.as_deref(), Some("0051500255162"));
+        assert_eq!(peanut_butter.serving_size_g, Some(32.0));
+
+        // No nutrient rows
+        assert!(foods[2].is_none());
+    }
+}
@@ -0,0 +1,126 @@
+use chrono::{Datelike, Duration};
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    models::{
+        goal::{Goal, GOAL_BODYWEIGHT, GOAL_FREQUENCY, GOAL_STRENGTH},
+        notification::NOTIFICATION_GOAL_COMPLETED,
+        profile::UserProfile,
+    },
+    services::{notification::notify, profile::ensure_profile},
+};
+
+/// Where a goal stands according to the logged data. Custom goals are
+/// tracked by hand and keep their stored value.
+pub async fn measure_goal(
+    conn: &mut PgConnection,
+    profile: &UserProfile,
+    goal: &Goal,
+) -> Result<Option<f64>, AppError> {
+    let value = match goal.goal_type.as_str() {
+        GOAL_STRENGTH => {
+            sqlx::query_scalar!(
+                r#"
+                SELECT MAX(value) FROM personal_records
+                WHERE user_id = $1 AND exercise_id = $2 AND record_type = $3
+                "#,
+                goal.user_id,
+                goal.exercise_id,
+                goal.metric
+            )
+            .fetch_one(&mut *conn)
+            .await?
+        }
+        GOAL_BODYWEIGHT => {
+            sqlx::query_scalar!(
+                r#"
+                SELECT weight_kg AS "weight_kg!" FROM body_measurements
+                WHERE user_id = $1 AND weight_kg IS NOT NULL
+                ORDER BY measured_at DESC
+                LIMIT 1
+                "#,
+                goal.user_id
+            )
+            .fetch_optional(&mut *conn)
+            .await?
+        }
+        GOAL_FREQUENCY => {
+            let today = profile.today();
+            let monday = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+            let count = sqlx::query_scalar!(
+                r#"
+                SELECT COUNT(*) AS "count!" FROM workouts
+                WHERE user_id = $1 AND finished_at IS NOT NULL AND started_at >= $2
+                "#,
+                goal.user_id,
+                profile.start_of_day(monday)
+            )
+            .fetch_one(&mut *conn)
+            .await?;
+            Some(count as f64)
+        }
+        _ => goal.current_value,
+    };
+
+    Ok(value)
+}
+
+/// Recomputes the progress of the user's open goals and completes the ones
+/// that were reached, notifying the user. Returns all of the user's goals.
+pub async fn refresh_goals(conn: &mut PgConnection, user_id: Uuid) -> Result<Vec<Goal>, AppError> {
+    let profile = ensure_profile(&mut *conn, user_id).await?;
+    let goals = sqlx::query_as!(
+        Goal,
+        "SELECT * FROM goals WHERE user_id = $1 ORDER BY created_at",
+        user_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut refreshed = Vec::with_capacity(goals.len());
+    for mut goal in goals {
+        if goal.completed_at.is_none() {
+            let previous = goal.current_value;
+            goal.current_value = measure_goal(conn, &profile, &goal).await?;
+            let reached = goal.is_reached();
+            if !reached && goal.current_value == previous {
+                refreshed.push(goal);
+                continue;
+            }
+
+            goal = sqlx::query_as!(
+                Goal,
+                r#"
+                UPDATE goals SET
+                    current_value = $2,
+                    completed_at = CASE WHEN $3 THEN NOW() ELSE NULL END,
+                    updated_at = NOW()
+                WHERE id = $1
+                RETURNING *
+                "#,
+                goal.id,
+                goal.current_value,
+                reached
+            )
+            .fetch_one(&mut *conn)
+            .await?;
+
+            if reached {
+                notify(
+                    &mut *conn,
+                    user_id,
+                    NOTIFICATION_GOAL_COMPLETED,
+                    &format!("Goal reached: {}", goal.title),
+                    None,
+                    Some(goal.id),
+                )
+                .await?;
+            }
+        }
+        refreshed.push(goal);
+    }
+
+    Ok(refreshed)
+}
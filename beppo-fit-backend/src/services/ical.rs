@@ -0,0 +1,177 @@
+//! Minimal RFC 5545 writer for calendar feeds.
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+pub const ICS_CONTENT_TYPE: &str = "text/calendar; charset=utf-8";
+const PRODUCT_ID: &str = "-//BeppoFit//Workout Calendar//EN";
+const MAX_LINE_OCTETS: usize = 75;
+
+#[derive(Debug, Clone)]
+pub enum EventTime {
+    /// A whole day, for sessions without a planned time.
+    AllDay(NaiveDate),
+    /// Fixed instants; written in UTC so no VTIMEZONE definitions are needed.
+    Timed {
+        start: DateTime<Utc>,
+        duration: Duration,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct IcsEvent {
+    pub uid: String,
+    pub summary: String,
+    pub description: Option<String>,
+    pub time: EventTime,
+    /// RFC 5545 status, e.g. `CANCELLED` for skipped sessions.
+    pub status: Option<&'static str>,
+}
+
+/// Escapes a TEXT value.
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Writes a content line, folding it at 75 octets without splitting characters.
+fn push_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > MAX_LINE_OCTETS {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+fn format_utc(instant: DateTime<Utc>) -> String {
+    instant.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn format_date(date: NaiveDate) -> String {
+    date.format("%Y%m%d").to_string()
+}
+
+/// Renders a VCALENDAR. `timezone` is only a display hint for clients.
+pub fn render_calendar(name: &str, timezone: &str, events: &[IcsEvent]) -> String {
+    let stamp = format_utc(Utc::now());
+    let mut out = String::new();
+    push_line(&mut out, "BEGIN:VCALENDAR");
+    push_line(&mut out, "VERSION:2.0");
+    push_line(&mut out, &format!("PRODID:{}", PRODUCT_ID));
+    push_line(&mut out, "CALSCALE:GREGORIAN");
+    push_line(&mut out, "METHOD:PUBLISH");
+    push_line(&mut out, &format!("X-WR-CALNAME:{}", escape_text(name)));
+    push_line(&mut out, &format!("X-WR-TIMEZONE:{}", timezone));
+
+    for event in events {
+        push_line(&mut out, "BEGIN:VEVENT");
+        push_line(&mut out, &format!("UID:{}", event.uid));
+        push_line(&mut out, &format!("DTSTAMP:{}", stamp));
+        match &event.time {
+            EventTime::AllDay(date) => {
+                push_line(
+                    &mut out,
+                    &format!("DTSTART;VALUE=DATE:{}", format_date(*date)),
+                );
+                push_line(
+                    &mut out,
+                    &format!(
+                        "DTEND;VALUE=DATE:{}",
+                        format_date(*date + Duration::days(1))
+                    ),
+                );
+            }
+            EventTime::Timed { start, duration } => {
+                push_line(&mut out, &format!("DTSTART:{}", format_utc(*start)));
+                push_line(
+                    &mut out,
+                    &format!("DTEND:{}", format_utc(*start + *duration)),
+                );
+            }
+        }
+        push_line(
+            &mut out,
+            &format!("SUMMARY:{}", escape_text(&event.summary)),
+        );
+        if let Some(description) = &event.description {
+            push_line(
+                &mut out,
+                &format!("DESCRIPTION:{}", escape_text(description)),
+            );
+        }
+        if let Some(status) = event.status {
+            push_line(&mut out, &format!("STATUS:{}", status));
+        }
+        push_line(&mut out, "END:VEVENT");
+    }
+
+    push_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_and_folds_lines() {
+        assert_eq!(
+            escape_text("Legs; squat, lunge\nA\\B"),
+            "Legs\\; squat\\, lunge\\nA\\\\B"
+        );
+
+        let mut out = String::new();
+        push_line(&mut out, &format!("SUMMARY:{}", "é".repeat(40)));
+        let lines: Vec<&str> = out.trim_end().split("\r\n").collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines.iter().all(|l| l.len() <= MAX_LINE_OCTETS));
+        assert!(lines[1].starts_with(' '));
+    }
+
+    #[test]
+    fn renders_all_day_and_timed_events() {
+        let calendar = render_calendar(
+            "Training",
+            "Europe/Berlin",
+            &[
+                IcsEvent {
+                    uid: "a@beppofit".into(),
+                    summary: "Rest day walk".into(),
+                    description: None,
+                    time: EventTime::AllDay(NaiveDate::from_ymd_opt(2024, 12, 31).unwrap()),
+                    status: Some("CANCELLED"),
+                },
+                IcsEvent {
+                    uid: "b@beppofit".into(),
+                    summary: "Legs".into(),
+                    description: Some("Squat".into()),
+                    time: EventTime::Timed {
+                        start: "2024-12-02T17:30:00Z".parse().unwrap(),
+                        duration: Duration::minutes(60),
+                    },
+                    status: None,
+                },
+            ],
+        );
+
+        assert!(calendar.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(calendar.contains("DTSTART;VALUE=DATE:20241231\r\nDTEND;VALUE=DATE:20250101\r\n"));
+        assert!(calendar.contains("STATUS:CANCELLED\r\n"));
+        assert!(calendar.contains("DTSTART:20241202T173000Z\r\nDTEND:20241202T183000Z\r\n"));
+        assert!(calendar.ends_with("END:VCALENDAR\r\n"));
+    }
+}
@@ -0,0 +1,347 @@
+use sqlx::PgConnection;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    models::interval::{
+        IntervalBlock, IntervalBlockDetail, IntervalBlockResult, IntervalSession,
+        IntervalSessionDetail, IntervalStep, IntervalWorkout, IntervalWorkoutDetail,
+        SaveIntervalBlock, SaveIntervalStep, SaveIntervalWorkoutRequest, FORMAT_AMRAP,
+        FORMAT_CUSTOM, FORMAT_EMOM, FORMAT_TABATA, STEP_REST, STEP_WORK,
+    },
+};
+
+/// Longest planned interval workout, rests included.
+pub const MAX_INTERVAL_SECONDS: i32 = 4 * 3600;
+
+/// Planned length of one block, without the rest after it.
+fn block_seconds(format: &str, index: usize, block: &SaveIntervalBlock) -> Result<i32, AppError> {
+    let invalid = |message: &str| AppError::BadRequest(format!("Block {}: {}", index + 1, message));
+
+    if format == FORMAT_AMRAP && block.time_cap_seconds.is_none() {
+        return Err(invalid("AMRAP blocks need a time cap"));
+    }
+    if format != FORMAT_AMRAP && format != FORMAT_CUSTOM && block.time_cap_seconds.is_some() {
+        return Err(invalid("only AMRAP and custom blocks can have a time cap"));
+    }
+    if format == FORMAT_TABATA {
+        let kinds: Vec<&str> = block.steps.iter().map(|s| s.kind.as_str()).collect();
+        if kinds != [STEP_WORK, STEP_REST] {
+            return Err(invalid("Tabata rounds are one work and one rest step"));
+        }
+    }
+    if let Some(cap) = block.time_cap_seconds {
+        return Ok(cap);
+    }
+
+    let mut round = 0;
+    for (position, step) in block.steps.iter().enumerate() {
+        round += step.duration_seconds.ok_or_else(|| {
+            invalid(&format!(
+                "step {} needs a duration unless the block has a time cap",
+                position + 1
+            ))
+        })?;
+    }
+    if format == FORMAT_EMOM {
+        if round % 60 != 0 {
+            return Err(invalid("EMOM rounds must last whole minutes"));
+        }
+        if block.rest_between_rounds_seconds > 0 {
+            return Err(invalid("EMOM rests are part of the minute"));
+        }
+    }
+    Ok(block.rounds * round + (block.rounds - 1) * block.rest_between_rounds_seconds)
+}
+
+/// Checks the definition against its format and returns its planned length,
+/// including the rests between blocks.
+pub fn planned_seconds(workout: &SaveIntervalWorkoutRequest) -> Result<i32, AppError> {
+    let mut total = 0i64;
+    for (index, block) in workout.blocks.iter().enumerate() {
+        total += block_seconds(&workout.format, index, block)? as i64;
+        if index + 1 < workout.blocks.len() {
+            total += block.rest_after_seconds as i64;
+        }
+    }
+    if total > MAX_INTERVAL_SECONDS as i64 {
+        return Err(AppError::BadRequest(format!(
+            "An interval workout can last at most {} hours",
+            MAX_INTERVAL_SECONDS / 3600
+        )));
+    }
+    Ok(total as i32)
+}
+
+/// The exercises referenced by the definition, for access checks.
+pub fn step_exercise_ids(blocks: &[SaveIntervalBlock]) -> Vec<Uuid> {
+    blocks
+        .iter()
+        .flat_map(|b| &b.steps)
+        .filter_map(|s| s.exercise_id)
+        .collect()
+}
+
+pub async fn insert_interval_blocks(
+    conn: &mut PgConnection,
+    interval_workout_id: Uuid,
+    blocks: &[SaveIntervalBlock],
+) -> Result<(), AppError> {
+    for (position, block) in blocks.iter().enumerate() {
+        let block_id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO interval_blocks (interval_workout_id, position, name, rounds, time_cap_seconds, rest_between_rounds_seconds, rest_after_seconds)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING id
+            "#,
+            interval_workout_id,
+            position as i32,
+            block.name,
+            block.rounds,
+            block.time_cap_seconds,
+            block.rest_between_rounds_seconds,
+            block.rest_after_seconds
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+
+        for (position, step) in block.steps.iter().enumerate() {
+            sqlx::query!(
+                r#"
+                INSERT INTO interval_steps (block_id, position, kind, exercise_id, label, duration_seconds, target_reps)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                "#,
+                block_id,
+                position as i32,
+                step.kind,
+                step.exercise_id,
+                step.label,
+                step.duration_seconds,
+                step.target_reps
+            )
+            .execute(&mut *conn)
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Loads a definition with its blocks and steps. Callers are responsible for access checks.
+pub async fn load_interval_detail(
+    conn: &mut PgConnection,
+    workout: IntervalWorkout,
+) -> Result<IntervalWorkoutDetail, AppError> {
+    let blocks = sqlx::query_as!(
+        IntervalBlock,
+        "SELECT * FROM interval_blocks WHERE interval_workout_id = $1 ORDER BY position",
+        workout.id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let steps = sqlx::query_as!(
+        IntervalStep,
+        r#"
+        SELECT s.block_id, s.position, s.kind, s.exercise_id, e.name AS "exercise_name?",
+               s.label, s.duration_seconds, s.target_reps
+        FROM interval_steps s
+        JOIN interval_blocks b ON b.id = s.block_id
+        LEFT JOIN exercises e ON e.id = s.exercise_id
+        WHERE b.interval_workout_id = $1
+        ORDER BY s.position
+        "#,
+        workout.id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut steps_by_block: HashMap<Uuid, Vec<IntervalStep>> = HashMap::new();
+    for step in steps {
+        steps_by_block.entry(step.block_id).or_default().push(step);
+    }
+
+    Ok(IntervalWorkoutDetail {
+        workout,
+        blocks: blocks
+            .into_iter()
+            .map(|block| IntervalBlockDetail {
+                steps: steps_by_block.remove(&block.id).unwrap_or_default(),
+                block,
+            })
+            .collect(),
+    })
+}
+
+/// Creates a new definition for `user_id` with the same content as `source`.
+pub async fn copy_interval_workout(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    source: &IntervalWorkoutDetail,
+) -> Result<IntervalWorkout, AppError> {
+    let workout = sqlx::query_as!(
+        IntervalWorkout,
+        r#"
+        INSERT INTO interval_workouts (user_id, name, description, format, total_seconds)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING *
+        "#,
+        user_id,
+        source.workout.name,
+        source.workout.description,
+        source.workout.format,
+        source.workout.total_seconds
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    let blocks: Vec<SaveIntervalBlock> = source
+        .blocks
+        .iter()
+        .map(|b| SaveIntervalBlock {
+            name: b.block.name.clone(),
+            rounds: b.block.rounds,
+            time_cap_seconds: b.block.time_cap_seconds,
+            rest_between_rounds_seconds: b.block.rest_between_rounds_seconds,
+            rest_after_seconds: b.block.rest_after_seconds,
+            steps: b
+                .steps
+                .iter()
+                .map(|s| SaveIntervalStep {
+                    kind: s.kind.clone(),
+                    exercise_id: s.exercise_id,
+                    label: s.label.clone(),
+                    duration_seconds: s.duration_seconds,
+                    target_reps: s.target_reps,
+                })
+                .collect(),
+        })
+        .collect();
+    insert_interval_blocks(conn, workout.id, &blocks).await?;
+
+    Ok(workout)
+}
+
+pub async fn load_session_detail(
+    conn: &mut PgConnection,
+    session: IntervalSession,
+) -> Result<IntervalSessionDetail, AppError> {
+    let blocks = sqlx::query_as!(
+        IntervalBlockResult,
+        "SELECT * FROM interval_session_blocks WHERE session_id = $1 ORDER BY block_position",
+        session.id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(IntervalSessionDetail { session, blocks })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(kind: &str, duration_seconds: Option<i32>) -> SaveIntervalStep {
+        SaveIntervalStep {
+            kind: kind.into(),
+            exercise_id: None,
+            label: None,
+            duration_seconds,
+            target_reps: None,
+        }
+    }
+
+    fn block(rounds: i32, steps: Vec<SaveIntervalStep>) -> SaveIntervalBlock {
+        SaveIntervalBlock {
+            name: None,
+            rounds,
+            time_cap_seconds: None,
+            rest_between_rounds_seconds: 0,
+            rest_after_seconds: 0,
+            steps,
+        }
+    }
+
+    fn workout(format: &str, blocks: Vec<SaveIntervalBlock>) -> SaveIntervalWorkoutRequest {
+        SaveIntervalWorkoutRequest {
+            name: "Intervals".into(),
+            description: None,
+            format: format.into(),
+            blocks,
+        }
+    }
+
+    fn error(workout: &SaveIntervalWorkoutRequest) -> String {
+        match planned_seconds(workout) {
+            Err(AppError::BadRequest(message)) => message,
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn sums_rounds_and_rests() {
+        let tabata = block(
+            8,
+            vec![step(STEP_WORK, Some(20)), step(STEP_REST, Some(10))],
+        );
+        assert_eq!(
+            planned_seconds(&workout(FORMAT_TABATA, vec![tabata.clone()])).unwrap(),
+            240
+        );
+
+        let mut first = tabata.clone();
+        first.rest_after_seconds = 60;
+        let mut last = tabata;
+        last.rest_after_seconds = 300;
+        // The rest after the last block is not part of the workout
+        assert_eq!(
+            planned_seconds(&workout(FORMAT_TABATA, vec![first, last])).unwrap(),
+            540
+        );
+
+        let mut repeats = block(4, vec![step(STEP_WORK, Some(180))]);
+        repeats.rest_between_rounds_seconds = 90;
+        assert_eq!(
+            planned_seconds(&workout(FORMAT_CUSTOM, vec![repeats])).unwrap(),
+            990
+        );
+    }
+
+    #[test]
+    fn enforces_formats() {
+        let emom = block(
+            10,
+            vec![step(STEP_WORK, Some(40)), step(STEP_REST, Some(20))],
+        );
+        assert_eq!(
+            planned_seconds(&workout(FORMAT_EMOM, vec![emom])).unwrap(),
+            600
+        );
+        let uneven = block(10, vec![step(STEP_WORK, Some(45))]);
+        assert!(error(&workout(FORMAT_EMOM, vec![uneven])).contains("whole minutes"));
+
+        let mut amrap = block(1, vec![step(STEP_WORK, None), step(STEP_WORK, None)]);
+        assert!(error(&workout(FORMAT_AMRAP, vec![amrap.clone()])).contains("time cap"));
+        amrap.time_cap_seconds = Some(720);
+        assert_eq!(
+            planned_seconds(&workout(FORMAT_AMRAP, vec![amrap])).unwrap(),
+            720
+        );
+
+        let backwards = block(
+            8,
+            vec![step(STEP_REST, Some(10)), step(STEP_WORK, Some(20))],
+        );
+        assert!(error(&workout(FORMAT_TABATA, vec![backwards])).starts_with("Block 1"));
+    }
+
+    #[test]
+    fn rejects_open_ended_and_overlong_definitions() {
+        let open = block(3, vec![step(STEP_WORK, Some(30)), step(STEP_WORK, None)]);
+        assert!(error(&workout(FORMAT_CUSTOM, vec![open])).contains("step 2 needs a duration"));
+
+        let long = block(100, vec![step(STEP_WORK, Some(3600))]);
+        assert!(error(&workout(FORMAT_CUSTOM, vec![long])).contains("at most 4 hours"));
+    }
+}
@@ -0,0 +1,132 @@
+use hmac::{Hmac, Mac};
+use image::{
+    codecs::jpeg::JpegEncoder, DynamicImage, ImageDecoder, ImageFormat, ImageReader, Limits,
+};
+use sha2::Sha256;
+use std::{env, io::Cursor};
+use uuid::Uuid;
+
+use crate::error::AppError;
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub const MAX_UPLOAD_BYTES: usize = 10 * 1024 * 1024;
+pub const ALLOWED_CONTENT_TYPES: &[&str] = &["image/jpeg", "image/png", "image/webp"];
+/// Longest edge of the stored "original" variant.
+const MAX_DIMENSION: u32 = 2048;
+const THUMBNAIL_SIZE: u32 = 256;
+/// Source images larger than this are rejected before decoding.
+const MAX_SOURCE_DIMENSION: u32 = 12_000;
+const JPEG_QUALITY: u8 = 85;
+pub const SIGNED_URL_TTL_SECONDS: i64 = 3600;
+
+pub struct ProcessedImage {
+    pub original: Vec<u8>,
+    pub thumbnail: Vec<u8>,
+    pub content_type: &'static str,
+    pub extension: &'static str,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Validates an uploaded image, applies its EXIF orientation and re-encodes it
+/// as a downscaled original and a thumbnail. Re-encoding drops all EXIF and
+/// other metadata (GPS position, camera serials, ...) from the stored files.
+pub fn process_image(data: &[u8], declared_content_type: &str) -> Result<ProcessedImage, AppError> {
+    if !ALLOWED_CONTENT_TYPES.contains(&declared_content_type) {
+        return Err(AppError::BadRequest(format!(
+            "Unsupported file type. Allowed: {}",
+            ALLOWED_CONTENT_TYPES.join(", ")
+        )));
+    }
+
+    let format = image::guess_format(data)
+        .map_err(|_| AppError::BadRequest("File is not a valid image".into()))?;
+    if format.to_mime_type() != declared_content_type {
+        return Err(AppError::BadRequest(
+            "File content does not match its content type".into(),
+        ));
+    }
+
+    let mut reader = ImageReader::with_format(Cursor::new(data), format);
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_SOURCE_DIMENSION);
+    limits.max_image_height = Some(MAX_SOURCE_DIMENSION);
+    reader.limits(limits);
+
+    let invalid = |_| AppError::BadRequest("File is not a valid image".into());
+    let mut decoder = reader.into_decoder().map_err(invalid)?;
+    let orientation = decoder.orientation().map_err(invalid)?;
+    let mut image = DynamicImage::from_decoder(decoder).map_err(invalid)?;
+    image.apply_orientation(orientation);
+
+    if image.width() > MAX_DIMENSION || image.height() > MAX_DIMENSION {
+        image = image.resize(
+            MAX_DIMENSION,
+            MAX_DIMENSION,
+            image::imageops::FilterType::Lanczos3,
+        );
+    }
+    let thumbnail = image.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE);
+
+    // Keep transparency where the source has it, everything else becomes JPEG.
+    let (content_type, extension) = if image.color().has_alpha() {
+        ("image/png", "png")
+    } else {
+        ("image/jpeg", "jpg")
+    };
+
+    Ok(ProcessedImage {
+        original: encode(&image, content_type)?,
+        thumbnail: encode(&thumbnail, content_type)?,
+        content_type,
+        extension,
+        width: image.width(),
+        height: image.height(),
+    })
+}
+
+fn encode(image: &DynamicImage, content_type: &str) -> Result<Vec<u8>, AppError> {
+    let mut buffer = Vec::new();
+    let result = if content_type == "image/png" {
+        image.write_to(&mut Cursor::new(&mut buffer), ImageFormat::Png)
+    } else {
+        JpegEncoder::new_with_quality(&mut buffer, JPEG_QUALITY).encode_image(&image.to_rgb8())
+    };
+    result.map_err(|e| {
+        tracing::error!("Failed to encode image: {:?}", e);
+        AppError::InternalServerError
+    })?;
+    Ok(buffer)
+}
+
+fn signer(media_id: Uuid, variant: &str, expires: i64) -> HmacSha256 {
+    let secret = env::var("JWT_SECRET").expect("JWT_SECRET must be set");
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(format!("media:{}:{}:{}", media_id, variant, expires).as_bytes());
+    mac
+}
+
+/// Returns a relative download URL for a media variant that is valid for
+/// `SIGNED_URL_TTL_SECONDS` without further authentication.
+pub fn signed_url(media_id: Uuid, variant: &str) -> String {
+    let expires = chrono::Utc::now().timestamp() + SIGNED_URL_TTL_SECONDS;
+    let signature = hex::encode(signer(media_id, variant, expires).finalize().into_bytes());
+    format!(
+        "/media/{}/{}?expires={}&signature={}",
+        media_id, variant, expires, signature
+    )
+}
+
+pub fn verify_signature(media_id: Uuid, variant: &str, expires: i64, signature: &str) -> bool {
+    if expires < chrono::Utc::now().timestamp() {
+        return false;
+    }
+    let Ok(signature) = hex::decode(signature) else {
+        return false;
+    };
+    signer(media_id, variant, expires)
+        .verify_slice(&signature)
+        .is_ok()
+}
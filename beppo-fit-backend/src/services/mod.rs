@@ -1 +1,32 @@
+pub mod achievement;
+pub mod activity;
+pub mod activity_export;
+pub mod activity_import;
+pub mod activity_metrics;
 pub mod email;
+pub mod exercise_match;
+pub mod export;
+pub mod fit;
+pub mod food_import;
+pub mod goal;
+pub mod ical;
+pub mod interval;
+pub mod media;
+pub mod notification;
+pub mod nutrition;
+pub mod nutrition_targets;
+pub mod profile;
+pub mod program;
+pub mod progression;
+pub mod record;
+pub mod recurrence;
+pub mod schedule;
+pub mod social;
+pub mod stats;
+pub mod storage;
+pub mod strength_import;
+pub mod template;
+pub mod track;
+pub mod wellness;
+pub mod workout;
+pub mod workout_export;
@@ -0,0 +1,31 @@
+use sqlx::PgExecutor;
+use uuid::Uuid;
+
+use crate::{error::AppError, models::notification::Notification};
+
+pub async fn notify<'e>(
+    executor: impl PgExecutor<'e>,
+    user_id: Uuid,
+    kind: &str,
+    title: &str,
+    body: Option<&str>,
+    goal_id: Option<Uuid>,
+) -> Result<Notification, AppError> {
+    let notification = sqlx::query_as!(
+        Notification,
+        r#"
+        INSERT INTO notifications (user_id, kind, title, body, goal_id)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING *
+        "#,
+        user_id,
+        kind,
+        title,
+        body,
+        goal_id
+    )
+    .fetch_one(executor)
+    .await?;
+
+    Ok(notification)
+}
@@ -0,0 +1,136 @@
+use sqlx::PgConnection;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    models::nutrition::{Meal, MealDetail, MealItemDetail, Nutrients, SaveMealItem},
+};
+
+/// Replaces the items of a meal, converting servings to grams. Only the shared
+/// catalog and the user's own foods can be eaten. Each item keeps the food's
+/// current nutrients, so later edits to the food leave the meal as logged.
+pub async fn insert_meal_items(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    meal_id: Uuid,
+    items: &[SaveMealItem],
+) -> Result<(), AppError> {
+    let food_ids: Vec<Uuid> = items.iter().map(|i| i.food_id).collect();
+    let serving_sizes: HashMap<Uuid, Option<f64>> = sqlx::query!(
+        "SELECT id, serving_size_g FROM foods WHERE id = ANY($1) AND (owner_id IS NULL OR owner_id = $2)",
+        &food_ids,
+        user_id
+    )
+    .fetch_all(&mut *conn)
+    .await?
+    .into_iter()
+    .map(|row| (row.id, row.serving_size_g))
+    .collect();
+
+    sqlx::query!("DELETE FROM meal_items WHERE meal_id = $1", meal_id)
+        .execute(&mut *conn)
+        .await?;
+
+    for (position, item) in items.iter().enumerate() {
+        let serving_size = serving_sizes
+            .get(&item.food_id)
+            .ok_or_else(|| AppError::BadRequest(format!("Food {} not found", item.food_id)))?;
+        let grams = match (item.grams, item.servings) {
+            (Some(grams), _) => grams,
+            (None, Some(servings)) => {
+                let size = serving_size.ok_or_else(|| {
+                    AppError::BadRequest(format!(
+                        "Food {} has no serving size; give grams instead",
+                        item.food_id
+                    ))
+                })?;
+                (servings * size * 10.0).round() / 10.0
+            }
+            (None, None) => {
+                return Err(AppError::BadRequest(
+                    "Each meal item needs grams or servings".into(),
+                ))
+            }
+        };
+
+        sqlx::query!(
+            r#"
+            INSERT INTO meal_items (
+                meal_id, food_id, position, grams, energy_kcal, protein_g, carbohydrate_g,
+                fat_g, fiber_g, sugar_g, saturated_fat_g, sodium_mg, potassium_mg,
+                calcium_mg, iron_mg, vitamin_c_mg
+            )
+            SELECT $1, id, $3, $4, energy_kcal, protein_g, carbohydrate_g,
+                   fat_g, fiber_g, sugar_g, saturated_fat_g, sodium_mg, potassium_mg,
+                   calcium_mg, iron_mg, vitamin_c_mg
+            FROM foods WHERE id = $2
+            "#,
+            meal_id,
+            item.food_id,
+            position as i32,
+            grams
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Loads a meal with the nutrients of each item. Callers are responsible for access checks.
+pub async fn load_meal_detail(conn: &mut PgConnection, meal: Meal) -> Result<MealDetail, AppError> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT mi.id, mi.position, mi.grams, f.id AS food_id, f.name, f.brand,
+               mi.energy_kcal, mi.protein_g, mi.carbohydrate_g, mi.fat_g, mi.fiber_g,
+               mi.sugar_g, mi.saturated_fat_g, mi.sodium_mg, mi.potassium_mg, mi.calcium_mg,
+               mi.iron_mg, mi.vitamin_c_mg
+        FROM meal_items mi
+        JOIN foods f ON f.id = mi.food_id
+        WHERE mi.meal_id = $1
+        ORDER BY mi.position
+        "#,
+        meal.id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut totals = Nutrients::default();
+    let items: Vec<MealItemDetail> = rows
+        .into_iter()
+        .map(|row| {
+            let per_100g = Nutrients {
+                energy_kcal: row.energy_kcal,
+                protein_g: row.protein_g,
+                carbohydrate_g: row.carbohydrate_g,
+                fat_g: row.fat_g,
+                fiber_g: row.fiber_g,
+                sugar_g: row.sugar_g,
+                saturated_fat_g: row.saturated_fat_g,
+                sodium_mg: row.sodium_mg,
+                potassium_mg: row.potassium_mg,
+                calcium_mg: row.calcium_mg,
+                iron_mg: row.iron_mg,
+                vitamin_c_mg: row.vitamin_c_mg,
+            };
+            let nutrients = per_100g.scaled(row.grams / 100.0);
+            totals.add(&nutrients);
+            MealItemDetail {
+                id: row.id,
+                position: row.position,
+                food_id: row.food_id,
+                food_name: row.name,
+                brand: row.brand,
+                grams: row.grams,
+                nutrients,
+            }
+        })
+        .collect();
+
+    Ok(MealDetail {
+        meal,
+        items,
+        totals,
+    })
+}
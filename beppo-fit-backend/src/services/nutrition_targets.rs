@@ -0,0 +1,391 @@
+//! Daily calorie and macro targets from the profile, bodyweight log, training
+//! log and, once enough data exists, the observed intake vs. weight trend.
+
+use chrono::{Datelike, Duration, NaiveDate};
+use sqlx::PgConnection;
+
+use crate::{
+    error::AppError,
+    models::{
+        nutrition::{AdaptiveTdee, NutritionTargets, NUTRITION_GOAL_BULK, NUTRITION_GOAL_CUT},
+        profile::UserProfile,
+    },
+};
+
+/// Days of training, meals and weigh-ins the estimate looks back on.
+pub const TARGET_WINDOW_DAYS: i64 = 28;
+/// Older body fat readings are ignored in favour of Mifflin-St Jeor.
+const BODY_FAT_MAX_AGE_DAYS: i64 = 90;
+/// Logged days needed before the intake is trusted.
+const MIN_LOGGED_DAYS: i64 = 14;
+/// Weigh-ins must span at least this many days to give a trend.
+const MIN_WEIGHT_SPAN_DAYS: f64 = 14.0;
+const MAX_ADJUSTMENT_KCAL: f64 = 500.0;
+/// Energy stored in a kilogram of bodyweight change.
+const KCAL_PER_KG: f64 = 7700.0;
+
+const CUT_FACTOR: f64 = 0.8;
+const BULK_FACTOR: f64 = 1.1;
+
+fn round1(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+/// Resting energy from weight, height, age and sex. `other` uses the midpoint
+/// of the male and female constants.
+pub fn mifflin_st_jeor(weight_kg: f64, height_cm: f64, age: i32, sex: &str) -> f64 {
+    let constant = match sex {
+        "male" => 5.0,
+        "female" => -161.0,
+        _ => -78.0,
+    };
+    10.0 * weight_kg + 6.25 * height_cm - 5.0 * age as f64 + constant
+}
+
+/// Resting energy from lean body mass.
+pub fn katch_mcardle(weight_kg: f64, body_fat_percent: f64) -> f64 {
+    370.0 + 21.6 * weight_kg * (1.0 - body_fat_percent / 100.0)
+}
+
+/// The classic activity multipliers, picked by distinct training days per week.
+pub fn activity_level(training_days_per_week: f64) -> (&'static str, f64) {
+    match training_days_per_week {
+        d if d < 1.0 => ("sedentary", 1.2),
+        d if d < 3.0 => ("light", 1.375),
+        d if d < 5.0 => ("moderate", 1.55),
+        d if d < 6.5 => ("active", 1.725),
+        _ => ("very_active", 1.9),
+    }
+}
+
+fn age_on(date_of_birth: NaiveDate, today: NaiveDate) -> i32 {
+    let mut age = today.year() - date_of_birth.year();
+    if (today.month(), today.day()) < (date_of_birth.month(), date_of_birth.day()) {
+        age -= 1;
+    }
+    age
+}
+
+/// Least-squares slope of `(day, kg)` points, in kg per day.
+pub fn weight_slope(points: &[(f64, f64)]) -> Option<f64> {
+    let first = points.iter().map(|p| p.0).fold(f64::INFINITY, f64::min);
+    let last = points.iter().map(|p| p.0).fold(f64::NEG_INFINITY, f64::max);
+    if points.len() < 2 || last - first < MIN_WEIGHT_SPAN_DAYS {
+        return None;
+    }
+
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+    let covariance: f64 = points.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum();
+    let variance: f64 = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum();
+    Some(covariance / variance)
+}
+
+/// Expenditure implied by eating `daily_intake` while the weight moved along
+/// `weigh_ins`. `None` until enough of both is logged.
+pub fn adaptive_tdee(
+    formula_tdee: f64,
+    daily_intake: &[f64],
+    weigh_ins: &[(f64, f64)],
+) -> Option<AdaptiveTdee> {
+    let days_logged = daily_intake.len() as i64;
+    if days_logged < MIN_LOGGED_DAYS {
+        return None;
+    }
+    let slope = weight_slope(weigh_ins)?;
+
+    let average_intake = daily_intake.iter().sum::<f64>() / days_logged as f64;
+    let observed = average_intake - slope * KCAL_PER_KG;
+    Some(AdaptiveTdee {
+        days_logged,
+        average_intake_kcal: average_intake.round(),
+        weight_change_kg_per_week: (slope * 7.0 * 100.0).round() / 100.0,
+        observed_tdee_kcal: observed.round(),
+        adjustment_kcal: (observed - formula_tdee)
+            .clamp(-MAX_ADJUSTMENT_KCAL, MAX_ADJUSTMENT_KCAL)
+            .round(),
+    })
+}
+
+/// Protein and fat scale with bodyweight, carbohydrate takes the remaining energy.
+pub fn macro_split(calories: f64, weight_kg: f64, goal: &str) -> (f64, f64, f64) {
+    let protein_per_kg = match goal {
+        NUTRITION_GOAL_CUT => 2.2,
+        NUTRITION_GOAL_BULK => 2.0,
+        _ => 1.8,
+    };
+    let protein = (protein_per_kg * weight_kg).round();
+    let fat = (calories * 0.25 / 9.0).max(0.6 * weight_kg).round();
+    let carbohydrate = ((calories - protein * 4.0 - fat * 9.0) / 4.0)
+        .max(0.0)
+        .round();
+    (protein, fat, carbohydrate)
+}
+
+/// Everything the calculation needs that lives outside the profile.
+pub struct TargetInputs {
+    pub weight_kg: f64,
+    pub body_fat_percent: Option<f64>,
+    pub training_days: i64,
+    /// Energy eaten on each logged day of the window.
+    pub daily_intake: Vec<f64>,
+    /// Weigh-ins as (days since the window start, kg).
+    pub weigh_ins: Vec<(f64, f64)>,
+}
+
+pub fn compute_targets(
+    profile: &UserProfile,
+    goal: &str,
+    today: NaiveDate,
+    inputs: &TargetInputs,
+) -> Result<NutritionTargets, AppError> {
+    let (bmr_method, bmr) = match inputs.body_fat_percent {
+        Some(body_fat) => ("katch_mcardle", katch_mcardle(inputs.weight_kg, body_fat)),
+        None => {
+            let (Some(height_cm), Some(date_of_birth), Some(sex)) = (
+                profile.height_cm,
+                profile.date_of_birth,
+                profile.sex.as_deref(),
+            ) else {
+                return Err(AppError::BadRequest(
+                    "Set height, date of birth and sex in your profile, or log your body fat"
+                        .into(),
+                ));
+            };
+            let age = age_on(date_of_birth, today);
+            (
+                "mifflin_st_jeor",
+                mifflin_st_jeor(inputs.weight_kg, height_cm, age, sex),
+            )
+        }
+    };
+
+    let training_days_per_week =
+        round1(inputs.training_days as f64 * 7.0 / TARGET_WINDOW_DAYS as f64);
+    let (level, factor) = activity_level(training_days_per_week);
+    let formula_tdee = (bmr * factor).round();
+    let adaptive = adaptive_tdee(formula_tdee, &inputs.daily_intake, &inputs.weigh_ins);
+    let tdee = formula_tdee + adaptive.as_ref().map_or(0.0, |a| a.adjustment_kcal);
+
+    let goal_factor = match goal {
+        NUTRITION_GOAL_CUT => CUT_FACTOR,
+        NUTRITION_GOAL_BULK => BULK_FACTOR,
+        _ => 1.0,
+    };
+    // Never target less than the resting expenditure
+    let calories = (tdee * goal_factor).max(bmr).round();
+    let (protein_g, fat_g, carbohydrate_g) = macro_split(calories, inputs.weight_kg, goal);
+
+    Ok(NutritionTargets {
+        goal: goal.to_string(),
+        bmr_method,
+        weight_kg: inputs.weight_kg,
+        body_fat_percent: inputs.body_fat_percent,
+        bmr_kcal: bmr.round(),
+        training_days_per_week,
+        activity_level: level,
+        activity_factor: factor,
+        formula_tdee_kcal: formula_tdee,
+        adaptive,
+        tdee_kcal: tdee,
+        calories_kcal: calories,
+        protein_g,
+        fat_g,
+        carbohydrate_g,
+    })
+}
+
+/// Loads the bodyweight, training and intake data of the window ending
+/// before today in the profile timezone. Today is left out as it is not over yet.
+pub async fn load_target_inputs(
+    conn: &mut PgConnection,
+    profile: &UserProfile,
+) -> Result<TargetInputs, AppError> {
+    let today = profile.today();
+    let end = profile.start_of_day(today);
+    let start = profile.start_of_day(today - Duration::days(TARGET_WINDOW_DAYS));
+    let timezone = profile.tz().name().to_string();
+
+    let weight_kg = sqlx::query_scalar!(
+        r#"
+        SELECT weight_kg AS "weight_kg!" FROM body_measurements
+        WHERE user_id = $1 AND weight_kg IS NOT NULL
+        ORDER BY measured_at DESC
+        LIMIT 1
+        "#,
+        profile.user_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or_else(|| AppError::BadRequest("Log your bodyweight to calculate targets".into()))?;
+
+    let body_fat_percent = sqlx::query_scalar!(
+        r#"
+        SELECT body_fat_percent AS "body_fat_percent!" FROM body_measurements
+        WHERE user_id = $1 AND body_fat_percent IS NOT NULL AND measured_at >= $2
+        ORDER BY measured_at DESC
+        LIMIT 1
+        "#,
+        profile.user_id,
+        end - Duration::days(BODY_FAT_MAX_AGE_DAYS)
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    let training_days = sqlx::query_scalar!(
+        r#"
+        SELECT COUNT(DISTINCT day) AS "days!" FROM (
+            SELECT (started_at AT TIME ZONE $4)::DATE AS day FROM workouts
+            WHERE user_id = $1 AND finished_at IS NOT NULL
+              AND started_at >= $2 AND started_at < $3
+            UNION ALL
+            SELECT (started_at AT TIME ZONE $4)::DATE FROM activities
+            WHERE user_id = $1 AND started_at >= $2 AND started_at < $3
+        ) training
+        "#,
+        profile.user_id,
+        start,
+        end,
+        timezone
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    let daily_intake = sqlx::query_scalar!(
+        r#"
+        SELECT SUM(mi.energy_kcal * mi.grams / 100) AS "energy_kcal!"
+        FROM meals m
+        JOIN meal_items mi ON mi.meal_id = m.id
+        WHERE m.user_id = $1 AND m.eaten_at >= $2 AND m.eaten_at < $3
+        GROUP BY (m.eaten_at AT TIME ZONE $4)::DATE
+        "#,
+        profile.user_id,
+        start,
+        end,
+        timezone
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let weigh_ins = sqlx::query!(
+        r#"
+        SELECT measured_at, weight_kg AS "weight_kg!" FROM body_measurements
+        WHERE user_id = $1 AND weight_kg IS NOT NULL
+          AND measured_at >= $2 AND measured_at < $3
+        "#,
+        profile.user_id,
+        start,
+        end
+    )
+    .fetch_all(&mut *conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        let days = (row.measured_at - start).num_seconds() as f64 / 86400.0;
+        (days, row.weight_kg)
+    })
+    .collect();
+
+    Ok(TargetInputs {
+        weight_kg,
+        body_fat_percent,
+        training_days,
+        daily_intake,
+        weigh_ins,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::nutrition::NUTRITION_GOAL_MAINTAIN;
+
+    fn profile() -> UserProfile {
+        UserProfile {
+            date_of_birth: NaiveDate::from_ymd_opt(1994, 6, 15),
+            sex: Some("male".into()),
+            height_cm: Some(180.0),
+            ..UserProfile::test_default(uuid::Uuid::nil())
+        }
+    }
+
+    fn inputs(training_days: i64) -> TargetInputs {
+        TargetInputs {
+            weight_kg: 80.0,
+            body_fat_percent: None,
+            training_days,
+            daily_intake: Vec::new(),
+            weigh_ins: Vec::new(),
+        }
+    }
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 6, 14).unwrap()
+    }
+
+    #[test]
+    fn estimates_resting_energy() {
+        assert_eq!(mifflin_st_jeor(80.0, 180.0, 30, "male"), 1780.0);
+        assert_eq!(mifflin_st_jeor(80.0, 180.0, 30, "female"), 1614.0);
+        assert_eq!(mifflin_st_jeor(80.0, 180.0, 30, "other"), 1697.0);
+        assert_eq!(katch_mcardle(80.0, 20.0), 1752.4);
+        // The birthday is tomorrow
+        assert_eq!(
+            age_on(NaiveDate::from_ymd_opt(1994, 6, 15).unwrap(), today()),
+            29
+        );
+    }
+
+    #[test]
+    fn scales_with_training_and_goal() {
+        // 12 training days in four weeks is three per week
+        let targets =
+            compute_targets(&profile(), NUTRITION_GOAL_MAINTAIN, today(), &inputs(12)).unwrap();
+        assert_eq!(targets.bmr_method, "mifflin_st_jeor");
+        assert_eq!(targets.bmr_kcal, 1785.0);
+        assert_eq!(targets.training_days_per_week, 3.0);
+        assert_eq!(targets.activity_level, "moderate");
+        assert_eq!(targets.tdee_kcal, 2767.0);
+        assert_eq!(targets.calories_kcal, 2767.0);
+        assert_eq!(targets.protein_g, 144.0);
+        assert_eq!(targets.fat_g, 77.0);
+        assert_eq!(targets.carbohydrate_g, 375.0);
+
+        let cut = compute_targets(&profile(), NUTRITION_GOAL_CUT, today(), &inputs(0)).unwrap();
+        assert_eq!(cut.activity_level, "sedentary");
+        // 0.8 x 2142 would undercut the resting expenditure
+        assert_eq!(cut.calories_kcal, 1785.0);
+        assert_eq!(cut.protein_g, 176.0);
+
+        let mut lean = inputs(12);
+        lean.body_fat_percent = Some(20.0);
+        let mut unknown = profile();
+        unknown.height_cm = None;
+        let targets = compute_targets(&unknown, NUTRITION_GOAL_BULK, today(), &lean).unwrap();
+        assert_eq!(targets.bmr_method, "katch_mcardle");
+        assert_eq!(targets.calories_kcal, (2716.0f64 * 1.1).round());
+
+        assert!(compute_targets(&unknown, NUTRITION_GOAL_MAINTAIN, today(), &inputs(0)).is_err());
+    }
+
+    #[test]
+    fn adapts_to_weight_trend() {
+        // Too little data: no adjustment
+        assert!(adaptive_tdee(2500.0, &[2500.0; 10], &[(0.0, 80.0), (20.0, 79.0)]).is_none());
+        assert!(adaptive_tdee(2500.0, &[2500.0; 20], &[(0.0, 80.0), (10.0, 79.0)]).is_none());
+
+        // Losing 0.5 kg per week on 2200 kcal means burning 2750
+        let weigh_ins: Vec<(f64, f64)> = (0..4)
+            .map(|w| (w as f64 * 7.0, 80.0 - w as f64 * 0.5))
+            .collect();
+        let adaptive = adaptive_tdee(2500.0, &[2200.0; 20], &weigh_ins).unwrap();
+        assert_eq!(adaptive.weight_change_kg_per_week, -0.5);
+        assert_eq!(adaptive.observed_tdee_kcal, 2750.0);
+        assert_eq!(adaptive.adjustment_kcal, 250.0);
+
+        // Large gaps are capped
+        let adaptive = adaptive_tdee(1800.0, &[2200.0; 20], &weigh_ins).unwrap();
+        assert_eq!(adaptive.adjustment_kcal, 500.0);
+    }
+}
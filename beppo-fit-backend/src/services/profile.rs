@@ -0,0 +1,38 @@
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::{error::AppError, models::profile::UserProfile};
+
+/// Loads the profile of a user, creating the default one if it does not exist yet.
+pub async fn ensure_profile(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+) -> Result<UserProfile, AppError> {
+    let existing = sqlx::query_as!(
+        UserProfile,
+        "SELECT * FROM user_profiles WHERE user_id = $1",
+        user_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+    if let Some(profile) = existing {
+        return Ok(profile);
+    }
+
+    // A concurrent request may create it first, so read it back either way.
+    sqlx::query!(
+        "INSERT INTO user_profiles (user_id) VALUES ($1) ON CONFLICT (user_id) DO NOTHING",
+        user_id
+    )
+    .execute(&mut *conn)
+    .await?;
+    let profile = sqlx::query_as!(
+        UserProfile,
+        "SELECT * FROM user_profiles WHERE user_id = $1",
+        user_id
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(profile)
+}
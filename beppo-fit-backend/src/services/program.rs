@@ -0,0 +1,559 @@
+use sqlx::PgConnection;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    models::{
+        program::{
+            Enrollment, NextWorkout, PlannedExercise, PlannedSet, Prescription, Program,
+            ProgramDay, ProgramDayDetail, ProgramDetail, ProgramWeekDetail, SaveProgramWeek,
+        },
+        template::WorkoutTemplate,
+        workout::{SaveWorkoutExercise, SaveWorkoutSet},
+    },
+    services::{
+        progression::latest_recommendations,
+        template::{last_performance, load_template_detail, planned_sets},
+        wellness::{lighter_set_count, lighter_weight, session_adjustment},
+        workout::insert_workout_exercises,
+    },
+};
+
+/// Share of the usual load used in deload weeks for exercises without a prescription.
+pub const DELOAD_WEIGHT_FACTOR: f64 = 0.6;
+
+/// Rounds a weight to the nearest multiple of `increment`.
+pub fn round_to_increment(weight_kg: f64, increment: f64) -> f64 {
+    (weight_kg / increment).round() * increment
+}
+
+/// Fails unless every day references one of the user's templates and every
+/// prescription targets an exercise of that day's template.
+pub async fn check_program_weeks(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    weeks: &[SaveProgramWeek],
+) -> Result<(), AppError> {
+    let template_ids: Vec<Uuid> = weeks
+        .iter()
+        .flat_map(|w| w.days.iter().map(|d| d.template_id))
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT t.id, te.exercise_id AS "exercise_id?"
+        FROM workout_templates t
+        LEFT JOIN template_exercises te ON te.template_id = t.id
+        WHERE t.id = ANY($1) AND t.user_id = $2
+        "#,
+        &template_ids,
+        user_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut exercises_by_template: HashMap<Uuid, HashSet<Uuid>> = HashMap::new();
+    for row in rows {
+        let exercises = exercises_by_template.entry(row.id).or_default();
+        if let Some(exercise_id) = row.exercise_id {
+            exercises.insert(exercise_id);
+        }
+    }
+
+    for day in weeks.iter().flat_map(|w| &w.days) {
+        let exercises = exercises_by_template
+            .get(&day.template_id)
+            .ok_or_else(|| AppError::BadRequest("Unknown template referenced".into()))?;
+        if day
+            .prescriptions
+            .iter()
+            .any(|p| !exercises.contains(&p.exercise_id))
+        {
+            return Err(AppError::BadRequest(
+                "Prescriptions must target exercises of the day's template".into(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+pub async fn insert_program_weeks(
+    conn: &mut PgConnection,
+    program_id: Uuid,
+    weeks: &[SaveProgramWeek],
+) -> Result<(), AppError> {
+    for (week_index, week) in weeks.iter().enumerate() {
+        let week_number = week_index as i32 + 1;
+        sqlx::query!(
+            "INSERT INTO program_weeks (program_id, week_number, is_deload) VALUES ($1, $2, $3)",
+            program_id,
+            week_number,
+            week.is_deload
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        for (day_index, day) in week.days.iter().enumerate() {
+            let day_id = sqlx::query_scalar!(
+                r#"
+                INSERT INTO program_days (program_id, week_number, day_number, template_id, name)
+                VALUES ($1, $2, $3, $4, $5)
+                RETURNING id
+                "#,
+                program_id,
+                week_number,
+                day_index as i32 + 1,
+                day.template_id,
+                day.name
+            )
+            .fetch_one(&mut *conn)
+            .await?;
+
+            for (position, prescription) in day.prescriptions.iter().enumerate() {
+                sqlx::query!(
+                    r#"
+                    INSERT INTO program_prescriptions (program_day_id, exercise_id, position, percent_of_tm, reps, is_amrap)
+                    VALUES ($1, $2, $3, $4, $5, $6)
+                    "#,
+                    day_id,
+                    prescription.exercise_id,
+                    position as i32,
+                    prescription.percent_of_tm,
+                    prescription.reps,
+                    prescription.is_amrap
+                )
+                .execute(&mut *conn)
+                .await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Loads a program with its weeks, days and prescriptions. Callers are responsible for access checks.
+pub async fn load_program_detail(
+    conn: &mut PgConnection,
+    program: Program,
+) -> Result<ProgramDetail, AppError> {
+    let weeks = sqlx::query!(
+        "SELECT week_number, is_deload FROM program_weeks WHERE program_id = $1 ORDER BY week_number",
+        program.id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let days = sqlx::query_as!(
+        ProgramDay,
+        r#"
+        SELECT d.id, d.program_id, d.week_number, d.day_number, d.template_id, t.name AS template_name, d.name
+        FROM program_days d
+        JOIN workout_templates t ON t.id = d.template_id
+        WHERE d.program_id = $1
+        ORDER BY d.week_number, d.day_number
+        "#,
+        program.id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let prescriptions = sqlx::query_as!(
+        Prescription,
+        r#"
+        SELECT p.program_day_id, p.exercise_id, p.position, p.percent_of_tm, p.reps, p.is_amrap
+        FROM program_prescriptions p
+        JOIN program_days d ON d.id = p.program_day_id
+        WHERE d.program_id = $1
+        ORDER BY p.position
+        "#,
+        program.id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut prescriptions_by_day: HashMap<Uuid, Vec<Prescription>> = HashMap::new();
+    for prescription in prescriptions {
+        prescriptions_by_day
+            .entry(prescription.program_day_id)
+            .or_default()
+            .push(prescription);
+    }
+
+    let mut days_by_week: HashMap<i32, Vec<ProgramDayDetail>> = HashMap::new();
+    for day in days {
+        let prescriptions = prescriptions_by_day.remove(&day.id).unwrap_or_default();
+        days_by_week
+            .entry(day.week_number)
+            .or_default()
+            .push(ProgramDayDetail { day, prescriptions });
+    }
+
+    Ok(ProgramDetail {
+        program,
+        weeks: weeks
+            .into_iter()
+            .map(|week| ProgramWeekDetail {
+                week_number: week.week_number,
+                is_deload: week.is_deload,
+                days: days_by_week.remove(&week.week_number).unwrap_or_default(),
+            })
+            .collect(),
+    })
+}
+
+/// Computes the enrollment's next workout, or `None` once the program is done.
+///
+/// Prescribed exercises get their sets from the training max; everything else
+/// repeats the last performance like a plain template, lightened in deload weeks.
+/// Outside deload weeks a low readiness day lightens every exercise.
+pub async fn plan_next_workout(
+    conn: &mut PgConnection,
+    enrollment: Enrollment,
+) -> Result<Option<NextWorkout>, AppError> {
+    if enrollment.completed_at.is_some() {
+        return Ok(None);
+    }
+
+    // Days may have been removed since the position was stored, so take the
+    // first day at or after it.
+    let Some(day) = sqlx::query!(
+        r#"
+        SELECT d.id, d.week_number, d.day_number, d.template_id, d.name, w.is_deload,
+               p.name AS program_name, p.rounding_kg
+        FROM program_days d
+        JOIN program_weeks w ON w.program_id = d.program_id AND w.week_number = d.week_number
+        JOIN programs p ON p.id = d.program_id
+        WHERE d.program_id = $1 AND (d.week_number, d.day_number) >= ($2, $3)
+        ORDER BY d.week_number, d.day_number
+        LIMIT 1
+        "#,
+        enrollment.program_id,
+        enrollment.current_week,
+        enrollment.current_day
+    )
+    .fetch_optional(&mut *conn)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let template = sqlx::query_as!(
+        WorkoutTemplate,
+        "SELECT * FROM workout_templates WHERE id = $1",
+        day.template_id
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+    let template = load_template_detail(conn, template).await?;
+
+    let prescriptions = sqlx::query_as!(
+        Prescription,
+        r#"
+        SELECT program_day_id, exercise_id, position, percent_of_tm, reps, is_amrap
+        FROM program_prescriptions
+        WHERE program_day_id = $1
+        ORDER BY position
+        "#,
+        day.id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let training_maxes: HashMap<Uuid, f64> = sqlx::query!(
+        "SELECT exercise_id, weight_kg FROM training_maxes WHERE user_id = $1",
+        enrollment.user_id
+    )
+    .fetch_all(&mut *conn)
+    .await?
+    .into_iter()
+    .map(|row| (row.exercise_id, row.weight_kg))
+    .collect();
+
+    let exercise_ids: Vec<Uuid> = template.exercises.iter().map(|e| e.exercise_id).collect();
+    let previous = last_performance(conn, enrollment.user_id, &exercise_ids).await?;
+    let template_exercise_ids: Vec<Uuid> = template.exercises.iter().map(|e| e.id).collect();
+    let recommendations =
+        latest_recommendations(conn, enrollment.user_id, &template_exercise_ids).await?;
+    let readiness = if day.is_deload {
+        None
+    } else {
+        session_adjustment(conn, enrollment.user_id).await?
+    };
+
+    let mut missing_training_maxes = Vec::new();
+    let exercises = template
+        .exercises
+        .iter()
+        .map(|exercise| {
+            let prescribed: Vec<&Prescription> = prescriptions
+                .iter()
+                .filter(|p| p.exercise_id == exercise.exercise_id)
+                .collect();
+
+            let mut sets: Vec<PlannedSet> = if prescribed.is_empty() {
+                let mut sets = planned_sets(
+                    exercise,
+                    previous.get(&exercise.exercise_id),
+                    recommendations.get(&exercise.id),
+                );
+                if day.is_deload {
+                    sets.truncate(sets.len().div_ceil(2));
+                }
+                sets.into_iter()
+                    .map(|set| PlannedSet {
+                        reps: set.reps,
+                        weight_kg: set.weight_kg.map(|w| {
+                            if day.is_deload {
+                                round_to_increment(w * DELOAD_WEIGHT_FACTOR, day.rounding_kg)
+                            } else {
+                                w
+                            }
+                        }),
+                        duration_seconds: set.duration_seconds,
+                        distance_m: set.distance_m,
+                        percent_of_tm: None,
+                        is_amrap: false,
+                    })
+                    .collect()
+            } else {
+                let training_max = training_maxes.get(&exercise.exercise_id);
+                if training_max.is_none() && !missing_training_maxes.contains(&exercise.exercise_id)
+                {
+                    missing_training_maxes.push(exercise.exercise_id);
+                }
+                prescribed
+                    .into_iter()
+                    .map(|p| PlannedSet {
+                        reps: Some(p.reps),
+                        weight_kg: training_max.map(|tm| {
+                            round_to_increment(tm * p.percent_of_tm / 100.0, day.rounding_kg)
+                        }),
+                        duration_seconds: None,
+                        distance_m: None,
+                        percent_of_tm: Some(p.percent_of_tm),
+                        is_amrap: p.is_amrap,
+                    })
+                    .collect()
+            };
+            if let Some(adjustment) = &readiness {
+                sets.truncate(lighter_set_count(adjustment, sets.len()));
+                for set in &mut sets {
+                    set.weight_kg = set
+                        .weight_kg
+                        .map(|w| lighter_weight(adjustment, w, day.rounding_kg));
+                }
+            }
+
+            PlannedExercise {
+                exercise_id: exercise.exercise_id,
+                exercise_name: exercise.exercise_name.clone(),
+                group_label: exercise.group_label.clone(),
+                rest_seconds: exercise.rest_seconds,
+                notes: exercise.notes.clone(),
+                sets,
+            }
+        })
+        .collect();
+
+    Ok(Some(NextWorkout {
+        enrollment,
+        program_name: day.program_name,
+        week_number: day.week_number,
+        day_number: day.day_number,
+        day_name: day.name.clone(),
+        is_deload: day.is_deload,
+        template_id: day.template_id,
+        workout_name: day.name.unwrap_or(template.template.name),
+        exercises,
+        missing_training_maxes,
+        readiness,
+    }))
+}
+
+/// Creates the in-progress workout for a planned program day and returns its id.
+pub async fn start_planned_workout(
+    conn: &mut PgConnection,
+    plan: &NextWorkout,
+) -> Result<Uuid, AppError> {
+    let workout_id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO workouts (user_id, name, template_id, notes, readiness_adjusted, visibility)
+        VALUES (
+            $1, $2, $3, $4, $5,
+            COALESCE((SELECT default_workout_visibility FROM user_profiles WHERE user_id = $1), 'private')
+        )
+        RETURNING id
+        "#,
+        plan.enrollment.user_id,
+        plan.workout_name,
+        plan.template_id,
+        plan.readiness.as_ref().map(|r| r.reason.clone()),
+        plan.readiness.is_some()
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    let exercises: Vec<SaveWorkoutExercise> = plan
+        .exercises
+        .iter()
+        .map(|exercise| SaveWorkoutExercise {
+            exercise_id: exercise.exercise_id,
+            notes: exercise.notes.clone(),
+            group_label: exercise.group_label.clone(),
+            sets: exercise
+                .sets
+                .iter()
+                .map(|set| SaveWorkoutSet {
+                    set_type: "working".to_string(),
+                    reps: set.reps,
+                    weight_kg: set.weight_kg,
+                    duration_seconds: set.duration_seconds,
+                    distance_m: set.distance_m,
+                    rpe: None,
+                    rir: None,
+                    notes: set.is_amrap.then(|| "AMRAP".to_string()),
+                    completed_at: None,
+                })
+                .collect(),
+        })
+        .collect();
+    insert_workout_exercises(conn, workout_id, &exercises).await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE program_enrollments
+        SET pending_workout_id = $2, current_week = $3, current_day = $4, updated_at = NOW()
+        WHERE id = $1
+        "#,
+        plan.enrollment.id,
+        workout_id,
+        plan.week_number,
+        plan.day_number
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(workout_id)
+}
+
+/// Makes a newly logged workout the pending workout of the active enrollment
+/// if it follows the template of the next program day, so finishing it
+/// advances the program like a started one. Enrollments with a program
+/// workout in progress are left alone.
+pub async fn claim_program_day(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    workout_id: Uuid,
+    template_id: Uuid,
+) -> Result<(), AppError> {
+    sqlx::query!(
+        r#"
+        UPDATE program_enrollments e
+        SET pending_workout_id = $2, current_week = d.week_number, current_day = d.day_number,
+            updated_at = NOW()
+        FROM (
+            SELECT DISTINCT ON (en.id) en.id AS enrollment_id, pd.week_number, pd.day_number, pd.template_id
+            FROM program_enrollments en
+            JOIN program_days pd ON pd.program_id = en.program_id
+                AND (pd.week_number, pd.day_number) >= (en.current_week, en.current_day)
+            WHERE en.user_id = $1 AND en.completed_at IS NULL
+            ORDER BY en.id, pd.week_number, pd.day_number
+        ) d
+        WHERE e.id = d.enrollment_id AND d.template_id = $3
+          AND (
+              e.pending_workout_id IS NULL
+              OR NOT EXISTS (
+                  SELECT 1 FROM workouts w
+                  WHERE w.id = e.pending_workout_id AND w.finished_at IS NULL
+              )
+          )
+        "#,
+        user_id,
+        workout_id,
+        template_id
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Moves the enrollment waiting on `workout_id` to the following program day,
+/// marking it completed after the last one. Does nothing for other workouts.
+pub async fn advance_enrollment(conn: &mut PgConnection, workout_id: Uuid) -> Result<(), AppError> {
+    let Some(enrollment) = sqlx::query_as!(
+        Enrollment,
+        r#"
+        SELECT * FROM program_enrollments
+        WHERE pending_workout_id = $1 AND completed_at IS NULL
+        FOR UPDATE
+        "#,
+        workout_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?
+    else {
+        return Ok(());
+    };
+
+    let next = sqlx::query!(
+        r#"
+        SELECT week_number, day_number FROM program_days
+        WHERE program_id = $1 AND (week_number, day_number) > ($2, $3)
+        ORDER BY week_number, day_number
+        LIMIT 1
+        "#,
+        enrollment.program_id,
+        enrollment.current_week,
+        enrollment.current_day
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    match next {
+        Some(next) => {
+            sqlx::query!(
+                r#"
+                UPDATE program_enrollments
+                SET current_week = $2, current_day = $3, pending_workout_id = NULL, updated_at = NOW()
+                WHERE id = $1
+                "#,
+                enrollment.id,
+                next.week_number,
+                next.day_number
+            )
+            .execute(&mut *conn)
+            .await?;
+        }
+        None => {
+            sqlx::query!(
+                r#"
+                UPDATE program_enrollments
+                SET pending_workout_id = NULL, completed_at = NOW(), updated_at = NOW()
+                WHERE id = $1
+                "#,
+                enrollment.id
+            )
+            .execute(&mut *conn)
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rounds_to_plate_increments() {
+        assert_eq!(round_to_increment(100.0 * 0.65, 2.5), 65.0);
+        assert_eq!(round_to_increment(117.0 * 0.85, 2.5), 100.0);
+        assert_eq!(round_to_increment(101.3, 5.0), 100.0);
+        assert_eq!(round_to_increment(43.9, 1.0), 44.0);
+    }
+}
@@ -0,0 +1,499 @@
+use sqlx::PgConnection;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    models::{
+        progression::{
+            ProgressionRecommendation, PROGRESSION_DOUBLE, PROGRESSION_FAILURE_DELOAD,
+            PROGRESSION_LINEAR, PROGRESSION_RPE,
+        },
+        template::TemplateExercise,
+        workout::WorkoutSet,
+    },
+    services::program::round_to_increment,
+};
+
+pub const DEFAULT_INCREMENT_KG: f64 = 2.5;
+/// Failed sessions in a row before the failure rule deloads.
+pub const FAILURE_DELOAD_AFTER: i32 = 3;
+pub const FAILURE_DELOAD_FACTOR: f64 = 0.9;
+
+/// What a rule gets to look at after a finished session.
+pub struct ProgressionContext<'a> {
+    pub target: &'a TemplateExercise,
+    /// Working sets logged for the exercise, in order.
+    pub sets: &'a [WorkoutSet],
+    pub increment_kg: f64,
+    /// Failure streak carried over from the previous recommendation.
+    pub previous_failures: i32,
+}
+
+impl ProgressionContext<'_> {
+    /// The heaviest weight used, which the next session builds on.
+    pub fn working_weight(&self) -> Option<f64> {
+        self.sets
+            .iter()
+            .filter_map(|s| s.weight_kg)
+            .reduce(f64::max)
+    }
+
+    pub fn lowest_reps(&self) -> Option<i32> {
+        self.sets.iter().filter_map(|s| s.reps).min()
+    }
+
+    /// Whether all planned sets were done with at least `reps` repetitions.
+    pub fn all_sets_reached(&self, reps: i32) -> bool {
+        self.sets.len() >= self.target.target_sets as usize
+            && self.sets.iter().all(|s| s.reps.unwrap_or(0) >= reps)
+    }
+
+    fn adjusted_weight(&self, delta_kg: f64) -> Option<f64> {
+        self.working_weight()
+            .map(|w| round_to_increment((w + delta_kg).max(0.0), self.increment_kg))
+    }
+
+    fn streak(&self, failed: bool) -> i32 {
+        if failed {
+            self.previous_failures + 1
+        } else {
+            0
+        }
+    }
+}
+
+pub struct Suggestion {
+    pub target_weight_kg: Option<f64>,
+    pub target_reps: Option<i32>,
+    pub failure_streak: i32,
+    pub reason: String,
+}
+
+pub trait ProgressionRule: Send + Sync {
+    fn evaluate(&self, ctx: &ProgressionContext) -> Suggestion;
+}
+
+/// Adds the increment whenever every set reaches the target reps.
+pub struct LinearProgression;
+
+impl ProgressionRule for LinearProgression {
+    fn evaluate(&self, ctx: &ProgressionContext) -> Suggestion {
+        let reps = ctx
+            .target
+            .target_reps_min
+            .or(ctx.target.target_reps_max)
+            .or(ctx.lowest_reps())
+            .unwrap_or(0);
+
+        if ctx.all_sets_reached(reps) {
+            Suggestion {
+                target_weight_kg: ctx.adjusted_weight(ctx.increment_kg),
+                target_reps: Some(reps),
+                failure_streak: 0,
+                reason: format!(
+                    "All sets reached {} reps, adding {} kg",
+                    reps, ctx.increment_kg
+                ),
+            }
+        } else {
+            Suggestion {
+                target_weight_kg: ctx.working_weight(),
+                target_reps: Some(reps),
+                failure_streak: ctx.streak(true),
+                reason: format!("Missed {} reps, repeating the weight", reps),
+            }
+        }
+    }
+}
+
+/// Works up through the rep range before adding weight and starting again at the bottom.
+pub struct DoubleProgression;
+
+impl ProgressionRule for DoubleProgression {
+    fn evaluate(&self, ctx: &ProgressionContext) -> Suggestion {
+        let min = ctx.target.target_reps_min.unwrap_or(0);
+        let max = ctx.target.target_reps_max.unwrap_or(min).max(min);
+
+        if ctx.all_sets_reached(max) {
+            return Suggestion {
+                target_weight_kg: ctx.adjusted_weight(ctx.increment_kg),
+                target_reps: Some(min),
+                failure_streak: 0,
+                reason: format!(
+                    "Top of the {}-{} rep range reached, adding {} kg",
+                    min, max, ctx.increment_kg
+                ),
+            };
+        }
+
+        let lowest = ctx.lowest_reps().unwrap_or(0);
+        let failed = lowest < min;
+        let reps = (lowest + 1).clamp(min, max);
+        Suggestion {
+            target_weight_kg: ctx.working_weight(),
+            target_reps: Some(reps),
+            failure_streak: ctx.streak(failed),
+            reason: format!("Aim for {} reps on every set", reps),
+        }
+    }
+}
+
+/// Adjusts the load by one increment per RPE point away from the target.
+pub struct RpeAutoregulation;
+
+impl ProgressionRule for RpeAutoregulation {
+    fn evaluate(&self, ctx: &ProgressionContext) -> Suggestion {
+        let target_rpe = ctx.target.target_rpe.unwrap_or(8.0);
+        let reps = ctx.target.target_reps_min.or(ctx.lowest_reps());
+        // Sets logged with reps in reserve count as RPE 10 - RIR.
+        let observed = ctx
+            .sets
+            .iter()
+            .filter_map(|s| s.rpe.or(s.rir.map(|rir| 10.0 - rir as f64)))
+            .reduce(f64::max);
+
+        let Some(observed) = observed else {
+            return Suggestion {
+                target_weight_kg: ctx.working_weight(),
+                target_reps: reps,
+                failure_streak: ctx.previous_failures,
+                reason: "No RPE logged, repeating the weight".into(),
+            };
+        };
+
+        let steps = (target_rpe - observed).trunc();
+        let (reason, failed) = if steps > 0.0 {
+            (
+                format!(
+                    "RPE {} was below the target of {}, adding weight",
+                    observed, target_rpe
+                ),
+                false,
+            )
+        } else if steps < 0.0 {
+            (
+                format!(
+                    "RPE {} was above the target of {}, reducing weight",
+                    observed, target_rpe
+                ),
+                true,
+            )
+        } else {
+            (
+                format!("RPE {} was on target, repeating the weight", observed),
+                false,
+            )
+        };
+
+        Suggestion {
+            target_weight_kg: ctx.adjusted_weight(steps * ctx.increment_kg),
+            target_reps: reps,
+            failure_streak: ctx.streak(failed),
+            reason,
+        }
+    }
+}
+
+/// Linear progression that deloads after repeated failed sessions.
+pub struct FailureDeload;
+
+impl ProgressionRule for FailureDeload {
+    fn evaluate(&self, ctx: &ProgressionContext) -> Suggestion {
+        let suggestion = LinearProgression.evaluate(ctx);
+        if suggestion.failure_streak < FAILURE_DELOAD_AFTER {
+            return suggestion;
+        }
+
+        Suggestion {
+            target_weight_kg: ctx
+                .working_weight()
+                .map(|w| round_to_increment(w * FAILURE_DELOAD_FACTOR, ctx.increment_kg)),
+            target_reps: suggestion.target_reps,
+            failure_streak: 0,
+            reason: format!(
+                "Missed the target {} sessions in a row, deloading by {}%",
+                FAILURE_DELOAD_AFTER,
+                ((1.0 - FAILURE_DELOAD_FACTOR) * 100.0).round()
+            ),
+        }
+    }
+}
+
+pub fn rule_for(name: &str) -> Option<Box<dyn ProgressionRule>> {
+    match name {
+        PROGRESSION_LINEAR => Some(Box::new(LinearProgression)),
+        PROGRESSION_DOUBLE => Some(Box::new(DoubleProgression)),
+        PROGRESSION_RPE => Some(Box::new(RpeAutoregulation)),
+        PROGRESSION_FAILURE_DELOAD => Some(Box::new(FailureDeload)),
+        _ => None,
+    }
+}
+
+/// Evaluates the progression rules of the template a finished workout was
+/// started from and stores one recommendation per rule-bearing exercise.
+/// Re-evaluating a workout replaces its earlier recommendations. Sessions
+/// lightened for low readiness keep the previous targets and failure streak.
+pub async fn evaluate_workout(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    workout_id: Uuid,
+) -> Result<Vec<ProgressionRecommendation>, AppError> {
+    sqlx::query!(
+        "DELETE FROM progression_recommendations WHERE workout_id = $1",
+        workout_id
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    let targets = sqlx::query_as!(
+        TemplateExercise,
+        r#"
+        SELECT te.id, te.template_id, te.exercise_id, e.name AS exercise_name, te.position,
+               te.group_label, te.group_type, te.target_sets, te.target_reps_min, te.target_reps_max,
+               te.target_weight_kg, te.target_rpe, te.rest_seconds, te.notes, te.progression_rule, te.progression_increment_kg
+        FROM workouts w
+        JOIN workout_templates t ON t.id = w.template_id AND t.user_id = w.user_id
+        JOIN template_exercises te ON te.template_id = t.id
+        JOIN exercises e ON e.id = te.exercise_id
+        WHERE w.id = $1 AND w.user_id = $2 AND te.progression_rule IS NOT NULL
+          AND NOT w.readiness_adjusted
+        ORDER BY te.position
+        "#,
+        workout_id,
+        user_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    if targets.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let sets = sqlx::query!(
+        r#"
+        SELECT we.exercise_id, s.id, s.workout_exercise_id, s.position, s.set_type, s.reps, s.weight_kg,
+               s.duration_seconds, s.distance_m, s.rpe, s.rir, s.notes, s.completed_at
+        FROM sets s
+        JOIN workout_exercises we ON we.id = s.workout_exercise_id
+        WHERE we.workout_id = $1 AND s.set_type <> 'warmup' AND s.reps IS NOT NULL
+        ORDER BY we.position, s.position
+        "#,
+        workout_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut sets_by_exercise: HashMap<Uuid, Vec<WorkoutSet>> = HashMap::new();
+    for row in sets {
+        sets_by_exercise
+            .entry(row.exercise_id)
+            .or_default()
+            .push(WorkoutSet {
+                id: row.id,
+                workout_exercise_id: row.workout_exercise_id,
+                position: row.position,
+                set_type: row.set_type,
+                reps: row.reps,
+                weight_kg: row.weight_kg,
+                duration_seconds: row.duration_seconds,
+                distance_m: row.distance_m,
+                rpe: row.rpe,
+                rir: row.rir,
+                notes: row.notes,
+                completed_at: row.completed_at,
+            });
+    }
+
+    let template_exercise_ids: Vec<Uuid> = targets.iter().map(|t| t.id).collect();
+    let previous = latest_recommendations(conn, user_id, &template_exercise_ids).await?;
+
+    let mut recommendations = Vec::new();
+    for target in &targets {
+        let (Some(sets), Some(rule)) = (
+            sets_by_exercise.get(&target.exercise_id),
+            target.progression_rule.as_deref().and_then(rule_for),
+        ) else {
+            continue;
+        };
+
+        let ctx = ProgressionContext {
+            target,
+            sets,
+            increment_kg: target
+                .progression_increment_kg
+                .unwrap_or(DEFAULT_INCREMENT_KG),
+            previous_failures: previous
+                .get(&target.id)
+                .map(|r| r.failure_streak)
+                .unwrap_or(0),
+        };
+        let suggestion = rule.evaluate(&ctx);
+
+        let recommendation = sqlx::query_as!(
+            ProgressionRecommendation,
+            r#"
+            INSERT INTO progression_recommendations
+                (user_id, template_exercise_id, exercise_id, workout_id, rule, target_weight_kg, target_reps, failure_streak, reason)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING *
+            "#,
+            user_id,
+            target.id,
+            target.exercise_id,
+            workout_id,
+            target.progression_rule,
+            suggestion.target_weight_kg,
+            suggestion.target_reps,
+            suggestion.failure_streak,
+            suggestion.reason
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+        recommendations.push(recommendation);
+    }
+
+    Ok(recommendations)
+}
+
+/// The most recent recommendation for each of the given template exercises.
+pub async fn latest_recommendations(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    template_exercise_ids: &[Uuid],
+) -> Result<HashMap<Uuid, ProgressionRecommendation>, AppError> {
+    let recommendations = sqlx::query_as!(
+        ProgressionRecommendation,
+        r#"
+        SELECT DISTINCT ON (template_exercise_id) *
+        FROM progression_recommendations
+        WHERE user_id = $1 AND template_exercise_id = ANY($2)
+        ORDER BY template_exercise_id, created_at DESC
+        "#,
+        user_id,
+        template_exercise_ids
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(recommendations
+        .into_iter()
+        .map(|r| (r.template_exercise_id, r))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(rule: &str) -> TemplateExercise {
+        TemplateExercise {
+            id: Uuid::nil(),
+            template_id: Uuid::nil(),
+            exercise_id: Uuid::nil(),
+            exercise_name: "Squat".into(),
+            position: 0,
+            group_label: None,
+            group_type: None,
+            target_sets: 3,
+            target_reps_min: Some(8),
+            target_reps_max: Some(12),
+            target_weight_kg: None,
+            target_rpe: Some(8.0),
+            rest_seconds: None,
+            notes: None,
+            progression_rule: Some(rule.into()),
+            progression_increment_kg: None,
+        }
+    }
+
+    fn sets(performed: &[(i32, f64, Option<f64>)]) -> Vec<WorkoutSet> {
+        performed
+            .iter()
+            .enumerate()
+            .map(|(i, &(reps, weight_kg, rpe))| WorkoutSet {
+                id: Uuid::nil(),
+                workout_exercise_id: Uuid::nil(),
+                position: i as i32,
+                set_type: "working".into(),
+                reps: Some(reps),
+                weight_kg: Some(weight_kg),
+                duration_seconds: None,
+                distance_m: None,
+                rpe,
+                rir: None,
+                notes: None,
+                completed_at: None,
+            })
+            .collect()
+    }
+
+    fn evaluate(rule: &str, performed: &[(i32, f64, Option<f64>)], failures: i32) -> Suggestion {
+        let target = target(rule);
+        let sets = sets(performed);
+        rule_for(rule).unwrap().evaluate(&ProgressionContext {
+            target: &target,
+            sets: &sets,
+            increment_kg: DEFAULT_INCREMENT_KG,
+            previous_failures: failures,
+        })
+    }
+
+    #[test]
+    fn linear_adds_weight_only_when_all_sets_succeed() {
+        let done = evaluate("linear", &[(8, 100.0, None); 3], 0);
+        assert_eq!(done.target_weight_kg, Some(102.5));
+        assert_eq!(done.failure_streak, 0);
+
+        let missed = evaluate(
+            "linear",
+            &[(8, 100.0, None), (8, 100.0, None), (6, 100.0, None)],
+            1,
+        );
+        assert_eq!(missed.target_weight_kg, Some(100.0));
+        assert_eq!(missed.failure_streak, 2);
+
+        let short = evaluate("linear", &[(8, 100.0, None); 2], 0);
+        assert_eq!(short.failure_streak, 1);
+    }
+
+    #[test]
+    fn double_progression_climbs_the_rep_range() {
+        let partway = evaluate(
+            "double_progression",
+            &[(10, 60.0, None), (9, 60.0, None), (9, 60.0, None)],
+            0,
+        );
+        assert_eq!(partway.target_weight_kg, Some(60.0));
+        assert_eq!(partway.target_reps, Some(10));
+
+        let top = evaluate("double_progression", &[(12, 60.0, None); 3], 0);
+        assert_eq!(top.target_weight_kg, Some(62.5));
+        assert_eq!(top.target_reps, Some(8));
+    }
+
+    #[test]
+    fn rpe_autoregulation_follows_effort() {
+        let easy = evaluate("rpe", &[(8, 100.0, Some(6.0)); 3], 0);
+        assert_eq!(easy.target_weight_kg, Some(105.0));
+
+        let hard = evaluate("rpe", &[(8, 100.0, Some(9.5)); 3], 0);
+        assert_eq!(hard.target_weight_kg, Some(97.5));
+        assert_eq!(hard.failure_streak, 1);
+
+        let on_target = evaluate("rpe", &[(8, 100.0, Some(8.5)); 3], 0);
+        assert_eq!(on_target.target_weight_kg, Some(100.0));
+    }
+
+    #[test]
+    fn failure_deload_triggers_after_repeated_misses() {
+        let missed = [(5, 100.0, None); 3];
+        let second = evaluate("failure_deload", &missed, 1);
+        assert_eq!(second.target_weight_kg, Some(100.0));
+        assert_eq!(second.failure_streak, 2);
+
+        let third = evaluate("failure_deload", &missed, 2);
+        assert_eq!(third.target_weight_kg, Some(90.0));
+        assert_eq!(third.failure_streak, 0);
+    }
+}
@@ -0,0 +1,396 @@
+use sqlx::PgConnection;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    models::record::{
+        RECORD_ESTIMATED_1RM, RECORD_FASTEST_TIME, RECORD_MAX_REPS, RECORD_MAX_VOLUME,
+        RECORD_MAX_WEIGHT,
+    },
+};
+
+/// Above this many reps one-rep max estimates are too unreliable to record.
+pub const MAX_REPS_FOR_1RM: i32 = 20;
+
+pub fn epley(weight_kg: f64, reps: i32) -> f64 {
+    weight_kg * (1.0 + reps as f64 / 30.0)
+}
+
+pub fn brzycki(weight_kg: f64, reps: i32) -> f64 {
+    weight_kg * 36.0 / (37.0 - reps as f64)
+}
+
+/// Estimated one-rep max: Brzycki up to 10 reps, where it is the more
+/// accurate of the two, Epley above that.
+pub fn estimate_1rm(weight_kg: f64, reps: i32) -> Option<f64> {
+    match reps {
+        1 => Some(weight_kg),
+        2..=10 => Some(brzycki(weight_kg, reps)),
+        11..=MAX_REPS_FOR_1RM => Some(epley(weight_kg, reps)),
+        _ => None,
+    }
+}
+
+/// A logged, completed working set.
+#[derive(Debug, Clone)]
+pub struct DoneSet {
+    pub exercise_id: Uuid,
+    pub set_id: Uuid,
+    pub reps: Option<i32>,
+    pub weight_kg: Option<f64>,
+    pub duration_seconds: Option<i32>,
+    pub distance_m: Option<f64>,
+    pub achieved_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate {
+    pub exercise_id: Uuid,
+    pub record_type: &'static str,
+    pub value: f64,
+    pub weight_kg: Option<f64>,
+    pub distance_m: Option<f64>,
+    pub set_id: Option<Uuid>,
+    pub achieved_at: chrono::DateTime<chrono::Utc>,
+}
+
+type RecordKey = (Uuid, String, Option<u64>, Option<u64>);
+
+fn key(
+    exercise_id: Uuid,
+    record_type: &str,
+    weight_kg: Option<f64>,
+    distance_m: Option<f64>,
+) -> RecordKey {
+    (
+        exercise_id,
+        record_type.to_string(),
+        weight_kg.map(f64::to_bits),
+        distance_m.map(f64::to_bits),
+    )
+}
+
+/// Whether `value` improves on `best`; lower is better for times.
+pub fn beats(record_type: &str, value: f64, best: f64) -> bool {
+    if record_type == RECORD_FASTEST_TIME {
+        value < best
+    } else {
+        value > best
+    }
+}
+
+/// The best value of every record kind reached by a session's sets.
+pub fn session_bests(sets: &[DoneSet]) -> Vec<Candidate> {
+    let mut bests: HashMap<RecordKey, Candidate> = HashMap::new();
+    let mut offer = |candidate: Candidate| {
+        let k = key(
+            candidate.exercise_id,
+            candidate.record_type,
+            candidate.weight_kg,
+            candidate.distance_m,
+        );
+        match bests.get(&k) {
+            Some(best) if !beats(candidate.record_type, candidate.value, best.value) => {}
+            _ => {
+                bests.insert(k, candidate);
+            }
+        }
+    };
+
+    let mut volume: HashMap<Uuid, (f64, chrono::DateTime<chrono::Utc>)> = HashMap::new();
+    for set in sets {
+        let single = |record_type, value, weight_kg, distance_m| Candidate {
+            exercise_id: set.exercise_id,
+            record_type,
+            value,
+            weight_kg,
+            distance_m,
+            set_id: Some(set.set_id),
+            achieved_at: set.achieved_at,
+        };
+
+        if let (Some(weight), Some(reps)) = (set.weight_kg, set.reps) {
+            if weight > 0.0 && reps > 0 {
+                offer(single(RECORD_MAX_WEIGHT, weight, None, None));
+                offer(single(RECORD_MAX_REPS, reps as f64, Some(weight), None));
+                if let Some(e1rm) = estimate_1rm(weight, reps) {
+                    offer(single(
+                        RECORD_ESTIMATED_1RM,
+                        (e1rm * 10.0).round() / 10.0,
+                        None,
+                        None,
+                    ));
+                }
+                let entry = volume
+                    .entry(set.exercise_id)
+                    .or_insert((0.0, set.achieved_at));
+                entry.0 += weight * reps as f64;
+                entry.1 = entry.1.max(set.achieved_at);
+            }
+        }
+        if let (Some(duration), Some(distance)) = (set.duration_seconds, set.distance_m) {
+            if duration > 0 && distance > 0.0 {
+                offer(single(
+                    RECORD_FASTEST_TIME,
+                    duration as f64,
+                    None,
+                    Some(distance),
+                ));
+            }
+        }
+    }
+    for (exercise_id, (value, achieved_at)) in volume {
+        offer(Candidate {
+            exercise_id,
+            record_type: RECORD_MAX_VOLUME,
+            value,
+            weight_kg: None,
+            distance_m: None,
+            set_id: None,
+            achieved_at,
+        });
+    }
+
+    bests.into_values().collect()
+}
+
+/// The exercises and starting point from which a change to workouts can
+/// alter records.
+#[derive(Debug, Clone)]
+pub struct RecordScope {
+    pub exercise_ids: Vec<Uuid>,
+    pub since: chrono::DateTime<chrono::Utc>,
+}
+
+impl RecordScope {
+    pub fn merge(mut self, other: RecordScope) -> Self {
+        self.exercise_ids.extend(other.exercise_ids);
+        self.exercise_ids.sort();
+        self.exercise_ids.dedup();
+        self.since = self.since.min(other.since);
+        self
+    }
+}
+
+/// What a change to the workout can affect: the exercises it contains or
+/// holds records for, from its start onward. Taken before an edit as well as
+/// after it, so exercises and start times that are changed away still count.
+pub async fn record_scope(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    workout_id: Uuid,
+) -> Result<RecordScope, AppError> {
+    let since = sqlx::query_scalar!(
+        "SELECT started_at FROM workouts WHERE id = $1 AND user_id = $2",
+        workout_id,
+        user_id
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+    let exercise_ids = sqlx::query_scalar!(
+        r#"
+        SELECT exercise_id AS "exercise_id!" FROM workout_exercises WHERE workout_id = $1
+        UNION
+        SELECT exercise_id FROM personal_records WHERE workout_id = $1
+        "#,
+        workout_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(RecordScope {
+        exercise_ids,
+        since,
+    })
+}
+
+/// Rebuilds the records within `scope`, going through its workouts oldest first, so each workout is measured against what came
+/// before it rather than against later sessions. Only completed sets count:
+/// those marked complete, or every set once the workout is finished.
+pub async fn recompute_personal_records(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    scope: &RecordScope,
+) -> Result<(), AppError> {
+    let RecordScope {
+        exercise_ids,
+        since,
+    } = scope;
+    if exercise_ids.is_empty() {
+        return Ok(());
+    }
+
+    sqlx::query!(
+        r#"
+        DELETE FROM personal_records r USING workouts w
+        WHERE w.id = r.workout_id AND r.user_id = $1 AND r.exercise_id = ANY($2)
+          AND w.started_at >= $3
+        "#,
+        user_id,
+        exercise_ids,
+        *since
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    // Records only ever improve, so the best of the earlier ones is the bar.
+    let mut bests: HashMap<RecordKey, f64> = sqlx::query!(
+        r#"
+        SELECT r.exercise_id, r.record_type, r.weight_kg, r.distance_m,
+               CASE WHEN r.record_type = $4 THEN MIN(r.value) ELSE MAX(r.value) END AS "best!"
+        FROM personal_records r
+        JOIN workouts w ON w.id = r.workout_id
+        WHERE r.user_id = $1 AND r.exercise_id = ANY($2) AND w.started_at < $3
+        GROUP BY r.exercise_id, r.record_type, r.weight_kg, r.distance_m
+        "#,
+        user_id,
+        exercise_ids,
+        *since,
+        RECORD_FASTEST_TIME
+    )
+    .fetch_all(&mut *conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        (
+            key(
+                row.exercise_id,
+                &row.record_type,
+                row.weight_kg,
+                row.distance_m,
+            ),
+            row.best,
+        )
+    })
+    .collect();
+
+    let sets: Vec<(Uuid, DoneSet)> = sqlx::query!(
+        r#"
+        SELECT w.id AS workout_id, we.exercise_id, s.id, s.reps, s.weight_kg,
+               s.duration_seconds, s.distance_m,
+               COALESCE(s.completed_at, w.started_at) AS "achieved_at!"
+        FROM sets s
+        JOIN workout_exercises we ON we.id = s.workout_exercise_id
+        JOIN workouts w ON w.id = we.workout_id
+        WHERE w.user_id = $1 AND we.exercise_id = ANY($2) AND w.started_at >= $3
+          AND s.set_type <> 'warmup'
+          AND (s.completed_at IS NOT NULL OR w.finished_at IS NOT NULL)
+        ORDER BY w.started_at, w.id
+        "#,
+        user_id,
+        exercise_ids,
+        *since
+    )
+    .fetch_all(&mut *conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        (
+            row.workout_id,
+            DoneSet {
+                exercise_id: row.exercise_id,
+                set_id: row.id,
+                reps: row.reps,
+                weight_kg: row.weight_kg,
+                duration_seconds: row.duration_seconds,
+                distance_m: row.distance_m,
+                achieved_at: row.achieved_at,
+            },
+        )
+    })
+    .collect();
+
+    for workout in sets.chunk_by(|a, b| a.0 == b.0) {
+        let workout_id = workout[0].0;
+        let done: Vec<DoneSet> = workout.iter().map(|(_, set)| set.clone()).collect();
+        for candidate in session_bests(&done) {
+            let k = key(
+                candidate.exercise_id,
+                candidate.record_type,
+                candidate.weight_kg,
+                candidate.distance_m,
+            );
+            if let Some(best) = bests.get(&k) {
+                if !beats(candidate.record_type, candidate.value, *best) {
+                    continue;
+                }
+            }
+
+            sqlx::query!(
+                r#"
+                INSERT INTO personal_records (user_id, exercise_id, workout_id, set_id, record_type, value, weight_kg, distance_m, achieved_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                "#,
+                user_id,
+                candidate.exercise_id,
+                workout_id,
+                candidate.set_id,
+                candidate.record_type,
+                candidate.value,
+                candidate.weight_kg,
+                candidate.distance_m,
+                candidate.achieved_at
+            )
+            .execute(&mut *conn)
+            .await?;
+            bests.insert(k, candidate.value);
+        }
+    }
+
+    Ok(())
+}
+
+/// Brings the records up to date after a workout was logged or finished.
+pub async fn detect_personal_records(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    workout_id: Uuid,
+) -> Result<(), AppError> {
+    let scope = record_scope(conn, user_id, workout_id).await?;
+    recompute_personal_records(conn, user_id, &scope).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimates_one_rep_max() {
+        assert_eq!(estimate_1rm(100.0, 1), Some(100.0));
+        assert!((estimate_1rm(100.0, 5).unwrap() - 112.5).abs() < 1e-9);
+        assert!((estimate_1rm(100.0, 15).unwrap() - 150.0).abs() < 1e-9);
+        assert_eq!(estimate_1rm(100.0, 0), None);
+        assert_eq!(estimate_1rm(100.0, 25), None);
+        assert!((epley(100.0, 5) - 116.666).abs() < 1e-3);
+    }
+
+    #[test]
+    fn keeps_the_best_set_per_record() {
+        let exercise_id = Uuid::new_v4();
+        let now = chrono::Utc::now();
+        let set = |reps, weight_kg| DoneSet {
+            exercise_id,
+            set_id: Uuid::new_v4(),
+            reps: Some(reps),
+            weight_kg: Some(weight_kg),
+            duration_seconds: None,
+            distance_m: None,
+            achieved_at: now,
+        };
+        let bests = session_bests(&[set(5, 100.0), set(3, 110.0), set(8, 100.0)]);
+        let find = |record_type, weight_kg| {
+            bests
+                .iter()
+                .find(|c| c.record_type == record_type && c.weight_kg == weight_kg)
+                .unwrap()
+                .value
+        };
+
+        assert_eq!(find(RECORD_MAX_WEIGHT, None), 110.0);
+        assert_eq!(find(RECORD_MAX_REPS, Some(100.0)), 8.0);
+        assert_eq!(find(RECORD_MAX_REPS, Some(110.0)), 3.0);
+        assert_eq!(find(RECORD_MAX_VOLUME, None), 500.0 + 330.0 + 800.0);
+        assert_eq!(find(RECORD_ESTIMATED_1RM, None), 124.1);
+    }
+}
@@ -0,0 +1,338 @@
+//! The subset of RFC 5545 recurrence rules that workout schedules support:
+//! `FREQ=DAILY|WEEKLY|MONTHLY` with optional `INTERVAL`, `BYDAY` (weekly only)
+//! and either `COUNT` or `UNTIL`. Weeks start on Monday.
+
+use chrono::{Datelike, Duration, Months, NaiveDate, Weekday};
+use std::{fmt, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceRule {
+    pub frequency: Frequency,
+    pub interval: u32,
+    /// Weekdays of weekly rules, Monday first; empty means the start's weekday.
+    pub by_day: Vec<Weekday>,
+    pub count: Option<u32>,
+    pub until: Option<NaiveDate>,
+}
+
+const MAX_INTERVAL: u32 = 365;
+const MAX_COUNT: u32 = 1000;
+const WEEKDAYS: [(&str, Weekday); 7] = [
+    ("MO", Weekday::Mon),
+    ("TU", Weekday::Tue),
+    ("WE", Weekday::Wed),
+    ("TH", Weekday::Thu),
+    ("FR", Weekday::Fri),
+    ("SA", Weekday::Sat),
+    ("SU", Weekday::Sun),
+];
+
+impl FromStr for RecurrenceRule {
+    type Err = String;
+
+    fn from_str(rule: &str) -> Result<Self, Self::Err> {
+        let rule = rule.trim();
+        let rule = rule.strip_prefix("RRULE:").unwrap_or(rule);
+
+        let mut frequency = None;
+        let mut interval = 1;
+        let mut by_day = Vec::new();
+        let mut count = None;
+        let mut until = None;
+        for part in rule.split(';').filter(|p| !p.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("Malformed recurrence rule part '{}'", part))?;
+            match key.to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        _ => return Err(format!("Unsupported frequency '{}'", value)),
+                    })
+                }
+                "INTERVAL" => {
+                    interval = value
+                        .parse()
+                        .ok()
+                        .filter(|i| (1..=MAX_INTERVAL).contains(i))
+                        .ok_or_else(|| format!("INTERVAL must be between 1 and {}", MAX_INTERVAL))?
+                }
+                "BYDAY" => {
+                    for day in value.split(',') {
+                        let weekday = WEEKDAYS
+                            .iter()
+                            .find(|(code, _)| code.eq_ignore_ascii_case(day))
+                            .map(|(_, weekday)| *weekday)
+                            .ok_or_else(|| format!("Unsupported BYDAY value '{}'", day))?;
+                        if !by_day.contains(&weekday) {
+                            by_day.push(weekday);
+                        }
+                    }
+                    by_day.sort_by_key(|d| d.num_days_from_monday());
+                }
+                "COUNT" => {
+                    count = Some(
+                        value
+                            .parse()
+                            .ok()
+                            .filter(|c| (1..=MAX_COUNT).contains(c))
+                            .ok_or_else(|| format!("COUNT must be between 1 and {}", MAX_COUNT))?,
+                    )
+                }
+                "UNTIL" => {
+                    // Only the date matters; schedules are planned in whole days.
+                    until = Some(
+                        value
+                            .get(..8)
+                            .and_then(|date| NaiveDate::parse_from_str(date, "%Y%m%d").ok())
+                            .ok_or_else(|| format!("Invalid UNTIL date '{}'", value))?,
+                    )
+                }
+                _ => return Err(format!("Unsupported recurrence rule part '{}'", key)),
+            }
+        }
+
+        let frequency = frequency.ok_or("Recurrence rule requires FREQ")?;
+        if count.is_some() && until.is_some() {
+            return Err("COUNT and UNTIL cannot be combined".into());
+        }
+        if !by_day.is_empty() && frequency != Frequency::Weekly {
+            return Err("BYDAY is only supported for weekly rules".into());
+        }
+
+        Ok(Self {
+            frequency,
+            interval,
+            by_day,
+            count,
+            until,
+        })
+    }
+}
+
+/// Canonical form, used for storage.
+impl fmt::Display for RecurrenceRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let frequency = match self.frequency {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+        };
+        write!(f, "FREQ={}", frequency)?;
+        if self.interval != 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if !self.by_day.is_empty() {
+            let days: Vec<&str> = self
+                .by_day
+                .iter()
+                .map(|day| WEEKDAYS[day.num_days_from_monday() as usize].0)
+                .collect();
+            write!(f, ";BYDAY={}", days.join(","))?;
+        }
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={}", count)?;
+        }
+        if let Some(until) = self.until {
+            write!(f, ";UNTIL={}", until.format("%Y%m%d"))?;
+        }
+        Ok(())
+    }
+}
+
+impl RecurrenceRule {
+    /// Occurrences of a series starting on `start` that fall within
+    /// `from..=to`. None precede `start`, but `start` itself is skipped when
+    /// a weekly rule's BYDAY does not include its weekday.
+    pub fn occurrences(&self, start: NaiveDate, from: NaiveDate, to: NaiveDate) -> Vec<NaiveDate> {
+        let last = self.until.map_or(to, |until| until.min(to));
+        let mut dates = Vec::new();
+        let mut emitted = 0;
+        // COUNT needs every earlier occurrence counted; without it, periods
+        // before the range are skipped instead of walked through.
+        let mut period = match self.count {
+            Some(_) => 0,
+            None => self.period_before(start, from),
+        };
+
+        while self.period_start(start, period) <= last {
+            for date in self.period_dates(start, period) {
+                if date < start {
+                    continue;
+                }
+                if date > last || self.count.is_some_and(|count| emitted >= count) {
+                    return dates;
+                }
+                emitted += 1;
+                if date >= from {
+                    dates.push(date);
+                }
+            }
+            period += 1;
+        }
+
+        dates
+    }
+
+    /// The last period starting on or before `date`, or the first one.
+    fn period_before(&self, start: NaiveDate, date: NaiveDate) -> u32 {
+        let elapsed = match self.frequency {
+            Frequency::Daily => (date - start).num_days(),
+            Frequency::Weekly => (date - self.period_start(start, 0)).num_days() / 7,
+            Frequency::Monthly => {
+                (date.year() - start.year()) as i64 * 12 + date.month0() as i64
+                    - start.month0() as i64
+            }
+        };
+        u32::try_from(elapsed.max(0) / self.interval as i64).unwrap_or(u32::MAX)
+    }
+
+    fn period_start(&self, start: NaiveDate, period: u32) -> NaiveDate {
+        let step = period * self.interval;
+        match self.frequency {
+            Frequency::Daily => start + Duration::days(step as i64),
+            Frequency::Weekly => {
+                let monday = start - Duration::days(start.weekday().num_days_from_monday() as i64);
+                monday + Duration::weeks(step as i64)
+            }
+            Frequency::Monthly => start.with_day(1).unwrap() + Months::new(step),
+        }
+    }
+
+    /// Dates of one period in order; months without the start's day have none.
+    /// A period never starts after any of its dates.
+    fn period_dates(&self, start: NaiveDate, period: u32) -> Vec<NaiveDate> {
+        let period_start = self.period_start(start, period);
+        match self.frequency {
+            Frequency::Daily => vec![period_start],
+            Frequency::Weekly if self.by_day.is_empty() => {
+                vec![period_start + Duration::days(start.weekday().num_days_from_monday() as i64)]
+            }
+            Frequency::Weekly => self
+                .by_day
+                .iter()
+                .map(|day| period_start + Duration::days(day.num_days_from_monday() as i64))
+                .collect(),
+            Frequency::Monthly => period_start.with_day(start.day()).into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    fn dates(rule: &str, start: &str, from: &str, to: &str) -> Vec<String> {
+        rule.parse::<RecurrenceRule>()
+            .unwrap()
+            .occurrences(date(start), date(from), date(to))
+            .iter()
+            .map(|d| d.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn parses_and_normalizes_rules() {
+        let rule: RecurrenceRule =
+            "RRULE:freq=weekly;byday=FR,MO,MO;INTERVAL=1;UNTIL=20250131T000000Z"
+                .parse()
+                .unwrap();
+        assert_eq!(rule.to_string(), "FREQ=WEEKLY;BYDAY=MO,FR;UNTIL=20250131");
+
+        assert!("INTERVAL=2".parse::<RecurrenceRule>().is_err());
+        assert!("FREQ=YEARLY".parse::<RecurrenceRule>().is_err());
+        assert!("FREQ=DAILY;BYDAY=MO".parse::<RecurrenceRule>().is_err());
+        assert!("FREQ=DAILY;COUNT=3;UNTIL=20250101"
+            .parse::<RecurrenceRule>()
+            .is_err());
+        assert!("FREQ=DAILY;BYSETPOS=1".parse::<RecurrenceRule>().is_err());
+    }
+
+    #[test]
+    fn expands_weekly_rules_within_a_range() {
+        // Wednesday start: the Monday of the first week is before it
+        assert_eq!(
+            dates(
+                "FREQ=WEEKLY;BYDAY=MO,WE,FR",
+                "2024-12-04",
+                "2024-12-01",
+                "2024-12-10"
+            ),
+            ["2024-12-04", "2024-12-06", "2024-12-09"]
+        );
+        assert_eq!(
+            dates(
+                "FREQ=WEEKLY;INTERVAL=2",
+                "2024-12-04",
+                "2024-12-10",
+                "2025-01-10"
+            ),
+            ["2024-12-18", "2025-01-01"]
+        );
+    }
+
+    #[test]
+    fn count_includes_occurrences_before_the_range() {
+        assert_eq!(
+            dates(
+                "FREQ=DAILY;INTERVAL=3;COUNT=4",
+                "2024-12-01",
+                "2024-12-05",
+                "2024-12-31"
+            ),
+            ["2024-12-07", "2024-12-10"]
+        );
+        assert_eq!(
+            dates(
+                "FREQ=DAILY;UNTIL=20241203",
+                "2024-12-01",
+                "2024-12-01",
+                "2024-12-31"
+            ),
+            ["2024-12-01", "2024-12-02", "2024-12-03"]
+        );
+    }
+
+    #[test]
+    fn skips_periods_before_the_range() {
+        assert_eq!(
+            dates(
+                "FREQ=WEEKLY;INTERVAL=2;BYDAY=TU,TH",
+                "2024-12-04",
+                "2124-12-01",
+                "2124-12-14"
+            ),
+            ["2124-12-05", "2124-12-07"]
+        );
+        assert_eq!(
+            dates(
+                "FREQ=MONTHLY;INTERVAL=5",
+                "2024-12-31",
+                "2125-01-01",
+                "2125-12-31"
+            ),
+            ["2125-05-31", "2125-10-31"]
+        );
+    }
+
+    #[test]
+    fn monthly_rules_skip_short_months() {
+        assert_eq!(
+            dates("FREQ=MONTHLY", "2024-12-31", "2024-12-01", "2025-05-31"),
+            ["2024-12-31", "2025-01-31", "2025-03-31", "2025-05-31"]
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use chrono::NaiveDate;
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    models::schedule::{
+        AdherenceReport, CalendarEntry, SaveScheduleRequest, Schedule, ScheduledSession,
+        SESSION_DONE, SESSION_MISSED, SESSION_PLANNED, SESSION_SKIPPED,
+    },
+    services::recurrence::RecurrenceRule,
+};
+
+/// Occurrence dates of a schedule within `from..=to`.
+pub fn schedule_dates(
+    schedule: &Schedule,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<NaiveDate>, AppError> {
+    let Some(rrule) = &schedule.rrule else {
+        let date = schedule.start_date;
+        return Ok((from..=to)
+            .contains(&date)
+            .then_some(date)
+            .into_iter()
+            .collect());
+    };
+    let rule: RecurrenceRule = rrule.parse().map_err(|e| {
+        tracing::error!("Stored rule of schedule {} is invalid: {}", schedule.id, e);
+        AppError::InternalServerError
+    })?;
+
+    Ok(rule.occurrences(schedule.start_date, from, to))
+}
+
+/// Checks the scheduled template or program day belongs to the user and
+/// returns the title to use.
+pub async fn schedule_title(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    payload: &SaveScheduleRequest,
+) -> Result<String, AppError> {
+    let default_title = if let Some(template_id) = payload.template_id {
+        sqlx::query_scalar!(
+            "SELECT name FROM workout_templates WHERE id = $1 AND user_id = $2",
+            template_id,
+            user_id
+        )
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Template not found".into()))?
+    } else if let Some(program_day_id) = payload.program_day_id {
+        sqlx::query_scalar!(
+            r#"
+            SELECT COALESCE(d.name, p.name || ' - Week ' || d.week_number || ' Day ' || d.day_number) AS "title!"
+            FROM program_days d
+            JOIN programs p ON p.id = d.program_id
+            WHERE d.id = $1 AND p.user_id = $2
+            "#,
+            program_day_id,
+            user_id
+        )
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Program day not found".into()))?
+    } else {
+        String::new()
+    };
+
+    Ok(payload.title.clone().unwrap_or(default_title))
+}
+
+/// Every schedule occurrence within `from..=to`, in date and time order.
+pub async fn calendar_entries(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    from: NaiveDate,
+    to: NaiveDate,
+    today: NaiveDate,
+) -> Result<Vec<CalendarEntry>, AppError> {
+    let schedules = sqlx::query_as!(
+        Schedule,
+        "SELECT * FROM workout_schedules WHERE user_id = $1 AND start_date <= $2",
+        user_id,
+        to
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let sessions = sqlx::query_as!(
+        ScheduledSession,
+        r#"
+        SELECT ss.* FROM scheduled_sessions ss
+        JOIN workout_schedules s ON s.id = ss.schedule_id
+        WHERE s.user_id = $1 AND ss.scheduled_date BETWEEN $2 AND $3
+        "#,
+        user_id,
+        from,
+        to
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut entries = Vec::new();
+    for schedule in &schedules {
+        for date in schedule_dates(schedule, from, to)? {
+            let session = sessions
+                .iter()
+                .find(|s| s.schedule_id == schedule.id && s.scheduled_date == date);
+            let status = match session.map(|s| s.status.as_str()) {
+                Some(SESSION_DONE) => SESSION_DONE,
+                Some(SESSION_SKIPPED) => SESSION_SKIPPED,
+                _ if date < today => SESSION_MISSED,
+                _ => SESSION_PLANNED,
+            };
+            entries.push(CalendarEntry {
+                schedule_id: schedule.id,
+                date,
+                time: schedule.start_time,
+                title: schedule.title.clone(),
+                template_id: schedule.template_id,
+                program_day_id: schedule.program_day_id,
+                status,
+                workout_id: session.and_then(|s| s.workout_id),
+            });
+        }
+    }
+    entries.sort_by_key(|e| (e.date, e.time));
+
+    Ok(entries)
+}
+
+/// Summarizes calendar entries. Sessions still ahead are not held against
+/// the user, and neither is today's until the day is over.
+pub fn adherence_report(
+    entries: &[CalendarEntry],
+    from: NaiveDate,
+    to: NaiveDate,
+) -> AdherenceReport {
+    let count = |status: &str| entries.iter().filter(|e| e.status == status).count() as i64;
+    let done = count(SESSION_DONE);
+    let skipped = count(SESSION_SKIPPED);
+    let missed = count(SESSION_MISSED);
+    let due = done + skipped + missed;
+
+    AdherenceReport {
+        from,
+        to,
+        planned: entries.len() as i64,
+        done,
+        skipped,
+        missed,
+        upcoming: count(SESSION_PLANNED),
+        adherence_percent: (due > 0).then(|| (done as f64 / due as f64 * 1000.0).round() / 10.0),
+    }
+}
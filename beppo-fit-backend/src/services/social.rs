@@ -0,0 +1,335 @@
+use chrono::{DateTime, Utc};
+use sqlx::{PgConnection, PgExecutor};
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    models::{
+        social::{
+            FeedAuthor, FeedItem, FeedRecord, FeedWorkout, Page, FEED_PERSONAL_RECORD,
+            FEED_WORKOUT, FOLLOW_ACCEPTED,
+        },
+        workout::WorkoutSummary,
+    },
+};
+
+/// Cursors point at the last item of a page: its timestamp in microseconds,
+/// which Postgres stores exactly, and its id to break ties.
+pub fn encode_cursor(at: DateTime<Utc>, id: Uuid) -> String {
+    format!("{}_{}", at.timestamp_micros(), id)
+}
+
+pub fn decode_cursor(cursor: &str) -> Result<(DateTime<Utc>, Uuid), AppError> {
+    let invalid = || AppError::BadRequest("Invalid cursor".into());
+    let (micros, id) = cursor.split_once('_').ok_or_else(invalid)?;
+    let at = micros
+        .parse()
+        .ok()
+        .and_then(DateTime::from_timestamp_micros)
+        .ok_or_else(invalid)?;
+    let id = id.parse().map_err(|_| invalid())?;
+    Ok((at, id))
+}
+
+/// Splits off the extra row fetched to detect a following page.
+pub fn into_page<T>(
+    mut items: Vec<T>,
+    limit: i64,
+    key: impl Fn(&T) -> (DateTime<Utc>, Uuid),
+) -> Page<T> {
+    let next_cursor = if items.len() as i64 > limit {
+        items.truncate(limit as usize);
+        items.last().map(|item| {
+            let (at, id) = key(item);
+            encode_cursor(at, id)
+        })
+    } else {
+        None
+    };
+    Page { items, next_cursor }
+}
+
+/// Whether either user blocked the other.
+pub async fn is_blocked<'e>(
+    executor: impl PgExecutor<'e>,
+    user_id: Uuid,
+    other_id: Uuid,
+) -> Result<bool, AppError> {
+    let blocked = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM blocks
+            WHERE (blocker_id = $1 AND blocked_id = $2) OR (blocker_id = $2 AND blocked_id = $1)
+        ) AS "blocked!"
+        "#,
+        user_id,
+        other_id
+    )
+    .fetch_one(executor)
+    .await?;
+
+    Ok(blocked)
+}
+
+/// Finished workouts and personal records of accepted follows, newest first.
+/// Private workouts and users blocked in either direction are left out.
+pub async fn load_feed(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    cursor: Option<(DateTime<Utc>, Uuid)>,
+    limit: i64,
+) -> Result<Page<FeedItem>, AppError> {
+    let (before_at, before_id) = cursor.unzip();
+
+    let rows = sqlx::query!(
+        r#"
+        WITH followed AS (
+            SELECT f.followee_id AS user_id
+            FROM follows f
+            WHERE f.follower_id = $1 AND f.status = $7
+              AND NOT EXISTS (
+                  SELECT 1 FROM blocks b
+                  WHERE (b.blocker_id = $1 AND b.blocked_id = f.followee_id)
+                     OR (b.blocker_id = f.followee_id AND b.blocked_id = $1)
+              )
+        ),
+        items AS (
+            SELECT $5::TEXT AS kind, w.id, w.finished_at AS occurred_at, w.user_id, w.id AS workout_id
+            FROM workouts w
+            WHERE w.user_id IN (SELECT user_id FROM followed)
+              AND w.finished_at IS NOT NULL
+              AND w.visibility IN ('public', 'followers')
+              AND ($2::TIMESTAMPTZ IS NULL OR (w.finished_at, w.id) < ($2, $3::UUID))
+            UNION ALL
+            SELECT $6::TEXT, r.id, r.achieved_at, r.user_id, r.workout_id
+            FROM personal_records r
+            JOIN workouts w ON w.id = r.workout_id
+            WHERE r.user_id IN (SELECT user_id FROM followed)
+              AND w.finished_at IS NOT NULL
+              AND w.visibility IN ('public', 'followers')
+              AND ($2::TIMESTAMPTZ IS NULL OR (r.achieved_at, r.id) < ($2, $3::UUID))
+            ORDER BY occurred_at DESC, id DESC
+            LIMIT $4
+        )
+        SELECT
+            i.kind AS "kind!",
+            i.id AS "id!",
+            i.occurred_at AS "occurred_at!",
+            i.user_id AS "user_id!",
+            i.workout_id AS "workout_id!",
+            p.display_name AS "display_name?",
+            p.avatar_url AS "avatar_url?",
+            w.name AS workout_name,
+            w.started_at,
+            stats.exercise_count AS "exercise_count?",
+            stats.set_count AS "set_count?",
+            stats.volume_kg AS "volume_kg?",
+            r.exercise_id AS "exercise_id?",
+            e.name AS "exercise_name?",
+            r.record_type AS "record_type?",
+            r.value AS "value?",
+            r.weight_kg
+        FROM items i
+        JOIN workouts w ON w.id = i.workout_id
+        LEFT JOIN user_profiles p ON p.user_id = i.user_id
+        LEFT JOIN personal_records r ON i.kind = $6 AND r.id = i.id
+        LEFT JOIN exercises e ON e.id = r.exercise_id
+        LEFT JOIN LATERAL (
+            SELECT
+                COUNT(DISTINCT we.id) AS exercise_count,
+                COUNT(s.id) AS set_count,
+                COALESCE(SUM(s.reps * s.weight_kg), 0) AS volume_kg
+            FROM workout_exercises we
+            LEFT JOIN sets s ON s.workout_exercise_id = we.id
+            WHERE we.workout_id = i.workout_id
+        ) stats ON i.kind = $5
+        ORDER BY i.occurred_at DESC, i.id DESC
+        "#,
+        user_id,
+        before_at,
+        before_id,
+        limit + 1,
+        FEED_WORKOUT,
+        FEED_PERSONAL_RECORD,
+        FOLLOW_ACCEPTED
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let items = rows
+        .into_iter()
+        .map(|row| {
+            let is_workout = row.kind == FEED_WORKOUT;
+            FeedItem {
+                kind: if is_workout {
+                    FEED_WORKOUT
+                } else {
+                    FEED_PERSONAL_RECORD
+                },
+                id: row.id,
+                occurred_at: row.occurred_at,
+                author: FeedAuthor {
+                    user_id: row.user_id,
+                    display_name: row.display_name,
+                    avatar_url: row.avatar_url,
+                },
+                workout_id: row.workout_id,
+                workout: is_workout.then(|| FeedWorkout {
+                    name: row.workout_name,
+                    started_at: row.started_at,
+                    exercise_count: row.exercise_count.unwrap_or(0),
+                    set_count: row.set_count.unwrap_or(0),
+                    volume_kg: row.volume_kg.unwrap_or(0.0),
+                }),
+                personal_record: match (
+                    row.exercise_id,
+                    row.exercise_name,
+                    row.record_type,
+                    row.value,
+                ) {
+                    (Some(exercise_id), Some(exercise_name), Some(record_type), Some(value)) => {
+                        Some(FeedRecord {
+                            exercise_id,
+                            exercise_name,
+                            record_type,
+                            value,
+                            weight_kg: row.weight_kg,
+                        })
+                    }
+                    _ => None,
+                },
+            }
+        })
+        .collect();
+
+    Ok(into_page(items, limit, |item| (item.occurred_at, item.id)))
+}
+
+/// Whether `viewer_id` may see a finished workout of another user. Public
+/// workouts are visible to everyone unless the profile is private;
+/// followers-only workouts need an accepted follow. [`load_user_workouts`]
+/// filters by the same rule.
+pub async fn can_view_workout(
+    conn: &mut PgConnection,
+    viewer_id: Uuid,
+    owner_id: Uuid,
+    workout_id: Uuid,
+) -> Result<bool, AppError> {
+    let visible = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM workouts w
+            LEFT JOIN user_profiles p ON p.user_id = w.user_id
+            WHERE w.id = $2 AND w.user_id = $3
+              AND w.finished_at IS NOT NULL
+              AND (
+                  (w.visibility = 'public' AND NOT COALESCE(p.is_private, FALSE))
+                  OR (
+                      w.visibility IN ('public', 'followers')
+                      AND EXISTS (
+                          SELECT 1 FROM follows f
+                          WHERE f.follower_id = $1 AND f.followee_id = w.user_id AND f.status = $4
+                      )
+                  )
+              )
+        ) AS "visible!"
+        "#,
+        viewer_id,
+        workout_id,
+        owner_id,
+        FOLLOW_ACCEPTED
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(visible)
+}
+
+/// Finished workouts of `owner_id` that `viewer_id` may see (see
+/// [`can_view_workout`]), newest first; owners see all of their own.
+pub async fn load_user_workouts(
+    conn: &mut PgConnection,
+    viewer_id: Uuid,
+    owner_id: Uuid,
+    cursor: Option<(DateTime<Utc>, Uuid)>,
+    limit: i64,
+) -> Result<Vec<WorkoutSummary>, AppError> {
+    let (before_at, before_id) = cursor.unzip();
+
+    let workouts = sqlx::query_as!(
+        WorkoutSummary,
+        r#"
+        SELECT
+            w.id,
+            w.name,
+            w.started_at,
+            w.finished_at,
+            COUNT(DISTINCT we.id) AS "exercise_count!",
+            COUNT(s.id) AS "set_count!",
+            COALESCE(SUM(s.reps * s.weight_kg), 0) AS "volume_kg!"
+        FROM workouts w
+        LEFT JOIN user_profiles p ON p.user_id = w.user_id
+        LEFT JOIN workout_exercises we ON we.workout_id = w.id
+        LEFT JOIN sets s ON s.workout_exercise_id = we.id
+        WHERE w.user_id = $2
+          AND w.finished_at IS NOT NULL
+          AND (
+              w.user_id = $1
+              OR (w.visibility = 'public' AND NOT COALESCE(p.is_private, FALSE))
+              OR (
+                  w.visibility IN ('public', 'followers')
+                  AND EXISTS (
+                      SELECT 1 FROM follows f
+                      WHERE f.follower_id = $1 AND f.followee_id = w.user_id AND f.status = $6
+                  )
+              )
+          )
+          AND ($3::TIMESTAMPTZ IS NULL OR (w.finished_at, w.id) < ($3, $4::UUID))
+        GROUP BY w.id
+        ORDER BY w.finished_at DESC, w.id DESC
+        LIMIT $5
+        "#,
+        viewer_id,
+        owner_id,
+        before_at,
+        before_id,
+        limit,
+        FOLLOW_ACCEPTED
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(workouts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_round_trip() {
+        let at = DateTime::parse_from_rfc3339("2024-12-20T18:30:15.123456Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let id = Uuid::new_v4();
+        assert_eq!(decode_cursor(&encode_cursor(at, id)).unwrap(), (at, id));
+
+        assert!(decode_cursor("").is_err());
+        assert!(decode_cursor("abc_def").is_err());
+        assert!(decode_cursor(&format!("1734719415123456_{}x", id)).is_err());
+    }
+
+    #[test]
+    fn pages_only_when_more_rows() {
+        let at = Utc::now();
+        let ids: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+
+        let page = into_page(ids.clone(), 3, |id| (at, *id));
+        assert_eq!(page.items.len(), 3);
+        assert!(page.next_cursor.is_none());
+
+        let page = into_page(ids.clone(), 2, |id| (at, *id));
+        assert_eq!(page.items, ids[..2]);
+        assert_eq!(page.next_cursor, Some(encode_cursor(at, ids[1])));
+    }
+}
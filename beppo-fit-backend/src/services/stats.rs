@@ -0,0 +1,54 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{error::AppError, models::stats::PERIODS, services::profile::ensure_profile};
+
+const DEFAULT_RANGE_DAYS: i64 = 90;
+const MAX_RANGE_DAYS: i64 = 3660;
+
+/// A stats window resolved against the user's timezone.
+pub struct StatsRange {
+    /// IANA name handed to Postgres for local-date bucketing.
+    pub timezone: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub period: String,
+}
+
+/// Defaults to the last 90 days up to today and weekly periods.
+pub async fn resolve_range(
+    pool: &PgPool,
+    user_id: Uuid,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    period: Option<&str>,
+) -> Result<StatsRange, AppError> {
+    let period = period.unwrap_or("week").to_string();
+    if !PERIODS.contains(&period.as_str()) {
+        return Err(AppError::BadRequest(format!(
+            "Unknown period '{}'. Allowed: {}",
+            period,
+            PERIODS.join(", ")
+        )));
+    }
+
+    let profile = ensure_profile(&mut *pool.acquire().await?, user_id).await?;
+    let to = to.unwrap_or_else(|| profile.today());
+    let from = from.unwrap_or(to - Duration::days(DEFAULT_RANGE_DAYS - 1));
+    if from > to {
+        return Err(AppError::BadRequest("from must not be after to".into()));
+    }
+    if (to - from).num_days() >= MAX_RANGE_DAYS {
+        return Err(AppError::BadRequest(
+            "Date range must be at most ten years".into(),
+        ));
+    }
+
+    Ok(StatsRange {
+        timezone: profile.tz().name().to_string(),
+        start: profile.start_of_day(from),
+        end: profile.start_of_day(to + Duration::days(1)),
+        period,
+    })
+}
@@ -0,0 +1,335 @@
+use axum::async_trait;
+use hmac::{Hmac, Mac};
+use reqwest::{StatusCode, Url};
+use sha2::{Digest, Sha256};
+use std::{
+    env,
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
+
+use crate::error::AppError;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Blob storage for user media. Keys are `/`-separated relative paths.
+#[async_trait]
+pub trait ObjectStorage: Send + Sync {
+    async fn put(&self, key: &str, data: Vec<u8>, content_type: &str) -> Result<(), AppError>;
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, AppError>;
+    async fn delete(&self, key: &str) -> Result<(), AppError>;
+}
+
+/// Selects the storage backend from `STORAGE_BACKEND` (`local` or `s3`).
+pub fn storage_from_env() -> Arc<dyn ObjectStorage> {
+    match env::var("STORAGE_BACKEND").as_deref() {
+        Ok("s3") => Arc::new(S3Storage::from_env()),
+        _ => {
+            let root = env::var("STORAGE_LOCAL_PATH").unwrap_or_else(|_| "./media".to_string());
+            Arc::new(LocalStorage::new(root))
+        }
+    }
+}
+
+/// Stores objects as files below a root directory.
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path_for(&self, key: &str) -> Result<PathBuf, AppError> {
+        let relative = Path::new(key);
+        if !relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+        {
+            return Err(AppError::StorageError(format!(
+                "Invalid object key {}",
+                key
+            )));
+        }
+        Ok(self.root.join(relative))
+    }
+}
+
+#[async_trait]
+impl ObjectStorage for LocalStorage {
+    async fn put(&self, key: &str, data: Vec<u8>, _content_type: &str) -> Result<(), AppError> {
+        let path = self.path_for(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| AppError::StorageError(e.to_string()))?;
+        }
+        tokio::fs::write(&path, data)
+            .await
+            .map_err(|e| AppError::StorageError(e.to_string()))
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, AppError> {
+        match tokio::fs::read(self.path_for(key)?).await {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(AppError::StorageError(e.to_string())),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        match tokio::fs::remove_file(self.path_for(key)?).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(AppError::StorageError(e.to_string())),
+        }
+    }
+}
+
+/// Talks to an S3-compatible service (AWS, MinIO, ...) using path-style URLs
+/// and AWS Signature Version 4.
+pub struct S3Storage {
+    client: reqwest::Client,
+    endpoint: Url,
+    bucket: String,
+    region: String,
+    access_key: String,
+    secret_key: String,
+}
+
+impl S3Storage {
+    pub fn new(
+        endpoint: &str,
+        bucket: &str,
+        region: &str,
+        access_key: &str,
+        secret_key: &str,
+    ) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            endpoint: Url::parse(endpoint).expect("Invalid S3_ENDPOINT"),
+            bucket: bucket.to_string(),
+            region: region.to_string(),
+            access_key: access_key.to_string(),
+            secret_key: secret_key.to_string(),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        let endpoint = env::var("S3_ENDPOINT").expect("Missing S3_ENDPOINT");
+        let bucket = env::var("S3_BUCKET").expect("Missing S3_BUCKET");
+        let region = env::var("S3_REGION").unwrap_or_else(|_| "us-east-1".to_string());
+        let access_key = env::var("S3_ACCESS_KEY").expect("Missing S3_ACCESS_KEY");
+        let secret_key = env::var("S3_SECRET_KEY").expect("Missing S3_SECRET_KEY");
+
+        Self::new(&endpoint, &bucket, &region, &access_key, &secret_key)
+    }
+
+    async fn send(
+        &self,
+        method: reqwest::Method,
+        key: &str,
+        body: Vec<u8>,
+        content_type: Option<&str>,
+    ) -> Result<reqwest::Response, AppError> {
+        let path = format!(
+            "/{}/{}",
+            uri_encode(&self.bucket),
+            key.split('/').map(uri_encode).collect::<Vec<_>>().join("/")
+        );
+        let mut url = self.endpoint.clone();
+        url.set_path(&path);
+
+        let host = match url.port() {
+            Some(port) => format!("{}:{}", url.host_str().unwrap_or_default(), port),
+            None => url.host_str().unwrap_or_default().to_string(),
+        };
+        let now = chrono::Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let payload_hash = hex::encode(Sha256::digest(&body));
+        let authorization = sign_v4(
+            &SigningRequest {
+                method: method.as_str(),
+                path: &path,
+                host: &host,
+                payload_hash: &payload_hash,
+                amz_date: &amz_date,
+            },
+            &self.region,
+            &self.access_key,
+            &self.secret_key,
+        );
+
+        let mut request = self
+            .client
+            .request(method, url)
+            .header("x-amz-date", amz_date)
+            .header("x-amz-content-sha256", payload_hash)
+            .header("authorization", authorization);
+        if let Some(content_type) = content_type {
+            request = request.header("content-type", content_type);
+        }
+
+        request
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| AppError::StorageError(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl ObjectStorage for S3Storage {
+    async fn put(&self, key: &str, data: Vec<u8>, content_type: &str) -> Result<(), AppError> {
+        let response = self
+            .send(reqwest::Method::PUT, key, data, Some(content_type))
+            .await?;
+        if !response.status().is_success() {
+            return Err(AppError::StorageError(format!(
+                "S3 PUT {} failed with {}",
+                key,
+                response.status()
+            )));
+        }
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, AppError> {
+        let response = self
+            .send(reqwest::Method::GET, key, Vec::new(), None)
+            .await?;
+        match response.status() {
+            StatusCode::NOT_FOUND => Ok(None),
+            status if status.is_success() => response
+                .bytes()
+                .await
+                .map(|b| Some(b.to_vec()))
+                .map_err(|e| AppError::StorageError(e.to_string())),
+            status => Err(AppError::StorageError(format!(
+                "S3 GET {} failed with {}",
+                key, status
+            ))),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        let response = self
+            .send(reqwest::Method::DELETE, key, Vec::new(), None)
+            .await?;
+        if !response.status().is_success() && response.status() != StatusCode::NOT_FOUND {
+            return Err(AppError::StorageError(format!(
+                "S3 DELETE {} failed with {}",
+                key,
+                response.status()
+            )));
+        }
+        Ok(())
+    }
+}
+
+struct SigningRequest<'a> {
+    method: &'a str,
+    path: &'a str,
+    host: &'a str,
+    payload_hash: &'a str,
+    amz_date: &'a str,
+}
+
+/// Builds the `Authorization` header value for an S3 request.
+fn sign_v4(request: &SigningRequest, region: &str, access_key: &str, secret_key: &str) -> String {
+    let date = &request.amz_date[..8];
+    let scope = format!("{}/{}/s3/aws4_request", date, region);
+    let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+
+    let canonical_request = format!(
+        "{}\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+        request.method,
+        request.path,
+        request.host,
+        request.payload_hash,
+        request.amz_date,
+        signed_headers,
+        request.payload_hash
+    );
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        request.amz_date,
+        scope,
+        hex::encode(Sha256::digest(canonical_request.as_bytes()))
+    );
+
+    let key = hmac(format!("AWS4{}", secret_key).as_bytes(), date.as_bytes());
+    let key = hmac(&key, region.as_bytes());
+    let key = hmac(&key, b"s3");
+    let key = hmac(&key, b"aws4_request");
+    let signature = hex::encode(hmac(&key, string_to_sign.as_bytes()));
+
+    format!(
+        "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+        access_key, scope, signed_headers, signature
+    )
+}
+
+fn hmac(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn uri_encode(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_local_storage_round_trip() {
+        let root = env::temp_dir().join(format!("beppofit-storage-{}", uuid::Uuid::new_v4()));
+        let storage = LocalStorage::new(&root);
+
+        storage
+            .put("a/b/object.bin", vec![1, 2, 3], "application/octet-stream")
+            .await
+            .unwrap();
+        assert_eq!(
+            storage.get("a/b/object.bin").await.unwrap(),
+            Some(vec![1, 2, 3])
+        );
+
+        storage.delete("a/b/object.bin").await.unwrap();
+        assert_eq!(storage.get("a/b/object.bin").await.unwrap(), None);
+        assert!(storage.get("../escape").await.is_err());
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn test_sign_v4_matches_reference_signature() {
+        // Reference value computed with botocore's S3SigV4Auth for the same request.
+        let request = SigningRequest {
+            method: "GET",
+            path: "/media/user/photo.jpg",
+            host: "localhost:9000",
+            payload_hash: "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+            amz_date: "20261019T002839Z",
+        };
+
+        assert_eq!(
+            sign_v4(&request, "us-east-1", "minio", "minio123"),
+            "AWS4-HMAC-SHA256 Credential=minio/20261019/us-east-1/s3/aws4_request, \
+             SignedHeaders=host;x-amz-content-sha256;x-amz-date, \
+             Signature=dd4eea22282fe98dfe28c392f067bf4b3938589dd9dca76b50a68b4fa1d909b9"
+        );
+    }
+}
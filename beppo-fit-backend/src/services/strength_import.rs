@@ -0,0 +1,998 @@
+//! Readers for the CSV exports of Strong, Hevy and FitNotes, and the
+//! preview and commit steps of an import.
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use sqlx::PgConnection;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    models::{
+        measurement::{mass_to_kg, KG_PER_LB},
+        profile::UserProfile,
+        strength_import::{
+            ExerciseMapping, ImportPreview, ImportResult, ImportedWorkoutPreview, MatchCandidate,
+            StrengthImport, IMPORT_SOURCE_FITNOTES, IMPORT_SOURCE_HEVY, IMPORT_SOURCE_STRONG,
+            MAPPING_CONFIRMED, MAPPING_MATCHED, MAPPING_SKIPPED, MAPPING_UNMATCHED,
+        },
+    },
+    services::{
+        achievement::check_achievements,
+        exercise_match::{rank_exercises, AUTO_MATCH_SCORE},
+        goal::refresh_goals,
+        record::{recompute_personal_records, RecordScope},
+    },
+};
+
+/// Largest history file we accept; years of daily training stay well below.
+pub const MAX_IMPORT_FILE_BYTES: usize = 10 * 1024 * 1024;
+/// Uploads that were neither committed nor discarded are dropped after this.
+pub const IMPORT_RETENTION_HOURS: i64 = 24;
+
+const MAX_WARNINGS: usize = 50;
+const MAX_CANDIDATES: usize = 3;
+const M_PER_MILE: f64 = 1609.344;
+/// FitNotes only records dates; its workouts are placed at noon local time.
+const DATE_ONLY_HOUR: u32 = 12;
+// Imported rows are held to the limits of manually logged workouts.
+const MAX_REPS: i32 = 10_000;
+const MAX_WEIGHT_KG: f64 = 2000.0;
+const MAX_DURATION_SECONDS: i32 = 86_400;
+const MAX_DISTANCE_M: f64 = 1_000_000.0;
+const MAX_WORKOUT_NAME_CHARS: usize = 120;
+const MAX_EXERCISE_NAME_CHARS: usize = 200;
+const MAX_WORKOUT_NOTES_CHARS: usize = 5000;
+const MAX_EXERCISE_NOTES_CHARS: usize = 2000;
+const MAX_SET_NOTES_CHARS: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedSet {
+    pub set_type: &'static str,
+    pub reps: Option<i32>,
+    pub weight_kg: Option<f64>,
+    pub duration_seconds: Option<i32>,
+    pub distance_m: Option<f64>,
+    pub rpe: Option<f64>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedExercise {
+    /// The exercise name as the other app spells it.
+    pub name: String,
+    pub notes: Option<String>,
+    pub group_label: Option<String>,
+    pub sets: Vec<ImportedSet>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedWorkout {
+    pub name: Option<String>,
+    pub notes: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub exercises: Vec<ImportedExercise>,
+}
+
+/// A history file's workouts in import order, oldest first.
+#[derive(Debug, Default)]
+pub struct ParsedHistory {
+    pub source: &'static str,
+    pub workouts: Vec<ImportedWorkout>,
+    pub warnings: Vec<String>,
+}
+
+impl ParsedHistory {
+    fn warn(&mut self, line: u64, message: &str) {
+        if self.warnings.len() < MAX_WARNINGS {
+            self.warnings.push(format!("Line {}: {}", line, message));
+        }
+    }
+
+    /// Distinct exercise names with their number of sets, in order of appearance.
+    pub fn exercise_names(&self) -> Vec<(String, usize)> {
+        let mut names: Vec<(String, usize)> = Vec::new();
+        for exercise in self.workouts.iter().flat_map(|w| &w.exercises) {
+            match names.iter_mut().find(|(name, _)| *name == exercise.name) {
+                Some((_, count)) => *count += exercise.sets.len(),
+                None => names.push((exercise.name.clone(), exercise.sets.len())),
+            }
+        }
+        names
+    }
+}
+
+/// Collects rows into workouts, keeping exercises in the order they appear.
+#[derive(Default)]
+struct HistoryBuilder {
+    workouts: Vec<ImportedWorkout>,
+    index: HashMap<(DateTime<Utc>, Option<String>), usize>,
+}
+
+impl HistoryBuilder {
+    fn workout_index(&mut self, started_at: DateTime<Utc>, name: Option<&str>) -> usize {
+        let name = name.map(|n| n.chars().take(MAX_WORKOUT_NAME_CHARS).collect::<String>());
+        let workouts = &mut self.workouts;
+        *self
+            .index
+            .entry((started_at, name.clone()))
+            .or_insert_with(|| {
+                workouts.push(ImportedWorkout {
+                    name,
+                    notes: None,
+                    started_at,
+                    finished_at: None,
+                    exercises: Vec::new(),
+                });
+                workouts.len() - 1
+            })
+    }
+
+    fn workout(&mut self, started_at: DateTime<Utc>, name: Option<&str>) -> &mut ImportedWorkout {
+        let index = self.workout_index(started_at, name);
+        &mut self.workouts[index]
+    }
+
+    fn finish(mut self) -> Vec<ImportedWorkout> {
+        self.workouts.retain(|w| !w.exercises.is_empty());
+        self.workouts.sort_by_key(|w| w.started_at);
+        self.workouts
+    }
+}
+
+impl ImportedWorkout {
+    fn exercise(&mut self, name: &str) -> &mut ImportedExercise {
+        let position = match self.exercises.iter().position(|e| e.name == name) {
+            Some(position) => position,
+            None => {
+                self.exercises.push(ImportedExercise {
+                    name: name.to_string(),
+                    notes: None,
+                    group_label: None,
+                    sets: Vec::new(),
+                });
+                self.exercises.len() - 1
+            }
+        };
+        &mut self.exercises[position]
+    }
+}
+
+/// A CSV file with its header names lowercased for lookup.
+struct Table {
+    headers: HashMap<String, usize>,
+    records: Vec<csv::StringRecord>,
+}
+
+impl Table {
+    fn read(bytes: &[u8]) -> Result<Self, AppError> {
+        let text = std::str::from_utf8(bytes)
+            .map_err(|_| AppError::BadRequest("The file is not UTF-8 encoded CSV".into()))?
+            .trim_start_matches('\u{feff}');
+        // Strong writes semicolons in locales that use a decimal comma.
+        let first_line = text.lines().next().unwrap_or_default();
+        let delimiter = if first_line.matches(';').count() > first_line.matches(',').count() {
+            b';'
+        } else {
+            b','
+        };
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(delimiter)
+            .flexible(true)
+            .from_reader(text.as_bytes());
+        let headers = reader
+            .headers()
+            .map_err(|e| AppError::BadRequest(format!("Invalid CSV: {}", e)))?
+            .iter()
+            .enumerate()
+            .map(|(i, h)| (h.trim().to_lowercase(), i))
+            .collect();
+        let records = reader
+            .records()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::BadRequest(format!("Invalid CSV: {}", e)))?;
+        Ok(Self { headers, records })
+    }
+
+    fn has(&self, name: &str) -> bool {
+        self.headers.contains_key(name)
+    }
+
+    /// The trimmed value of a column, `None` when missing or empty.
+    fn get<'r>(&self, record: &'r csv::StringRecord, name: &str) -> Option<&'r str> {
+        self.headers
+            .get(name)
+            .and_then(|&i| record.get(i))
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+    }
+
+    fn number(&self, record: &csv::StringRecord, name: &str) -> Option<f64> {
+        self.get(record, name)
+            .and_then(|v| v.replace(',', ".").parse::<f64>().ok())
+            .filter(|v| v.is_finite() && *v > 0.0)
+    }
+
+    /// Free text cut to what the column holds.
+    fn text(&self, record: &csv::StringRecord, name: &str, max_chars: usize) -> Option<String> {
+        self.get(record, name)
+            .map(|v| v.chars().take(max_chars).collect())
+    }
+}
+
+fn line_of(record: &csv::StringRecord) -> u64 {
+    record.position().map(|p| p.line()).unwrap_or(0)
+}
+
+/// Timestamps as the apps write them, in the user's local time unless they
+/// carry an offset.
+fn parse_timestamp(value: &str, profile: &UserProfile) -> Option<DateTime<Utc>> {
+    if let Ok(instant) = DateTime::parse_from_rfc3339(value) {
+        return Some(instant.with_timezone(&Utc));
+    }
+    const FORMATS: &[&str] = &[
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%dT%H:%M:%S",
+        "%d %b %Y, %H:%M",
+        "%d %b %Y %H:%M",
+    ];
+    FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .map(|local| profile.local_instant(local))
+}
+
+/// Strong writes durations as "1h 5m" or "45m".
+fn parse_duration_text(value: &str) -> Option<i64> {
+    let mut seconds = 0;
+    let mut found = false;
+    for part in value.split_whitespace() {
+        let (number, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+        let number: i64 = number.parse().ok()?;
+        seconds += match unit {
+            "h" => number * 3600,
+            "m" | "min" => number * 60,
+            "s" => number,
+            _ => return None,
+        };
+        found = true;
+    }
+    found.then_some(seconds)
+}
+
+/// FitNotes writes times as "H:MM:SS" or "MM:SS".
+fn parse_clock(value: &str) -> Option<i32> {
+    value
+        .split(':')
+        .try_fold(0i32, |total, part| {
+            total
+                .checked_mul(60)?
+                .checked_add(part.parse::<i32>().ok()?)
+        })
+        .filter(|seconds| *seconds > 0)
+}
+
+fn valid_rpe(rpe: Option<f64>) -> Option<f64> {
+    rpe.filter(|r| (1.0..=10.0).contains(r))
+}
+
+/// Why a row is left out: its exercise name does not fit or a value is beyond
+/// what could have been lifted or run.
+fn row_problem(exercise: &str, set: &ImportedSet) -> Option<&'static str> {
+    if exercise.chars().count() > MAX_EXERCISE_NAME_CHARS {
+        Some("exercise name longer than 200 characters")
+    } else if set.reps.is_some_and(|r| r > MAX_REPS) {
+        Some("reps above 10000")
+    } else if set.weight_kg.is_some_and(|w| w > MAX_WEIGHT_KG) {
+        Some("weight above 2000 kg")
+    } else if set
+        .duration_seconds
+        .is_some_and(|d| d > MAX_DURATION_SECONDS)
+    {
+        Some("duration above 24 hours")
+    } else if set.distance_m.is_some_and(|d| d > MAX_DISTANCE_M) {
+        Some("distance above 1000 km")
+    } else {
+        None
+    }
+}
+
+fn has_values(set: &ImportedSet) -> bool {
+    set.reps.is_some()
+        || set.weight_kg.is_some()
+        || set.duration_seconds.is_some()
+        || set.distance_m.is_some()
+}
+
+fn parse_strong(table: &Table, units: &str, profile: &UserProfile, history: &mut ParsedHistory) {
+    let mut builder = HistoryBuilder::default();
+    for record in &table.records {
+        let line = line_of(record);
+        let set_order = table.get(record, "set order").unwrap_or_default();
+        if set_order.to_lowercase().contains("rest") {
+            continue;
+        }
+        let Some(started_at) = table
+            .get(record, "date")
+            .and_then(|d| parse_timestamp(d, profile))
+        else {
+            history.warn(line, "unreadable date");
+            continue;
+        };
+        let Some(exercise) = table.get(record, "exercise name") else {
+            history.warn(line, "missing exercise name");
+            continue;
+        };
+        let set = ImportedSet {
+            set_type: match set_order {
+                "W" => "warmup",
+                "D" => "drop",
+                "F" => "failure",
+                _ => "working",
+            },
+            reps: table.number(record, "reps").map(|r| r.round() as i32),
+            weight_kg: table.number(record, "weight").map(|w| mass_to_kg(w, units)),
+            duration_seconds: table.number(record, "seconds").map(|s| s.round() as i32),
+            distance_m: table.number(record, "distance").map(|d| match units {
+                "imperial" => d * M_PER_MILE,
+                _ => d * 1000.0,
+            }),
+            rpe: valid_rpe(table.number(record, "rpe")),
+            notes: table.text(record, "notes", MAX_SET_NOTES_CHARS),
+        };
+        if let Some(problem) = row_problem(exercise, &set) {
+            history.warn(line, problem);
+            continue;
+        }
+
+        let workout = builder.workout(started_at, table.get(record, "workout name"));
+        if workout.finished_at.is_none() {
+            workout.finished_at = table
+                .get(record, "duration")
+                .and_then(parse_duration_text)
+                .map(|seconds| started_at + Duration::seconds(seconds));
+        }
+        if workout.notes.is_none() {
+            workout.notes = table.text(record, "workout notes", MAX_WORKOUT_NOTES_CHARS);
+        }
+        if has_values(&set) {
+            workout.exercise(exercise).sets.push(set);
+        }
+    }
+    history.workouts = builder.finish();
+}
+
+fn parse_hevy(table: &Table, profile: &UserProfile, history: &mut ParsedHistory) {
+    let mut builder = HistoryBuilder::default();
+    let mut superset_labels: HashMap<(usize, String), String> = HashMap::new();
+    for record in &table.records {
+        let line = line_of(record);
+        let Some(started_at) = table
+            .get(record, "start_time")
+            .and_then(|d| parse_timestamp(d, profile))
+        else {
+            history.warn(line, "unreadable start_time");
+            continue;
+        };
+        let Some(exercise_name) = table.get(record, "exercise_title") else {
+            history.warn(line, "missing exercise_title");
+            continue;
+        };
+        // Newer exports name the unit in the column instead of using kg throughout.
+        let set = ImportedSet {
+            set_type: match table.get(record, "set_type").unwrap_or_default() {
+                "warmup" => "warmup",
+                "dropset" => "drop",
+                "failure" => "failure",
+                _ => "working",
+            },
+            reps: table.number(record, "reps").map(|r| r.round() as i32),
+            weight_kg: table
+                .number(record, "weight_kg")
+                .or_else(|| table.number(record, "weight_lbs").map(|w| w * KG_PER_LB)),
+            duration_seconds: table
+                .number(record, "duration_seconds")
+                .map(|s| s.round() as i32),
+            distance_m: table
+                .number(record, "distance_km")
+                .map(|d| d * 1000.0)
+                .or_else(|| {
+                    table
+                        .number(record, "distance_miles")
+                        .map(|d| d * M_PER_MILE)
+                }),
+            rpe: valid_rpe(table.number(record, "rpe")),
+            notes: None,
+        };
+        if let Some(problem) = row_problem(exercise_name, &set) {
+            history.warn(line, problem);
+            continue;
+        }
+
+        let index = builder.workout_index(started_at, table.get(record, "title"));
+        // Label supersets A, B, C in the order they appear in the workout.
+        let group_label = table.get(record, "superset_id").map(|superset| {
+            let used = superset_labels.keys().filter(|(w, _)| *w == index).count();
+            superset_labels
+                .entry((index, superset.to_string()))
+                .or_insert_with(|| char::from(b'A' + (used % 26) as u8).to_string())
+                .clone()
+        });
+
+        let workout = &mut builder.workouts[index];
+        if workout.finished_at.is_none() {
+            workout.finished_at = table
+                .get(record, "end_time")
+                .and_then(|d| parse_timestamp(d, profile))
+                .filter(|end| *end >= started_at);
+        }
+        if workout.notes.is_none() {
+            workout.notes = table.text(record, "description", MAX_WORKOUT_NOTES_CHARS);
+        }
+        let exercise = workout.exercise(exercise_name);
+        if exercise.notes.is_none() {
+            exercise.notes = table.text(record, "exercise_notes", MAX_EXERCISE_NOTES_CHARS);
+        }
+        if group_label.is_some() {
+            exercise.group_label = group_label;
+        }
+        if has_values(&set) {
+            exercise.sets.push(set);
+        }
+    }
+    history.workouts = builder.finish();
+}
+
+fn parse_fitnotes(table: &Table, profile: &UserProfile, history: &mut ParsedHistory) {
+    let mut builder = HistoryBuilder::default();
+    for record in &table.records {
+        let line = line_of(record);
+        let Some(date) = table
+            .get(record, "date")
+            .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+        else {
+            history.warn(line, "unreadable date");
+            continue;
+        };
+        let Some(exercise) = table.get(record, "exercise") else {
+            history.warn(line, "missing exercise");
+            continue;
+        };
+        // Older exports put the unit in the header, newer ones in a column.
+        let weight_kg = if let Some(kg) = table
+            .number(record, "weight (kgs)")
+            .or_else(|| table.number(record, "weight (kg)"))
+        {
+            Some(kg)
+        } else if let Some(lbs) = table.number(record, "weight (lbs)") {
+            Some(lbs * KG_PER_LB)
+        } else {
+            table.number(record, "weight").map(|w| {
+                match table.get(record, "weight unit").unwrap_or("kgs") {
+                    "lbs" | "lb" => w * KG_PER_LB,
+                    _ => w,
+                }
+            })
+        };
+        let distance_m = table.number(record, "distance").and_then(|d| {
+            match table.get(record, "distance unit").unwrap_or("m") {
+                "m" => Some(d),
+                "km" => Some(d * 1000.0),
+                "mi" | "miles" => Some(d * M_PER_MILE),
+                "ft" => Some(d * 0.3048),
+                "yd" | "yards" => Some(d * 0.9144),
+                _ => None,
+            }
+        });
+        let set = ImportedSet {
+            set_type: "working",
+            reps: table.number(record, "reps").map(|r| r.round() as i32),
+            weight_kg,
+            duration_seconds: table.get(record, "time").and_then(parse_clock),
+            distance_m,
+            rpe: None,
+            notes: table.text(record, "comment", MAX_SET_NOTES_CHARS),
+        };
+        if let Some(problem) = row_problem(exercise, &set) {
+            history.warn(line, problem);
+            continue;
+        }
+
+        let started_at = profile
+            .local_instant(date.and_time(NaiveTime::from_hms_opt(DATE_ONLY_HOUR, 0, 0).unwrap()));
+        if has_values(&set) {
+            builder
+                .workout(started_at, None)
+                .exercise(exercise)
+                .sets
+                .push(set);
+        }
+    }
+    history.workouts = builder.finish();
+}
+
+/// Tells the three export formats apart by their header row.
+fn detect_source(table: &Table) -> Option<&'static str> {
+    if table.has("exercise name") && table.has("set order") {
+        Some(IMPORT_SOURCE_STRONG)
+    } else if table.has("exercise_title") && table.has("start_time") {
+        Some(IMPORT_SOURCE_HEVY)
+    } else if table.has("exercise") && table.has("category") && table.has("date") {
+        Some(IMPORT_SOURCE_FITNOTES)
+    } else {
+        None
+    }
+}
+
+/// Reads a history export. `units` applies where the file does not state
+/// its units, which only Strong leaves out.
+pub fn parse_history(
+    bytes: &[u8],
+    units: &str,
+    profile: &UserProfile,
+) -> Result<ParsedHistory, AppError> {
+    let table = Table::read(bytes)?;
+    let source = detect_source(&table).ok_or_else(|| {
+        AppError::BadRequest("Expected a CSV export from Strong, Hevy or FitNotes".into())
+    })?;
+
+    let mut history = ParsedHistory {
+        source,
+        ..Default::default()
+    };
+    match source {
+        IMPORT_SOURCE_STRONG => parse_strong(&table, units, profile, &mut history),
+        IMPORT_SOURCE_HEVY => parse_hevy(&table, profile, &mut history),
+        _ => parse_fitnotes(&table, profile, &mut history),
+    }
+
+    if history.workouts.is_empty() {
+        return Err(AppError::BadRequest(
+            "The file contains no workouts with logged sets".into(),
+        ));
+    }
+    Ok(history)
+}
+
+/// Resolves every exercise name in the file: a mapping the user saved wins,
+/// otherwise the closest catalog exercise if it is close enough.
+pub async fn resolve_exercises(
+    conn: &mut PgConnection,
+    import: &StrengthImport,
+    history: &ParsedHistory,
+) -> Result<Vec<ExerciseMapping>, AppError> {
+    let catalog: Vec<(Uuid, String)> = sqlx::query!(
+        "SELECT id, name FROM exercises WHERE owner_id IS NULL OR owner_id = $1",
+        import.user_id
+    )
+    .fetch_all(&mut *conn)
+    .await?
+    .into_iter()
+    .map(|row| (row.id, row.name))
+    .collect();
+    let names: HashMap<Uuid, &str> = catalog
+        .iter()
+        .map(|(id, name)| (*id, name.as_str()))
+        .collect();
+    let saved: HashMap<String, Option<Uuid>> = sqlx::query!(
+        "SELECT source_name, exercise_id FROM strength_import_mappings WHERE import_id = $1",
+        import.id
+    )
+    .fetch_all(&mut *conn)
+    .await?
+    .into_iter()
+    .map(|row| (row.source_name, row.exercise_id))
+    .collect();
+
+    Ok(history
+        .exercise_names()
+        .into_iter()
+        .map(|(name, set_count)| {
+            let candidates: Vec<MatchCandidate> = rank_exercises(&name, &catalog, MAX_CANDIDATES)
+                .into_iter()
+                .map(|c| MatchCandidate {
+                    exercise_id: c.exercise_id,
+                    name: c.name,
+                    score: c.score,
+                })
+                .collect();
+            let best = candidates
+                .first()
+                .filter(|c| c.score >= AUTO_MATCH_SCORE)
+                .map(|c| (c.exercise_id, c.score));
+            let (status, exercise_id, score) = match saved.get(&name) {
+                Some(Some(id)) => (MAPPING_CONFIRMED, Some(*id), None),
+                Some(None) => (MAPPING_SKIPPED, None, None),
+                None => match best {
+                    Some((id, score)) => (MAPPING_MATCHED, Some(id), Some(score)),
+                    None => (MAPPING_UNMATCHED, None, None),
+                },
+            };
+            ExerciseMapping {
+                exercise_name: exercise_id.and_then(|id| names.get(&id).map(|n| n.to_string())),
+                name,
+                set_count,
+                status,
+                exercise_id,
+                score,
+                candidates,
+            }
+        })
+        .collect())
+}
+
+/// Start times of workouts the user already has, so re-imports are skipped.
+async fn existing_starts(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    history: &ParsedHistory,
+) -> Result<HashSet<DateTime<Utc>>, AppError> {
+    let starts: Vec<DateTime<Utc>> = history.workouts.iter().map(|w| w.started_at).collect();
+    let existing = sqlx::query_scalar!(
+        "SELECT started_at FROM workouts WHERE user_id = $1 AND started_at = ANY($2)",
+        user_id,
+        &starts
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    Ok(existing.into_iter().collect())
+}
+
+pub async fn build_preview(
+    conn: &mut PgConnection,
+    import: &StrengthImport,
+    history: &ParsedHistory,
+) -> Result<ImportPreview, AppError> {
+    let exercises = resolve_exercises(conn, import, history).await?;
+    let duplicates = existing_starts(conn, import.user_id, history).await?;
+    let skipped: HashSet<&str> = exercises
+        .iter()
+        .filter(|e| e.status == MAPPING_SKIPPED)
+        .map(|e| e.name.as_str())
+        .collect();
+
+    let workouts: Vec<ImportedWorkoutPreview> = history
+        .workouts
+        .iter()
+        .map(|w| {
+            let kept: Vec<&ImportedExercise> = w
+                .exercises
+                .iter()
+                .filter(|e| !skipped.contains(e.name.as_str()))
+                .collect();
+            ImportedWorkoutPreview {
+                name: w.name.clone(),
+                started_at: w.started_at,
+                finished_at: w.finished_at,
+                exercise_count: kept.len(),
+                set_count: kept.iter().map(|e| e.sets.len()).sum(),
+                duplicate: duplicates.contains(&w.started_at),
+            }
+        })
+        .collect();
+    let to_create = || {
+        workouts
+            .iter()
+            .filter(|w| !w.duplicate && w.exercise_count > 0)
+    };
+
+    Ok(ImportPreview {
+        id: import.id,
+        source: import.source.clone(),
+        units: import.units.clone(),
+        created_at: import.created_at,
+        ready: exercises.iter().all(|e| e.status != MAPPING_UNMATCHED),
+        workout_count: to_create().count(),
+        duplicate_count: workouts.iter().filter(|w| w.duplicate).count(),
+        set_count: to_create().map(|w| w.set_count).sum(),
+        exercises,
+        workouts,
+        warnings: history.warnings.clone(),
+    })
+}
+
+/// Writes the history in one go: every workout, exercise and set is inserted
+/// with a single statement per table. Runs inside the caller's transaction.
+pub async fn commit_history(
+    conn: &mut PgConnection,
+    import: &StrengthImport,
+    history: &ParsedHistory,
+) -> Result<ImportResult, AppError> {
+    let exercises = resolve_exercises(conn, import, history).await?;
+    let unmatched: Vec<&str> = exercises
+        .iter()
+        .filter(|e| e.status == MAPPING_UNMATCHED)
+        .map(|e| e.name.as_str())
+        .collect();
+    if !unmatched.is_empty() {
+        return Err(AppError::BadRequest(format!(
+            "Map or skip these exercises first: {}",
+            unmatched.join(", ")
+        )));
+    }
+    let mapping: HashMap<&str, Uuid> = exercises
+        .iter()
+        .filter_map(|e| e.exercise_id.map(|id| (e.name.as_str(), id)))
+        .collect();
+    let duplicates = existing_starts(conn, import.user_id, history).await?;
+
+    let mut workout_ids = Vec::new();
+    let mut names = Vec::new();
+    let mut notes = Vec::new();
+    let mut started = Vec::new();
+    let mut finished = Vec::new();
+    let mut entry_ids = Vec::new();
+    let mut entry_workouts = Vec::new();
+    let mut entry_exercises = Vec::new();
+    let mut entry_positions = Vec::new();
+    let mut entry_notes = Vec::new();
+    let mut group_labels = Vec::new();
+    let mut set_entries = Vec::new();
+    let mut set_positions = Vec::new();
+    let mut set_types = Vec::new();
+    let mut set_reps = Vec::new();
+    let mut set_weights = Vec::new();
+    let mut set_durations = Vec::new();
+    let mut set_distances = Vec::new();
+    let mut set_rpes = Vec::new();
+    let mut set_notes = Vec::new();
+
+    for workout in &history.workouts {
+        if duplicates.contains(&workout.started_at) {
+            continue;
+        }
+        let kept: Vec<(&ImportedExercise, Uuid)> = workout
+            .exercises
+            .iter()
+            .filter_map(|e| mapping.get(e.name.as_str()).map(|id| (e, *id)))
+            .collect();
+        if kept.is_empty() {
+            continue;
+        }
+
+        let workout_id = Uuid::new_v4();
+        workout_ids.push(workout_id);
+        names.push(workout.name.clone());
+        notes.push(workout.notes.clone());
+        started.push(workout.started_at);
+        // History without an end time is stored as finished when it started.
+        finished.push(workout.finished_at.unwrap_or(workout.started_at));
+
+        for (position, (exercise, exercise_id)) in kept.into_iter().enumerate() {
+            let workout_exercise_id = Uuid::new_v4();
+            entry_ids.push(workout_exercise_id);
+            entry_workouts.push(workout_id);
+            entry_exercises.push(exercise_id);
+            entry_positions.push(position as i32);
+            entry_notes.push(exercise.notes.clone());
+            group_labels.push(exercise.group_label.clone());
+
+            for (set_position, set) in exercise.sets.iter().enumerate() {
+                set_entries.push(workout_exercise_id);
+                set_positions.push(set_position as i32);
+                set_types.push(set.set_type.to_string());
+                set_reps.push(set.reps);
+                set_weights.push(set.weight_kg);
+                set_durations.push(set.duration_seconds);
+                set_distances.push(set.distance_m);
+                set_rpes.push(set.rpe);
+                set_notes.push(set.notes.clone());
+            }
+        }
+    }
+
+    sqlx::query!(
+        r#"
+        INSERT INTO workouts (id, user_id, name, notes, started_at, finished_at, visibility)
+        SELECT id, $1, name, notes, started_at, finished_at,
+               COALESCE((SELECT default_workout_visibility FROM user_profiles WHERE user_id = $1), 'private')
+        FROM UNNEST($2::UUID[], $3::VARCHAR[], $4::TEXT[], $5::TIMESTAMPTZ[], $6::TIMESTAMPTZ[])
+            AS t(id, name, notes, started_at, finished_at)
+        "#,
+        import.user_id,
+        &workout_ids,
+        &names as &[Option<String>],
+        &notes as &[Option<String>],
+        &started,
+        &finished
+    )
+    .execute(&mut *conn)
+    .await?;
+    sqlx::query!(
+        r#"
+        INSERT INTO workout_exercises (id, workout_id, exercise_id, position, notes, group_label)
+        SELECT * FROM UNNEST($1::UUID[], $2::UUID[], $3::UUID[], $4::INTEGER[], $5::TEXT[], $6::VARCHAR[])
+        "#,
+        &entry_ids,
+        &entry_workouts,
+        &entry_exercises,
+        &entry_positions,
+        &entry_notes as &[Option<String>],
+        &group_labels as &[Option<String>]
+    )
+    .execute(&mut *conn)
+    .await?;
+    sqlx::query!(
+        r#"
+        INSERT INTO sets (workout_exercise_id, position, set_type, reps, weight_kg, duration_seconds, distance_m, rpe, notes)
+        SELECT * FROM UNNEST(
+            $1::UUID[], $2::INTEGER[], $3::VARCHAR[], $4::INTEGER[], $5::DOUBLE PRECISION[],
+            $6::INTEGER[], $7::DOUBLE PRECISION[], $8::DOUBLE PRECISION[], $9::TEXT[]
+        )
+        "#,
+        &set_entries,
+        &set_positions,
+        &set_types,
+        &set_reps as &[Option<i32>],
+        &set_weights as &[Option<f64>],
+        &set_durations as &[Option<i32>],
+        &set_distances as &[Option<f64>],
+        &set_rpes as &[Option<f64>],
+        &set_notes as &[Option<String>]
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    // Imported history can predate what is logged, so every later session is
+    // measured again too.
+    if let Some(since) = started.iter().min() {
+        let mut exercise_ids = entry_exercises.clone();
+        exercise_ids.sort();
+        exercise_ids.dedup();
+        let scope = RecordScope {
+            exercise_ids,
+            since: *since,
+        };
+        recompute_personal_records(conn, import.user_id, &scope).await?;
+    }
+    refresh_goals(conn, import.user_id).await?;
+    check_achievements(conn, import.user_id).await?;
+
+    Ok(ImportResult {
+        workouts_created: workout_ids.len(),
+        sets_created: set_types.len(),
+        duplicates_skipped: history
+            .workouts
+            .iter()
+            .filter(|w| duplicates.contains(&w.started_at))
+            .count(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile() -> UserProfile {
+        UserProfile {
+            timezone: "Europe/Zurich".into(),
+            ..UserProfile::test_default(Uuid::nil())
+        }
+    }
+
+    #[test]
+    fn reads_strong_exports() {
+        let csv = "Date;Workout Name;Duration;Exercise Name;Set Order;Weight;Reps;Distance;Seconds;Notes;Workout Notes;RPE\n\
+            2024-01-15 18:30:00;Push;1h 5m;Bench Press (Barbell);W;40;10;0;0;;Felt good;\n\
+            2024-01-15 18:30:00;Push;1h 5m;Bench Press (Barbell);1;82,5;5;0;0;;;8\n\
+            2024-01-15 18:30:00;Push;1h 5m;Bench Press (Barbell);Rest Timer;0;0;0;90;;;\n\
+            2024-01-15 18:30:00;Push;1h 5m;Pull Up;1;0;8;0;0;;;\n\
+            2024-01-12 07:00:00;Legs;45m;Squat (Barbell);1;100;5;0;0;;;\n\
+            yesterday;Legs;45m;Squat (Barbell);1;100;5;0;0;;;\n";
+        let history = parse_history(csv.as_bytes(), "metric", &profile()).unwrap();
+
+        assert_eq!(history.source, IMPORT_SOURCE_STRONG);
+        assert_eq!(history.workouts.len(), 2);
+        assert_eq!(history.warnings, ["Line 7: unreadable date"]);
+        let push = &history.workouts[1];
+        assert_eq!(push.name.as_deref(), Some("Push"));
+        assert_eq!(push.notes.as_deref(), Some("Felt good"));
+        assert_eq!(push.started_at.to_rfc3339(), "2024-01-15T17:30:00+00:00");
+        assert_eq!(
+            push.finished_at.unwrap() - push.started_at,
+            Duration::minutes(65)
+        );
+        assert_eq!(push.exercises.len(), 2);
+        let bench = &push.exercises[0].sets;
+        assert_eq!(bench.len(), 2);
+        assert_eq!(bench[0].set_type, "warmup");
+        assert_eq!(bench[1].weight_kg, Some(82.5));
+        assert_eq!(bench[1].rpe, Some(8.0));
+        assert_eq!(push.exercises[1].sets[0].weight_kg, None);
+        assert_eq!(
+            history.exercise_names(),
+            [
+                ("Squat (Barbell)".to_string(), 1),
+                ("Bench Press (Barbell)".to_string(), 2),
+                ("Pull Up".to_string(), 1)
+            ]
+        );
+
+        let imperial = parse_history(csv.as_bytes(), "imperial", &profile()).unwrap();
+        assert_eq!(
+            imperial.workouts[0].exercises[0].sets[0].weight_kg,
+            Some(100.0 * KG_PER_LB)
+        );
+    }
+
+    #[test]
+    fn reads_hevy_exports_with_supersets() {
+        let csv = "\"title\",\"start_time\",\"end_time\",\"description\",\"exercise_title\",\"superset_id\",\"exercise_notes\",\"set_index\",\"set_type\",\"weight_lbs\",\"reps\",\"distance_miles\",\"duration_seconds\",\"rpe\"\n\
+            \"Upper\",\"15 Jan 2024, 18:30\",\"15 Jan 2024, 19:40\",\"\",\"Bench Press (Barbell)\",\"\",\"\",\"0\",\"normal\",\"225\",\"5\",\"\",\"\",\"\"\n\
+            \"Upper\",\"15 Jan 2024, 18:30\",\"15 Jan 2024, 19:40\",\"\",\"Bicep Curl (Dumbbell)\",\"7\",\"\",\"0\",\"dropset\",\"30\",\"12\",\"\",\"\",\"\"\n\
+            \"Upper\",\"15 Jan 2024, 18:30\",\"15 Jan 2024, 19:40\",\"\",\"Triceps Pushdown\",\"7\",\"\",\"0\",\"normal\",\"50\",\"12\",\"\",\"\",\"\"\n";
+        let history = parse_history(csv.as_bytes(), "metric", &profile()).unwrap();
+
+        assert_eq!(history.source, IMPORT_SOURCE_HEVY);
+        let workout = &history.workouts[0];
+        assert_eq!(
+            workout.finished_at.unwrap() - workout.started_at,
+            Duration::minutes(70)
+        );
+        assert_eq!(workout.exercises[0].group_label, None);
+        assert_eq!(workout.exercises[1].group_label.as_deref(), Some("A"));
+        assert_eq!(workout.exercises[2].group_label.as_deref(), Some("A"));
+        assert_eq!(workout.exercises[1].sets[0].set_type, "drop");
+        assert!((workout.exercises[0].sets[0].weight_kg.unwrap() - 102.06).abs() < 0.01);
+    }
+
+    #[test]
+    fn reads_fitnotes_exports() {
+        let csv = "Date,Exercise,Category,Weight (kgs),Reps,Distance,Distance Unit,Time,Comment\n\
+            2024-01-15,Flat Barbell Bench Press,Chest,80.0,5,,,,\n\
+            2024-01-15,Flat Barbell Bench Press,Chest,80.0,5,,,,Paused\n\
+            2024-01-15,Treadmill,Cardio,,,2.5,km,0:15:00,\n\
+            2024-01-17,Barbell Squat,Legs,100.0,5,,,,\n";
+        let history = parse_history(csv.as_bytes(), "metric", &profile()).unwrap();
+
+        assert_eq!(history.source, IMPORT_SOURCE_FITNOTES);
+        assert_eq!(history.workouts.len(), 2);
+        let first = &history.workouts[0];
+        assert_eq!(first.started_at.to_rfc3339(), "2024-01-15T11:00:00+00:00");
+        assert_eq!(first.exercises[0].sets[1].notes.as_deref(), Some("Paused"));
+        assert_eq!(first.exercises[1].sets[0].distance_m, Some(2500.0));
+        assert_eq!(first.exercises[1].sets[0].duration_seconds, Some(900));
+    }
+
+    #[test]
+    fn leaves_out_rows_beyond_the_limits() {
+        let long_name = "x".repeat(201);
+        let long_comment = "y".repeat(1500);
+        let csv = format!(
+            "Date,Exercise,Category,Weight (kgs),Reps,Distance,Distance Unit,Time,Comment\n\
+            2024-01-15,Barbell Squat,Legs,100.0,5,,,,{long_comment}\n\
+            2024-01-15,Barbell Squat,Legs,100.0,50000,,,,\n\
+            2024-01-15,Barbell Squat,Legs,25000,5,,,,\n\
+            2024-01-15,Treadmill,Cardio,,,5000,km,,\n\
+            2024-01-15,Treadmill,Cardio,,,,,99999999:00:00,\n\
+            2024-01-15,{long_name},Legs,100.0,5,,,,\n"
+        );
+        let history = parse_history(csv.as_bytes(), "metric", &profile()).unwrap();
+
+        assert_eq!(
+            history.warnings,
+            [
+                "Line 3: reps above 10000",
+                "Line 4: weight above 2000 kg",
+                "Line 5: distance above 1000 km",
+                "Line 7: exercise name longer than 200 characters"
+            ]
+        );
+        assert_eq!(history.workouts.len(), 1);
+        let squat = &history.workouts[0].exercises[0];
+        assert_eq!(squat.sets.len(), 1);
+        assert_eq!(squat.sets[0].notes.as_ref().unwrap().len(), 1000);
+    }
+
+    #[test]
+    fn rejects_unknown_files() {
+        assert!(parse_history(b"a,b,c\n1,2,3\n", "metric", &profile()).is_err());
+        let empty = "Date,Exercise,Category,Weight (kgs),Reps\n";
+        assert!(parse_history(empty.as_bytes(), "metric", &profile()).is_err());
+        assert_eq!(parse_duration_text("1h 5m"), Some(3900));
+        assert_eq!(parse_duration_text("soon"), None);
+        assert_eq!(parse_clock("1:02:03"), Some(3723));
+    }
+}
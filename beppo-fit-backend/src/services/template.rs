@@ -0,0 +1,250 @@
+use sqlx::PgConnection;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    models::{
+        progression::ProgressionRecommendation,
+        template::{SaveTemplateExercise, TemplateDetail, TemplateExercise, WorkoutTemplate},
+        workout::{SaveWorkoutExercise, SaveWorkoutSet, WorkoutSet},
+    },
+    services::{
+        progression::{latest_recommendations, DEFAULT_INCREMENT_KG},
+        wellness::{lighter_set_count, lighter_weight, session_adjustment},
+        workout::insert_workout_exercises,
+    },
+};
+
+pub async fn insert_template_exercises(
+    conn: &mut PgConnection,
+    template_id: Uuid,
+    exercises: &[SaveTemplateExercise],
+) -> Result<(), AppError> {
+    for (position, exercise) in exercises.iter().enumerate() {
+        sqlx::query!(
+            r#"
+            INSERT INTO template_exercises (template_id, exercise_id, position, group_label, group_type, target_sets, target_reps_min, target_reps_max, target_weight_kg, target_rpe, rest_seconds, notes, progression_rule, progression_increment_kg)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+            "#,
+            template_id,
+            exercise.exercise_id,
+            position as i32,
+            exercise.group_label,
+            exercise.group_type,
+            exercise.target_sets,
+            exercise.target_reps_min,
+            exercise.target_reps_max,
+            exercise.target_weight_kg,
+            exercise.target_rpe,
+            exercise.rest_seconds,
+            exercise.notes,
+            exercise.progression_rule,
+            exercise.progression_increment_kg
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Loads a template with its exercises. Callers are responsible for access checks.
+pub async fn load_template_detail(
+    conn: &mut PgConnection,
+    template: WorkoutTemplate,
+) -> Result<TemplateDetail, AppError> {
+    let exercises = sqlx::query_as!(
+        TemplateExercise,
+        r#"
+        SELECT te.id, te.template_id, te.exercise_id, e.name AS exercise_name, te.position,
+               te.group_label, te.group_type, te.target_sets, te.target_reps_min, te.target_reps_max,
+               te.target_weight_kg, te.target_rpe, te.rest_seconds, te.notes, te.progression_rule, te.progression_increment_kg
+        FROM template_exercises te
+        JOIN exercises e ON e.id = te.exercise_id
+        WHERE te.template_id = $1
+        ORDER BY te.position
+        "#,
+        template.id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(TemplateDetail {
+        template,
+        exercises,
+    })
+}
+
+/// Creates a new template for `user_id` with the same content as `source`.
+pub async fn copy_template(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    source: &TemplateDetail,
+    name: &str,
+) -> Result<WorkoutTemplate, AppError> {
+    let template = sqlx::query_as!(
+        WorkoutTemplate,
+        "INSERT INTO workout_templates (user_id, name, description) VALUES ($1, $2, $3) RETURNING *",
+        user_id,
+        name,
+        source.template.description
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    let exercises: Vec<SaveTemplateExercise> = source
+        .exercises
+        .iter()
+        .map(|e| SaveTemplateExercise {
+            exercise_id: e.exercise_id,
+            group_label: e.group_label.clone(),
+            group_type: e.group_type.clone(),
+            target_sets: e.target_sets,
+            target_reps_min: e.target_reps_min,
+            target_reps_max: e.target_reps_max,
+            target_weight_kg: e.target_weight_kg,
+            target_rpe: e.target_rpe,
+            rest_seconds: e.rest_seconds,
+            notes: e.notes.clone(),
+            progression_rule: e.progression_rule.clone(),
+            progression_increment_kg: e.progression_increment_kg,
+        })
+        .collect();
+    insert_template_exercises(conn, template.id, &exercises).await?;
+
+    Ok(template)
+}
+
+/// The non-warm-up sets of the most recent finished workout containing each exercise.
+pub async fn last_performance(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    exercise_ids: &[Uuid],
+) -> Result<HashMap<Uuid, Vec<WorkoutSet>>, AppError> {
+    let latest = sqlx::query!(
+        r#"
+        SELECT DISTINCT ON (we.exercise_id) we.exercise_id, we.id
+        FROM workout_exercises we
+        JOIN workouts w ON w.id = we.workout_id
+        WHERE w.user_id = $1 AND w.finished_at IS NOT NULL AND we.exercise_id = ANY($2)
+        ORDER BY we.exercise_id, w.started_at DESC, we.position
+        "#,
+        user_id,
+        exercise_ids
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let exercise_by_entry: HashMap<Uuid, Uuid> =
+        latest.iter().map(|row| (row.id, row.exercise_id)).collect();
+    let entry_ids: Vec<Uuid> = exercise_by_entry.keys().copied().collect();
+
+    let sets = sqlx::query_as!(
+        WorkoutSet,
+        "SELECT * FROM sets WHERE workout_exercise_id = ANY($1) AND set_type <> 'warmup' ORDER BY position",
+        &entry_ids
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut performance: HashMap<Uuid, Vec<WorkoutSet>> = HashMap::new();
+    for set in sets {
+        if let Some(exercise_id) = exercise_by_entry.get(&set.workout_exercise_id) {
+            performance.entry(*exercise_id).or_default().push(set);
+        }
+    }
+    Ok(performance)
+}
+
+/// Builds the planned sets for a template exercise. A progression
+/// recommendation sets the reps and weight; otherwise each set repeats what
+/// the user did last time for that exercise, falling back to the template targets.
+pub fn planned_sets(
+    exercise: &TemplateExercise,
+    previous: Option<&Vec<WorkoutSet>>,
+    recommendation: Option<&ProgressionRecommendation>,
+) -> Vec<SaveWorkoutSet> {
+    (0..exercise.target_sets as usize)
+        .map(|i| {
+            let last = previous.and_then(|sets| sets.get(i).or(sets.last()));
+            SaveWorkoutSet {
+                set_type: "working".to_string(),
+                reps: recommendation
+                    .and_then(|r| r.target_reps)
+                    .or(last.and_then(|s| s.reps))
+                    .or(exercise.target_reps_min),
+                weight_kg: recommendation
+                    .and_then(|r| r.target_weight_kg)
+                    .or(last.and_then(|s| s.weight_kg))
+                    .or(exercise.target_weight_kg),
+                duration_seconds: last.and_then(|s| s.duration_seconds),
+                distance_m: last.and_then(|s| s.distance_m),
+                rpe: None,
+                rir: None,
+                notes: None,
+                completed_at: None,
+            }
+        })
+        .collect()
+}
+
+/// Creates an in-progress workout pre-filled from a template and returns its id.
+///
+/// On a low readiness day the sets are lightened and the workout notes say why.
+pub async fn start_workout_from_template(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    template: &TemplateDetail,
+) -> Result<Uuid, AppError> {
+    let exercise_ids: Vec<Uuid> = template.exercises.iter().map(|e| e.exercise_id).collect();
+    let previous = last_performance(conn, user_id, &exercise_ids).await?;
+    let template_exercise_ids: Vec<Uuid> = template.exercises.iter().map(|e| e.id).collect();
+    let recommendations = latest_recommendations(conn, user_id, &template_exercise_ids).await?;
+    let adjustment = session_adjustment(conn, user_id).await?;
+
+    let workout_id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO workouts (user_id, name, template_id, notes, readiness_adjusted, visibility)
+        VALUES (
+            $1, $2, $3, $4, $5,
+            COALESCE((SELECT default_workout_visibility FROM user_profiles WHERE user_id = $1), 'private')
+        )
+        RETURNING id
+        "#,
+        user_id,
+        template.template.name,
+        template.template.id,
+        adjustment.as_ref().map(|a| a.reason.clone()),
+        adjustment.is_some()
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    let exercises: Vec<SaveWorkoutExercise> = template
+        .exercises
+        .iter()
+        .map(|e| SaveWorkoutExercise {
+            exercise_id: e.exercise_id,
+            notes: e.notes.clone(),
+            group_label: e.group_label.clone(),
+            sets: {
+                let mut sets =
+                    planned_sets(e, previous.get(&e.exercise_id), recommendations.get(&e.id));
+                if let Some(adjustment) = &adjustment {
+                    let increment = e.progression_increment_kg.unwrap_or(DEFAULT_INCREMENT_KG);
+                    sets.truncate(lighter_set_count(adjustment, sets.len()));
+                    for set in &mut sets {
+                        set.weight_kg = set
+                            .weight_kg
+                            .map(|w| lighter_weight(adjustment, w, increment));
+                    }
+                }
+                sets
+            },
+        })
+        .collect();
+    insert_workout_exercises(conn, workout_id, &exercises).await?;
+
+    Ok(workout_id)
+}
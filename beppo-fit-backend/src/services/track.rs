@@ -0,0 +1,406 @@
+//! Compact storage and summary metrics for recorded tracks.
+//!
+//! Tracks are stored as a versioned byte stream: the start time, then per
+//! point a presence bitmask followed by zigzag varint deltas of each present
+//! value against the last point that had it. Positions are kept at 1e-6
+//! degrees (about 0.1 m), elevation and distance at decimetres, time at seconds.
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::models::activity::TrackPoint;
+
+const ENCODING_VERSION: u8 = 1;
+
+const HAS_POSITION: u8 = 1;
+const HAS_ELEVATION: u8 = 1 << 1;
+const HAS_DISTANCE: u8 = 1 << 2;
+const HAS_HEART_RATE: u8 = 1 << 3;
+const HAS_CADENCE: u8 = 1 << 4;
+
+const DEGREE_SCALE: f64 = 1e6;
+const METRE_SCALE: f64 = 10.0;
+
+/// Slower than this counts as standing still.
+const MOVING_SPEED_MPS: f64 = 0.5;
+/// Elevation changes smaller than this are treated as GPS noise.
+const ELEVATION_THRESHOLD_M: f64 = 2.0;
+const EARTH_RADIUS_M: f64 = 6_371_008.8;
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn write_signed(out: &mut Vec<u8>, value: i64) {
+    write_varint(out, ((value << 1) ^ (value >> 63)) as u64);
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn byte(&mut self) -> Option<u8> {
+        let byte = *self.bytes.get(self.pos)?;
+        self.pos += 1;
+        Some(byte)
+    }
+
+    fn varint(&mut self) -> Option<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            value |= ((byte & 0x7F) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+        }
+        None
+    }
+
+    fn signed(&mut self) -> Option<i64> {
+        let value = self.varint()?;
+        Some((value >> 1) as i64 ^ -((value & 1) as i64))
+    }
+}
+
+/// Delta coder for one value stream.
+#[derive(Default)]
+struct Stream {
+    last: i64,
+}
+
+impl Stream {
+    fn write(&mut self, out: &mut Vec<u8>, value: i64) {
+        write_signed(out, value - self.last);
+        self.last = value;
+    }
+
+    fn read(&mut self, reader: &mut Reader) -> Option<i64> {
+        self.last += reader.signed()?;
+        Some(self.last)
+    }
+}
+
+fn scaled(value: f64, scale: f64) -> i64 {
+    (value * scale).round() as i64
+}
+
+pub fn encode_track(points: &[TrackPoint]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(points.len() * 12);
+    out.push(ENCODING_VERSION);
+    write_varint(&mut out, points.len() as u64);
+    let Some(first) = points.first() else {
+        return out;
+    };
+    write_signed(&mut out, first.time.timestamp());
+
+    let mut streams: [Stream; 7] = Default::default();
+    let [time, lat, lon, elevation, distance, heart_rate, cadence] = &mut streams;
+    time.last = first.time.timestamp();
+    for point in points {
+        let position = point.lat.zip(point.lon);
+        let mut flags = 0;
+        for (present, flag) in [
+            (position.is_some(), HAS_POSITION),
+            (point.elevation_m.is_some(), HAS_ELEVATION),
+            (point.distance_m.is_some(), HAS_DISTANCE),
+            (point.heart_rate.is_some(), HAS_HEART_RATE),
+            (point.cadence.is_some(), HAS_CADENCE),
+        ] {
+            if present {
+                flags |= flag;
+            }
+        }
+        out.push(flags);
+
+        time.write(&mut out, point.time.timestamp());
+        if let Some((point_lat, point_lon)) = position {
+            lat.write(&mut out, scaled(point_lat, DEGREE_SCALE));
+            lon.write(&mut out, scaled(point_lon, DEGREE_SCALE));
+        }
+        if let Some(value) = point.elevation_m {
+            elevation.write(&mut out, scaled(value, METRE_SCALE));
+        }
+        if let Some(value) = point.distance_m {
+            distance.write(&mut out, scaled(value, METRE_SCALE));
+        }
+        if let Some(value) = point.heart_rate {
+            heart_rate.write(&mut out, value as i64);
+        }
+        if let Some(value) = point.cadence {
+            cadence.write(&mut out, value as i64);
+        }
+    }
+
+    out
+}
+
+/// Decodes a stored track; `None` if the data is corrupt or from an unknown version.
+pub fn decode_track(bytes: &[u8]) -> Option<Vec<TrackPoint>> {
+    let mut reader = Reader { bytes, pos: 0 };
+    if reader.byte()? != ENCODING_VERSION {
+        return None;
+    }
+    let count = reader.varint()? as usize;
+    let mut points = Vec::with_capacity(count.min(bytes.len()));
+    if count == 0 {
+        return Some(points);
+    }
+
+    let mut streams: [Stream; 7] = Default::default();
+    let [time, lat, lon, elevation, distance, heart_rate, cadence] = &mut streams;
+    time.last = reader.signed()?;
+    for _ in 0..count {
+        let flags = reader.byte()?;
+        let has = |flag: u8| flags & flag != 0;
+
+        let timestamp = time.read(&mut reader)?;
+        let (point_lat, point_lon) = if has(HAS_POSITION) {
+            (
+                Some(lat.read(&mut reader)? as f64 / DEGREE_SCALE),
+                Some(lon.read(&mut reader)? as f64 / DEGREE_SCALE),
+            )
+        } else {
+            (None, None)
+        };
+        let elevation_m = if has(HAS_ELEVATION) {
+            Some(elevation.read(&mut reader)? as f64 / METRE_SCALE)
+        } else {
+            None
+        };
+        let distance_m = if has(HAS_DISTANCE) {
+            Some(distance.read(&mut reader)? as f64 / METRE_SCALE)
+        } else {
+            None
+        };
+        let point_heart_rate = if has(HAS_HEART_RATE) {
+            Some(heart_rate.read(&mut reader)? as i32)
+        } else {
+            None
+        };
+        let point_cadence = if has(HAS_CADENCE) {
+            Some(cadence.read(&mut reader)? as i32)
+        } else {
+            None
+        };
+
+        points.push(TrackPoint {
+            time: DateTime::from_timestamp(timestamp, 0)?,
+            lat: point_lat,
+            lon: point_lon,
+            elevation_m,
+            distance_m,
+            heart_rate: point_heart_rate,
+            cadence: point_cadence,
+        });
+    }
+
+    Some(points)
+}
+
+/// Google encoded polyline of the positions, at precision 5.
+pub fn encode_polyline(points: &[TrackPoint]) -> Option<String> {
+    let mut out = String::new();
+    let (mut last_lat, mut last_lon) = (0i64, 0i64);
+    let mut any = false;
+    for (lat, lon) in points.iter().filter_map(|p| p.lat.zip(p.lon)) {
+        let (lat, lon) = (scaled(lat, 1e5), scaled(lon, 1e5));
+        for delta in [lat - last_lat, lon - last_lon] {
+            let mut value = (if delta < 0 { !(delta << 1) } else { delta << 1 }) as u64;
+            while value >= 0x20 {
+                out.push(char::from((((value & 0x1F) | 0x20) + 63) as u8));
+                value >>= 5;
+            }
+            out.push(char::from((value + 63) as u8));
+        }
+        (last_lat, last_lon) = (lat, lon);
+        any = true;
+    }
+    any.then_some(out)
+}
+
+/// Great-circle distance between two positions.
+pub fn haversine_m(from: (f64, f64), to: (f64, f64)) -> f64 {
+    let (lat1, lon1) = (from.0.to_radians(), from.1.to_radians());
+    let (lat2, lon2) = (to.0.to_radians(), to.1.to_radians());
+    let a = ((lat2 - lat1) / 2.0).sin().powi(2)
+        + lat1.cos() * lat2.cos() * ((lon2 - lon1) / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_M * a.sqrt().asin()
+}
+
+/// Sorts points by time, drops duplicate timestamps and fills in cumulative
+/// distance from the positions where the device did not record it.
+pub fn normalize_track(points: &mut Vec<TrackPoint>) {
+    points.sort_by_key(|p| p.time);
+    points.dedup_by_key(|p| p.time);
+    if points.iter().all(|p| p.distance_m.is_some()) {
+        return;
+    }
+
+    let mut total = 0.0;
+    let mut last_position = None;
+    for point in points.iter_mut() {
+        if let Some(position) = point.lat.zip(point.lon) {
+            if let Some(last) = last_position {
+                total += haversine_m(last, position);
+            }
+            last_position = Some(position);
+        }
+        point.distance_m = Some(total);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackSummary {
+    pub started_at: DateTime<Utc>,
+    pub duration_seconds: i32,
+    pub moving_seconds: i32,
+    pub distance_m: Option<f64>,
+    pub elevation_gain_m: Option<f64>,
+    pub elevation_loss_m: Option<f64>,
+    pub avg_heart_rate: Option<i32>,
+    pub max_heart_rate: Option<i32>,
+    pub avg_cadence: Option<i32>,
+}
+
+fn round_tenth(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+fn average(values: impl Iterator<Item = i32>) -> Option<i32> {
+    let (sum, count) = values.fold((0i64, 0i64), |(sum, count), v| (sum + v as i64, count + 1));
+    (count > 0).then(|| (sum as f64 / count as f64).round() as i32)
+}
+
+/// Summary metrics of a normalized, non-empty track.
+pub fn summarize_track(points: &[TrackPoint]) -> Option<TrackSummary> {
+    let first = points.first()?;
+    let last = points.last()?;
+
+    let mut moving = Duration::zero();
+    for pair in points.windows(2) {
+        let elapsed = pair[1].time - pair[0].time;
+        let covered = pair[1]
+            .distance_m
+            .zip(pair[0].distance_m)
+            .map(|(b, a)| b - a);
+        let seconds = elapsed.num_milliseconds() as f64 / 1000.0;
+        if seconds > 0.0 && covered.is_some_and(|m| m / seconds >= MOVING_SPEED_MPS) {
+            moving += elapsed;
+        }
+    }
+
+    // Only count a climb or descent once it exceeds the noise threshold.
+    let (mut gain, mut loss) = (0.0, 0.0);
+    let mut reference: Option<f64> = None;
+    for elevation in points.iter().filter_map(|p| p.elevation_m) {
+        match reference {
+            None => reference = Some(elevation),
+            Some(base) if (elevation - base).abs() >= ELEVATION_THRESHOLD_M => {
+                if elevation > base {
+                    gain += elevation - base;
+                } else {
+                    loss += base - elevation;
+                }
+                reference = Some(elevation);
+            }
+            Some(_) => {}
+        }
+    }
+    let has_elevation = reference.is_some();
+
+    Some(TrackSummary {
+        started_at: first.time,
+        duration_seconds: (last.time - first.time).num_seconds() as i32,
+        moving_seconds: moving.num_seconds() as i32,
+        distance_m: last.distance_m.filter(|d| *d > 0.0).map(round_tenth),
+        elevation_gain_m: has_elevation.then(|| round_tenth(gain)),
+        elevation_loss_m: has_elevation.then(|| round_tenth(loss)),
+        avg_heart_rate: average(points.iter().filter_map(|p| p.heart_rate)),
+        max_heart_rate: points.iter().filter_map(|p| p.heart_rate).max(),
+        avg_cadence: average(points.iter().filter_map(|p| p.cadence)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(
+        second: i64,
+        lat: f64,
+        lon: f64,
+        elevation: f64,
+        heart_rate: Option<i32>,
+    ) -> TrackPoint {
+        TrackPoint {
+            time: DateTime::from_timestamp(1_733_000_000 + second, 0).unwrap(),
+            lat: Some(lat),
+            lon: Some(lon),
+            elevation_m: Some(elevation),
+            distance_m: None,
+            heart_rate,
+            cadence: None,
+        }
+    }
+
+    #[test]
+    fn track_encoding_round_trips() {
+        let mut points = vec![
+            point(0, 52.520008, 13.404954, 34.5, Some(120)),
+            point(5, 52.520108, 13.405054, 35.1, None),
+            point(10, 52.520208, 13.405154, 33.9, Some(135)),
+        ];
+        points[1].lat = None;
+        points[1].lon = None;
+        points[0].distance_m = Some(0.0);
+        points[2].distance_m = Some(22.3);
+        points[2].cadence = Some(88);
+
+        let encoded = encode_track(&points);
+        assert!(encoded.len() < points.len() * 16);
+        assert_eq!(decode_track(&encoded).unwrap(), points);
+        assert_eq!(decode_track(&encoded[..encoded.len() - 1]), None);
+    }
+
+    #[test]
+    fn encodes_polylines() {
+        // The example from Google's polyline documentation
+        let points = [
+            point(0, 38.5, -120.2, 0.0, None),
+            point(1, 40.7, -120.95, 0.0, None),
+            point(2, 43.252, -126.453, 0.0, None),
+        ];
+        assert_eq!(
+            encode_polyline(&points).unwrap(),
+            "_p~iF~ps|U_ulLnnqC_mqNvxq`@"
+        );
+    }
+
+    #[test]
+    fn summarizes_distance_stops_and_climbing() {
+        // About 11 m per point north, with a 60 s stop in the middle
+        let mut points = vec![
+            point(0, 52.0, 13.0, 100.0, Some(140)),
+            point(5, 52.0001, 13.0, 101.0, Some(150)),
+            point(10, 52.0002, 13.0, 103.0, Some(160)),
+            point(70, 52.0002, 13.0, 103.5, Some(100)),
+            point(75, 52.0003, 13.0, 99.0, Some(130)),
+        ];
+        normalize_track(&mut points);
+        let summary = summarize_track(&points).unwrap();
+
+        assert_eq!(summary.duration_seconds, 75);
+        assert_eq!(summary.moving_seconds, 15);
+        assert_eq!(summary.distance_m, Some(33.4));
+        assert_eq!(summary.elevation_gain_m, Some(3.0));
+        assert_eq!(summary.elevation_loss_m, Some(4.0));
+        assert_eq!(summary.avg_heart_rate, Some(136));
+        assert_eq!(summary.max_heart_rate, Some(160));
+    }
+}
@@ -0,0 +1,296 @@
+use chrono::{Duration, NaiveDate};
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    models::wellness::{
+        Readiness, ReadinessComponents, SessionAdjustment, WellnessLog, SUGGESTION_LIGHTER,
+        SUGGESTION_TRAIN,
+    },
+    services::{profile::ensure_profile, program::round_to_increment},
+};
+
+/// Days before the scored day that heart rate and HRV are compared to.
+pub const BASELINE_DAYS: i64 = 28;
+/// Readings needed before a baseline is trusted.
+const MIN_BASELINE_READINGS: i64 = 3;
+/// Below this score sessions start lighter.
+pub const LOW_READINESS_SCORE: i32 = 50;
+pub const LIGHTER_WEIGHT_FACTOR: f64 = 0.9;
+
+const TARGET_SLEEP_MINUTES: f64 = 480.0;
+const TARGET_WATER_ML: f64 = 2500.0;
+
+const SLEEP_WEIGHT: f64 = 0.30;
+const HRV_WEIGHT: f64 = 0.20;
+const RESTING_HEART_RATE_WEIGHT: f64 = 0.15;
+const SORENESS_WEIGHT: f64 = 0.15;
+const STRESS_WEIGHT: f64 = 0.15;
+const HYDRATION_WEIGHT: f64 = 0.05;
+
+/// Maps a 1-5 scale to 0-100, where `best` is the good end.
+fn scale_score(value: i32, best: i32) -> f64 {
+    let steps = (value - 1) as f64 / 4.0;
+    if best == 5 {
+        steps * 100.0
+    } else {
+        (1.0 - steps) * 100.0
+    }
+}
+
+fn sleep_score(log: &WellnessLog) -> Option<f64> {
+    let duration = log
+        .sleep_minutes
+        .map(|m| (m as f64 / TARGET_SLEEP_MINUTES).min(1.0) * 100.0);
+    let quality = log.sleep_quality.map(|q| scale_score(q, 5));
+    match (duration, quality) {
+        (Some(d), Some(q)) => Some((d + q) / 2.0),
+        (d, q) => d.or(q),
+    }
+}
+
+/// Each beat above the baseline costs ten points.
+fn resting_heart_rate_score(resting_heart_rate: i32, baseline: f64) -> f64 {
+    (100.0 - (resting_heart_rate as f64 - baseline).max(0.0) * 10.0).max(0.0)
+}
+
+/// Full marks at or above the baseline; 25% below it scores zero.
+fn hrv_score(hrv_ms: f64, baseline: f64) -> f64 {
+    let ratio = hrv_ms / baseline;
+    if ratio >= 1.0 {
+        100.0
+    } else {
+        (100.0 - (1.0 - ratio) * 400.0).max(0.0)
+    }
+}
+
+/// Scores a day's log. Heart rate and HRV only count once a baseline exists.
+/// `None` if nothing scorable was logged.
+pub fn readiness(
+    log: &WellnessLog,
+    baseline_resting_heart_rate: Option<f64>,
+    baseline_hrv_ms: Option<f64>,
+) -> Option<Readiness> {
+    let sleep = sleep_score(log);
+    let hrv = log
+        .hrv_ms
+        .zip(baseline_hrv_ms)
+        .map(|(v, b)| hrv_score(v, b));
+    let resting_heart_rate = log
+        .resting_heart_rate
+        .zip(baseline_resting_heart_rate)
+        .map(|(v, b)| resting_heart_rate_score(v, b));
+    let soreness = log.soreness.map(|s| scale_score(s, 1));
+    let stress = log.stress.map(|s| scale_score(s, 1));
+    let hydration = log
+        .water_ml
+        .map(|w| (w as f64 / TARGET_WATER_ML).min(1.0) * 100.0);
+
+    let weighted = [
+        (sleep, SLEEP_WEIGHT),
+        (hrv, HRV_WEIGHT),
+        (resting_heart_rate, RESTING_HEART_RATE_WEIGHT),
+        (soreness, SORENESS_WEIGHT),
+        (stress, STRESS_WEIGHT),
+        (hydration, HYDRATION_WEIGHT),
+    ];
+    let total_weight: f64 = weighted
+        .iter()
+        .filter(|(score, _)| score.is_some())
+        .map(|(_, weight)| weight)
+        .sum();
+    if total_weight == 0.0 {
+        return None;
+    }
+    let score = (weighted
+        .iter()
+        .filter_map(|(score, weight)| score.map(|s| s * weight))
+        .sum::<f64>()
+        / total_weight)
+        .round() as i32;
+
+    let round = |score: Option<f64>| score.map(|s| s.round() as i32);
+    Some(Readiness {
+        date: log.log_date,
+        score,
+        components: ReadinessComponents {
+            sleep: round(sleep),
+            hrv: round(hrv),
+            resting_heart_rate: round(resting_heart_rate),
+            soreness: round(soreness),
+            stress: round(stress),
+            hydration: round(hydration),
+        },
+        baseline_resting_heart_rate,
+        baseline_hrv_ms,
+        suggestion: if score < LOW_READINESS_SCORE {
+            SUGGESTION_LIGHTER
+        } else {
+            SUGGESTION_TRAIN
+        },
+    })
+}
+
+/// Scores the log of `date` against the preceding weeks. `None` without a
+/// scorable log for that day.
+pub async fn load_readiness(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    date: NaiveDate,
+) -> Result<Option<Readiness>, AppError> {
+    let Some(log) = sqlx::query_as!(
+        WellnessLog,
+        "SELECT * FROM wellness_logs WHERE user_id = $1 AND log_date = $2",
+        user_id,
+        date
+    )
+    .fetch_optional(&mut *conn)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let baseline = sqlx::query!(
+        r#"
+        SELECT
+            AVG(resting_heart_rate)::FLOAT8 AS resting_heart_rate,
+            COUNT(resting_heart_rate) AS "resting_heart_rate_readings!",
+            AVG(hrv_ms) AS hrv_ms,
+            COUNT(hrv_ms) AS "hrv_readings!"
+        FROM wellness_logs
+        WHERE user_id = $1 AND log_date >= $2 AND log_date < $3
+        "#,
+        user_id,
+        date - Duration::days(BASELINE_DAYS),
+        date
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    let round1 = |v: f64| (v * 10.0).round() / 10.0;
+    let baseline_resting_heart_rate = baseline
+        .resting_heart_rate
+        .filter(|_| baseline.resting_heart_rate_readings >= MIN_BASELINE_READINGS)
+        .map(round1);
+    let baseline_hrv_ms = baseline
+        .hrv_ms
+        .filter(|_| baseline.hrv_readings >= MIN_BASELINE_READINGS)
+        .map(round1);
+
+    Ok(readiness(
+        &log,
+        baseline_resting_heart_rate,
+        baseline_hrv_ms,
+    ))
+}
+
+/// The adjustment to apply to a session started today, if readiness is low.
+pub async fn session_adjustment(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+) -> Result<Option<SessionAdjustment>, AppError> {
+    let today = ensure_profile(&mut *conn, user_id).await?.today();
+    let Some(readiness) = load_readiness(conn, user_id, today).await? else {
+        return Ok(None);
+    };
+    if readiness.score >= LOW_READINESS_SCORE {
+        return Ok(None);
+    }
+
+    Ok(Some(SessionAdjustment {
+        readiness_score: readiness.score,
+        weight_factor: LIGHTER_WEIGHT_FACTOR,
+        sets_removed: 1,
+        reason: format!(
+            "Readiness {}/100: lighter session suggested (weights -{}%, one set fewer per exercise)",
+            readiness.score,
+            ((1.0 - LIGHTER_WEIGHT_FACTOR) * 100.0).round()
+        ),
+    }))
+}
+
+/// Number of planned sets to keep; every exercise keeps at least one.
+pub fn lighter_set_count(adjustment: &SessionAdjustment, planned: usize) -> usize {
+    planned.saturating_sub(adjustment.sets_removed).max(1)
+}
+
+pub fn lighter_weight(adjustment: &SessionAdjustment, weight_kg: f64, increment_kg: f64) -> f64 {
+    round_to_increment(weight_kg * adjustment.weight_factor, increment_kg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log() -> WellnessLog {
+        WellnessLog {
+            id: Uuid::nil(),
+            user_id: Uuid::nil(),
+            log_date: NaiveDate::from_ymd_opt(2024, 12, 20).unwrap(),
+            water_ml: None,
+            sleep_minutes: None,
+            sleep_quality: None,
+            soreness: None,
+            stress: None,
+            resting_heart_rate: None,
+            hrv_ms: None,
+            notes: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn weighs_available_components() {
+        let mut rested = log();
+        rested.sleep_minutes = Some(480);
+        rested.sleep_quality = Some(5);
+        rested.soreness = Some(1);
+        rested.stress = Some(2);
+        let score = readiness(&rested, None, None).unwrap();
+        // (0.3 x 100 + 0.15 x 100 + 0.15 x 75) / 0.6
+        assert_eq!(score.score, 94);
+        assert_eq!(score.components.stress, Some(75));
+        assert_eq!(score.components.hrv, None);
+        assert_eq!(score.suggestion, SUGGESTION_TRAIN);
+
+        // Heart rate and HRV need a baseline to count
+        let mut only_hr = log();
+        only_hr.resting_heart_rate = Some(60);
+        assert!(readiness(&only_hr, None, None).is_none());
+    }
+
+    #[test]
+    fn compares_heart_rate_and_hrv_to_baseline() {
+        let mut strained = log();
+        strained.resting_heart_rate = Some(58);
+        strained.hrv_ms = Some(45.0);
+        strained.sleep_minutes = Some(300);
+        strained.soreness = Some(4);
+        let score = readiness(&strained, Some(52.0), Some(60.0)).unwrap();
+        assert_eq!(score.components.resting_heart_rate, Some(40));
+        assert_eq!(score.components.hrv, Some(0));
+        assert_eq!(score.components.sleep, Some(63));
+        assert_eq!(score.components.soreness, Some(25));
+        assert!(score.score < LOW_READINESS_SCORE);
+        assert_eq!(score.suggestion, SUGGESTION_LIGHTER);
+
+        assert_eq!(hrv_score(66.0, 60.0), 100.0);
+        assert_eq!(resting_heart_rate_score(50, 52.0), 100.0);
+    }
+
+    #[test]
+    fn lightens_sessions() {
+        let adjustment = SessionAdjustment {
+            readiness_score: 40,
+            weight_factor: LIGHTER_WEIGHT_FACTOR,
+            sets_removed: 1,
+            reason: String::new(),
+        };
+        assert_eq!(lighter_set_count(&adjustment, 3), 2);
+        assert_eq!(lighter_set_count(&adjustment, 1), 1);
+        assert_eq!(lighter_weight(&adjustment, 100.0, 2.5), 90.0);
+        assert_eq!(lighter_weight(&adjustment, 62.5, 2.5), 57.5);
+    }
+}
@@ -0,0 +1,204 @@
+use sqlx::{PgConnection, PgExecutor};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    models::workout::{
+        SaveWorkoutExercise, Workout, WorkoutDetail, WorkoutExercise, WorkoutExerciseDetail,
+        WorkoutSet,
+    },
+};
+
+/// Fails unless every referenced exercise is in the global catalog or owned by the user.
+pub async fn check_exercise_access<'e>(
+    executor: impl PgExecutor<'e>,
+    user_id: Uuid,
+    exercise_ids: &[Uuid],
+) -> Result<(), AppError> {
+    let unique: Vec<Uuid> = exercise_ids
+        .iter()
+        .copied()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+
+    let found = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM exercises WHERE id = ANY($1) AND (owner_id IS NULL OR owner_id = $2)"#,
+        &unique,
+        user_id
+    )
+    .fetch_one(executor)
+    .await?;
+
+    if found != unique.len() as i64 {
+        return Err(AppError::BadRequest("Unknown exercise referenced".into()));
+    }
+    Ok(())
+}
+
+/// Fails if something shared references exercises the copier cannot use:
+/// private exercises of the original author cannot be referenced by someone else.
+pub async fn check_shared_exercises<'e>(
+    executor: impl PgExecutor<'e>,
+    user_id: Uuid,
+    exercise_ids: &[Uuid],
+) -> Result<(), AppError> {
+    check_exercise_access(executor, user_id, exercise_ids)
+        .await
+        .map_err(|e| match e {
+            AppError::BadRequest(_) => {
+                AppError::BadRequest("The original uses exercises private to its author".into())
+            }
+            e => e,
+        })
+}
+
+/// Fails unless the template exists and belongs to the user.
+pub async fn check_template_access<'e>(
+    executor: impl PgExecutor<'e>,
+    user_id: Uuid,
+    template_id: Uuid,
+) -> Result<(), AppError> {
+    let owned = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM workout_templates WHERE id = $1 AND user_id = $2) AS "owned!""#,
+        template_id,
+        user_id
+    )
+    .fetch_one(executor)
+    .await?;
+
+    if !owned {
+        return Err(AppError::BadRequest("Unknown template referenced".into()));
+    }
+    Ok(())
+}
+
+/// Inserts the exercises and sets of a workout. Meant to run inside the
+/// transaction that created or cleared the workout.
+pub async fn insert_workout_exercises(
+    conn: &mut PgConnection,
+    workout_id: Uuid,
+    exercises: &[SaveWorkoutExercise],
+) -> Result<(), AppError> {
+    for (position, exercise) in exercises.iter().enumerate() {
+        let workout_exercise_id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO workout_exercises (workout_id, exercise_id, position, notes, group_label)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id
+            "#,
+            workout_id,
+            exercise.exercise_id,
+            position as i32,
+            exercise.notes,
+            exercise.group_label
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+
+        for (set_position, set) in exercise.sets.iter().enumerate() {
+            sqlx::query!(
+                r#"
+                INSERT INTO sets (workout_exercise_id, position, set_type, reps, weight_kg, duration_seconds, distance_m, rpe, rir, notes, completed_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                "#,
+                workout_exercise_id,
+                set_position as i32,
+                set.set_type,
+                set.reps,
+                set.weight_kg,
+                set.duration_seconds,
+                set.distance_m,
+                set.rpe,
+                set.rir,
+                set.notes,
+                set.completed_at
+            )
+            .execute(&mut *conn)
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Loads a workout with its exercises and sets, scoped to the owning user.
+pub async fn load_workout_detail(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    workout_id: Uuid,
+) -> Result<WorkoutDetail, AppError> {
+    let workout = sqlx::query_as!(
+        Workout,
+        "SELECT * FROM workouts WHERE id = $1 AND user_id = $2",
+        workout_id,
+        user_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or(AppError::NotFound("Workout not found".into()))?;
+
+    Ok(attach_exercises(conn, vec![workout]).await?.remove(0))
+}
+
+/// Loads the exercises and sets of several workouts at once, keeping their order.
+pub async fn attach_exercises(
+    conn: &mut PgConnection,
+    workouts: Vec<Workout>,
+) -> Result<Vec<WorkoutDetail>, AppError> {
+    let workout_ids: Vec<Uuid> = workouts.iter().map(|w| w.id).collect();
+
+    let exercises = sqlx::query_as!(
+        WorkoutExercise,
+        r#"
+        SELECT we.id, we.workout_id, we.exercise_id, e.name AS exercise_name, we.position, we.notes, we.group_label
+        FROM workout_exercises we
+        JOIN exercises e ON e.id = we.exercise_id
+        WHERE we.workout_id = ANY($1)
+        ORDER BY we.position
+        "#,
+        &workout_ids
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let sets = sqlx::query_as!(
+        WorkoutSet,
+        r#"
+        SELECT s.* FROM sets s
+        JOIN workout_exercises we ON we.id = s.workout_exercise_id
+        WHERE we.workout_id = ANY($1)
+        ORDER BY s.position
+        "#,
+        &workout_ids
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut sets_by_exercise: HashMap<Uuid, Vec<WorkoutSet>> = HashMap::new();
+    for set in sets {
+        sets_by_exercise
+            .entry(set.workout_exercise_id)
+            .or_default()
+            .push(set);
+    }
+    let mut exercises_by_workout: HashMap<Uuid, Vec<WorkoutExerciseDetail>> = HashMap::new();
+    for exercise in exercises {
+        exercises_by_workout
+            .entry(exercise.workout_id)
+            .or_default()
+            .push(WorkoutExerciseDetail {
+                sets: sets_by_exercise.remove(&exercise.id).unwrap_or_default(),
+                exercise,
+            });
+    }
+
+    Ok(workouts
+        .into_iter()
+        .map(|workout| WorkoutDetail {
+            exercises: exercises_by_workout.remove(&workout.id).unwrap_or_default(),
+            workout,
+        })
+        .collect())
+}
@@ -0,0 +1,169 @@
+use axum::body::Bytes;
+use chrono::{DateTime, Utc};
+use futures_util::{stream, Stream};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    models::{
+        export::WorkoutSetRecord,
+        workout::{Workout, EXPORT_FORMAT_JSON},
+    },
+    services::workout::attach_exercises,
+};
+
+/// Workouts loaded per chunk; keeps memory flat no matter how long the history is.
+const EXPORT_BATCH_SIZE: i64 = 100;
+
+/// Where a history export stands between two chunks.
+struct ExportCursor {
+    pool: PgPool,
+    user_id: Uuid,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+    json: bool,
+    /// Key of the last exported workout; batches continue after it.
+    after: Option<(DateTime<Utc>, Uuid)>,
+    /// Whether anything was written yet: the CSV header or the opening bracket.
+    started: bool,
+    finished: bool,
+}
+
+impl ExportCursor {
+    async fn next_batch(&self) -> Result<Vec<Workout>, AppError> {
+        let (after_started_at, after_id) = self.after.unzip();
+        let workouts = sqlx::query_as!(
+            Workout,
+            r#"
+            SELECT * FROM workouts
+            WHERE user_id = $1
+              AND ($2::TIMESTAMPTZ IS NULL OR started_at >= $2)
+              AND ($3::TIMESTAMPTZ IS NULL OR started_at < $3)
+              AND ($4::TIMESTAMPTZ IS NULL OR (started_at, id) > ($4, $5))
+            ORDER BY started_at, id
+            LIMIT $6
+            "#,
+            self.user_id,
+            self.from,
+            self.to,
+            after_started_at,
+            after_id,
+            EXPORT_BATCH_SIZE
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(workouts)
+    }
+
+    async fn render_json(&self, workouts: Vec<Workout>) -> Result<Vec<u8>, AppError> {
+        let mut conn = self.pool.acquire().await?;
+        let mut chunk = Vec::new();
+        for (index, detail) in attach_exercises(&mut conn, workouts)
+            .await?
+            .iter()
+            .enumerate()
+        {
+            if self.started || index > 0 {
+                chunk.push(b',');
+            }
+            serde_json::to_writer(&mut chunk, detail).map_err(|e| {
+                tracing::error!("Failed to serialize workout export: {:?}", e);
+                AppError::InternalServerError
+            })?;
+        }
+        Ok(chunk)
+    }
+
+    async fn render_csv(&self, workouts: &[Workout]) -> Result<Vec<u8>, AppError> {
+        let ids: Vec<Uuid> = workouts.iter().map(|w| w.id).collect();
+        let rows = sqlx::query_as!(
+            WorkoutSetRecord,
+            r#"
+            SELECT w.id AS workout_id, w.name AS workout_name, w.started_at, w.finished_at,
+                   e.name AS exercise, we.position AS exercise_position, s.position AS set_position,
+                   s.set_type, s.reps, s.weight_kg, s.duration_seconds, s.distance_m, s.rpe, s.rir, s.notes
+            FROM workouts w
+            JOIN workout_exercises we ON we.workout_id = w.id
+            JOIN exercises e ON e.id = we.exercise_id
+            JOIN sets s ON s.workout_exercise_id = we.id
+            WHERE w.id = ANY($1)
+            ORDER BY w.started_at, w.id, we.position, s.position
+            "#,
+            &ids
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut writer = csv::WriterBuilder::new()
+            .has_headers(!self.started)
+            .from_writer(Vec::new());
+        for row in &rows {
+            writer.serialize(row).map_err(|e| {
+                tracing::error!("Failed to write workout export: {:?}", e);
+                AppError::InternalServerError
+            })?;
+        }
+        writer.into_inner().map_err(|e| {
+            tracing::error!("Failed to write workout export: {:?}", e);
+            AppError::InternalServerError
+        })
+    }
+
+    /// Produces the next chunk of the export, or `None` once it is complete.
+    async fn next_chunk(mut self) -> Result<Option<(Bytes, Self)>, AppError> {
+        if self.finished {
+            return Ok(None);
+        }
+        let workouts = self.next_batch().await?;
+        self.finished = (workouts.len() as i64) < EXPORT_BATCH_SIZE;
+        let last = workouts.last().map(|w| (w.started_at, w.id));
+
+        let mut chunk = Vec::new();
+        if self.json {
+            if !self.started {
+                chunk.push(b'[');
+            }
+            chunk.extend(self.render_json(workouts).await?);
+            self.started = true;
+            if self.finished {
+                chunk.push(b']');
+            }
+        } else {
+            chunk = self.render_csv(&workouts).await?;
+            self.started |= !chunk.is_empty();
+        }
+        self.after = last.or(self.after);
+
+        Ok(Some((Bytes::from(chunk), self)))
+    }
+}
+
+/// Streams a user's workouts oldest first, as one CSV row per set or as a
+/// JSON array of workouts with their exercises and sets.
+pub fn workout_history_stream(
+    pool: PgPool,
+    user_id: Uuid,
+    format: &str,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+) -> impl Stream<Item = Result<Bytes, std::io::Error>> {
+    let cursor = ExportCursor {
+        pool,
+        user_id,
+        from,
+        to,
+        json: format == EXPORT_FORMAT_JSON,
+        after: None,
+        started: false,
+        finished: false,
+    };
+
+    // The response has already started, so a failure can only cut it short.
+    stream::try_unfold(cursor, |cursor| async move {
+        cursor.next_chunk().await.map_err(|e| {
+            tracing::error!("Workout export failed: {:?}", e);
+            std::io::Error::other("workout export failed")
+        })
+    })
+}
@@ -1,4 +1,4 @@
-use crate::services::email::EmailService;
+use crate::services::{email::EmailService, storage::ObjectStorage};
 use sqlx::PgPool;
 use std::sync::Arc;
 
@@ -6,4 +6,5 @@ use std::sync::Arc;
 pub struct AppState {
     pub pool: PgPool,
     pub email_service: Arc<EmailService>,
+    pub storage: Arc<dyn ObjectStorage>,
 }
@@ -0,0 +1,97 @@
+use axum::http::StatusCode;
+use beppo_fit_backend::{app, services::achievement::backfill_achievements};
+use serde_json::json;
+use sqlx::PgPool;
+
+mod common;
+
+fn find<'a>(achievements: &'a serde_json::Value, code: &str) -> &'a serde_json::Value {
+    achievements["achievements"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|a| a["code"] == code)
+        .unwrap()
+}
+
+#[sqlx::test]
+async fn test_achievements_unlock_and_backfill(pool: PgPool) {
+    let app = app(pool.clone()).await;
+    let token = common::register(&app, "achievement_test@example.com").await;
+    let squat = common::exercise_id(&pool, "barbell-back-squat").await;
+
+    // Four Mondays in a row
+    for (day, weight) in [("02", 100.0), ("09", 102.5), ("16", 105.0), ("23", 107.5)] {
+        let (status, _) = common::send(
+            &app,
+            "POST",
+            "/workouts",
+            Some(&token),
+            Some(json!({
+                "started_at": format!("2024-12-{}T17:00:00Z", day),
+                "finished_at": format!("2024-12-{}T18:00:00Z", day),
+                "exercises": [{ "exercise_id": squat, "sets": [
+                    { "reps": 5, "weight_kg": weight },
+                    { "reps": 5, "weight_kg": weight },
+                    { "reps": 5, "weight_kg": weight }
+                ] }]
+            })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    let (status, achievements) =
+        common::send(&app, "GET", "/me/achievements", Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(achievements["streak"]["longest_weeks"], 4);
+
+    let first = find(&achievements, "first_workout");
+    assert_eq!(first["unlocked_at"], "2024-12-02T18:00:00Z");
+    let streak = find(&achievements, "streak_4_weeks");
+    assert_eq!(streak["unlocked_at"], "2024-12-23T18:00:00Z");
+    assert!(!find(&achievements, "first_pr")["unlocked_at"].is_null());
+    // Each session broke several squat records but counts once
+    let prs = find(&achievements, "prs_10");
+    assert!(prs["unlocked_at"].is_null());
+    assert_eq!(prs["progress"], 4.0);
+    let ten = find(&achievements, "workouts_10");
+    assert!(ten["unlocked_at"].is_null());
+    assert_eq!(ten["progress"], 4.0);
+    assert_eq!(ten["metric"], "workouts");
+    assert_eq!(find(&achievements, "volume_10t")["progress"], 6225.0);
+
+    let (_, notifications) =
+        common::send(&app, "GET", "/me/notifications", Some(&token), None).await;
+    let unlocked = notifications
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|n| n["kind"] == "achievement_unlocked")
+        .count();
+
+    // The backfill restores lost unlocks with their original dates, once
+    sqlx::query!("DELETE FROM user_achievements WHERE code = 'first_workout'")
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert_eq!(backfill_achievements(&pool).await.unwrap(), 1);
+    assert_eq!(backfill_achievements(&pool).await.unwrap(), 0);
+
+    let (_, achievements) = common::send(&app, "GET", "/me/achievements", Some(&token), None).await;
+    assert_eq!(
+        find(&achievements, "first_workout")["unlocked_at"],
+        "2024-12-02T18:00:00Z"
+    );
+    let (_, notifications) =
+        common::send(&app, "GET", "/me/notifications", Some(&token), None).await;
+    assert_eq!(
+        notifications
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|n| n["kind"] == "achievement_unlocked")
+            .count(),
+        unlocked
+    );
+}
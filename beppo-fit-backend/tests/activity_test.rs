@@ -0,0 +1,363 @@
+use axum::http::StatusCode;
+use beppo_fit_backend::app;
+use serde_json::json;
+use sqlx::PgPool;
+
+mod common;
+
+/// A GPX run heading north at about 5.6 m/s, one point every 10 seconds.
+fn gpx_run(start_minute: u32, points: usize) -> String {
+    let mut trkpts = String::new();
+    for i in 0..points {
+        trkpts.push_str(&format!(
+            r#"<trkpt lat="{:.6}" lon="13.4"><ele>{}</ele><time>2024-12-01T07:{:02}:{:02}Z</time>
+               <extensions><gpxtpx:TrackPointExtension><gpxtpx:hr>{}</gpxtpx:hr></gpxtpx:TrackPointExtension></extensions></trkpt>"#,
+            52.5 + i as f64 * 0.0005,
+            30 + i * 3,
+            start_minute + (i as u32 * 10) / 60,
+            (i * 10) % 60,
+            140 + i
+        ));
+    }
+    format!(
+        r#"<?xml version="1.0"?>
+        <gpx version="1.1" xmlns="http://www.topografix.com/GPX/1/1" xmlns:gpxtpx="http://www.garmin.com/xmlschemas/TrackPointExtension/v1">
+          <trk><name>Tempo</name><type>running</type><trkseg>{}</trkseg></trk>
+        </gpx>"#,
+        trkpts
+    )
+}
+
+/// A FIT file with one record message per (seconds since the FIT epoch, latitude) sample.
+fn fit_ride(samples: &[(u32, f64)]) -> Vec<u8> {
+    let mut data = vec![0x40, 0, 0, 20, 0, 3, 253, 4, 0x86, 0, 4, 0x85, 1, 4, 0x85];
+    for (timestamp, lat) in samples {
+        data.push(0x00);
+        data.extend_from_slice(&timestamp.to_le_bytes());
+        data.extend_from_slice(&((lat / 180.0 * 2_147_483_648.0) as i32).to_le_bytes());
+        data.extend_from_slice(&((13.4 / 180.0 * 2_147_483_648.0) as i32).to_le_bytes());
+    }
+    let mut file = vec![12, 0x10, 0, 0];
+    file.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    file.extend_from_slice(b".FIT");
+    file.extend_from_slice(&data);
+    file.extend_from_slice(&[0, 0]);
+    file
+}
+
+#[sqlx::test]
+async fn test_import_gpx_and_deduplicate(pool: PgPool) {
+    let app = app(pool).await;
+    let token = common::register(&app, "activity_gpx@example.com").await;
+
+    let gpx = gpx_run(0, 11);
+    let (status, activity) = common::send_multipart(
+        &app,
+        "/activities/import",
+        &token,
+        ("application/gpx+xml", gpx.as_bytes()),
+        &[("name", "Sunday tempo")],
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(activity["activity_type"], "run");
+    assert_eq!(activity["name"], "Sunday tempo");
+    assert_eq!(activity["source_format"], "gpx");
+    assert_eq!(activity["started_at"], "2024-12-01T07:00:00Z");
+    assert_eq!(activity["duration_seconds"], 100);
+    assert_eq!(activity["moving_seconds"], 100);
+    assert_eq!(activity["distance_m"].as_f64().unwrap().round(), 556.0);
+    assert_eq!(activity["elevation_gain_m"], 30.0);
+    assert_eq!(activity["max_heart_rate"], 150);
+    assert!(activity["polyline"].as_str().unwrap().len() > 10);
+    let uri = format!("/activities/{}", activity["id"].as_str().unwrap());
+
+    let (status, track) =
+        common::send(&app, "GET", &format!("{}/track", uri), Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let track = track.as_array().unwrap();
+    assert_eq!(track.len(), 11);
+    assert_eq!(track[10]["lat"], 52.505);
+    assert_eq!(track[10]["heart_rate"], 150);
+
+    // The same recording again, even re-exported with a second's offset
+    let (status, _) = common::send_multipart(
+        &app,
+        "/activities/import",
+        &token,
+        (
+            "application/gpx+xml",
+            gpx.replace(":00Z", ":01Z").as_bytes(),
+        ),
+        &[],
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, _) = common::send_multipart(
+        &app,
+        "/activities/import",
+        &token,
+        ("text/plain", b"not an activity"),
+        &[],
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (_, activities) = common::send(&app, "GET", "/activities", Some(&token), None).await;
+    assert_eq!(activities.as_array().unwrap().len(), 1);
+    let (status, _) = common::send(&app, "DELETE", &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = common::send(&app, "GET", &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn test_import_tcx_and_fit(pool: PgPool) {
+    let app = app(pool).await;
+    let token = common::register(&app, "activity_tcx@example.com").await;
+
+    let tcx = r#"<TrainingCenterDatabase xmlns="http://www.garmin.com/xmlschemas/TrainingCenterDatabase/v2">
+      <Activities><Activity Sport="Biking"><Id>2024-12-02T07:00:00Z</Id>
+        <Lap StartTime="2024-12-02T07:00:00Z"><TotalTimeSeconds>60</TotalTimeSeconds><DistanceMeters>600</DistanceMeters><Track>
+          <Trackpoint><Time>2024-12-02T07:00:00Z</Time><DistanceMeters>0</DistanceMeters><Cadence>80</Cadence></Trackpoint>
+          <Trackpoint><Time>2024-12-02T07:01:00Z</Time><DistanceMeters>600</DistanceMeters><Cadence>90</Cadence></Trackpoint>
+        </Track></Lap>
+        <Lap StartTime="2024-12-02T07:01:00Z"><TotalTimeSeconds>60</TotalTimeSeconds><Track>
+          <Trackpoint><Time>2024-12-02T07:02:00Z</Time><DistanceMeters>1300</DistanceMeters><Cadence>94</Cadence></Trackpoint>
+        </Track></Lap>
+      </Activity></Activities>
+    </TrainingCenterDatabase>"#;
+    let (status, activity) = common::send_multipart(
+        &app,
+        "/activities/import",
+        &token,
+        ("application/vnd.garmin.tcx+xml", tcx.as_bytes()),
+        &[],
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(activity["activity_type"], "ride");
+    assert_eq!(activity["distance_m"], 1300.0);
+    assert_eq!(activity["avg_cadence"], 88);
+    // Indoor recordings have no route
+    assert!(activity["polyline"].is_null());
+    let laps = activity["laps"].as_array().unwrap();
+    assert_eq!(laps.len(), 2);
+    assert_eq!(laps[1]["distance_m"], 700.0);
+
+    // 2024-12-03T07:00:00Z in FIT time
+    let start = 1_733_209_200 - 631_065_600;
+    let fit = fit_ride(&[(start, 52.5), (start + 30, 52.501), (start + 60, 52.502)]);
+    let (status, activity) = common::send_multipart(
+        &app,
+        "/activities/import",
+        &token,
+        ("application/octet-stream", &fit),
+        &[("activity_type", "ride")],
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(activity["source_format"], "fit");
+    assert_eq!(activity["activity_type"], "ride");
+    assert_eq!(activity["started_at"], "2024-12-03T07:00:00Z");
+    assert_eq!(activity["distance_m"].as_f64().unwrap().round(), 222.0);
+
+    let (status, _) = common::send_multipart(
+        &app,
+        "/activities/import",
+        &token,
+        ("application/octet-stream", &fit),
+        &[("activity_type", "skating")],
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (_, rides) = common::send(
+        &app,
+        "GET",
+        "/activities?activity_type=ride&from=2024-12-03T00:00:00Z",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(rides.as_array().unwrap().len(), 1);
+}
+
+#[sqlx::test]
+async fn test_splits_zones_and_manual_activities(pool: PgPool) {
+    let app = app(pool).await;
+    let token = common::register(&app, "activity_metrics@example.com").await;
+
+    let (status, _) = common::send(
+        &app,
+        "PATCH",
+        "/me/profile",
+        Some(&token),
+        Some(json!({ "max_heart_rate": 190, "resting_heart_rate": 195 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, profile) = common::send(
+        &app,
+        "PATCH",
+        "/me/profile",
+        Some(&token),
+        Some(json!({ "max_heart_rate": 190, "resting_heart_rate": 60, "heart_rate_zone_method": "karvonen" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(profile["heart_rate_zone_method"], "karvonen");
+
+    // About 2.17 km in 390 seconds
+    let (status, run) = common::send_multipart(
+        &app,
+        "/activities/import",
+        &token,
+        ("application/gpx+xml", gpx_run(0, 40).as_bytes()),
+        &[],
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(run["pace_seconds_per_km"].as_f64().unwrap().round(), 180.0);
+    assert_eq!(run["split_unit"], "km");
+    let splits = run["splits"].as_array().unwrap();
+    assert_eq!(splits.len(), 3);
+    assert_eq!(splits[0]["distance_m"], 1000.0);
+    assert_eq!(
+        splits[0]["duration_seconds"].as_f64().unwrap().round(),
+        180.0
+    );
+    assert!(splits[1]["avg_heart_rate"].as_i64().unwrap() > 150);
+    let zones = &run["heart_rate_zones"];
+    assert_eq!(zones["method"], "karvonen");
+    assert_eq!(zones["zones"][0]["min_bpm"], 125);
+    let in_zones: i64 = zones["zones"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|z| z["seconds"].as_i64().unwrap())
+        .sum();
+    assert_eq!(
+        in_zones + zones["below_zones_seconds"].as_i64().unwrap(),
+        390
+    );
+    let run_uri = format!("/activities/{}", run["id"].as_str().unwrap());
+
+    // Recorded metrics are fixed, the name is not
+    let (status, _) = common::send(
+        &app,
+        "PUT",
+        &run_uri,
+        Some(&token),
+        Some(json!({ "distance_m": 3000.0 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, renamed) = common::send(
+        &app,
+        "PUT",
+        &run_uri,
+        Some(&token),
+        Some(json!({ "name": "Hill reps", "notes": "Windy" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(renamed["name"], "Hill reps");
+    assert_eq!(renamed["splits"].as_array().unwrap().len(), 3);
+
+    let treadmill = json!({
+        "activity_type": "run",
+        "name": "Treadmill",
+        "started_at": "2024-12-10T18:00:00Z",
+        "duration_seconds": 1500,
+        "distance_m": 5000.0,
+        "avg_heart_rate": 150
+    });
+    let (status, manual) = common::send(
+        &app,
+        "POST",
+        "/activities",
+        Some(&token),
+        Some(treadmill.clone()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(manual["pace_seconds_per_km"], 300.0);
+    assert!(manual["source_format"].is_null());
+    assert!(manual["splits"].as_array().unwrap().is_empty());
+    assert!(manual["heart_rate_zones"].is_null());
+
+    let (status, _) =
+        common::send(&app, "POST", "/activities", Some(&token), Some(treadmill)).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, _) = common::send(
+        &app,
+        "POST",
+        "/activities",
+        Some(&token),
+        Some(json!({
+            "activity_type": "swim",
+            "started_at": "2024-12-11T18:00:00Z",
+            "duration_seconds": 600,
+            "moving_seconds": 700
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let manual_uri = format!("/activities/{}", manual["id"].as_str().unwrap());
+    let (status, manual) = common::send(
+        &app,
+        "PUT",
+        &manual_uri,
+        Some(&token),
+        Some(json!({ "distance_m": 6000.0 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(manual["pace_seconds_per_km"], 250.0);
+
+    let other = common::register(&app, "activity_metrics_other@example.com").await;
+    let (status, _) = common::send(
+        &app,
+        "PUT",
+        &manual_uri,
+        Some(&other),
+        Some(json!({ "name": "Mine" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, summary) = common::send(
+        &app,
+        "GET",
+        "/activities/summary?from=2024-11-01&to=2024-12-31&period=month",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let totals = summary["totals"].as_array().unwrap();
+    assert_eq!(totals.len(), 1);
+    assert_eq!(totals[0]["activity_type"], "run");
+    assert_eq!(totals[0]["activities"], 2);
+    assert_eq!(totals[0]["duration_seconds"], 1890);
+    assert!(totals[0]["distance_m"].as_f64().unwrap() > 8000.0);
+    let periods = summary["periods"].as_array().unwrap();
+    assert_eq!(periods.len(), 1);
+    assert_eq!(periods[0]["period_start"], "2024-12-01");
+
+    // Imperial profiles get mile splits
+    common::send(
+        &app,
+        "PATCH",
+        "/me/profile",
+        Some(&token),
+        Some(json!({ "preferred_units": "imperial" })),
+    )
+    .await;
+    let (_, run) = common::send(&app, "GET", &run_uri, Some(&token), None).await;
+    assert_eq!(run["split_unit"], "mi");
+    assert_eq!(run["splits"].as_array().unwrap().len(), 2);
+}
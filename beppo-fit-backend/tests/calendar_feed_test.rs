@@ -0,0 +1,115 @@
+use axum::http::StatusCode;
+use beppo_fit_backend::app;
+use serde_json::json;
+use sqlx::PgPool;
+
+mod common;
+
+#[sqlx::test]
+async fn test_calendar_feed(pool: PgPool) {
+    let app = app(pool.clone()).await;
+    let token = common::register(&app, "calendar_feed_test@example.com").await;
+    let squat = common::exercise_id(&pool, "barbell-back-squat").await;
+
+    common::send(
+        &app,
+        "PATCH",
+        "/me/profile",
+        Some(&token),
+        Some(json!({ "timezone": "Europe/Berlin" })),
+    )
+    .await;
+    let (_, template) = common::send(
+        &app,
+        "POST",
+        "/templates",
+        Some(&token),
+        Some(json!({ "name": "Legs", "exercises": [{ "exercise_id": squat }] })),
+    )
+    .await;
+    let start = chrono::Utc::now().date_naive() + chrono::Duration::days(1);
+    common::send(
+        &app,
+        "POST",
+        "/schedules",
+        Some(&token),
+        Some(json!({
+            "template_id": template["id"],
+            "start_date": start,
+            "start_time": "18:30:00",
+            "rrule": "FREQ=WEEKLY;COUNT=3"
+        })),
+    )
+    .await;
+
+    let (status, _) = common::send(&app, "GET", "/me/calendar-feed", Some(&token), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, feed) = common::send(&app, "POST", "/me/calendar-feed", Some(&token), None).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let url = feed["url"].as_str().unwrap().to_string();
+    assert!(url.ends_with(".ics"));
+
+    // The feed needs no Authorization header
+    let (status, content_type, calendar) = common::get_text(&app, &url, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(content_type.starts_with("text/calendar"));
+    assert_eq!(calendar.matches("BEGIN:VEVENT").count(), 3);
+    assert!(calendar.contains("SUMMARY:Legs\r\n"));
+    assert!(calendar.contains("DESCRIPTION:Barbell Back Squat\r\n"));
+    assert!(calendar.contains("X-WR-TIMEZONE:Europe/Berlin\r\n"));
+
+    // Rotating the token revokes the old URL
+    let (_, rotated) = common::send(&app, "POST", "/me/calendar-feed", Some(&token), None).await;
+    let (status, _, _) = common::get_text(&app, &url, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let new_url = rotated["url"].as_str().unwrap();
+    let (status, _, _) = common::get_text(&app, new_url, None).await;
+    assert_eq!(status, StatusCode::OK);
+    common::send(&app, "DELETE", "/me/calendar-feed", Some(&token), None).await;
+    let (status, _, _) = common::get_text(&app, new_url, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn test_single_session_ics_uses_profile_timezone(pool: PgPool) {
+    let app = app(pool.clone()).await;
+    let token = common::register(&app, "session_ics_test@example.com").await;
+
+    common::send(
+        &app,
+        "PATCH",
+        "/me/profile",
+        Some(&token),
+        Some(json!({ "timezone": "Europe/Berlin" })),
+    )
+    .await;
+    let (_, schedule) = common::send(
+        &app,
+        "POST",
+        "/schedules",
+        Some(&token),
+        Some(json!({
+            "title": "Run, easy; 5k",
+            "start_date": "2024-06-03",
+            "start_time": "18:30:00",
+            "rrule": "FREQ=MONTHLY;INTERVAL=6"
+        })),
+    )
+    .await;
+    let sessions = format!("/schedules/{}/sessions", schedule["id"].as_str().unwrap());
+
+    // Summer and winter time in Berlin
+    let (status, content_type, summer) =
+        common::get_text(&app, &format!("{}/2024-06-03/ics", sessions), Some(&token)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(content_type.starts_with("text/calendar"));
+    assert!(summer.contains("DTSTART:20240603T163000Z\r\nDTEND:20240603T173000Z\r\n"));
+    assert!(summer.contains("SUMMARY:Run\\, easy\\; 5k\r\n"));
+    let (_, _, winter) =
+        common::get_text(&app, &format!("{}/2024-12-03/ics", sessions), Some(&token)).await;
+    assert!(winter.contains("DTSTART:20241203T173000Z\r\n"));
+
+    let (status, _, _) =
+        common::get_text(&app, &format!("{}/2024-12-04/ics", sessions), Some(&token)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,160 @@
+#![allow(dead_code)]
+
+use axum::{
+    body::Body,
+    http::{header::CONTENT_TYPE, Request, StatusCode},
+    Router,
+};
+use tower::ServiceExt;
+
+/// Registers a user and returns the JWT from the auth response.
+pub async fn register(app: &Router, email: &str) -> String {
+    let (status, body) = send(
+        app,
+        "POST",
+        "/auth/register",
+        None,
+        Some(serde_json::json!({ "email": email, "password": "Password123!" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    body["token"].as_str().unwrap().to_string()
+}
+
+/// Registers a user and returns the JWT together with the user's id.
+pub async fn register_user(app: &Router, email: &str) -> (String, String) {
+    let (status, body) = send(
+        app,
+        "POST",
+        "/auth/register",
+        None,
+        Some(serde_json::json!({ "email": email, "password": "Password123!" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    (
+        body["token"].as_str().unwrap().to_string(),
+        body["user"]["id"].as_str().unwrap().to_string(),
+    )
+}
+
+/// Sends a JSON request and returns the status with the parsed JSON body (`Null` if empty).
+pub async fn send(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    token: Option<&str>,
+    body: Option<serde_json::Value>,
+) -> (StatusCode, serde_json::Value) {
+    let mut request = Request::builder().uri(uri).method(method);
+    if let Some(token) = token {
+        request = request.header("authorization", format!("Bearer {}", token));
+    }
+    let request = match body {
+        Some(body) => request
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap(),
+        None => request.body(Body::empty()).unwrap(),
+    };
+
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json = serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null);
+    (status, json)
+}
+
+/// Sends a GET request and returns the status, content type and body as text.
+pub async fn get_text(
+    app: &Router,
+    uri: &str,
+    token: Option<&str>,
+) -> (StatusCode, String, String) {
+    let mut request = Request::builder().uri(uri);
+    if let Some(token) = token {
+        request = request.header("authorization", format!("Bearer {}", token));
+    }
+    let response = app
+        .clone()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let content_type = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .map(|v| v.to_str().unwrap().to_string())
+        .unwrap_or_default();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (
+        status,
+        content_type,
+        String::from_utf8(bytes.to_vec()).unwrap(),
+    )
+}
+
+/// Sends a `multipart/form-data` request with a single `file` part plus extra text fields.
+pub async fn send_multipart(
+    app: &Router,
+    uri: &str,
+    token: &str,
+    file: (&str, &[u8]),
+    fields: &[(&str, &str)],
+) -> (StatusCode, serde_json::Value) {
+    let boundary = "beppofit-test-boundary";
+    let mut body = Vec::new();
+    for (name, value) in fields {
+        body.extend_from_slice(
+            format!(
+                "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+                boundary, name, value
+            )
+            .as_bytes(),
+        );
+    }
+    body.extend_from_slice(
+        format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"upload\"\r\nContent-Type: {}\r\n\r\n",
+            boundary, file.0
+        )
+        .as_bytes(),
+    );
+    body.extend_from_slice(file.1);
+    body.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+
+    let request = Request::builder()
+        .uri(uri)
+        .method("POST")
+        .header("authorization", format!("Bearer {}", token))
+        .header(
+            "content-type",
+            format!("multipart/form-data; boundary={}", boundary),
+        )
+        .body(Body::from(body))
+        .unwrap();
+
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json = serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null);
+    (status, json)
+}
+
+/// Looks up the id of a seeded catalog exercise by slug.
+pub async fn exercise_id(pool: &sqlx::PgPool, slug: &str) -> String {
+    sqlx::query_scalar!(
+        "SELECT id FROM exercises WHERE slug = $1 AND owner_id IS NULL",
+        slug
+    )
+    .fetch_one(pool)
+    .await
+    .unwrap()
+    .to_string()
+}
@@ -0,0 +1,202 @@
+use axum::http::StatusCode;
+use beppo_fit_backend::app;
+use serde_json::json;
+use sqlx::PgPool;
+
+mod common;
+
+#[sqlx::test]
+async fn test_exercise_catalog_filtering(pool: PgPool) {
+    let app = app(pool.clone()).await;
+    let token = common::register(&app, "exercise_test@example.com").await;
+
+    let (status, body) = common::send(
+        &app,
+        "GET",
+        "/exercises?muscle=chest&equipment=barbell",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let exercises = body.as_array().unwrap();
+    assert!(!exercises.is_empty());
+    for exercise in exercises {
+        assert_eq!(exercise["equipment"], "barbell");
+        let trains_chest = ["primary_muscles", "secondary_muscles"]
+            .iter()
+            .any(|field| {
+                exercise[field]
+                    .as_array()
+                    .unwrap()
+                    .contains(&"chest".into())
+            });
+        assert!(trains_chest);
+    }
+
+    let (status, body) = common::send(
+        &app,
+        "GET",
+        "/exercises?q=barbell%20bench",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let bench = body
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|e| e["slug"] == "barbell-bench-press")
+        .expect("seeded bench press");
+
+    // LIKE wildcards in the search are matched literally
+    let (_, body) = common::send(&app, "GET", "/exercises?q=%25", Some(&token), None).await;
+    assert!(body.as_array().unwrap().is_empty());
+    let (_, body) = common::send(&app, "GET", "/exercises?q=b_r", Some(&token), None).await;
+    assert!(body.as_array().unwrap().is_empty());
+
+    let (status, body) = common::send(
+        &app,
+        "GET",
+        &format!("/exercises/{}", bench["id"].as_str().unwrap()),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["name"], "Barbell Bench Press");
+
+    let (status, _) =
+        common::send(&app, "GET", "/exercises?muscle=wings", Some(&token), None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = common::send(
+        &app,
+        "GET",
+        "/exercises/00000000-0000-0000-0000-000000000000",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // No seeded exercise lists a muscle as both primary and secondary
+    let overlapping = sqlx::query_scalar!(
+        "SELECT COUNT(*) FROM exercises WHERE primary_muscles && secondary_muscles"
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(overlapping, Some(0));
+}
+
+#[sqlx::test]
+async fn test_custom_exercise_lifecycle(pool: PgPool) {
+    let app = app(pool.clone()).await;
+    let owner = common::register(&app, "custom_owner@example.com").await;
+    let other = common::register(&app, "custom_other@example.com").await;
+
+    let (status, _) = common::send(
+        &app,
+        "POST",
+        "/exercises",
+        Some(&owner),
+        Some(json!({ "name": "Zombie Squat", "primary_muscles": ["wings"], "equipment": "barbell", "category": "strength" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, created) = common::send(
+        &app,
+        "POST",
+        "/exercises",
+        Some(&owner),
+        Some(json!({
+            "name": "Zombie Squat",
+            "primary_muscles": ["quadriceps"],
+            "secondary_muscles": ["abdominals"],
+            "equipment": "barbell",
+            "category": "strength",
+            "mechanics": "compound"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(created["slug"], "zombie-squat");
+    let id = created["id"].as_str().unwrap();
+
+    // Only the owner can see it
+    let (_, mine) = common::send(&app, "GET", "/exercises?q=zombie", Some(&owner), None).await;
+    assert_eq!(mine.as_array().unwrap().len(), 1);
+    let (_, theirs) = common::send(&app, "GET", "/exercises?q=zombie", Some(&other), None).await;
+    assert!(theirs.as_array().unwrap().is_empty());
+    let (status, _) = common::send(
+        &app,
+        "GET",
+        &format!("/exercises/{}", id),
+        Some(&other),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Other users cannot edit it, the owner can
+    let uri = format!("/exercises/{}", id);
+    let (status, _) = common::send(
+        &app,
+        "PATCH",
+        &uri,
+        Some(&other),
+        Some(json!({ "name": "Mine" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, updated) = common::send(
+        &app,
+        "PATCH",
+        &uri,
+        Some(&owner),
+        Some(json!({ "description": "Front squat with arms extended" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(updated["description"], "Front squat with arms extended");
+    let (status, updated) = common::send(
+        &app,
+        "PATCH",
+        &uri,
+        Some(&owner),
+        Some(json!({ "description": null })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(updated["description"].is_null());
+    assert_eq!(updated["mechanics"], "compound");
+
+    // Archived exercises drop out of searches but stay resolvable by id
+    let (status, _) = common::send(&app, "DELETE", &uri, Some(&owner), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, mine) = common::send(&app, "GET", "/exercises?q=zombie", Some(&owner), None).await;
+    assert!(mine.as_array().unwrap().is_empty());
+    let (status, archived) = common::send(&app, "GET", &uri, Some(&owner), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(!archived["archived_at"].is_null());
+
+    // Only admins may promote
+    let promote = format!("/exercises/{}/promote", id);
+    let (status, _) = common::send(&app, "POST", &promote, Some(&owner), None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let admin = common::register(&app, "custom_admin@example.com").await;
+    sqlx::query!("UPDATE users SET is_admin = TRUE WHERE email = 'custom_admin@example.com'")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let (status, promoted) = common::send(&app, "POST", &promote, Some(&admin), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(promoted["owner_id"].is_null());
+
+    let (_, theirs) = common::send(&app, "GET", "/exercises?q=zombie", Some(&other), None).await;
+    assert_eq!(theirs.as_array().unwrap().len(), 1);
+}
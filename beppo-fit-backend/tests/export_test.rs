@@ -0,0 +1,132 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use beppo_fit_backend::app;
+use sqlx::PgPool;
+use std::io::{Cursor, Read};
+use tower::ServiceExt;
+
+mod common;
+
+#[sqlx::test]
+async fn test_data_export_is_generated_and_downloadable(pool: PgPool) {
+    let app = app(pool.clone()).await;
+    let token = common::register(&app, "export_test@example.com").await;
+
+    // 1. Request an export
+    let (status, body) = common::send(&app, "POST", "/auth/me/export", Some(&token), None).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    assert_eq!(body["status"], "pending");
+
+    // 2. Wait for the background job to finish
+    let mut download_token = None;
+    for _ in 0..50 {
+        let row = sqlx::query!(
+            "SELECT status, download_token FROM data_exports WHERE user_id = (SELECT id FROM users WHERE email = 'export_test@example.com')"
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        if row.status == "ready" {
+            download_token = row.download_token;
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    let download_token = download_token.expect("export was not generated");
+
+    // 3. Download and inspect the archive
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/auth/me/export/download?token={}", download_token))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "application/zip");
+
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let mut zip = zip::ZipArchive::new(Cursor::new(bytes.to_vec())).unwrap();
+
+    let mut account = String::new();
+    zip.by_name("account.json")
+        .unwrap()
+        .read_to_string(&mut account)
+        .unwrap();
+    let account: serde_json::Value = serde_json::from_str(&account).unwrap();
+    assert_eq!(account["email"], "export_test@example.com");
+    assert!(account.get("password_hash").is_none());
+
+    let mut identities = String::new();
+    zip.by_name("identities.csv")
+        .unwrap()
+        .read_to_string(&mut identities)
+        .unwrap();
+    assert!(identities.starts_with("provider,subject"));
+    assert!(identities.contains("password,export_test@example.com"));
+    for name in [
+        "README.txt",
+        "program_enrollments.csv",
+        "activity_laps.csv",
+        "strength_imports.csv",
+        "calendar_feeds.csv",
+    ] {
+        assert!(zip.by_name(name).is_ok(), "{} is missing", name);
+    }
+
+    // 4. Unknown tokens are rejected
+    let (status, _) = common::send(
+        &app,
+        "GET",
+        "/auth/me/export/download?token=invalid",
+        None,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test]
+async fn test_one_pending_export_at_a_time(pool: PgPool) {
+    let app = app(pool.clone()).await;
+    let token = common::register(&app, "export_pending@example.com").await;
+    let user_id =
+        sqlx::query_scalar!("SELECT id FROM users WHERE email = 'export_pending@example.com'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+    // An export still being prepared blocks a new request
+    sqlx::query!("INSERT INTO data_exports (user_id) VALUES ($1)", user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let (status, _) = common::send(&app, "POST", "/auth/me/export", Some(&token), None).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // One that has been pending for too long was lost and is given up on
+    sqlx::query!(
+        "UPDATE data_exports SET created_at = NOW() - INTERVAL '2 hours' WHERE user_id = $1",
+        user_id
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    let (status, _) = common::send(&app, "POST", "/auth/me/export", Some(&token), None).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let failed = sqlx::query_scalar!(
+        "SELECT COUNT(*) FROM data_exports WHERE user_id = $1 AND status = 'failed'",
+        user_id
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(failed, Some(1));
+}
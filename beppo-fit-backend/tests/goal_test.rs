@@ -0,0 +1,260 @@
+use axum::http::StatusCode;
+use beppo_fit_backend::app;
+use serde_json::json;
+use sqlx::PgPool;
+
+mod common;
+
+#[sqlx::test]
+async fn test_strength_goal_completes_with_notification(pool: PgPool) {
+    let app = app(pool.clone()).await;
+    let token = common::register(&app, "goal_strength@example.com").await;
+    let squat = common::exercise_id(&pool, "barbell-back-squat").await;
+
+    let (status, goal) = common::send(
+        &app,
+        "POST",
+        "/goals",
+        Some(&token),
+        Some(json!({
+            "goal_type": "strength",
+            "title": "Squat 140",
+            "exercise_id": squat,
+            "target_value": 140.0
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(goal["metric"], "max_weight");
+    assert_eq!(goal["status"], "active");
+    assert!(goal["current_value"].is_null());
+    let uri = format!("/goals/{}", goal["id"].as_str().unwrap());
+
+    for weight in [130.0, 142.5] {
+        let (status, _) = common::send(
+            &app,
+            "POST",
+            "/workouts",
+            Some(&token),
+            Some(json!({
+                "started_at": "2024-12-01T17:00:00Z",
+                "finished_at": "2024-12-01T18:00:00Z",
+                "exercises": [{ "exercise_id": squat, "sets": [{ "reps": 1, "weight_kg": weight }] }]
+            })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    let (_, goal) = common::send(&app, "GET", &uri, Some(&token), None).await;
+    assert_eq!(goal["status"], "completed");
+    assert_eq!(goal["current_value"], 142.5);
+    assert_eq!(goal["progress_percent"], 100.0);
+
+    let (_, notifications) = common::send(
+        &app,
+        "GET",
+        "/me/notifications?unread=true",
+        Some(&token),
+        None,
+    )
+    .await;
+    let notifications: Vec<_> = notifications
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|n| n["kind"] == "goal_completed")
+        .collect();
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0]["goal_id"], goal["id"]);
+
+    // Reading the goal again does not notify twice
+    common::send(&app, "GET", "/goals", Some(&token), None).await;
+    let (status, _) = common::send(
+        &app,
+        "POST",
+        &format!(
+            "/me/notifications/{}/read",
+            notifications[0]["id"].as_str().unwrap()
+        ),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, unread) = common::send(
+        &app,
+        "GET",
+        "/me/notifications?unread=true",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert!(unread
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|n| n["kind"] != "goal_completed"));
+
+    // Strength goals need an exercise
+    let (status, _) = common::send(
+        &app,
+        "POST",
+        "/goals",
+        Some(&token),
+        Some(json!({ "goal_type": "strength", "title": "Lift", "target_value": 100.0 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test]
+async fn test_bodyweight_frequency_and_custom_goals(pool: PgPool) {
+    let app = app(pool.clone()).await;
+    let token = common::register(&app, "goal_other@example.com").await;
+    let squat = common::exercise_id(&pool, "barbell-back-squat").await;
+
+    // Bodyweight goals need a logged weight to know which way they go
+    let (status, _) = common::send(
+        &app,
+        "POST",
+        "/goals",
+        Some(&token),
+        Some(json!({ "goal_type": "bodyweight", "title": "Cut", "target_value": 75.0 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (_, goals) = common::send(&app, "GET", "/goals", Some(&token), None).await;
+    assert!(goals.as_array().unwrap().is_empty());
+
+    common::send(
+        &app,
+        "POST",
+        "/measurements",
+        Some(&token),
+        Some(json!({ "measured_at": "2024-12-01T07:00:00Z", "units": "metric", "weight": 80.0 })),
+    )
+    .await;
+    let (_, goal) = common::send(
+        &app,
+        "POST",
+        "/goals",
+        Some(&token),
+        Some(json!({ "goal_type": "bodyweight", "title": "Cut", "target_value": 75.0 })),
+    )
+    .await;
+    assert_eq!(goal["start_value"], 80.0);
+    assert_eq!(goal["progress_percent"], 0.0);
+    let bodyweight_uri = format!("/goals/{}", goal["id"].as_str().unwrap());
+
+    common::send(
+        &app,
+        "POST",
+        "/measurements",
+        Some(&token),
+        Some(json!({ "measured_at": "2024-12-08T07:00:00Z", "units": "metric", "weight": 77.0 })),
+    )
+    .await;
+    let (_, goal) = common::send(&app, "GET", &bodyweight_uri, Some(&token), None).await;
+    assert_eq!(goal["current_value"], 77.0);
+    assert_eq!(goal["progress_percent"], 60.0);
+    assert_eq!(goal["status"], "active");
+
+    // Frequency goals count this week's finished workouts
+    let (_, goal) = common::send(
+        &app,
+        "POST",
+        "/goals",
+        Some(&token),
+        Some(json!({ "goal_type": "frequency", "title": "Train twice", "target_value": 2.0 })),
+    )
+    .await;
+    assert_eq!(goal["current_value"], 0.0);
+    let frequency_uri = format!("/goals/{}", goal["id"].as_str().unwrap());
+    let (_, workout) = common::send(
+        &app,
+        "POST",
+        "/workouts",
+        Some(&token),
+        Some(json!({
+            "exercises": [{ "exercise_id": squat, "sets": [{ "reps": 5, "weight_kg": 100.0 }] }]
+        })),
+    )
+    .await;
+    let (_, goal) = common::send(&app, "GET", &frequency_uri, Some(&token), None).await;
+    assert_eq!(goal["current_value"], 0.0);
+    common::send(
+        &app,
+        "POST",
+        &format!("/workouts/{}/finish", workout["id"].as_str().unwrap()),
+        Some(&token),
+        None,
+    )
+    .await;
+    let (_, goal) = common::send(&app, "GET", &frequency_uri, Some(&token), None).await;
+    assert_eq!(goal["current_value"], 1.0);
+    assert_eq!(goal["progress_percent"], 50.0);
+
+    // Custom goals are tracked by hand
+    let (_, goal) = common::send(
+        &app,
+        "POST",
+        "/goals",
+        Some(&token),
+        Some(json!({
+            "goal_type": "custom",
+            "title": "Pull-ups",
+            "unit": "reps",
+            "start_value": 5.0,
+            "target_value": 15.0
+        })),
+    )
+    .await;
+    let custom_uri = format!("/goals/{}", goal["id"].as_str().unwrap());
+    let (status, goal) = common::send(
+        &app,
+        "PATCH",
+        &custom_uri,
+        Some(&token),
+        Some(json!({ "current_value": 10.0 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(goal["progress_percent"], 50.0);
+    let (_, goal) = common::send(
+        &app,
+        "PATCH",
+        &custom_uri,
+        Some(&token),
+        Some(json!({ "current_value": 15.0 })),
+    )
+    .await;
+    assert_eq!(goal["status"], "completed");
+
+    let (status, _) = common::send(
+        &app,
+        "PATCH",
+        &bodyweight_uri,
+        Some(&token),
+        Some(json!({ "current_value": 70.0 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // A passed deadline marks an open goal as missed
+    let (_, goal) = common::send(
+        &app,
+        "PATCH",
+        &bodyweight_uri,
+        Some(&token),
+        Some(json!({ "deadline": "2024-01-01" })),
+    )
+    .await;
+    assert_eq!(goal["status"], "missed");
+    assert!(goal["days_remaining"].as_i64().unwrap() < 0);
+
+    let (status, _) = common::send(&app, "DELETE", &bodyweight_uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, goals) = common::send(&app, "GET", "/goals", Some(&token), None).await;
+    assert_eq!(goals.as_array().unwrap().len(), 2);
+}
@@ -0,0 +1,171 @@
+use axum::http::StatusCode;
+use beppo_fit_backend::app;
+use serde_json::json;
+use sqlx::PgPool;
+
+mod common;
+
+#[sqlx::test]
+async fn test_export_workouts_as_csv_and_json(pool: PgPool) {
+    let app = app(pool.clone()).await;
+    let token = common::register(&app, "history_export@example.com").await;
+    let squat = common::exercise_id(&pool, "barbell-back-squat").await;
+
+    for day in [1, 5, 10] {
+        let (status, _) = common::send(
+            &app,
+            "POST",
+            "/workouts",
+            Some(&token),
+            Some(json!({
+                "name": format!("Leg day {}", day),
+                "started_at": format!("2024-12-{:02}T17:00:00Z", day),
+                "exercises": [{
+                    "exercise_id": squat,
+                    "sets": [{ "reps": 5, "weight_kg": 100.0 }, { "reps": 5, "weight_kg": 102.5 }]
+                }]
+            })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    let (status, content_type, csv) = common::get_text(
+        &app,
+        "/workouts/export?format=csv&from=2024-12-02T00:00:00Z",
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(content_type.starts_with("text/csv"));
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 5);
+    assert!(lines[0].starts_with("workout_id,workout_name,started_at"));
+    assert!(lines[1].contains("Leg day 5"));
+    assert!(lines[4].contains("Leg day 10") && lines[4].contains("102.5"));
+
+    let (status, content_type, body) = common::get_text(
+        &app,
+        "/workouts/export?format=json&to=2024-12-10T00:00:00Z",
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "application/json");
+    let workouts: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(workouts.as_array().unwrap().len(), 2);
+    assert_eq!(workouts[0]["name"], "Leg day 1");
+    assert_eq!(workouts[1]["exercises"][0]["sets"][1]["weight_kg"], 102.5);
+
+    // An empty range still yields a valid document
+    let (_, _, body) = common::get_text(
+        &app,
+        "/workouts/export?format=json&from=2025-01-01T00:00:00Z",
+        Some(&token),
+    )
+    .await;
+    assert_eq!(body, "[]");
+
+    let (status, _, _) = common::get_text(&app, "/workouts/export?format=xml", Some(&token)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _, _) = common::get_text(
+        &app,
+        "/workouts/export?from=2024-12-10T00:00:00Z&to=2024-12-01T00:00:00Z",
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test]
+async fn test_export_spans_several_batches(pool: PgPool) {
+    let app = app(pool.clone()).await;
+    let token = common::register(&app, "history_export_batches@example.com").await;
+
+    // Pairs of workouts share a start time, so the cursor has to break ties by id
+    sqlx::query!(
+        r#"
+        INSERT INTO workouts (user_id, name, started_at)
+        SELECT u.id, 'Session ' || n, TIMESTAMPTZ '2024-01-01 08:00Z' + (n / 2) * INTERVAL '1 day'
+        FROM users u, generate_series(1, 250) n
+        WHERE u.email = 'history_export_batches@example.com'
+        "#
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let (status, _, body) =
+        common::get_text(&app, "/workouts/export?format=json", Some(&token)).await;
+    assert_eq!(status, StatusCode::OK);
+    let workouts: Vec<serde_json::Value> = serde_json::from_str(&body).unwrap();
+    assert_eq!(workouts.len(), 250);
+    let mut ids: Vec<&str> = workouts.iter().map(|w| w["id"].as_str().unwrap()).collect();
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), 250);
+}
+
+#[sqlx::test]
+async fn test_export_activity_as_gpx_and_tcx(pool: PgPool) {
+    let app = app(pool).await;
+    let token = common::register(&app, "activity_export@example.com").await;
+
+    let mut trkpts = String::new();
+    for i in 0..11 {
+        trkpts.push_str(&format!(
+            r#"<trkpt lat="{:.4}" lon="13.4"><ele>{}</ele><time>2024-12-01T07:00:{:02}Z</time></trkpt>"#,
+            52.5 + i as f64 * 0.0005,
+            30 + i,
+            i * 5
+        ));
+    }
+    let gpx = format!(
+        r#"<gpx version="1.1" xmlns="http://www.topografix.com/GPX/1/1"><trk><type>running</type><trkseg>{}</trkseg></trk></gpx>"#,
+        trkpts
+    );
+    let (status, activity) = common::send_multipart(
+        &app,
+        "/activities/import",
+        &token,
+        ("application/gpx+xml", gpx.as_bytes()),
+        &[("name", "Track & field")],
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let uri = format!("/activities/{}/export", activity["id"].as_str().unwrap());
+
+    let (status, content_type, exported) =
+        common::get_text(&app, &format!("{}?format=gpx", uri), Some(&token)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "application/gpx+xml");
+    assert_eq!(exported.matches("<trkpt ").count(), 11);
+    assert!(exported.contains("<name>Track &amp; field</name>"));
+
+    let (status, content_type, tcx) =
+        common::get_text(&app, &format!("{}?format=tcx", uri), Some(&token)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "application/vnd.garmin.tcx+xml");
+
+    // The exported file imports into another account with the same figures
+    let other = common::register(&app, "activity_export_other@example.com").await;
+    let (status, copy) = common::send_multipart(
+        &app,
+        "/activities/import",
+        &other,
+        ("application/vnd.garmin.tcx+xml", tcx.as_bytes()),
+        &[],
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(copy["activity_type"], "run");
+    assert_eq!(copy["name"], "Track & field");
+    assert_eq!(copy["started_at"], activity["started_at"]);
+    assert_eq!(copy["duration_seconds"], activity["duration_seconds"]);
+    assert_eq!(copy["distance_m"], activity["distance_m"]);
+
+    let (status, _, _) = common::get_text(&app, &format!("{}?format=fit", uri), Some(&token)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _, _) = common::get_text(&app, &format!("{}?format=gpx", uri), Some(&other)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,279 @@
+use axum::http::StatusCode;
+use beppo_fit_backend::app;
+use serde_json::json;
+use sqlx::PgPool;
+
+mod common;
+
+#[sqlx::test]
+async fn test_interval_definitions_and_sharing(pool: PgPool) {
+    let app = app(pool.clone()).await;
+    let token = common::register(&app, "intervals@example.com").await;
+    let burpee = common::exercise_id(&pool, "burpee").await;
+    let swing = common::exercise_id(&pool, "kettlebell-swing").await;
+
+    let tabata = json!({
+        "name": "Burpee Tabata",
+        "format": "tabata",
+        "blocks": [
+            {
+                "rounds": 8,
+                "rest_after_seconds": 60,
+                "steps": [
+                    { "kind": "work", "exercise_id": burpee, "duration_seconds": 20 },
+                    { "kind": "rest", "duration_seconds": 10 }
+                ]
+            },
+            {
+                "rounds": 8,
+                "steps": [
+                    { "kind": "work", "exercise_id": swing, "duration_seconds": 20 },
+                    { "kind": "rest", "duration_seconds": 10 }
+                ]
+            }
+        ]
+    });
+    let (status, created) = common::send(
+        &app,
+        "POST",
+        "/intervals",
+        Some(&token),
+        Some(tabata.clone()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(created["total_seconds"], 540);
+    assert_eq!(created["blocks"][0]["steps"][0]["exercise_name"], "Burpee");
+    assert!(created["blocks"][1]["steps"][1]["exercise_id"].is_null());
+    let uri = format!("/intervals/{}", created["id"].as_str().unwrap());
+
+    // Format rules and total duration are checked
+    let mut invalid = tabata.clone();
+    invalid["format"] = json!("emom");
+    let (status, body) =
+        common::send(&app, "POST", "/intervals", Some(&token), Some(invalid)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"].as_str().unwrap().contains("whole minutes"));
+    let (status, _) = common::send(
+        &app,
+        "POST",
+        "/intervals",
+        Some(&token),
+        Some(json!({
+            "name": "Forever",
+            "format": "custom",
+            "blocks": [{ "rounds": 100, "steps": [{ "kind": "work", "duration_seconds": 600 }] }]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = common::send(
+        &app,
+        "POST",
+        "/intervals",
+        Some(&token),
+        Some(json!({
+            "name": "Rest with reps",
+            "format": "custom",
+            "blocks": [{ "steps": [{ "kind": "rest", "duration_seconds": 30, "target_reps": 5 }] }]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, updated) = common::send(
+        &app,
+        "PUT",
+        &uri,
+        Some(&token),
+        Some(json!({
+            "name": "Cindy",
+            "format": "amrap",
+            "blocks": [{
+                "time_cap_seconds": 1200,
+                "steps": [
+                    { "kind": "work", "exercise_id": burpee, "target_reps": 5 },
+                    { "kind": "work", "exercise_id": swing, "target_reps": 10 }
+                ]
+            }]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(updated["total_seconds"], 1200);
+    assert_eq!(updated["blocks"].as_array().unwrap().len(), 1);
+
+    let other = common::register(&app, "intervals_other@example.com").await;
+    let (status, _) = common::send(&app, "GET", &uri, Some(&other), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, shared) =
+        common::send(&app, "POST", &format!("{}/share", uri), Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let code = shared["share_code"].as_str().unwrap().to_string();
+    let (status, preview) = common::send(
+        &app,
+        "GET",
+        &format!("/intervals/shared/{}", code),
+        Some(&other),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(preview["name"], "Cindy");
+    assert!(preview["share_code"].is_null());
+
+    let (status, copy) = common::send(
+        &app,
+        "POST",
+        &format!("/intervals/shared/{}/copy", code),
+        Some(&other),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_ne!(copy["id"], updated["id"]);
+    assert_eq!(copy["blocks"][0]["time_cap_seconds"], 1200);
+    assert_eq!(copy["blocks"][0]["steps"][1]["target_reps"], 10);
+    let (_, theirs) = common::send(&app, "GET", "/intervals", Some(&other), None).await;
+    assert_eq!(theirs.as_array().unwrap().len(), 1);
+
+    let (status, _) = common::send(
+        &app,
+        "DELETE",
+        &format!("{}/share", uri),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = common::send(
+        &app,
+        "GET",
+        &format!("/intervals/shared/{}", code),
+        Some(&other),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn test_log_interval_sessions(pool: PgPool) {
+    let app = app(pool).await;
+    let token = common::register(&app, "interval_sessions@example.com").await;
+
+    // Only AMRAP and custom blocks have time caps
+    let (status, _) = common::send(
+        &app,
+        "POST",
+        "/intervals",
+        Some(&token),
+        Some(json!({
+            "name": "EMOM 10",
+            "format": "emom",
+            "blocks": [
+                { "rounds": 10, "steps": [{ "kind": "work", "label": "5 pull-ups", "duration_seconds": 60 }] },
+                { "rounds": 1, "time_cap_seconds": 60, "steps": [{ "kind": "work" }] }
+            ]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, emom) = common::send(
+        &app,
+        "POST",
+        "/intervals",
+        Some(&token),
+        Some(json!({
+            "name": "EMOM 10",
+            "format": "emom",
+            "blocks": [
+                { "rounds": 10, "rest_after_seconds": 120, "steps": [{ "kind": "work", "label": "5 pull-ups", "duration_seconds": 60 }] },
+                { "rounds": 5, "steps": [{ "kind": "work", "duration_seconds": 30 }, { "kind": "rest", "duration_seconds": 90 }] }
+            ]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(emom["total_seconds"], 1320);
+    let uri = format!("/intervals/{}", emom["id"].as_str().unwrap());
+
+    let (status, _) = common::send(
+        &app,
+        "POST",
+        &format!("{}/sessions", uri),
+        Some(&token),
+        Some(json!({
+            "started_at": "2024-12-20T07:00:00Z",
+            "duration_seconds": 1320,
+            "blocks": [{ "block_position": 0, "rounds_completed": 11 }]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = common::send(
+        &app,
+        "POST",
+        &format!("{}/sessions", uri),
+        Some(&token),
+        Some(json!({
+            "started_at": "2024-12-20T07:00:00Z",
+            "duration_seconds": 1320,
+            "blocks": [{ "block_position": 2, "rounds_completed": 1 }]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, session) = common::send(
+        &app,
+        "POST",
+        &format!("{}/sessions", uri),
+        Some(&token),
+        Some(json!({
+            "started_at": "2024-12-20T07:00:00Z",
+            "duration_seconds": 1300,
+            "notes": "Dropped off in round 9",
+            "blocks": [
+                { "block_position": 0, "rounds_completed": 8, "reps": 3 },
+                { "block_position": 1, "rounds_completed": 5 }
+            ]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(session["name"], "EMOM 10");
+    assert_eq!(session["format"], "emom");
+    assert_eq!(session["blocks"][0]["rounds_completed"], 8);
+    assert_eq!(session["blocks"][0]["reps"], 3);
+    let session_uri = format!("/intervals/sessions/{}", session["id"].as_str().unwrap());
+
+    let (_, sessions) = common::send(
+        &app,
+        "GET",
+        &format!(
+            "/intervals/sessions?interval_workout_id={}",
+            emom["id"].as_str().unwrap()
+        ),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(sessions.as_array().unwrap().len(), 1);
+
+    // Sessions outlive their definition
+    let (status, _) = common::send(&app, "DELETE", &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, kept) = common::send(&app, "GET", &session_uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(kept["interval_workout_id"].is_null());
+    assert_eq!(kept["blocks"].as_array().unwrap().len(), 2);
+
+    let other = common::register(&app, "interval_sessions_other@example.com").await;
+    let (status, _) = common::send(&app, "DELETE", &session_uri, Some(&other), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = common::send(&app, "DELETE", &session_uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+}
@@ -0,0 +1,176 @@
+use axum::http::StatusCode;
+use beppo_fit_backend::app;
+use serde_json::json;
+use sqlx::PgPool;
+
+mod common;
+
+#[sqlx::test]
+async fn test_measurements(pool: PgPool) {
+    let app = app(pool).await;
+    let token = common::register(&app, "measurement_test@example.com").await;
+
+    common::send(
+        &app,
+        "PATCH",
+        "/me/profile",
+        Some(&token),
+        Some(json!({ "height_cm": 180.0, "preferred_units": "imperial" })),
+    )
+    .await;
+
+    // Values are read and written in the preferred units
+    let (status, created) = common::send(
+        &app,
+        "POST",
+        "/measurements",
+        Some(&token),
+        Some(json!({
+            "measured_at": "2024-12-01T07:00:00Z",
+            "weight": 176.37,
+            "body_fat_percent": 15.0,
+            "waist": 32.0
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(created["units"], "imperial");
+    assert_eq!(created["weight"], 176.37);
+    assert_eq!(created["waist"], 32.0);
+    assert!(created["chest"].is_null());
+    assert_eq!(created["bmi"], 24.7);
+    assert_eq!(created["ffmi"], 21.0);
+    let uri = format!("/measurements/{}", created["id"].as_str().unwrap());
+
+    let (_, metric) = common::send(
+        &app,
+        "GET",
+        &format!("{}?units=metric", uri),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(metric["weight"], 80.0);
+    assert_eq!(metric["waist"], 81.28);
+
+    for (measured_at, weight) in [
+        ("2024-12-03T07:00:00Z", 81.0),
+        ("2024-12-05T07:00:00Z", 82.0),
+    ] {
+        let (status, _) = common::send(
+            &app,
+            "POST",
+            "/measurements",
+            Some(&token),
+            Some(json!({ "measured_at": measured_at, "units": "metric", "weight": weight })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    let (status, series) = common::send(
+        &app,
+        "GET",
+        "/measurements/series?metric=weight&units=metric&window_days=3",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let averages: Vec<f64> = series
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["moving_average"].as_f64().unwrap())
+        .collect();
+    assert_eq!(averages, vec![80.0, 80.5, 81.5]);
+
+    // Points before the range still feed the moving average
+    let (_, series) = common::send(
+        &app,
+        "GET",
+        "/measurements/series?metric=weight&units=metric&window_days=7&from=2024-12-04T00:00:00Z",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(series.as_array().unwrap().len(), 1);
+    assert_eq!(series[0]["value"], 82.0);
+    assert_eq!(series[0]["moving_average"], 81.0);
+
+    let (_, bmi) = common::send(
+        &app,
+        "GET",
+        "/measurements/series?metric=bmi",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(bmi[0]["value"], 24.7);
+
+    let (status, updated) = common::send(
+        &app,
+        "PATCH",
+        &uri,
+        Some(&token),
+        Some(json!({ "waist": 31.0 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(updated["waist"], 31.0);
+    assert_eq!(updated["weight"], 176.37);
+
+    // Explicit nulls clear values, omitted ones stay
+    let (status, updated) = common::send(
+        &app,
+        "PATCH",
+        &uri,
+        Some(&token),
+        Some(json!({ "waist": null, "notes": null })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(updated["waist"].is_null());
+    assert!(updated["notes"].is_null());
+    assert_eq!(updated["weight"], 176.37);
+
+    let (_, list) = common::send(&app, "GET", "/measurements", Some(&token), None).await;
+    assert_eq!(list.as_array().unwrap().len(), 3);
+    assert_eq!(list[0]["measured_at"], "2024-12-05T07:00:00Z");
+
+    for body in [
+        json!({ "notes": "nothing measured" }),
+        json!({ "weight": 0.0 }),
+    ] {
+        let (status, _) =
+            common::send(&app, "POST", "/measurements", Some(&token), Some(body)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+    let (status, _) = common::send(
+        &app,
+        "GET",
+        "/measurements/series?metric=shoe_size",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let other = common::register(&app, "measurement_other@example.com").await;
+    let (status, _) = common::send(&app, "GET", &uri, Some(&other), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = common::send(
+        &app,
+        "GET",
+        "/measurements/series?metric=ffmi",
+        Some(&other),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = common::send(&app, "DELETE", &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = common::send(&app, "GET", &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,144 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use beppo_fit_backend::app;
+use image::{ImageFormat, RgbImage};
+use sqlx::PgPool;
+use std::io::Cursor;
+use tower::ServiceExt;
+
+mod common;
+
+fn test_image(width: u32, height: u32, format: ImageFormat) -> Vec<u8> {
+    let image = RgbImage::from_fn(width, height, |x, y| {
+        image::Rgb([(x % 256) as u8, (y % 256) as u8, 128])
+    });
+    let mut buffer = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut buffer), format)
+        .unwrap();
+    buffer
+}
+
+async fn get_status(app: &axum::Router, uri: &str) -> (StatusCode, Vec<u8>) {
+    let response = app
+        .clone()
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, bytes.to_vec())
+}
+
+#[sqlx::test]
+async fn test_progress_photo_upload_and_signed_download(pool: PgPool) {
+    let app = app(pool).await;
+    let token = common::register(&app, "media_test@example.com").await;
+
+    // Content that does not match the declared type is rejected
+    let png = test_image(10, 10, ImageFormat::Png);
+    let (status, _) = common::send_multipart(
+        &app,
+        "/media/progress-photos",
+        &token,
+        ("image/jpeg", &png),
+        &[],
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Unsupported types are rejected
+    let (status, _) = common::send_multipart(
+        &app,
+        "/media/progress-photos",
+        &token,
+        ("application/pdf", b"%PDF-1.4"),
+        &[],
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Large images are downscaled
+    let jpeg = test_image(3000, 1000, ImageFormat::Jpeg);
+    let (status, body) = common::send_multipart(
+        &app,
+        "/media/progress-photos",
+        &token,
+        ("image/jpeg", &jpeg),
+        &[("taken_at", "2024-12-01T08:00:00Z"), ("notes", "Week 1")],
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["width"], 2048);
+    assert_eq!(body["notes"], "Week 1");
+
+    // The signed URLs serve the image without authentication
+    let url = body["url"].as_str().unwrap();
+    let (status, bytes) = get_status(&app, url).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(image::guess_format(&bytes).unwrap(), ImageFormat::Jpeg);
+
+    let (status, bytes) = get_status(&app, body["thumbnail_url"].as_str().unwrap()).await;
+    assert_eq!(status, StatusCode::OK);
+    let thumbnail = image::load_from_memory(&bytes).unwrap();
+    assert!(thumbnail.width() <= 256 && thumbnail.height() <= 256);
+
+    // Tampered signatures are rejected
+    let tampered = url.replace("/original?", "/thumbnail?");
+    let (status, _) = get_status(&app, &tampered).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, list) =
+        common::send(&app, "GET", "/media/progress-photos", Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(list.as_array().unwrap().len(), 1);
+
+    let id = body["id"].as_str().unwrap();
+    let (status, _) = common::send(
+        &app,
+        "DELETE",
+        &format!("/media/{}", id),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = get_status(&app, url).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn test_avatar_upload_replaces_previous(pool: PgPool) {
+    let app = app(pool.clone()).await;
+    let token = common::register(&app, "avatar_test@example.com").await;
+
+    let png = test_image(64, 64, ImageFormat::Png);
+    let (status, first) =
+        common::send_multipart(&app, "/me/avatar", &token, ("image/png", &png), &[]).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, second) =
+        common::send_multipart(&app, "/me/avatar", &token, ("image/png", &png), &[]).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_ne!(first["id"], second["id"]);
+
+    let (_, profile) = common::send(&app, "GET", "/me/profile", Some(&token), None).await;
+    assert_eq!(profile["avatar_media_id"], second["id"]);
+
+    let count = sqlx::query_scalar!("SELECT COUNT(*) FROM media WHERE kind = 'avatar'")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(count, Some(1));
+
+    let (status, _) = common::send(&app, "DELETE", "/me/avatar", Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = common::send(&app, "GET", "/me/avatar", Some(&token), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
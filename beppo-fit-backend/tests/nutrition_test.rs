@@ -0,0 +1,487 @@
+use axum::http::StatusCode;
+use beppo_fit_backend::{
+    app,
+    models::nutrition::SOURCE_OPEN_FOOD_FACTS,
+    services::food_import::{import_foods, read_open_food_facts},
+};
+use serde_json::json;
+use sqlx::PgPool;
+
+mod common;
+
+const NUTELLA: &str = "3017620422003";
+
+async fn import_dump(pool: &PgPool, dump: &str) -> usize {
+    let rows = read_open_food_facts(dump.as_bytes()).unwrap();
+    import_foods(pool, SOURCE_OPEN_FOOD_FACTS, rows)
+        .await
+        .unwrap()
+        .imported
+}
+
+#[sqlx::test]
+async fn test_food_catalog_import_and_custom_foods(pool: PgPool) {
+    let header = "code\tproduct_name\tbrands\tserving_quantity\tenergy-kcal_100g\tproteins_100g\tcarbohydrates_100g\tfat_100g\tsodium_100g\n";
+    let dump = format!(
+        "{}{}\tNutella\tFerrero\t15\t539\t6.3\t57.5\t30.9\t0.0428\n4000000000001\tRye bread\t\t\t220\t6\t40\t1.5\t\n",
+        header, NUTELLA
+    );
+    assert_eq!(import_dump(&pool, &dump).await, 2);
+    // Re-importing a newer dump updates the entry instead of duplicating it
+    let newer = format!(
+        "{}{}\tNutella\tFerrero\t15\t540\t6.3\t57.5\t30.9\t\n",
+        header, NUTELLA
+    );
+    assert_eq!(import_dump(&pool, &newer).await, 1);
+
+    let app = app(pool.clone()).await;
+    let token = common::register(&app, "foods@example.com").await;
+
+    let (status, found) = common::send(
+        &app,
+        "GET",
+        &format!("/foods?barcode={}", NUTELLA),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let found = found.as_array().unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0]["energy_kcal"], 540.0);
+    assert!(found[0]["sodium_mg"].is_null());
+    assert_eq!(found[0]["source"], "off");
+
+    let (status, _) = common::send(
+        &app,
+        "POST",
+        "/foods",
+        Some(&token),
+        Some(json!({ "name": "Impossible", "energy_kcal": 400, "protein_g": 60, "fat_g": 50 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = common::send(
+        &app,
+        "POST",
+        "/foods",
+        Some(&token),
+        Some(json!({ "name": "Bad barcode", "energy_kcal": 100, "barcode": "12ab" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, granola) = common::send(
+        &app,
+        "POST",
+        "/foods",
+        Some(&token),
+        Some(json!({
+            "name": "Homemade granola",
+            "serving_size_g": 50,
+            "serving_name": "bowl",
+            "energy_kcal": 450,
+            "protein_g": 10,
+            "carbohydrate_g": 60,
+            "fat_g": 18
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(granola["source"], "user");
+    let uri = format!("/foods/{}", granola["id"].as_str().unwrap());
+
+    // Partial updates are checked against the stored macros
+    let (status, _) = common::send(
+        &app,
+        "PATCH",
+        &uri,
+        Some(&token),
+        Some(json!({ "fat_g": 40 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, updated) = common::send(
+        &app,
+        "PATCH",
+        &uri,
+        Some(&token),
+        Some(json!({ "fat_g": 20 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(updated["fat_g"], 20.0);
+    assert_eq!(updated["protein_g"], 10.0);
+
+    let (_, search) = common::send(&app, "GET", "/foods?q=granola", Some(&token), None).await;
+    assert_eq!(search.as_array().unwrap().len(), 1);
+
+    let other = common::register(&app, "foods_other@example.com").await;
+    let (status, _) = common::send(&app, "GET", &uri, Some(&other), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (_, search) = common::send(&app, "GET", "/foods?q=granola", Some(&other), None).await;
+    assert!(search.as_array().unwrap().is_empty());
+    let (status, _) = common::send(&app, "DELETE", &uri, Some(&other), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, archived) = common::send(&app, "DELETE", &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(archived["archived_at"].is_string());
+    let (_, search) = common::send(&app, "GET", "/foods?q=granola", Some(&token), None).await;
+    assert!(search.as_array().unwrap().is_empty());
+    let (_, search) = common::send(
+        &app,
+        "GET",
+        "/foods?q=granola&include_archived=true",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(search.as_array().unwrap().len(), 1);
+}
+
+#[sqlx::test]
+async fn test_meals_and_daily_totals(pool: PgPool) {
+    let dump = format!(
+        "code\tproduct_name\tenergy-kcal_100g\tproteins_100g\tcarbohydrates_100g\tfat_100g\n{}\tNutella\t540\t6.3\t57.5\t30.9\n",
+        NUTELLA
+    );
+    import_dump(&pool, &dump).await;
+
+    let app = app(pool.clone()).await;
+    let token = common::register(&app, "meals@example.com").await;
+    let (status, _) = common::send(
+        &app,
+        "PATCH",
+        "/me/profile",
+        Some(&token),
+        Some(json!({ "timezone": "Europe/Berlin" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (_, found) = common::send(
+        &app,
+        "GET",
+        &format!("/foods?barcode={}", NUTELLA),
+        Some(&token),
+        None,
+    )
+    .await;
+    let nutella = found[0]["id"].as_str().unwrap().to_string();
+    let (_, granola) = common::send(
+        &app,
+        "POST",
+        "/foods",
+        Some(&token),
+        Some(json!({
+            "name": "Homemade granola",
+            "serving_size_g": 50,
+            "energy_kcal": 450,
+            "protein_g": 10,
+            "carbohydrate_g": 60,
+            "fat_g": 18
+        })),
+    )
+    .await;
+    let granola = granola["id"].as_str().unwrap().to_string();
+
+    // Servings need a serving size, and portions are grams or servings
+    let (status, body) = common::send(
+        &app,
+        "POST",
+        "/meals",
+        Some(&token),
+        Some(json!({
+            "meal_type": "breakfast",
+            "eaten_at": "2024-12-20T07:00:00Z",
+            "items": [{ "food_id": nutella, "servings": 1 }]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"].as_str().unwrap().contains("serving size"));
+    let (status, _) = common::send(
+        &app,
+        "POST",
+        "/meals",
+        Some(&token),
+        Some(json!({
+            "meal_type": "breakfast",
+            "eaten_at": "2024-12-20T07:00:00Z",
+            "items": [{ "food_id": nutella, "grams": 20, "servings": 1 }]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, breakfast) = common::send(
+        &app,
+        "POST",
+        "/meals",
+        Some(&token),
+        Some(json!({
+            "meal_type": "breakfast",
+            "eaten_at": "2024-12-20T07:00:00Z",
+            "items": [
+                { "food_id": granola, "servings": 1 },
+                { "food_id": nutella, "grams": 20 }
+            ]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(breakfast["items"][0]["grams"], 50.0);
+    assert_eq!(breakfast["items"][0]["nutrients"]["energy_kcal"], 225.0);
+    assert_eq!(breakfast["items"][1]["food_name"], "Nutella");
+    assert_eq!(breakfast["items"][1]["nutrients"]["fat_g"], 6.2);
+    assert_eq!(breakfast["totals"]["energy_kcal"], 333.0);
+    assert_eq!(breakfast["totals"]["protein_g"], 6.3);
+    assert!(breakfast["totals"]["sodium_mg"].is_null());
+    let breakfast_uri = format!("/meals/{}", breakfast["id"].as_str().unwrap());
+
+    // 23:30 UTC is already the next day in Berlin
+    let (status, dinner) = common::send(
+        &app,
+        "POST",
+        "/meals",
+        Some(&token),
+        Some(json!({
+            "meal_type": "dinner",
+            "eaten_at": "2024-12-20T23:30:00Z",
+            "items": [{ "food_id": nutella, "grams": 100 }]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, days) = common::send(
+        &app,
+        "GET",
+        "/nutrition/daily?from=2024-12-20&to=2024-12-21",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let days = days.as_array().unwrap();
+    assert_eq!(days.len(), 2);
+    assert_eq!(days[0]["date"], "2024-12-20");
+    assert_eq!(days[0]["meals"], 1);
+    assert_eq!(days[0]["energy_kcal"], 333.0);
+    assert_eq!(days[0]["carbohydrate_g"], 41.5);
+    assert_eq!(days[1]["date"], "2024-12-21");
+    assert_eq!(days[1]["energy_kcal"], 540.0);
+
+    let (_, meals) = common::send(
+        &app,
+        "GET",
+        "/meals?from=2024-12-21&to=2024-12-21",
+        Some(&token),
+        None,
+    )
+    .await;
+    let meals = meals.as_array().unwrap();
+    assert_eq!(meals.len(), 1);
+    assert_eq!(meals[0]["id"], dinner["id"]);
+
+    // Re-imported catalog values do not change what was already logged
+    sqlx::query!(
+        "UPDATE foods SET energy_kcal = 600 WHERE id = $1",
+        uuid::Uuid::parse_str(&nutella).unwrap()
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    let (_, dinner) = common::send(
+        &app,
+        "GET",
+        &format!("/meals/{}", dinner["id"].as_str().unwrap()),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(dinner["totals"]["energy_kcal"], 540.0);
+    let (_, days) = common::send(
+        &app,
+        "GET",
+        "/nutrition/daily?from=2024-12-21&to=2024-12-21",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(days[0]["energy_kcal"], 540.0);
+
+    // Archived foods stay part of the meals that contain them
+    common::send(
+        &app,
+        "DELETE",
+        &format!("/foods/{}", granola),
+        Some(&token),
+        None,
+    )
+    .await;
+    let (status, kept) = common::send(&app, "GET", &breakfast_uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(kept["totals"]["energy_kcal"], 333.0);
+
+    let (status, updated) = common::send(
+        &app,
+        "PUT",
+        &breakfast_uri,
+        Some(&token),
+        Some(json!({
+            "meal_type": "breakfast",
+            "eaten_at": "2024-12-20T07:00:00Z",
+            "notes": "Smaller portion",
+            "items": [{ "food_id": granola, "servings": 0.5 }]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(updated["items"].as_array().unwrap().len(), 1);
+    assert_eq!(updated["totals"]["energy_kcal"], 112.5);
+
+    // Other users cannot see the meal or eat the custom food
+    let other = common::register(&app, "meals_other@example.com").await;
+    let (status, _) = common::send(&app, "GET", &breakfast_uri, Some(&other), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = common::send(
+        &app,
+        "POST",
+        "/meals",
+        Some(&other),
+        Some(json!({
+            "meal_type": "snack",
+            "eaten_at": "2024-12-20T15:00:00Z",
+            "items": [{ "food_id": granola, "grams": 30 }]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = common::send(&app, "DELETE", &breakfast_uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, days) = common::send(
+        &app,
+        "GET",
+        "/nutrition/daily?from=2024-12-20&to=2024-12-21",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(days.as_array().unwrap().len(), 1);
+}
+
+#[sqlx::test]
+async fn test_nutrition_targets(pool: PgPool) {
+    let app = app(pool).await;
+    let token = common::register(&app, "targets@example.com").await;
+
+    let (status, body) = common::send(&app, "GET", "/nutrition/targets", Some(&token), None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"].as_str().unwrap().contains("bodyweight"));
+
+    let now = chrono::Utc::now();
+    for (days_ago, weight) in [(22, 80.0), (15, 79.5), (8, 79.0), (1, 78.5)] {
+        let (status, _) = common::send(
+            &app,
+            "POST",
+            "/measurements",
+            Some(&token),
+            Some(json!({
+                "measured_at": now - chrono::Duration::days(days_ago),
+                "weight": weight
+            })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    // Mifflin-St Jeor needs the profile
+    let (status, _) = common::send(&app, "GET", "/nutrition/targets", Some(&token), None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, profile) = common::send(
+        &app,
+        "PATCH",
+        "/me/profile",
+        Some(&token),
+        Some(json!({
+            "sex": "female",
+            "date_of_birth": "1990-01-01",
+            "height_cm": 170,
+            "nutrition_goal": "cut"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(profile["nutrition_goal"], "cut");
+
+    let (status, targets) =
+        common::send(&app, "GET", "/nutrition/targets", Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(targets["goal"], "cut");
+    assert_eq!(targets["bmr_method"], "mifflin_st_jeor");
+    assert_eq!(targets["weight_kg"], 78.5);
+    assert_eq!(targets["activity_level"], "sedentary");
+    assert!(targets["adaptive"].is_null());
+    assert_eq!(targets["tdee_kcal"], targets["formula_tdee_kcal"]);
+    let formula_tdee = targets["formula_tdee_kcal"].as_f64().unwrap();
+    assert!(targets["calories_kcal"].as_f64().unwrap() < formula_tdee);
+
+    let (status, _) = common::send(
+        &app,
+        "GET",
+        "/nutrition/targets?goal=shred",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Two weeks of 2000 kcal days while losing half a kilo a week
+    let (_, food) = common::send(
+        &app,
+        "POST",
+        "/foods",
+        Some(&token),
+        Some(json!({ "name": "Meal prep", "energy_kcal": 400, "protein_g": 25 })),
+    )
+    .await;
+    for days_ago in 2..16 {
+        let (status, _) = common::send(
+            &app,
+            "POST",
+            "/meals",
+            Some(&token),
+            Some(json!({
+                "meal_type": "lunch",
+                "eaten_at": now - chrono::Duration::days(days_ago),
+                "items": [{ "food_id": food["id"], "grams": 500 }]
+            })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    let (status, targets) = common::send(
+        &app,
+        "GET",
+        "/nutrition/targets?goal=maintain",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(targets["goal"], "maintain");
+    let adaptive = &targets["adaptive"];
+    assert_eq!(adaptive["days_logged"], 14);
+    assert_eq!(adaptive["average_intake_kcal"], 2000.0);
+    assert_eq!(adaptive["weight_change_kg_per_week"], -0.5);
+    assert_eq!(adaptive["observed_tdee_kcal"], 2550.0);
+    let adjustment = (2550.0 - formula_tdee).clamp(-500.0, 500.0);
+    assert_eq!(adaptive["adjustment_kcal"], adjustment);
+    assert_eq!(targets["tdee_kcal"], formula_tdee + adjustment);
+    assert_eq!(targets["calories_kcal"], formula_tdee + adjustment);
+    assert_eq!(targets["protein_g"], (78.5f64 * 1.8).round());
+}
@@ -0,0 +1,119 @@
+use axum::http::StatusCode;
+use beppo_fit_backend::app;
+use serde_json::json;
+use sqlx::PgPool;
+
+mod common;
+
+#[sqlx::test]
+async fn test_register_returns_default_profile(pool: PgPool) {
+    let app = app(pool).await;
+
+    let (status, body) = common::send(
+        &app,
+        "POST",
+        "/auth/register",
+        None,
+        Some(json!({ "email": "profile_default@example.com", "password": "Password123!" })),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["profile"]["preferred_units"], "metric");
+    assert_eq!(body["profile"]["timezone"], "UTC");
+    assert_eq!(body["profile"]["is_private"], false);
+}
+
+#[sqlx::test]
+async fn test_update_profile(pool: PgPool) {
+    let app = app(pool).await;
+    let token = common::register(&app, "profile_update@example.com").await;
+
+    // Invalid values are rejected
+    let (status, _) = common::send(
+        &app,
+        "PATCH",
+        "/me/profile",
+        Some(&token),
+        Some(json!({ "timezone": "Mars/Olympus" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = common::send(
+        &app,
+        "PATCH",
+        "/me/profile",
+        Some(&token),
+        Some(json!({ "height_cm": 20.0 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Valid partial update
+    let (status, body) = common::send(
+        &app,
+        "PATCH",
+        "/me/profile",
+        Some(&token),
+        Some(json!({
+            "display_name": "Beppo",
+            "date_of_birth": "1990-05-17",
+            "sex": "male",
+            "height_cm": 182.5,
+            "preferred_units": "imperial",
+            "timezone": "Europe/Zurich",
+            "locale": "de-CH"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["display_name"], "Beppo");
+    assert_eq!(body["preferred_units"], "imperial");
+
+    // Omitted fields keep their values
+    let (status, _) = common::send(
+        &app,
+        "PATCH",
+        "/me/profile",
+        Some(&token),
+        Some(json!({ "is_private": true })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = common::send(&app, "GET", "/me/profile", Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["display_name"], "Beppo");
+    assert_eq!(body["date_of_birth"], "1990-05-17");
+    assert_eq!(body["timezone"], "Europe/Zurich");
+    assert_eq!(body["is_private"], true);
+
+    // Explicit nulls clear optional fields, omitted ones stay
+    let (status, body) = common::send(
+        &app,
+        "PATCH",
+        "/me/profile",
+        Some(&token),
+        Some(json!({ "date_of_birth": null, "height_cm": null })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["date_of_birth"].is_null());
+    assert!(body["height_cm"].is_null());
+    assert_eq!(body["display_name"], "Beppo");
+    assert_eq!(body["sex"], "male");
+    let (status, _) = common::send(
+        &app,
+        "PATCH",
+        "/me/profile",
+        Some(&token),
+        Some(json!({ "display_name": "" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Unauthenticated access is rejected
+    let (status, _) = common::send(&app, "GET", "/me/profile", None, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
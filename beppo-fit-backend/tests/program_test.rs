@@ -0,0 +1,320 @@
+use axum::http::StatusCode;
+use beppo_fit_backend::app;
+use serde_json::json;
+use sqlx::PgPool;
+
+mod common;
+
+#[sqlx::test]
+async fn test_program_enrollment_flow(pool: PgPool) {
+    let app = app(pool.clone()).await;
+    let token = common::register(&app, "program_test@example.com").await;
+    let squat = common::exercise_id(&pool, "barbell-back-squat").await;
+    let bench = common::exercise_id(&pool, "barbell-bench-press").await;
+
+    let (_, template) = common::send(
+        &app,
+        "POST",
+        "/templates",
+        Some(&token),
+        Some(json!({
+            "name": "Squat day",
+            "exercises": [
+                { "exercise_id": squat, "target_sets": 3 },
+                { "exercise_id": bench, "target_sets": 4, "target_reps_min": 10, "target_weight_kg": 50.0 }
+            ]
+        })),
+    )
+    .await;
+    let template_id = template["id"].as_str().unwrap();
+
+    // Prescriptions must target exercises of the day's template
+    let deadlift = common::exercise_id(&pool, "barbell-deadlift").await;
+    let (status, _) = common::send(
+        &app,
+        "POST",
+        "/programs",
+        Some(&token),
+        Some(json!({
+            "name": "Broken",
+            "weeks": [{ "days": [{
+                "template_id": template_id,
+                "prescriptions": [{ "exercise_id": deadlift, "percent_of_tm": 70.0, "reps": 5 }]
+            }] }]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, program) = common::send(
+        &app,
+        "POST",
+        "/programs",
+        Some(&token),
+        Some(json!({
+            "name": "5/3/1 squat",
+            "weeks": [
+                { "days": [{
+                    "template_id": template_id,
+                    "prescriptions": [
+                        { "exercise_id": squat, "percent_of_tm": 65.0, "reps": 5 },
+                        { "exercise_id": squat, "percent_of_tm": 75.0, "reps": 5 },
+                        { "exercise_id": squat, "percent_of_tm": 85.0, "reps": 5, "is_amrap": true }
+                    ]
+                }] },
+                { "is_deload": true, "days": [{
+                    "template_id": template_id,
+                    "name": "Deload",
+                    "prescriptions": [
+                        { "exercise_id": squat, "percent_of_tm": 40.0, "reps": 5 },
+                        { "exercise_id": squat, "percent_of_tm": 50.0, "reps": 5 }
+                    ]
+                }] }
+            ]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(program["weeks"].as_array().unwrap().len(), 2);
+    assert_eq!(program["weeks"][1]["is_deload"], true);
+    assert_eq!(program["weeks"][0]["days"][0]["template_name"], "Squat day");
+    let program_uri = format!("/programs/{}", program["id"].as_str().unwrap());
+
+    let (status, _) = common::send(
+        &app,
+        "PUT",
+        &format!("/me/training-maxes/{}", squat),
+        Some(&token),
+        Some(json!({ "weight_kg": 117.0 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = common::send(&app, "GET", "/me/enrollment", Some(&token), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let enroll_uri = format!("{}/enroll", program_uri);
+    let (status, enrollment) = common::send(&app, "POST", &enroll_uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(enrollment["current_week"], 1);
+    let (status, _) = common::send(&app, "POST", &enroll_uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, next) = common::send(&app, "GET", "/me/enrollment/next", Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(next["week_number"], 1);
+    assert_eq!(next["is_deload"], false);
+    let squat_sets = next["exercises"][0]["sets"].as_array().unwrap();
+    // 117 kg training max, rounded to 2.5 kg
+    assert_eq!(squat_sets[0]["weight_kg"], 75.0);
+    assert_eq!(squat_sets[1]["weight_kg"], 87.5);
+    assert_eq!(squat_sets[2]["weight_kg"], 100.0);
+    assert_eq!(squat_sets[2]["is_amrap"], true);
+    assert_eq!(next["exercises"][1]["sets"].as_array().unwrap().len(), 4);
+    assert_eq!(next["exercises"][1]["sets"][0]["weight_kg"], 50.0);
+
+    let (status, workout) = common::send(
+        &app,
+        "POST",
+        "/me/enrollment/next/start",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(workout["exercises"][0]["sets"][2]["weight_kg"], 100.0);
+    assert_eq!(workout["exercises"][0]["sets"][2]["notes"], "AMRAP");
+    let (status, _) = common::send(
+        &app,
+        "POST",
+        "/me/enrollment/next/start",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // Templates used by a program cannot be deleted
+    let (status, _) = common::send(
+        &app,
+        "DELETE",
+        &format!("/templates/{}", template_id),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // Finishing the workout advances to the deload week
+    let finish_uri = format!("/workouts/{}/finish", workout["id"].as_str().unwrap());
+    let (status, _) = common::send(&app, "POST", &finish_uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (_, next) = common::send(&app, "GET", "/me/enrollment/next", Some(&token), None).await;
+    assert_eq!(next["week_number"], 2);
+    assert_eq!(next["is_deload"], true);
+    assert_eq!(next["workout_name"], "Deload");
+    assert_eq!(next["exercises"][0]["sets"][0]["weight_kg"], 47.5);
+    // Bench repeats the last performance, lightened and with half the sets
+    let bench_sets = next["exercises"][1]["sets"].as_array().unwrap();
+    assert_eq!(bench_sets.len(), 2);
+    assert_eq!(bench_sets[0]["weight_kg"], 30.0);
+
+    let (_, workout) = common::send(
+        &app,
+        "POST",
+        "/me/enrollment/next/start",
+        Some(&token),
+        None,
+    )
+    .await;
+    let finish_uri = format!("/workouts/{}/finish", workout["id"].as_str().unwrap());
+    common::send(&app, "POST", &finish_uri, Some(&token), None).await;
+
+    // The last day completes the enrollment
+    let (status, _) = common::send(&app, "GET", "/me/enrollment", Some(&token), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = common::send(&app, "POST", &enroll_uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // Missing training maxes are reported instead of guessed
+    common::send(
+        &app,
+        "DELETE",
+        &format!("/me/training-maxes/{}", squat),
+        Some(&token),
+        None,
+    )
+    .await;
+    let (_, next) = common::send(&app, "GET", "/me/enrollment/next", Some(&token), None).await;
+    assert_eq!(next["missing_training_maxes"][0], squat.as_str());
+    assert!(next["exercises"][0]["sets"][0]["weight_kg"].is_null());
+
+    let (status, _) = common::send(&app, "DELETE", "/me/enrollment", Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let other = common::register(&app, "program_other@example.com").await;
+    let (status, _) = common::send(&app, "GET", &program_uri, Some(&other), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = common::send(&app, "POST", &enroll_uri, Some(&other), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn test_logging_finished_program_workout(pool: PgPool) {
+    let app = app(pool.clone()).await;
+    let token = common::register(&app, "program_logged@example.com").await;
+    let squat = common::exercise_id(&pool, "barbell-back-squat").await;
+
+    let (_, template) = common::send(
+        &app,
+        "POST",
+        "/templates",
+        Some(&token),
+        Some(json!({
+            "name": "Squat day",
+            "exercises": [{
+                "exercise_id": squat, "target_sets": 2, "target_reps_min": 5, "target_weight_kg": 100.0,
+                "progression_rule": "linear", "progression_increment_kg": 5.0
+            }]
+        })),
+    )
+    .await;
+    let template_id = template["id"].as_str().unwrap();
+    let (_, program) = common::send(
+        &app,
+        "POST",
+        "/programs",
+        Some(&token),
+        Some(json!({
+            "name": "Squat twice",
+            "weeks": [
+                { "days": [{ "template_id": template_id }] },
+                { "days": [{ "template_id": template_id }] }
+            ]
+        })),
+    )
+    .await;
+    let (status, _) = common::send(
+        &app,
+        "POST",
+        &format!("/programs/{}/enroll", program["id"].as_str().unwrap()),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // A session logged after the fact counts like a started one
+    let (status, workout) = common::send(
+        &app,
+        "POST",
+        "/workouts",
+        Some(&token),
+        Some(json!({
+            "template_id": template_id,
+            "started_at": "2024-12-10T17:00:00Z",
+            "finished_at": "2024-12-10T18:00:00Z",
+            "exercises": [{
+                "exercise_id": squat,
+                "sets": [{ "reps": 5, "weight_kg": 100.0 }, { "reps": 5, "weight_kg": 100.0 }]
+            }]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(workout["template_id"], template_id);
+
+    let (_, enrollment) = common::send(&app, "GET", "/me/enrollment", Some(&token), None).await;
+    assert_eq!(enrollment["current_week"], 2);
+    assert!(enrollment["pending_workout_id"].is_null());
+
+    let (status, recommendations) = common::send(
+        &app,
+        "GET",
+        &format!(
+            "/workouts/{}/recommendations",
+            workout["id"].as_str().unwrap()
+        ),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(recommendations.as_array().unwrap().len(), 1);
+    assert_eq!(recommendations[0]["target_weight_kg"], 105.0);
+
+    // Replacing the workout keeps the template unless it is explicitly unlinked
+    let workout_uri = format!("/workouts/{}", workout["id"].as_str().unwrap());
+    let replacement = json!({
+        "started_at": "2024-12-10T17:00:00Z",
+        "finished_at": "2024-12-10T18:00:00Z",
+        "exercises": [{ "exercise_id": squat, "sets": [{ "reps": 5, "weight_kg": 100.0 }] }]
+    });
+    let (_, updated) = common::send(
+        &app,
+        "PUT",
+        &workout_uri,
+        Some(&token),
+        Some(replacement.clone()),
+    )
+    .await;
+    assert_eq!(updated["template_id"], template_id);
+    let mut unlinked = replacement;
+    unlinked["template_id"] = json!(null);
+    let (status, updated) =
+        common::send(&app, "PUT", &workout_uri, Some(&token), Some(unlinked)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(updated["template_id"].is_null());
+
+    // Other users' templates cannot be referenced
+    let other = common::register(&app, "program_logged_other@example.com").await;
+    let (status, _) = common::send(
+        &app,
+        "POST",
+        "/workouts",
+        Some(&other),
+        Some(json!({ "template_id": template_id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,185 @@
+use axum::http::StatusCode;
+use beppo_fit_backend::app;
+use serde_json::json;
+use sqlx::PgPool;
+
+mod common;
+
+#[sqlx::test]
+async fn test_schedule_calendar_and_adherence(pool: PgPool) {
+    let app = app(pool.clone()).await;
+    let token = common::register(&app, "schedule_test@example.com").await;
+    let squat = common::exercise_id(&pool, "barbell-back-squat").await;
+
+    let (_, template) = common::send(
+        &app,
+        "POST",
+        "/templates",
+        Some(&token),
+        Some(json!({ "name": "Legs", "exercises": [{ "exercise_id": squat }] })),
+    )
+    .await;
+
+    // Mondays and Thursdays; the title comes from the template
+    let (status, schedule) = common::send(
+        &app,
+        "POST",
+        "/schedules",
+        Some(&token),
+        Some(json!({
+            "template_id": template["id"],
+            "start_date": "2024-12-02",
+            "start_time": "18:30:00",
+            "rrule": "freq=weekly;byday=th,mo"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(schedule["title"], "Legs");
+    assert_eq!(schedule["rrule"], "FREQ=WEEKLY;BYDAY=MO,TH");
+    let sessions = format!("/schedules/{}/sessions", schedule["id"].as_str().unwrap());
+
+    // A one-off session in the same range
+    let (status, _) = common::send(
+        &app,
+        "POST",
+        "/schedules",
+        Some(&token),
+        Some(json!({ "title": "Mobility", "start_date": "2024-12-07" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    for payload in [
+        json!({ "title": "Broken", "start_date": "2024-12-02", "rrule": "FREQ=YEARLY" }),
+        json!({ "start_date": "2024-12-02" }),
+    ] {
+        let (status, _) =
+            common::send(&app, "POST", "/schedules", Some(&token), Some(payload)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    let (_, workout) = common::send(
+        &app,
+        "POST",
+        "/workouts",
+        Some(&token),
+        Some(json!({
+            "started_at": "2024-12-02T17:30:00Z",
+            "finished_at": "2024-12-02T18:30:00Z",
+            "exercises": [{ "exercise_id": squat, "sets": [{ "reps": 5, "weight_kg": 100.0 }] }]
+        })),
+    )
+    .await;
+    let (status, session) = common::send(
+        &app,
+        "POST",
+        &format!("{}/2024-12-02/complete", sessions),
+        Some(&token),
+        Some(json!({ "workout_id": workout["id"] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(session["status"], "done");
+    let (status, _) = common::send(
+        &app,
+        "POST",
+        &format!("{}/2024-12-05/skip", sessions),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Tuesday is not part of the schedule
+    let (status, _) = common::send(
+        &app,
+        "POST",
+        &format!("{}/2024-12-03/skip", sessions),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, calendar) = common::send(
+        &app,
+        "GET",
+        "/calendar?from=2024-12-01&to=2024-12-15",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let entries: Vec<(&str, &str)> = calendar
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| (e["date"].as_str().unwrap(), e["status"].as_str().unwrap()))
+        .collect();
+    assert_eq!(
+        entries,
+        [
+            ("2024-12-02", "done"),
+            ("2024-12-05", "skipped"),
+            ("2024-12-07", "missed"),
+            ("2024-12-09", "missed"),
+            ("2024-12-12", "missed"),
+        ]
+    );
+    assert_eq!(calendar[0]["workout_id"], workout["id"]);
+    assert_eq!(calendar[0]["time"], "18:30:00");
+
+    let (_, adherence) = common::send(
+        &app,
+        "GET",
+        "/calendar/adherence?from=2024-12-01&to=2024-12-15",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(adherence["planned"], 5);
+    assert_eq!(adherence["done"], 1);
+    assert_eq!(adherence["skipped"], 1);
+    assert_eq!(adherence["missed"], 3);
+    assert_eq!(adherence["upcoming"], 0);
+    assert_eq!(adherence["adherence_percent"], 20.0);
+
+    // Resetting a session or deleting its workout makes it count as missed again
+    common::send(
+        &app,
+        "DELETE",
+        &format!("{}/2024-12-05", sessions),
+        Some(&token),
+        None,
+    )
+    .await;
+    common::send(
+        &app,
+        "DELETE",
+        &format!("/workouts/{}", workout["id"].as_str().unwrap()),
+        Some(&token),
+        None,
+    )
+    .await;
+    let (_, adherence) = common::send(
+        &app,
+        "GET",
+        "/calendar/adherence?from=2024-12-01&to=2024-12-15",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(adherence["missed"], 5);
+    assert_eq!(adherence["adherence_percent"], 0.0);
+
+    let (status, _) = common::send(
+        &app,
+        "GET",
+        "/calendar?from=2024-01-01&to=2025-12-31",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
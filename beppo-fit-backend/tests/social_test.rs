@@ -0,0 +1,196 @@
+use axum::{http::StatusCode, Router};
+use beppo_fit_backend::app;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+mod common;
+
+async fn log_workout(
+    app: &Router,
+    token: &str,
+    exercise_id: impl serde::Serialize,
+    day: u32,
+    visibility: &str,
+) -> String {
+    let (status, workout) = common::send(
+        app,
+        "POST",
+        "/workouts",
+        Some(token),
+        Some(json!({
+            "name": format!("{} session", visibility),
+            "started_at": format!("2024-12-{:02}T17:00:00Z", day),
+            "finished_at": format!("2024-12-{:02}T18:00:00Z", day),
+            "visibility": visibility,
+            "exercises": [{ "exercise_id": exercise_id, "sets": [{ "reps": 5, "weight_kg": 80.0 + day as f64 }] }]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(workout["visibility"], visibility);
+    workout["id"].as_str().unwrap().to_string()
+}
+
+/// Follows `next_cursor` until the last page and returns all items.
+async fn read_feed(app: &Router, token: &str) -> Vec<Value> {
+    let mut items = Vec::new();
+    let mut uri = "/feed?limit=2".to_string();
+    loop {
+        let (status, page) = common::send(app, "GET", &uri, Some(token), None).await;
+        assert_eq!(status, StatusCode::OK);
+        let page_items = page["items"].as_array().unwrap();
+        assert!(page_items.len() <= 2);
+        items.extend(page_items.iter().cloned());
+        match page["next_cursor"].as_str() {
+            Some(cursor) => uri = format!("/feed?limit=2&cursor={}", cursor),
+            None => return items,
+        }
+    }
+}
+
+#[sqlx::test]
+async fn test_follows_feed_and_visibility(pool: PgPool) {
+    let app = app(pool.clone()).await;
+    let (alice, alice_id) = common::register_user(&app, "alice@example.com").await;
+    let (bob, bob_id) = common::register_user(&app, "bob@example.com").await;
+    let (carol, carol_id) = common::register_user(&app, "carol@example.com").await;
+    let (dave, _) = common::register_user(&app, "dave@example.com").await;
+    let squat = common::exercise_id(&pool, "barbell-back-squat").await;
+
+    let (status, _) = common::send(
+        &app,
+        "PATCH",
+        "/me/profile",
+        Some(&bob),
+        Some(json!({ "is_private": true, "display_name": "Bob" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Public profiles are followed right away, private ones need approval
+    let carol_follow = format!("/users/{}/follow", carol_id);
+    let (status, follow) = common::send(&app, "POST", &carol_follow, Some(&alice), None).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(follow["status"], "accepted");
+    let (status, _) = common::send(&app, "POST", &carol_follow, Some(&alice), None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, follow) = common::send(
+        &app,
+        "POST",
+        &format!("/users/{}/follow", bob_id),
+        Some(&alice),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(follow["status"], "pending");
+
+    let public = log_workout(&app, &carol, &squat, 10, "public").await;
+    let followers_only = log_workout(&app, &carol, &squat, 11, "followers").await;
+    let private = log_workout(&app, &carol, &squat, 12, "private").await;
+    let bobs = log_workout(&app, &bob, &squat, 13, "followers").await;
+
+    // Pending follows see nothing of the private profile yet
+    let workout_ids = |items: &[Value]| -> Vec<String> {
+        items
+            .iter()
+            .filter(|i| i["kind"] == "workout")
+            .map(|i| i["workout_id"].as_str().unwrap().to_string())
+            .collect()
+    };
+    let feed = read_feed(&app, &alice).await;
+    assert_eq!(
+        workout_ids(&feed),
+        vec![followers_only.clone(), public.clone()]
+    );
+
+    let (_, requests) = common::send(&app, "GET", "/me/follow-requests", Some(&bob), None).await;
+    assert_eq!(requests[0]["user_id"], alice_id.as_str());
+    let (status, follow) = common::send(
+        &app,
+        "POST",
+        &format!("/me/follow-requests/{}/accept", alice_id),
+        Some(&bob),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(follow["status"], "accepted");
+    let (_, notifications) =
+        common::send(&app, "GET", "/me/notifications", Some(&alice), None).await;
+    assert_eq!(
+        notifications[0]["title"],
+        "Bob accepted your follow request"
+    );
+
+    let feed = read_feed(&app, &alice).await;
+    assert_eq!(
+        workout_ids(&feed),
+        vec![bobs.clone(), followers_only.clone(), public.clone()]
+    );
+    assert!(!feed.iter().any(|i| i["workout_id"] == private.as_str()));
+    let record = feed
+        .iter()
+        .find(|i| i["kind"] == "personal_record")
+        .unwrap();
+    assert_eq!(
+        record["personal_record"]["exercise_name"],
+        "Barbell Back Squat"
+    );
+    assert!(record["workout"].is_null());
+    let first = &feed[0];
+    assert_eq!(first["author"]["display_name"], "Bob");
+    assert_eq!(first["workout"]["set_count"], 1);
+    let mut ids: Vec<&str> = feed.iter().map(|i| i["id"].as_str().unwrap()).collect();
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), feed.len());
+
+    // Strangers only see public workouts; followers also see followers-only ones
+    let carol_workouts = format!("/users/{}/workouts", carol_id);
+    let (_, page) = common::send(&app, "GET", &carol_workouts, Some(&dave), None).await;
+    assert_eq!(page["items"].as_array().unwrap().len(), 1);
+    assert_eq!(page["items"][0]["id"], public.as_str());
+    let (_, page) = common::send(&app, "GET", &carol_workouts, Some(&alice), None).await;
+    assert_eq!(page["items"].as_array().unwrap().len(), 2);
+    let followers_uri = format!("{}/{}", carol_workouts, followers_only);
+    let (status, _) = common::send(&app, "GET", &followers_uri, Some(&dave), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, workout) = common::send(&app, "GET", &followers_uri, Some(&alice), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(workout["exercises"][0]["sets"][0]["weight_kg"], 91.0);
+    let (status, _) = common::send(
+        &app,
+        "GET",
+        &format!("{}/{}", carol_workouts, private),
+        Some(&alice),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Blocking ends the follow and hides everything in both directions
+    let (_, followers) = common::send(&app, "GET", "/me/followers", Some(&carol), None).await;
+    assert_eq!(followers[0]["user_id"], alice_id.as_str());
+    let (status, _) = common::send(
+        &app,
+        "POST",
+        &format!("/users/{}/block", alice_id),
+        Some(&carol),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let feed = read_feed(&app, &alice).await;
+    assert_eq!(workout_ids(&feed), vec![bobs]);
+    let (status, _) = common::send(&app, "GET", &carol_workouts, Some(&alice), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = common::send(&app, "POST", &carol_follow, Some(&alice), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (_, following) = common::send(&app, "GET", "/me/following", Some(&alice), None).await;
+    assert_eq!(following.as_array().unwrap().len(), 1);
+
+    let (status, _) = common::send(&app, "GET", "/feed?cursor=nope", Some(&alice), None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,380 @@
+use axum::http::StatusCode;
+use beppo_fit_backend::app;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+mod common;
+
+fn current<'a>(records: &'a Value, record_type: &str) -> Vec<&'a Value> {
+    records["current"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|r| r["record_type"] == record_type)
+        .collect()
+}
+
+#[sqlx::test]
+async fn test_personal_records(pool: PgPool) {
+    let app = app(pool.clone()).await;
+    let token = common::register(&app, "records_test@example.com").await;
+    let squat = common::exercise_id(&pool, "barbell-back-squat").await;
+    let running = common::exercise_id(&pool, "running").await;
+
+    let (status, _) = common::send(
+        &app,
+        "POST",
+        "/workouts",
+        Some(&token),
+        Some(json!({
+            "started_at": "2024-12-01T17:00:00Z",
+            "finished_at": "2024-12-01T18:00:00Z",
+            "exercises": [
+                { "exercise_id": squat, "sets": [
+                    { "set_type": "warmup", "reps": 5, "weight_kg": 120.0 },
+                    { "reps": 5, "weight_kg": 100.0 },
+                    { "reps": 5, "weight_kg": 100.0 }
+                ] },
+                { "exercise_id": running, "sets": [{ "duration_seconds": 1500, "distance_m": 5000.0 }] }
+            ]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, _) = common::send(
+        &app,
+        "POST",
+        "/workouts",
+        Some(&token),
+        Some(json!({
+            "started_at": "2024-12-08T17:00:00Z",
+            "finished_at": "2024-12-08T18:00:00Z",
+            "exercises": [
+                { "exercise_id": squat, "sets": [
+                    { "reps": 3, "weight_kg": 105.0 },
+                    { "reps": 6, "weight_kg": 100.0 }
+                ] },
+                { "exercise_id": running, "sets": [{ "duration_seconds": 1450, "distance_m": 5000.0 }] }
+            ]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // Planned sets of an unfinished workout are not records yet
+    let (status, pending) = common::send(
+        &app,
+        "POST",
+        "/workouts",
+        Some(&token),
+        Some(json!({
+            "exercises": [{ "exercise_id": squat, "sets": [{ "reps": 5, "weight_kg": 200.0 }] }]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, records) = common::send(
+        &app,
+        "GET",
+        &format!("/stats/prs?exercise_id={}", squat),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(records.as_array().unwrap().len(), 1);
+    let squat_records = &records[0];
+    assert_eq!(squat_records["exercise_name"], "Barbell Back Squat");
+    assert_eq!(current(squat_records, "max_weight")[0]["value"], 105.0);
+    // Brzycki: 100 kg x 6 beats 100 kg x 5 and 105 kg x 3
+    assert_eq!(current(squat_records, "estimated_1rm")[0]["value"], 116.1);
+    let reps = current(squat_records, "max_reps");
+    assert_eq!(reps.len(), 2);
+    assert_eq!(reps[0]["weight_kg"], 100.0);
+    assert_eq!(reps[0]["value"], 6.0);
+    // 915 kg in the second session does not beat 1000 kg
+    assert_eq!(current(squat_records, "max_volume")[0]["value"], 1000.0);
+    let history = squat_records["history"].as_array().unwrap();
+    assert_eq!(
+        history
+            .iter()
+            .filter(|r| r["record_type"] == "max_weight")
+            .count(),
+        2
+    );
+
+    let (_, runs) = common::send(
+        &app,
+        "GET",
+        "/stats/prs?record_type=fastest_time",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(runs[0]["current"][0]["value"], 1450.0);
+    assert_eq!(runs[0]["current"][0]["distance_m"], 5000.0);
+
+    // Finishing the pending workout turns its sets into records
+    let pending_uri = format!("/workouts/{}", pending["id"].as_str().unwrap());
+    common::send(
+        &app,
+        "POST",
+        &format!("{}/finish", pending_uri),
+        Some(&token),
+        None,
+    )
+    .await;
+    let (_, new_records) = common::send(
+        &app,
+        "GET",
+        &format!("/stats/prs?workout_id={}", pending["id"].as_str().unwrap()),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(current(&new_records[0], "max_weight")[0]["value"], 200.0);
+
+    // Deleting a workout removes the records it set
+    common::send(&app, "DELETE", &pending_uri, Some(&token), None).await;
+    let (_, records) = common::send(
+        &app,
+        "GET",
+        &format!("/stats/prs?exercise_id={}", squat),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(current(&records[0], "max_weight")[0]["value"], 105.0);
+
+    let (status, _) = common::send(
+        &app,
+        "GET",
+        "/stats/prs?record_type=bogus",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let other = common::register(&app, "records_other@example.com").await;
+    let (_, none) = common::send(&app, "GET", "/stats/prs", Some(&other), None).await;
+    assert!(none.as_array().unwrap().is_empty());
+}
+
+#[sqlx::test]
+async fn test_records_follow_workout_order(pool: PgPool) {
+    let app = app(pool.clone()).await;
+    let token = common::register(&app, "records_order@example.com").await;
+    let squat = common::exercise_id(&pool, "barbell-back-squat").await;
+
+    let workout = |day: &str, weight: f64| {
+        json!({
+            "started_at": format!("2024-12-{}T17:00:00Z", day),
+            "finished_at": format!("2024-12-{}T18:00:00Z", day),
+            "exercises": [{ "exercise_id": squat, "sets": [{ "reps": 1, "weight_kg": weight }] }]
+        })
+    };
+    let max_weights = || async {
+        let (_, records) = common::send(
+            &app,
+            "GET",
+            &format!("/stats/prs?exercise_id={}&record_type=max_weight", squat),
+            Some(&token),
+            None,
+        )
+        .await;
+        records[0]["history"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r["value"].as_f64().unwrap())
+            .collect::<Vec<_>>()
+    };
+
+    common::send(
+        &app,
+        "POST",
+        "/workouts",
+        Some(&token),
+        Some(workout("10", 100.0)),
+    )
+    .await;
+    // Logged afterwards but done earlier: a record then, and still beaten later
+    let (_, earlier) = common::send(
+        &app,
+        "POST",
+        "/workouts",
+        Some(&token),
+        Some(workout("05", 90.0)),
+    )
+    .await;
+    assert_eq!(max_weights().await, [90.0, 100.0]);
+
+    // Editing the earlier session to a heavier lift takes the later record away
+    let earlier_uri = format!("/workouts/{}", earlier["id"].as_str().unwrap());
+    let (status, _) = common::send(
+        &app,
+        "PUT",
+        &earlier_uri,
+        Some(&token),
+        Some(workout("05", 110.0)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(max_weights().await, [110.0]);
+
+    // Deleting it gives the record back
+    common::send(&app, "DELETE", &earlier_uri, Some(&token), None).await;
+    assert_eq!(max_weights().await, [100.0]);
+}
+
+#[sqlx::test]
+async fn test_training_stats_use_profile_timezone(pool: PgPool) {
+    let app = app(pool.clone()).await;
+    let token = common::register(&app, "stats_test@example.com").await;
+    let squat = common::exercise_id(&pool, "barbell-back-squat").await;
+    let bench = common::exercise_id(&pool, "barbell-bench-press").await;
+
+    let (status, _) = common::send(
+        &app,
+        "PATCH",
+        "/me/profile",
+        Some(&token),
+        Some(json!({ "timezone": "America/New_York" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Monday 03:00 UTC is still Sunday evening in New York
+    for workout in [
+        json!({
+            "started_at": "2024-12-02T03:00:00Z",
+            "finished_at": "2024-12-02T03:45:00Z",
+            "exercises": [
+                { "exercise_id": squat, "sets": [
+                    { "set_type": "warmup", "reps": 5, "weight_kg": 60.0 },
+                    { "reps": 5, "weight_kg": 100.0 },
+                    { "reps": 5, "weight_kg": 100.0 }
+                ] },
+                { "exercise_id": bench, "sets": [{ "reps": 5, "weight_kg": 80.0 }] }
+            ]
+        }),
+        json!({
+            "started_at": "2024-12-03T23:00:00Z",
+            "finished_at": "2024-12-04T00:10:00Z",
+            "exercises": [{ "exercise_id": squat, "sets": [{ "reps": 3, "weight_kg": 110.0 }] }]
+        }),
+    ] {
+        let (status, _) =
+            common::send(&app, "POST", "/workouts", Some(&token), Some(workout)).await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    let range = "from=2024-11-25&to=2024-12-08";
+
+    let (status, frequency) = common::send(
+        &app,
+        "GET",
+        &format!("/stats/frequency?{}", range),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        frequency,
+        json!([
+            { "period_start": "2024-11-25", "workouts": 1, "training_days": 1 },
+            { "period_start": "2024-12-02", "workouts": 1, "training_days": 1 }
+        ])
+    );
+
+    let (_, tonnage) = common::send(
+        &app,
+        "GET",
+        &format!("/stats/tonnage?{}&period=day", range),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(tonnage[0]["period_start"], "2024-12-01");
+    assert_eq!(tonnage[0]["tonnage_kg"], 1400.0);
+    assert_eq!(tonnage[1]["period_start"], "2024-12-03");
+    assert_eq!(tonnage[1]["tonnage_kg"], 330.0);
+
+    let (_, volume) = common::send(
+        &app,
+        "GET",
+        &format!("/stats/volume?{}", range),
+        Some(&token),
+        None,
+    )
+    .await;
+    let volume = volume.as_array().unwrap();
+    let quads = volume
+        .iter()
+        .find(|v| v["period_start"] == "2024-11-25" && v["muscle_group"] == "quadriceps")
+        .unwrap();
+    assert_eq!(quads["sets"], 2);
+    assert_eq!(quads["volume_kg"], 1000.0);
+    assert!(volume
+        .iter()
+        .any(|v| v["period_start"] == "2024-11-25" && v["muscle_group"] == "chest"));
+
+    let (_, trend) = common::send(
+        &app,
+        "GET",
+        &format!("/stats/e1rm?{}&exercise_id={}", range, squat),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(
+        trend,
+        json!([
+            { "date": "2024-12-01", "estimated_1rm": 112.5 },
+            { "date": "2024-12-03", "estimated_1rm": 116.5 }
+        ])
+    );
+
+    let (_, durations) = common::send(
+        &app,
+        "GET",
+        &format!("/stats/durations?{}", range),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(durations["workouts"], 2);
+    assert_eq!(durations["average_minutes"], 57.5);
+    assert_eq!(durations["median_minutes"], 57.5);
+    assert_eq!(
+        durations["buckets"],
+        json!([
+            { "minutes_from": 45, "minutes_to": 60, "workouts": 1 },
+            { "minutes_from": 60, "minutes_to": 75, "workouts": 1 }
+        ])
+    );
+
+    // The range is inclusive of whole local days
+    let (_, frequency) = common::send(
+        &app,
+        "GET",
+        "/stats/frequency?from=2024-12-02&to=2024-12-03",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(frequency.as_array().unwrap().len(), 1);
+
+    for uri in [
+        "/stats/e1rm",
+        "/stats/volume?period=year",
+        "/stats/tonnage?from=2024-12-08&to=2024-12-01",
+    ] {
+        let (status, _) = common::send(&app, "GET", uri, Some(&token), None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", uri);
+    }
+}
@@ -0,0 +1,26 @@
+use beppo_fit_backend::services::storage::{ObjectStorage, S3Storage};
+use std::env;
+
+/// Runs against the MinIO service from `docker-compose.yml`:
+/// `docker-compose up -d minio && cargo test --test storage_test -- --ignored`
+#[tokio::test]
+#[ignore = "requires a running MinIO (or other S3-compatible) instance"]
+async fn test_s3_storage_round_trip() {
+    let storage = S3Storage::new(
+        &env::var("S3_ENDPOINT").unwrap_or_else(|_| "http://localhost:9000".to_string()),
+        &env::var("S3_BUCKET").unwrap_or_else(|_| "beppofit-media".to_string()),
+        &env::var("S3_REGION").unwrap_or_else(|_| "us-east-1".to_string()),
+        &env::var("S3_ACCESS_KEY").unwrap_or_else(|_| "minioadmin".to_string()),
+        &env::var("S3_SECRET_KEY").unwrap_or_else(|_| "minioadmin".to_string()),
+    );
+    let key = format!("tests/{}/object with spaces.bin", uuid::Uuid::new_v4());
+
+    storage
+        .put(&key, b"hello".to_vec(), "application/octet-stream")
+        .await
+        .unwrap();
+    assert_eq!(storage.get(&key).await.unwrap(), Some(b"hello".to_vec()));
+
+    storage.delete(&key).await.unwrap();
+    assert_eq!(storage.get(&key).await.unwrap(), None);
+}
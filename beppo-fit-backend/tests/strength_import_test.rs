@@ -0,0 +1,186 @@
+use axum::http::StatusCode;
+use beppo_fit_backend::app;
+use serde_json::json;
+use sqlx::PgPool;
+
+mod common;
+
+const STRONG_CSV: &str = "Date,Workout Name,Duration,Exercise Name,Set Order,Weight,Reps,Distance,Seconds,Notes,Workout Notes,RPE\n\
+    2024-01-15 18:30:00,Push,1h 5m,Bench Press (Barbell),W,40,10,0,0,,,\n\
+    2024-01-15 18:30:00,Push,1h 5m,Bench Press (Barbell),1,80,5,0,0,,,8\n\
+    2024-01-15 18:30:00,Push,1h 5m,Bench Press (Barbell),2,82.5,5,0,0,,,9\n\
+    2024-01-15 18:30:00,Push,1h 5m,Landmine Twist Thing,1,20,10,0,0,,,\n\
+    2024-01-12 07:00:00,Legs,45m,Squat (Barbell),1,100,5,0,0,,,\n\
+    2024-01-12 07:00:00,Legs,45m,Squat (Barbell),2,100,5,0,0,,,\n";
+
+#[sqlx::test]
+async fn test_strong_import_with_mapping_and_commit(pool: PgPool) {
+    let app = app(pool.clone()).await;
+    let token = common::register(&app, "strength_import@example.com").await;
+
+    // Uploading only previews the import
+    let (status, preview) = common::send_multipart(
+        &app,
+        "/imports/strength",
+        &token,
+        ("text/csv", STRONG_CSV.as_bytes()),
+        &[],
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(preview["source"], "strong");
+    assert_eq!(preview["units"], "metric");
+    assert_eq!(preview["workout_count"], 2);
+    assert_eq!(preview["set_count"], 6);
+    assert_eq!(preview["ready"], false);
+    let exercises = preview["exercises"].as_array().unwrap();
+    assert_eq!(exercises.len(), 3);
+    assert_eq!(exercises[0]["name"], "Squat (Barbell)");
+    assert_eq!(exercises[0]["status"], "matched");
+    assert_eq!(exercises[0]["exercise_name"], "Barbell Back Squat");
+    assert_eq!(exercises[1]["exercise_name"], "Barbell Bench Press");
+    assert_eq!(exercises[2]["status"], "unmatched");
+    assert!(!exercises[2]["candidates"].as_array().unwrap().is_empty());
+    let (_, workouts) = common::send(&app, "GET", "/workouts", Some(&token), None).await;
+    assert_eq!(workouts.as_array().unwrap().len(), 0);
+
+    let uri = format!("/imports/strength/{}", preview["id"].as_str().unwrap());
+    let (status, _) =
+        common::send(&app, "POST", &format!("{}/commit", uri), Some(&token), None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Names must come from the file
+    let mappings_uri = format!("{}/mappings", uri);
+    let (status, _) = common::send(
+        &app,
+        "PUT",
+        &mappings_uri,
+        Some(&token),
+        Some(json!({ "mappings": [{ "name": "Curl", "exercise_id": null }] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let landmine = common::exercise_id(&pool, "landmine-rotation").await;
+    let (status, preview) = common::send(
+        &app,
+        "PUT",
+        &mappings_uri,
+        Some(&token),
+        Some(json!({ "mappings": [{ "name": "Landmine Twist Thing", "exercise_id": landmine }] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(preview["ready"], true);
+    assert_eq!(preview["exercises"][2]["status"], "confirmed");
+    assert_eq!(
+        preview["exercises"][2]["exercise_name"],
+        "Landmine Rotation"
+    );
+
+    // Skipping a name leaves its sets out
+    let (_, preview) = common::send(
+        &app,
+        "PUT",
+        &mappings_uri,
+        Some(&token),
+        Some(json!({ "mappings": [{ "name": "Landmine Twist Thing", "exercise_id": null }] })),
+    )
+    .await;
+    assert_eq!(preview["exercises"][2]["status"], "skipped");
+    assert_eq!(preview["set_count"], 5);
+
+    let other = common::register(&app, "strength_import_other@example.com").await;
+    let (status, _) = common::send(&app, "GET", &uri, Some(&other), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, result) =
+        common::send(&app, "POST", &format!("{}/commit", uri), Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(result["workouts_created"], 2);
+    assert_eq!(result["sets_created"], 5);
+    let (status, _) = common::send(&app, "GET", &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (_, workouts) = common::send(&app, "GET", "/workouts", Some(&token), None).await;
+    let workouts = workouts.as_array().unwrap();
+    assert_eq!(workouts.len(), 2);
+    assert_eq!(workouts[0]["name"], "Push");
+    assert_eq!(workouts[0]["started_at"], "2024-01-15T18:30:00Z");
+    let (_, push) = common::send(
+        &app,
+        "GET",
+        &format!("/workouts/{}", workouts[0]["id"].as_str().unwrap()),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(push["finished_at"], "2024-01-15T19:35:00Z");
+    let sets = push["exercises"][0]["sets"].as_array().unwrap();
+    assert_eq!(sets[0]["set_type"], "warmup");
+    assert_eq!(sets[2]["weight_kg"], 82.5);
+    assert_eq!(sets[2]["rpe"], 9.0);
+
+    // Imported sessions count towards records
+    let records = sqlx::query_scalar!("SELECT COUNT(*) FROM personal_records")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert!(records.unwrap() > 0);
+
+    // Uploading the same file again only finds duplicates
+    let (_, again) = common::send_multipart(
+        &app,
+        "/imports/strength",
+        &token,
+        ("text/csv", STRONG_CSV.as_bytes()),
+        &[],
+    )
+    .await;
+    assert_eq!(again["duplicate_count"], 2);
+    assert_eq!(again["workout_count"], 0);
+    assert_eq!(again["workouts"][0]["duplicate"], true);
+}
+
+#[sqlx::test]
+async fn test_hevy_import_and_discard(pool: PgPool) {
+    let app = app(pool).await;
+    let token = common::register(&app, "hevy_import@example.com").await;
+
+    let csv = "\"title\",\"start_time\",\"end_time\",\"description\",\"exercise_title\",\"superset_id\",\"exercise_notes\",\"set_index\",\"set_type\",\"weight_kg\",\"reps\",\"distance_km\",\"duration_seconds\",\"rpe\"\n\
+        \"Pull\",\"15 Jan 2024, 18:30\",\"15 Jan 2024, 19:30\",\"\",\"Deadlift (Barbell)\",\"\",\"\",\"0\",\"normal\",\"140\",\"5\",\"\",\"\",\"\"\n\
+        \"Pull\",\"15 Jan 2024, 18:30\",\"15 Jan 2024, 19:30\",\"\",\"Pull Up\",\"\",\"\",\"0\",\"normal\",\"\",\"10\",\"\",\"\",\"\"\n";
+    let (status, preview) = common::send_multipart(
+        &app,
+        "/imports/strength",
+        &token,
+        ("text/csv", csv.as_bytes()),
+        &[("units", "imperial")],
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(preview["source"], "hevy");
+    assert_eq!(preview["ready"], true);
+    assert_eq!(preview["exercises"][1]["exercise_name"], "Pull-Up");
+    assert_eq!(
+        preview["workouts"][0]["finished_at"],
+        "2024-01-15T19:30:00Z"
+    );
+
+    let uri = format!("/imports/strength/{}", preview["id"].as_str().unwrap());
+    let (status, _) = common::send(&app, "DELETE", &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) =
+        common::send(&app, "POST", &format!("{}/commit", uri), Some(&token), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = common::send_multipart(
+        &app,
+        "/imports/strength",
+        &token,
+        ("text/csv", b"name,value\nfoo,1\n"),
+        &[],
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,317 @@
+use axum::http::StatusCode;
+use beppo_fit_backend::app;
+use serde_json::json;
+use sqlx::PgPool;
+
+mod common;
+
+#[sqlx::test]
+async fn test_template_lifecycle(pool: PgPool) {
+    let app = app(pool.clone()).await;
+    let token = common::register(&app, "template_test@example.com").await;
+    let squat = common::exercise_id(&pool, "barbell-back-squat").await;
+    let bench = common::exercise_id(&pool, "barbell-bench-press").await;
+    let row = common::exercise_id(&pool, "barbell-bent-over-row").await;
+
+    let (status, created) = common::send(
+        &app,
+        "POST",
+        "/templates",
+        Some(&token),
+        Some(json!({
+            "name": "Full body A",
+            "exercises": [
+                { "exercise_id": squat, "target_sets": 3, "target_reps_min": 5, "target_reps_max": 5, "target_weight_kg": 100.0, "rest_seconds": 180 },
+                { "exercise_id": bench, "target_sets": 2, "target_reps_min": 8, "target_reps_max": 12, "group_label": "A", "group_type": "superset" },
+                { "exercise_id": row, "target_sets": 2, "target_reps_min": 8, "target_reps_max": 12, "group_label": "A", "group_type": "superset" }
+            ]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(created["exercises"].as_array().unwrap().len(), 3);
+    assert_eq!(created["exercises"][1]["group_label"], "A");
+    assert!(created["share_code"].is_null());
+    let id = created["id"].as_str().unwrap();
+    let uri = format!("/templates/{}", id);
+
+    // Invalid rep range and group type without a label are rejected
+    let (status, _) = common::send(
+        &app,
+        "POST",
+        "/templates",
+        Some(&token),
+        Some(json!({
+            "name": "Broken",
+            "exercises": [{ "exercise_id": squat, "target_reps_min": 10, "target_reps_max": 5 }]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = common::send(
+        &app,
+        "POST",
+        "/templates",
+        Some(&token),
+        Some(json!({
+            "name": "Broken",
+            "exercises": [{ "exercise_id": squat, "group_type": "circuit" }]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, copy) = common::send(
+        &app,
+        "POST",
+        &format!("{}/duplicate", uri),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(copy["name"], "Full body A (copy)");
+    assert_eq!(
+        copy["exercises"][2]["exercise_name"],
+        "Barbell Bent-Over Row"
+    );
+
+    let (status, list) = common::send(&app, "GET", "/templates", Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(list.as_array().unwrap().len(), 2);
+
+    // Sharing hands out a code others can view and copy until it is revoked
+    let (status, shared) =
+        common::send(&app, "POST", &format!("{}/share", uri), Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let code = shared["share_code"].as_str().unwrap().to_string();
+
+    let other = common::register(&app, "template_other@example.com").await;
+    let (status, _) = common::send(&app, "GET", &uri, Some(&other), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let shared_uri = format!("/templates/shared/{}", code);
+    let (status, viewed) = common::send(&app, "GET", &shared_uri, Some(&other), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(viewed["name"], "Full body A");
+    let (status, copied) = common::send(
+        &app,
+        "POST",
+        &format!("{}/copy", shared_uri),
+        Some(&other),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_ne!(copied["id"], created["id"]);
+    assert_eq!(copied["exercises"].as_array().unwrap().len(), 3);
+
+    let (status, _) = common::send(
+        &app,
+        "DELETE",
+        &format!("{}/share", uri),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = common::send(&app, "GET", &shared_uri, Some(&other), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = common::send(&app, "DELETE", &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = common::send(&app, "GET", &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn test_start_workout_from_template(pool: PgPool) {
+    let app = app(pool.clone()).await;
+    let token = common::register(&app, "template_start@example.com").await;
+    let squat = common::exercise_id(&pool, "barbell-back-squat").await;
+    let bench = common::exercise_id(&pool, "barbell-bench-press").await;
+
+    let (_, template) = common::send(
+        &app,
+        "POST",
+        "/templates",
+        Some(&token),
+        Some(json!({
+            "name": "Squat and bench",
+            "exercises": [
+                { "exercise_id": squat, "target_sets": 3, "target_reps_min": 5, "target_weight_kg": 90.0 },
+                { "exercise_id": bench, "target_sets": 2, "target_reps_min": 8, "target_weight_kg": 60.0 }
+            ]
+        })),
+    )
+    .await;
+    let start_uri = format!("/templates/{}/start", template["id"].as_str().unwrap());
+
+    // Squat was done before: its last working sets win over the template targets
+    let (status, _) = common::send(
+        &app,
+        "POST",
+        "/workouts",
+        Some(&token),
+        Some(json!({
+            "started_at": "2024-12-10T17:00:00Z",
+            "finished_at": "2024-12-10T18:00:00Z",
+            "exercises": [{
+                "exercise_id": squat,
+                "sets": [
+                    { "set_type": "warmup", "reps": 5, "weight_kg": 60.0 },
+                    { "reps": 5, "weight_kg": 105.0 },
+                    { "reps": 4, "weight_kg": 107.5 }
+                ]
+            }]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, workout) = common::send(&app, "POST", &start_uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(workout["name"], "Squat and bench");
+    assert_eq!(workout["template_id"], template["id"]);
+    assert!(workout["finished_at"].is_null());
+
+    let squat_sets = workout["exercises"][0]["sets"].as_array().unwrap();
+    assert_eq!(squat_sets.len(), 3);
+    assert_eq!(squat_sets[0]["weight_kg"], 105.0);
+    assert_eq!(squat_sets[1]["reps"], 4);
+    assert_eq!(squat_sets[1]["weight_kg"], 107.5);
+    assert_eq!(squat_sets[2]["weight_kg"], 107.5);
+    assert!(squat_sets[0]["completed_at"].is_null());
+
+    let bench_sets = workout["exercises"][1]["sets"].as_array().unwrap();
+    assert_eq!(bench_sets.len(), 2);
+    assert_eq!(bench_sets[0]["reps"], 8);
+    assert_eq!(bench_sets[0]["weight_kg"], 60.0);
+
+    // Only the owner can start a template
+    let other = common::register(&app, "template_start_other@example.com").await;
+    let (status, _) = common::send(&app, "POST", &start_uri, Some(&other), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn test_progression_recommendations(pool: PgPool) {
+    let app = app(pool.clone()).await;
+    let token = common::register(&app, "template_progression@example.com").await;
+    let squat = common::exercise_id(&pool, "barbell-back-squat").await;
+    let bench = common::exercise_id(&pool, "barbell-bench-press").await;
+
+    // Rules are validated against the targets they rely on
+    let (status, _) = common::send(
+        &app,
+        "POST",
+        "/templates",
+        Some(&token),
+        Some(json!({
+            "name": "Broken",
+            "exercises": [{ "exercise_id": squat, "progression_rule": "rpe" }]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, template) = common::send(
+        &app,
+        "POST",
+        "/templates",
+        Some(&token),
+        Some(json!({
+            "name": "Progression",
+            "exercises": [
+                { "exercise_id": squat, "target_sets": 3, "target_reps_min": 5, "target_weight_kg": 100.0,
+                  "progression_rule": "linear", "progression_increment_kg": 5.0 },
+                { "exercise_id": bench, "target_sets": 3, "target_reps_min": 8, "target_reps_max": 12,
+                  "target_weight_kg": 60.0, "progression_rule": "double_progression" }
+            ]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(template["exercises"][0]["progression_rule"], "linear");
+    let template_uri = format!("/templates/{}", template["id"].as_str().unwrap());
+
+    let (_, workout) = common::send(
+        &app,
+        "POST",
+        &format!("{}/start", template_uri),
+        Some(&token),
+        None,
+    )
+    .await;
+    let workout_uri = format!("/workouts/{}", workout["id"].as_str().unwrap());
+    let (status, finished) = common::send(
+        &app,
+        "POST",
+        &format!("{}/finish", workout_uri),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, recommendations) = common::send(
+        &app,
+        "GET",
+        &format!("{}/recommendations", workout_uri),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(recommendations.as_array().unwrap().len(), 2);
+    assert_eq!(recommendations[0]["rule"], "linear");
+    assert_eq!(recommendations[0]["target_weight_kg"], 105.0);
+    assert_eq!(recommendations[1]["target_weight_kg"], 60.0);
+    assert_eq!(recommendations[1]["target_reps"], 9);
+
+    let (_, latest) = common::send(
+        &app,
+        "GET",
+        &format!("{}/recommendations", template_uri),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(latest, recommendations);
+
+    // Editing the finished workout later leaves the targets alone
+    let (status, _) = common::send(
+        &app,
+        "PUT",
+        &workout_uri,
+        Some(&token),
+        Some(json!({
+            "notes": "Fixed the notes",
+            "started_at": finished["started_at"],
+            "finished_at": finished["finished_at"],
+            "exercises": [{ "exercise_id": squat, "sets": [{ "reps": 2, "weight_kg": 100.0 }] }]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, latest) = common::send(
+        &app,
+        "GET",
+        &format!("{}/recommendations", template_uri),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(latest, recommendations);
+
+    // The next session starts from the recommendation
+    let (_, next) = common::send(
+        &app,
+        "POST",
+        &format!("{}/start", template_uri),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(next["exercises"][0]["sets"][0]["weight_kg"], 105.0);
+    assert_eq!(next["exercises"][1]["sets"][2]["reps"], 9);
+}
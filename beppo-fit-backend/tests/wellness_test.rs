@@ -0,0 +1,212 @@
+use axum::http::StatusCode;
+use beppo_fit_backend::app;
+use chrono::{Duration, Utc};
+use serde_json::json;
+use sqlx::PgPool;
+
+mod common;
+
+#[sqlx::test]
+async fn test_wellness_logs_and_readiness(pool: PgPool) {
+    let app = app(pool.clone()).await;
+    let token = common::register(&app, "wellness@example.com").await;
+    let today = Utc::now().date_naive();
+
+    // A baseline week of ordinary days
+    for days_ago in 1..=5 {
+        let (status, _) = common::send(
+            &app,
+            "PUT",
+            &format!("/wellness/{}", today - Duration::days(days_ago)),
+            Some(&token),
+            Some(json!({ "resting_heart_rate": 52, "hrv_ms": 60.0, "sleep_minutes": 480 })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    let uri = format!("/wellness/{}", today);
+    let (status, log) = common::send(
+        &app,
+        "PUT",
+        &uri,
+        Some(&token),
+        Some(json!({ "sleep_minutes": 420, "sleep_quality": 4, "water_ml": 2500 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(log["log_date"], today.to_string());
+
+    // Saving again replaces the day
+    let (status, log) = common::send(
+        &app,
+        "PUT",
+        &uri,
+        Some(&token),
+        Some(json!({
+            "sleep_minutes": 290,
+            "sleep_quality": 1,
+            "soreness": 5,
+            "stress": 4,
+            "resting_heart_rate": 59,
+            "hrv_ms": 42.0,
+            "notes": "Bad night"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(log["water_ml"].is_null());
+
+    let (_, logs) = common::send(&app, "GET", "/wellness", Some(&token), None).await;
+    assert_eq!(logs.as_array().unwrap().len(), 6);
+
+    let (status, readiness) =
+        common::send(&app, "GET", "/wellness/readiness", Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(readiness["baseline_resting_heart_rate"], 52.0);
+    assert_eq!(readiness["baseline_hrv_ms"], 60.0);
+    assert_eq!(readiness["components"]["hrv"], 0);
+    assert!(readiness["components"]["hydration"].is_null());
+    assert!(readiness["score"].as_i64().unwrap() < 50);
+    assert_eq!(readiness["suggestion"], "lighter");
+
+    // Low readiness lightens a template session
+    let squat = common::exercise_id(&pool, "barbell-back-squat").await;
+    let (_, template) = common::send(
+        &app,
+        "POST",
+        "/templates",
+        Some(&token),
+        Some(json!({
+            "name": "Squat day",
+            "exercises": [
+                { "exercise_id": squat, "target_sets": 3, "target_reps_min": 5, "target_weight_kg": 100.0 }
+            ]
+        })),
+    )
+    .await;
+    let (status, workout) = common::send(
+        &app,
+        "POST",
+        &format!("/templates/{}/start", template["id"].as_str().unwrap()),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert!(workout["notes"]
+        .as_str()
+        .unwrap()
+        .contains("lighter session suggested"));
+    let sets = workout["exercises"][0]["sets"].as_array().unwrap();
+    assert_eq!(sets.len(), 2);
+    assert_eq!(sets[0]["weight_kg"], 90.0);
+
+    // Future days and empty logs are rejected
+    let (status, _) = common::send(
+        &app,
+        "PUT",
+        &format!("/wellness/{}", today + Duration::days(2)),
+        Some(&token),
+        Some(json!({ "water_ml": 2000 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = common::send(&app, "PUT", &uri, Some(&token), Some(json!({}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = common::send(&app, "DELETE", &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = common::send(&app, "GET", &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = common::send(&app, "GET", "/wellness/readiness", Some(&token), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn test_low_readiness_session_keeps_progression(pool: PgPool) {
+    let app = app(pool.clone()).await;
+    let token = common::register(&app, "wellness_progression@example.com").await;
+    let squat = common::exercise_id(&pool, "barbell-back-squat").await;
+    let today = Utc::now().date_naive();
+
+    let (_, template) = common::send(
+        &app,
+        "POST",
+        "/templates",
+        Some(&token),
+        Some(json!({
+            "name": "Squat day",
+            "exercises": [{
+                "exercise_id": squat, "target_sets": 3, "target_reps_min": 5, "target_weight_kg": 100.0,
+                "progression_rule": "linear", "progression_increment_kg": 5.0
+            }]
+        })),
+    )
+    .await;
+    let template_uri = format!("/templates/{}", template["id"].as_str().unwrap());
+
+    // Starts a session from the template and finishes it as planned
+    let train = || async {
+        let (_, workout) = common::send(
+            &app,
+            "POST",
+            &format!("{}/start", template_uri),
+            Some(&token),
+            None,
+        )
+        .await;
+        let (status, _) = common::send(
+            &app,
+            "POST",
+            &format!("/workouts/{}/finish", workout["id"].as_str().unwrap()),
+            Some(&token),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        workout
+    };
+    let recommendations = || async {
+        let (_, recommendations) = common::send(
+            &app,
+            "GET",
+            &format!("{}/recommendations", template_uri),
+            Some(&token),
+            None,
+        )
+        .await;
+        recommendations
+    };
+
+    let workout = train().await;
+    assert_eq!(workout["readiness_adjusted"], false);
+    let before = recommendations().await;
+    assert_eq!(before[0]["target_weight_kg"], 105.0);
+    assert_eq!(before[0]["failure_streak"], 0);
+
+    for days_ago in 1..=5 {
+        common::send(
+            &app,
+            "PUT",
+            &format!("/wellness/{}", today - Duration::days(days_ago)),
+            Some(&token),
+            Some(json!({ "resting_heart_rate": 52, "hrv_ms": 60.0, "sleep_minutes": 480 })),
+        )
+        .await;
+    }
+    common::send(
+        &app,
+        "PUT",
+        &format!("/wellness/{}", today),
+        Some(&token),
+        Some(json!({ "sleep_minutes": 290, "sleep_quality": 1, "soreness": 5, "resting_heart_rate": 59, "hrv_ms": 42.0 })),
+    )
+    .await;
+
+    // The lighter session misses the plan but leaves the targets alone
+    let workout = train().await;
+    assert_eq!(workout["readiness_adjusted"], true);
+    assert_eq!(workout["exercises"][0]["sets"].as_array().unwrap().len(), 2);
+    assert_eq!(recommendations().await, before);
+}
@@ -0,0 +1,127 @@
+use axum::http::StatusCode;
+use beppo_fit_backend::app;
+use serde_json::json;
+use sqlx::PgPool;
+
+mod common;
+
+#[sqlx::test]
+async fn test_workout_crud(pool: PgPool) {
+    let app = app(pool.clone()).await;
+    let token = common::register(&app, "workout_test@example.com").await;
+    let squat = common::exercise_id(&pool, "barbell-back-squat").await;
+    let bench = common::exercise_id(&pool, "barbell-bench-press").await;
+
+    let (status, created) = common::send(
+        &app,
+        "POST",
+        "/workouts",
+        Some(&token),
+        Some(json!({
+            "name": "Leg day",
+            "started_at": "2024-12-16T17:00:00Z",
+            "exercises": [
+                {
+                    "exercise_id": squat,
+                    "sets": [
+                        { "set_type": "warmup", "reps": 5, "weight_kg": 60.0 },
+                        { "reps": 5, "weight_kg": 100.0, "rpe": 8.0 },
+                        { "reps": 5, "weight_kg": 100.0, "rir": 1 }
+                    ]
+                },
+                { "exercise_id": bench, "sets": [{ "reps": 8, "weight_kg": 80.0 }] }
+            ]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert!(created["finished_at"].is_null());
+    assert_eq!(
+        created["exercises"][0]["exercise_name"],
+        "Barbell Back Squat"
+    );
+    assert_eq!(created["exercises"][0]["sets"][0]["set_type"], "warmup");
+    assert_eq!(created["exercises"][0]["sets"][1]["set_type"], "working");
+    let uri = format!("/workouts/{}", created["id"].as_str().unwrap());
+
+    let (status, list) = common::send(&app, "GET", "/workouts", Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(list[0]["exercise_count"], 2);
+    assert_eq!(list[0]["set_count"], 4);
+    assert_eq!(list[0]["volume_kg"], 60.0 * 5.0 + 100.0 * 10.0 + 80.0 * 8.0);
+
+    // Replace the whole workout
+    let (status, updated) = common::send(
+        &app,
+        "PUT",
+        &uri,
+        Some(&token),
+        Some(json!({
+            "name": "Squat only",
+            "exercises": [{ "exercise_id": squat, "sets": [{ "reps": 3, "weight_kg": 110.0 }] }]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(updated["name"], "Squat only");
+    assert_eq!(updated["started_at"], "2024-12-16T17:00:00Z");
+    assert_eq!(updated["exercises"].as_array().unwrap().len(), 1);
+
+    let (status, finished) =
+        common::send(&app, "POST", &format!("{}/finish", uri), Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(!finished["finished_at"].is_null());
+    let (status, _) =
+        common::send(&app, "POST", &format!("{}/finish", uri), Some(&token), None).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // Workouts are scoped to their owner
+    let other = common::register(&app, "workout_other@example.com").await;
+    let (status, _) = common::send(&app, "GET", &uri, Some(&other), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = common::send(&app, "DELETE", &uri, Some(&other), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = common::send(&app, "DELETE", &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = common::send(&app, "GET", &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn test_invalid_workout_is_not_saved(pool: PgPool) {
+    let app = app(pool.clone()).await;
+    let token = common::register(&app, "workout_invalid@example.com").await;
+    let squat = common::exercise_id(&pool, "barbell-back-squat").await;
+
+    let (status, _) = common::send(
+        &app,
+        "POST",
+        "/workouts",
+        Some(&token),
+        Some(json!({ "exercises": [{ "exercise_id": squat, "sets": [{ "reps": 5, "rpe": 11.0 }] }] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = common::send(
+        &app,
+        "POST",
+        "/workouts",
+        Some(&token),
+        Some(json!({
+            "exercises": [
+                { "exercise_id": squat, "sets": [{ "reps": 5 }] },
+                { "exercise_id": "00000000-0000-0000-0000-000000000000", "sets": [] }
+            ]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let count = sqlx::query_scalar!("SELECT COUNT(*) FROM workouts")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(count, Some(0));
+}